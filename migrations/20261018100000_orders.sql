@@ -0,0 +1,52 @@
+-- =====================================================
+-- ====================== ORDERS =======================
+-- =====================================================
+
+-- All amounts are integer minor units (satang). Prices are VAT-inclusive.
+CREATE TABLE orders (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    status VARCHAR(30) NOT NULL CHECK (status IN (
+        'pending_payment', 'paid', 'picking', 'shipped',
+        'delivered', 'cancelled', 'refunded'
+    )),
+    subtotal BIGINT NOT NULL CHECK (subtotal >= 0),
+    tax_total BIGINT NOT NULL CHECK (tax_total >= 0),
+    total BIGINT NOT NULL CHECK (total >= 0),
+    tracking_number VARCHAR(100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_orders_user ON orders(user_id);
+CREATE INDEX idx_orders_status ON orders(status);
+
+-- Lines keep a frozen copy of the book data, price and tax at checkout time.
+CREATE TABLE order_items (
+    id SERIAL PRIMARY KEY,
+    order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE RESTRICT,
+    isbn VARCHAR(13) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    unit_price BIGINT NOT NULL CHECK (unit_price >= 0),
+    tax_rate_bps INTEGER NOT NULL CHECK (tax_rate_bps >= 0),
+    tax_amount BIGINT NOT NULL CHECK (tax_amount >= 0),
+    line_total BIGINT NOT NULL CHECK (line_total >= 0)
+);
+
+CREATE INDEX idx_order_items_order ON order_items(order_id);
+CREATE INDEX idx_order_items_book ON order_items(book_id);
+
+-- Who changed the status of an order, and when.
+CREATE TABLE order_status_history (
+    id SERIAL PRIMARY KEY,
+    order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    from_status VARCHAR(30),
+    to_status VARCHAR(30) NOT NULL,
+    changed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    note TEXT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_order_status_history_order ON order_status_history(order_id);
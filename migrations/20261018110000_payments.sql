@@ -0,0 +1,35 @@
+-- =====================================================
+-- ===================== PAYMENTS ======================
+-- =====================================================
+
+-- Every attempt to pay an order, including declined ones.
+CREATE TABLE payments (
+    id SERIAL PRIMARY KEY,
+    order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE RESTRICT,
+    provider VARCHAR(50) NOT NULL,
+    provider_intent_id VARCHAR(255),
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency CHAR(3) NOT NULL,
+    status VARCHAR(30) NOT NULL CHECK (status IN (
+        'pending', 'requires_action', 'authorized', 'captured',
+        'declined', 'voided', 'partially_refunded', 'refunded'
+    )),
+    captured_amount BIGINT NOT NULL DEFAULT 0 CHECK (captured_amount >= 0),
+    refunded_amount BIGINT NOT NULL DEFAULT 0 CHECK (refunded_amount >= 0),
+    next_action_url TEXT,
+    failure_reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (provider, provider_intent_id)
+);
+
+CREATE INDEX idx_payments_order ON payments(order_id);
+
+-- Processed webhook events, so redelivered events are ignored.
+CREATE TABLE payment_webhook_events (
+    provider VARCHAR(50) NOT NULL,
+    event_id VARCHAR(255) NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (provider, event_id)
+);
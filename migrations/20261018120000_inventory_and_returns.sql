@@ -0,0 +1,53 @@
+-- =====================================================
+-- ================= INVENTORY LEDGER ==================
+-- =====================================================
+
+-- Append-only stock movements. books.stock_quantity mirrors the sum of the
+-- 'available' bucket and is only changed together with a movement.
+CREATE TABLE inventory_movements (
+    id SERIAL PRIMARY KEY,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    bucket VARCHAR(20) NOT NULL CHECK (bucket IN ('available', 'damaged')),
+    quantity INTEGER NOT NULL CHECK (quantity <> 0),
+    reason VARCHAR(30) NOT NULL CHECK (reason IN (
+        'opening_balance', 'sale', 'order_cancelled',
+        'return_restock', 'return_write_off', 'adjustment'
+    )),
+    reference VARCHAR(100),
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_inventory_movements_book ON inventory_movements(book_id, created_at);
+
+-- Existing stock becomes the opening balance of the ledger
+INSERT INTO inventory_movements (book_id, bucket, quantity, reason)
+SELECT id, 'available', stock_quantity, 'opening_balance'
+FROM books
+WHERE stock_quantity > 0;
+
+-- =====================================================
+-- ================== RETURNS (RMA) ====================
+-- =====================================================
+
+CREATE TABLE return_requests (
+    id SERIAL PRIMARY KEY,
+    order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE RESTRICT,
+    order_item_id INTEGER NOT NULL REFERENCES order_items(id) ON DELETE RESTRICT,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE RESTRICT,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    reason TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'requested' CHECK (status IN (
+        'requested', 'approved', 'rejected', 'received', 'refunded'
+    )),
+    reviewed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    review_note TEXT,
+    condition VARCHAR(20) CHECK (condition IN ('resellable', 'damaged')),
+    refund_amount BIGINT NOT NULL DEFAULT 0 CHECK (refund_amount >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_return_requests_order ON return_requests(order_id);
+CREATE INDEX idx_return_requests_user ON return_requests(user_id);
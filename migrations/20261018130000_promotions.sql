@@ -0,0 +1,65 @@
+-- =====================================================
+-- ============== PROMOTIONS AND COUPONS ===============
+-- =====================================================
+
+ALTER TABLE books ADD COLUMN category VARCHAR(100);
+CREATE INDEX idx_books_category ON books(category);
+
+-- conditions: JSON array of {"type": "category" | "author" | "min_cart_total" |
+--             "customer_role" | "date_window", ...}
+-- action:     {"type": "percent_off" | "fixed_off" | "buy_x_pay_y" | "free_shipping", ...}
+CREATE TABLE promotions (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    conditions JSONB NOT NULL DEFAULT '[]',
+    action JSONB NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    stackable BOOLEAN NOT NULL DEFAULT TRUE,
+    requires_coupon BOOLEAN NOT NULL DEFAULT FALSE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_promotions_active ON promotions(is_active);
+
+CREATE TABLE coupons (
+    id SERIAL PRIMARY KEY,
+    code VARCHAR(32) NOT NULL UNIQUE,
+    promotion_id INTEGER NOT NULL REFERENCES promotions(id) ON DELETE CASCADE,
+    usage_limit INTEGER CHECK (usage_limit > 0),
+    per_customer_limit INTEGER CHECK (per_customer_limit > 0),
+    times_used INTEGER NOT NULL DEFAULT 0 CHECK (times_used >= 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (usage_limit IS NULL OR times_used <= usage_limit)
+);
+
+CREATE TABLE coupon_redemptions (
+    id SERIAL PRIMARY KEY,
+    coupon_id INTEGER NOT NULL REFERENCES coupons(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    redeemed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_coupon_redemptions_coupon_user ON coupon_redemptions(coupon_id, user_id);
+
+ALTER TABLE carts ADD COLUMN coupon_code VARCHAR(32);
+
+ALTER TABLE orders ADD COLUMN discount_total BIGINT NOT NULL DEFAULT 0
+    CHECK (discount_total >= 0);
+
+-- Promotions applied at checkout, frozen with their discount.
+CREATE TABLE order_promotions (
+    id SERIAL PRIMARY KEY,
+    order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    promotion_id INTEGER NOT NULL REFERENCES promotions(id) ON DELETE RESTRICT,
+    name VARCHAR(255) NOT NULL,
+    coupon_id INTEGER REFERENCES coupons(id) ON DELETE SET NULL,
+    coupon_code VARCHAR(32),
+    discount BIGINT NOT NULL CHECK (discount >= 0)
+);
+
+CREATE INDEX idx_order_promotions_order ON order_promotions(order_id);
@@ -0,0 +1,76 @@
+-- =====================================================
+-- ========== STORES, PRICES AND TAX RATES =============
+-- =====================================================
+-- All amounts are integer minor units (satang for THB).
+
+-- books.price is the base price in books.currency
+ALTER TABLE books
+    ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'THB',
+    ADD COLUMN tax_class VARCHAR(30) NOT NULL DEFAULT 'printed_book'
+        CHECK (tax_class IN ('standard', 'printed_book', 'ebook', 'exempt'));
+
+CREATE TABLE stores (
+    id SERIAL PRIMARY KEY,
+    code VARCHAR(50) NOT NULL UNIQUE,
+    name VARCHAR(255) NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    -- ISO 3166 country, optionally with a region: 'TH', 'US-CA'
+    jurisdiction VARCHAR(10) NOT NULL,
+    prices_include_tax BOOLEAN NOT NULL DEFAULT TRUE,
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- มี default store ได้แค่ร้านเดียว
+CREATE UNIQUE INDEX idx_stores_single_default ON stores(is_default) WHERE is_default;
+
+INSERT INTO stores (code, name, currency, jurisdiction, prices_include_tax, is_default)
+VALUES ('TH-ONLINE', 'Online Store (Thailand)', 'THB', 'TH', TRUE, TRUE);
+
+-- A cart keeps the store it was opened in, so its price snapshots stay in
+-- that store's currency
+ALTER TABLE carts ADD COLUMN store_id INTEGER REFERENCES stores(id);
+UPDATE carts SET store_id = (SELECT id FROM stores WHERE is_default);
+ALTER TABLE carts ALTER COLUMN store_id SET NOT NULL;
+
+-- Overrides of the base price: per store (store_id set) or per currency (store_id NULL)
+CREATE TABLE book_prices (
+    id SERIAL PRIMARY KEY,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    store_id INTEGER REFERENCES stores(id) ON DELETE CASCADE,
+    currency VARCHAR(3) NOT NULL,
+    amount BIGINT NOT NULL CHECK (amount >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_book_prices_store ON book_prices(book_id, store_id)
+    WHERE store_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_book_prices_currency ON book_prices(book_id, currency)
+    WHERE store_id IS NULL;
+
+CREATE TABLE tax_rates (
+    id SERIAL PRIMARY KEY,
+    jurisdiction VARCHAR(10) NOT NULL,
+    tax_class VARCHAR(30) NOT NULL
+        CHECK (tax_class IN ('standard', 'printed_book', 'ebook', 'exempt')),
+    name VARCHAR(100) NOT NULL,
+    -- basis points: 700 = 7%
+    rate_bps INTEGER NOT NULL CHECK (rate_bps >= 0),
+    valid_from TIMESTAMPTZ NOT NULL,
+    valid_to TIMESTAMPTZ,
+    CHECK (valid_to IS NULL OR valid_to > valid_from)
+);
+
+CREATE INDEX idx_tax_rates_lookup ON tax_rates(jurisdiction, tax_class);
+
+-- Thai VAT 7%; the sale of printed books is exempt under Revenue Code
+-- s.81(1)(ฌ), e-books are not
+INSERT INTO tax_rates (jurisdiction, tax_class, name, rate_bps, valid_from) VALUES
+    ('TH', 'standard', 'VAT 7%', 700, '2000-01-01'),
+    ('TH', 'printed_book', 'VAT exempt (Revenue Code s.81)', 0, '2000-01-01'),
+    ('TH', 'ebook', 'VAT 7%', 700, '2000-01-01');
+
+ALTER TABLE orders ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'THB';
+ALTER TABLE order_items ADD COLUMN price_includes_tax BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,93 @@
+-- =====================================================
+-- ===================== SHIPPING ======================
+-- =====================================================
+
+-- Shipping profile of a book; weight falls back to a default when NULL
+ALTER TABLE books
+    ADD COLUMN weight_grams INTEGER CHECK (weight_grams > 0),
+    ADD COLUMN length_mm INTEGER CHECK (length_mm > 0),
+    ADD COLUMN width_mm INTEGER CHECK (width_mm > 0),
+    ADD COLUMN height_mm INTEGER CHECK (height_mm > 0),
+    ADD CONSTRAINT books_dimensions_complete CHECK (
+        (length_mm IS NULL AND width_mm IS NULL AND height_mm IS NULL)
+        OR (length_mm IS NOT NULL AND width_mm IS NOT NULL AND height_mm IS NOT NULL)
+    );
+
+-- A zone covers a country, or part of it by postal code prefix
+CREATE TABLE shipping_zones (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    country VARCHAR(2) NOT NULL,
+    postal_prefixes TEXT[] NOT NULL DEFAULT '{}',
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_shipping_zones_country ON shipping_zones(country);
+
+CREATE TABLE shipping_methods (
+    id SERIAL PRIMARY KEY,
+    zone_id INTEGER NOT NULL REFERENCES shipping_zones(id) ON DELETE CASCADE,
+    code VARCHAR(50) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('standard', 'express', 'store_pickup')),
+    rate_basis VARCHAR(20) NOT NULL CHECK (rate_basis IN ('weight', 'item_count', 'subtotal')),
+    -- estimate in business days
+    min_days INTEGER NOT NULL CHECK (min_days >= 0),
+    max_days INTEGER NOT NULL,
+    max_weight_grams INTEGER CHECK (max_weight_grams > 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (zone_id, code),
+    CHECK (max_days >= min_days)
+);
+
+-- Rate table bracket: min_value <= value < max_value (NULL = no upper bound)
+CREATE TABLE shipping_rates (
+    id SERIAL PRIMARY KEY,
+    method_id INTEGER NOT NULL REFERENCES shipping_methods(id) ON DELETE CASCADE,
+    min_value BIGINT NOT NULL CHECK (min_value >= 0),
+    max_value BIGINT,
+    amount BIGINT NOT NULL CHECK (amount >= 0),
+    CHECK (max_value IS NULL OR max_value > min_value)
+);
+
+CREATE INDEX idx_shipping_rates_method ON shipping_rates(method_id);
+
+-- Default Thai zone: standard/express by weight, free store pickup
+INSERT INTO shipping_zones (name, country) VALUES ('Thailand', 'TH');
+
+INSERT INTO shipping_methods (zone_id, code, name, kind, rate_basis, min_days, max_days, max_weight_grams)
+SELECT id, 'standard', 'Standard delivery', 'standard', 'weight', 2, 5, 20000
+FROM shipping_zones WHERE country = 'TH';
+
+INSERT INTO shipping_methods (zone_id, code, name, kind, rate_basis, min_days, max_days, max_weight_grams)
+SELECT id, 'express', 'Express delivery', 'express', 'weight', 1, 2, 10000
+FROM shipping_zones WHERE country = 'TH';
+
+INSERT INTO shipping_methods (zone_id, code, name, kind, rate_basis, min_days, max_days)
+SELECT id, 'pickup', 'Store pickup', 'store_pickup', 'item_count', 1, 3
+FROM shipping_zones WHERE country = 'TH';
+
+INSERT INTO shipping_rates (method_id, min_value, max_value, amount)
+SELECT m.id, r.min_value, r.max_value, r.amount
+FROM shipping_methods m
+JOIN (VALUES
+    ('standard', 0, 1000, 4000),
+    ('standard', 1000, 3000, 6000),
+    ('standard', 3000, NULL, 9000),
+    ('express', 0, 1000, 8000),
+    ('express', 1000, 3000, 12000),
+    ('express', 3000, NULL, 18000)
+) AS r(code, min_value, max_value, amount) ON r.code = m.code;
+
+-- Shipping chosen at checkout (frozen on the order)
+ALTER TABLE orders
+    ADD COLUMN shipping_total BIGINT NOT NULL DEFAULT 0 CHECK (shipping_total >= 0),
+    ADD COLUMN shipping_method_id INTEGER REFERENCES shipping_methods(id) ON DELETE SET NULL,
+    ADD COLUMN shipping_method_name VARCHAR(100),
+    ADD COLUMN shipping_kind VARCHAR(20),
+    ADD COLUMN estimated_delivery_from DATE,
+    ADD COLUMN estimated_delivery_to DATE;
@@ -0,0 +1,49 @@
+-- =====================================================
+-- =================== ADDRESS BOOK ====================
+-- =====================================================
+
+CREATE TABLE addresses (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    label VARCHAR(50),
+    recipient_name VARCHAR(100) NOT NULL,
+    phone VARCHAR(20) NOT NULL,
+    line1 VARCHAR(200) NOT NULL,
+    line2 VARCHAR(200),
+    subdistrict VARCHAR(200),   -- ตำบล / แขวง
+    district VARCHAR(200),      -- อำเภอ / เขต
+    province VARCHAR(100),
+    postal_code VARCHAR(10),
+    country VARCHAR(2) NOT NULL,
+    is_default_shipping BOOLEAN NOT NULL DEFAULT FALSE,
+    is_default_billing BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_addresses_user ON addresses(user_id);
+-- At most one default of each kind per user
+CREATE UNIQUE INDEX idx_addresses_default_shipping ON addresses(user_id) WHERE is_default_shipping;
+CREATE UNIQUE INDEX idx_addresses_default_billing ON addresses(user_id) WHERE is_default_billing;
+
+-- Copies of the addresses used at checkout
+ALTER TABLE orders
+    ADD COLUMN shipping_address JSONB,
+    ADD COLUMN billing_address JSONB;
+
+-- ที่อยู่ใน order เป็น snapshot แก้ไม่ได้หลังสร้าง order
+CREATE OR REPLACE FUNCTION orders_addresses_immutable()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.shipping_address IS DISTINCT FROM OLD.shipping_address
+        OR NEW.billing_address IS DISTINCT FROM OLD.billing_address THEN
+        RAISE EXCEPTION 'Order addresses cannot be changed';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_orders_addresses_immutable
+    BEFORE UPDATE ON orders
+    FOR EACH ROW
+    EXECUTE FUNCTION orders_addresses_immutable();
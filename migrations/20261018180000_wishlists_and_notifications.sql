@@ -0,0 +1,58 @@
+-- =====================================================
+-- ===================== WISHLISTS =====================
+-- =====================================================
+
+CREATE TABLE wishlists (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    -- NULL = private; set while the list is shared by link
+    share_token VARCHAR(24) UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_wishlists_user ON wishlists(user_id);
+
+CREATE TABLE wishlist_items (
+    wishlist_id INTEGER NOT NULL REFERENCES wishlists(id) ON DELETE CASCADE,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    note TEXT,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (wishlist_id, book_id)
+);
+
+-- =====================================================
+-- ============ BACK-IN-STOCK NOTIFICATIONS ============
+-- =====================================================
+
+-- Fires once: the inventory ledger sets notified_at when stock goes 0 → positive
+CREATE TABLE stock_subscriptions (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    notified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, book_id)
+);
+
+CREATE INDEX idx_stock_subscriptions_waiting ON stock_subscriptions(book_id)
+    WHERE notified_at IS NULL;
+
+-- Outgoing messages, delivered by a background worker
+CREATE TABLE notification_jobs (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(30) NOT NULL CHECK (kind IN ('back_in_stock')),
+    payload JSONB NOT NULL DEFAULT '{}'::jsonb,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'sent', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    run_after TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notification_jobs_due ON notification_jobs(run_after)
+    WHERE status = 'pending';
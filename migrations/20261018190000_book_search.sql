@@ -0,0 +1,40 @@
+-- =====================================================
+-- ================ FULL-TEXT BOOK SEARCH ==============
+-- =====================================================
+
+ALTER TABLE books ADD COLUMN description TEXT;
+
+-- Postgres has no Thai dictionary and Thai is written without spaces, so
+-- runs of Thai characters are indexed as overlapping character bigrams.
+-- Must stay in sync with the query tokenizer (domain/services/search_text.rs).
+CREATE FUNCTION thai_bigrams(input TEXT) RETURNS TEXT[]
+LANGUAGE plpgsql IMMUTABLE STRICT PARALLEL SAFE AS $$
+DECLARE
+    run TEXT;
+    i INTEGER;
+    result TEXT[] := '{}';
+BEGIN
+    FOR run IN SELECT m[1] FROM regexp_matches(input, '([ก-๛]+)', 'g') AS m LOOP
+        IF char_length(run) = 1 THEN
+            result := result || run;
+        ELSE
+            FOR i IN 1 .. char_length(run) - 1 LOOP
+                result := result || substr(run, i, 2);
+            END LOOP;
+        END IF;
+    END LOOP;
+    RETURN result;
+END;
+$$;
+
+-- Weights: title (A) > author (B) > description (C)
+ALTER TABLE books ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', title), 'A')
+    || setweight(array_to_tsvector(thai_bigrams(title)), 'A')
+    || setweight(to_tsvector('simple', author), 'B')
+    || setweight(array_to_tsvector(thai_bigrams(author)), 'B')
+    || setweight(to_tsvector('simple', coalesce(description, '')), 'C')
+    || setweight(array_to_tsvector(coalesce(thai_bigrams(description), '{}')), 'C')
+) STORED;
+
+CREATE INDEX idx_books_search ON books USING GIN (search_vector);
@@ -0,0 +1,26 @@
+-- =====================================================
+-- ================= CATALOG FACETS ====================
+-- =====================================================
+
+ALTER TABLE books
+    ADD COLUMN publisher VARCHAR(255),
+    ADD COLUMN language VARCHAR(2) CHECK (language ~ '^[a-z]{2}$'),
+    ADD COLUMN format VARCHAR(20) NOT NULL DEFAULT 'paperback'
+        CHECK (format IN ('paperback', 'hardcover', 'ebook', 'audiobook'));
+
+UPDATE books SET format = 'ebook' WHERE tax_class = 'ebook';
+
+-- Filters and sorts of the storefront (only active books are browsable)
+CREATE INDEX idx_books_active_category ON books(category) WHERE is_active;
+CREATE INDEX idx_books_active_author ON books(author) WHERE is_active;
+CREATE INDEX idx_books_active_publisher ON books(publisher) WHERE is_active;
+CREATE INDEX idx_books_active_language ON books(language) WHERE is_active;
+CREATE INDEX idx_books_active_format ON books(format) WHERE is_active;
+CREATE INDEX idx_books_active_price ON books(price) WHERE is_active;
+CREATE INDEX idx_books_active_created ON books(created_at DESC, id DESC) WHERE is_active;
+
+-- Facet counting reads every matching row; this covering index lets it use an
+-- index-only scan instead of reading wide heap rows (descriptions, tsvector)
+CREATE INDEX idx_books_facets ON books(category, author, publisher, language, format)
+    INCLUDE (price, rating_count, rating_total, stock_quantity)
+    WHERE is_active;
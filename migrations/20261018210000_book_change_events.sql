@@ -0,0 +1,64 @@
+-- =====================================================
+-- ================ BOOK CHANGE EVENTS =================
+-- =====================================================
+
+-- Outbox feeding external search indexes. Consumers keep a (txid, id)
+-- cursor and only read transactions older than the snapshot xmin, so an
+-- event committed late is never skipped.
+CREATE TABLE book_change_events (
+    id BIGSERIAL PRIMARY KEY,
+    txid BIGINT NOT NULL DEFAULT pg_current_xact_id()::TEXT::BIGINT,
+    -- No FK: deletions are events too
+    book_id INTEGER NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_book_change_events_cursor ON book_change_events(txid, id);
+
+CREATE FUNCTION record_book_change() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO book_change_events (book_id) VALUES (OLD.id);
+    ELSE
+        INSERT INTO book_change_events (book_id) VALUES (NEW.id);
+    END IF;
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER trg_books_change_insert_delete
+    AFTER INSERT OR DELETE ON books
+    FOR EACH ROW EXECUTE FUNCTION record_book_change();
+
+-- Only changes that affect what is indexed (stock only when it crosses zero)
+CREATE TRIGGER trg_books_change_update
+    AFTER UPDATE ON books
+    FOR EACH ROW
+    WHEN (
+        OLD.isbn IS DISTINCT FROM NEW.isbn
+        OR OLD.title IS DISTINCT FROM NEW.title
+        OR OLD.author IS DISTINCT FROM NEW.author
+        OR OLD.description IS DISTINCT FROM NEW.description
+        OR OLD.category IS DISTINCT FROM NEW.category
+        OR OLD.publisher IS DISTINCT FROM NEW.publisher
+        OR OLD.is_active IS DISTINCT FROM NEW.is_active
+        OR (OLD.stock_quantity > 0) IS DISTINCT FROM (NEW.stock_quantity > 0)
+    )
+    EXECUTE FUNCTION record_book_change();
+
+-- A paid order changes the sales boost of its books
+CREATE FUNCTION record_order_book_changes() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+    INSERT INTO book_change_events (book_id)
+    SELECT DISTINCT book_id FROM order_items WHERE order_id = NEW.id;
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER trg_orders_paid_book_change
+    AFTER UPDATE OF status ON orders
+    FOR EACH ROW
+    WHEN (NEW.status = 'paid' AND OLD.status IS DISTINCT FROM 'paid')
+    EXECUTE FUNCTION record_order_book_changes();
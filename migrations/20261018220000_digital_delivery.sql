@@ -0,0 +1,55 @@
+-- =====================================================
+-- ================= DIGITAL DELIVERY ==================
+-- =====================================================
+
+-- Files of e-book/audiobook editions; the bytes live in file storage
+CREATE TABLE digital_assets (
+    id SERIAL PRIMARY KEY,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    file_format VARCHAR(10) NOT NULL CHECK (file_format IN ('epub', 'pdf', 'mp3')),
+    storage_key VARCHAR(500) NOT NULL UNIQUE,
+    file_name VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    sha256 CHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (book_id, file_format)
+);
+
+-- Right of a user to download a digital book, granted when an order is paid
+-- (order_id set) or by staff (order_id NULL)
+CREATE TABLE entitlements (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE RESTRICT,
+    order_id INTEGER REFERENCES orders(id) ON DELETE SET NULL,
+    -- NULL = unlimited
+    download_limit INTEGER CHECK (download_limit IS NULL OR download_limit >= 0),
+    downloads_used INTEGER NOT NULL DEFAULT 0 CHECK (downloads_used >= 0),
+    revoked_at TIMESTAMPTZ,
+    revoke_reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (order_id, book_id)
+);
+
+CREATE INDEX idx_entitlements_user ON entitlements(user_id, book_id);
+
+-- Every request made with a validly signed link, served or not
+CREATE TABLE download_logs (
+    id BIGSERIAL PRIMARY KEY,
+    entitlement_id INTEGER NOT NULL REFERENCES entitlements(id) ON DELETE CASCADE,
+    -- No FK: logs outlive replaced or deleted files
+    asset_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    outcome VARCHAR(20) NOT NULL CHECK (outcome IN ('served', 'expired', 'revoked', 'limit_reached')),
+    range_start BIGINT,
+    range_end BIGINT,
+    bytes_served BIGINT NOT NULL DEFAULT 0,
+    counted BOOLEAN NOT NULL DEFAULT FALSE,
+    ip_address VARCHAR(45),
+    user_agent VARCHAR(500),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_download_logs_entitlement ON download_logs(entitlement_id, created_at DESC);
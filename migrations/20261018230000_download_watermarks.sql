@@ -0,0 +1,23 @@
+-- =====================================================
+-- =============== DOWNLOAD WATERMARKS =================
+-- =====================================================
+
+-- Stamped per-customer copies of EPUB files, and the lookup from the code
+-- inside a leaked file back to its buyer. Buyer details are copied so the
+-- trail survives the entitlement or user being deleted.
+CREATE TABLE download_watermarks (
+    id SERIAL PRIMARY KEY,
+    code VARCHAR(32) NOT NULL UNIQUE,
+    entitlement_id INTEGER REFERENCES entitlements(id) ON DELETE SET NULL,
+    asset_id INTEGER NOT NULL,
+    asset_sha256 CHAR(64) NOT NULL,
+    user_id INTEGER NOT NULL,
+    order_id INTEGER,
+    licensee VARCHAR(255) NOT NULL,
+    storage_key VARCHAR(500) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (entitlement_id, asset_id, asset_sha256)
+);
+
+CREATE INDEX idx_download_watermarks_order ON download_watermarks(order_id);
@@ -0,0 +1,28 @@
+-- =====================================================
+-- ===================== PRE-ORDERS ====================
+-- =====================================================
+
+-- Announced titles: stock of an unreleased book is its pre-order allocation
+ALTER TABLE books
+    ADD COLUMN release_date DATE,
+    ADD COLUMN preorder_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD CONSTRAINT books_preorder_needs_release_date CHECK (
+        NOT preorder_enabled OR release_date IS NOT NULL
+    );
+
+-- Pre-orders wait in 'preordered' with the payment authorized, not captured
+ALTER TABLE orders DROP CONSTRAINT orders_status_check;
+ALTER TABLE orders ADD CONSTRAINT orders_status_check CHECK (status IN (
+    'pending_payment', 'preordered', 'paid', 'picking', 'shipped',
+    'delivered', 'cancelled', 'refunded'
+));
+
+-- Day the order is captured and released to fulfilment (NULL = not a pre-order)
+ALTER TABLE orders ADD COLUMN release_date DATE;
+
+CREATE INDEX idx_orders_preorder_release ON orders(release_date)
+    WHERE status = 'preordered';
+
+-- Price-drop protection: lowest shelf price seen between checkout and release
+ALTER TABLE order_items
+    ADD COLUMN lowest_unit_price BIGINT CHECK (lowest_unit_price >= 0);
@@ -0,0 +1,58 @@
+-- =====================================================
+-- ========== GIFT CARDS & STORE CREDIT LEDGER =========
+-- =====================================================
+
+-- Gift cards (bearer, found by the hash of their code) and per-customer store
+-- credit. balance mirrors the sum of the ledger and only changes with an entry.
+CREATE TABLE stored_value_accounts (
+    id SERIAL PRIMARY KEY,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('gift_card', 'store_credit')),
+    code_hash CHAR(64) UNIQUE,
+    code_last4 CHAR(4),
+    user_id INTEGER REFERENCES users(id) ON DELETE RESTRICT,
+    currency VARCHAR(3) NOT NULL,
+    balance BIGINT NOT NULL DEFAULT 0 CHECK (balance >= 0),
+    expires_at TIMESTAMPTZ,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT stored_value_accounts_kind_fields CHECK (
+        (kind = 'gift_card' AND code_hash IS NOT NULL AND code_last4 IS NOT NULL)
+        OR (kind = 'store_credit' AND user_id IS NOT NULL AND code_hash IS NULL)
+    )
+);
+
+CREATE UNIQUE INDEX uq_stored_value_store_credit ON stored_value_accounts(user_id, currency)
+    WHERE kind = 'store_credit';
+CREATE INDEX idx_stored_value_accounts_expiry ON stored_value_accounts(expires_at)
+    WHERE balance > 0;
+
+CREATE TABLE stored_value_entries (
+    id SERIAL PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES stored_value_accounts(id) ON DELETE RESTRICT,
+    amount BIGINT NOT NULL CHECK (amount <> 0),
+    reason VARCHAR(30) NOT NULL CHECK (reason IN (
+        'issue', 'redemption', 'redemption_refund', 'refund_credit', 'adjustment', 'expiry'
+    )),
+    order_id INTEGER REFERENCES orders(id) ON DELETE RESTRICT,
+    note TEXT,
+    created_by INTEGER REFERENCES users(id) ON DELETE RESTRICT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (reason <> 'adjustment' OR note IS NOT NULL)
+);
+
+CREATE INDEX idx_stored_value_entries_account ON stored_value_entries(account_id, created_at);
+CREATE INDEX idx_stored_value_entries_order ON stored_value_entries(order_id)
+    WHERE order_id IS NOT NULL;
+
+-- The ledger is append-only; corrections are new entries. Its references are
+-- RESTRICT because the trigger would reject SET NULL / CASCADE anyway.
+CREATE FUNCTION stored_value_entries_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'stored_value_entries is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_stored_value_entries_append_only
+    BEFORE UPDATE OR DELETE ON stored_value_entries
+    FOR EACH ROW EXECUTE FUNCTION stored_value_entries_append_only();
@@ -0,0 +1,77 @@
+-- =====================================================
+-- ================== LOYALTY POINTS ===================
+-- =====================================================
+
+-- Tier levels are ordinary roles, so promotions can target them
+-- with a customer_role condition
+INSERT INTO roles (name, description) VALUES
+    ('SILVER', 'Loyalty tier: rolling 12-month spend above the Silver threshold'),
+    ('GOLD', 'Loyalty tier: rolling 12-month spend above the Gold threshold')
+ON CONFLICT (name) DO NOTHING;
+
+-- Earn rate per category, in percent of the base rate (100 = 1x)
+CREATE TABLE loyalty_category_multipliers (
+    category VARCHAR(100) PRIMARY KEY,
+    multiplier_pct INTEGER NOT NULL CHECK (multiplier_pct >= 0 AND multiplier_pct <= 1000),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Time-boxed bonus campaigns, for one category or everything (category NULL)
+CREATE TABLE loyalty_campaigns (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    category VARCHAR(100),
+    multiplier_pct INTEGER NOT NULL CHECK (multiplier_pct >= 100 AND multiplier_pct <= 1000),
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (ends_at > starts_at)
+);
+
+CREATE INDEX idx_loyalty_campaigns_window ON loyalty_campaigns(starts_at, ends_at)
+    WHERE is_active;
+
+-- One balance per member; mirrors the sum of the ledger
+CREATE TABLE loyalty_accounts (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE RESTRICT,
+    balance BIGINT NOT NULL DEFAULT 0 CHECK (balance >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Points ledger. Positive entries expire at expires_at; expiry consumes the
+-- oldest points first, so what is due is matured points minus all debits.
+CREATE TABLE loyalty_point_entries (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    points BIGINT NOT NULL CHECK (points <> 0),
+    reason VARCHAR(30) NOT NULL CHECK (reason IN (
+        'earn', 'clawback', 'redemption', 'redemption_reversal', 'expiry'
+    )),
+    order_id INTEGER REFERENCES orders(id) ON DELETE RESTRICT,
+    expires_at TIMESTAMPTZ,
+    note TEXT,
+    created_by INTEGER REFERENCES users(id) ON DELETE RESTRICT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((points > 0) = (expires_at IS NOT NULL))
+);
+
+CREATE INDEX idx_loyalty_point_entries_user ON loyalty_point_entries(user_id, created_at);
+CREATE INDEX idx_loyalty_point_entries_order ON loyalty_point_entries(order_id)
+    WHERE order_id IS NOT NULL;
+-- Points are earned once per order
+CREATE UNIQUE INDEX uq_loyalty_point_entries_earn ON loyalty_point_entries(order_id)
+    WHERE reason = 'earn';
+
+-- Append-only like the stored value ledger, hence RESTRICT references above
+CREATE FUNCTION loyalty_point_entries_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'loyalty_point_entries is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_loyalty_point_entries_append_only
+    BEFORE UPDATE OR DELETE ON loyalty_point_entries
+    FOR EACH ROW EXECUTE FUNCTION loyalty_point_entries_append_only();
@@ -0,0 +1,80 @@
+-- =====================================================
+-- ============ TAX INVOICES & CREDIT NOTES ============
+-- =====================================================
+
+-- Last number used per document kind and year. Issuing locks the row
+-- until the invoice is stored, so a failed issue rolls the number back
+-- and numbers never skip.
+CREATE TABLE invoice_sequences (
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('tax_invoice', 'credit_note')),
+    fiscal_year INTEGER NOT NULL,
+    last_number INTEGER NOT NULL CHECK (last_number > 0),
+    PRIMARY KEY (kind, fiscal_year)
+);
+
+-- Issued documents with the PDF as rendered. Seller and buyer details are
+-- copies taken at issue time.
+CREATE TABLE invoices (
+    id SERIAL PRIMARY KEY,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('tax_invoice', 'credit_note')),
+    number VARCHAR(30) NOT NULL UNIQUE,
+    fiscal_year INTEGER NOT NULL,
+    sequence INTEGER NOT NULL CHECK (sequence > 0),
+    order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE RESTRICT,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+
+    -- Credit notes: the tax invoice they reduce
+    original_invoice_id INTEGER REFERENCES invoices(id) ON DELETE RESTRICT,
+    original_number VARCHAR(30),
+    original_issued_at TIMESTAMPTZ,
+    value_before BIGINT,
+    return_id INTEGER REFERENCES return_requests(id) ON DELETE RESTRICT,
+    reason TEXT,
+
+    seller_name VARCHAR(255) NOT NULL,
+    seller_tax_id CHAR(13) NOT NULL,
+    seller_branch CHAR(5) NOT NULL,
+    seller_address TEXT NOT NULL,
+    buyer_name VARCHAR(255) NOT NULL,
+    buyer_tax_id CHAR(13) NOT NULL,
+    buyer_branch CHAR(5) NOT NULL,
+    buyer_address TEXT NOT NULL,
+
+    currency VARCHAR(3) NOT NULL,
+    lines JSONB NOT NULL,
+    net_amount BIGINT NOT NULL,
+    tax_amount BIGINT NOT NULL CHECK (tax_amount >= 0),
+    total BIGINT NOT NULL CHECK (total >= 0),
+
+    document BYTEA NOT NULL,
+    sha256 CHAR(64) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    issued_by INTEGER REFERENCES users(id) ON DELETE RESTRICT,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE (kind, fiscal_year, sequence),
+    CHECK (net_amount + tax_amount = total),
+    CHECK ((kind = 'credit_note') = (original_invoice_id IS NOT NULL))
+);
+
+CREATE INDEX idx_invoices_user ON invoices(user_id, issued_at DESC);
+CREATE INDEX idx_invoices_order ON invoices(order_id);
+CREATE INDEX idx_invoices_original ON invoices(original_invoice_id)
+    WHERE original_invoice_id IS NOT NULL;
+-- One full tax invoice per order, one credit note per refunded return
+CREATE UNIQUE INDEX uq_invoices_tax_invoice_order ON invoices(order_id)
+    WHERE kind = 'tax_invoice';
+CREATE UNIQUE INDEX uq_invoices_credit_note_return ON invoices(return_id)
+    WHERE return_id IS NOT NULL;
+
+-- Issued documents are never edited or removed; mistakes are corrected
+-- with a credit note
+CREATE FUNCTION invoices_immutable() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'invoices are immutable once issued';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_invoices_immutable
+    BEFORE UPDATE OR DELETE ON invoices
+    FOR EACH ROW EXECUTE FUNCTION invoices_immutable();
@@ -0,0 +1,75 @@
+-- =====================================================
+-- ============ CONTRIBUTORS & SUBJECTS ================
+-- =====================================================
+
+-- books.author stays the display string; these keep the full credits
+CREATE TABLE book_contributors (
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL CHECK (sequence > 0),
+    -- ONIX contributor role code, e.g. A01 (author), B06 (translator)
+    role VARCHAR(3) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    PRIMARY KEY (book_id, sequence)
+);
+
+CREATE TABLE book_subjects (
+    book_id INTEGER NOT NULL REFERENCES books(id) ON DELETE CASCADE,
+    scheme VARCHAR(10) NOT NULL CHECK (scheme IN ('bisac', 'thema')),
+    code VARCHAR(20) NOT NULL,
+    heading VARCHAR(255),
+    is_main BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (book_id, scheme, code)
+);
+
+CREATE INDEX idx_book_subjects_code ON book_subjects(scheme, code);
+
+-- =====================================================
+-- ================ CATALOG IMPORT JOBS ================
+-- =====================================================
+
+-- One feed file, worked through by a background job. checkpoint_offset is
+-- the byte offset the next record starts at, so a restarted job carries on
+-- where the last checkpoint left it.
+CREATE TABLE catalog_import_jobs (
+    id SERIAL PRIMARY KEY,
+    format VARCHAR(10) NOT NULL CHECK (format IN ('onix')),
+    -- Relative to the import inbox directory
+    file_name VARCHAR(255) NOT NULL,
+    -- From the feed header, once read
+    sender VARCHAR(255),
+    status VARCHAR(20) NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'completed', 'failed')),
+    checkpoint_offset BIGINT NOT NULL DEFAULT 0 CHECK (checkpoint_offset >= 0),
+    records_processed INTEGER NOT NULL DEFAULT 0,
+    created_count INTEGER NOT NULL DEFAULT 0,
+    updated_count INTEGER NOT NULL DEFAULT 0,
+    rejected_count INTEGER NOT NULL DEFAULT 0,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    -- Claim lease: a running job whose lease ran out is picked up again
+    run_after TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    requested_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_catalog_import_jobs_due ON catalog_import_jobs(run_after)
+    WHERE status IN ('queued', 'running');
+
+-- Per-record report. A record replayed after a restart overwrites its row.
+CREATE TABLE catalog_import_records (
+    job_id INTEGER NOT NULL REFERENCES catalog_import_jobs(id) ON DELETE CASCADE,
+    -- 0-based position of the record in the file
+    record_index INTEGER NOT NULL CHECK (record_index >= 0),
+    -- The sender's record reference, when given
+    reference VARCHAR(255),
+    isbn VARCHAR(13),
+    book_id INTEGER REFERENCES books(id) ON DELETE SET NULL,
+    outcome VARCHAR(10) NOT NULL CHECK (outcome IN ('created', 'updated', 'rejected')),
+    -- Why it was rejected, or what was skipped on an accepted record
+    messages TEXT[] NOT NULL DEFAULT '{}',
+    PRIMARY KEY (job_id, record_index)
+);
+
+CREATE INDEX idx_catalog_import_records_outcome ON catalog_import_records(job_id, outcome, record_index);
@@ -0,0 +1,7 @@
+-- =====================================================
+-- ==================== BOOK COVERS ====================
+-- =====================================================
+
+-- Rendered cover variants (sizes × formats, storage keys, public URLs) and
+-- the BlurHash placeholder; NULL until a cover is uploaded
+ALTER TABLE books ADD COLUMN cover JSONB;
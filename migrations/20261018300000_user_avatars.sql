@@ -0,0 +1,6 @@
+-- =====================================================
+-- ==================== USER AVATARS ===================
+-- =====================================================
+
+-- Rendered avatar sizes (storage keys, public URLs); NULL until uploaded
+ALTER TABLE users ADD COLUMN avatar JSONB;
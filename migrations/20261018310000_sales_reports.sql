@@ -0,0 +1,99 @@
+-- =====================================================
+-- =================== SALES REPORTS ===================
+-- =====================================================
+
+-- Store the order was placed in. Orders so far all went through the default store.
+ALTER TABLE orders ADD COLUMN store_id INTEGER REFERENCES stores(id) ON DELETE RESTRICT;
+UPDATE orders SET store_id = (SELECT id FROM stores WHERE is_default);
+ALTER TABLE orders ALTER COLUMN store_id SET NOT NULL;
+
+CREATE INDEX idx_orders_store_created ON orders(store_id, created_at);
+
+INSERT INTO roles (name, description) VALUES
+    ('REPORTS', 'Can read sales reports and analytics')
+ON CONFLICT (name) DO NOTHING;
+
+-- Reports read pre-aggregated daily rows instead of the order tables; the
+-- `reports` job refreshes them on a schedule. A sale is an order whose
+-- payment was captured (paid onwards, refunded included), counted on the day
+-- it was placed in Thai time, the same day as its tax invoice.
+
+-- Order totals per day and store. Refunds (card, gift card and store credit)
+-- count against the day the order was placed.
+CREATE MATERIALIZED VIEW report_sales_daily AS
+SELECT (o.created_at AT TIME ZONE 'Asia/Bangkok')::date AS sales_date,
+       o.store_id,
+       o.currency,
+       COUNT(*) AS orders,
+       COALESCE(SUM(i.units), 0)::bigint AS units,
+       SUM(o.total)::bigint AS revenue,
+       SUM(o.discount_total)::bigint AS discount_total,
+       SUM(o.shipping_total)::bigint AS shipping_total,
+       SUM(o.tax_total)::bigint AS tax_total,
+       COUNT(*) FILTER (WHERE COALESCE(p.refunded, 0) + COALESCE(c.refunded, 0) > 0) AS refunded_orders,
+       SUM(COALESCE(p.refunded, 0) + COALESCE(c.refunded, 0))::bigint AS refunded_amount
+FROM orders o
+LEFT JOIN (
+    SELECT order_id, SUM(quantity) AS units FROM order_items GROUP BY order_id
+) i ON i.order_id = o.id
+LEFT JOIN (
+    SELECT order_id, SUM(refunded_amount) AS refunded FROM payments GROUP BY order_id
+) p ON p.order_id = o.id
+LEFT JOIN (
+    SELECT order_id, SUM(amount) AS refunded
+    FROM stored_value_entries
+    WHERE reason = 'refund_credit' AND order_id IS NOT NULL
+    GROUP BY order_id
+) c ON c.order_id = o.id
+WHERE o.status IN ('paid', 'picking', 'shipped', 'delivered', 'refunded')
+GROUP BY 1, 2, 3;
+
+-- Unique indexes let the job refresh CONCURRENTLY, without blocking readers
+CREATE UNIQUE INDEX idx_report_sales_daily ON report_sales_daily(sales_date, store_id, currency);
+
+-- Units and line revenue (before order-level discounts) per day, store and
+-- book. Author and category are the book's current ones.
+CREATE MATERIALIZED VIEW report_book_sales_daily AS
+SELECT (o.created_at AT TIME ZONE 'Asia/Bangkok')::date AS sales_date,
+       o.store_id,
+       o.currency,
+       oi.book_id,
+       b.title,
+       b.author,
+       b.category,
+       SUM(oi.quantity)::bigint AS units,
+       SUM(oi.line_total)::bigint AS revenue
+FROM order_items oi
+JOIN orders o ON o.id = oi.order_id
+JOIN books b ON b.id = oi.book_id
+WHERE o.status IN ('paid', 'picking', 'shipped', 'delivered', 'refunded')
+GROUP BY 1, 2, 3, 4, 5, 6, 7;
+
+CREATE UNIQUE INDEX idx_report_book_sales_daily
+    ON report_book_sales_daily(sales_date, store_id, currency, book_id);
+
+-- Spend per day, store and customer, for the top customers report
+CREATE MATERIALIZED VIEW report_customer_sales_daily AS
+SELECT (o.created_at AT TIME ZONE 'Asia/Bangkok')::date AS sales_date,
+       o.store_id,
+       o.currency,
+       o.user_id,
+       COUNT(*) AS orders,
+       SUM(o.total)::bigint AS revenue
+FROM orders o
+WHERE o.status IN ('paid', 'picking', 'shipped', 'delivered', 'refunded')
+GROUP BY 1, 2, 3, 4;
+
+CREATE UNIQUE INDEX idx_report_customer_sales_daily
+    ON report_customer_sales_daily(sales_date, store_id, currency, user_id);
+
+-- When each view was last refreshed, so reports can say how fresh they are
+CREATE TABLE report_refreshes (
+    view_name VARCHAR(63) PRIMARY KEY,
+    refreshed_at TIMESTAMPTZ NOT NULL
+);
+
+INSERT INTO report_refreshes (view_name, refreshed_at) VALUES
+    ('report_sales_daily', NOW()),
+    ('report_book_sales_daily', NOW()),
+    ('report_customer_sales_daily', NOW());
@@ -0,0 +1,9 @@
+-- =====================================================
+-- ============ ONE OPEN PAYMENT PER ORDER =============
+-- =====================================================
+
+-- An attempt that is pending, waiting on 3-D Secure or authorized may still
+-- take money. Only one may be open per order, so two attempts started at
+-- the same time cannot both be charged.
+CREATE UNIQUE INDEX uq_payments_open_attempt ON payments(order_id)
+    WHERE status IN ('pending', 'requires_action', 'authorized');
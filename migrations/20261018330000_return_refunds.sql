@@ -0,0 +1,32 @@
+-- =====================================================
+-- ================== RETURN REFUNDS ===================
+-- =====================================================
+
+-- A refund is planned (status 'refunding') before any money moves, so a
+-- failed refund can be retried without paying a part twice.
+ALTER TABLE return_requests DROP CONSTRAINT return_requests_status_check;
+ALTER TABLE return_requests ADD CONSTRAINT return_requests_status_check CHECK (status IN (
+    'requested', 'approved', 'rejected', 'received', 'refunding', 'refunded'
+));
+
+-- The parts a return's refund is split into: one per order payment it goes
+-- back through, or a single store credit part (payment_id NULL).
+CREATE TABLE return_refunds (
+    id SERIAL PRIMARY KEY,
+    return_id INTEGER NOT NULL REFERENCES return_requests(id) ON DELETE RESTRICT,
+    payment_id INTEGER REFERENCES payments(id) ON DELETE RESTRICT,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX uq_return_refunds_payment ON return_refunds(return_id, payment_id)
+    WHERE payment_id IS NOT NULL;
+CREATE UNIQUE INDEX uq_return_refunds_store_credit ON return_refunds(return_id)
+    WHERE payment_id IS NULL;
+
+-- Back-office roles checked by the application (authorization.rs)
+INSERT INTO roles (name, description) VALUES
+    ('ADMIN', 'Full access, including every back-office workflow'),
+    ('STAFF', 'Runs back-office workflows: returns, fulfilment, catalogue')
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,10 @@
+-- Signed links that have used up a download. Only the first request on a
+-- link counts, whatever byte range it asks for; resumes on it are free.
+CREATE TABLE download_link_uses (
+    entitlement_id INTEGER NOT NULL REFERENCES entitlements(id) ON DELETE CASCADE,
+    asset_id INTEGER NOT NULL,
+    -- A link is identified by its file and expiry; the signature follows from them
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (entitlement_id, asset_id, expires_at)
+);
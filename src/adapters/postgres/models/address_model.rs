@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::address::AddressEntity,
+    value_objects::postal_address::PostalAddress,
+};
+
+// ======================
+// AddressModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AddressModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub label: Option<String>,
+    pub recipient_name: String,
+    pub phone: String,
+    pub line1: String,
+    pub line2: Option<String>,
+    pub subdistrict: Option<String>,
+    pub district: Option<String>,
+    pub province: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: String,
+    pub is_default_shipping: bool,
+    pub is_default_billing: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<AddressModel> for AddressEntity {
+    fn from(model: AddressModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            label: model.label,
+            // ไม่ validate ซ้ำ: กติกาอาจเปลี่ยนหลังจากบันทึกไปแล้ว
+            address: PostalAddress {
+                recipient_name: model.recipient_name,
+                phone: model.phone,
+                line1: model.line1,
+                line2: model.line2,
+                subdistrict: model.subdistrict,
+                district: model.district,
+                province: model.province,
+                postal_code: model.postal_code,
+                country: model.country,
+            },
+            is_default_shipping: model.is_default_shipping,
+            is_default_billing: model.is_default_billing,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::entities::book_metadata::{BookContributor, BookSubject, SubjectScheme};
+
+// ==================================
+// BookContributorModel / BookSubjectModel (SQLx)
+// ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BookContributorModel {
+    pub book_id: i32,
+    pub sequence: i32,
+    pub role: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BookSubjectModel {
+    pub book_id: i32,
+    pub scheme: String,
+    pub code: String,
+    pub heading: Option<String>,
+    pub is_main: bool,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<BookContributorModel> for BookContributor {
+    fn from(model: BookContributorModel) -> Self {
+        Self {
+            sequence: model.sequence,
+            role: model.role,
+            name: model.name,
+        }
+    }
+}
+
+impl From<BookSubjectModel> for BookSubject {
+    fn from(model: BookSubjectModel) -> Self {
+        Self {
+            scheme: model
+                .scheme
+                .parse::<SubjectScheme>()
+                .expect("Invalid subject scheme in database"),
+            code: model.code,
+            heading: model.heading,
+            is_main: model.is_main,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+
+use crate::domain::{
+    entities::book::BookEntity,
+    value_objects::{
+        book_cover::BookCover,
+        book_title::BookTitle,
+        dimensions::Dimensions,
+        isbn::Isbn,
+        money::{Currency, Money},
+    },
+};
+
+// ======================
+// BookModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BookModel {
+    pub id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub author: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub publisher: Option<String>,
+    pub language: Option<String>,
+    pub format: String,
+    pub price: i64,
+    pub currency: String,
+    pub tax_class: String,
+    pub weight_grams: Option<i32>,
+    pub length_mm: Option<i32>,
+    pub width_mm: Option<i32>,
+    pub height_mm: Option<i32>,
+    pub stock_quantity: i32,
+    pub release_date: Option<NaiveDate>,
+    pub preorder_enabled: bool,
+    pub rating_count: i32,
+    pub rating_total: i32,
+    pub cover: Option<Json<BookCover>>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<BookModel> for BookEntity {
+    fn from(model: BookModel) -> Self {
+        Self {
+            id: model.id,
+            isbn: Isbn::new(&model.isbn).expect("Invalid ISBN in database"),
+            title: BookTitle::new(model.title).expect("Invalid book title in database"),
+            author: model.author,
+            description: model.description,
+            category: model.category,
+            publisher: model.publisher,
+            language: model.language,
+            format: model.format.parse().expect("Invalid book format in database"),
+            price: Money::new(
+                model.price,
+                Currency::new(&model.currency).expect("Invalid currency in database"),
+            ),
+            tax_class: model.tax_class.parse().expect("Invalid tax class in database"),
+            weight_grams: model.weight_grams,
+            dimensions: match (model.length_mm, model.width_mm, model.height_mm) {
+                (Some(l), Some(w), Some(h)) => {
+                    Some(Dimensions::new(l, w, h).expect("Invalid dimensions in database"))
+                }
+                _ => None,
+            },
+            stock_quantity: model.stock_quantity,
+            release_date: model.release_date,
+            preorder_enabled: model.preorder_enabled,
+            rating_count: model.rating_count,
+            rating_total: model.rating_total,
+            cover: model.cover.map(|c| c.0),
+            is_active: model.is_active,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<BookEntity> for BookModel {
+    fn from(entity: BookEntity) -> Self {
+        Self {
+            id: entity.id,
+            isbn: entity.isbn.as_str().to_string(),
+            title: entity.title.as_str().to_string(),
+            author: entity.author,
+            description: entity.description,
+            category: entity.category,
+            publisher: entity.publisher,
+            language: entity.language,
+            format: entity.format.as_str().to_string(),
+            price: entity.price.amount(),
+            currency: entity.price.currency().as_str().to_string(),
+            tax_class: entity.tax_class.as_str().to_string(),
+            weight_grams: entity.weight_grams,
+            length_mm: entity.dimensions.map(|d| d.length_mm()),
+            width_mm: entity.dimensions.map(|d| d.width_mm()),
+            height_mm: entity.dimensions.map(|d| d.height_mm()),
+            stock_quantity: entity.stock_quantity,
+            release_date: entity.release_date,
+            preorder_enabled: entity.preorder_enabled,
+            rating_count: entity.rating_count,
+            rating_total: entity.rating_total,
+            cover: entity.cover.map(Json),
+            is_active: entity.is_active,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+/// A book row returned by full-text search.
+#[derive(Debug, Clone, FromRow)]
+pub struct BookSearchRowModel {
+    #[sqlx(flatten)]
+    pub book: BookModel,
+    pub rank: f32,
+    /// Matches across all pages (window count)
+    pub total_count: i64,
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::book_price::BookPriceEntity,
+    value_objects::money::{Currency, Money},
+};
+
+// =======================
+// BookPriceModel (SQLx)
+// =======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BookPriceModel {
+    pub id: i32,
+    pub book_id: i32,
+    pub store_id: Option<i32>,
+    pub currency: String,
+    pub amount: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<BookPriceModel> for BookPriceEntity {
+    fn from(model: BookPriceModel) -> Self {
+        Self {
+            id: model.id,
+            book_id: model.book_id,
+            store_id: model.store_id,
+            price: Money::new(
+                model.amount,
+                Currency::new(&model.currency).expect("Invalid currency in database"),
+            ),
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::cart::{CartEntity, CartItemEntity, CartOwner},
+    value_objects::{
+        cart_token::CartToken,
+        coupon_code::CouponCode,
+        quantity::Quantity,
+    },
+};
+
+// ======================
+// CartModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CartModel {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub token: Option<String>,
+    pub store_id: i32,
+    pub coupon_code: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CartItemModel {
+    pub id: i32,
+    pub cart_id: i32,
+    pub book_id: i32,
+    pub quantity: i32,
+    pub unit_price: i64,
+    pub added_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<CartItemModel> for CartItemEntity {
+    fn from(model: CartItemModel) -> Self {
+        Self {
+            id: model.id,
+            book_id: model.book_id,
+            quantity: Quantity::new(model.quantity).expect("Invalid cart quantity in database"),
+            unit_price: model.unit_price,
+            added_at: model.added_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl CartModel {
+    /// Builds the aggregate from the cart row and its line rows.
+    pub fn into_entity(self, items: Vec<CartItemModel>) -> CartEntity {
+        let owner = match (self.user_id, self.token) {
+            (Some(user_id), _) => CartOwner::User(user_id),
+            (None, Some(token)) => {
+                CartOwner::Guest(CartToken::new(token).expect("Invalid cart token in database"))
+            }
+            (None, None) => panic!("Cart without owner in database"),
+        };
+
+        CartEntity {
+            id: self.id,
+            owner,
+            store_id: self.store_id,
+            items: items.into_iter().map(CartItemEntity::from).collect(),
+            coupon_code: self
+                .coupon_code
+                .map(|c| CouponCode::new(c).expect("Invalid coupon code in database")),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
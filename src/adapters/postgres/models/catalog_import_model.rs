@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::{
+        catalog_import_job::{CatalogImportJobEntity, ImportFormat},
+        catalog_import_record::{CatalogImportRecordEntity, ImportOutcome},
+    },
+    value_objects::import_job_status::ImportJobStatus,
+};
+
+// ==================================
+// CatalogImportJobModel (SQLx)
+// ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CatalogImportJobModel {
+    pub id: i32,
+    pub format: String,
+    pub file_name: String,
+    pub sender: Option<String>,
+    pub status: String,
+    pub checkpoint_offset: i64,
+    pub records_processed: i32,
+    pub created_count: i32,
+    pub updated_count: i32,
+    pub rejected_count: i32,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub run_after: DateTime<Utc>,
+    pub requested_by: Option<i32>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CatalogImportRecordModel {
+    pub job_id: i32,
+    pub record_index: i32,
+    pub reference: Option<String>,
+    pub isbn: Option<String>,
+    pub book_id: Option<i32>,
+    pub outcome: String,
+    pub messages: Vec<String>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<CatalogImportJobModel> for CatalogImportJobEntity {
+    fn from(model: CatalogImportJobModel) -> Self {
+        Self {
+            id: model.id,
+            format: model
+                .format
+                .parse::<ImportFormat>()
+                .expect("Invalid import format in database"),
+            file_name: model.file_name,
+            sender: model.sender,
+            status: model
+                .status
+                .parse::<ImportJobStatus>()
+                .expect("Invalid import job status in database"),
+            checkpoint_offset: model.checkpoint_offset,
+            records_processed: model.records_processed,
+            created_count: model.created_count,
+            updated_count: model.updated_count,
+            rejected_count: model.rejected_count,
+            attempts: model.attempts,
+            last_error: model.last_error,
+            run_after: model.run_after,
+            requested_by: model.requested_by,
+            started_at: model.started_at,
+            finished_at: model.finished_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+impl From<CatalogImportRecordModel> for CatalogImportRecordEntity {
+    fn from(model: CatalogImportRecordModel) -> Self {
+        Self {
+            job_id: model.job_id,
+            record_index: model.record_index,
+            reference: model.reference,
+            isbn: model.isbn,
+            book_id: model.book_id,
+            outcome: model
+                .outcome
+                .parse::<ImportOutcome>()
+                .expect("Invalid import outcome in database"),
+            messages: model.messages,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::repositories::catalog_repository::{Facet, FacetCount};
+
+// ======================
+// FacetCountModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FacetCountModel {
+    pub facet: String,
+    pub value: String,
+    pub count: i64,
+}
+
+// ==================================
+// Mapping between Domain ↔ Model
+// ==================================
+
+impl From<FacetCountModel> for FacetCount {
+    fn from(model: FacetCountModel) -> Self {
+        Self {
+            facet: model.facet.parse::<Facet>().expect("Invalid facet from catalog query"),
+            value: model.value,
+            count: model.count,
+        }
+    }
+}
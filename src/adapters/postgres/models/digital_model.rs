@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::{
+        digital_asset::DigitalAssetEntity, download_log::DownloadLogEntity,
+        download_watermark::DownloadWatermarkEntity, entitlement::EntitlementEntity,
+    },
+    value_objects::watermark_code::WatermarkCode,
+};
+
+// ================================
+// DigitalAssetModel (SQLx)
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DigitalAssetModel {
+    pub id: i32,
+    pub book_id: i32,
+    pub file_format: String,
+    pub storage_key: String,
+    pub file_name: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ================================
+// EntitlementModel (SQLx)
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EntitlementModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub book_id: i32,
+    pub order_id: Option<i32>,
+    pub download_limit: Option<i32>,
+    pub downloads_used: i32,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoke_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ================================
+// DownloadLogModel (SQLx)
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DownloadLogModel {
+    pub id: i64,
+    pub entitlement_id: i32,
+    pub asset_id: i32,
+    pub user_id: i32,
+    pub outcome: String,
+    pub range_start: Option<i64>,
+    pub range_end: Option<i64>,
+    pub bytes_served: i64,
+    pub counted: bool,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ================================
+// DownloadWatermarkModel (SQLx)
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DownloadWatermarkModel {
+    pub id: i32,
+    pub code: String,
+    pub entitlement_id: Option<i32>,
+    pub asset_id: i32,
+    pub asset_sha256: String,
+    pub user_id: i32,
+    pub order_id: Option<i32>,
+    pub licensee: String,
+    pub storage_key: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<DigitalAssetModel> for DigitalAssetEntity {
+    fn from(model: DigitalAssetModel) -> Self {
+        Self {
+            id: model.id,
+            book_id: model.book_id,
+            file_format: model
+                .file_format
+                .parse()
+                .expect("Invalid digital file format in database"),
+            storage_key: model.storage_key,
+            file_name: model.file_name,
+            size_bytes: model.size_bytes,
+            sha256: model.sha256,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<EntitlementModel> for EntitlementEntity {
+    fn from(model: EntitlementModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            book_id: model.book_id,
+            order_id: model.order_id,
+            download_limit: model.download_limit,
+            downloads_used: model.downloads_used,
+            revoked_at: model.revoked_at,
+            revoke_reason: model.revoke_reason,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<DownloadLogModel> for DownloadLogEntity {
+    fn from(model: DownloadLogModel) -> Self {
+        Self {
+            id: model.id,
+            entitlement_id: model.entitlement_id,
+            asset_id: model.asset_id,
+            user_id: model.user_id,
+            outcome: model
+                .outcome
+                .parse()
+                .expect("Invalid download outcome in database"),
+            range_start: model.range_start,
+            range_end: model.range_end,
+            bytes_served: model.bytes_served,
+            counted: model.counted,
+            ip_address: model.ip_address,
+            user_agent: model.user_agent,
+            created_at: model.created_at,
+        }
+    }
+}
+
+impl From<DownloadWatermarkModel> for DownloadWatermarkEntity {
+    fn from(model: DownloadWatermarkModel) -> Self {
+        Self {
+            id: model.id,
+            code: WatermarkCode::new(model.code).expect("Invalid watermark code in database"),
+            entitlement_id: model.entitlement_id,
+            asset_id: model.asset_id,
+            asset_sha256: model.asset_sha256,
+            user_id: model.user_id,
+            order_id: model.order_id,
+            licensee: model.licensee,
+            storage_key: model.storage_key,
+            size_bytes: model.size_bytes,
+            created_at: model.created_at,
+        }
+    }
+}
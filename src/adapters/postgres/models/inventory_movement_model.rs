@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::entities::inventory_movement::InventoryMovementEntity;
+
+// ==================================
+// InventoryMovementModel (SQLx)
+// ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct InventoryMovementModel {
+    pub id: i32,
+    pub book_id: i32,
+    pub bucket: String,
+    pub quantity: i32,
+    pub reason: String,
+    pub reference: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<InventoryMovementModel> for InventoryMovementEntity {
+    fn from(model: InventoryMovementModel) -> Self {
+        Self {
+            id: model.id,
+            book_id: model.book_id,
+            bucket: model.bucket.parse().expect("Invalid stock bucket in database"),
+            quantity: model.quantity,
+            reason: model.reason.parse().expect("Invalid movement reason in database"),
+            reference: model.reference,
+            created_by: model.created_by,
+            created_at: model.created_at,
+        }
+    }
+}
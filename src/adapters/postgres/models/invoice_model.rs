@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+
+use crate::domain::{
+    entities::invoice::{CreditNoteReference, InvoiceEntity, InvoiceLine, InvoiceParty},
+    value_objects::{
+        money::Currency,
+        tax_id::{BranchCode, TaxId},
+    },
+};
+
+// ======================
+// InvoiceModel (SQLx)
+// ======================
+
+/// Everything but the stored document, which is only loaded for downloads
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct InvoiceModel {
+    pub id: i32,
+    pub kind: String,
+    pub number: String,
+    pub fiscal_year: i32,
+    pub sequence: i32,
+    pub order_id: i32,
+    pub user_id: i32,
+    pub original_invoice_id: Option<i32>,
+    pub original_number: Option<String>,
+    pub original_issued_at: Option<DateTime<Utc>>,
+    pub value_before: Option<i64>,
+    pub return_id: Option<i32>,
+    pub reason: Option<String>,
+    pub seller_name: String,
+    pub seller_tax_id: String,
+    pub seller_branch: String,
+    pub seller_address: String,
+    pub buyer_name: String,
+    pub buyer_tax_id: String,
+    pub buyer_branch: String,
+    pub buyer_address: String,
+    pub currency: String,
+    pub lines: Json<Vec<InvoiceLine>>,
+    pub net_amount: i64,
+    pub tax_amount: i64,
+    pub total: i64,
+    pub sha256: String,
+    pub size_bytes: i64,
+    pub issued_by: Option<i32>,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl From<InvoiceModel> for InvoiceEntity {
+    fn from(model: InvoiceModel) -> Self {
+        let reference = match (
+            model.original_invoice_id,
+            model.original_number,
+            model.original_issued_at,
+            model.value_before,
+        ) {
+            (Some(invoice_id), Some(number), Some(issued_at), Some(value_before)) => {
+                Some(CreditNoteReference {
+                    invoice_id,
+                    number,
+                    issued_at,
+                    value_before,
+                })
+            }
+            _ => None,
+        };
+
+        Self {
+            id: model.id,
+            kind: model.kind.parse().expect("Invalid invoice kind in database"),
+            number: model.number,
+            fiscal_year: model.fiscal_year,
+            sequence: model.sequence,
+            order_id: model.order_id,
+            user_id: model.user_id,
+            reference,
+            return_id: model.return_id,
+            seller: party(
+                model.seller_name,
+                model.seller_tax_id,
+                model.seller_branch,
+                model.seller_address,
+            ),
+            buyer: party(
+                model.buyer_name,
+                model.buyer_tax_id,
+                model.buyer_branch,
+                model.buyer_address,
+            ),
+            currency: Currency::new(&model.currency).expect("Invalid currency in database"),
+            lines: model.lines.0,
+            net_amount: model.net_amount,
+            tax_amount: model.tax_amount,
+            total: model.total,
+            reason: model.reason,
+            sha256: model.sha256,
+            size_bytes: model.size_bytes,
+            issued_by: model.issued_by,
+            issued_at: model.issued_at,
+        }
+    }
+}
+
+fn party(name: String, tax_id: String, branch: String, address: String) -> InvoiceParty {
+    InvoiceParty {
+        name,
+        tax_id: TaxId::new(tax_id).expect("Invalid tax ID in database"),
+        branch: BranchCode::new(branch).expect("Invalid branch in database"),
+        address,
+    }
+}
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::entities::{
+    loyalty_campaign::{LoyaltyCampaignEntity, LoyaltyCategoryMultiplier},
+    loyalty_entry::LoyaltyEntryEntity,
+};
+
+// ==================================
+// LoyaltyEntryModel (SQLx)
+// ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LoyaltyEntryModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub points: i64,
+    pub reason: String,
+    pub order_id: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<LoyaltyEntryModel> for LoyaltyEntryEntity {
+    fn from(model: LoyaltyEntryModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            points: model.points,
+            reason: model.reason.parse().expect("Invalid points reason in database"),
+            order_id: model.order_id,
+            expires_at: model.expires_at,
+            note: model.note,
+            created_by: model.created_by,
+            created_at: model.created_at,
+        }
+    }
+}
+
+// ==================================
+// LoyaltyCategoryMultiplierModel (SQLx)
+// ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LoyaltyCategoryMultiplierModel {
+    pub category: String,
+    pub multiplier_pct: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<LoyaltyCategoryMultiplierModel> for LoyaltyCategoryMultiplier {
+    fn from(model: LoyaltyCategoryMultiplierModel) -> Self {
+        Self {
+            category: model.category,
+            multiplier_pct: model.multiplier_pct,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+// ==================================
+// LoyaltyCampaignModel (SQLx)
+// ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LoyaltyCampaignModel {
+    pub id: i32,
+    pub name: String,
+    pub category: Option<String>,
+    pub multiplier_pct: i32,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub is_active: bool,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<LoyaltyCampaignModel> for LoyaltyCampaignEntity {
+    fn from(model: LoyaltyCampaignModel) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            category: model.category,
+            multiplier_pct: model.multiplier_pct,
+            starts_at: model.starts_at,
+            ends_at: model.ends_at,
+            is_active: model.is_active,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
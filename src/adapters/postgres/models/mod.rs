@@ -1,2 +1,27 @@
+pub mod address_model;
+pub mod book_metadata_model;
+pub mod book_model;
+pub mod book_price_model;
+pub mod cart_model;
+pub mod catalog_import_model;
+pub mod catalog_model;
+pub mod digital_model;
+pub mod inventory_movement_model;
+pub mod invoice_model;
+pub mod loyalty_model;
+pub mod notification_job_model;
+pub mod order_model;
+pub mod payment_model;
+pub mod promotion_model;
+pub mod report_model;
+pub mod return_request_model;
+pub mod review_model;
 pub mod role_model;
+pub mod search_feed_model;
+pub mod shipping_model;
+pub mod stock_subscription_model;
+pub mod store_model;
+pub mod stored_value_model;
+pub mod tax_rate_model;
 pub mod user_model;
+pub mod wishlist_model;
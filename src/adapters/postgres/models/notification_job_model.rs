@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+
+use crate::domain::{
+    entities::notification_job::{NotificationJobEntity, NotificationKind},
+    value_objects::notification_status::NotificationStatus,
+};
+
+// ==============================
+// NotificationJobModel (SQLx)
+// ==============================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NotificationJobModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: String,
+    pub payload: Json<serde_json::Value>,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub run_after: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<NotificationJobModel> for NotificationJobEntity {
+    fn from(model: NotificationJobModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            kind: model
+                .kind
+                .parse::<NotificationKind>()
+                .expect("Invalid notification kind in database"),
+            payload: model.payload.0,
+            status: model
+                .status
+                .parse::<NotificationStatus>()
+                .expect("Invalid notification status in database"),
+            attempts: model.attempts,
+            last_error: model.last_error,
+            run_after: model.run_after,
+            sent_at: model.sent_at,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+
+use crate::domain::{
+    entities::order::{
+        OrderEntity, OrderItemEntity, OrderPromotion, OrderShipping, OrderStatusChange,
+    },
+    value_objects::{
+        money::Currency,
+        order_status::OrderStatus,
+        postal_address::PostalAddress,
+        quantity::Quantity,
+    },
+};
+
+// ======================
+// OrderModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrderModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub store_id: i32,
+    pub status: String,
+    pub currency: String,
+    pub subtotal: i64,
+    pub discount_total: i64,
+    pub shipping_total: i64,
+    pub tax_total: i64,
+    pub total: i64,
+    pub shipping_method_id: Option<i32>,
+    pub shipping_method_name: Option<String>,
+    pub shipping_kind: Option<String>,
+    pub estimated_delivery_from: Option<NaiveDate>,
+    pub estimated_delivery_to: Option<NaiveDate>,
+    pub shipping_address: Option<Json<PostalAddress>>,
+    pub billing_address: Option<Json<PostalAddress>>,
+    pub tracking_number: Option<String>,
+    pub release_date: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrderItemModel {
+    pub id: i32,
+    pub order_id: i32,
+    pub book_id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub quantity: i32,
+    pub unit_price: i64,
+    pub price_includes_tax: bool,
+    pub tax_rate_bps: i32,
+    pub tax_amount: i64,
+    pub line_total: i64,
+    pub lowest_unit_price: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrderPromotionModel {
+    pub id: i32,
+    pub order_id: i32,
+    pub promotion_id: i32,
+    pub name: String,
+    pub coupon_id: Option<i32>,
+    pub coupon_code: Option<String>,
+    pub discount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrderStatusHistoryModel {
+    pub id: i32,
+    pub order_id: i32,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub changed_by: Option<i32>,
+    pub note: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<OrderItemModel> for OrderItemEntity {
+    fn from(model: OrderItemModel) -> Self {
+        Self {
+            id: model.id,
+            book_id: model.book_id,
+            isbn: model.isbn,
+            title: model.title,
+            quantity: Quantity::new(model.quantity).expect("Invalid order quantity in database"),
+            unit_price: model.unit_price,
+            price_includes_tax: model.price_includes_tax,
+            tax_rate_bps: model.tax_rate_bps,
+            tax_amount: model.tax_amount,
+            line_total: model.line_total,
+            lowest_unit_price: model.lowest_unit_price,
+        }
+    }
+}
+
+impl From<OrderPromotionModel> for OrderPromotion {
+    fn from(model: OrderPromotionModel) -> Self {
+        Self {
+            id: model.id,
+            promotion_id: model.promotion_id,
+            name: model.name,
+            coupon_id: model.coupon_id,
+            coupon_code: model.coupon_code,
+            discount: model.discount,
+        }
+    }
+}
+
+impl From<OrderStatusHistoryModel> for OrderStatusChange {
+    fn from(model: OrderStatusHistoryModel) -> Self {
+        Self {
+            id: model.id,
+            from_status: model
+                .from_status
+                .map(|s| s.parse().expect("Invalid order status in database")),
+            to_status: model.to_status.parse().expect("Invalid order status in database"),
+            changed_by: model.changed_by,
+            note: model.note,
+            changed_at: model.changed_at,
+        }
+    }
+}
+
+impl OrderModel {
+    /// Builds the aggregate from the order row, its lines, promotions and history.
+    pub fn into_entity(
+        self,
+        items: Vec<OrderItemModel>,
+        promotions: Vec<OrderPromotionModel>,
+        history: Vec<OrderStatusHistoryModel>,
+    ) -> OrderEntity {
+        let shipping = match (
+            self.shipping_method_name,
+            self.shipping_kind,
+            self.estimated_delivery_from,
+            self.estimated_delivery_to,
+        ) {
+            (Some(method_name), Some(kind), Some(estimated_from), Some(estimated_to)) => {
+                Some(OrderShipping {
+                    method_id: self.shipping_method_id,
+                    method_name,
+                    kind: kind.parse().expect("Invalid shipping method kind in database"),
+                    amount: self.shipping_total,
+                    estimated_from,
+                    estimated_to,
+                })
+            }
+            _ => None,
+        };
+
+        OrderEntity {
+            id: self.id,
+            user_id: self.user_id,
+            store_id: self.store_id,
+            status: self
+                .status
+                .parse::<OrderStatus>()
+                .expect("Invalid order status in database"),
+            currency: Currency::new(&self.currency).expect("Invalid currency in database"),
+            items: items.into_iter().map(OrderItemEntity::from).collect(),
+            subtotal: self.subtotal,
+            discount_total: self.discount_total,
+            shipping_total: self.shipping_total,
+            tax_total: self.tax_total,
+            total: self.total,
+            promotions: promotions.into_iter().map(OrderPromotion::from).collect(),
+            shipping,
+            shipping_address: self.shipping_address.map(|a| a.0),
+            billing_address: self.billing_address.map(|a| a.0),
+            tracking_number: self.tracking_number,
+            release_date: self.release_date,
+            history: history.into_iter().map(OrderStatusChange::from).collect(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
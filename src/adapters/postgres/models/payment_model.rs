@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::entities::payment::PaymentEntity;
+
+// ======================
+// PaymentModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PaymentModel {
+    pub id: i32,
+    pub order_id: i32,
+    pub provider: String,
+    pub provider_intent_id: Option<String>,
+    pub amount: i64,
+    pub currency: String,
+    pub status: String,
+    pub captured_amount: i64,
+    pub refunded_amount: i64,
+    pub next_action_url: Option<String>,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<PaymentModel> for PaymentEntity {
+    fn from(model: PaymentModel) -> Self {
+        Self {
+            id: model.id,
+            order_id: model.order_id,
+            provider: model.provider,
+            provider_intent_id: model.provider_intent_id,
+            amount: model.amount,
+            currency: model.currency,
+            status: model.status.parse().expect("Invalid payment status in database"),
+            captured_amount: model.captured_amount,
+            refunded_amount: model.refunded_amount,
+            next_action_url: model.next_action_url,
+            failure_reason: model.failure_reason,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<PaymentEntity> for PaymentModel {
+    fn from(entity: PaymentEntity) -> Self {
+        Self {
+            id: entity.id,
+            order_id: entity.order_id,
+            provider: entity.provider,
+            provider_intent_id: entity.provider_intent_id,
+            amount: entity.amount,
+            currency: entity.currency,
+            status: entity.status.as_str().to_string(),
+            captured_amount: entity.captured_amount,
+            refunded_amount: entity.refunded_amount,
+            next_action_url: entity.next_action_url,
+            failure_reason: entity.failure_reason,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
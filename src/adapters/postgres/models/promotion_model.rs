@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+
+use crate::domain::{
+    entities::{
+        coupon::CouponEntity,
+        promotion::{PromotionAction, PromotionCondition, PromotionEntity},
+    },
+    value_objects::coupon_code::CouponCode,
+};
+
+// ======================
+// PromotionModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PromotionModel {
+    pub id: i32,
+    pub name: String,
+    pub conditions: Json<Vec<PromotionCondition>>,
+    pub action: Json<PromotionAction>,
+    pub priority: i32,
+    pub stackable: bool,
+    pub requires_coupon: bool,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CouponModel {
+    pub id: i32,
+    pub code: String,
+    pub promotion_id: i32,
+    pub usage_limit: Option<i32>,
+    pub per_customer_limit: Option<i32>,
+    pub times_used: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<PromotionModel> for PromotionEntity {
+    fn from(model: PromotionModel) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            conditions: model.conditions.0,
+            action: model.action.0,
+            priority: model.priority,
+            stackable: model.stackable,
+            requires_coupon: model.requires_coupon,
+            is_active: model.is_active,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<CouponModel> for CouponEntity {
+    fn from(model: CouponModel) -> Self {
+        Self {
+            id: model.id,
+            code: CouponCode::new(model.code).expect("Invalid coupon code in database"),
+            promotion_id: model.promotion_id,
+            usage_limit: model.usage_limit,
+            per_customer_limit: model.per_customer_limit,
+            times_used: model.times_used,
+            is_active: model.is_active,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    repositories::report_repository::{CustomerSales, RevenuePeriod, SalesTotals, UnitsSold},
+    value_objects::money::Currency,
+};
+
+// ======================
+// SalesTotalsModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SalesTotalsModel {
+    pub currency: String,
+    pub orders: i64,
+    pub units: i64,
+    pub revenue: i64,
+    pub discount_total: i64,
+    pub shipping_total: i64,
+    pub tax_total: i64,
+    pub refunded_orders: i64,
+    pub refunded_amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RevenuePeriodModel {
+    pub period_start: NaiveDate,
+    #[sqlx(flatten)]
+    pub totals: SalesTotalsModel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UnitsSoldModel {
+    pub key: Option<String>,
+    pub book_id: Option<i32>,
+    pub currency: String,
+    pub units: i64,
+    pub revenue: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CustomerSalesModel {
+    pub user_id: i32,
+    pub email: String,
+    pub fname: String,
+    pub lname: String,
+    pub currency: String,
+    pub orders: i64,
+    pub revenue: i64,
+}
+
+// ==================================
+// Mapping between Domain ↔ Model
+// ==================================
+
+impl From<SalesTotalsModel> for SalesTotals {
+    fn from(model: SalesTotalsModel) -> Self {
+        Self {
+            currency: Currency::new(&model.currency).expect("Invalid currency in database"),
+            orders: model.orders,
+            units: model.units,
+            revenue: model.revenue,
+            discount_total: model.discount_total,
+            shipping_total: model.shipping_total,
+            tax_total: model.tax_total,
+            refunded_orders: model.refunded_orders,
+            refunded_amount: model.refunded_amount,
+        }
+    }
+}
+
+impl From<RevenuePeriodModel> for RevenuePeriod {
+    fn from(model: RevenuePeriodModel) -> Self {
+        Self {
+            period_start: model.period_start,
+            totals: model.totals.into(),
+        }
+    }
+}
+
+impl From<UnitsSoldModel> for UnitsSold {
+    fn from(model: UnitsSoldModel) -> Self {
+        Self {
+            key: model.key,
+            book_id: model.book_id,
+            currency: Currency::new(&model.currency).expect("Invalid currency in database"),
+            units: model.units,
+            revenue: model.revenue,
+        }
+    }
+}
+
+impl From<CustomerSalesModel> for CustomerSales {
+    fn from(model: CustomerSalesModel) -> Self {
+        Self {
+            user_id: model.user_id,
+            email: model.email,
+            first_name: model.fname,
+            last_name: model.lname,
+            currency: Currency::new(&model.currency).expect("Invalid currency in database"),
+            orders: model.orders,
+            revenue: model.revenue,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::return_request::{ReturnRefundEntity, ReturnRequestEntity},
+    value_objects::quantity::Quantity,
+};
+
+// ================================
+// ReturnRequestModel (SQLx)
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReturnRequestModel {
+    pub id: i32,
+    pub order_id: i32,
+    pub order_item_id: i32,
+    pub book_id: i32,
+    pub user_id: i32,
+    pub quantity: i32,
+    pub reason: String,
+    pub status: String,
+    pub reviewed_by: Option<i32>,
+    pub review_note: Option<String>,
+    pub condition: Option<String>,
+    pub refund_amount: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReturnRefundModel {
+    pub id: i32,
+    pub return_id: i32,
+    pub payment_id: Option<i32>,
+    pub amount: i64,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<ReturnRequestModel> for ReturnRequestEntity {
+    fn from(model: ReturnRequestModel) -> Self {
+        Self {
+            id: model.id,
+            order_id: model.order_id,
+            order_item_id: model.order_item_id,
+            book_id: model.book_id,
+            user_id: model.user_id,
+            quantity: Quantity::new(model.quantity).expect("Invalid quantity in database"),
+            reason: model.reason,
+            status: model.status.parse().expect("Invalid return status in database"),
+            reviewed_by: model.reviewed_by,
+            review_note: model.review_note,
+            condition: model
+                .condition
+                .map(|c| c.parse().expect("Invalid return condition in database")),
+            refund_amount: model.refund_amount,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<ReturnRefundModel> for ReturnRefundEntity {
+    fn from(model: ReturnRefundModel) -> Self {
+        Self {
+            id: model.id,
+            return_id: model.return_id,
+            payment_id: model.payment_id,
+            amount: model.amount,
+            completed_at: model.completed_at,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::review::ReviewEntity,
+    value_objects::rating::Rating,
+};
+
+// ======================
+// ReviewModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReviewModel {
+    pub id: i32,
+    pub book_id: i32,
+    pub user_id: i32,
+    pub order_id: Option<i32>,
+    pub rating: i32,
+    pub title: String,
+    pub body: String,
+    pub verified_purchase: bool,
+    pub status: String,
+    pub rejection_reason: Option<String>,
+    pub moderated_by: Option<i32>,
+    pub moderated_at: Option<DateTime<Utc>>,
+    pub helpful_count: i32,
+    pub unhelpful_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<ReviewModel> for ReviewEntity {
+    fn from(model: ReviewModel) -> Self {
+        Self {
+            id: model.id,
+            book_id: model.book_id,
+            user_id: model.user_id,
+            order_id: model.order_id,
+            rating: Rating::new(model.rating).expect("Invalid rating in database"),
+            title: model.title,
+            body: model.body,
+            verified_purchase: model.verified_purchase,
+            status: model.status.parse().expect("Invalid review status in database"),
+            rejection_reason: model.rejection_reason,
+            moderated_by: model.moderated_by,
+            moderated_at: model.moderated_at,
+            helpful_count: model.helpful_count,
+            unhelpful_count: model.unhelpful_count,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
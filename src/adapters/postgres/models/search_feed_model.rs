@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::repositories::search_feed_repository::{
+    BookChange, ChangeCursor, SearchDocument,
+};
+
+// ===========================
+// Search feed models (SQLx)
+// ===========================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BookChangeModel {
+    pub id: i64,
+    pub txid: i64,
+    pub book_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SearchDocumentModel {
+    pub id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub author: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub publisher: Option<String>,
+    pub stock_quantity: i32,
+    pub sales_count: i64,
+}
+
+// ==================================
+// Mapping between Domain ↔ Model
+// ==================================
+
+impl From<BookChangeModel> for BookChange {
+    fn from(model: BookChangeModel) -> Self {
+        Self {
+            cursor: ChangeCursor {
+                txid: model.txid,
+                id: model.id,
+            },
+            book_id: model.book_id,
+        }
+    }
+}
+
+impl From<SearchDocumentModel> for SearchDocument {
+    fn from(model: SearchDocumentModel) -> Self {
+        Self {
+            book_id: model.id,
+            isbn: model.isbn,
+            title: model.title,
+            author: model.author,
+            description: model.description,
+            category: model.category,
+            publisher: model.publisher,
+            in_stock: model.stock_quantity > 0,
+            sales_count: model.sales_count,
+        }
+    }
+}
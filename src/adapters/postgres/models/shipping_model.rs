@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::entities::shipping_zone::{
+    ShippingMethodEntity, ShippingRateEntity, ShippingZoneEntity,
+};
+
+// ==========================
+// Shipping models (SQLx)
+// ==========================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ShippingZoneModel {
+    pub id: i32,
+    pub name: String,
+    pub country: String,
+    pub postal_prefixes: Vec<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ShippingMethodModel {
+    pub id: i32,
+    pub zone_id: i32,
+    pub code: String,
+    pub name: String,
+    pub kind: String,
+    pub rate_basis: String,
+    pub min_days: i32,
+    pub max_days: i32,
+    pub max_weight_grams: Option<i32>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ShippingRateModel {
+    pub id: i32,
+    pub method_id: i32,
+    pub min_value: i64,
+    pub max_value: Option<i64>,
+    pub amount: i64,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<ShippingRateModel> for ShippingRateEntity {
+    fn from(model: ShippingRateModel) -> Self {
+        Self {
+            id: model.id,
+            min_value: model.min_value,
+            max_value: model.max_value,
+            amount: model.amount,
+        }
+    }
+}
+
+impl ShippingMethodModel {
+    pub fn into_entity(self, rates: Vec<ShippingRateModel>) -> ShippingMethodEntity {
+        ShippingMethodEntity {
+            id: self.id,
+            zone_id: self.zone_id,
+            code: self.code,
+            name: self.name,
+            kind: self.kind.parse().expect("Invalid shipping method kind in database"),
+            rate_basis: self.rate_basis.parse().expect("Invalid rate basis in database"),
+            min_days: self.min_days,
+            max_days: self.max_days,
+            max_weight_grams: self.max_weight_grams,
+            is_active: self.is_active,
+            rates: rates.into_iter().map(ShippingRateEntity::from).collect(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+impl ShippingZoneModel {
+    pub fn into_entity(self, methods: Vec<ShippingMethodEntity>) -> ShippingZoneEntity {
+        ShippingZoneEntity {
+            id: self.id,
+            name: self.name,
+            country: self.country,
+            postal_prefixes: self.postal_prefixes,
+            is_active: self.is_active,
+            methods,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
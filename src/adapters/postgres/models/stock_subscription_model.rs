@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::entities::stock_subscription::StockSubscriptionEntity;
+
+// ================================
+// StockSubscriptionModel (SQLx)
+// ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StockSubscriptionModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub book_id: i32,
+    pub notified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<StockSubscriptionModel> for StockSubscriptionEntity {
+    fn from(model: StockSubscriptionModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            book_id: model.book_id,
+            notified_at: model.notified_at,
+            created_at: model.created_at,
+        }
+    }
+}
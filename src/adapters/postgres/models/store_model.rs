@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::store::StoreEntity,
+    value_objects::money::Currency,
+};
+
+// ===================
+// StoreModel (SQLx)
+// ===================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StoreModel {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    pub currency: String,
+    pub jurisdiction: String,
+    pub prices_include_tax: bool,
+    pub is_default: bool,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<StoreModel> for StoreEntity {
+    fn from(model: StoreModel) -> Self {
+        Self {
+            id: model.id,
+            code: model.code,
+            name: model.name,
+            currency: Currency::new(&model.currency).expect("Invalid currency in database"),
+            jurisdiction: model.jurisdiction,
+            prices_include_tax: model.prices_include_tax,
+            is_default: model.is_default,
+            is_active: model.is_active,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::{
+        stored_value_account::StoredValueAccountEntity,
+        stored_value_entry::StoredValueEntryEntity,
+    },
+    value_objects::money::Currency,
+};
+
+// ==================================
+// StoredValueAccountModel (SQLx)
+// ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StoredValueAccountModel {
+    pub id: i32,
+    pub kind: String,
+    pub code_hash: Option<String>,
+    pub code_last4: Option<String>,
+    pub user_id: Option<i32>,
+    pub currency: String,
+    pub balance: i64,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<StoredValueAccountModel> for StoredValueAccountEntity {
+    fn from(model: StoredValueAccountModel) -> Self {
+        Self {
+            id: model.id,
+            kind: model.kind.parse().expect("Invalid stored value kind in database"),
+            code_hash: model.code_hash,
+            code_last4: model.code_last4,
+            user_id: model.user_id,
+            currency: Currency::new(&model.currency).expect("Invalid currency in database"),
+            balance: model.balance,
+            expires_at: model.expires_at,
+            created_by: model.created_by,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+// ==================================
+// StoredValueEntryModel (SQLx)
+// ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StoredValueEntryModel {
+    pub id: i32,
+    pub account_id: i32,
+    pub amount: i64,
+    pub reason: String,
+    pub order_id: Option<i32>,
+    pub note: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoredValueEntryModel> for StoredValueEntryEntity {
+    fn from(model: StoredValueEntryModel) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            amount: model.amount,
+            reason: model.reason.parse().expect("Invalid stored value entry reason in database"),
+            order_id: model.order_id,
+            note: model.note,
+            created_by: model.created_by,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::entities::tax_rate::TaxRateEntity;
+
+// =====================
+// TaxRateModel (SQLx)
+// =====================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaxRateModel {
+    pub id: i32,
+    pub jurisdiction: String,
+    pub tax_class: String,
+    pub name: String,
+    pub rate_bps: i32,
+    pub valid_from: DateTime<Utc>,
+    pub valid_to: Option<DateTime<Utc>>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<TaxRateModel> for TaxRateEntity {
+    fn from(model: TaxRateModel) -> Self {
+        Self {
+            id: model.id,
+            jurisdiction: model.jurisdiction,
+            tax_class: model.tax_class.parse().expect("Invalid tax class in database"),
+            name: model.name,
+            rate_bps: model.rate_bps,
+            valid_from: model.valid_from,
+            valid_to: model.valid_to,
+        }
+    }
+}
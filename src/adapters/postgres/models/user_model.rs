@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{types::Json, FromRow};
 
 use crate::domain::{
     entities::user::UserEntity,
@@ -10,6 +10,7 @@ use crate::domain::{
         password::Password,
         person_name::PersonName,
         phone_number::PhoneNumber,
+        user_avatar::UserAvatar,
     },
 };
 
@@ -27,6 +28,7 @@ pub struct UserModel {
     pub sex: String,
     pub phone: String,
     pub password: String,
+    pub avatar: Option<Json<UserAvatar>>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -47,6 +49,7 @@ impl From<UserModel> for UserEntity {
             sex: model.sex,
             phone: PhoneNumber::new(model.phone).expect("Invalid phone in database"),
             password: Password::new(model.password).expect("Invalid password in database"),
+            avatar: model.avatar.map(|a| a.0),
             is_active: model.is_active,
             created_at: model.created_at,
             updated_at: model.updated_at,
@@ -65,6 +68,7 @@ impl From<UserEntity> for UserModel {
             sex: entity.sex,
             phone: entity.phone.as_str().to_string(),
             password: entity.password.as_str().to_string(),
+            avatar: entity.avatar.map(Json),
             is_active: entity.is_active,
             created_at: entity.created_at,
             updated_at: entity.updated_at,
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{
+    entities::wishlist::{WishlistEntity, WishlistItemEntity},
+    value_objects::share_token::ShareToken,
+};
+
+// ======================
+// WishlistModel (SQLx)
+// ======================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WishlistModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub share_token: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WishlistItemModel {
+    pub wishlist_id: i32,
+    pub book_id: i32,
+    pub note: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+// ==================================
+// Mapping between Entity ↔ Model
+// ==================================
+
+impl From<WishlistItemModel> for WishlistItemEntity {
+    fn from(model: WishlistItemModel) -> Self {
+        Self {
+            book_id: model.book_id,
+            note: model.note,
+            added_at: model.added_at,
+        }
+    }
+}
+
+impl WishlistModel {
+    /// Builds the aggregate from the wishlist row and its item rows.
+    pub fn into_entity(self, items: Vec<WishlistItemModel>) -> WishlistEntity {
+        WishlistEntity {
+            id: self.id,
+            user_id: self.user_id,
+            name: self.name,
+            share_token: self
+                .share_token
+                .map(|t| ShareToken::new(t).expect("Invalid share token in database")),
+            items: items.into_iter().map(WishlistItemEntity::from).collect(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
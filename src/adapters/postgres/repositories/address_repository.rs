@@ -0,0 +1,173 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::address::AddressEntity,
+    repositories::address_repository::AddressRepository,
+};
+use crate::adapters::postgres::models::address_model::AddressModel;
+
+const ADDRESS_COLUMNS: &str = "id, user_id, label, recipient_name, phone, line1, line2, \
+                               subdistrict, district, province, postal_code, country, \
+                               is_default_shipping, is_default_billing, created_at, updated_at";
+
+pub struct PostgresAddressRepository {
+    pool: PgPool,
+}
+
+impl PostgresAddressRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// A user has at most one default of each kind
+    async fn clear_other_defaults(
+        tx: &mut Transaction<'_, Postgres>,
+        address_id: i32,
+        address: &AddressEntity,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE addresses
+            SET
+                is_default_shipping = is_default_shipping AND NOT $3,
+                is_default_billing = is_default_billing AND NOT $4
+            WHERE user_id = $1 AND id <> $2
+            "#,
+        )
+        .bind(address.user_id)
+        .bind(address_id)
+        .bind(address.is_default_shipping)
+        .bind(address.is_default_billing)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AddressRepository for PostgresAddressRepository {
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<AddressEntity>> {
+        let results = sqlx::query_as::<_, AddressModel>(&format!(
+            "SELECT {} FROM addresses WHERE user_id = $1 ORDER BY created_at ASC, id ASC",
+            ADDRESS_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(AddressEntity::from).collect())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<AddressEntity>> {
+        let result = sqlx::query_as::<_, AddressModel>(&format!(
+            "SELECT {} FROM addresses WHERE id = $1",
+            ADDRESS_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(AddressEntity::from))
+    }
+
+    async fn save(&self, address: &AddressEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+
+        // ล้าง default เดิมก่อน insert เพื่อไม่ให้ชน unique index
+        Self::clear_other_defaults(&mut tx, 0, address).await?;
+
+        let a = &address.address;
+        let row = sqlx::query(
+            r#"
+            INSERT INTO addresses
+                (user_id, label, recipient_name, phone, line1, line2, subdistrict, district,
+                 province, postal_code, country, is_default_shipping, is_default_billing,
+                 created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            RETURNING id
+            "#,
+        )
+        .bind(address.user_id)
+        .bind(&address.label)
+        .bind(&a.recipient_name)
+        .bind(&a.phone)
+        .bind(&a.line1)
+        .bind(&a.line2)
+        .bind(&a.subdistrict)
+        .bind(&a.district)
+        .bind(&a.province)
+        .bind(&a.postal_code)
+        .bind(&a.country)
+        .bind(address.is_default_shipping)
+        .bind(address.is_default_billing)
+        .bind(address.created_at)
+        .bind(address.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn update(&self, address: &AddressEntity) -> Result<AddressEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        Self::clear_other_defaults(&mut tx, address.id, address).await?;
+
+        let a = &address.address;
+        let result = sqlx::query_as::<_, AddressModel>(&format!(
+            r#"
+            UPDATE addresses
+            SET
+                label = $1,
+                recipient_name = $2,
+                phone = $3,
+                line1 = $4,
+                line2 = $5,
+                subdistrict = $6,
+                district = $7,
+                province = $8,
+                postal_code = $9,
+                country = $10,
+                is_default_shipping = $11,
+                is_default_billing = $12,
+                updated_at = $13
+            WHERE id = $14
+            RETURNING {}
+            "#,
+            ADDRESS_COLUMNS
+        ))
+        .bind(&address.label)
+        .bind(&a.recipient_name)
+        .bind(&a.phone)
+        .bind(&a.line1)
+        .bind(&a.line2)
+        .bind(&a.subdistrict)
+        .bind(&a.district)
+        .bind(&a.province)
+        .bind(&a.postal_code)
+        .bind(&a.country)
+        .bind(address.is_default_shipping)
+        .bind(address.is_default_billing)
+        .bind(address.updated_at)
+        .bind(address.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(AddressEntity::from(result))
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM addresses WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
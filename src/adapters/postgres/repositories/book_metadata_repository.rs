@@ -0,0 +1,54 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::domain::{
+    entities::book_metadata::{BookContributor, BookSubject},
+    repositories::book_metadata_repository::BookMetadataRepository,
+};
+use crate::adapters::postgres::models::book_metadata_model::{BookContributorModel, BookSubjectModel};
+
+pub struct PostgresBookMetadataRepository {
+    pool: PgPool,
+}
+
+impl PostgresBookMetadataRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BookMetadataRepository for PostgresBookMetadataRepository {
+    async fn find_contributors(&self, book_id: i32) -> Result<Vec<BookContributor>> {
+        let results = sqlx::query_as::<_, BookContributorModel>(
+            r#"
+            SELECT book_id, sequence, role, name
+            FROM book_contributors
+            WHERE book_id = $1
+            ORDER BY sequence ASC
+            "#,
+        )
+        .bind(book_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(BookContributor::from).collect())
+    }
+
+    async fn find_subjects(&self, book_id: i32) -> Result<Vec<BookSubject>> {
+        let results = sqlx::query_as::<_, BookSubjectModel>(
+            r#"
+            SELECT book_id, scheme, code, heading, is_main
+            FROM book_subjects
+            WHERE book_id = $1
+            ORDER BY is_main DESC, scheme ASC, code ASC
+            "#,
+        )
+        .bind(book_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(BookSubject::from).collect())
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::domain::{
+    entities::book_price::BookPriceEntity,
+    repositories::book_price_repository::BookPriceRepository,
+};
+use crate::adapters::postgres::models::book_price_model::BookPriceModel;
+
+pub struct PostgresBookPriceRepository {
+    pool: PgPool,
+}
+
+impl PostgresBookPriceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Replaces a price inside an existing transaction
+    pub(crate) async fn upsert_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        price: &BookPriceEntity,
+    ) -> Result<BookPriceEntity> {
+        // NULL store_id ไม่ชนกันใน UNIQUE จึงลบแถวเดิมก่อนแล้วค่อย insert
+        sqlx::query(
+            r#"
+            DELETE FROM book_prices
+            WHERE book_id = $1 AND store_id IS NOT DISTINCT FROM $2 AND currency = $3
+            "#,
+        )
+        .bind(price.book_id)
+        .bind(price.store_id)
+        .bind(price.price.currency().as_str())
+        .execute(&mut **tx)
+        .await?;
+
+        let result = sqlx::query_as::<_, BookPriceModel>(
+            r#"
+            INSERT INTO book_prices (book_id, store_id, currency, amount, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, book_id, store_id, currency, amount, updated_at
+            "#,
+        )
+        .bind(price.book_id)
+        .bind(price.store_id)
+        .bind(price.price.currency().as_str())
+        .bind(price.price.amount())
+        .bind(price.updated_at)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(BookPriceEntity::from(result))
+    }
+}
+
+#[async_trait]
+impl BookPriceRepository for PostgresBookPriceRepository {
+    async fn find_by_books(&self, book_ids: &[i32]) -> Result<Vec<BookPriceEntity>> {
+        let results = sqlx::query_as::<_, BookPriceModel>(
+            r#"
+            SELECT id, book_id, store_id, currency, amount, updated_at
+            FROM book_prices
+            WHERE book_id = ANY($1)
+            "#,
+        )
+        .bind(book_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(BookPriceEntity::from).collect())
+    }
+
+    async fn upsert(&self, price: &BookPriceEntity) -> Result<BookPriceEntity> {
+        let mut tx = self.pool.begin().await?;
+        let result = Self::upsert_in_tx(&mut tx, price).await?;
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM book_prices WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{types::Json, PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        inventory_movement::{InventoryMovementEntity, MovementReason},
+    },
+    repositories::book_repository::BookRepository,
+    value_objects::{book_cover::BookCover, stock_bucket::StockBucket},
+};
+use crate::adapters::postgres::{
+    models::book_model::BookModel,
+    repositories::inventory_repository::PostgresInventoryRepository,
+};
+
+pub(crate) const BOOK_COLUMNS: &str = "id, isbn, title, author, description, category, publisher, \
+                                        language, format, price, currency, tax_class, weight_grams, \
+                                        length_mm, width_mm, height_mm, stock_quantity, \
+                                        release_date, preorder_enabled, rating_count, rating_total, cover, is_active, created_at, updated_at";
+
+pub struct PostgresBookRepository {
+    pool: PgPool,
+}
+
+impl PostgresBookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Inserts a book inside an existing transaction, opening balance included
+    pub(crate) async fn insert_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        book: &BookEntity,
+    ) -> Result<i32> {
+        // stock เริ่มต้นที่ 0 แล้วลงยอดยกมาผ่าน ledger เพื่อให้ยอดตรงกันเสมอ
+        let row = sqlx::query(
+            r#"
+            INSERT INTO books
+                (isbn, title, author, description, category, publisher, language, format,
+                 price, currency, tax_class, weight_grams, length_mm, width_mm, height_mm,
+                 stock_quantity, release_date, preorder_enabled, is_active, created_at,
+                 updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15,
+                 0, $16, $17, $18, $19, $20)
+            RETURNING id
+            "#,
+        )
+        .bind(book.isbn.as_str())
+        .bind(book.title.as_str())
+        .bind(&book.author)
+        .bind(&book.description)
+        .bind(&book.category)
+        .bind(&book.publisher)
+        .bind(&book.language)
+        .bind(book.format.as_str())
+        .bind(book.price.amount())
+        .bind(book.price.currency().as_str())
+        .bind(book.tax_class.as_str())
+        .bind(book.weight_grams)
+        .bind(book.dimensions.map(|d| d.length_mm()))
+        .bind(book.dimensions.map(|d| d.width_mm()))
+        .bind(book.dimensions.map(|d| d.height_mm()))
+        .bind(book.release_date)
+        .bind(book.preorder_enabled)
+        .bind(book.is_active)
+        .bind(book.created_at)
+        .bind(book.updated_at)
+        .fetch_one(&mut **tx)
+        .await?;
+        let book_id: i32 = row.try_get("id")?;
+
+        if book.stock_quantity > 0 {
+            let opening = InventoryMovementEntity::new(
+                book_id,
+                StockBucket::Available,
+                book.stock_quantity,
+                MovementReason::OpeningBalance,
+                None,
+                None,
+            )?;
+            PostgresInventoryRepository::record_in_tx(tx, &opening).await?;
+        }
+
+        Ok(book_id)
+    }
+
+    pub(crate) async fn update_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        book: &BookEntity,
+    ) -> Result<BookEntity> {
+        let result = sqlx::query_as::<_, BookModel>(&format!(
+            r#"
+            UPDATE books
+            SET
+                isbn = $1,
+                title = $2,
+                author = $3,
+                description = $4,
+                category = $5,
+                publisher = $6,
+                language = $7,
+                format = $8,
+                price = $9,
+                currency = $10,
+                tax_class = $11,
+                weight_grams = $12,
+                length_mm = $13,
+                width_mm = $14,
+                height_mm = $15,
+                release_date = $16,
+                preorder_enabled = $17,
+                is_active = $18,
+                updated_at = $19
+            WHERE id = $20
+            RETURNING {}
+            "#,
+            BOOK_COLUMNS
+        ))
+        .bind(book.isbn.as_str())
+        .bind(book.title.as_str())
+        .bind(&book.author)
+        .bind(&book.description)
+        .bind(&book.category)
+        .bind(&book.publisher)
+        .bind(&book.language)
+        .bind(book.format.as_str())
+        .bind(book.price.amount())
+        .bind(book.price.currency().as_str())
+        .bind(book.tax_class.as_str())
+        .bind(book.weight_grams)
+        .bind(book.dimensions.map(|d| d.length_mm()))
+        .bind(book.dimensions.map(|d| d.width_mm()))
+        .bind(book.dimensions.map(|d| d.height_mm()))
+        .bind(book.release_date)
+        .bind(book.preorder_enabled)
+        .bind(book.is_active)
+        .bind(book.updated_at)
+        .bind(book.id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(BookEntity::from(result))
+    }
+}
+
+#[async_trait]
+impl BookRepository for PostgresBookRepository {
+    async fn find_all(&self) -> Result<Vec<BookEntity>> {
+        let results = sqlx::query_as::<_, BookModel>(&format!(
+            "SELECT {} FROM books ORDER BY id ASC",
+            BOOK_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(BookEntity::from).collect())
+    }
+
+    async fn find_after(&self, after_id: i32, limit: i64) -> Result<Vec<BookEntity>> {
+        let results = sqlx::query_as::<_, BookModel>(&format!(
+            "SELECT {} FROM books WHERE id > $1 ORDER BY id ASC LIMIT $2",
+            BOOK_COLUMNS
+        ))
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(BookEntity::from).collect())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<BookEntity>> {
+        let result = sqlx::query_as::<_, BookModel>(&format!(
+            "SELECT {} FROM books WHERE id = $1",
+            BOOK_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(BookEntity::from))
+    }
+
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<BookEntity>> {
+        let results = sqlx::query_as::<_, BookModel>(&format!(
+            "SELECT {} FROM books WHERE id = ANY($1) ORDER BY id ASC",
+            BOOK_COLUMNS
+        ))
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(BookEntity::from).collect())
+    }
+
+    async fn find_by_isbn(&self, isbn: &str) -> Result<Option<BookEntity>> {
+        let result = sqlx::query_as::<_, BookModel>(&format!(
+            "SELECT {} FROM books WHERE isbn = $1",
+            BOOK_COLUMNS
+        ))
+        .bind(isbn)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(BookEntity::from))
+    }
+
+    async fn save(&self, book: &BookEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+        let book_id = Self::insert_in_tx(&mut tx, book).await?;
+        tx.commit().await?;
+
+        Ok(book_id)
+    }
+
+    async fn update(&self, book: &BookEntity) -> Result<BookEntity> {
+        let mut tx = self.pool.begin().await?;
+        let updated = Self::update_in_tx(&mut tx, book).await?;
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    async fn update_cover(&self, book_id: i32, cover: Option<&BookCover>) -> Result<Option<BookCover>> {
+        // อ่านปกเดิมในคำสั่งเดียวกัน ผู้เรียกจะได้ลบไฟล์เก่าได้ถูกชุด
+        let previous: Option<Option<Json<BookCover>>> = sqlx::query_scalar(
+            r#"
+            UPDATE books b
+            SET cover = $2, updated_at = NOW()
+            FROM (SELECT id, cover FROM books WHERE id = $1 FOR UPDATE) old
+            WHERE b.id = old.id
+            RETURNING old.cover
+            "#,
+        )
+        .bind(book_id)
+        .bind(cover.map(Json))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match previous {
+            Some(previous) => Ok(previous.map(|c| c.0)),
+            None => Err(anyhow::anyhow!("Book not found")),
+        }
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM books WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
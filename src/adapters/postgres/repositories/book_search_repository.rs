@@ -0,0 +1,71 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::domain::{
+    entities::book::BookEntity,
+    repositories::book_search_repository::{BookSearchHit, BookSearchPage, BookSearchRepository},
+};
+use crate::adapters::postgres::{
+    models::book_model::BookSearchRowModel,
+    repositories::book_repository::BOOK_COLUMNS,
+};
+
+pub struct PostgresBookSearchRepository {
+    pool: PgPool,
+}
+
+impl PostgresBookSearchRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BookSearchRepository for PostgresBookSearchRepository {
+    async fn search(&self, tsquery: &str, offset: i64, limit: i64) -> Result<BookSearchPage> {
+        let rows = sqlx::query_as::<_, BookSearchRowModel>(&format!(
+            r#"
+            SELECT {},
+                   ts_rank(search_vector, q) AS rank,
+                   COUNT(*) OVER () AS total_count
+            FROM books, CAST($1 AS tsquery) AS q
+            WHERE is_active AND search_vector @@ q
+            ORDER BY rank DESC, id ASC
+            OFFSET $2
+            LIMIT $3
+            "#,
+            BOOK_COLUMNS
+        ))
+        .bind(tsquery)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        // หน้าเกินจำนวนผลลัพธ์จะไม่มีแถวกลับมา จึงต้องนับแยก
+        let total = match rows.first() {
+            Some(r) => r.total_count,
+            None if offset > 0 => {
+                sqlx::query_scalar::<_, i64>(
+                    "SELECT COUNT(*) FROM books WHERE is_active AND search_vector @@ CAST($1 AS tsquery)",
+                )
+                .bind(tsquery)
+                .fetch_one(&self.pool)
+                .await?
+            }
+            None => 0,
+        };
+
+        Ok(BookSearchPage {
+            hits: rows
+                .into_iter()
+                .map(|r| BookSearchHit {
+                    book: BookEntity::from(r.book),
+                    rank: r.rank,
+                })
+                .collect(),
+            total,
+        })
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::domain::{
+    entities::{book::BookEntity, user::UserEntity},
+    repositories::bulk_import_repository::BulkImportRepository,
+};
+use crate::adapters::postgres::repositories::{
+    book_repository::PostgresBookRepository, user_repository::PostgresUserRepository,
+};
+
+pub struct PostgresBulkImportRepository {
+    pool: PgPool,
+}
+
+impl PostgresBulkImportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BulkImportRepository for PostgresBulkImportRepository {
+    async fn apply_books(&self, books: &[BookEntity]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for book in books {
+            // ผิดแถวเดียว rollback ทั้งไฟล์ บอกว่าพังที่ ISBN ไหน
+            let result = if book.id == 0 {
+                PostgresBookRepository::insert_in_tx(&mut tx, book).await.map(|_| ())
+            } else {
+                PostgresBookRepository::update_in_tx(&mut tx, book).await.map(|_| ())
+            };
+            result.map_err(|e| anyhow!("ISBN {}: {}", book.isbn.as_str(), e))?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn apply_users(&self, users: &[UserEntity]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for user in users {
+            let result = if user.id == 0 {
+                PostgresUserRepository::insert_in_tx(&mut tx, user).await.map(|_| ())
+            } else {
+                PostgresUserRepository::update_in_tx(&mut tx, user).await.map(|_| ())
+            };
+            result.map_err(|e| anyhow!("{}: {}", user.email.as_str(), e))?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::cart::{CartEntity, CartOwner},
+    repositories::cart_repository::CartRepository,
+    value_objects::cart_token::CartToken,
+};
+use crate::adapters::postgres::models::cart_model::{CartItemModel, CartModel};
+
+pub struct PostgresCartRepository {
+    pool: PgPool,
+}
+
+impl PostgresCartRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn load_items(&self, cart: CartModel) -> Result<CartEntity> {
+        let items = sqlx::query_as::<_, CartItemModel>(
+            r#"
+            SELECT id, cart_id, book_id, quantity, unit_price, added_at, updated_at
+            FROM cart_items
+            WHERE cart_id = $1
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(cart.id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(cart.into_entity(items))
+    }
+
+    async fn insert_items(
+        tx: &mut Transaction<'_, Postgres>,
+        cart_id: i32,
+        cart: &CartEntity,
+    ) -> Result<()> {
+        for item in &cart.items {
+            sqlx::query(
+                r#"
+                INSERT INTO cart_items
+                    (cart_id, book_id, quantity, unit_price, added_at, updated_at)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(cart_id)
+            .bind(item.book_id)
+            .bind(item.quantity.value())
+            .bind(item.unit_price)
+            .bind(item.added_at)
+            .bind(item.updated_at)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Cart row and its lines, replaced as a whole
+    async fn update_in_tx(tx: &mut Transaction<'_, Postgres>, cart: &CartEntity) -> Result<CartModel> {
+        let (user_id, token) = owner_columns(&cart.owner);
+        let model = sqlx::query_as::<_, CartModel>(
+            r#"
+            UPDATE carts
+            SET
+                user_id = $1,
+                token = $2,
+                store_id = $3,
+                coupon_code = $4,
+                updated_at = $5
+            WHERE id = $6
+            RETURNING id, user_id, token, store_id, coupon_code, created_at, updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(token)
+        .bind(cart.store_id)
+        .bind(cart.coupon_code.as_ref().map(|c| c.as_str()))
+        .bind(cart.updated_at)
+        .bind(cart.id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        // ลบ line เดิมทั้งหมดแล้วเขียนใหม่ตาม state ล่าสุดของ aggregate
+        sqlx::query("DELETE FROM cart_items WHERE cart_id = $1")
+            .bind(cart.id)
+            .execute(&mut **tx)
+            .await?;
+        Self::insert_items(tx, cart.id, cart).await?;
+
+        Ok(model)
+    }
+}
+
+fn owner_columns(owner: &CartOwner) -> (Option<i32>, Option<&str>) {
+    match owner {
+        CartOwner::User(user_id) => (Some(*user_id), None),
+        CartOwner::Guest(token) => (None, Some(token.as_str())),
+    }
+}
+
+#[async_trait]
+impl CartRepository for PostgresCartRepository {
+    async fn find_by_user(&self, user_id: i32) -> Result<Option<CartEntity>> {
+        let cart = sqlx::query_as::<_, CartModel>(
+            r#"
+            SELECT id, user_id, token, store_id, coupon_code, created_at, updated_at
+            FROM carts
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match cart {
+            Some(c) => Ok(Some(self.load_items(c).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_token(&self, token: &CartToken) -> Result<Option<CartEntity>> {
+        let cart = sqlx::query_as::<_, CartModel>(
+            r#"
+            SELECT id, user_id, token, store_id, coupon_code, created_at, updated_at
+            FROM carts
+            WHERE token = $1
+            "#,
+        )
+        .bind(token.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match cart {
+            Some(c) => Ok(Some(self.load_items(c).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn save(&self, cart: &CartEntity) -> Result<i32> {
+        let (user_id, token) = owner_columns(&cart.owner);
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO carts (user_id, token, store_id, coupon_code, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(user_id)
+        .bind(token)
+        .bind(cart.store_id)
+        .bind(cart.coupon_code.as_ref().map(|c| c.as_str()))
+        .bind(cart.created_at)
+        .bind(cart.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        let cart_id: i32 = row.try_get("id")?;
+
+        Self::insert_items(&mut tx, cart_id, cart).await?;
+        tx.commit().await?;
+
+        Ok(cart_id)
+    }
+
+    async fn update(&self, cart: &CartEntity) -> Result<CartEntity> {
+        let mut tx = self.pool.begin().await?;
+        let model = Self::update_in_tx(&mut tx, cart).await?;
+        tx.commit().await?;
+
+        self.load_items(model).await
+    }
+
+    async fn update_merged(&self, cart: &CartEntity, guest_cart_id: i32) -> Result<CartEntity> {
+        let mut tx = self.pool.begin().await?;
+        let model = Self::update_in_tx(&mut tx, cart).await?;
+
+        // login สองที่พร้อมกัน: ใครลบตะกร้า guest ไม่ได้แปลว่าอีกฝ่าย merge ไปแล้ว
+        let deleted = sqlx::query("DELETE FROM carts WHERE id = $1 AND user_id IS NULL")
+            .bind(guest_cart_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if deleted == 0 {
+            return Err(anyhow!("Guest cart {} was already merged", guest_cart_id));
+        }
+        tx.commit().await?;
+
+        self.load_items(model).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM carts WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::domain::{
+    entities::{
+        book_price::BookPriceEntity,
+        catalog_import_job::CatalogImportJobEntity,
+        catalog_import_record::{CatalogImportRecordEntity, ImportOutcome},
+        catalog_record::ImportedBook,
+    },
+    repositories::catalog_import_repository::CatalogImportRepository,
+};
+use crate::adapters::postgres::{
+    models::catalog_import_model::{CatalogImportJobModel, CatalogImportRecordModel},
+    repositories::{
+        book_price_repository::PostgresBookPriceRepository,
+        book_repository::PostgresBookRepository,
+    },
+};
+
+const JOB_COLUMNS: &str = "id, format, file_name, sender, status, checkpoint_offset, \
+                           records_processed, created_count, updated_count, rejected_count, \
+                           attempts, last_error, run_after, requested_by, started_at, \
+                           finished_at, created_at";
+
+const RECORD_COLUMNS: &str = "job_id, record_index, reference, isbn, book_id, outcome, messages";
+
+/// How long a claimed job stays invisible to other workers; every
+/// checkpoint renews it
+const CLAIM_LEASE_SECONDS: i32 = 600;
+
+pub struct PostgresCatalogImportRepository {
+    pool: PgPool,
+}
+
+impl PostgresCatalogImportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CatalogImportRepository for PostgresCatalogImportRepository {
+    async fn create_job(&self, job: &CatalogImportJobEntity) -> Result<CatalogImportJobEntity> {
+        let result = sqlx::query_as::<_, CatalogImportJobModel>(&format!(
+            r#"
+            INSERT INTO catalog_import_jobs (format, file_name, status, requested_by, run_after, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(job.format.as_str())
+        .bind(&job.file_name)
+        .bind(job.status.as_str())
+        .bind(job.requested_by)
+        .bind(job.run_after)
+        .bind(job.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(CatalogImportJobEntity::from(result))
+    }
+
+    async fn find_job(&self, id: i32) -> Result<Option<CatalogImportJobEntity>> {
+        let result = sqlx::query_as::<_, CatalogImportJobModel>(&format!(
+            "SELECT {} FROM catalog_import_jobs WHERE id = $1",
+            JOB_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(CatalogImportJobEntity::from))
+    }
+
+    async fn find_jobs(&self, limit: i64) -> Result<Vec<CatalogImportJobEntity>> {
+        let results = sqlx::query_as::<_, CatalogImportJobModel>(&format!(
+            "SELECT {} FROM catalog_import_jobs ORDER BY created_at DESC, id DESC LIMIT $1",
+            JOB_COLUMNS
+        ))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(CatalogImportJobEntity::from).collect())
+    }
+
+    async fn claim_next(&self) -> Result<Option<CatalogImportJobEntity>> {
+        // งานที่ status ยังเป็น running แต่ lease หมดแล้ว = worker ตัวเดิมตายไป รับช่วงต่อจาก checkpoint
+        let result = sqlx::query_as::<_, CatalogImportJobModel>(&format!(
+            r#"
+            UPDATE catalog_import_jobs
+            SET status = 'running',
+                attempts = attempts + 1,
+                run_after = NOW() + make_interval(secs => $1),
+                started_at = COALESCE(started_at, NOW())
+            WHERE id = (
+                SELECT id FROM catalog_import_jobs
+                WHERE status IN ('queued', 'running') AND run_after <= NOW()
+                ORDER BY created_at ASC, id ASC
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(CLAIM_LEASE_SECONDS)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(CatalogImportJobEntity::from))
+    }
+
+    async fn checkpoint(
+        &self,
+        job: &CatalogImportJobEntity,
+        records: &[CatalogImportRecordEntity],
+    ) -> Result<CatalogImportJobEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        for record in records {
+            sqlx::query(
+                r#"
+                INSERT INTO catalog_import_records
+                    (job_id, record_index, reference, isbn, book_id, outcome, messages)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (job_id, record_index) DO UPDATE
+                SET reference = EXCLUDED.reference,
+                    isbn = EXCLUDED.isbn,
+                    book_id = EXCLUDED.book_id,
+                    outcome = EXCLUDED.outcome,
+                    messages = EXCLUDED.messages
+                "#,
+            )
+            .bind(record.job_id)
+            .bind(record.record_index)
+            .bind(&record.reference)
+            .bind(&record.isbn)
+            .bind(record.book_id)
+            .bind(record.outcome.as_str())
+            .bind(&record.messages)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // นับจากตาราง report เสมอ record ที่ถูก replay หลัง restart จะไม่ถูกนับซ้ำ
+        // attempts ต้องตรงกับตอน claim ถ้าไม่ตรงแปลว่า worker อื่นรับงานไปแล้ว
+        let result = sqlx::query_as::<_, CatalogImportJobModel>(&format!(
+            r#"
+            UPDATE catalog_import_jobs
+            SET sender = $2,
+                checkpoint_offset = $3,
+                records_processed = $4,
+                created_count = c.created,
+                updated_count = c.updated,
+                rejected_count = c.rejected,
+                run_after = NOW() + make_interval(secs => $6)
+            FROM (
+                SELECT COUNT(*) FILTER (WHERE outcome = 'created')::INT AS created,
+                       COUNT(*) FILTER (WHERE outcome = 'updated')::INT AS updated,
+                       COUNT(*) FILTER (WHERE outcome = 'rejected')::INT AS rejected
+                FROM catalog_import_records
+                WHERE job_id = $1
+            ) c
+            WHERE id = $1 AND status = 'running' AND attempts = $5
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(job.id)
+        .bind(&job.sender)
+        .bind(job.checkpoint_offset)
+        .bind(job.records_processed)
+        .bind(job.attempts)
+        .bind(CLAIM_LEASE_SECONDS)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| anyhow!("Import job {} was taken over by another worker", job.id))?;
+
+        tx.commit().await?;
+        Ok(CatalogImportJobEntity::from(result))
+    }
+
+    async fn finish(&self, job: &CatalogImportJobEntity) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE catalog_import_jobs
+            SET status = $2, last_error = $3, finished_at = $4
+            WHERE id = $1 AND attempts = $5
+            "#,
+        )
+        .bind(job.id)
+        .bind(job.status.as_str())
+        .bind(&job.last_error)
+        .bind(job.finished_at)
+        .bind(job.attempts)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow!("Import job {} was taken over by another worker", job.id));
+        }
+        Ok(())
+    }
+
+    async fn find_records(
+        &self,
+        job_id: i32,
+        outcome: Option<ImportOutcome>,
+        after_index: i32,
+        limit: i64,
+    ) -> Result<Vec<CatalogImportRecordEntity>> {
+        let results = sqlx::query_as::<_, CatalogImportRecordModel>(&format!(
+            r#"
+            SELECT {} FROM catalog_import_records
+            WHERE job_id = $1 AND ($2::TEXT IS NULL OR outcome = $2) AND record_index > $3
+            ORDER BY record_index ASC
+            LIMIT $4
+            "#,
+            RECORD_COLUMNS
+        ))
+        .bind(job_id)
+        .bind(outcome.map(|o| o.as_str()))
+        .bind(after_index)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(CatalogImportRecordEntity::from).collect())
+    }
+
+    async fn save_book(&self, imported: &ImportedBook) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+
+        let book_id = if imported.book.id == 0 {
+            PostgresBookRepository::insert_in_tx(&mut tx, &imported.book).await?
+        } else {
+            PostgresBookRepository::update_in_tx(&mut tx, &imported.book).await?.id
+        };
+
+        if let Some(contributors) = &imported.contributors {
+            sqlx::query("DELETE FROM book_contributors WHERE book_id = $1")
+                .bind(book_id)
+                .execute(&mut *tx)
+                .await?;
+            for contributor in contributors {
+                sqlx::query(
+                    r#"
+                    INSERT INTO book_contributors (book_id, sequence, role, name)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (book_id, sequence) DO NOTHING
+                    "#,
+                )
+                .bind(book_id)
+                .bind(contributor.sequence)
+                .bind(&contributor.role)
+                .bind(&contributor.name)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        if let Some(subjects) = &imported.subjects {
+            sqlx::query("DELETE FROM book_subjects WHERE book_id = $1")
+                .bind(book_id)
+                .execute(&mut *tx)
+                .await?;
+            for subject in subjects {
+                sqlx::query(
+                    r#"
+                    INSERT INTO book_subjects (book_id, scheme, code, heading, is_main)
+                    VALUES ($1, $2, $3, $4, $5)
+                    ON CONFLICT (book_id, scheme, code) DO NOTHING
+                    "#,
+                )
+                .bind(book_id)
+                .bind(subject.scheme.as_str())
+                .bind(&subject.code)
+                .bind(&subject.heading)
+                .bind(subject.is_main)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        for price in &imported.currency_prices {
+            let price = BookPriceEntity::new(book_id, None, price.clone())?;
+            PostgresBookPriceRepository::upsert_in_tx(&mut tx, &price).await?;
+        }
+
+        tx.commit().await?;
+        Ok(book_id)
+    }
+}
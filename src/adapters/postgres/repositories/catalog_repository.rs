@@ -0,0 +1,225 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+use crate::domain::{
+    entities::book::BookEntity,
+    repositories::catalog_repository::{
+        CatalogFilter, CatalogPage, CatalogRepository, CatalogSort, Facet, FacetCount,
+        PRICE_FACET_BOUNDS,
+    },
+};
+use crate::adapters::postgres::{
+    models::{book_model::BookSearchRowModel, catalog_model::FacetCountModel},
+    repositories::book_repository::BOOK_COLUMNS,
+};
+
+pub struct PostgresCatalogRepository {
+    pool: PgPool,
+}
+
+impl PostgresCatalogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Bit of a facet in the per-row "filters missed" mask
+fn facet_bit(facet: Facet) -> i32 {
+    1 << Facet::ALL.iter().position(|f| *f == facet).unwrap_or(0)
+}
+
+/// Facet columns as (facet, column) pairs, in `Facet::ALL` order
+const FACET_COLUMNS: [(Facet, &str); 8] = [
+    (Facet::Category, "category"),
+    (Facet::Author, "author"),
+    (Facet::Publisher, "publisher"),
+    (Facet::Language, "language"),
+    (Facet::Format, "format"),
+    (Facet::Price, "price_bucket"),
+    (Facet::Rating, "rating_floor"),
+    (Facet::Availability, "availability"),
+];
+
+fn is_filtered(facet: Facet, filter: &CatalogFilter) -> bool {
+    match facet {
+        Facet::Category => !filter.categories.is_empty(),
+        Facet::Author => !filter.authors.is_empty(),
+        Facet::Publisher => !filter.publishers.is_empty(),
+        Facet::Language => !filter.languages.is_empty(),
+        Facet::Format => !filter.formats.is_empty(),
+        Facet::Price => filter.price_min.is_some() || filter.price_max.is_some(),
+        Facet::Rating => filter.min_rating.is_some(),
+        Facet::Availability => filter.in_stock_only,
+    }
+}
+
+/// Pushes the SQL condition of one facet's filter (see `is_filtered`).
+fn push_condition(qb: &mut QueryBuilder<'_, Postgres>, facet: Facet, filter: &CatalogFilter) {
+    match facet {
+        Facet::Category => {
+            qb.push("category = ANY(").push_bind(filter.categories.clone()).push(")");
+        }
+        Facet::Author => {
+            qb.push("author = ANY(").push_bind(filter.authors.clone()).push(")");
+        }
+        Facet::Publisher => {
+            qb.push("publisher = ANY(").push_bind(filter.publishers.clone()).push(")");
+        }
+        Facet::Language => {
+            qb.push("language = ANY(").push_bind(filter.languages.clone()).push(")");
+        }
+        Facet::Format => {
+            let formats: Vec<String> = filter.formats.iter().map(|f| f.as_str().to_string()).collect();
+            qb.push("format = ANY(").push_bind(formats).push(")");
+        }
+        Facet::Price => {
+            qb.push("price >= ")
+                .push_bind(filter.price_min.unwrap_or(0))
+                .push(" AND price < ")
+                .push_bind(filter.price_max.unwrap_or(i64::MAX));
+        }
+        Facet::Rating => {
+            // เทียบผลรวมแทนการหาร เพื่อไม่ต้องคำนวณค่าเฉลี่ยทุกแถว
+            qb.push("rating_count > 0 AND rating_total >= rating_count * ")
+                .push_bind(filter.min_rating.unwrap_or(0));
+        }
+        Facet::Availability => {
+            qb.push("stock_quantity > 0");
+        }
+    }
+}
+
+fn push_base_where(qb: &mut QueryBuilder<'_, Postgres>, filter: &CatalogFilter) {
+    qb.push(" WHERE is_active");
+    if let Some(tsquery) = &filter.tsquery {
+        qb.push(" AND search_vector @@ CAST(")
+            .push_bind(tsquery.clone())
+            .push(" AS tsquery)");
+    }
+}
+
+#[async_trait]
+impl CatalogRepository for PostgresCatalogRepository {
+    async fn browse(
+        &self,
+        filter: &CatalogFilter,
+        sort: CatalogSort,
+        offset: i64,
+        limit: i64,
+        facet_limit: i64,
+    ) -> Result<CatalogPage> {
+        // ---------- Matching books ----------
+        let mut qb = QueryBuilder::<Postgres>::new(format!("SELECT {}, ", BOOK_COLUMNS));
+        match &filter.tsquery {
+            Some(tsquery) => {
+                qb.push("ts_rank(search_vector, CAST(")
+                    .push_bind(tsquery.clone())
+                    .push(" AS tsquery)) AS rank");
+            }
+            None => {
+                qb.push("CAST(0 AS REAL) AS rank");
+            }
+        }
+        qb.push(", COUNT(*) OVER () AS total_count FROM books");
+        push_base_where(&mut qb, filter);
+        for facet in Facet::ALL.into_iter().filter(|f| is_filtered(*f, filter)) {
+            qb.push(" AND (");
+            push_condition(&mut qb, facet, filter);
+            qb.push(")");
+        }
+
+        qb.push(match sort {
+            CatalogSort::Relevance if filter.tsquery.is_some() => " ORDER BY rank DESC, id ASC",
+            CatalogSort::Relevance | CatalogSort::Newest => " ORDER BY created_at DESC, id DESC",
+            CatalogSort::PriceAsc => " ORDER BY price ASC, id ASC",
+            CatalogSort::PriceDesc => " ORDER BY price DESC, id ASC",
+            CatalogSort::Rating => {
+                " ORDER BY rating_total::float8 / NULLIF(rating_count, 0) DESC NULLS LAST, \
+                 rating_count DESC, id ASC"
+            }
+            CatalogSort::Title => " ORDER BY title ASC, id ASC",
+        });
+        qb.push(" OFFSET ").push_bind(offset);
+        qb.push(" LIMIT ").push_bind(limit);
+
+        let rows = qb
+            .build_query_as::<BookSearchRowModel>()
+            .fetch_all(&self.pool)
+            .await?;
+        let total = rows.first().map(|r| r.total_count);
+
+        // ---------- Facet counts ----------
+        // แต่ละแถวมี bitmask ของ filter ที่ไม่ผ่าน แถวนับให้ facet F ได้เมื่อ
+        // ไม่ผ่านเฉพาะ filter ของ F เอง (หรือผ่านทั้งหมด) แถวที่ไม่ผ่าน
+        // ตั้งแต่ 2 filter ขึ้นไปจึงตัดทิ้งได้ก่อน GROUPING SETS
+        let mut fq = QueryBuilder::<Postgres>::new(
+            "WITH base AS (SELECT category, author, publisher, language, format, \
+             width_bucket(price, ",
+        );
+        fq.push_bind(PRICE_FACET_BOUNDS.to_vec());
+        fq.push(
+            ") AS price_bucket, \
+             FLOOR(rating_total::numeric / NULLIF(rating_count, 0))::int AS rating_floor, \
+             CASE WHEN stock_quantity > 0 THEN 'in_stock' ELSE 'out_of_stock' END AS availability, \
+             (0",
+        );
+        for facet in Facet::ALL.into_iter().filter(|f| is_filtered(*f, filter)) {
+            fq.push(" | CASE WHEN ");
+            push_condition(&mut fq, facet, filter);
+            fq.push(format!(" THEN 0 ELSE {} END", facet_bit(facet)));
+        }
+        fq.push(") AS miss FROM books");
+        push_base_where(&mut fq, filter);
+        fq.push(
+            "), candidates AS (SELECT * FROM base WHERE miss & (miss - 1) = 0), \
+             counts AS (SELECT CASE",
+        );
+        for (facet, column) in FACET_COLUMNS {
+            fq.push(format!(" WHEN GROUPING({}) = 0 THEN '{}'", column, facet.as_str()));
+        }
+        fq.push(" END AS facet, COALESCE(");
+        fq.push(FACET_COLUMNS.map(|(_, c)| format!("{}::text", c)).join(", "));
+        fq.push(") AS value, CASE");
+        for (facet, column) in FACET_COLUMNS {
+            fq.push(format!(
+                " WHEN GROUPING({}) = 0 THEN COUNT(*) FILTER (WHERE miss & ~{} = 0)",
+                column,
+                facet_bit(facet)
+            ));
+        }
+        fq.push(" END AS count FROM candidates GROUP BY GROUPING SETS (");
+        fq.push(FACET_COLUMNS.map(|(_, c)| format!("({})", c)).join(", "));
+        fq.push(
+            ")) SELECT facet, value, count FROM (\
+             SELECT facet, value, count, \
+             ROW_NUMBER() OVER (PARTITION BY facet ORDER BY count DESC, value ASC) AS position \
+             FROM counts WHERE value IS NOT NULL AND count > 0) ranked \
+             WHERE position <= ",
+        );
+        fq.push_bind(facet_limit);
+        fq.push(" ORDER BY facet, count DESC, value ASC");
+
+        let facet_rows = fq
+            .build_query_as::<FacetCountModel>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        // หน้าเกินจำนวนผลลัพธ์: ใช้ยอดจาก facet availability แทน
+        // (นับด้วย filter อื่นครบทุกตัว ยกเว้น in-stock ของมันเอง)
+        let total = total.unwrap_or_else(|| {
+            facet_rows
+                .iter()
+                .filter(|f| f.facet == Facet::Availability.as_str())
+                .filter(|f| !filter.in_stock_only || f.value == "in_stock")
+                .map(|f| f.count)
+                .sum()
+        });
+
+        Ok(CatalogPage {
+            books: rows.into_iter().map(|r| BookEntity::from(r.book)).collect(),
+            total,
+            facets: facet_rows.into_iter().map(FacetCount::from).collect(),
+        })
+    }
+}
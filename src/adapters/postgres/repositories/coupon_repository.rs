@@ -0,0 +1,158 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::coupon::CouponEntity,
+    repositories::coupon_repository::CouponRepository,
+};
+use crate::adapters::postgres::models::promotion_model::CouponModel;
+
+const COUPON_COLUMNS: &str = "id, code, promotion_id, usage_limit, per_customer_limit, \
+                              times_used, is_active, created_at, updated_at";
+
+pub struct PostgresCouponRepository {
+    pool: PgPool,
+}
+
+impl PostgresCouponRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Redeems a coupon for an order inside the order's transaction. The
+    /// row lock taken by the UPDATE serializes concurrent redemptions, so the
+    /// global and per-customer limits cannot be overrun.
+    pub async fn redeem_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        coupon_id: i32,
+        user_id: i32,
+        order_id: i32,
+    ) -> Result<()> {
+        let redeemed = sqlx::query(
+            r#"
+            UPDATE coupons
+            SET times_used = times_used + 1, updated_at = NOW()
+            WHERE id = $1
+              AND is_active
+              AND (usage_limit IS NULL OR times_used < usage_limit)
+              AND (per_customer_limit IS NULL OR per_customer_limit > (
+                  SELECT COUNT(*) FROM coupon_redemptions
+                  WHERE coupon_id = $1 AND user_id = $2
+              ))
+            "#,
+        )
+        .bind(coupon_id)
+        .bind(user_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if redeemed.rows_affected() == 0 {
+            bail!("Coupon can no longer be redeemed");
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO coupon_redemptions (coupon_id, user_id, order_id)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(coupon_id)
+        .bind(user_id)
+        .bind(order_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CouponRepository for PostgresCouponRepository {
+    async fn find_by_code(&self, code: &str) -> Result<Option<CouponEntity>> {
+        let result = sqlx::query_as::<_, CouponModel>(&format!(
+            "SELECT {} FROM coupons WHERE code = UPPER($1)",
+            COUPON_COLUMNS
+        ))
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(CouponEntity::from))
+    }
+
+    async fn find_by_promotion(&self, promotion_id: i32) -> Result<Vec<CouponEntity>> {
+        let results = sqlx::query_as::<_, CouponModel>(&format!(
+            "SELECT {} FROM coupons WHERE promotion_id = $1 ORDER BY id ASC",
+            COUPON_COLUMNS
+        ))
+        .bind(promotion_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(CouponEntity::from).collect())
+    }
+
+    async fn save(&self, coupon: &CouponEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO coupons
+                (code, promotion_id, usage_limit, per_customer_limit,
+                 times_used, is_active, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(coupon.code.as_str())
+        .bind(coupon.promotion_id)
+        .bind(coupon.usage_limit)
+        .bind(coupon.per_customer_limit)
+        .bind(coupon.times_used)
+        .bind(coupon.is_active)
+        .bind(coupon.created_at)
+        .bind(coupon.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn update(&self, coupon: &CouponEntity) -> Result<CouponEntity> {
+        // times_used เปลี่ยนผ่าน redeem_in_tx เท่านั้น
+        let result = sqlx::query_as::<_, CouponModel>(&format!(
+            r#"
+            UPDATE coupons
+            SET
+                usage_limit = $1,
+                per_customer_limit = $2,
+                is_active = $3,
+                updated_at = $4
+            WHERE id = $5
+            RETURNING {}
+            "#,
+            COUPON_COLUMNS
+        ))
+        .bind(coupon.usage_limit)
+        .bind(coupon.per_customer_limit)
+        .bind(coupon.is_active)
+        .bind(coupon.updated_at)
+        .bind(coupon.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(CouponEntity::from(result))
+    }
+
+    async fn count_redemptions(&self, coupon_id: i32, user_id: i32) -> Result<i64> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS count FROM coupon_redemptions WHERE coupon_id = $1 AND user_id = $2",
+        )
+        .bind(coupon_id)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("count")?)
+    }
+}
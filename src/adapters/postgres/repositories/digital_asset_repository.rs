@@ -0,0 +1,105 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use crate::domain::{
+    entities::digital_asset::DigitalAssetEntity,
+    repositories::digital_asset_repository::DigitalAssetRepository,
+};
+use crate::adapters::postgres::models::digital_model::DigitalAssetModel;
+
+const ASSET_COLUMNS: &str = "id, book_id, file_format, storage_key, file_name, size_bytes, \
+                             sha256, created_at, updated_at";
+
+pub struct PostgresDigitalAssetRepository {
+    pool: PgPool,
+}
+
+impl PostgresDigitalAssetRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DigitalAssetRepository for PostgresDigitalAssetRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<DigitalAssetEntity>> {
+        let result = sqlx::query_as::<_, DigitalAssetModel>(&format!(
+            "SELECT {} FROM digital_assets WHERE id = $1",
+            ASSET_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(DigitalAssetEntity::from))
+    }
+
+    async fn find_by_book(&self, book_id: i32) -> Result<Vec<DigitalAssetEntity>> {
+        self.find_by_books(&[book_id]).await
+    }
+
+    async fn find_by_books(&self, book_ids: &[i32]) -> Result<Vec<DigitalAssetEntity>> {
+        let results = sqlx::query_as::<_, DigitalAssetModel>(&format!(
+            "SELECT {} FROM digital_assets WHERE book_id = ANY($1) ORDER BY book_id, file_format",
+            ASSET_COLUMNS
+        ))
+        .bind(book_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(DigitalAssetEntity::from).collect())
+    }
+
+    async fn save(&self, asset: &DigitalAssetEntity) -> Result<(i32, Option<String>)> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous_key: Option<String> = sqlx::query_scalar(
+            "SELECT storage_key FROM digital_assets WHERE book_id = $1 AND file_format = $2 FOR UPDATE",
+        )
+        .bind(asset.book_id)
+        .bind(asset.file_format.as_str())
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO digital_assets (
+                book_id, file_format, storage_key, file_name, size_bytes, sha256,
+                created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (book_id, file_format) DO UPDATE SET
+                storage_key = EXCLUDED.storage_key,
+                file_name = EXCLUDED.file_name,
+                size_bytes = EXCLUDED.size_bytes,
+                sha256 = EXCLUDED.sha256,
+                updated_at = EXCLUDED.updated_at
+            RETURNING id
+            "#,
+        )
+        .bind(asset.book_id)
+        .bind(asset.file_format.as_str())
+        .bind(&asset.storage_key)
+        .bind(&asset.file_name)
+        .bind(asset.size_bytes)
+        .bind(&asset.sha256)
+        .bind(asset.created_at)
+        .bind(asset.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let replaced = previous_key.filter(|key| *key != asset.storage_key);
+        Ok((row.try_get("id")?, replaced))
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM digital_assets WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use crate::domain::{
+    entities::download_log::DownloadLogEntity,
+    repositories::download_log_repository::DownloadLogRepository,
+};
+use crate::adapters::postgres::models::digital_model::DownloadLogModel;
+
+const DOWNLOAD_LOG_COLUMNS: &str = "id, entitlement_id, asset_id, user_id, outcome, range_start, \
+                                    range_end, bytes_served, counted, ip_address, user_agent, \
+                                    created_at";
+
+pub struct PostgresDownloadLogRepository {
+    pool: PgPool,
+}
+
+impl PostgresDownloadLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DownloadLogRepository for PostgresDownloadLogRepository {
+    async fn save(&self, log: &DownloadLogEntity) -> Result<i64> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO download_logs (
+                entitlement_id, asset_id, user_id, outcome, range_start, range_end,
+                bytes_served, counted, ip_address, user_agent, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING id
+            "#,
+        )
+        .bind(log.entitlement_id)
+        .bind(log.asset_id)
+        .bind(log.user_id)
+        .bind(log.outcome.as_str())
+        .bind(log.range_start)
+        .bind(log.range_end)
+        .bind(log.bytes_served)
+        .bind(log.counted)
+        .bind(&log.ip_address)
+        .bind(&log.user_agent)
+        .bind(log.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn find_by_entitlement(&self, entitlement_id: i32, limit: i64) -> Result<Vec<DownloadLogEntity>> {
+        let results = sqlx::query_as::<_, DownloadLogModel>(&format!(
+            r#"
+            SELECT {} FROM download_logs
+            WHERE entitlement_id = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2
+            "#,
+            DOWNLOAD_LOG_COLUMNS
+        ))
+        .bind(entitlement_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(DownloadLogEntity::from).collect())
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::domain::{
+    entities::download_watermark::DownloadWatermarkEntity,
+    repositories::download_watermark_repository::DownloadWatermarkRepository,
+};
+use crate::adapters::postgres::models::digital_model::DownloadWatermarkModel;
+
+const WATERMARK_COLUMNS: &str = "id, code, entitlement_id, asset_id, asset_sha256, user_id, order_id, \
+                                 licensee, storage_key, size_bytes, created_at";
+
+pub struct PostgresDownloadWatermarkRepository {
+    pool: PgPool,
+}
+
+impl PostgresDownloadWatermarkRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DownloadWatermarkRepository for PostgresDownloadWatermarkRepository {
+    async fn find_by_code(&self, code: &str) -> Result<Option<DownloadWatermarkEntity>> {
+        let result = sqlx::query_as::<_, DownloadWatermarkModel>(&format!(
+            "SELECT {} FROM download_watermarks WHERE code = $1",
+            WATERMARK_COLUMNS
+        ))
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(DownloadWatermarkEntity::from))
+    }
+
+    async fn find_for_download(
+        &self,
+        entitlement_id: i32,
+        asset_id: i32,
+        asset_sha256: &str,
+    ) -> Result<Option<DownloadWatermarkEntity>> {
+        let result = sqlx::query_as::<_, DownloadWatermarkModel>(&format!(
+            r#"
+            SELECT {} FROM download_watermarks
+            WHERE entitlement_id = $1 AND asset_id = $2 AND asset_sha256 = $3
+            "#,
+            WATERMARK_COLUMNS
+        ))
+        .bind(entitlement_id)
+        .bind(asset_id)
+        .bind(asset_sha256)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(DownloadWatermarkEntity::from))
+    }
+
+    async fn save(&self, watermark: &DownloadWatermarkEntity) -> Result<DownloadWatermarkEntity> {
+        let inserted = sqlx::query_as::<_, DownloadWatermarkModel>(&format!(
+            r#"
+            INSERT INTO download_watermarks (
+                code, entitlement_id, asset_id, asset_sha256, user_id, order_id,
+                licensee, storage_key, size_bytes, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (entitlement_id, asset_id, asset_sha256) DO NOTHING
+            RETURNING {}
+            "#,
+            WATERMARK_COLUMNS
+        ))
+        .bind(watermark.code.as_str())
+        .bind(watermark.entitlement_id)
+        .bind(watermark.asset_id)
+        .bind(&watermark.asset_sha256)
+        .bind(watermark.user_id)
+        .bind(watermark.order_id)
+        .bind(&watermark.licensee)
+        .bind(&watermark.storage_key)
+        .bind(watermark.size_bytes)
+        .bind(watermark.created_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if let Some(model) = inserted {
+            return Ok(DownloadWatermarkEntity::from(model));
+        }
+
+        // request อื่นสร้างไว้ก่อนแล้ว ใช้ของเดิม
+        let entitlement_id = watermark.entitlement_id.unwrap_or_default();
+        self.find_for_download(entitlement_id, watermark.asset_id, &watermark.asset_sha256)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Watermark vanished after conflict"))
+    }
+}
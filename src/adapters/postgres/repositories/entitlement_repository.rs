@@ -0,0 +1,216 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::entitlement::{EntitlementEntity, DEFAULT_DOWNLOAD_LIMIT},
+    repositories::entitlement_repository::{EntitlementRepository, LinkUse},
+};
+use crate::adapters::postgres::models::digital_model::EntitlementModel;
+
+const ENTITLEMENT_COLUMNS: &str = "id, user_id, book_id, order_id, download_limit, downloads_used, \
+                                   revoked_at, revoke_reason, created_at, updated_at";
+
+pub struct PostgresEntitlementRepository {
+    pool: PgPool,
+}
+
+impl PostgresEntitlementRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Grants the order's buyer every digital book in it. Called by the order
+    /// repository in the transaction that marks the order paid; safe to repeat.
+    pub async fn grant_for_order_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: i32,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO entitlements (user_id, book_id, order_id, download_limit)
+            SELECT DISTINCT o.user_id, oi.book_id, o.id, $2::INTEGER
+            FROM orders o
+            JOIN order_items oi ON oi.order_id = o.id
+            JOIN books b ON b.id = oi.book_id
+            WHERE o.id = $1 AND b.format IN ('ebook', 'audiobook')
+            ON CONFLICT (order_id, book_id) DO NOTHING
+            "#,
+        )
+        .bind(order_id)
+        .bind(DEFAULT_DOWNLOAD_LIMIT)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Revokes what the order granted, when it is cancelled or refunded.
+    pub async fn revoke_for_order_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: i32,
+        reason: &str,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE entitlements
+            SET revoked_at = NOW(), revoke_reason = $2, updated_at = NOW()
+            WHERE order_id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(order_id)
+        .bind(reason)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl EntitlementRepository for PostgresEntitlementRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<EntitlementEntity>> {
+        let result = sqlx::query_as::<_, EntitlementModel>(&format!(
+            "SELECT {} FROM entitlements WHERE id = $1",
+            ENTITLEMENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(EntitlementEntity::from))
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<EntitlementEntity>> {
+        let results = sqlx::query_as::<_, EntitlementModel>(&format!(
+            "SELECT {} FROM entitlements WHERE user_id = $1 ORDER BY created_at DESC, id DESC",
+            ENTITLEMENT_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(EntitlementEntity::from).collect())
+    }
+
+    async fn find_active(&self, user_id: i32, book_id: i32) -> Result<Option<EntitlementEntity>> {
+        let result = sqlx::query_as::<_, EntitlementModel>(&format!(
+            r#"
+            SELECT {} FROM entitlements
+            WHERE user_id = $1 AND book_id = $2 AND revoked_at IS NULL
+            ORDER BY created_at DESC, id DESC
+            LIMIT 1
+            "#,
+            ENTITLEMENT_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(book_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(EntitlementEntity::from))
+    }
+
+    async fn save(&self, entitlement: &EntitlementEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO entitlements (
+                user_id, book_id, order_id, download_limit, downloads_used,
+                revoked_at, revoke_reason, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(entitlement.user_id)
+        .bind(entitlement.book_id)
+        .bind(entitlement.order_id)
+        .bind(entitlement.download_limit)
+        .bind(entitlement.downloads_used)
+        .bind(entitlement.revoked_at)
+        .bind(&entitlement.revoke_reason)
+        .bind(entitlement.created_at)
+        .bind(entitlement.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn update(&self, entitlement: &EntitlementEntity) -> Result<EntitlementEntity> {
+        let result = sqlx::query_as::<_, EntitlementModel>(&format!(
+            r#"
+            UPDATE entitlements
+            SET
+                download_limit = $1,
+                downloads_used = $2,
+                revoked_at = $3,
+                revoke_reason = $4,
+                updated_at = $5
+            WHERE id = $6
+            RETURNING {}
+            "#,
+            ENTITLEMENT_COLUMNS
+        ))
+        .bind(entitlement.download_limit)
+        .bind(entitlement.downloads_used)
+        .bind(entitlement.revoked_at)
+        .bind(&entitlement.revoke_reason)
+        .bind(entitlement.updated_at)
+        .bind(entitlement.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(EntitlementEntity::from(result))
+    }
+
+    async fn consume_download(
+        &self,
+        id: i32,
+        asset_id: i32,
+        expires_at: DateTime<Utc>,
+        resume: bool,
+    ) -> Result<LinkUse> {
+        let mut tx = self.pool.begin().await?;
+
+        // request พร้อมกันบนลิงก์เดียวกันรอกันที่ primary key: นับได้ครั้งเดียว
+        // โหลดใหม่ตั้งแต่ไบต์แรกบนลิงก์เดิมนับเป็นอีกครั้ง ไม่งั้นลิงก์เดียวโหลดได้ไม่จำกัดจนหมดอายุ
+        let first_use = sqlx::query(
+            r#"
+            INSERT INTO download_link_uses (entitlement_id, asset_id, expires_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(asset_id)
+        .bind(expires_at)
+        .execute(&mut *tx)
+        .await?;
+        if first_use.rows_affected() == 0 && resume {
+            return Ok(LinkUse::AlreadyCounted);
+        }
+
+        // เช็คและเพิ่มในคำสั่งเดียว กันโหลดพร้อมกันหลายแท็บจนเกินลิมิต
+        let result = sqlx::query(
+            r#"
+            UPDATE entitlements
+            SET downloads_used = downloads_used + 1, updated_at = NOW()
+            WHERE id = $1
+              AND revoked_at IS NULL
+              AND (download_limit IS NULL OR downloads_used < download_limit)
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            // ไม่ commit: ลิงก์ยังไม่ถูกนับ
+            return Ok(LinkUse::LimitReached);
+        }
+
+        tx.commit().await?;
+        Ok(LinkUse::Counted)
+    }
+}
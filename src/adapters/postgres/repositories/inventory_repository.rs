@@ -0,0 +1,93 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::inventory_movement::InventoryMovementEntity,
+    repositories::inventory_repository::{InsufficientStock, InventoryRepository},
+    value_objects::stock_bucket::StockBucket,
+};
+use crate::adapters::postgres::models::inventory_movement_model::InventoryMovementModel;
+
+pub struct PostgresInventoryRepository {
+    pool: PgPool,
+}
+
+impl PostgresInventoryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Writes a movement inside an existing transaction so other aggregates
+    /// (e.g. orders) can move stock atomically with their own changes.
+    pub async fn record_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        movement: &InventoryMovementEntity,
+    ) -> Result<i32> {
+        if movement.bucket == StockBucket::Available {
+            let updated = sqlx::query(
+                r#"
+                UPDATE books
+                SET stock_quantity = stock_quantity + $1, updated_at = NOW()
+                WHERE id = $2 AND stock_quantity + $1 >= 0
+                RETURNING stock_quantity
+                "#,
+            )
+            .bind(movement.quantity)
+            .bind(movement.book_id)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+            if updated.is_none() {
+                bail!(InsufficientStock { book_id: movement.book_id });
+            }
+        }
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO inventory_movements
+                (book_id, bucket, quantity, reason, reference, created_by, created_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(movement.book_id)
+        .bind(movement.bucket.as_str())
+        .bind(movement.quantity)
+        .bind(movement.reason.as_str())
+        .bind(&movement.reference)
+        .bind(movement.created_by)
+        .bind(movement.created_at)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+}
+
+#[async_trait]
+impl InventoryRepository for PostgresInventoryRepository {
+    async fn record(&self, movement: &InventoryMovementEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+        let id = Self::record_in_tx(&mut tx, movement).await?;
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    async fn find_by_book(&self, book_id: i32) -> Result<Vec<InventoryMovementEntity>> {
+        let results = sqlx::query_as::<_, InventoryMovementModel>(
+            r#"
+            SELECT id, book_id, bucket, quantity, reason, reference, created_by, created_at
+            FROM inventory_movements
+            WHERE book_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(book_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(InventoryMovementEntity::from).collect())
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use sqlx::{types::Json, PgPool, Row};
+
+use crate::domain::{
+    entities::invoice::{InvoiceEntity, InvoiceKind},
+    repositories::invoice_repository::{InvoiceRepository, RenderInvoice},
+};
+use crate::adapters::postgres::models::invoice_model::InvoiceModel;
+
+const INVOICE_COLUMNS: &str = "id, kind, number, fiscal_year, sequence, order_id, user_id, \
+                               original_invoice_id, original_number, original_issued_at, \
+                               value_before, return_id, reason, \
+                               seller_name, seller_tax_id, seller_branch, seller_address, \
+                               buyer_name, buyer_tax_id, buyer_branch, buyer_address, \
+                               currency, lines, net_amount, tax_amount, total, \
+                               sha256, size_bytes, issued_by, issued_at";
+
+pub struct PostgresInvoiceRepository {
+    pool: PgPool,
+}
+
+impl PostgresInvoiceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl InvoiceRepository for PostgresInvoiceRepository {
+    async fn issue(&self, invoice: &InvoiceEntity, render: RenderInvoice<'_>) -> Result<InvoiceEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        // row lock ของ sequence ถือไว้จน commit — ออกเอกสารชนิดเดียวกันพร้อมกันจะต่อคิวกัน
+        // ถ้า render/insert ล้มเหลว rollback แล้วเลขนี้จะถูกใช้ใหม่ ไม่มีเลขขาด
+        let row = sqlx::query(
+            r#"
+            INSERT INTO invoice_sequences (kind, fiscal_year, last_number)
+            VALUES ($1, $2, 1)
+            ON CONFLICT (kind, fiscal_year)
+            DO UPDATE SET last_number = invoice_sequences.last_number + 1
+            RETURNING last_number
+            "#,
+        )
+        .bind(invoice.kind.as_str())
+        .bind(invoice.fiscal_year)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut issued = invoice.clone();
+        issued.assign_number(row.try_get("last_number")?);
+
+        let document = render(&issued)?;
+        issued.sha256 = hex::encode(Sha256::digest(&document));
+        issued.size_bytes = document.len() as i64;
+
+        let reference = issued.reference.as_ref();
+        let row = sqlx::query(
+            r#"
+            INSERT INTO invoices
+                (kind, number, fiscal_year, sequence, order_id, user_id,
+                 original_invoice_id, original_number, original_issued_at, value_before,
+                 return_id, reason,
+                 seller_name, seller_tax_id, seller_branch, seller_address,
+                 buyer_name, buyer_tax_id, buyer_branch, buyer_address,
+                 currency, lines, net_amount, tax_amount, total,
+                 document, sha256, size_bytes, issued_by, issued_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15,
+                 $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30)
+            RETURNING id
+            "#,
+        )
+        .bind(issued.kind.as_str())
+        .bind(&issued.number)
+        .bind(issued.fiscal_year)
+        .bind(issued.sequence)
+        .bind(issued.order_id)
+        .bind(issued.user_id)
+        .bind(reference.map(|r| r.invoice_id))
+        .bind(reference.map(|r| r.number.clone()))
+        .bind(reference.map(|r| r.issued_at))
+        .bind(reference.map(|r| r.value_before))
+        .bind(issued.return_id)
+        .bind(&issued.reason)
+        .bind(&issued.seller.name)
+        .bind(issued.seller.tax_id.as_str())
+        .bind(issued.seller.branch.as_str())
+        .bind(&issued.seller.address)
+        .bind(&issued.buyer.name)
+        .bind(issued.buyer.tax_id.as_str())
+        .bind(issued.buyer.branch.as_str())
+        .bind(&issued.buyer.address)
+        .bind(issued.currency.as_str())
+        .bind(Json(&issued.lines))
+        .bind(issued.net_amount)
+        .bind(issued.tax_amount)
+        .bind(issued.total)
+        .bind(&document)
+        .bind(&issued.sha256)
+        .bind(issued.size_bytes)
+        .bind(issued.issued_by)
+        .bind(issued.issued_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        issued.id = row.try_get("id")?;
+
+        tx.commit().await?;
+        Ok(issued)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<InvoiceEntity>> {
+        let result = sqlx::query_as::<_, InvoiceModel>(&format!(
+            "SELECT {} FROM invoices WHERE id = $1",
+            INVOICE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(InvoiceEntity::from))
+    }
+
+    async fn find_by_order(&self, order_id: i32, kind: InvoiceKind) -> Result<Vec<InvoiceEntity>> {
+        let results = sqlx::query_as::<_, InvoiceModel>(&format!(
+            "SELECT {} FROM invoices WHERE order_id = $1 AND kind = $2 ORDER BY issued_at, id",
+            INVOICE_COLUMNS
+        ))
+        .bind(order_id)
+        .bind(kind.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(InvoiceEntity::from).collect())
+    }
+
+    async fn find_credit_notes(&self, invoice_id: i32) -> Result<Vec<InvoiceEntity>> {
+        let results = sqlx::query_as::<_, InvoiceModel>(&format!(
+            "SELECT {} FROM invoices WHERE original_invoice_id = $1 ORDER BY issued_at, id",
+            INVOICE_COLUMNS
+        ))
+        .bind(invoice_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(InvoiceEntity::from).collect())
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<InvoiceEntity>> {
+        let results = sqlx::query_as::<_, InvoiceModel>(&format!(
+            "SELECT {} FROM invoices WHERE user_id = $1 ORDER BY issued_at DESC, id DESC",
+            INVOICE_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(InvoiceEntity::from).collect())
+    }
+
+    async fn find_document(&self, id: i32) -> Result<Option<Vec<u8>>> {
+        let row = sqlx::query("SELECT document FROM invoices WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|r| r.try_get("document")).transpose().map_err(Into::into)
+    }
+}
@@ -0,0 +1,622 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::{
+        loyalty_campaign::{LoyaltyCampaignEntity, LoyaltyCategoryMultiplier},
+        loyalty_entry::{
+            points_to_return, LoyaltyEntryEntity, PointsReason, LOYALTY_PAYMENT_PROVIDER,
+        },
+    },
+    repositories::loyalty_repository::{LoyaltyRepository, MemberSpend},
+    services::points_calculator::{self, EarnLine},
+    value_objects::{loyalty_tier::LoyaltyTier, money::Currency},
+};
+use crate::adapters::postgres::models::loyalty_model::{
+    LoyaltyCampaignModel, LoyaltyCategoryMultiplierModel, LoyaltyEntryModel,
+};
+
+const ENTRY_COLUMNS: &str = "id, user_id, points, reason, order_id, expires_at, note, created_by, created_at";
+
+const CAMPAIGN_COLUMNS: &str = "id, name, category, multiplier_pct, starts_at, ends_at, is_active, \
+                                created_by, created_at, updated_at";
+
+// ยอดซื้อต่อ order สำหรับคำนวณ tier: ไม่รวมค่าส่ง หักยอดที่คืนผ่าน return แล้ว
+const ORDER_SPEND_SQL: &str = r#"
+    SELECT o.user_id,
+           o.total - o.shipping_total - COALESCE((
+               SELECT SUM(r.refund_amount)
+               FROM return_requests r
+               WHERE r.order_id = o.id AND r.status = 'refunded'
+           ), 0) AS amount
+    FROM orders o
+    WHERE o.created_at >= $1
+      AND o.status IN ('paid', 'picking', 'shipped', 'delivered')
+"#;
+
+pub struct PostgresLoyaltyRepository {
+    pool: PgPool,
+}
+
+impl PostgresLoyaltyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Writes an entry inside an existing transaction so orders can move
+    /// points atomically with their own changes.
+    pub async fn record_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        entry: &LoyaltyEntryEntity,
+    ) -> Result<i32> {
+        sqlx::query(
+            r#"
+            INSERT INTO loyalty_accounts (user_id)
+            VALUES ($1)
+            ON CONFLICT (user_id) DO NOTHING
+            "#,
+        )
+        .bind(entry.user_id)
+        .execute(&mut **tx)
+        .await?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE loyalty_accounts
+            SET balance = balance + $1, updated_at = NOW()
+            WHERE user_id = $2 AND balance + $1 >= 0
+            "#,
+        )
+        .bind(entry.points)
+        .bind(entry.user_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            bail!("Insufficient points for user {}", entry.user_id);
+        }
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO loyalty_point_entries
+                (user_id, points, reason, order_id, expires_at, note, created_by, created_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(entry.user_id)
+        .bind(entry.points)
+        .bind(entry.reason.as_str())
+        .bind(entry.order_id)
+        .bind(entry.expires_at)
+        .bind(&entry.note)
+        .bind(entry.created_by)
+        .bind(entry.created_at)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    /// Credits the points earned on an order once it is paid. Points are
+    /// earned on goods after discounts, not on the part paid with points.
+    /// Safe to repeat; returns the points credited.
+    pub async fn award_for_order_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: i32,
+    ) -> Result<i64> {
+        let already = sqlx::query(
+            "SELECT 1 FROM loyalty_point_entries WHERE order_id = $1 AND reason = 'earn'",
+        )
+        .bind(order_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+        if already.is_some() {
+            return Ok(0);
+        }
+
+        let order = sqlx::query(
+            r#"
+            SELECT o.user_id, o.currency, o.subtotal, o.total - o.shipping_total AS goods_total,
+                   COALESCE((
+                       SELECT SUM(p.captured_amount - p.refunded_amount)
+                       FROM payments p
+                       WHERE p.order_id = o.id
+                         AND p.provider = $2
+                         AND p.status IN ('captured', 'partially_refunded')
+                   ), 0)::BIGINT AS paid_with_points
+            FROM orders o
+            WHERE o.id = $1
+            "#,
+        )
+        .bind(order_id)
+        .bind(LOYALTY_PAYMENT_PROVIDER)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        let user_id: i32 = order.try_get("user_id")?;
+        let currency = Currency::new(order.try_get::<String, _>("currency")?.as_str())?;
+        let subtotal: i64 = order.try_get("subtotal")?;
+        let goods_total: i64 = order.try_get("goods_total")?;
+        let paid_with_points: i64 = order.try_get("paid_with_points")?;
+        let eligible = goods_total - paid_with_points;
+        if subtotal <= 0 || eligible <= 0 {
+            return Ok(0);
+        }
+
+        // แบ่งยอดที่จ่ายจริงลงแต่ละ line ตามสัดส่วน เพื่อใช้ตัวคูณของหมวดหมู่
+        let lines = sqlx::query(
+            r#"
+            SELECT oi.line_total, b.category
+            FROM order_items oi
+            JOIN books b ON b.id = oi.book_id
+            WHERE oi.order_id = $1
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(&mut **tx)
+        .await?
+        .iter()
+        .map(|row| {
+            let line_total: i64 = row.try_get("line_total")?;
+            Ok(EarnLine {
+                category: row.try_get("category")?,
+                amount: (line_total as i128 * eligible as i128 / subtotal as i128) as i64,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+        let now = Utc::now();
+        let categories = sqlx::query_as::<_, LoyaltyCategoryMultiplierModel>(
+            "SELECT category, multiplier_pct, updated_at FROM loyalty_category_multipliers",
+        )
+        .fetch_all(&mut **tx)
+        .await?
+        .into_iter()
+        .map(LoyaltyCategoryMultiplier::from)
+        .collect::<Vec<_>>();
+        let campaigns = sqlx::query_as::<_, LoyaltyCampaignModel>(&format!(
+            r#"
+            SELECT {}
+            FROM loyalty_campaigns
+            WHERE is_active AND starts_at <= $1 AND ends_at > $1
+            "#,
+            CAMPAIGN_COLUMNS
+        ))
+        .bind(now)
+        .fetch_all(&mut **tx)
+        .await?
+        .into_iter()
+        .map(LoyaltyCampaignEntity::from)
+        .collect::<Vec<_>>();
+
+        let points = points_calculator::points_for(
+            &lines,
+            &categories,
+            &campaigns,
+            &currency,
+            now,
+        );
+        if points <= 0 {
+            return Ok(0);
+        }
+
+        let entry = LoyaltyEntryEntity::new(user_id, points, PointsReason::Earn, Some(order_id), None, None)?;
+        Self::record_in_tx(tx, &entry).await?;
+
+        Ok(points)
+    }
+
+    /// Gives back points spent on a cancelled order and marks those payments
+    /// refunded. Safe to repeat.
+    pub async fn reverse_redemptions_for_order_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: i32,
+        changed_by: Option<i32>,
+    ) -> Result<u64> {
+        // provider_intent_id ของ payment ด้วยแต้ม = id ของ redemption entry
+        let redemptions = sqlx::query(
+            r#"
+            SELECT p.id, p.captured_amount - p.refunded_amount AS remaining, e.user_id
+            FROM payments p
+            JOIN loyalty_point_entries e ON e.id::TEXT = p.provider_intent_id
+            WHERE p.order_id = $1
+              AND p.provider = $2
+              AND p.status IN ('captured', 'partially_refunded')
+              AND e.reason = 'redemption'
+            FOR UPDATE OF p
+            "#,
+        )
+        .bind(order_id)
+        .bind(LOYALTY_PAYMENT_PROVIDER)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        for row in &redemptions {
+            let payment_id: i32 = row.try_get("id")?;
+            let remaining: i64 = row.try_get("remaining")?;
+            let user_id: i32 = row.try_get("user_id")?;
+
+            let entry = LoyaltyEntryEntity::new(
+                user_id,
+                points_to_return(remaining),
+                PointsReason::RedemptionReversal,
+                Some(order_id),
+                Some("Order cancelled".to_string()),
+                changed_by,
+            )?;
+            Self::record_in_tx(tx, &entry).await?;
+
+            sqlx::query(
+                r#"
+                UPDATE payments
+                SET refunded_amount = captured_amount, status = 'refunded', updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(payment_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(redemptions.len() as u64)
+    }
+
+    /// Takes back what is left of the points earned on a cancelled or
+    /// refunded order, never more than the member still has. Safe to repeat.
+    pub async fn revoke_for_order_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: i32,
+        changed_by: Option<i32>,
+    ) -> Result<()> {
+        let earned = sqlx::query(
+            r#"
+            SELECT e.user_id, SUM(e.points)::BIGINT AS net, a.balance
+            FROM loyalty_point_entries e
+            JOIN loyalty_accounts a ON a.user_id = e.user_id
+            WHERE e.order_id = $1 AND e.reason IN ('earn', 'clawback')
+            GROUP BY e.user_id, a.balance
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        if let Some(row) = earned {
+            let user_id: i32 = row.try_get("user_id")?;
+            let net: i64 = row.try_get("net")?;
+            let balance: i64 = row.try_get("balance")?;
+            let points = net.min(balance);
+            if points > 0 {
+                let entry = LoyaltyEntryEntity::new(
+                    user_id,
+                    -points,
+                    PointsReason::Clawback,
+                    Some(order_id),
+                    None,
+                    changed_by,
+                )?;
+                Self::record_in_tx(tx, &entry).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LoyaltyRepository for PostgresLoyaltyRepository {
+    async fn find_balance(&self, user_id: i32) -> Result<i64> {
+        let row = sqlx::query("SELECT balance FROM loyalty_accounts WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match row {
+            Some(row) => Ok(row.try_get("balance")?),
+            None => Ok(0),
+        }
+    }
+
+    async fn balance_before(&self, user_id: i32, before: DateTime<Utc>) -> Result<i64> {
+        let row = sqlx::query(
+            r#"
+            SELECT COALESCE(SUM(points), 0)::BIGINT AS balance
+            FROM loyalty_point_entries
+            WHERE user_id = $1 AND created_at < $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(before)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("balance")?)
+    }
+
+    async fn record(&self, entry: &LoyaltyEntryEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+        let id = Self::record_in_tx(&mut tx, entry).await?;
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    async fn reverse_redemptions_for_order(&self, order_id: i32, changed_by: Option<i32>) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let reversed = Self::reverse_redemptions_for_order_in_tx(&mut tx, order_id, changed_by).await?;
+        tx.commit().await?;
+        Ok(reversed)
+    }
+
+    async fn find_entry(&self, id: i32) -> Result<Option<LoyaltyEntryEntity>> {
+        let result = sqlx::query_as::<_, LoyaltyEntryModel>(&format!(
+            "SELECT {} FROM loyalty_point_entries WHERE id = $1",
+            ENTRY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(LoyaltyEntryEntity::from))
+    }
+
+    async fn find_entries(&self, user_id: i32, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<LoyaltyEntryEntity>> {
+        let results = sqlx::query_as::<_, LoyaltyEntryModel>(&format!(
+            r#"
+            SELECT {}
+            FROM loyalty_point_entries
+            WHERE user_id = $1 AND created_at >= $2 AND created_at < $3
+            ORDER BY created_at ASC, id ASC
+            "#,
+            ENTRY_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(LoyaltyEntryEntity::from).collect())
+    }
+
+    async fn earned_for_order(&self, order_id: i32) -> Result<(i64, i64)> {
+        let row = sqlx::query(
+            r#"
+            SELECT COALESCE(SUM(points) FILTER (WHERE reason = 'earn'), 0)::BIGINT AS earned,
+                   COALESCE(SUM(points), 0)::BIGINT AS net
+            FROM loyalty_point_entries
+            WHERE order_id = $1 AND reason IN ('earn', 'clawback')
+            "#,
+        )
+        .bind(order_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((row.try_get("earned")?, row.try_get("net")?))
+    }
+
+    async fn points_due_to_expire(&self, user_id: i32, at: DateTime<Utc>) -> Result<i64> {
+        // แต้มเก่าสุดถูกใช้ก่อน: ที่หมดอายุ = แต้มที่ครบกำหนดแล้ว - ทุกอย่างที่หักไปแล้ว
+        let row = sqlx::query(
+            r#"
+            SELECT GREATEST(
+                COALESCE(SUM(points) FILTER (WHERE points > 0 AND expires_at <= $2), 0)
+                + COALESCE(SUM(points) FILTER (WHERE points < 0), 0),
+                0
+            )::BIGINT AS due
+            FROM loyalty_point_entries
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("due")?)
+    }
+
+    async fn find_expiring(&self, at: DateTime<Utc>, limit: i64) -> Result<Vec<(i32, i64)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT user_id, due
+            FROM (
+                SELECT user_id,
+                       (COALESCE(SUM(points) FILTER (WHERE points > 0 AND expires_at <= $1), 0)
+                        + COALESCE(SUM(points) FILTER (WHERE points < 0), 0))::BIGINT AS due
+                FROM loyalty_point_entries
+                GROUP BY user_id
+            ) d
+            WHERE due > 0
+            ORDER BY user_id ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(at)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("user_id")?, row.try_get("due")?)))
+            .collect()
+    }
+
+    async fn spend_since(&self, user_id: i32, since: DateTime<Utc>) -> Result<i64> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT COALESCE(SUM(s.amount), 0)::BIGINT AS spend
+            FROM ({}) s
+            WHERE s.user_id = $2
+            "#,
+            ORDER_SPEND_SQL
+        ))
+        .bind(since)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("spend")?)
+    }
+
+    async fn find_member_spend(&self, since: DateTime<Utc>) -> Result<Vec<MemberSpend>> {
+        let tier_roles: Vec<&str> = [LoyaltyTier::Silver, LoyaltyTier::Gold]
+            .iter()
+            .filter_map(|t| t.role_name())
+            .collect();
+
+        let rows = sqlx::query(&format!(
+            r#"
+            WITH spend AS (
+                SELECT s.user_id, SUM(s.amount)::BIGINT AS spend
+                FROM ({}) s
+                GROUP BY s.user_id
+            ),
+            held AS (
+                SELECT ur.user_id, ARRAY_AGG(r.name::TEXT) AS roles
+                FROM user_roles ur
+                JOIN roles r ON r.id = ur.role_id
+                WHERE r.name = ANY($2)
+                GROUP BY ur.user_id
+            )
+            SELECT COALESCE(s.user_id, h.user_id) AS user_id,
+                   COALESCE(s.spend, 0) AS spend,
+                   COALESCE(h.roles, ARRAY[]::TEXT[]) AS roles
+            FROM spend s
+            FULL JOIN held h ON h.user_id = s.user_id
+            ORDER BY 1
+            "#,
+            ORDER_SPEND_SQL
+        ))
+        .bind(since)
+        .bind(&tier_roles)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let roles: Vec<String> = row.try_get("roles")?;
+                Ok(MemberSpend {
+                    user_id: row.try_get("user_id")?,
+                    spend: row.try_get("spend")?,
+                    current_tiers: roles
+                        .iter()
+                        .filter_map(|r| LoyaltyTier::from_role_name(r))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    async fn find_category_multipliers(&self) -> Result<Vec<LoyaltyCategoryMultiplier>> {
+        let results = sqlx::query_as::<_, LoyaltyCategoryMultiplierModel>(
+            r#"
+            SELECT category, multiplier_pct, updated_at
+            FROM loyalty_category_multipliers
+            ORDER BY category ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(LoyaltyCategoryMultiplier::from).collect())
+    }
+
+    async fn save_category_multiplier(&self, multiplier: &LoyaltyCategoryMultiplier) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO loyalty_category_multipliers (category, multiplier_pct, updated_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (category) DO UPDATE
+            SET multiplier_pct = EXCLUDED.multiplier_pct, updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(&multiplier.category)
+        .bind(multiplier.multiplier_pct)
+        .bind(multiplier.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_category_multiplier(&self, category: &str) -> Result<()> {
+        sqlx::query("DELETE FROM loyalty_category_multipliers WHERE category = $1")
+            .bind(category)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_campaigns(&self) -> Result<Vec<LoyaltyCampaignEntity>> {
+        let results = sqlx::query_as::<_, LoyaltyCampaignModel>(&format!(
+            "SELECT {} FROM loyalty_campaigns ORDER BY starts_at DESC, id DESC",
+            CAMPAIGN_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(LoyaltyCampaignEntity::from).collect())
+    }
+
+    async fn find_campaign(&self, id: i32) -> Result<Option<LoyaltyCampaignEntity>> {
+        let result = sqlx::query_as::<_, LoyaltyCampaignModel>(&format!(
+            "SELECT {} FROM loyalty_campaigns WHERE id = $1",
+            CAMPAIGN_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(LoyaltyCampaignEntity::from))
+    }
+
+    async fn save_campaign(&self, campaign: &LoyaltyCampaignEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO loyalty_campaigns
+                (name, category, multiplier_pct, starts_at, ends_at, is_active,
+                 created_by, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(&campaign.name)
+        .bind(&campaign.category)
+        .bind(campaign.multiplier_pct)
+        .bind(campaign.starts_at)
+        .bind(campaign.ends_at)
+        .bind(campaign.is_active)
+        .bind(campaign.created_by)
+        .bind(campaign.created_at)
+        .bind(campaign.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn update_campaign(&self, campaign: &LoyaltyCampaignEntity) -> Result<LoyaltyCampaignEntity> {
+        let result = sqlx::query_as::<_, LoyaltyCampaignModel>(&format!(
+            r#"
+            UPDATE loyalty_campaigns
+            SET is_active = $1, updated_at = $2
+            WHERE id = $3
+            RETURNING {}
+            "#,
+            CAMPAIGN_COLUMNS
+        ))
+        .bind(campaign.is_active)
+        .bind(campaign.updated_at)
+        .bind(campaign.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(LoyaltyCampaignEntity::from(result))
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::domain::{
+    entities::notification_job::NotificationJobEntity,
+    repositories::notification_job_repository::NotificationJobRepository,
+};
+use crate::adapters::postgres::models::notification_job_model::NotificationJobModel;
+
+const JOB_COLUMNS: &str = "id, user_id, kind, payload, status, attempts, last_error, \
+                           run_after, sent_at, created_at";
+
+/// How long a claimed job stays invisible to other workers
+const CLAIM_LEASE_SECONDS: i32 = 300;
+
+pub struct PostgresNotificationJobRepository {
+    pool: PgPool,
+}
+
+impl PostgresNotificationJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationJobRepository for PostgresNotificationJobRepository {
+    async fn claim_due(&self, limit: i64) -> Result<Vec<NotificationJobEntity>> {
+        // SKIP LOCKED ให้ worker หลายตัวดึงงานพร้อมกันได้โดยไม่ชนกัน
+        let results = sqlx::query_as::<_, NotificationJobModel>(&format!(
+            r#"
+            UPDATE notification_jobs
+            SET attempts = attempts + 1,
+                run_after = NOW() + make_interval(secs => $2)
+            WHERE id IN (
+                SELECT id FROM notification_jobs
+                WHERE status = 'pending' AND run_after <= NOW()
+                ORDER BY run_after ASC, id ASC
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(limit)
+        .bind(CLAIM_LEASE_SECONDS)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(NotificationJobEntity::from).collect())
+    }
+
+    async fn update(&self, job: &NotificationJobEntity) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE notification_jobs
+            SET
+                status = $1,
+                last_error = $2,
+                run_after = $3,
+                sent_at = $4
+            WHERE id = $5
+            "#,
+        )
+        .bind(job.status.as_str())
+        .bind(&job.last_error)
+        .bind(job.run_after)
+        .bind(job.sent_at)
+        .bind(job.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
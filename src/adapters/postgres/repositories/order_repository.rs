@@ -0,0 +1,453 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{types::Json, PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::{
+        inventory_movement::{InventoryMovementEntity, MovementReason},
+        order::OrderEntity,
+    },
+    repositories::{inventory_repository::InsufficientStock, order_repository::OrderRepository},
+    value_objects::{order_status::OrderStatus, stock_bucket::StockBucket},
+};
+use crate::adapters::postgres::{
+    models::order_model::{
+        OrderItemModel, OrderModel, OrderPromotionModel, OrderStatusHistoryModel,
+    },
+    repositories::{
+        coupon_repository::PostgresCouponRepository,
+        entitlement_repository::PostgresEntitlementRepository,
+        inventory_repository::PostgresInventoryRepository,
+        loyalty_repository::PostgresLoyaltyRepository,
+    },
+};
+
+const ORDER_COLUMNS: &str = "id, user_id, store_id, status, currency, subtotal, discount_total, \
+                             shipping_total, tax_total, total, shipping_method_id, \
+                             shipping_method_name, shipping_kind, estimated_delivery_from, \
+                             estimated_delivery_to, shipping_address, billing_address, \
+                             tracking_number, release_date, created_at, updated_at";
+
+pub struct PostgresOrderRepository {
+    pool: PgPool,
+}
+
+impl PostgresOrderRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn load_children(&self, order: OrderModel) -> Result<OrderEntity> {
+        let items = sqlx::query_as::<_, OrderItemModel>(
+            r#"
+            SELECT id, order_id, book_id, isbn, title, quantity, unit_price,
+                   price_includes_tax, tax_rate_bps, tax_amount, line_total, lowest_unit_price
+            FROM order_items
+            WHERE order_id = $1
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(order.id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let promotions = sqlx::query_as::<_, OrderPromotionModel>(
+            r#"
+            SELECT id, order_id, promotion_id, name, coupon_id, coupon_code, discount
+            FROM order_promotions
+            WHERE order_id = $1
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(order.id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let history = sqlx::query_as::<_, OrderStatusHistoryModel>(
+            r#"
+            SELECT id, order_id, from_status, to_status, changed_by, note, changed_at
+            FROM order_status_history
+            WHERE order_id = $1
+            ORDER BY changed_at ASC, id ASC
+            "#,
+        )
+        .bind(order.id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(order.into_entity(items, promotions, history))
+    }
+
+    async fn insert_new_history(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: i32,
+        order: &OrderEntity,
+    ) -> Result<()> {
+        for change in order.history.iter().filter(|h| h.id == 0) {
+            sqlx::query(
+                r#"
+                INSERT INTO order_status_history
+                    (order_id, from_status, to_status, changed_by, note, changed_at)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(order_id)
+            .bind(change.from_status.map(|s| s.as_str()))
+            .bind(change.to_status.as_str())
+            .bind(change.changed_by)
+            .bind(&change.note)
+            .bind(change.changed_at)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Order row, line prices and new history entries; what every save of an
+    /// existing order writes. Fails when the stored status is no longer the one
+    /// the order was loaded with, so two transitions cannot both win.
+    async fn update_in_tx(tx: &mut Transaction<'_, Postgres>, order: &OrderEntity) -> Result<OrderModel> {
+        let model = sqlx::query_as::<_, OrderModel>(&format!(
+            r#"
+            UPDATE orders
+            SET
+                status = $1,
+                tracking_number = $2,
+                subtotal = $3,
+                discount_total = $4,
+                tax_total = $5,
+                total = $6,
+                updated_at = $7
+            WHERE id = $8 AND status = $9
+            RETURNING {}
+            "#,
+            ORDER_COLUMNS
+        ))
+        .bind(order.status.as_str())
+        .bind(&order.tracking_number)
+        .bind(order.subtotal)
+        .bind(order.discount_total)
+        .bind(order.tax_total)
+        .bind(order.total)
+        .bind(order.updated_at)
+        .bind(order.id)
+        .bind(order.stored_status().as_str())
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| anyhow!("Order {} was changed by another request", order.id))?;
+
+        // ราคาต่อ line เปลี่ยนได้เฉพาะ price-drop protection ของ pre-order
+        for item in &order.items {
+            sqlx::query(
+                r#"
+                UPDATE order_items
+                SET unit_price = $1, tax_amount = $2, line_total = $3, lowest_unit_price = $4
+                WHERE id = $5 AND order_id = $6
+                "#,
+            )
+            .bind(item.unit_price)
+            .bind(item.tax_amount)
+            .bind(item.line_total)
+            .bind(item.lowest_unit_price)
+            .bind(item.id)
+            .bind(order.id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Self::insert_new_history(tx, order.id, order).await?;
+
+        Ok(model)
+    }
+}
+
+#[async_trait]
+impl OrderRepository for PostgresOrderRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<OrderEntity>> {
+        let order = sqlx::query_as::<_, OrderModel>(&format!(
+            "SELECT {} FROM orders WHERE id = $1",
+            ORDER_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match order {
+            Some(o) => Ok(Some(self.load_children(o).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<OrderEntity>> {
+        let orders = sqlx::query_as::<_, OrderModel>(&format!(
+            "SELECT {} FROM orders WHERE user_id = $1 ORDER BY created_at DESC, id DESC",
+            ORDER_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::with_capacity(orders.len());
+        for order in orders {
+            result.push(self.load_children(order).await?);
+        }
+        Ok(result)
+    }
+
+    async fn find_preordered_book_ids(&self, store_id: i32) -> Result<Vec<i32>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT oi.book_id
+            FROM order_items oi
+            JOIN orders o ON o.id = oi.order_id
+            WHERE o.status = 'preordered'
+              AND o.store_id = $1
+            ORDER BY oi.book_id
+            "#,
+        )
+        .bind(store_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|r| r.try_get("book_id").map_err(Into::into))
+            .collect()
+    }
+
+    async fn record_preorder_price(&self, store_id: i32, book_id: i32, unit_price: i64) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE order_items oi
+            SET lowest_unit_price = $2
+            FROM orders o
+            WHERE o.id = oi.order_id
+              AND o.status = 'preordered'
+              AND o.store_id = $3
+              AND oi.book_id = $1
+              AND oi.lowest_unit_price > $2
+            "#,
+        )
+        .bind(book_id)
+        .bind(unit_price)
+        .bind(store_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn reschedule_preorders(&self, book_id: i32) -> Result<u64> {
+        // วันที่ถูกลบ = วางขายแล้ว ปล่อยในรอบถัดไป
+        let result = sqlx::query(
+            r#"
+            UPDATE orders o
+            SET release_date = latest.release_date, updated_at = NOW()
+            FROM (
+                SELECT oi.order_id, MAX(COALESCE(b.release_date, CURRENT_DATE)) AS release_date
+                FROM order_items oi
+                JOIN books b ON b.id = oi.book_id
+                WHERE oi.order_id IN (SELECT order_id FROM order_items WHERE book_id = $1)
+                GROUP BY oi.order_id
+            ) latest
+            WHERE o.id = latest.order_id
+              AND o.status = 'preordered'
+              AND o.release_date IS DISTINCT FROM latest.release_date
+            "#,
+        )
+        .bind(book_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_preorders_due(&self, on: NaiveDate, limit: i64) -> Result<Vec<OrderEntity>> {
+        let orders = sqlx::query_as::<_, OrderModel>(&format!(
+            r#"
+            SELECT {}
+            FROM orders
+            WHERE status = 'preordered' AND release_date <= $1
+            ORDER BY release_date ASC, id ASC
+            LIMIT $2
+            "#,
+            ORDER_COLUMNS
+        ))
+        .bind(on)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::with_capacity(orders.len());
+        for order in orders {
+            result.push(self.load_children(order).await?);
+        }
+        Ok(result)
+    }
+
+    async fn save(&self, order: &OrderEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO orders
+                (user_id, store_id, status, currency, subtotal, discount_total, shipping_total,
+                 tax_total, total, shipping_method_id, shipping_method_name, shipping_kind,
+                 estimated_delivery_from, estimated_delivery_to, shipping_address,
+                 billing_address, tracking_number, release_date, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16,
+                 $17, $18, $19, $20)
+            RETURNING id
+            "#,
+        )
+        .bind(order.user_id)
+        .bind(order.store_id)
+        .bind(order.status.as_str())
+        .bind(order.currency.as_str())
+        .bind(order.subtotal)
+        .bind(order.discount_total)
+        .bind(order.shipping_total)
+        .bind(order.tax_total)
+        .bind(order.total)
+        .bind(order.shipping.as_ref().and_then(|s| s.method_id))
+        .bind(order.shipping.as_ref().map(|s| s.method_name.as_str()))
+        .bind(order.shipping.as_ref().map(|s| s.kind.as_str()))
+        .bind(order.shipping.as_ref().map(|s| s.estimated_from))
+        .bind(order.shipping.as_ref().map(|s| s.estimated_to))
+        .bind(order.shipping_address.as_ref().map(Json))
+        .bind(order.billing_address.as_ref().map(Json))
+        .bind(&order.tracking_number)
+        .bind(order.release_date)
+        .bind(order.created_at)
+        .bind(order.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        let order_id: i32 = row.try_get("id")?;
+
+        for item in &order.items {
+            // ตัด stock ผ่าน ledger ใน transaction เดียวกับการสร้าง order
+            let sale = InventoryMovementEntity::new(
+                item.book_id,
+                StockBucket::Available,
+                -item.quantity.value(),
+                MovementReason::Sale,
+                Some(format!("order:{}", order_id)),
+                Some(order.user_id),
+            )?;
+            PostgresInventoryRepository::record_in_tx(&mut tx, &sale)
+                .await
+                .map_err(|e| match e.downcast_ref::<InsufficientStock>() {
+                    Some(_) => anyhow!("Insufficient stock for '{}'", item.title),
+                    None => e,
+                })?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO order_items
+                    (order_id, book_id, isbn, title, quantity, unit_price,
+                     price_includes_tax, tax_rate_bps, tax_amount, line_total,
+                     lowest_unit_price)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+            )
+            .bind(order_id)
+            .bind(item.book_id)
+            .bind(&item.isbn)
+            .bind(&item.title)
+            .bind(item.quantity.value())
+            .bind(item.unit_price)
+            .bind(item.price_includes_tax)
+            .bind(item.tax_rate_bps)
+            .bind(item.tax_amount)
+            .bind(item.line_total)
+            .bind(item.lowest_unit_price)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for promotion in &order.promotions {
+            sqlx::query(
+                r#"
+                INSERT INTO order_promotions
+                    (order_id, promotion_id, name, coupon_id, coupon_code, discount)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(order_id)
+            .bind(promotion.promotion_id)
+            .bind(&promotion.name)
+            .bind(promotion.coupon_id)
+            .bind(&promotion.coupon_code)
+            .bind(promotion.discount)
+            .execute(&mut *tx)
+            .await?;
+
+            if let Some(coupon_id) = promotion.coupon_id {
+                PostgresCouponRepository::redeem_in_tx(&mut tx, coupon_id, order.user_id, order_id)
+                    .await?;
+            }
+        }
+
+        Self::insert_new_history(&mut tx, order_id, order).await?;
+        tx.commit().await?;
+
+        Ok(order_id)
+    }
+
+    async fn update(&self, order: &OrderEntity) -> Result<OrderEntity> {
+        let mut tx = self.pool.begin().await?;
+        let model = Self::update_in_tx(&mut tx, order).await?;
+        tx.commit().await?;
+
+        self.load_children(model).await
+    }
+
+    async fn update_paid(&self, order: &OrderEntity) -> Result<OrderEntity> {
+        if order.status != OrderStatus::Paid {
+            return Err(anyhow!("Order {} is {}, not paid", order.id, order.status));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let model = Self::update_in_tx(&mut tx, order).await?;
+        PostgresEntitlementRepository::grant_for_order_in_tx(&mut tx, order.id).await?;
+        PostgresLoyaltyRepository::award_for_order_in_tx(&mut tx, order.id).await?;
+        tx.commit().await?;
+
+        self.load_children(model).await
+    }
+
+    async fn update_closed(&self, order: &OrderEntity) -> Result<OrderEntity> {
+        if !matches!(order.status, OrderStatus::Cancelled | OrderStatus::Refunded) {
+            return Err(anyhow!("Order {} is {}, not cancelled or refunded", order.id, order.status));
+        }
+        let changed_by = order.history.last().and_then(|h| h.changed_by);
+        let release_stock = order.status == OrderStatus::Cancelled && order.stored_status().holds_stock();
+
+        let mut tx = self.pool.begin().await?;
+        let model = Self::update_in_tx(&mut tx, order).await?;
+        if release_stock {
+            for item in &order.items {
+                let movement = InventoryMovementEntity::new(
+                    item.book_id,
+                    StockBucket::Available,
+                    item.quantity.value(),
+                    MovementReason::OrderCancelled,
+                    Some(format!("order:{}", order.id)),
+                    changed_by,
+                )?;
+                PostgresInventoryRepository::record_in_tx(&mut tx, &movement).await?;
+            }
+        }
+        PostgresEntitlementRepository::revoke_for_order_in_tx(&mut tx, order.id, order.status.as_str())
+            .await?;
+        PostgresLoyaltyRepository::revoke_for_order_in_tx(&mut tx, order.id, changed_by)
+            .await?;
+        tx.commit().await?;
+
+        self.load_children(model).await
+    }
+}
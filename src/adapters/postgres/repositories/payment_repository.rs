@@ -0,0 +1,164 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use crate::domain::{
+    entities::payment::PaymentEntity,
+    repositories::payment_repository::PaymentRepository,
+};
+use crate::adapters::postgres::models::payment_model::PaymentModel;
+
+pub struct PostgresPaymentRepository {
+    pool: PgPool,
+}
+
+impl PostgresPaymentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PaymentRepository for PostgresPaymentRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<PaymentEntity>> {
+        let result = sqlx::query_as::<_, PaymentModel>(
+            r#"
+            SELECT id, order_id, provider, provider_intent_id, amount, currency, status,
+                   captured_amount, refunded_amount, next_action_url, failure_reason,
+                   created_at, updated_at
+            FROM payments
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(PaymentEntity::from))
+    }
+
+    async fn find_by_order(&self, order_id: i32) -> Result<Vec<PaymentEntity>> {
+        let results = sqlx::query_as::<_, PaymentModel>(
+            r#"
+            SELECT id, order_id, provider, provider_intent_id, amount, currency, status,
+                   captured_amount, refunded_amount, next_action_url, failure_reason,
+                   created_at, updated_at
+            FROM payments
+            WHERE order_id = $1
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(PaymentEntity::from).collect())
+    }
+
+    async fn find_by_intent(&self, provider: &str, intent_id: &str) -> Result<Option<PaymentEntity>> {
+        let result = sqlx::query_as::<_, PaymentModel>(
+            r#"
+            SELECT id, order_id, provider, provider_intent_id, amount, currency, status,
+                   captured_amount, refunded_amount, next_action_url, failure_reason,
+                   created_at, updated_at
+            FROM payments
+            WHERE provider = $1 AND provider_intent_id = $2
+            "#,
+        )
+        .bind(provider)
+        .bind(intent_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(PaymentEntity::from))
+    }
+
+    async fn save(&self, payment: &PaymentEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO payments
+                (order_id, provider, provider_intent_id, amount, currency, status,
+                 captured_amount, refunded_amount, next_action_url, failure_reason,
+                 created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING id
+            "#,
+        )
+        .bind(payment.order_id)
+        .bind(&payment.provider)
+        .bind(&payment.provider_intent_id)
+        .bind(payment.amount)
+        .bind(&payment.currency)
+        .bind(payment.status.as_str())
+        .bind(payment.captured_amount)
+        .bind(payment.refunded_amount)
+        .bind(&payment.next_action_url)
+        .bind(&payment.failure_reason)
+        .bind(payment.created_at)
+        .bind(payment.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn update(&self, payment: &PaymentEntity) -> Result<PaymentEntity> {
+        let result = sqlx::query_as::<_, PaymentModel>(
+            r#"
+            UPDATE payments
+            SET
+                provider_intent_id = $1,
+                status = $2,
+                captured_amount = $3,
+                refunded_amount = $4,
+                next_action_url = $5,
+                failure_reason = $6,
+                updated_at = $7
+            WHERE id = $8
+            RETURNING id, order_id, provider, provider_intent_id, amount, currency, status,
+                      captured_amount, refunded_amount, next_action_url, failure_reason,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(&payment.provider_intent_id)
+        .bind(payment.status.as_str())
+        .bind(payment.captured_amount)
+        .bind(payment.refunded_amount)
+        .bind(&payment.next_action_url)
+        .bind(&payment.failure_reason)
+        .bind(payment.updated_at)
+        .bind(payment.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(PaymentEntity::from(result))
+    }
+
+    async fn record_webhook_event(&self, provider: &str, event_id: &str, kind: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO payment_webhook_events (provider, event_id, event_type)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (provider, event_id) DO NOTHING
+            "#,
+        )
+        .bind(provider)
+        .bind(event_id)
+        .bind(kind)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn forget_webhook_event(&self, provider: &str, event_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM payment_webhook_events WHERE provider = $1 AND event_id = $2")
+            .bind(provider)
+            .bind(event_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{types::Json, PgPool, Row};
+
+use crate::domain::{
+    entities::promotion::PromotionEntity,
+    repositories::promotion_repository::PromotionRepository,
+};
+use crate::adapters::postgres::models::promotion_model::PromotionModel;
+
+const PROMOTION_COLUMNS: &str = "id, name, conditions, action, priority, stackable, \
+                                 requires_coupon, is_active, created_at, updated_at";
+
+pub struct PostgresPromotionRepository {
+    pool: PgPool,
+}
+
+impl PostgresPromotionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PromotionRepository for PostgresPromotionRepository {
+    async fn find_all(&self) -> Result<Vec<PromotionEntity>> {
+        let results = sqlx::query_as::<_, PromotionModel>(&format!(
+            "SELECT {} FROM promotions ORDER BY priority DESC, id ASC",
+            PROMOTION_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(PromotionEntity::from).collect())
+    }
+
+    async fn find_active(&self) -> Result<Vec<PromotionEntity>> {
+        let results = sqlx::query_as::<_, PromotionModel>(&format!(
+            "SELECT {} FROM promotions WHERE is_active ORDER BY priority DESC, id ASC",
+            PROMOTION_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(PromotionEntity::from).collect())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<PromotionEntity>> {
+        let result = sqlx::query_as::<_, PromotionModel>(&format!(
+            "SELECT {} FROM promotions WHERE id = $1",
+            PROMOTION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(PromotionEntity::from))
+    }
+
+    async fn save(&self, promotion: &PromotionEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO promotions
+                (name, conditions, action, priority, stackable,
+                 requires_coupon, is_active, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(&promotion.name)
+        .bind(Json(&promotion.conditions))
+        .bind(Json(&promotion.action))
+        .bind(promotion.priority)
+        .bind(promotion.stackable)
+        .bind(promotion.requires_coupon)
+        .bind(promotion.is_active)
+        .bind(promotion.created_at)
+        .bind(promotion.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn update(&self, promotion: &PromotionEntity) -> Result<PromotionEntity> {
+        let result = sqlx::query_as::<_, PromotionModel>(&format!(
+            r#"
+            UPDATE promotions
+            SET
+                name = $1,
+                conditions = $2,
+                action = $3,
+                priority = $4,
+                stackable = $5,
+                requires_coupon = $6,
+                is_active = $7,
+                updated_at = $8
+            WHERE id = $9
+            RETURNING {}
+            "#,
+            PROMOTION_COLUMNS
+        ))
+        .bind(&promotion.name)
+        .bind(Json(&promotion.conditions))
+        .bind(Json(&promotion.action))
+        .bind(promotion.priority)
+        .bind(promotion.stackable)
+        .bind(promotion.requires_coupon)
+        .bind(promotion.is_active)
+        .bind(promotion.updated_at)
+        .bind(promotion.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(PromotionEntity::from(result))
+    }
+}
@@ -0,0 +1,195 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::adapters::postgres::models::report_model::{
+    CustomerSalesModel, RevenuePeriodModel, SalesTotalsModel, UnitsSoldModel,
+};
+use crate::domain::repositories::report_repository::{
+    CustomerSales, ReportGranularity, ReportRepository, RevenuePeriod, SalesDimension, SalesFilter,
+    SalesTotals, UnitsSold,
+};
+
+/// Refreshed in this order by `refresh`
+const REPORT_VIEWS: [&str; 3] = [
+    "report_sales_daily",
+    "report_book_sales_daily",
+    "report_customer_sales_daily",
+];
+
+/// Sums of `report_sales_daily` columns, as `SalesTotalsModel` reads them
+const TOTALS_COLUMNS: &str = "currency, \
+                              SUM(orders)::bigint AS orders, \
+                              SUM(units)::bigint AS units, \
+                              SUM(revenue)::bigint AS revenue, \
+                              SUM(discount_total)::bigint AS discount_total, \
+                              SUM(shipping_total)::bigint AS shipping_total, \
+                              SUM(tax_total)::bigint AS tax_total, \
+                              SUM(refunded_orders)::bigint AS refunded_orders, \
+                              SUM(refunded_amount)::bigint AS refunded_amount";
+
+pub struct PostgresReportRepository {
+    pool: PgPool,
+}
+
+impl PostgresReportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Date range and store condition on the view aliased `alias`; binds $1
+/// (from), $2 (to) and $3 (store)
+fn filter_condition(alias: &str) -> String {
+    format!(
+        "{0}.sales_date BETWEEN $1 AND $2 AND ($3::INTEGER IS NULL OR {0}.store_id = $3)",
+        alias
+    )
+}
+
+/// (key, book_id) expressions of a breakdown over `report_book_sales_daily r`
+/// joined with `stores s`
+fn dimension_columns(dimension: SalesDimension) -> (&'static str, &'static str) {
+    match dimension {
+        SalesDimension::Title => ("r.title", "r.book_id"),
+        SalesDimension::Author => ("r.author", "NULL::INTEGER"),
+        SalesDimension::Category => ("r.category", "NULL::INTEGER"),
+        SalesDimension::Store => ("s.code", "NULL::INTEGER"),
+    }
+}
+
+#[async_trait]
+impl ReportRepository for PostgresReportRepository {
+    async fn sales_totals(&self, filter: &SalesFilter) -> Result<Vec<SalesTotals>> {
+        let rows = sqlx::query_as::<_, SalesTotalsModel>(&format!(
+            "SELECT {} FROM report_sales_daily r WHERE {} GROUP BY currency ORDER BY currency",
+            TOTALS_COLUMNS,
+            filter_condition("r")
+        ))
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.store_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(SalesTotals::from).collect())
+    }
+
+    async fn revenue_series(
+        &self,
+        filter: &SalesFilter,
+        granularity: ReportGranularity,
+    ) -> Result<Vec<RevenuePeriod>> {
+        let rows = sqlx::query_as::<_, RevenuePeriodModel>(&format!(
+            r#"
+            SELECT date_trunc('{}', sales_date)::date AS period_start, {}
+            FROM report_sales_daily r
+            WHERE {}
+            GROUP BY period_start, currency
+            ORDER BY period_start, currency
+            "#,
+            granularity.as_str(),
+            TOTALS_COLUMNS,
+            filter_condition("r")
+        ))
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.store_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(RevenuePeriod::from).collect())
+    }
+
+    async fn units_sold(
+        &self,
+        filter: &SalesFilter,
+        dimension: SalesDimension,
+        limit: Option<i64>,
+    ) -> Result<Vec<UnitsSold>> {
+        let (key, book_id) = dimension_columns(dimension);
+        let rows = sqlx::query_as::<_, UnitsSoldModel>(&format!(
+            r#"
+            SELECT {} AS key, {} AS book_id, r.currency,
+                   SUM(r.units)::bigint AS units, SUM(r.revenue)::bigint AS revenue
+            FROM report_book_sales_daily r
+            JOIN stores s ON s.id = r.store_id
+            WHERE {}
+            GROUP BY 1, 2, 3
+            ORDER BY units DESC, revenue DESC, key ASC NULLS LAST
+            LIMIT $4
+            "#,
+            key,
+            book_id,
+            filter_condition("r")
+        ))
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.store_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(UnitsSold::from).collect())
+    }
+
+    async fn top_customers(&self, filter: &SalesFilter, limit: Option<i64>) -> Result<Vec<CustomerSales>> {
+        let rows = sqlx::query_as::<_, CustomerSalesModel>(&format!(
+            r#"
+            SELECT r.user_id, u.email, u.fname, u.lname, r.currency,
+                   SUM(r.orders)::bigint AS orders, SUM(r.revenue)::bigint AS revenue
+            FROM report_customer_sales_daily r
+            JOIN users u ON u.id = r.user_id
+            WHERE {}
+            GROUP BY r.user_id, u.email, u.fname, u.lname, r.currency
+            ORDER BY revenue DESC, orders DESC, r.user_id ASC
+            LIMIT $4
+            "#,
+            filter_condition("r")
+        ))
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.store_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(CustomerSales::from).collect())
+    }
+
+    async fn refreshed_at(&self) -> Result<Option<DateTime<Utc>>> {
+        let refreshed_at = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT MIN(refreshed_at) FROM report_refreshes WHERE view_name = ANY($1)",
+        )
+        .bind(&REPORT_VIEWS[..])
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(refreshed_at)
+    }
+
+    async fn refresh(&self) -> Result<DateTime<Utc>> {
+        let started_at = Utc::now();
+        // ทีละ view นอก transaction: CONCURRENTLY ไม่ล็อกคนอ่าน แต่ใช้เวลานานกว่า
+        for view in REPORT_VIEWS {
+            sqlx::query(&format!("REFRESH MATERIALIZED VIEW CONCURRENTLY {}", view))
+                .execute(&self.pool)
+                .await?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO report_refreshes (view_name, refreshed_at)
+                VALUES ($1, $2)
+                ON CONFLICT (view_name) DO UPDATE SET refreshed_at = EXCLUDED.refreshed_at
+                "#,
+            )
+            .bind(view)
+            .bind(started_at)
+            .execute(&self.pool)
+            .await?;
+        }
+
+        Ok(started_at)
+    }
+}
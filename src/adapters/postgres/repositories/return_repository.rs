@@ -0,0 +1,297 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use crate::domain::{
+    entities::{
+        inventory_movement::InventoryMovementEntity,
+        loyalty_entry::LoyaltyEntryEntity,
+        return_request::{ReturnRefundEntity, ReturnRequestEntity},
+    },
+    repositories::return_repository::{RefundCredit, ReturnRepository},
+};
+use crate::adapters::postgres::{
+    models::return_request_model::{ReturnRefundModel, ReturnRequestModel},
+    repositories::{
+        inventory_repository::PostgresInventoryRepository,
+        loyalty_repository::PostgresLoyaltyRepository,
+        stored_value_repository::PostgresStoredValueRepository,
+    },
+};
+
+const RETURN_COLUMNS: &str = "id, order_id, order_item_id, book_id, user_id, quantity, reason, \
+                              status, reviewed_by, review_note, condition, refund_amount, \
+                              created_at, updated_at";
+
+const REFUND_COLUMNS: &str = "id, return_id, payment_id, amount, completed_at";
+
+pub struct PostgresReturnRepository {
+    pool: PgPool,
+}
+
+impl PostgresReturnRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReturnRepository for PostgresReturnRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<ReturnRequestEntity>> {
+        let result = sqlx::query_as::<_, ReturnRequestModel>(&format!(
+            "SELECT {} FROM return_requests WHERE id = $1",
+            RETURN_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(ReturnRequestEntity::from))
+    }
+
+    async fn find_by_order(&self, order_id: i32) -> Result<Vec<ReturnRequestEntity>> {
+        let results = sqlx::query_as::<_, ReturnRequestModel>(&format!(
+            "SELECT {} FROM return_requests WHERE order_id = $1 ORDER BY id ASC",
+            RETURN_COLUMNS
+        ))
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(ReturnRequestEntity::from).collect())
+    }
+
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<ReturnRequestEntity>> {
+        let results = sqlx::query_as::<_, ReturnRequestModel>(&format!(
+            "SELECT {} FROM return_requests WHERE user_id = $1 ORDER BY created_at DESC",
+            RETURN_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(ReturnRequestEntity::from).collect())
+    }
+
+    async fn save(&self, request: &ReturnRequestEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO return_requests
+                (order_id, order_item_id, book_id, user_id, quantity, reason, status,
+                 reviewed_by, review_note, condition, refund_amount, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING id
+            "#,
+        )
+        .bind(request.order_id)
+        .bind(request.order_item_id)
+        .bind(request.book_id)
+        .bind(request.user_id)
+        .bind(request.quantity.value())
+        .bind(&request.reason)
+        .bind(request.status.as_str())
+        .bind(request.reviewed_by)
+        .bind(&request.review_note)
+        .bind(request.condition.map(|c| c.as_str()))
+        .bind(request.refund_amount)
+        .bind(request.created_at)
+        .bind(request.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn update(&self, request: &ReturnRequestEntity) -> Result<ReturnRequestEntity> {
+        let result = sqlx::query_as::<_, ReturnRequestModel>(&format!(
+            r#"
+            UPDATE return_requests
+            SET
+                status = $1,
+                reviewed_by = $2,
+                review_note = $3,
+                condition = $4,
+                refund_amount = $5,
+                updated_at = $6
+            WHERE id = $7
+            RETURNING {}
+            "#,
+            RETURN_COLUMNS
+        ))
+        .bind(request.status.as_str())
+        .bind(request.reviewed_by)
+        .bind(&request.review_note)
+        .bind(request.condition.map(|c| c.as_str()))
+        .bind(request.refund_amount)
+        .bind(request.updated_at)
+        .bind(request.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(ReturnRequestEntity::from(result))
+    }
+
+    async fn receive(&self, request: &ReturnRequestEntity, movement: &InventoryMovementEntity) -> Result<ReturnRequestEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query_as::<_, ReturnRequestModel>(&format!(
+            r#"
+            UPDATE return_requests
+            SET status = $1, condition = $2, updated_at = $3
+            WHERE id = $4 AND status = 'approved'
+            RETURNING {}
+            "#,
+            RETURN_COLUMNS
+        ))
+        .bind(request.status.as_str())
+        .bind(request.condition.map(|c| c.as_str()))
+        .bind(request.updated_at)
+        .bind(request.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(result) = result else {
+            bail!("Return request {} is no longer awaiting its parcel", request.id);
+        };
+
+        PostgresInventoryRepository::record_in_tx(&mut tx, movement).await?;
+
+        tx.commit().await?;
+        Ok(ReturnRequestEntity::from(result))
+    }
+
+    async fn start_refund(&self, request: &ReturnRequestEntity, refunds: &[ReturnRefundEntity]) -> Result<ReturnRequestEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        // เปลี่ยนจาก received เท่านั้น: staff สองคนกดพร้อมกันจะได้แผนเดียว
+        let result = sqlx::query_as::<_, ReturnRequestModel>(&format!(
+            r#"
+            UPDATE return_requests
+            SET status = $1, refund_amount = $2, updated_at = $3
+            WHERE id = $4 AND status = 'received'
+            RETURNING {}
+            "#,
+            RETURN_COLUMNS
+        ))
+        .bind(request.status.as_str())
+        .bind(request.refund_amount)
+        .bind(request.updated_at)
+        .bind(request.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(result) = result else {
+            bail!("Return request {} is no longer awaiting a refund", request.id);
+        };
+
+        for refund in refunds {
+            sqlx::query(
+                r#"
+                INSERT INTO return_refunds (return_id, payment_id, amount)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(refund.return_id)
+            .bind(refund.payment_id)
+            .bind(refund.amount)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(ReturnRequestEntity::from(result))
+    }
+
+    async fn find_refunds(&self, return_id: i32) -> Result<Vec<ReturnRefundEntity>> {
+        let results = sqlx::query_as::<_, ReturnRefundModel>(&format!(
+            "SELECT {} FROM return_refunds WHERE return_id = $1 ORDER BY id ASC",
+            REFUND_COLUMNS
+        ))
+        .bind(return_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(ReturnRefundEntity::from).collect())
+    }
+
+    async fn complete_refund(&self, refund: &ReturnRefundEntity, credit: RefundCredit) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let completed = sqlx::query(
+            "UPDATE return_refunds SET completed_at = NOW() WHERE id = $1 AND completed_at IS NULL",
+        )
+        .bind(refund.id)
+        .execute(&mut *tx)
+        .await?;
+        if completed.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        if let Some(payment_id) = refund.payment_id {
+            let updated = sqlx::query(
+                r#"
+                UPDATE payments
+                SET refunded_amount = refunded_amount + $1,
+                    status = CASE WHEN refunded_amount + $1 = captured_amount
+                                  THEN 'refunded' ELSE 'partially_refunded' END,
+                    updated_at = NOW()
+                WHERE id = $2
+                  AND status IN ('captured', 'partially_refunded')
+                  AND refunded_amount + $1 <= captured_amount
+                "#,
+            )
+            .bind(refund.amount)
+            .bind(payment_id)
+            .execute(&mut *tx)
+            .await?;
+            if updated.rows_affected() == 0 {
+                bail!("Payment {} cannot take a refund of {}", payment_id, refund.amount);
+            }
+        }
+
+        match &credit {
+            RefundCredit::Provider => {}
+            RefundCredit::Balance(entry) => {
+                PostgresStoredValueRepository::record_in_tx(&mut tx, entry).await?;
+            }
+            RefundCredit::Points(entry) => {
+                PostgresLoyaltyRepository::record_in_tx(&mut tx, entry).await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn finish_refund(
+        &self,
+        request: &ReturnRequestEntity,
+        clawback: Option<&LoyaltyEntryEntity>,
+    ) -> Result<ReturnRequestEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query_as::<_, ReturnRequestModel>(&format!(
+            r#"
+            UPDATE return_requests
+            SET status = $1, updated_at = $2
+            WHERE id = $3 AND status = 'refunding'
+            RETURNING {}
+            "#,
+            RETURN_COLUMNS
+        ))
+        .bind(request.status.as_str())
+        .bind(request.updated_at)
+        .bind(request.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(result) = result else {
+            bail!("Return request {} is no longer refunding", request.id);
+        };
+
+        if let Some(entry) = clawback {
+            PostgresLoyaltyRepository::record_in_tx(&mut tx, entry).await?;
+        }
+
+        tx.commit().await?;
+        Ok(ReturnRequestEntity::from(result))
+    }
+}
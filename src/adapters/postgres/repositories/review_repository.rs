@@ -0,0 +1,232 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::review::ReviewEntity,
+    repositories::review_repository::ReviewRepository,
+    value_objects::review_status::ReviewStatus,
+};
+use crate::adapters::postgres::models::review_model::ReviewModel;
+
+const REVIEW_COLUMNS: &str = "id, book_id, user_id, order_id, rating, title, body, \
+                              verified_purchase, status, rejection_reason, moderated_by, \
+                              moderated_at, helpful_count, unhelpful_count, created_at, updated_at";
+
+pub struct PostgresReviewRepository {
+    pool: PgPool,
+}
+
+impl PostgresReviewRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Recomputes the book's denormalized rating from its approved reviews
+    async fn refresh_book_rating_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        book_id: i32,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE books
+            SET (rating_count, rating_total) = (
+                SELECT COUNT(*), COALESCE(SUM(rating), 0)
+                FROM reviews
+                WHERE book_id = $1 AND status = 'approved'
+            )
+            WHERE id = $1
+            "#,
+        )
+        .bind(book_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReviewRepository for PostgresReviewRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<ReviewEntity>> {
+        let result = sqlx::query_as::<_, ReviewModel>(&format!(
+            "SELECT {} FROM reviews WHERE id = $1",
+            REVIEW_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(ReviewEntity::from))
+    }
+
+    async fn find_by_book(&self, book_id: i32, status: ReviewStatus) -> Result<Vec<ReviewEntity>> {
+        let results = sqlx::query_as::<_, ReviewModel>(&format!(
+            r#"
+            SELECT {} FROM reviews
+            WHERE book_id = $1 AND status = $2
+            ORDER BY helpful_count - unhelpful_count DESC, created_at DESC, id DESC
+            "#,
+            REVIEW_COLUMNS
+        ))
+        .bind(book_id)
+        .bind(status.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(ReviewEntity::from).collect())
+    }
+
+    async fn find_by_status(&self, status: ReviewStatus) -> Result<Vec<ReviewEntity>> {
+        let results = sqlx::query_as::<_, ReviewModel>(&format!(
+            "SELECT {} FROM reviews WHERE status = $1 ORDER BY updated_at ASC, id ASC",
+            REVIEW_COLUMNS
+        ))
+        .bind(status.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(ReviewEntity::from).collect())
+    }
+
+    async fn find_by_user_and_book(&self, user_id: i32, book_id: i32) -> Result<Option<ReviewEntity>> {
+        let result = sqlx::query_as::<_, ReviewModel>(&format!(
+            "SELECT {} FROM reviews WHERE user_id = $1 AND book_id = $2",
+            REVIEW_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(book_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(ReviewEntity::from))
+    }
+
+    async fn save(&self, review: &ReviewEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO reviews
+                (book_id, user_id, order_id, rating, title, body, verified_purchase, status,
+                 rejection_reason, moderated_by, moderated_at, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING id
+            "#,
+        )
+        .bind(review.book_id)
+        .bind(review.user_id)
+        .bind(review.order_id)
+        .bind(review.rating.value())
+        .bind(&review.title)
+        .bind(&review.body)
+        .bind(review.verified_purchase)
+        .bind(review.status.as_str())
+        .bind(&review.rejection_reason)
+        .bind(review.moderated_by)
+        .bind(review.moderated_at)
+        .bind(review.created_at)
+        .bind(review.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::refresh_book_rating_in_tx(&mut tx, review.book_id).await?;
+        tx.commit().await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn update(&self, review: &ReviewEntity) -> Result<ReviewEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query_as::<_, ReviewModel>(&format!(
+            r#"
+            UPDATE reviews
+            SET
+                rating = $1,
+                title = $2,
+                body = $3,
+                status = $4,
+                rejection_reason = $5,
+                moderated_by = $6,
+                moderated_at = $7,
+                updated_at = $8
+            WHERE id = $9
+            RETURNING {}
+            "#,
+            REVIEW_COLUMNS
+        ))
+        .bind(review.rating.value())
+        .bind(&review.title)
+        .bind(&review.body)
+        .bind(review.status.as_str())
+        .bind(&review.rejection_reason)
+        .bind(review.moderated_by)
+        .bind(review.moderated_at)
+        .bind(review.updated_at)
+        .bind(review.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::refresh_book_rating_in_tx(&mut tx, review.book_id).await?;
+        tx.commit().await?;
+
+        Ok(ReviewEntity::from(result))
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query("DELETE FROM reviews WHERE id = $1 RETURNING book_id")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if let Some(row) = row {
+            Self::refresh_book_rating_in_tx(&mut tx, row.try_get("book_id")?).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn vote(&self, review_id: i32, user_id: i32, helpful: bool) -> Result<ReviewEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO review_votes (review_id, user_id, helpful)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (review_id, user_id) DO UPDATE SET helpful = EXCLUDED.helpful, voted_at = NOW()
+            "#,
+        )
+        .bind(review_id)
+        .bind(user_id)
+        .bind(helpful)
+        .execute(&mut *tx)
+        .await?;
+
+        // นับใหม่ทั้งหมดแทนการ +1/-1 เพื่อให้การเปลี่ยนโหวตถูกต้องเสมอ
+        let result = sqlx::query_as::<_, ReviewModel>(&format!(
+            r#"
+            UPDATE reviews
+            SET (helpful_count, unhelpful_count) = (
+                SELECT COUNT(*) FILTER (WHERE helpful), COUNT(*) FILTER (WHERE NOT helpful)
+                FROM review_votes
+                WHERE review_id = $1
+            )
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            REVIEW_COLUMNS
+        ))
+        .bind(review_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| anyhow!("Review not found"))?;
+
+        tx.commit().await?;
+
+        Ok(ReviewEntity::from(result))
+    }
+}
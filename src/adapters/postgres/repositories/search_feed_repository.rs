@@ -0,0 +1,97 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::domain::repositories::search_feed_repository::{
+    BookChange, ChangeCursor, SearchDocument, SearchFeedRepository,
+};
+use crate::adapters::postgres::models::search_feed_model::{BookChangeModel, SearchDocumentModel};
+
+/// Copies sold in the last 90 days count towards the sales boost
+const SEARCH_DOCUMENT_SELECT: &str = r#"
+    SELECT b.id, b.isbn, b.title, b.author, b.description, b.category, b.publisher,
+           b.stock_quantity,
+           COALESCE((
+               SELECT SUM(oi.quantity)
+               FROM order_items oi
+               JOIN orders o ON o.id = oi.order_id
+               WHERE oi.book_id = b.id
+                 AND o.status IN ('paid', 'picking', 'shipped', 'delivered')
+                 AND o.created_at > NOW() - INTERVAL '90 days'
+           ), 0)::BIGINT AS sales_count
+    FROM books b
+"#;
+
+pub struct PostgresSearchFeedRepository {
+    pool: PgPool,
+}
+
+impl PostgresSearchFeedRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SearchFeedRepository for PostgresSearchFeedRepository {
+    async fn latest_cursor(&self) -> Result<ChangeCursor> {
+        // ทุก transaction ก่อน xmin จบไปแล้ว ส่วนที่ยังค้างอยู่จะถูกอ่านซ้ำภายหลัง
+        let xmin: i64 = sqlx::query_scalar(
+            "SELECT pg_snapshot_xmin(pg_current_snapshot())::TEXT::BIGINT",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(ChangeCursor {
+            txid: xmin - 1,
+            id: i64::MAX,
+        })
+    }
+
+    async fn find_changes_after(&self, cursor: ChangeCursor, limit: i64) -> Result<Vec<BookChange>> {
+        // อ่านเฉพาะ transaction ที่เก่ากว่า xmin ของ snapshot ปัจจุบัน (commit หรือ
+        // rollback ไปแล้วทั้งหมด) เหตุการณ์ที่จะ commit ทีหลังจึงไม่มีทางอยู่หลัง cursor
+        let results = sqlx::query_as::<_, BookChangeModel>(
+            r#"
+            SELECT id, txid, book_id
+            FROM book_change_events
+            WHERE (txid, id) > ($1, $2)
+              AND txid < pg_snapshot_xmin(pg_current_snapshot())::TEXT::BIGINT
+            ORDER BY txid ASC, id ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(cursor.txid)
+        .bind(cursor.id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(BookChange::from).collect())
+    }
+
+    async fn find_documents(&self, book_ids: &[i32]) -> Result<Vec<SearchDocument>> {
+        let results = sqlx::query_as::<_, SearchDocumentModel>(&format!(
+            "{} WHERE b.is_active AND b.id = ANY($1) ORDER BY b.id ASC",
+            SEARCH_DOCUMENT_SELECT
+        ))
+        .bind(book_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(SearchDocument::from).collect())
+    }
+
+    async fn find_documents_after(&self, after_book_id: i32, limit: i64) -> Result<Vec<SearchDocument>> {
+        let results = sqlx::query_as::<_, SearchDocumentModel>(&format!(
+            "{} WHERE b.is_active AND b.id > $1 ORDER BY b.id ASC LIMIT $2",
+            SEARCH_DOCUMENT_SELECT
+        ))
+        .bind(after_book_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(SearchDocument::from).collect())
+    }
+}
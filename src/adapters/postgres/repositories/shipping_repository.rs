@@ -0,0 +1,211 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use crate::domain::{
+    entities::shipping_zone::{ShippingMethodEntity, ShippingZoneEntity},
+    repositories::shipping_repository::ShippingRepository,
+};
+use crate::adapters::postgres::models::shipping_model::{
+    ShippingMethodModel, ShippingRateModel, ShippingZoneModel,
+};
+
+const ZONE_COLUMNS: &str = "id, name, country, postal_prefixes, is_active, created_at, updated_at";
+
+pub struct PostgresShippingRepository {
+    pool: PgPool,
+}
+
+impl PostgresShippingRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Loads methods and rate tables for all zones in two queries
+    async fn load_methods(&self, zones: Vec<ShippingZoneModel>) -> Result<Vec<ShippingZoneEntity>> {
+        let zone_ids: Vec<i32> = zones.iter().map(|z| z.id).collect();
+
+        let methods = sqlx::query_as::<_, ShippingMethodModel>(
+            r#"
+            SELECT id, zone_id, code, name, kind, rate_basis, min_days, max_days,
+                   max_weight_grams, is_active, created_at, updated_at
+            FROM shipping_methods
+            WHERE zone_id = ANY($1)
+            ORDER BY zone_id ASC, min_days ASC, id ASC
+            "#,
+        )
+        .bind(&zone_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let method_ids: Vec<i32> = methods.iter().map(|m| m.id).collect();
+        let mut rates = sqlx::query_as::<_, ShippingRateModel>(
+            r#"
+            SELECT id, method_id, min_value, max_value, amount
+            FROM shipping_rates
+            WHERE method_id = ANY($1)
+            ORDER BY method_id ASC, min_value ASC
+            "#,
+        )
+        .bind(&method_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut methods: Vec<ShippingMethodEntity> = methods
+            .into_iter()
+            .map(|m| {
+                let (own, rest): (Vec<_>, Vec<_>) =
+                    rates.drain(..).partition(|r| r.method_id == m.id);
+                rates = rest;
+                m.into_entity(own)
+            })
+            .collect();
+
+        Ok(zones
+            .into_iter()
+            .map(|z| {
+                let (own, rest): (Vec<_>, Vec<_>) =
+                    methods.drain(..).partition(|m| m.zone_id == z.id);
+                methods = rest;
+                z.into_entity(own)
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ShippingRepository for PostgresShippingRepository {
+    async fn find_all_zones(&self) -> Result<Vec<ShippingZoneEntity>> {
+        let zones = sqlx::query_as::<_, ShippingZoneModel>(&format!(
+            "SELECT {} FROM shipping_zones ORDER BY country ASC, id ASC",
+            ZONE_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.load_methods(zones).await
+    }
+
+    async fn find_zones_by_country(&self, country: &str) -> Result<Vec<ShippingZoneEntity>> {
+        let zones = sqlx::query_as::<_, ShippingZoneModel>(&format!(
+            "SELECT {} FROM shipping_zones WHERE country = UPPER($1) AND is_active ORDER BY id ASC",
+            ZONE_COLUMNS
+        ))
+        .bind(country)
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.load_methods(zones).await
+    }
+
+    async fn find_zone_by_id(&self, id: i32) -> Result<Option<ShippingZoneEntity>> {
+        let zone = sqlx::query_as::<_, ShippingZoneModel>(&format!(
+            "SELECT {} FROM shipping_zones WHERE id = $1",
+            ZONE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match zone {
+            Some(z) => Ok(self.load_methods(vec![z]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    async fn save_zone(&self, zone: &ShippingZoneEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO shipping_zones
+                (name, country, postal_prefixes, is_active, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(&zone.name)
+        .bind(&zone.country)
+        .bind(&zone.postal_prefixes)
+        .bind(zone.is_active)
+        .bind(zone.created_at)
+        .bind(zone.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn save_method(&self, method: &ShippingMethodEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO shipping_methods
+                (zone_id, code, name, kind, rate_basis, min_days, max_days,
+                 max_weight_grams, is_active, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING id
+            "#,
+        )
+        .bind(method.zone_id)
+        .bind(&method.code)
+        .bind(&method.name)
+        .bind(method.kind.as_str())
+        .bind(method.rate_basis.as_str())
+        .bind(method.min_days)
+        .bind(method.max_days)
+        .bind(method.max_weight_grams)
+        .bind(method.is_active)
+        .bind(method.created_at)
+        .bind(method.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        let method_id: i32 = row.try_get("id")?;
+
+        for rate in &method.rates {
+            sqlx::query(
+                r#"
+                INSERT INTO shipping_rates (method_id, min_value, max_value, amount)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(method_id)
+            .bind(rate.min_value)
+            .bind(rate.max_value)
+            .bind(rate.amount)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(method_id)
+    }
+
+    async fn update_method(&self, method: &ShippingMethodEntity) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE shipping_methods
+            SET
+                name = $1,
+                min_days = $2,
+                max_days = $3,
+                max_weight_grams = $4,
+                is_active = $5,
+                updated_at = $6
+            WHERE id = $7
+            "#,
+        )
+        .bind(&method.name)
+        .bind(method.min_days)
+        .bind(method.max_days)
+        .bind(method.max_weight_grams)
+        .bind(method.is_active)
+        .bind(method.updated_at)
+        .bind(method.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use crate::domain::{
+    entities::stock_subscription::StockSubscriptionEntity,
+    entities::notification_job::NotificationKind,
+    repositories::stock_subscription_repository::StockSubscriptionRepository,
+};
+use crate::adapters::postgres::models::stock_subscription_model::StockSubscriptionModel;
+
+const SUBSCRIPTION_COLUMNS: &str = "id, user_id, book_id, notified_at, created_at";
+
+pub struct PostgresStockSubscriptionRepository {
+    pool: PgPool,
+}
+
+impl PostgresStockSubscriptionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StockSubscriptionRepository for PostgresStockSubscriptionRepository {
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<StockSubscriptionEntity>> {
+        let results = sqlx::query_as::<_, StockSubscriptionModel>(&format!(
+            "SELECT {} FROM stock_subscriptions WHERE user_id = $1 ORDER BY created_at DESC, id DESC",
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(StockSubscriptionEntity::from).collect())
+    }
+
+    async fn find_by_user_and_book(
+        &self,
+        user_id: i32,
+        book_id: i32,
+    ) -> Result<Option<StockSubscriptionEntity>> {
+        let result = sqlx::query_as::<_, StockSubscriptionModel>(&format!(
+            "SELECT {} FROM stock_subscriptions WHERE user_id = $1 AND book_id = $2",
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(book_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(StockSubscriptionEntity::from))
+    }
+
+    async fn save(&self, subscription: &StockSubscriptionEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO stock_subscriptions (user_id, book_id, notified_at, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, book_id)
+            DO UPDATE SET notified_at = EXCLUDED.notified_at, created_at = EXCLUDED.created_at
+            RETURNING id
+            "#,
+        )
+        .bind(subscription.user_id)
+        .bind(subscription.book_id)
+        .bind(subscription.notified_at)
+        .bind(subscription.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    async fn notify_back_in_stock(&self, book_id: i32) -> Result<u64> {
+        // ยังไม่มีของก็ไม่แจ้ง: คนรอยังรออยู่จนกว่าของจะเข้าจริง
+        let result = sqlx::query(
+            r#"
+            WITH fired AS (
+                UPDATE stock_subscriptions s
+                SET notified_at = NOW()
+                FROM books b
+                WHERE s.book_id = $1 AND s.notified_at IS NULL
+                  AND b.id = s.book_id AND b.stock_quantity > 0
+                RETURNING s.user_id, s.book_id
+            )
+            INSERT INTO notification_jobs (user_id, kind, payload)
+            SELECT f.user_id, $2,
+                   jsonb_build_object('book_id', b.id, 'isbn', b.isbn, 'title', b.title)
+            FROM fired f
+            JOIN books b ON b.id = f.book_id
+            "#,
+        )
+        .bind(book_id)
+        .bind(NotificationKind::BackInStock.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM stock_subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool};
+
+use crate::domain::{
+    entities::store::StoreEntity,
+    repositories::store_repository::StoreRepository,
+};
+use crate::adapters::postgres::models::store_model::StoreModel;
+
+const STORE_COLUMNS: &str = "id, code, name, currency, jurisdiction, prices_include_tax, \
+                             is_default, is_active, created_at, updated_at";
+
+pub struct PostgresStoreRepository {
+    pool: PgPool,
+}
+
+impl PostgresStoreRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StoreRepository for PostgresStoreRepository {
+    async fn find_all(&self) -> Result<Vec<StoreEntity>> {
+        let results = sqlx::query_as::<_, StoreModel>(&format!(
+            "SELECT {} FROM stores ORDER BY id ASC",
+            STORE_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(StoreEntity::from).collect())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<StoreEntity>> {
+        let result = sqlx::query_as::<_, StoreModel>(&format!(
+            "SELECT {} FROM stores WHERE id = $1",
+            STORE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(StoreEntity::from))
+    }
+
+    async fn find_by_code(&self, code: &str) -> Result<Option<StoreEntity>> {
+        let result = sqlx::query_as::<_, StoreModel>(&format!(
+            "SELECT {} FROM stores WHERE code = UPPER($1)",
+            STORE_COLUMNS
+        ))
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(StoreEntity::from))
+    }
+
+    async fn find_default(&self) -> Result<Option<StoreEntity>> {
+        let result = sqlx::query_as::<_, StoreModel>(&format!(
+            "SELECT {} FROM stores WHERE is_default AND is_active",
+            STORE_COLUMNS
+        ))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(StoreEntity::from))
+    }
+}
@@ -0,0 +1,332 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::{
+        stored_value_account::StoredValueAccountEntity,
+        stored_value_entry::{EntryReason, StoredValueEntryEntity},
+    },
+    repositories::stored_value_repository::StoredValueRepository,
+};
+use crate::adapters::postgres::models::stored_value_model::{
+    StoredValueAccountModel, StoredValueEntryModel,
+};
+
+const ACCOUNT_COLUMNS: &str = "id, kind, code_hash, code_last4, user_id, currency, balance, \
+                               expires_at, created_by, created_at, updated_at";
+
+const ENTRY_COLUMNS: &str = "id, account_id, amount, reason, order_id, note, created_by, created_at";
+
+pub struct PostgresStoredValueRepository {
+    pool: PgPool,
+}
+
+impl PostgresStoredValueRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Writes an entry inside an existing transaction so other aggregates
+    /// (e.g. orders) can move balances atomically with their own changes.
+    pub async fn record_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        entry: &StoredValueEntryEntity,
+    ) -> Result<i32> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE stored_value_accounts
+            SET balance = balance + $1, updated_at = NOW()
+            WHERE id = $2 AND balance + $1 >= 0
+            "#,
+        )
+        .bind(entry.amount)
+        .bind(entry.account_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            bail!("Insufficient balance on account {}", entry.account_id);
+        }
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO stored_value_entries
+                (account_id, amount, reason, order_id, note, created_by, created_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(entry.account_id)
+        .bind(entry.amount)
+        .bind(entry.reason.as_str())
+        .bind(entry.order_id)
+        .bind(&entry.note)
+        .bind(entry.created_by)
+        .bind(entry.created_at)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    /// Puts gift card and store credit money spent on an order back on the
+    /// accounts it came from and marks those payments refunded.
+    pub async fn refund_redemptions_for_order_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: i32,
+        changed_by: Option<i32>,
+    ) -> Result<u64> {
+        // provider_intent_id ของ payment จากยอดคงเหลือ = id ของ redemption entry
+        let rows = sqlx::query(
+            r#"
+            SELECT p.id, p.captured_amount - p.refunded_amount AS remaining, e.account_id
+            FROM payments p
+            JOIN stored_value_entries e ON e.id::TEXT = p.provider_intent_id
+            WHERE p.order_id = $1
+              AND p.provider IN ('gift_card', 'store_credit')
+              AND p.status IN ('captured', 'partially_refunded')
+              AND e.reason = 'redemption'
+            FOR UPDATE OF p
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        for row in &rows {
+            let payment_id: i32 = row.try_get("id")?;
+            let remaining: i64 = row.try_get("remaining")?;
+            let account_id: i32 = row.try_get("account_id")?;
+
+            let entry = StoredValueEntryEntity::new(
+                account_id,
+                remaining,
+                EntryReason::RedemptionRefund,
+                Some(order_id),
+                Some("Order cancelled".to_string()),
+                changed_by,
+            )?;
+            Self::record_in_tx(tx, &entry).await?;
+
+            sqlx::query(
+                r#"
+                UPDATE payments
+                SET refunded_amount = captured_amount, status = 'refunded', updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(payment_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(rows.len() as u64)
+    }
+}
+
+#[async_trait]
+impl StoredValueRepository for PostgresStoredValueRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<StoredValueAccountEntity>> {
+        let result = sqlx::query_as::<_, StoredValueAccountModel>(&format!(
+            "SELECT {} FROM stored_value_accounts WHERE id = $1",
+            ACCOUNT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(StoredValueAccountEntity::from))
+    }
+
+    async fn find_by_code_hash(&self, code_hash: &str) -> Result<Option<StoredValueAccountEntity>> {
+        let result = sqlx::query_as::<_, StoredValueAccountModel>(&format!(
+            "SELECT {} FROM stored_value_accounts WHERE code_hash = $1",
+            ACCOUNT_COLUMNS
+        ))
+        .bind(code_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(StoredValueAccountEntity::from))
+    }
+
+    async fn find_store_credit(&self, user_id: i32, currency: &str) -> Result<Option<StoredValueAccountEntity>> {
+        let result = sqlx::query_as::<_, StoredValueAccountModel>(&format!(
+            r#"
+            SELECT {}
+            FROM stored_value_accounts
+            WHERE kind = 'store_credit' AND user_id = $1 AND currency = $2
+            "#,
+            ACCOUNT_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(currency)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(StoredValueAccountEntity::from))
+    }
+
+    async fn create(&self, account: &StoredValueAccountEntity, opening_amount: i64) -> Result<StoredValueAccountEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        // balance เริ่มที่ 0 แล้วลงยอดผ่าน ledger เพื่อให้ยอดตรงกันเสมอ
+        let row = sqlx::query(
+            r#"
+            INSERT INTO stored_value_accounts
+                (kind, code_hash, code_last4, user_id, currency, balance, expires_at,
+                 created_by, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, 0, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(account.kind.as_str())
+        .bind(&account.code_hash)
+        .bind(&account.code_last4)
+        .bind(account.user_id)
+        .bind(account.currency.as_str())
+        .bind(account.expires_at)
+        .bind(account.created_by)
+        .bind(account.created_at)
+        .bind(account.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        let account_id: i32 = row.try_get("id")?;
+
+        let opening = StoredValueEntryEntity::new(
+            account_id,
+            opening_amount,
+            EntryReason::Issue,
+            None,
+            None,
+            account.created_by,
+        )?;
+        Self::record_in_tx(&mut tx, &opening).await?;
+
+        tx.commit().await?;
+
+        match self.find_by_id(account_id).await? {
+            Some(account) => Ok(account),
+            None => bail!("Account {} not found", account_id),
+        }
+    }
+
+    async fn find_or_create_store_credit(&self, user_id: i32, currency: &str) -> Result<StoredValueAccountEntity> {
+        sqlx::query(
+            r#"
+            INSERT INTO stored_value_accounts (kind, user_id, currency)
+            VALUES ('store_credit', $1, $2)
+            ON CONFLICT (user_id, currency) WHERE kind = 'store_credit' DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(currency)
+        .execute(&self.pool)
+        .await?;
+
+        match self.find_store_credit(user_id, currency).await? {
+            Some(account) => Ok(account),
+            None => bail!("Store credit account for user {} not found", user_id),
+        }
+    }
+
+    async fn update(&self, account: &StoredValueAccountEntity) -> Result<StoredValueAccountEntity> {
+        let result = sqlx::query_as::<_, StoredValueAccountModel>(&format!(
+            r#"
+            UPDATE stored_value_accounts
+            SET expires_at = $1, updated_at = $2
+            WHERE id = $3
+            RETURNING {}
+            "#,
+            ACCOUNT_COLUMNS
+        ))
+        .bind(account.expires_at)
+        .bind(account.updated_at)
+        .bind(account.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(StoredValueAccountEntity::from(result))
+    }
+
+    async fn record(&self, entry: &StoredValueEntryEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+        let id = Self::record_in_tx(&mut tx, entry).await?;
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    async fn refund_redemptions_for_order(&self, order_id: i32, changed_by: Option<i32>) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let refunded = Self::refund_redemptions_for_order_in_tx(&mut tx, order_id, changed_by).await?;
+        tx.commit().await?;
+        Ok(refunded)
+    }
+
+    async fn find_entry(&self, id: i32) -> Result<Option<StoredValueEntryEntity>> {
+        let result = sqlx::query_as::<_, StoredValueEntryModel>(&format!(
+            "SELECT {} FROM stored_value_entries WHERE id = $1",
+            ENTRY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(StoredValueEntryEntity::from))
+    }
+
+    async fn find_entries(&self, account_id: i32) -> Result<Vec<StoredValueEntryEntity>> {
+        let results = sqlx::query_as::<_, StoredValueEntryModel>(&format!(
+            r#"
+            SELECT {}
+            FROM stored_value_entries
+            WHERE account_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+            ENTRY_COLUMNS
+        ))
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(StoredValueEntryEntity::from).collect())
+    }
+
+    async fn refund_credit_total(&self, order_id: i32) -> Result<i64> {
+        let row = sqlx::query(
+            r#"
+            SELECT COALESCE(SUM(amount), 0)::BIGINT AS total
+            FROM stored_value_entries
+            WHERE order_id = $1 AND reason = 'refund_credit'
+            "#,
+        )
+        .bind(order_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("total")?)
+    }
+
+    async fn find_expired(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<StoredValueAccountEntity>> {
+        let results = sqlx::query_as::<_, StoredValueAccountModel>(&format!(
+            r#"
+            SELECT {}
+            FROM stored_value_accounts
+            WHERE balance > 0 AND expires_at <= $1
+            ORDER BY expires_at ASC, id ASC
+            LIMIT $2
+            "#,
+            ACCOUNT_COLUMNS
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(StoredValueAccountEntity::from).collect())
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use crate::domain::{
+    entities::tax_rate::TaxRateEntity,
+    repositories::tax_rate_repository::TaxRateRepository,
+};
+use crate::adapters::postgres::models::tax_rate_model::TaxRateModel;
+
+pub struct PostgresTaxRateRepository {
+    pool: PgPool,
+}
+
+impl PostgresTaxRateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TaxRateRepository for PostgresTaxRateRepository {
+    async fn find_by_jurisdiction(&self, jurisdiction: &str) -> Result<Vec<TaxRateEntity>> {
+        let results = sqlx::query_as::<_, TaxRateModel>(
+            r#"
+            SELECT id, jurisdiction, tax_class, name, rate_bps, valid_from, valid_to
+            FROM tax_rates
+            WHERE jurisdiction = UPPER($1) OR jurisdiction = SPLIT_PART(UPPER($1), '-', 1)
+            ORDER BY valid_from ASC
+            "#,
+        )
+        .bind(jurisdiction)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results.into_iter().map(TaxRateEntity::from).collect())
+    }
+
+    async fn save(&self, rate: &TaxRateEntity) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO tax_rates
+                (jurisdiction, tax_class, name, rate_bps, valid_from, valid_to)
+            VALUES
+                ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(&rate.jurisdiction)
+        .bind(rate.tax_class.as_str())
+        .bind(&rate.name)
+        .bind(rate.rate_bps)
+        .bind(rate.valid_from)
+        .bind(rate.valid_to)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+}
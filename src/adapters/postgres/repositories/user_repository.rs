@@ -1,10 +1,11 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{types::Json, PgPool, Postgres, Row, Transaction};
 
 use crate::domain::{
     entities::{user::UserEntity, role::RoleEntity},
     repositories::user_repository::UserRepository,
+    value_objects::user_avatar::UserAvatar,
 };
 use crate::adapters::postgres::models::{user_model::UserModel, role_model::RoleModel};
 
@@ -16,6 +17,76 @@ impl PostgresUserRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Inserts a user inside an existing transaction
+    pub(crate) async fn insert_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        user: &UserEntity,
+    ) -> Result<i32> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO users
+                (fname, lname, email, age, sex, phone, password,
+                 is_active, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id
+            "#,
+        )
+        // ดึงค่าจาก Value Objects
+        .bind(user.first_name.as_str())
+        .bind(user.last_name.as_str())
+        .bind(user.email.as_str())
+        .bind(user.age.value())
+        .bind(&user.sex)
+        .bind(user.phone.as_str())
+        .bind(user.password.as_str())
+        .bind(user.is_active)
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(row.try_get("id")?)
+    }
+
+    /// Updates profile fields inside an existing transaction; the password
+    /// has its own statement
+    pub(crate) async fn update_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        user: &UserEntity,
+    ) -> Result<UserEntity> {
+        let result = sqlx::query_as::<_, UserModel>(
+            r#"
+            UPDATE users
+            SET
+                fname = $1,
+                lname = $2,
+                email = $3,
+                age = $4,
+                sex = $5,
+                phone = $6,
+                is_active = $7,
+                updated_at = $8
+            WHERE id = $9
+            RETURNING id, fname, lname, email, age, sex, phone, password,
+                      avatar, is_active, created_at, updated_at
+            "#,
+        )
+        .bind(user.first_name.as_str())
+        .bind(user.last_name.as_str())
+        .bind(user.email.as_str())
+        .bind(user.age.value())
+        .bind(&user.sex)
+        .bind(user.phone.as_str())
+        .bind(user.is_active)
+        .bind(user.updated_at) // เวลาอัปเดตถูกเปลี่ยนมาจาก Domain logic แล้ว
+        .bind(user.id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(UserEntity::from(result))
+    }
 }
 
 #[async_trait]
@@ -24,7 +95,7 @@ impl UserRepository for PostgresUserRepository {
         let result = sqlx::query_as::<_, UserModel>(
             r#"
             SELECT id, fname, lname, email, age, sex, phone, password,
-                   is_active, created_at, updated_at
+                   avatar, is_active, created_at, updated_at
             FROM users
             WHERE id = $1
             "#,
@@ -41,7 +112,7 @@ impl UserRepository for PostgresUserRepository {
         let result = sqlx::query_as::<_, UserModel>(
             r#"
             SELECT id, fname, lname, email, age, sex, phone, password,
-                   is_active, created_at, updated_at
+                   avatar, is_active, created_at, updated_at
             FROM users
             WHERE email = $1
             "#,
@@ -57,7 +128,7 @@ impl UserRepository for PostgresUserRepository {
         let results = sqlx::query_as::<_, UserModel>(
             r#"
             SELECT id, fname, lname, email, age, sex, phone, password,
-                   is_active, created_at, updated_at
+                   avatar, is_active, created_at, updated_at
             FROM users
             ORDER BY id ASC
             "#,
@@ -68,66 +139,38 @@ impl UserRepository for PostgresUserRepository {
         Ok(results.into_iter().map(UserEntity::from).collect())
     }
 
-    async fn save(&self, user: &UserEntity) -> Result<i32> {
-        let row = sqlx::query(
+    async fn find_after(&self, after_id: i32, limit: i64) -> Result<Vec<UserEntity>> {
+        let results = sqlx::query_as::<_, UserModel>(
             r#"
-            INSERT INTO users
-                (fname, lname, email, age, sex, phone, password,
-                 is_active, created_at, updated_at)
-            VALUES
-                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            RETURNING id
+            SELECT id, fname, lname, email, age, sex, phone, password,
+                   avatar, is_active, created_at, updated_at
+            FROM users
+            WHERE id > $1
+            ORDER BY id ASC
+            LIMIT $2
             "#,
         )
-        // ดึงค่าจาก Value Objects
-        .bind(user.first_name.as_str())
-        .bind(user.last_name.as_str())
-        .bind(user.email.as_str())
-        .bind(user.age.value())
-        .bind(&user.sex)
-        .bind(user.phone.as_str())
-        .bind(user.password.as_str())
-        .bind(user.is_active)
-        .bind(user.created_at)
-        .bind(user.updated_at)
-        .fetch_one(&self.pool)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(row.try_get("id")?)
+        Ok(results.into_iter().map(UserEntity::from).collect())
+    }
+
+    async fn save(&self, user: &UserEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+        let user_id = Self::insert_in_tx(&mut tx, user).await?;
+        tx.commit().await?;
+        Ok(user_id)
     }
 
     //แก้ไข: รับ Entity ทั้งก้อน เพื่อ update state ล่าสุดลง DB
     async fn update(&self, user: &UserEntity) -> Result<UserEntity> {
-        let result = sqlx::query_as::<_, UserModel>(
-            r#"
-            UPDATE users
-            SET
-                fname = $1,
-                lname = $2,
-                email = $3,
-                age = $4,
-                sex = $5,
-                phone = $6,
-                is_active = $7,
-                updated_at = $8
-            WHERE id = $9
-            RETURNING id, fname, lname, email, age, sex, phone, password,
-                      is_active, created_at, updated_at
-            "#,
-        )
-        .bind(user.first_name.as_str())
-        .bind(user.last_name.as_str())
-        .bind(user.email.as_str())
-        .bind(user.age.value())
-        .bind(&user.sex)
-        .bind(user.phone.as_str())
-        .bind(user.is_active)
-        .bind(user.updated_at) // เวลาอัปเดตถูกเปลี่ยนมาจาก Domain logic แล้ว
-        .bind(user.id)
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(UserEntity::from(result))
+        let mut tx = self.pool.begin().await?;
+        let updated = Self::update_in_tx(&mut tx, user).await?;
+        tx.commit().await?;
+        Ok(updated)
     }
 
     //เพิ่ม: Method สำหรับเปลี่ยน Password โดยเฉพาะ
@@ -147,6 +190,28 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
+    async fn update_avatar(&self, user_id: i32, avatar: Option<&UserAvatar>) -> Result<Option<UserAvatar>> {
+        // อ่าน avatar เดิมในคำสั่งเดียวกัน ผู้เรียกจะได้ลบไฟล์เก่าได้ถูกชุด
+        let previous: Option<Option<Json<UserAvatar>>> = sqlx::query_scalar(
+            r#"
+            UPDATE users u
+            SET avatar = $2, updated_at = NOW()
+            FROM (SELECT id, avatar FROM users WHERE id = $1 FOR UPDATE) old
+            WHERE u.id = old.id
+            RETURNING old.avatar
+            "#,
+        )
+        .bind(user_id)
+        .bind(avatar.map(Json))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match previous {
+            Some(previous) => Ok(previous.map(|a| a.0)),
+            None => Err(anyhow::anyhow!("User not found")),
+        }
+    }
+
     async fn delete(&self, id: i32) -> Result<()> {
         sqlx::query("DELETE FROM users WHERE id = $1")
             .bind(id)
@@ -0,0 +1,174 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use crate::domain::{
+    entities::wishlist::WishlistEntity,
+    repositories::wishlist_repository::WishlistRepository,
+};
+use crate::adapters::postgres::models::wishlist_model::{WishlistItemModel, WishlistModel};
+
+const WISHLIST_COLUMNS: &str = "id, user_id, name, share_token, created_at, updated_at";
+
+pub struct PostgresWishlistRepository {
+    pool: PgPool,
+}
+
+impl PostgresWishlistRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn load_items(&self, wishlist: WishlistModel) -> Result<WishlistEntity> {
+        let items = sqlx::query_as::<_, WishlistItemModel>(
+            r#"
+            SELECT wishlist_id, book_id, note, added_at
+            FROM wishlist_items
+            WHERE wishlist_id = $1
+            ORDER BY added_at ASC, book_id ASC
+            "#,
+        )
+        .bind(wishlist.id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(wishlist.into_entity(items))
+    }
+
+    async fn insert_items(
+        tx: &mut Transaction<'_, Postgres>,
+        wishlist_id: i32,
+        wishlist: &WishlistEntity,
+    ) -> Result<()> {
+        for item in &wishlist.items {
+            sqlx::query(
+                r#"
+                INSERT INTO wishlist_items (wishlist_id, book_id, note, added_at)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(wishlist_id)
+            .bind(item.book_id)
+            .bind(&item.note)
+            .bind(item.added_at)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WishlistRepository for PostgresWishlistRepository {
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<WishlistEntity>> {
+        let wishlists = sqlx::query_as::<_, WishlistModel>(&format!(
+            "SELECT {} FROM wishlists WHERE user_id = $1 ORDER BY created_at ASC, id ASC",
+            WISHLIST_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut results = Vec::with_capacity(wishlists.len());
+        for w in wishlists {
+            results.push(self.load_items(w).await?);
+        }
+        Ok(results)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<WishlistEntity>> {
+        let wishlist = sqlx::query_as::<_, WishlistModel>(&format!(
+            "SELECT {} FROM wishlists WHERE id = $1",
+            WISHLIST_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match wishlist {
+            Some(w) => Ok(Some(self.load_items(w).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_share_token(&self, token: &str) -> Result<Option<WishlistEntity>> {
+        let wishlist = sqlx::query_as::<_, WishlistModel>(&format!(
+            "SELECT {} FROM wishlists WHERE share_token = $1",
+            WISHLIST_COLUMNS
+        ))
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match wishlist {
+            Some(w) => Ok(Some(self.load_items(w).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn save(&self, wishlist: &WishlistEntity) -> Result<i32> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO wishlists (user_id, name, share_token, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(wishlist.user_id)
+        .bind(&wishlist.name)
+        .bind(wishlist.share_token.as_ref().map(|t| t.as_str()))
+        .bind(wishlist.created_at)
+        .bind(wishlist.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        let wishlist_id: i32 = row.try_get("id")?;
+
+        Self::insert_items(&mut tx, wishlist_id, wishlist).await?;
+        tx.commit().await?;
+
+        Ok(wishlist_id)
+    }
+
+    async fn update(&self, wishlist: &WishlistEntity) -> Result<WishlistEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        let model = sqlx::query_as::<_, WishlistModel>(&format!(
+            r#"
+            UPDATE wishlists
+            SET
+                name = $1,
+                share_token = $2,
+                updated_at = $3
+            WHERE id = $4
+            RETURNING {}
+            "#,
+            WISHLIST_COLUMNS
+        ))
+        .bind(&wishlist.name)
+        .bind(wishlist.share_token.as_ref().map(|t| t.as_str()))
+        .bind(wishlist.updated_at)
+        .bind(wishlist.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM wishlist_items WHERE wishlist_id = $1")
+            .bind(wishlist.id)
+            .execute(&mut *tx)
+            .await?;
+        Self::insert_items(&mut tx, wishlist.id, wishlist).await?;
+
+        tx.commit().await?;
+
+        self.load_items(model).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM wishlists WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use anyhow::{anyhow, Result};
+
+use crate::application::dtos::auth_dto::UserInfo;
+
+pub const ROLE_ADMIN: &str = "ADMIN";
+pub const ROLE_STAFF: &str = "STAFF";
+pub const ROLE_REPORTS: &str = "REPORTS";
+
+/// Fails unless the caller holds at least one of `roles`.
+pub fn ensure_any_role(caller: &UserInfo, roles: &[&str]) -> Result<()> {
+    if caller.roles.iter().any(|r| roles.contains(&r.as_str())) {
+        Ok(())
+    } else {
+        Err(anyhow!("Permission denied"))
+    }
+}
+
+/// Staff and admins run back-office workflows (returns, fulfilment, ...).
+pub fn ensure_staff(caller: &UserInfo) -> Result<()> {
+    ensure_any_role(caller, &[ROLE_ADMIN, ROLE_STAFF])
+}
+
+/// Sales reports are for management: admins and holders of the REPORTS role.
+pub fn ensure_reports(caller: &UserInfo) -> Result<()> {
+    ensure_any_role(caller, &[ROLE_ADMIN, ROLE_REPORTS])
+}
@@ -5,6 +5,8 @@ use serde_aux::field_attributes::deserialize_number_from_string;
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
+    /// Guest cart to merge into the user's cart after login
+    pub cart_token: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
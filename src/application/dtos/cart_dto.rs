@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::application::dtos::promotion_dto::AppliedPromotionResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct AddCartItemRequest {
+    pub book_id: i32,
+    pub quantity: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCartItemRequest {
+    pub quantity: i32,
+}
+
+/// A cart line after prices and stock have been rechecked against the catalog.
+#[derive(Debug, Serialize)]
+pub struct CartItemResponse {
+    pub book_id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub quantity: i32,
+    pub unit_price: i64,
+    pub line_total: i64,
+    pub price_changed: bool,
+    pub previous_unit_price: Option<i64>,
+    pub out_of_stock: bool,
+    pub available_quantity: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CartResponse {
+    pub id: i32,
+    /// Only set for guest carts; the client keeps it to identify the cart.
+    pub cart_token: Option<String>,
+    pub items: Vec<CartItemResponse>,
+    pub item_count: i32,
+    pub currency: String,
+    pub subtotal: i64,
+    pub discount_total: i64,
+    pub total: i64,
+    pub free_shipping: bool,
+    pub applied_promotions: Vec<AppliedPromotionResponse>,
+    pub coupon_code: Option<String>,
+    /// Set when the entered coupon cannot be used
+    pub coupon_error: Option<String>,
+    /// True when at least one line changed price or cannot be fulfilled.
+    pub has_issues: bool,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::application::dtos::cover_dto::BookCoverResponse;
+
+/// Storefront filters; list parameters are OR-ed within a facet.
+#[derive(Debug, Default, Deserialize)]
+pub struct CatalogQueryRequest {
+    pub q: Option<String>,
+    #[serde(default)]
+    pub category: Vec<String>,
+    #[serde(default)]
+    pub author: Vec<String>,
+    #[serde(default)]
+    pub publisher: Vec<String>,
+    #[serde(default)]
+    pub language: Vec<String>,
+    #[serde(default)]
+    pub format: Vec<String>,
+    /// Minor units, inclusive
+    pub price_min: Option<i64>,
+    /// Minor units, exclusive
+    pub price_max: Option<i64>,
+    /// 1-5: average rating at least this
+    pub min_rating: Option<i32>,
+    #[serde(default)]
+    pub in_stock: bool,
+    /// relevance | newest | price_asc | price_desc | rating | title
+    pub sort: Option<String>,
+    /// 1-based
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CatalogBookResponse {
+    pub id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub author: String,
+    pub publisher: Option<String>,
+    pub category: Option<String>,
+    pub language: Option<String>,
+    pub format: String,
+    pub price: i64,
+    pub currency: String,
+    pub in_stock: bool,
+    pub release_date: Option<NaiveDate>,
+    /// Not released yet but can be ordered now
+    pub preorder: bool,
+    pub average_rating: Option<f64>,
+    pub rating_count: i32,
+    pub cover: Option<BookCoverResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FacetValueResponse {
+    pub value: String,
+    pub count: i64,
+    pub selected: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PriceRangeFacetResponse {
+    pub min: i64,
+    /// `None` for the open-ended top range
+    pub max: Option<i64>,
+    pub count: i64,
+    pub selected: bool,
+}
+
+/// "N stars & up"; counts are cumulative
+#[derive(Debug, Serialize)]
+pub struct RatingFacetResponse {
+    pub min_rating: i32,
+    pub count: i64,
+    pub selected: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CatalogFacetsResponse {
+    pub categories: Vec<FacetValueResponse>,
+    pub authors: Vec<FacetValueResponse>,
+    pub publishers: Vec<FacetValueResponse>,
+    pub languages: Vec<FacetValueResponse>,
+    pub formats: Vec<FacetValueResponse>,
+    pub price_ranges: Vec<PriceRangeFacetResponse>,
+    pub ratings: Vec<RatingFacetResponse>,
+    /// Matching books that are in stock
+    pub in_stock: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CatalogResponse {
+    pub page: u32,
+    pub per_page: u32,
+    pub total: i64,
+    pub total_pages: u32,
+    pub books: Vec<CatalogBookResponse>,
+    pub facets: CatalogFacetsResponse,
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{
+    catalog_import_job::CatalogImportJobEntity,
+    catalog_import_record::CatalogImportRecordEntity,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCatalogImportRequest {
+    /// A file already in the import inbox
+    pub file_name: String,
+    /// "onix"
+    pub format: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CatalogImportReportQuery {
+    /// "created", "updated" or "rejected"; all when omitted
+    pub outcome: Option<String>,
+    /// `record_index` of the last line already seen
+    pub after: Option<i32>,
+    pub per_page: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CatalogImportJobResponse {
+    pub id: i32,
+    pub format: String,
+    pub file_name: String,
+    pub sender: Option<String>,
+    pub status: String,
+    pub records_processed: i32,
+    pub created_count: i32,
+    pub updated_count: i32,
+    pub rejected_count: i32,
+    pub last_error: Option<String>,
+    pub requested_by: Option<i32>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CatalogImportRecordResponse {
+    pub record_index: i32,
+    pub reference: Option<String>,
+    pub isbn: Option<String>,
+    pub book_id: Option<i32>,
+    pub outcome: String,
+    pub messages: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CatalogImportReportResponse {
+    pub job: CatalogImportJobResponse,
+    pub records: Vec<CatalogImportRecordResponse>,
+    /// Pass as `after` for the next page; `None` on the last page
+    pub next_after: Option<i32>,
+}
+
+impl From<CatalogImportJobEntity> for CatalogImportJobResponse {
+    fn from(job: CatalogImportJobEntity) -> Self {
+        Self {
+            id: job.id,
+            format: job.format.as_str().to_string(),
+            file_name: job.file_name,
+            sender: job.sender,
+            status: job.status.as_str().to_string(),
+            records_processed: job.records_processed,
+            created_count: job.created_count,
+            updated_count: job.updated_count,
+            rejected_count: job.rejected_count,
+            last_error: job.last_error,
+            requested_by: job.requested_by,
+            started_at: job.started_at,
+            finished_at: job.finished_at,
+            created_at: job.created_at,
+        }
+    }
+}
+
+impl From<CatalogImportRecordEntity> for CatalogImportRecordResponse {
+    fn from(record: CatalogImportRecordEntity) -> Self {
+        Self {
+            record_index: record.record_index,
+            reference: record.reference,
+            isbn: record.isbn,
+            book_id: record.book_id,
+            outcome: record.outcome.as_str().to_string(),
+            messages: record.messages,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::book_cover::{BookCover, CoverVariant};
+
+/// Metadata of an uploaded cover; the bytes are passed alongside
+#[derive(Debug, Deserialize)]
+pub struct UploadCoverRequest {
+    /// As sent by the client: image/jpeg or image/png
+    pub content_type: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoverVariantResponse {
+    /// thumbnail, medium or large
+    pub size: String,
+    /// webp or jpeg
+    pub format: String,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub size_bytes: u64,
+    pub url: String,
+}
+
+impl From<CoverVariant> for CoverVariantResponse {
+    fn from(variant: CoverVariant) -> Self {
+        Self {
+            size: variant.size.as_str().to_string(),
+            format: variant.format.as_str().to_string(),
+            content_type: variant.format.content_type().to_string(),
+            width: variant.width,
+            height: variant.height,
+            size_bytes: variant.size_bytes,
+            url: variant.url,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookCoverResponse {
+    pub width: u32,
+    pub height: u32,
+    /// Decode with any BlurHash library to paint a placeholder
+    pub blurhash: String,
+    pub variants: Vec<CoverVariantResponse>,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl From<BookCover> for BookCoverResponse {
+    fn from(cover: BookCover) -> Self {
+        Self {
+            width: cover.width,
+            height: cover.height,
+            blurhash: cover.blurhash,
+            variants: cover.variants.into_iter().map(CoverVariantResponse::from).collect(),
+            uploaded_at: cover.uploaded_at,
+        }
+    }
+}
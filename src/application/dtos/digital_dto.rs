@@ -0,0 +1,204 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{
+    digital_asset::DigitalAssetEntity, download_log::DownloadLogEntity,
+    download_watermark::DownloadWatermarkEntity, entitlement::EntitlementEntity,
+};
+use crate::infrastructure::file_storage::ByteStream;
+
+/// Metadata of an uploaded file; the bytes are passed alongside
+#[derive(Debug, Deserialize)]
+pub struct UploadAssetRequest {
+    /// epub, pdf or mp3
+    pub file_format: String,
+    pub file_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DigitalAssetResponse {
+    pub id: i32,
+    pub book_id: i32,
+    pub file_format: String,
+    pub content_type: String,
+    pub file_name: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DigitalAssetEntity> for DigitalAssetResponse {
+    fn from(asset: DigitalAssetEntity) -> Self {
+        Self {
+            id: asset.id,
+            book_id: asset.book_id,
+            file_format: asset.file_format.as_str().to_string(),
+            content_type: asset.file_format.content_type().to_string(),
+            file_name: asset.file_name,
+            size_bytes: asset.size_bytes,
+            sha256: asset.sha256,
+            updated_at: asset.updated_at,
+        }
+    }
+}
+
+/// A book in the customer's digital library
+#[derive(Debug, Serialize)]
+pub struct LibraryItemResponse {
+    pub entitlement_id: i32,
+    pub book_id: i32,
+    pub title: String,
+    pub author: String,
+    pub format: String,
+    /// `None` means unlimited
+    pub downloads_remaining: Option<i32>,
+    pub revoked: bool,
+    pub files: Vec<DigitalAssetResponse>,
+    pub granted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DownloadLinkRequest {
+    pub asset_id: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadLinkResponse {
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+    pub file_name: String,
+    /// Downloads left before this one is used
+    pub downloads_remaining: Option<i32>,
+}
+
+/// An incoming request for a signed download link
+#[derive(Debug)]
+pub struct DownloadRequest {
+    pub entitlement_id: i32,
+    pub asset_id: i32,
+    /// Query parameters of the link
+    pub expires: i64,
+    pub signature: String,
+    /// Raw `Range` header
+    pub range: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// What to send back: status, headers and body of the HTTP response
+pub struct DownloadResponse {
+    /// 200, 206 or 416
+    pub status: u16,
+    pub content_type: String,
+    pub content_disposition: String,
+    /// Set on 206 and 416 responses
+    pub content_range: Option<String>,
+    /// Read from storage as it is sent, never held whole in memory
+    pub body: ByteStream,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GrantEntitlementRequest {
+    pub user_id: i32,
+    pub book_id: i32,
+    /// Omit for unlimited downloads
+    pub download_limit: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevokeEntitlementRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DownloadLimitRequest {
+    pub download_limit: Option<i32>,
+    #[serde(default)]
+    pub reset_used: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntitlementResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub book_id: i32,
+    pub order_id: Option<i32>,
+    pub download_limit: Option<i32>,
+    pub downloads_used: i32,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoke_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<EntitlementEntity> for EntitlementResponse {
+    fn from(entitlement: EntitlementEntity) -> Self {
+        Self {
+            id: entitlement.id,
+            user_id: entitlement.user_id,
+            book_id: entitlement.book_id,
+            order_id: entitlement.order_id,
+            download_limit: entitlement.download_limit,
+            downloads_used: entitlement.downloads_used,
+            revoked_at: entitlement.revoked_at,
+            revoke_reason: entitlement.revoke_reason,
+            created_at: entitlement.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadLogResponse {
+    pub id: i64,
+    pub asset_id: i32,
+    pub outcome: String,
+    pub range_start: Option<i64>,
+    pub range_end: Option<i64>,
+    pub bytes_served: i64,
+    pub counted: bool,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DownloadLogEntity> for DownloadLogResponse {
+    fn from(log: DownloadLogEntity) -> Self {
+        Self {
+            id: log.id,
+            asset_id: log.asset_id,
+            outcome: log.outcome.as_str().to_string(),
+            range_start: log.range_start,
+            range_end: log.range_end,
+            bytes_served: log.bytes_served,
+            counted: log.counted,
+            ip_address: log.ip_address,
+            user_agent: log.user_agent,
+            created_at: log.created_at,
+        }
+    }
+}
+
+/// Who a watermarked copy was made for
+#[derive(Debug, Serialize)]
+pub struct WatermarkTraceResponse {
+    pub code: String,
+    pub user_id: i32,
+    pub order_id: Option<i32>,
+    pub licensee: String,
+    pub entitlement_id: Option<i32>,
+    pub asset_id: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DownloadWatermarkEntity> for WatermarkTraceResponse {
+    fn from(watermark: DownloadWatermarkEntity) -> Self {
+        Self {
+            code: watermark.code.as_str().to_string(),
+            user_id: watermark.user_id,
+            order_id: watermark.order_id,
+            licensee: watermark.licensee,
+            entitlement_id: watermark.entitlement_id,
+            asset_id: watermark.asset_id,
+            created_at: watermark.created_at,
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::invoice::{InvoiceEntity, InvoiceLine, InvoiceParty};
+
+#[derive(Debug, Deserialize)]
+pub struct RequestTaxInvoiceRequest {
+    /// Registered name of the business (or the person's full name)
+    pub name: String,
+    pub tax_id: String,
+    /// Defaults to the head office (00000)
+    pub branch: Option<String>,
+    /// Registered address; defaults to the order's billing address
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssueCreditNoteRequest {
+    /// Credit a refunded return; leave out to credit what is left of a
+    /// cancelled or refunded order
+    pub return_id: Option<i32>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvoicePartyResponse {
+    pub name: String,
+    pub tax_id: String,
+    pub branch: String,
+    pub address: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvoiceLineResponse {
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: i64,
+    pub amount: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvoiceResponse {
+    pub id: i32,
+    pub kind: String,
+    pub number: String,
+    pub order_id: i32,
+    /// Credit notes: the tax invoice they reduce
+    pub original_invoice_id: Option<i32>,
+    pub original_number: Option<String>,
+    pub return_id: Option<i32>,
+    pub reason: Option<String>,
+    pub seller: InvoicePartyResponse,
+    pub buyer: InvoicePartyResponse,
+    pub currency: String,
+    pub lines: Vec<InvoiceLineResponse>,
+    pub net_amount: i64,
+    pub tax_amount: i64,
+    pub total: i64,
+    /// SHA-256 of the PDF, to check a copy against the original
+    pub sha256: String,
+    pub issued_at: DateTime<Utc>,
+}
+
+/// The stored PDF, ready to send as a file
+#[derive(Debug)]
+pub struct InvoiceDocumentResponse {
+    pub content_type: String,
+    pub content_disposition: String,
+    pub body: Vec<u8>,
+}
+
+impl From<InvoiceParty> for InvoicePartyResponse {
+    fn from(party: InvoiceParty) -> Self {
+        Self {
+            name: party.name,
+            tax_id: party.tax_id.as_str().to_string(),
+            branch: party.branch.as_str().to_string(),
+            address: party.address,
+        }
+    }
+}
+
+impl From<InvoiceLine> for InvoiceLineResponse {
+    fn from(line: InvoiceLine) -> Self {
+        Self {
+            description: line.description,
+            quantity: line.quantity,
+            unit_price: line.unit_price,
+            amount: line.amount,
+        }
+    }
+}
+
+impl From<InvoiceEntity> for InvoiceResponse {
+    fn from(invoice: InvoiceEntity) -> Self {
+        let (original_invoice_id, original_number) = match invoice.reference {
+            Some(r) => (Some(r.invoice_id), Some(r.number)),
+            None => (None, None),
+        };
+        Self {
+            id: invoice.id,
+            kind: invoice.kind.as_str().to_string(),
+            number: invoice.number,
+            order_id: invoice.order_id,
+            original_invoice_id,
+            original_number,
+            return_id: invoice.return_id,
+            reason: invoice.reason,
+            seller: InvoicePartyResponse::from(invoice.seller),
+            buyer: InvoicePartyResponse::from(invoice.buyer),
+            currency: invoice.currency.as_str().to_string(),
+            lines: invoice.lines.into_iter().map(InvoiceLineResponse::from).collect(),
+            net_amount: invoice.net_amount,
+            tax_amount: invoice.tax_amount,
+            total: invoice.total,
+            sha256: invoice.sha256,
+            issued_at: invoice.issued_at,
+        }
+    }
+}
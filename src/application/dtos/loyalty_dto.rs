@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{
+    loyalty_campaign::{LoyaltyCampaignEntity, LoyaltyCategoryMultiplier},
+    loyalty_entry::LoyaltyEntryEntity,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct RedeemPointsRequest {
+    /// Defaults to as many as the balance and the order allow
+    pub points: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetCategoryMultiplierRequest {
+    pub category: String,
+    /// Percent of the base rate; 100 removes the rule
+    pub multiplier_pct: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLoyaltyCampaignRequest {
+    pub name: String,
+    /// Leave out for every category
+    pub category: Option<String>,
+    pub multiplier_pct: i32,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatementQuery {
+    /// Defaults to 12 months before `to`
+    pub from: Option<DateTime<Utc>>,
+    /// Defaults to now
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CategoryMultiplierResponse {
+    pub category: String,
+    pub multiplier_pct: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoyaltyCampaignResponse {
+    pub id: i32,
+    pub name: String,
+    pub category: Option<String>,
+    pub multiplier_pct: i32,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PointsEntryResponse {
+    pub id: i32,
+    pub points: i64,
+    pub reason: String,
+    pub order_id: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoyaltyStatementResponse {
+    pub user_id: i32,
+    pub tier: String,
+    /// Spend over the last 12 months, in minor units
+    pub rolling_spend: i64,
+    pub next_tier: Option<String>,
+    /// Still to spend to reach `next_tier`
+    pub spend_to_next_tier: Option<i64>,
+    pub balance: i64,
+    /// What the balance is worth at checkout, in minor units
+    pub balance_value: i64,
+    /// Points that lapse by `expiring_by` unless spent
+    pub expiring_points: i64,
+    pub expiring_by: DateTime<Utc>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub opening_balance: i64,
+    pub closing_balance: i64,
+    pub entries: Vec<PointsEntryResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PointsExpiryResponse {
+    pub members: usize,
+    pub points_expired: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TierSyncResponse {
+    pub members_checked: usize,
+    /// Members whose tier role changed
+    pub members_changed: usize,
+}
+
+impl From<LoyaltyCategoryMultiplier> for CategoryMultiplierResponse {
+    fn from(multiplier: LoyaltyCategoryMultiplier) -> Self {
+        Self {
+            category: multiplier.category,
+            multiplier_pct: multiplier.multiplier_pct,
+            updated_at: multiplier.updated_at,
+        }
+    }
+}
+
+impl From<LoyaltyCampaignEntity> for LoyaltyCampaignResponse {
+    fn from(campaign: LoyaltyCampaignEntity) -> Self {
+        Self {
+            id: campaign.id,
+            name: campaign.name,
+            category: campaign.category,
+            multiplier_pct: campaign.multiplier_pct,
+            starts_at: campaign.starts_at,
+            ends_at: campaign.ends_at,
+            is_active: campaign.is_active,
+            created_at: campaign.created_at,
+        }
+    }
+}
+
+impl From<LoyaltyEntryEntity> for PointsEntryResponse {
+    fn from(entry: LoyaltyEntryEntity) -> Self {
+        Self {
+            id: entry.id,
+            points: entry.points,
+            reason: entry.reason.as_str().to_string(),
+            order_id: entry.order_id,
+            expires_at: entry.expires_at,
+            note: entry.note,
+            created_at: entry.created_at,
+        }
+    }
+}
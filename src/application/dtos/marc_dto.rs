@@ -0,0 +1,17 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct MarcExportQuery {
+    /// "marc21" (ISO 2709, the default) or "marcxml"
+    pub format: Option<String>,
+}
+
+/// A file of MARC records, ready to send as a download
+#[derive(Debug)]
+pub struct MarcDocumentResponse {
+    pub content_type: String,
+    pub content_disposition: String,
+    /// Records in the file
+    pub records: usize,
+    pub body: Vec<u8>,
+}
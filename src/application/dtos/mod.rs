@@ -1,3 +1,24 @@
 pub mod auth_dto;
+pub mod cart_dto;
 pub mod user_dto;
 pub mod role_dto;
+pub mod order_dto;
+pub mod payment_dto;
+pub mod return_dto;
+pub mod promotion_dto;
+pub mod pricing_dto;
+pub mod shipping_dto;
+pub mod review_dto;
+pub mod wishlist_dto;
+pub mod search_dto;
+pub mod catalog_dto;
+pub mod digital_dto;
+pub mod preorder_dto;
+pub mod stored_value_dto;
+pub mod loyalty_dto;
+pub mod invoice_dto;
+pub mod catalog_import_dto;
+pub mod spreadsheet_dto;
+pub mod marc_dto;
+pub mod cover_dto;
+pub mod report_dto;
@@ -0,0 +1,178 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    entities::order::{
+        OrderEntity, OrderItemEntity, OrderPromotion, OrderShipping, OrderStatusChange,
+    },
+    value_objects::postal_address::PostalAddress,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CheckoutRequest {
+    pub shipping_method_id: i32,
+    /// Defaults to the user's default shipping address
+    pub shipping_address_id: Option<i32>,
+    /// Defaults to the default billing address, then the shipping address
+    pub billing_address_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CancelOrderRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShipOrderRequest {
+    pub tracking_number: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderItemResponse {
+    pub book_id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub quantity: i32,
+    pub unit_price: i64,
+    pub price_includes_tax: bool,
+    pub tax_rate_bps: i32,
+    pub tax_amount: i64,
+    pub line_total: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderPromotionResponse {
+    pub promotion_id: i32,
+    pub name: String,
+    pub coupon_code: Option<String>,
+    pub discount: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderShippingResponse {
+    pub method_id: Option<i32>,
+    pub method_name: String,
+    pub kind: String,
+    pub amount: i64,
+    pub estimated_from: NaiveDate,
+    pub estimated_to: NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderStatusChangeResponse {
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub changed_by: Option<i32>,
+    pub note: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrderResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub status: String,
+    pub currency: String,
+    pub items: Vec<OrderItemResponse>,
+    pub subtotal: i64,
+    pub discount_total: i64,
+    pub shipping_total: i64,
+    pub tax_total: i64,
+    pub total: i64,
+    pub promotions: Vec<OrderPromotionResponse>,
+    pub shipping: Option<OrderShippingResponse>,
+    pub shipping_address: Option<PostalAddress>,
+    pub billing_address: Option<PostalAddress>,
+    pub tracking_number: Option<String>,
+    /// Set for pre-orders: when the payment is captured and the order ships
+    pub release_date: Option<NaiveDate>,
+    pub history: Vec<OrderStatusChangeResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<OrderItemEntity> for OrderItemResponse {
+    fn from(item: OrderItemEntity) -> Self {
+        Self {
+            book_id: item.book_id,
+            isbn: item.isbn,
+            title: item.title,
+            quantity: item.quantity.value(),
+            unit_price: item.unit_price,
+            price_includes_tax: item.price_includes_tax,
+            tax_rate_bps: item.tax_rate_bps,
+            tax_amount: item.tax_amount,
+            line_total: item.line_total,
+        }
+    }
+}
+
+impl From<OrderPromotion> for OrderPromotionResponse {
+    fn from(promotion: OrderPromotion) -> Self {
+        Self {
+            promotion_id: promotion.promotion_id,
+            name: promotion.name,
+            coupon_code: promotion.coupon_code,
+            discount: promotion.discount,
+        }
+    }
+}
+
+impl From<OrderShipping> for OrderShippingResponse {
+    fn from(shipping: OrderShipping) -> Self {
+        Self {
+            method_id: shipping.method_id,
+            method_name: shipping.method_name,
+            kind: shipping.kind.as_str().to_string(),
+            amount: shipping.amount,
+            estimated_from: shipping.estimated_from,
+            estimated_to: shipping.estimated_to,
+        }
+    }
+}
+
+impl From<OrderStatusChange> for OrderStatusChangeResponse {
+    fn from(change: OrderStatusChange) -> Self {
+        Self {
+            from_status: change.from_status.map(|s| s.as_str().to_string()),
+            to_status: change.to_status.as_str().to_string(),
+            changed_by: change.changed_by,
+            note: change.note,
+            changed_at: change.changed_at,
+        }
+    }
+}
+
+impl From<OrderEntity> for OrderResponse {
+    fn from(order: OrderEntity) -> Self {
+        Self {
+            id: order.id,
+            user_id: order.user_id,
+            status: order.status.as_str().to_string(),
+            currency: order.currency.as_str().to_string(),
+            items: order.items.into_iter().map(OrderItemResponse::from).collect(),
+            subtotal: order.subtotal,
+            discount_total: order.discount_total,
+            shipping_total: order.shipping_total,
+            tax_total: order.tax_total,
+            total: order.total,
+            promotions: order
+                .promotions
+                .into_iter()
+                .map(OrderPromotionResponse::from)
+                .collect(),
+            shipping: order.shipping.map(OrderShippingResponse::from),
+            shipping_address: order.shipping_address,
+            billing_address: order.billing_address,
+            tracking_number: order.tracking_number,
+            release_date: order.release_date,
+            history: order
+                .history
+                .into_iter()
+                .map(OrderStatusChangeResponse::from)
+                .collect(),
+            created_at: order.created_at,
+            updated_at: order.updated_at,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::payment::PaymentEntity;
+
+#[derive(Debug, Deserialize)]
+pub struct PayOrderRequest {
+    /// Payment method token from the PSP's client-side SDK
+    pub payment_method: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaymentResponse {
+    pub id: i32,
+    pub order_id: i32,
+    pub provider: String,
+    pub status: String,
+    pub amount: i64,
+    pub currency: String,
+    pub captured_amount: i64,
+    pub refunded_amount: i64,
+    /// Set when the customer must complete 3-D Secure
+    pub next_action_url: Option<String>,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PaymentEntity> for PaymentResponse {
+    fn from(payment: PaymentEntity) -> Self {
+        Self {
+            id: payment.id,
+            order_id: payment.order_id,
+            provider: payment.provider,
+            status: payment.status.as_str().to_string(),
+            amount: payment.amount,
+            currency: payment.currency,
+            captured_amount: payment.captured_amount,
+            refunded_amount: payment.refunded_amount,
+            next_action_url: payment.next_action_url,
+            failure_reason: payment.failure_reason,
+            created_at: payment.created_at,
+            updated_at: payment.updated_at,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::book::BookEntity;
+
+#[derive(Debug, Deserialize)]
+pub struct SetReleaseRequest {
+    /// `None` for a book that is out (or has no announced date)
+    pub release_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub preorder_enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookReleaseResponse {
+    pub book_id: i32,
+    pub title: String,
+    pub release_date: Option<NaiveDate>,
+    pub preorder_enabled: bool,
+    /// Copies that can still be pre-ordered
+    pub allocation: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreorderPriceCheckResponse {
+    pub books_checked: usize,
+    /// Pre-order lines whose protected price went down
+    pub lines_lowered: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreorderReleaseResponse {
+    pub released: usize,
+    /// Left waiting for the next run, e.g. the capture was refused
+    pub failed: usize,
+    /// Total given back through price-drop protection, in minor units
+    pub price_protection_total: i64,
+}
+
+impl From<BookEntity> for BookReleaseResponse {
+    fn from(book: BookEntity) -> Self {
+        Self {
+            book_id: book.id,
+            title: book.title.as_str().to_string(),
+            release_date: book.release_date,
+            preorder_enabled: book.preorder_enabled,
+            allocation: book.stock_quantity,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{book_price::BookPriceEntity, store::StoreEntity};
+
+#[derive(Debug, Deserialize)]
+pub struct SetBookPriceRequest {
+    /// Price for one store; omit to set the price for every store in `currency`
+    pub store_code: Option<String>,
+    pub currency: String,
+    /// Decimal string in major units, e.g. "395.00"
+    pub amount: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoreResponse {
+    pub code: String,
+    pub name: String,
+    pub currency: String,
+    pub jurisdiction: String,
+    pub prices_include_tax: bool,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookPriceResponse {
+    pub id: i32,
+    pub book_id: i32,
+    pub store_id: Option<i32>,
+    pub currency: String,
+    pub amount: i64,
+    pub formatted: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Price of a book in a store, with the tax split out.
+#[derive(Debug, Serialize)]
+pub struct PriceQuoteResponse {
+    pub book_id: i32,
+    pub store_code: String,
+    pub currency: String,
+    pub tax_class: String,
+    pub tax_rate_bps: i32,
+    pub net: i64,
+    pub tax: i64,
+    pub gross: i64,
+    /// The amount to show on the shelf (gross or net depending on the store)
+    pub display_amount: i64,
+    pub display_includes_tax: bool,
+    /// e.g. "395.00 THB"
+    pub formatted: String,
+}
+
+impl From<StoreEntity> for StoreResponse {
+    fn from(store: StoreEntity) -> Self {
+        Self {
+            code: store.code,
+            name: store.name,
+            currency: store.currency.as_str().to_string(),
+            jurisdiction: store.jurisdiction,
+            prices_include_tax: store.prices_include_tax,
+            is_default: store.is_default,
+        }
+    }
+}
+
+impl From<BookPriceEntity> for BookPriceResponse {
+    fn from(price: BookPriceEntity) -> Self {
+        Self {
+            id: price.id,
+            book_id: price.book_id,
+            store_id: price.store_id,
+            currency: price.price.currency().as_str().to_string(),
+            amount: price.price.amount(),
+            formatted: price.price.to_string(),
+            updated_at: price.updated_at,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    entities::{
+        coupon::CouponEntity,
+        promotion::{PromotionAction, PromotionCondition, PromotionEntity},
+    },
+    services::promotion_evaluator::AppliedPromotion,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePromotionRequest {
+    pub name: String,
+    #[serde(default)]
+    pub conditions: Vec<PromotionCondition>,
+    pub action: PromotionAction,
+    pub priority: Option<i32>,
+    pub stackable: Option<bool>,
+    pub requires_coupon: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCouponRequest {
+    pub code: String,
+    /// Total redemptions allowed; 1 makes a single-use code
+    pub usage_limit: Option<i32>,
+    pub per_customer_limit: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplyCouponRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PromotionResponse {
+    pub id: i32,
+    pub name: String,
+    pub conditions: Vec<PromotionCondition>,
+    pub action: PromotionAction,
+    pub priority: i32,
+    pub stackable: bool,
+    pub requires_coupon: bool,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CouponResponse {
+    pub id: i32,
+    pub code: String,
+    pub promotion_id: i32,
+    pub usage_limit: Option<i32>,
+    pub per_customer_limit: Option<i32>,
+    pub times_used: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A promotion the evaluator applied to a cart.
+#[derive(Debug, Serialize)]
+pub struct AppliedPromotionResponse {
+    pub promotion_id: i32,
+    pub name: String,
+    pub coupon_code: Option<String>,
+    pub discount: i64,
+    pub free_shipping: bool,
+}
+
+impl From<PromotionEntity> for PromotionResponse {
+    fn from(promotion: PromotionEntity) -> Self {
+        Self {
+            id: promotion.id,
+            name: promotion.name,
+            conditions: promotion.conditions,
+            action: promotion.action,
+            priority: promotion.priority,
+            stackable: promotion.stackable,
+            requires_coupon: promotion.requires_coupon,
+            is_active: promotion.is_active,
+            created_at: promotion.created_at,
+            updated_at: promotion.updated_at,
+        }
+    }
+}
+
+impl From<CouponEntity> for CouponResponse {
+    fn from(coupon: CouponEntity) -> Self {
+        Self {
+            id: coupon.id,
+            code: coupon.code.as_str().to_string(),
+            promotion_id: coupon.promotion_id,
+            usage_limit: coupon.usage_limit,
+            per_customer_limit: coupon.per_customer_limit,
+            times_used: coupon.times_used,
+            is_active: coupon.is_active,
+            created_at: coupon.created_at,
+        }
+    }
+}
+
+impl From<AppliedPromotion> for AppliedPromotionResponse {
+    fn from(applied: AppliedPromotion) -> Self {
+        Self {
+            promotion_id: applied.promotion_id,
+            name: applied.name,
+            coupon_code: applied.coupon_code,
+            discount: applied.discount,
+            free_shipping: applied.free_shipping,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SalesReportQuery {
+    /// First sales day (Thai time); defaults to 29 days before `to`
+    pub from: Option<NaiveDate>,
+    /// Last sales day, included; defaults to today
+    pub to: Option<NaiveDate>,
+    /// Store code, e.g. "TH-ONLINE"; leave out for every store
+    pub store: Option<String>,
+    /// Revenue series only: "day" (default), "week" or "month"
+    pub granularity: Option<String>,
+    /// Ranked reports only: rows to return, 20 by default
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportExportQuery {
+    /// "revenue", "units" or "customers"
+    pub report: String,
+    /// Units report only: "title" (default), "author", "category" or "store"
+    pub by: Option<String>,
+    /// "csv" (default) or "xlsx"
+    pub format: Option<String>,
+}
+
+/// Amounts are minor units of `currency`
+#[derive(Debug, Serialize)]
+pub struct SalesTotalsResponse {
+    pub currency: String,
+    pub orders: i64,
+    pub units: i64,
+    /// Order totals after discounts, shipping and tax included
+    pub revenue: i64,
+    pub discount_total: i64,
+    pub shipping_total: i64,
+    pub tax_total: i64,
+    /// Orders with any money given back
+    pub refunded_orders: i64,
+    pub refunded_amount: i64,
+    /// revenue - refunded_amount
+    pub net_revenue: i64,
+    pub average_order_value: i64,
+    /// refunded_orders / orders, 0 to 1
+    pub refund_rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SalesSummaryResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub store: Option<String>,
+    /// Figures include sales up to this time
+    pub refreshed_at: Option<DateTime<Utc>>,
+    /// One entry per currency
+    pub totals: Vec<SalesTotalsResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevenuePeriodResponse {
+    /// First day of the day, week (Monday) or month
+    pub period_start: NaiveDate,
+    #[serde(flatten)]
+    pub totals: SalesTotalsResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevenueReportResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub store: Option<String>,
+    pub granularity: String,
+    pub refreshed_at: Option<DateTime<Utc>>,
+    /// Oldest first; periods without sales are left out
+    pub periods: Vec<RevenuePeriodResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnitsSoldResponse {
+    /// Title, author, category or store code; null for uncategorised books
+    pub key: Option<String>,
+    /// Set when broken down by title
+    pub book_id: Option<i32>,
+    pub currency: String,
+    pub units: i64,
+    /// Line totals before order-level discounts
+    pub revenue: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnitsSoldReportResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub store: Option<String>,
+    /// "title", "author", "category" or "store"
+    pub by: String,
+    pub refreshed_at: Option<DateTime<Utc>>,
+    /// Most units first
+    pub rows: Vec<UnitsSoldResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CustomerSalesResponse {
+    pub user_id: i32,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub currency: String,
+    pub orders: i64,
+    pub revenue: i64,
+    pub average_order_value: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TopCustomersResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub store: Option<String>,
+    pub refreshed_at: Option<DateTime<Utc>>,
+    /// Highest spend first
+    pub customers: Vec<CustomerSalesResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportRefreshResponse {
+    pub refreshed_at: DateTime<Utc>,
+}
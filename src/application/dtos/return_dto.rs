@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::return_request::ReturnRequestEntity;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReturnRequest {
+    pub order_item_id: i32,
+    pub quantity: i32,
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewReturnRequest {
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReceiveReturnRequest {
+    /// `resellable` or `damaged`
+    pub condition: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RefundReturnRequest {
+    /// Credit the customer's store balance instead of the original payment
+    #[serde(default)]
+    pub to_store_credit: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReturnResponse {
+    pub id: i32,
+    pub order_id: i32,
+    pub order_item_id: i32,
+    pub book_id: i32,
+    pub user_id: i32,
+    pub quantity: i32,
+    pub reason: String,
+    pub status: String,
+    pub reviewed_by: Option<i32>,
+    pub review_note: Option<String>,
+    pub condition: Option<String>,
+    pub refund_amount: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ReturnRequestEntity> for ReturnResponse {
+    fn from(request: ReturnRequestEntity) -> Self {
+        Self {
+            id: request.id,
+            order_id: request.order_id,
+            order_item_id: request.order_item_id,
+            book_id: request.book_id,
+            user_id: request.user_id,
+            quantity: request.quantity.value(),
+            reason: request.reason,
+            status: request.status.as_str().to_string(),
+            reviewed_by: request.reviewed_by,
+            review_note: request.review_note,
+            condition: request.condition.map(|c| c.as_str().to_string()),
+            refund_amount: request.refund_amount,
+            created_at: request.created_at,
+            updated_at: request.updated_at,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{book::BookEntity, review::ReviewEntity};
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewRequest {
+    /// 1-5 stars
+    pub rating: i32,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RejectReviewRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VoteReviewRequest {
+    pub helpful: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewResponse {
+    pub id: i32,
+    pub book_id: i32,
+    pub user_id: i32,
+    pub rating: i32,
+    pub title: String,
+    pub body: String,
+    pub verified_purchase: bool,
+    pub status: String,
+    /// Only shown to the author and staff
+    pub rejection_reason: Option<String>,
+    pub helpful_count: i32,
+    pub unhelpful_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookRatingResponse {
+    pub book_id: i32,
+    pub rating_count: i32,
+    pub average_rating: Option<f64>,
+}
+
+impl From<ReviewEntity> for ReviewResponse {
+    fn from(review: ReviewEntity) -> Self {
+        Self {
+            id: review.id,
+            book_id: review.book_id,
+            user_id: review.user_id,
+            rating: review.rating.value(),
+            title: review.title,
+            body: review.body,
+            verified_purchase: review.verified_purchase,
+            status: review.status.as_str().to_string(),
+            rejection_reason: review.rejection_reason,
+            helpful_count: review.helpful_count,
+            unhelpful_count: review.unhelpful_count,
+            created_at: review.created_at,
+            updated_at: review.updated_at,
+        }
+    }
+}
+
+impl From<&BookEntity> for BookRatingResponse {
+    fn from(book: &BookEntity) -> Self {
+        Self {
+            book_id: book.id,
+            rating_count: book.rating_count,
+            average_rating: book.average_rating(),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct SearchRequest {
+    pub q: String,
+    /// 1-based
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
+
+/// Highlighted fields are HTML-escaped with matches wrapped in `<mark>`.
+#[derive(Debug, Serialize)]
+pub struct SearchHitResponse {
+    pub book_id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub author: String,
+    pub price: i64,
+    pub currency: String,
+    pub in_stock: bool,
+    pub average_rating: Option<f64>,
+    pub rank: f32,
+    pub title_highlight: String,
+    pub author_highlight: String,
+    pub description_snippet: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    /// True when the query was an ISBN and matched a book exactly
+    pub isbn_match: bool,
+    pub page: u32,
+    pub per_page: u32,
+    pub total: i64,
+    pub total_pages: u32,
+    pub results: Vec<SearchHitResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestRequest {
+    pub q: String,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuggestionResponse {
+    pub book_id: i32,
+    pub title: String,
+}
+
+/// Outcome of syncing or rebuilding the search index
+#[derive(Debug, Serialize)]
+pub struct IndexSyncResponse {
+    pub indexed: usize,
+    pub removed: usize,
+}
@@ -0,0 +1,131 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::shipping_zone::{
+    ShippingMethodEntity, ShippingRateEntity, ShippingZoneEntity,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateShippingZoneRequest {
+    pub name: String,
+    pub country: String,
+    /// Postal code prefixes covered by the zone; empty for the whole country
+    #[serde(default)]
+    pub postal_prefixes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShippingRateRequest {
+    pub min_value: i64,
+    pub max_value: Option<i64>,
+    pub amount: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateShippingMethodRequest {
+    pub code: String,
+    pub name: String,
+    /// "standard" | "express" | "store_pickup"
+    pub kind: String,
+    /// "weight" | "item_count" | "subtotal"
+    pub rate_basis: String,
+    pub min_days: i32,
+    pub max_days: i32,
+    pub max_weight_grams: Option<i32>,
+    #[serde(default)]
+    pub rates: Vec<ShippingRateRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShippingDestinationRequest {
+    pub country: String,
+    pub postal_code: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShippingRateResponse {
+    pub min_value: i64,
+    pub max_value: Option<i64>,
+    pub amount: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShippingMethodResponse {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    pub kind: String,
+    pub rate_basis: String,
+    pub min_days: i32,
+    pub max_days: i32,
+    pub max_weight_grams: Option<i32>,
+    pub is_active: bool,
+    pub rates: Vec<ShippingRateResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShippingZoneResponse {
+    pub id: i32,
+    pub name: String,
+    pub country: String,
+    pub postal_prefixes: Vec<String>,
+    pub is_active: bool,
+    pub methods: Vec<ShippingMethodResponse>,
+}
+
+/// A delivery option the customer can pick at checkout.
+#[derive(Debug, Serialize)]
+pub struct ShippingOptionResponse {
+    pub method_id: i32,
+    pub code: String,
+    pub name: String,
+    pub kind: String,
+    pub currency: String,
+    pub amount: i64,
+    pub estimated_from: NaiveDate,
+    pub estimated_to: NaiveDate,
+}
+
+impl From<ShippingRateEntity> for ShippingRateResponse {
+    fn from(rate: ShippingRateEntity) -> Self {
+        Self {
+            min_value: rate.min_value,
+            max_value: rate.max_value,
+            amount: rate.amount,
+        }
+    }
+}
+
+impl From<ShippingMethodEntity> for ShippingMethodResponse {
+    fn from(method: ShippingMethodEntity) -> Self {
+        Self {
+            id: method.id,
+            code: method.code,
+            name: method.name,
+            kind: method.kind.as_str().to_string(),
+            rate_basis: method.rate_basis.as_str().to_string(),
+            min_days: method.min_days,
+            max_days: method.max_days,
+            max_weight_grams: method.max_weight_grams,
+            is_active: method.is_active,
+            rates: method.rates.into_iter().map(ShippingRateResponse::from).collect(),
+        }
+    }
+}
+
+impl From<ShippingZoneEntity> for ShippingZoneResponse {
+    fn from(zone: ShippingZoneEntity) -> Self {
+        Self {
+            id: zone.id,
+            name: zone.name,
+            country: zone.country,
+            postal_prefixes: zone.postal_prefixes,
+            is_active: zone.is_active,
+            methods: zone
+                .methods
+                .into_iter()
+                .map(ShippingMethodResponse::from)
+                .collect(),
+        }
+    }
+}
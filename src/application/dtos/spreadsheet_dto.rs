@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct SpreadsheetImportRequest {
+    /// "csv" or "xlsx"
+    pub format: String,
+    /// The uploaded file
+    #[serde(skip)]
+    pub content: Vec<u8>,
+    /// Field -> header in the file, e.g. {"price": "Retail THB"}. Fields not
+    /// listed are matched by name, ignoring case.
+    pub columns: Option<HashMap<String, String>>,
+    /// Validate and report only; nothing is written
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpreadsheetExportQuery {
+    /// "csv" or "xlsx"
+    pub format: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RowErrorResponse {
+    /// Spreadsheet row number, the header being row 1
+    pub row: usize,
+    /// Field the error is about; `None` for the row as a whole
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SpreadsheetImportResponse {
+    pub dry_run: bool,
+    /// True only when every row was written
+    pub applied: bool,
+    /// Data rows read, blank rows not counted
+    pub rows: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub errors: Vec<RowErrorResponse>,
+}
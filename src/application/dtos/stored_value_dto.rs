@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{
+    stored_value_account::StoredValueAccountEntity,
+    stored_value_entry::StoredValueEntryEntity,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct IssueGiftCardRequest {
+    /// Value in minor units
+    pub amount: i64,
+    /// Defaults to THB
+    pub currency: Option<String>,
+    /// Defaults to 3 years; at least 1 year
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GiftCardCodeRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RedeemGiftCardRequest {
+    pub code: String,
+    /// Defaults to as much as the card and the order allow
+    pub amount: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RedeemStoreCreditRequest {
+    /// Defaults to as much as the balance and the order allow
+    pub amount: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdjustBalanceRequest {
+    /// Signed, in minor units
+    pub amount: i64,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoredValueAccountResponse {
+    pub id: i32,
+    pub kind: String,
+    /// Gift cards only, e.g. `...7KQM`
+    pub code_last4: Option<String>,
+    pub user_id: Option<i32>,
+    pub currency: String,
+    pub balance: i64,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Returned once at issuance; the code cannot be looked up again
+#[derive(Debug, Serialize)]
+pub struct IssuedGiftCardResponse {
+    pub code: String,
+    pub account: StoredValueAccountResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoredValueEntryResponse {
+    pub id: i32,
+    pub amount: i64,
+    pub reason: String,
+    pub order_id: Option<i32>,
+    pub note: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountLedgerResponse {
+    pub account: StoredValueAccountResponse,
+    pub entries: Vec<StoredValueEntryResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExpiryRunResponse {
+    pub accounts_expired: usize,
+    /// Balance written off, in minor units (mixed currencies are summed as-is)
+    pub amount_expired: i64,
+}
+
+impl From<StoredValueAccountEntity> for StoredValueAccountResponse {
+    fn from(account: StoredValueAccountEntity) -> Self {
+        Self {
+            id: account.id,
+            kind: account.kind.as_str().to_string(),
+            code_last4: account.code_last4,
+            user_id: account.user_id,
+            currency: account.currency.as_str().to_string(),
+            balance: account.balance,
+            expires_at: account.expires_at,
+            created_at: account.created_at,
+        }
+    }
+}
+
+impl From<StoredValueEntryEntity> for StoredValueEntryResponse {
+    fn from(entry: StoredValueEntryEntity) -> Self {
+        Self {
+            id: entry.id,
+            amount: entry.amount,
+            reason: entry.reason.as_str().to_string(),
+            order_id: entry.order_id,
+            note: entry.note,
+            created_by: entry.created_by,
+            created_at: entry.created_at,
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::entities::{role::RoleEntity, user::UserEntity};
+use crate::domain::{
+    entities::{address::AddressEntity, role::RoleEntity, user::UserEntity},
+    value_objects::postal_address::PostalAddress,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateUserRequest {
@@ -25,11 +28,46 @@ pub struct UpdateUserRequest {
     pub phone: Option<String>,
 }
 
+/// Metadata of an uploaded avatar; the bytes are passed alongside
+#[derive(Debug, Deserialize)]
+pub struct UploadAvatarRequest {
+    /// As sent by the client: image/jpeg or image/png
+    pub content_type: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdatePasswordRequest {
     pub new_password: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AddressRequest {
+    pub label: Option<String>,
+    pub recipient_name: String,
+    pub phone: String,
+    pub line1: String,
+    pub line2: Option<String>,
+    pub subdistrict: Option<String>,
+    pub district: Option<String>,
+    pub province: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: String,
+    pub is_default_shipping: Option<bool>,
+    pub is_default_billing: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddressResponse {
+    pub id: i32,
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub address: PostalAddress,
+    pub is_default_shipping: bool,
+    pub is_default_billing: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct RoleSummary {
     pub id: i32,
@@ -46,6 +84,8 @@ pub struct UserResponse {
     pub sex: String,
     pub phone: String,
     pub is_active: bool,
+    /// Largest square rendition of the profile picture (WebP)
+    pub avatar_url: Option<String>,
     pub roles: Vec<RoleSummary>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -61,6 +101,7 @@ impl From<UserEntity> for UserResponse {
             sex: user.sex,
             phone: user.phone.as_str().to_string(),
             is_active: user.is_active,
+            avatar_url: user.avatar.as_ref().and_then(|a| a.url()).map(str::to_string),
             roles: Vec::new(),
             created_at: user.created_at,
             updated_at: user.updated_at,
@@ -76,4 +117,34 @@ impl From<RoleEntity> for RoleSummary {
             description: role.description.map(|d| d.as_str().to_string()),
         }
     }
-}
\ No newline at end of file
+}
+impl AddressRequest {
+    /// Validates the address part with the country's rules
+    pub fn to_postal_address(&self) -> anyhow::Result<PostalAddress> {
+        PostalAddress::new(
+            self.recipient_name.clone(),
+            self.phone.clone(),
+            self.line1.clone(),
+            self.line2.clone(),
+            self.subdistrict.clone(),
+            self.district.clone(),
+            self.province.clone(),
+            self.postal_code.clone(),
+            self.country.clone(),
+        )
+    }
+}
+
+impl From<AddressEntity> for AddressResponse {
+    fn from(address: AddressEntity) -> Self {
+        Self {
+            id: address.id,
+            label: address.label,
+            address: address.address,
+            is_default_shipping: address.is_default_shipping,
+            is_default_billing: address.is_default_billing,
+            created_at: address.created_at,
+            updated_at: address.updated_at,
+        }
+    }
+}
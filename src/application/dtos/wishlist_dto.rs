@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct WishlistRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WishlistItemRequest {
+    pub book_id: i32,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WishlistItemResponse {
+    pub book_id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub author: String,
+    pub price: i64,
+    pub currency: String,
+    pub in_stock: bool,
+    pub note: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WishlistResponse {
+    pub id: i32,
+    pub name: String,
+    /// Set while the list is shared; build the public link from it
+    pub share_token: Option<String>,
+    /// Books removed from the catalog are left out
+    pub items: Vec<WishlistItemResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What visitors see through a share link: no ids, no owner.
+#[derive(Debug, Serialize)]
+pub struct SharedWishlistResponse {
+    pub name: String,
+    pub owner_name: String,
+    pub items: Vec<WishlistItemResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StockWatchResponse {
+    pub id: i32,
+    pub book_id: i32,
+    pub title: String,
+    /// False once the back-in-stock notification has been queued
+    pub waiting: bool,
+    pub notified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationDispatchResponse {
+    pub sent: usize,
+    pub failed: usize,
+}
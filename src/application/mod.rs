@@ -1,2 +1,3 @@
+pub mod authorization;
 pub mod dtos;
 pub mod use_cases;
@@ -5,6 +5,7 @@ use crate::application::{
 use anyhow::{Result, anyhow, Context};
 use crate::{
     domain::repositories::{
+        cart_repository::CartRepository,
         user_repository::UserRepository,
     },
     infrastructure::{
@@ -12,6 +13,7 @@ use crate::{
         jwt::JwtService,
     },
     domain::entities::user::UserEntity,
+    domain::value_objects::cart_token::CartToken,
 };
 
 /// AuthUseCase จัดการ Authentication flow ทั้งหมด (AT/RT Stateless)
@@ -19,6 +21,7 @@ pub struct AuthUseCase {
     user_repo: Arc<dyn UserRepository>,
     password_repo: Arc<dyn PasswordService>,
     jwt_repo: Arc<dyn JwtService>,
+    cart_repo: Arc<dyn CartRepository>,
 }
 
 impl AuthUseCase {
@@ -26,11 +29,13 @@ impl AuthUseCase {
         user_repo: Arc<dyn UserRepository>,
         password_repo: Arc<dyn PasswordService>,
         jwt_repo: Arc<dyn JwtService>,
+        cart_repo: Arc<dyn CartRepository>,
     ) -> Self {
         Self {
             user_repo,
             password_repo,
             jwt_repo,
+            cart_repo,
         }
     }
 
@@ -86,7 +91,14 @@ impl AuthUseCase {
             return Err(anyhow!("Invalid credentials"));
         }
 
-        // 3. DB Call (Async)
+        // 3. Merge guest cart (merge ไม่สำเร็จไม่ควรทำให้ login ล้มเหลว)
+        if let Some(token) = req.cart_token.as_deref()
+            && let Err(e) = self.merge_guest_cart(user.id, token).await
+        {
+            tracing::warn!("Failed to merge guest cart for user {}: {:?}", user.id, e);
+        }
+
+        // 4. DB Call (Async)
         let roles = self.user_repo.find_roles(user.id).await
             .context("Failed to fetch user roles")?;
         
@@ -94,13 +106,13 @@ impl AuthUseCase {
             .map(|r| r.name.as_str().to_string()) 
             .collect();
 
-        // 4. JWT Generation (Sync - ไม่มี .await แล้ว!)
+        // 5. JWT Generation (Sync - ไม่มี .await แล้ว!)
         // นี่คือจุดที่แก้ครับ
         let access_token = self.jwt_repo
             .generate_access_token(user.id, &role_names) // <-- No await
             .context("Failed to create access token")?;
 
-        // 5. JWT Generation (Sync - ไม่มี .await แล้ว!)
+        // 6. JWT Generation (Sync - ไม่มี .await แล้ว!)
         let refresh_token = self.jwt_repo
             .generate_refresh_token(user.id) // <-- No await
             .context("Failed to create refresh token")?;
@@ -159,6 +171,37 @@ impl AuthUseCase {
         })
     }
 
+    /// ย้ายของในตะกร้า guest เข้าตะกร้าของ user หลัง login
+    async fn merge_guest_cart(&self, user_id: i32, cart_token: &str) -> Result<()> {
+        let token = CartToken::new(cart_token.to_string())?;
+        let guest_cart = match self.cart_repo.find_by_token(&token).await
+            .context("Database error while fetching guest cart")?
+        {
+            Some(c) => c,
+            None => return Ok(()),
+        };
+
+        match self.cart_repo.find_by_user(user_id).await
+            .context("Database error while fetching user cart")?
+        {
+            Some(mut user_cart) => {
+                let guest_cart_id = guest_cart.id;
+                user_cart.merge(guest_cart);
+                self.cart_repo.update_merged(&user_cart, guest_cart_id).await
+                    .context("Failed to save merged cart")?;
+            }
+            None => {
+                // ยังไม่มีตะกร้าของ user ให้โอนตะกร้า guest มาเป็นของ user เลย
+                let mut cart = guest_cart;
+                cart.assign_to_user(user_id);
+                self.cart_repo.update(&cart).await
+                    .context("Failed to assign guest cart to user")?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn validate_token(&self, token: &str) -> Result<UserInfo> {
         // 1. Validate JWT (Sync - ไม่มี .await แล้ว!)
         let claims = self.jwt_repo.validate_access_token(token) // <-- No await
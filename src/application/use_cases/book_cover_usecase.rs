@@ -0,0 +1,159 @@
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        cover_dto::{BookCoverResponse, UploadCoverRequest},
+    },
+};
+use crate::domain::{
+    repositories::book_repository::BookRepository,
+    value_objects::book_cover::{BookCover, CoverVariant},
+};
+use crate::infrastructure::{cover_image, file_storage::FileStorage, uploaded_image};
+
+/// Largest upload accepted
+const MAX_COVER_BYTES: usize = 20 * 1024 * 1024;
+/// Larger pictures are refused before decoding (a 5000×5000 scan is fine)
+const MAX_COVER_PIXELS: u64 = 25_000_000;
+
+/// BookCoverUseCase — cover art: staff upload a JPEG or PNG, we render
+/// every size in WebP and JPEG without the original's metadata, store the
+/// files and keep their public URLs on the book.
+pub struct BookCoverUseCase {
+    book_repo: Arc<dyn BookRepository>,
+    storage: Arc<dyn FileStorage>,
+    /// Origin the stored files are served from, without trailing slash
+    public_base_url: String,
+}
+
+impl BookCoverUseCase {
+    pub fn new(book_repo: Arc<dyn BookRepository>, storage: Arc<dyn FileStorage>, public_base_url: String) -> Self {
+        Self {
+            book_repo,
+            storage,
+            public_base_url,
+        }
+    }
+
+    /// Uploads (or replaces) the cover of a book
+    pub async fn upload_cover(
+        &self,
+        caller: &UserInfo,
+        book_id: i32,
+        req: UploadCoverRequest,
+        content: Vec<u8>,
+    ) -> Result<BookCoverResponse> {
+        ensure_staff(caller)?;
+
+        self.book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .ok_or_else(|| anyhow!("Book not found"))?;
+        if content.len() > MAX_COVER_BYTES {
+            return Err(anyhow!("Image is too large (max 20 MB)"));
+        }
+
+        // เชื่อ content type ที่ client ส่งมาไม่ได้ ต้องตรงกับหัวไฟล์จริงด้วย
+        let declared = req.content_type.trim().to_lowercase();
+        if declared != "image/jpeg" && declared != "image/png" {
+            return Err(anyhow!("Unsupported image type {}; upload a JPEG or PNG", declared));
+        }
+        if uploaded_image::sniff_content_type(&content) != Some(declared.as_str()) {
+            return Err(anyhow!("File content is not {}", declared));
+        }
+
+        let digest = hex::encode(Sha256::digest(&content));
+        let rendered = tokio::task::spawn_blocking(move || cover_image::render(&content, MAX_COVER_PIXELS))
+            .await
+            .map_err(|e| anyhow!("Image processing task failed: {}", e))?
+            .map_err(|e| anyhow!("Cannot process image: {}", e))?;
+
+        // key มี hash ของไฟล์ต้นฉบับ ปกใหม่จึงได้ URL ใหม่ ไม่ติด cache ของปกเก่า
+        let mut variants = Vec::with_capacity(rendered.variants.len());
+        for variant in rendered.variants {
+            let storage_key = format!(
+                "covers/{}/{}-{}.{}",
+                book_id,
+                &digest[..16],
+                variant.size.as_str(),
+                variant.format.extension()
+            );
+            self.storage
+                .put(&storage_key, &variant.content, variant.format.content_type())
+                .await
+                .map_err(|e| anyhow!("Failed to store cover: {}", e))?;
+            variants.push(CoverVariant {
+                size: variant.size,
+                format: variant.format,
+                width: variant.width,
+                height: variant.height,
+                size_bytes: variant.content.len() as u64,
+                url: format!("{}/{}", self.public_base_url, storage_key),
+                storage_key,
+            });
+        }
+
+        let cover = BookCover {
+            width: rendered.width,
+            height: rendered.height,
+            blurhash: rendered.blurhash,
+            variants,
+            uploaded_at: Utc::now(),
+        };
+        let previous = self
+            .book_repo
+            .update_cover(book_id, Some(&cover))
+            .await
+            .map_err(|e| anyhow!("Failed to save cover: {}", e))?;
+
+        if let Some(previous) = previous {
+            // อัปโหลดไฟล์เดิมซ้ำจะได้ key เดิม ห้ามลบ
+            let kept: Vec<&str> = cover.variants.iter().map(|v| v.storage_key.as_str()).collect();
+            self.delete_files(&previous, &kept).await;
+        }
+
+        Ok(BookCoverResponse::from(cover))
+    }
+
+    pub async fn remove_cover(&self, caller: &UserInfo, book_id: i32) -> Result<()> {
+        ensure_staff(caller)?;
+
+        let previous = self
+            .book_repo
+            .update_cover(book_id, None)
+            .await
+            .map_err(|e| anyhow!("Failed to remove cover: {}", e))?;
+        if let Some(previous) = previous {
+            self.delete_files(&previous, &[]).await;
+        }
+        Ok(())
+    }
+
+    pub async fn get_cover(&self, book_id: i32) -> Result<Option<BookCoverResponse>> {
+        let book = self
+            .book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .ok_or_else(|| anyhow!("Book not found"))?;
+        Ok(book.cover.map(BookCoverResponse::from))
+    }
+
+    /// Files of a replaced cover; failures only leave orphans behind
+    async fn delete_files(&self, cover: &BookCover, keep: &[&str]) {
+        for variant in &cover.variants {
+            if keep.contains(&variant.storage_key.as_str()) {
+                continue;
+            }
+            if let Err(e) = self.storage.delete(&variant.storage_key).await {
+                tracing::warn!(key = %variant.storage_key, "Failed to delete replaced cover: {}", e);
+            }
+        }
+    }
+}
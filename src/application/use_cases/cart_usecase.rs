@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+
+use crate::application::{
+    dtos::{
+        cart_dto::{AddCartItemRequest, CartItemResponse, CartResponse, UpdateCartItemRequest},
+        promotion_dto::{AppliedPromotionResponse, ApplyCouponRequest},
+        shipping_dto::{ShippingDestinationRequest, ShippingOptionResponse},
+    },
+    use_cases::{
+        pricing_usecase::PricingUseCase,
+        promotion_usecase::{CartPricing, PromotionUseCase},
+        shipping_usecase::ShippingUseCase,
+    },
+};
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        cart::{CartEntity, CartOwner},
+    },
+    repositories::{book_repository::BookRepository, cart_repository::CartRepository},
+    services::shipping_calculator::{Parcel, ShippingDestination},
+};
+
+/// CartUseCase — shopping cart for guests (cart token) and logged-in users
+///
+/// `store_code` is the storefront the request came from (None = default
+/// store). It only matters when a cart is opened: a cart keeps selling from
+/// the store it was opened in.
+pub struct CartUseCase {
+    cart_repo: Arc<dyn CartRepository>,
+    book_repo: Arc<dyn BookRepository>,
+    promotions: Arc<PromotionUseCase>,
+    pricing: Arc<PricingUseCase>,
+    shipping: Arc<ShippingUseCase>,
+}
+
+impl CartUseCase {
+    pub fn new(
+        cart_repo: Arc<dyn CartRepository>,
+        book_repo: Arc<dyn BookRepository>,
+        promotions: Arc<PromotionUseCase>,
+        pricing: Arc<PricingUseCase>,
+        shipping: Arc<ShippingUseCase>,
+    ) -> Self {
+        Self {
+            cart_repo,
+            book_repo,
+            promotions,
+            pricing,
+            shipping,
+        }
+    }
+
+    /// Create an empty guest cart and hand out its token
+    pub async fn create_guest_cart(&self, store_code: Option<&str>) -> Result<CartResponse> {
+        let store = self.pricing.find_store(store_code).await?;
+        let mut cart = CartEntity::new_guest(store.id);
+
+        let cart_id = self
+            .cart_repo
+            .save(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to create cart: {}", e))?;
+        cart.id = cart_id;
+
+        self.build_response(cart).await
+    }
+
+    /// Get the cart with prices and stock rechecked
+    pub async fn get_cart(&self, owner: CartOwner, store_code: Option<&str>) -> Result<CartResponse> {
+        let cart = match self.find_cart(&owner).await? {
+            Some(c) => c,
+            None => match owner {
+                // User ที่ยังไม่เคยหยิบของลงตะกร้า ให้ถือว่าเป็นตะกร้าว่าง
+                CartOwner::User(_) => {
+                    let store = self.pricing.find_store(store_code).await?;
+                    CartEntity::new(owner, store.id)
+                }
+                CartOwner::Guest(_) => return Err(anyhow!("Cart not found")),
+            },
+        };
+
+        self.build_response(cart).await
+    }
+
+    pub async fn add_item(
+        &self,
+        owner: CartOwner,
+        store_code: Option<&str>,
+        req: AddCartItemRequest,
+    ) -> Result<CartResponse> {
+        let book = self.find_book(req.book_id).await?;
+        let mut cart = self.find_or_create_cart(owner, store_code).await?;
+
+        let requested = cart
+            .items
+            .iter()
+            .find(|i| i.book_id == book.id)
+            .map(|i| i.quantity.value())
+            .unwrap_or(0)
+            + req.quantity;
+        if !book.can_fulfil(requested) {
+            return Err(anyhow!("Only {} left in stock", book.stock_quantity.max(0)));
+        }
+
+        let store = self.pricing.store_by_id(cart.store_id).await?;
+        let price = self
+            .pricing
+            .shelf_prices(&store, &[&book])
+            .await?
+            .remove(&book.id)
+            .ok_or_else(|| anyhow!("'{}' is not sold in this store", book.title))?;
+
+        cart.add_item(book.id, req.quantity, price.amount())
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let updated = self
+            .cart_repo
+            .update(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to update cart: {}", e))?;
+
+        self.build_response(updated).await
+    }
+
+    pub async fn update_item(
+        &self,
+        owner: CartOwner,
+        book_id: i32,
+        req: UpdateCartItemRequest,
+    ) -> Result<CartResponse> {
+        let book = self.find_book(book_id).await?;
+        let mut cart = self
+            .find_cart(&owner)
+            .await?
+            .ok_or_else(|| anyhow!("Cart not found"))?;
+
+        if !book.can_fulfil(req.quantity) {
+            return Err(anyhow!("Only {} left in stock", book.stock_quantity.max(0)));
+        }
+
+        cart.set_quantity(book_id, req.quantity)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let updated = self
+            .cart_repo
+            .update(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to update cart: {}", e))?;
+
+        self.build_response(updated).await
+    }
+
+    pub async fn remove_item(&self, owner: CartOwner, book_id: i32) -> Result<CartResponse> {
+        let mut cart = self
+            .find_cart(&owner)
+            .await?
+            .ok_or_else(|| anyhow!("Cart not found"))?;
+
+        cart.remove_item(book_id).map_err(|e| anyhow!("{}", e))?;
+
+        let updated = self
+            .cart_repo
+            .update(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to update cart: {}", e))?;
+
+        self.build_response(updated).await
+    }
+
+    pub async fn clear_cart(&self, owner: CartOwner) -> Result<CartResponse> {
+        let mut cart = self
+            .find_cart(&owner)
+            .await?
+            .ok_or_else(|| anyhow!("Cart not found"))?;
+
+        cart.clear();
+
+        let updated = self
+            .cart_repo
+            .update(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to clear cart: {}", e))?;
+
+        self.build_response(updated).await
+    }
+
+    /// Attach a coupon code; it is validated every time the cart is priced
+    pub async fn apply_coupon(
+        &self,
+        owner: CartOwner,
+        store_code: Option<&str>,
+        req: ApplyCouponRequest,
+    ) -> Result<CartResponse> {
+        let mut cart = self.find_or_create_cart(owner, store_code).await?;
+
+        cart.apply_coupon(req.code).map_err(|e| anyhow!("{}", e))?;
+
+        let updated = self
+            .cart_repo
+            .update(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to update cart: {}", e))?;
+
+        self.build_response(updated).await
+    }
+
+    pub async fn remove_coupon(&self, owner: CartOwner) -> Result<CartResponse> {
+        let mut cart = self
+            .find_cart(&owner)
+            .await?
+            .ok_or_else(|| anyhow!("Cart not found"))?;
+
+        cart.remove_coupon();
+
+        let updated = self
+            .cart_repo
+            .update(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to update cart: {}", e))?;
+
+        self.build_response(updated).await
+    }
+
+    /// Delivery options for the cart's contents, cheapest first
+    pub async fn shipping_options(
+        &self,
+        owner: CartOwner,
+        req: ShippingDestinationRequest,
+    ) -> Result<Vec<ShippingOptionResponse>> {
+        let cart = self
+            .find_cart(&owner)
+            .await?
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| anyhow!("Cart is empty"))?;
+
+        let book_ids: Vec<i32> = cart.items.iter().map(|i| i.book_id).collect();
+        let books: HashMap<i32, BookEntity> = self
+            .book_repo
+            .find_by_ids(&book_ids)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch books: {}", e))?
+            .into_iter()
+            .map(|b| (b.id, b))
+            .collect();
+
+        let CartPricing { pricing, .. } = self.promotions.price_cart(&cart, &books).await?;
+        let parcel = Parcel::from_lines(
+            cart.items
+                .iter()
+                .filter_map(|i| Some((books.get(&i.book_id)?, i.quantity.value()))),
+            pricing.total,
+        );
+        let destination = ShippingDestination {
+            country: req.country,
+            postal_code: req.postal_code,
+        };
+
+        let store = self.pricing.store_by_id(cart.store_id).await?;
+        let options = self.shipping.options(&destination, &parcel, Utc::now()).await?;
+
+        Ok(options
+            .into_iter()
+            .map(|o| ShippingOptionResponse {
+                method_id: o.method.id,
+                code: o.method.code,
+                name: o.method.name,
+                kind: o.method.kind.as_str().to_string(),
+                currency: store.currency.as_str().to_string(),
+                // promotion ส่งฟรีใช้ได้กับทุกวิธีจัดส่ง
+                amount: if pricing.free_shipping { 0 } else { o.rate.amount },
+                estimated_from: o.rate.estimated_from,
+                estimated_to: o.rate.estimated_to,
+            })
+            .collect())
+    }
+
+    async fn find_cart(&self, owner: &CartOwner) -> Result<Option<CartEntity>> {
+        let result = match owner {
+            CartOwner::User(user_id) => self.cart_repo.find_by_user(*user_id).await,
+            CartOwner::Guest(token) => self.cart_repo.find_by_token(token).await,
+        };
+
+        result.map_err(|e| anyhow!("Database error while fetching cart: {}", e))
+    }
+
+    async fn find_or_create_cart(&self, owner: CartOwner, store_code: Option<&str>) -> Result<CartEntity> {
+        if let Some(cart) = self.find_cart(&owner).await? {
+            return Ok(cart);
+        }
+
+        if let CartOwner::Guest(_) = owner {
+            return Err(anyhow!("Cart not found"));
+        }
+
+        let store = self.pricing.find_store(store_code).await?;
+        let mut cart = CartEntity::new(owner, store.id);
+        cart.id = self
+            .cart_repo
+            .save(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to create cart: {}", e))?;
+
+        Ok(cart)
+    }
+
+    async fn find_book(&self, book_id: i32) -> Result<BookEntity> {
+        self.book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .filter(|b| b.is_active)
+            .ok_or_else(|| anyhow!("Book not found"))
+    }
+
+    /// Rechecks every line against the catalog, refreshes price snapshots that
+    /// went stale, flags lines whose price changed or that went out of stock,
+    /// and applies promotions.
+    async fn build_response(&self, mut cart: CartEntity) -> Result<CartResponse> {
+        let book_ids: Vec<i32> = cart.items.iter().map(|i| i.book_id).collect();
+        let books: HashMap<i32, BookEntity> = self
+            .book_repo
+            .find_by_ids(&book_ids)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch books: {}", e))?
+            .into_iter()
+            .map(|b| (b.id, b))
+            .collect();
+
+        // ราคาขึ้นกับ store ไม่ใช่ base price ของหนังสือ
+        let store = self.pricing.store_by_id(cart.store_id).await?;
+        let prices = self
+            .pricing
+            .shelf_prices(&store, &books.values().collect::<Vec<_>>())
+            .await?;
+
+        let mut previous_prices = HashMap::new();
+        for (book_id, price) in &prices {
+            if let Some(previous) = cart.reprice_item(*book_id, price.amount()) {
+                previous_prices.insert(*book_id, previous);
+            }
+        }
+
+        if !previous_prices.is_empty() && cart.id != 0 {
+            cart = self
+                .cart_repo
+                .update(&cart)
+                .await
+                .map_err(|e| anyhow!("Failed to update cart prices: {}", e))?;
+        }
+
+        let items: Vec<CartItemResponse> = cart
+            .items
+            .iter()
+            .map(|item| {
+                let book = books.get(&item.book_id);
+                let available_quantity = book
+                    .filter(|b| b.is_active && prices.contains_key(&b.id))
+                    .map(|b| b.stock_quantity.max(0))
+                    .unwrap_or(0);
+                let previous_unit_price = previous_prices.get(&item.book_id).copied();
+
+                CartItemResponse {
+                    book_id: item.book_id,
+                    isbn: book.map(|b| b.isbn.as_str().to_string()).unwrap_or_default(),
+                    title: book.map(|b| b.title.as_str().to_string()).unwrap_or_default(),
+                    quantity: item.quantity.value(),
+                    unit_price: item.unit_price,
+                    line_total: item.line_total(),
+                    price_changed: previous_unit_price.is_some(),
+                    previous_unit_price,
+                    out_of_stock: available_quantity < item.quantity.value(),
+                    available_quantity,
+                }
+            })
+            .collect();
+
+        let CartPricing {
+            pricing,
+            coupon_error,
+        } = self.promotions.price_cart(&cart, &books).await?;
+
+        let cart_token = match &cart.owner {
+            CartOwner::Guest(token) => Some(token.as_str().to_string()),
+            CartOwner::User(_) => None,
+        };
+
+        Ok(CartResponse {
+            id: cart.id,
+            cart_token,
+            currency: store.currency.as_str().to_string(),
+            has_issues: items.iter().any(|i| i.price_changed || i.out_of_stock),
+            item_count: cart.item_count(),
+            subtotal: cart.subtotal(),
+            discount_total: pricing.discount_total,
+            total: cart.subtotal() - pricing.discount_total,
+            free_shipping: pricing.free_shipping,
+            applied_promotions: pricing
+                .applied
+                .into_iter()
+                .map(AppliedPromotionResponse::from)
+                .collect(),
+            coupon_code: cart.coupon_code.as_ref().map(|c| c.as_str().to_string()),
+            coupon_error,
+            items,
+            updated_at: cart.updated_at,
+        })
+    }
+}
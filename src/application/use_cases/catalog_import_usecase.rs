@@ -0,0 +1,300 @@
+use std::{path::PathBuf, sync::Arc};
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use tracing::info;
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        catalog_import_dto::{
+            CatalogImportJobResponse, CatalogImportRecordResponse, CatalogImportReportQuery,
+            CatalogImportReportResponse, CreateCatalogImportRequest,
+        },
+    },
+};
+use crate::domain::{
+    entities::{
+        catalog_import_job::{CatalogImportJobEntity, ImportFormat, MAX_IMPORT_ATTEMPTS},
+        catalog_import_record::{CatalogImportRecordEntity, ImportOutcome},
+        catalog_record::CatalogRecord,
+    },
+    repositories::{
+        book_repository::BookRepository, catalog_import_repository::CatalogImportRepository,
+    },
+    value_objects::isbn::Isbn,
+};
+use crate::infrastructure::onix_reader::OnixReader;
+
+/// Records between checkpoints; a restarted job redoes at most this many
+const CHECKPOINT_EVERY: usize = 100;
+const JOB_LIST_LIMIT: i64 = 50;
+const DEFAULT_REPORT_PAGE: u32 = 100;
+const MAX_REPORT_PAGE: u32 = 1000;
+
+/// CatalogImportUseCase — supplier feeds (ONIX 3.0) into the catalog.
+///
+/// Staff queue a file from the import inbox; a background worker streams it,
+/// upserting books by ISBN and writing one report line per record. Progress
+/// is checkpointed by byte offset so a job cut off halfway resumes instead
+/// of starting over.
+pub struct CatalogImportUseCase {
+    import_repo: Arc<dyn CatalogImportRepository>,
+    book_repo: Arc<dyn BookRepository>,
+    inbox_dir: PathBuf,
+}
+
+impl CatalogImportUseCase {
+    pub fn new(
+        import_repo: Arc<dyn CatalogImportRepository>,
+        book_repo: Arc<dyn BookRepository>,
+        inbox_dir: PathBuf,
+    ) -> Self {
+        Self {
+            import_repo,
+            book_repo,
+            inbox_dir,
+        }
+    }
+
+    /// Staff: queue a feed that is already in the inbox
+    pub async fn create_import(
+        &self,
+        caller: &UserInfo,
+        req: CreateCatalogImportRequest,
+    ) -> Result<CatalogImportJobResponse> {
+        ensure_staff(caller)?;
+
+        let format: ImportFormat = req.format.parse()?;
+        let file_name = req.file_name.trim();
+        // ชื่อไฟล์เท่านั้น ห้ามมี path ไม่ให้อ่านไฟล์นอก inbox
+        if file_name.is_empty()
+            || file_name.starts_with('.')
+            || file_name.contains(['/', '\\'])
+        {
+            return Err(anyhow!("Invalid file name: {}", file_name));
+        }
+        if !self.inbox_dir.join(file_name).is_file() {
+            return Err(anyhow!("File not found in the import inbox: {}", file_name));
+        }
+
+        let job = CatalogImportJobEntity::new(format, file_name.to_string(), Some(caller.id))
+            .map_err(|e| anyhow!("{}", e))?;
+        let job = self
+            .import_repo
+            .create_job(&job)
+            .await
+            .map_err(|e| anyhow!("Failed to queue import: {}", e))?;
+
+        Ok(CatalogImportJobResponse::from(job))
+    }
+
+    pub async fn list_imports(&self, caller: &UserInfo) -> Result<Vec<CatalogImportJobResponse>> {
+        ensure_staff(caller)?;
+
+        let jobs = self
+            .import_repo
+            .find_jobs(JOB_LIST_LIMIT)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch imports: {}", e))?;
+
+        Ok(jobs.into_iter().map(CatalogImportJobResponse::from).collect())
+    }
+
+    pub async fn get_import(&self, caller: &UserInfo, id: i32) -> Result<CatalogImportJobResponse> {
+        ensure_staff(caller)?;
+        Ok(CatalogImportJobResponse::from(self.find_job(id).await?))
+    }
+
+    /// The per-record report, in file order
+    pub async fn get_report(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        query: CatalogImportReportQuery,
+    ) -> Result<CatalogImportReportResponse> {
+        ensure_staff(caller)?;
+
+        let job = self.find_job(id).await?;
+        let outcome = query
+            .outcome
+            .map(|o| o.parse::<ImportOutcome>())
+            .transpose()?;
+        let per_page = query
+            .per_page
+            .unwrap_or(DEFAULT_REPORT_PAGE)
+            .clamp(1, MAX_REPORT_PAGE);
+
+        let records = self
+            .import_repo
+            .find_records(job.id, outcome, query.after.unwrap_or(-1), per_page as i64)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch import report: {}", e))?;
+        let next_after = records
+            .last()
+            .filter(|_| records.len() == per_page as usize)
+            .map(|r| r.record_index);
+
+        Ok(CatalogImportReportResponse {
+            job: CatalogImportJobResponse::from(job),
+            records: records.into_iter().map(CatalogImportRecordResponse::from).collect(),
+            next_after,
+        })
+    }
+
+    /// Worker: claims the next due job and works it to the end. `None` when
+    /// nothing is waiting.
+    ///
+    /// A feed that cannot be read fails the job. Database errors are
+    /// returned as-is and leave the job running; once its lease runs out the
+    /// next worker resumes it from the last checkpoint.
+    pub async fn run_next(&self) -> Result<Option<CatalogImportJobResponse>> {
+        let Some(mut job) = self
+            .import_repo
+            .claim_next()
+            .await
+            .map_err(|e| anyhow!("Failed to claim import job: {}", e))?
+        else {
+            return Ok(None);
+        };
+
+        if job.is_exhausted() {
+            let reason = format!(
+                "Gave up after {} attempts: {}",
+                MAX_IMPORT_ATTEMPTS,
+                job.last_error.as_deref().unwrap_or("the worker stopped")
+            );
+            job.fail(reason);
+        } else {
+            info!(job_id = job.id, file = %job.file_name, offset = job.checkpoint_offset, "Catalog import started");
+            match self.process(&mut job).await? {
+                None => job.complete(),
+                Some(reason) => job.fail(reason),
+            }
+        }
+
+        self.import_repo
+            .finish(&job)
+            .await
+            .map_err(|e| anyhow!("Failed to finish import job: {}", e))?;
+        info!(
+            job_id = job.id,
+            status = job.status.as_str(),
+            records = job.records_processed,
+            created = job.created_count,
+            updated = job.updated_count,
+            rejected = job.rejected_count,
+            "Catalog import finished"
+        );
+
+        Ok(Some(CatalogImportJobResponse::from(job)))
+    }
+
+    /// Streams the file from the job's checkpoint. `Some(reason)` when the
+    /// file itself is broken; what was read before that stays imported.
+    async fn process(&self, job: &mut CatalogImportJobEntity) -> Result<Option<String>> {
+        let path = self.inbox_dir.join(&job.file_name);
+        let mut reader = match job.format {
+            ImportFormat::Onix => match OnixReader::open(&path, job.checkpoint_offset as u64) {
+                Ok(reader) => reader,
+                Err(e) => return Ok(Some(format!("{:#}", e))),
+            },
+        };
+        job.sender = reader.sender().map(str::to_string);
+
+        let mut batch = Vec::with_capacity(CHECKPOINT_EVERY);
+        let failure = loop {
+            let product = match reader.next_product() {
+                Ok(Some(product)) => product,
+                Ok(None) => break None,
+                Err(e) => break Some(format!("{:#}", e)),
+            };
+
+            let line = self
+                .import_record(job.id, job.records_processed, product.record)
+                .await?;
+            batch.push(line);
+            job.checkpoint_offset = product.end_offset as i64;
+            job.records_processed += 1;
+
+            if batch.len() >= CHECKPOINT_EVERY {
+                *job = self.checkpoint(job, &batch).await?;
+                batch.clear();
+            }
+        };
+
+        *job = self.checkpoint(job, &batch).await?;
+        Ok(failure)
+    }
+
+    /// Upserts one record by ISBN and returns its report line
+    async fn import_record(
+        &self,
+        job_id: i32,
+        record_index: i32,
+        record: CatalogRecord,
+    ) -> Result<CatalogImportRecordEntity> {
+        let mut line = CatalogImportRecordEntity {
+            job_id,
+            record_index,
+            reference: record.reference.clone(),
+            isbn: record.isbn.clone(),
+            book_id: None,
+            outcome: ImportOutcome::Rejected,
+            messages: Vec::new(),
+        };
+
+        let existing = match record.isbn.as_deref().map(Isbn::new) {
+            Some(Ok(isbn)) => self
+                .book_repo
+                .find_by_isbn(isbn.as_str())
+                .await
+                .map_err(|e| anyhow!("Database error while fetching book: {}", e))?,
+            _ => None,
+        };
+
+        let imported = match record.into_book(existing, Utc::now().date_naive()) {
+            Ok(imported) => imported,
+            Err(reasons) => {
+                line.messages = reasons;
+                return Ok(line);
+            }
+        };
+
+        let is_new = imported.book.id == 0;
+        line.messages = imported.warnings.clone();
+        // บันทึกไม่ผ่าน (เช่นค่าเกินขนาดคอลัมน์) ปฏิเสธเฉพาะ record นี้ ไม่หยุดทั้งไฟล์
+        match self.import_repo.save_book(&imported).await {
+            Ok(book_id) => {
+                line.book_id = Some(book_id);
+                line.outcome = if is_new {
+                    ImportOutcome::Created
+                } else {
+                    ImportOutcome::Updated
+                };
+            }
+            Err(e) => line.messages.push(format!("Could not be saved: {}", e)),
+        }
+
+        Ok(line)
+    }
+
+    async fn checkpoint(
+        &self,
+        job: &CatalogImportJobEntity,
+        batch: &[CatalogImportRecordEntity],
+    ) -> Result<CatalogImportJobEntity> {
+        self.import_repo
+            .checkpoint(job, batch)
+            .await
+            .map_err(|e| anyhow!("Failed to save import progress: {}", e))
+    }
+
+    async fn find_job(&self, id: i32) -> Result<CatalogImportJobEntity> {
+        self.import_repo
+            .find_job(id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching import: {}", e))?
+            .ok_or_else(|| anyhow!("Import not found"))
+    }
+}
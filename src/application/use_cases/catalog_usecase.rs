@@ -0,0 +1,196 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+
+use crate::application::dtos::{
+    catalog_dto::{
+        CatalogBookResponse, CatalogFacetsResponse, CatalogQueryRequest, CatalogResponse,
+        FacetValueResponse, PriceRangeFacetResponse, RatingFacetResponse,
+    },
+    cover_dto::BookCoverResponse,
+};
+use crate::domain::{
+    entities::book::BookEntity,
+    repositories::catalog_repository::{
+        CatalogFilter, CatalogRepository, CatalogSort, Facet, FacetCount, PRICE_FACET_BOUNDS,
+    },
+    services::search_text,
+    value_objects::book_format::BookFormat,
+};
+
+const DEFAULT_PER_PAGE: u32 = 24;
+const MAX_PER_PAGE: u32 = 100;
+/// Values shown per facet in the sidebar
+const FACET_LIMIT: i64 = 30;
+/// Values accepted per facet filter
+const MAX_FILTER_VALUES: usize = 50;
+
+/// CatalogUseCase — filtered storefront browsing with facet counts
+pub struct CatalogUseCase {
+    catalog_repo: Arc<dyn CatalogRepository>,
+}
+
+impl CatalogUseCase {
+    pub fn new(catalog_repo: Arc<dyn CatalogRepository>) -> Self {
+        Self { catalog_repo }
+    }
+
+    pub async fn browse(&self, req: CatalogQueryRequest) -> Result<CatalogResponse> {
+        let page = req.page.unwrap_or(1).max(1);
+        let per_page = req.per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE);
+        let sort = match req.sort.as_deref() {
+            Some(s) => s.parse::<CatalogSort>()?,
+            None => CatalogSort::default(),
+        };
+        let filter = build_filter(req)?;
+
+        let offset = (page as i64 - 1) * per_page as i64;
+        let result = self
+            .catalog_repo
+            .browse(&filter, sort, offset, per_page as i64, FACET_LIMIT)
+            .await
+            .map_err(|e| anyhow!("Failed to browse catalog: {}", e))?;
+
+        Ok(CatalogResponse {
+            page,
+            per_page,
+            total: result.total,
+            total_pages: (result.total as u64).div_ceil(per_page as u64) as u32,
+            books: result.books.into_iter().map(to_book_response).collect(),
+            facets: to_facets_response(&result.facets, &filter),
+        })
+    }
+}
+
+fn build_filter(req: CatalogQueryRequest) -> Result<CatalogFilter> {
+    let clean = |values: Vec<String>| -> Result<Vec<String>> {
+        let mut values: Vec<String> = values
+            .into_iter()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        values.sort();
+        values.dedup();
+        if values.len() > MAX_FILTER_VALUES {
+            return Err(anyhow!("Too many filter values (max {})", MAX_FILTER_VALUES));
+        }
+        Ok(values)
+    };
+
+    let formats = clean(req.format)?
+        .iter()
+        .map(|f| f.parse::<BookFormat>())
+        .collect::<Result<Vec<_>>>()?;
+
+    if req.price_min.is_some_and(|p| p < 0) {
+        return Err(anyhow!("Minimum price cannot be negative"));
+    }
+    if let (Some(min), Some(max)) = (req.price_min, req.price_max)
+        && max <= min
+    {
+        return Err(anyhow!("Maximum price must be above the minimum price"));
+    }
+    if req.min_rating.is_some_and(|r| !(1..=5).contains(&r)) {
+        return Err(anyhow!("Minimum rating must be between 1 and 5"));
+    }
+
+    let tsquery = req
+        .q
+        .as_deref()
+        .and_then(|q| search_text::to_tsquery(&search_text::tokenize(q)));
+
+    Ok(CatalogFilter {
+        tsquery,
+        categories: clean(req.category)?,
+        authors: clean(req.author)?,
+        publishers: clean(req.publisher)?,
+        languages: clean(req.language)?
+            .into_iter()
+            .map(|l| l.to_lowercase())
+            .collect(),
+        formats,
+        price_min: req.price_min,
+        price_max: req.price_max,
+        min_rating: req.min_rating,
+        in_stock_only: req.in_stock,
+    })
+}
+
+fn to_book_response(book: BookEntity) -> CatalogBookResponse {
+    CatalogBookResponse {
+        id: book.id,
+        isbn: book.isbn.as_str().to_string(),
+        title: book.title.as_str().to_string(),
+        average_rating: book.average_rating(),
+        preorder: book.is_preorder(Utc::now().date_naive()),
+        author: book.author,
+        publisher: book.publisher,
+        category: book.category,
+        language: book.language,
+        format: book.format.as_str().to_string(),
+        price: book.price.amount(),
+        currency: book.price.currency().as_str().to_string(),
+        in_stock: book.stock_quantity > 0,
+        release_date: book.release_date,
+        rating_count: book.rating_count,
+        cover: book.cover.map(BookCoverResponse::from),
+    }
+}
+
+fn to_facets_response(facets: &[FacetCount], filter: &CatalogFilter) -> CatalogFacetsResponse {
+    let values = |facet: Facet, selected: &[String]| -> Vec<FacetValueResponse> {
+        facets
+            .iter()
+            .filter(|f| f.facet == facet)
+            .map(|f| FacetValueResponse {
+                selected: selected.contains(&f.value),
+                value: f.value.clone(),
+                count: f.count,
+            })
+            .collect()
+    };
+    let count_of = |facet: Facet, value: &str| -> i64 {
+        facets
+            .iter()
+            .find(|f| f.facet == facet && f.value == value)
+            .map(|f| f.count)
+            .unwrap_or(0)
+    };
+
+    // width_bucket: bucket i ครอบคลุม [bounds[i-1], bounds[i])
+    let price_ranges = (0..=PRICE_FACET_BOUNDS.len())
+        .map(|i| {
+            let min = if i == 0 { 0 } else { PRICE_FACET_BOUNDS[i - 1] };
+            let max = PRICE_FACET_BOUNDS.get(i).copied();
+            PriceRangeFacetResponse {
+                min,
+                max,
+                count: count_of(Facet::Price, &i.to_string()),
+                selected: filter.price_min.unwrap_or(0) == min && filter.price_max == max,
+            }
+        })
+        .filter(|r| r.count > 0)
+        .collect();
+
+    let ratings = (1..=4)
+        .rev()
+        .map(|stars: i32| RatingFacetResponse {
+            min_rating: stars,
+            count: (stars..=5).map(|s| count_of(Facet::Rating, &s.to_string())).sum(),
+            selected: filter.min_rating == Some(stars),
+        })
+        .collect();
+
+    let formats: Vec<String> = filter.formats.iter().map(|f| f.as_str().to_string()).collect();
+
+    CatalogFacetsResponse {
+        categories: values(Facet::Category, &filter.categories),
+        authors: values(Facet::Author, &filter.authors),
+        publishers: values(Facet::Publisher, &filter.publishers),
+        languages: values(Facet::Language, &filter.languages),
+        formats: values(Facet::Format, &formats),
+        price_ranges,
+        ratings,
+        in_stock: count_of(Facet::Availability, "in_stock"),
+    }
+}
@@ -0,0 +1,659 @@
+use std::{
+    io::{BufReader, BufWriter, Cursor, Seek},
+    sync::Arc,
+};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        digital_dto::{
+            DigitalAssetResponse, DownloadLimitRequest, DownloadLinkRequest, DownloadLinkResponse,
+            DownloadLogResponse, DownloadRequest, DownloadResponse, EntitlementResponse,
+            GrantEntitlementRequest, LibraryItemResponse, RevokeEntitlementRequest,
+            UploadAssetRequest, WatermarkTraceResponse,
+        },
+    },
+};
+use crate::domain::{
+    entities::{
+        digital_asset::DigitalAssetEntity, download_log::DownloadLogEntity,
+        download_watermark::DownloadWatermarkEntity, entitlement::EntitlementEntity,
+    },
+    repositories::{
+        book_repository::BookRepository, digital_asset_repository::DigitalAssetRepository,
+        download_log_repository::DownloadLogRepository,
+        download_watermark_repository::DownloadWatermarkRepository,
+        entitlement_repository::{EntitlementRepository, LinkUse}, user_repository::UserRepository,
+    },
+    value_objects::{
+        byte_range::RangeRequest, digital_file_format::DigitalFileFormat,
+        download_outcome::DownloadOutcome,
+    },
+};
+use crate::infrastructure::{
+    epub_watermark::{self, WatermarkStamp},
+    file_storage::{ByteStream, FileStorage},
+    url_signer::UrlSigner,
+};
+
+/// Largest file accepted for upload
+const MAX_ASSET_BYTES: u64 = 1024 * 1024 * 1024;
+const DOWNLOAD_LOG_LIMIT: i64 = 200;
+
+/// DigitalDeliveryUseCase — files of e-book/audiobook editions, the buyers'
+/// entitlements to them, and signed, limited, logged downloads. EPUBs are
+/// served as a copy watermarked for the buyer.
+pub struct DigitalDeliveryUseCase {
+    asset_repo: Arc<dyn DigitalAssetRepository>,
+    entitlement_repo: Arc<dyn EntitlementRepository>,
+    log_repo: Arc<dyn DownloadLogRepository>,
+    watermark_repo: Arc<dyn DownloadWatermarkRepository>,
+    book_repo: Arc<dyn BookRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    storage: Arc<dyn FileStorage>,
+    signer: Arc<UrlSigner>,
+    /// Origin download links are built on, without trailing slash
+    base_url: String,
+    link_ttl: Duration,
+}
+
+impl DigitalDeliveryUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        asset_repo: Arc<dyn DigitalAssetRepository>,
+        entitlement_repo: Arc<dyn EntitlementRepository>,
+        log_repo: Arc<dyn DownloadLogRepository>,
+        watermark_repo: Arc<dyn DownloadWatermarkRepository>,
+        book_repo: Arc<dyn BookRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        storage: Arc<dyn FileStorage>,
+        signer: Arc<UrlSigner>,
+        base_url: String,
+        link_ttl_minutes: i64,
+    ) -> Self {
+        Self {
+            asset_repo,
+            entitlement_repo,
+            log_repo,
+            watermark_repo,
+            book_repo,
+            user_repo,
+            storage,
+            signer,
+            base_url,
+            link_ttl: Duration::minutes(link_ttl_minutes),
+        }
+    }
+
+    // ---------- Back office ----------
+
+    /// Uploads (or replaces) the file of one format of a digital book. The
+    /// content is spooled to a temp file, never held in memory.
+    pub async fn upload_asset(
+        &self,
+        caller: &UserInfo,
+        book_id: i32,
+        req: UploadAssetRequest,
+        content: ByteStream,
+    ) -> Result<DigitalAssetResponse> {
+        ensure_staff(caller)?;
+
+        let file_format: DigitalFileFormat = req.file_format.parse()?;
+        let book = self
+            .book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .ok_or_else(|| anyhow!("Book not found"))?;
+        if book.format != file_format.book_format() {
+            return Err(anyhow!(
+                "Cannot attach a {} file to a book in {} format",
+                file_format.as_str(),
+                book.format.as_str()
+            ));
+        }
+
+        let (file, size, sha256) = spool_upload(content).await?;
+
+        // EPUB ต้องประทับ watermark ได้ ไม่อย่างนั้นจะไปพังตอนลูกค้าดาวน์โหลด
+        let file = if file_format == DigitalFileFormat::Epub {
+            tokio::task::spawn_blocking(move || -> Result<std::fs::File> {
+                let probe = WatermarkStamp {
+                    code: String::new(),
+                    licensee: String::new(),
+                    order_reference: String::new(),
+                };
+                let scratch = BufWriter::new(tempfile::tempfile()?);
+                epub_watermark::stamp(BufReader::new(&file), scratch, &probe)?;
+                (&file).rewind()?;
+                Ok(file)
+            })
+            .await
+            .map_err(|e| anyhow!("Watermark task failed: {}", e))?
+            .map_err(|e| anyhow!("EPUB cannot be watermarked: {}", e))?
+        } else {
+            file
+        };
+
+        let mut asset = DigitalAssetEntity::new(
+            book_id,
+            file_format,
+            req.file_name,
+            size as i64,
+            sha256,
+        )?;
+
+        // เก็บไฟล์ก่อนบันทึก record เพื่อไม่ให้มี record ที่ชี้ไปยังไฟล์ที่ไม่มีอยู่
+        self.storage
+            .put_file(
+                &asset.storage_key,
+                tokio::fs::File::from_std(file),
+                file_format.content_type(),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to store file: {}", e))?;
+
+        let (id, replaced_key) = self
+            .asset_repo
+            .save(&asset)
+            .await
+            .map_err(|e| anyhow!("Failed to save digital asset: {}", e))?;
+        asset.id = id;
+
+        if let Some(key) = replaced_key
+            && let Err(e) = self.storage.delete(&key).await
+        {
+            tracing::warn!(key = %key, "Failed to delete replaced file: {}", e);
+        }
+
+        Ok(DigitalAssetResponse::from(asset))
+    }
+
+    pub async fn get_book_assets(&self, caller: &UserInfo, book_id: i32) -> Result<Vec<DigitalAssetResponse>> {
+        ensure_staff(caller)?;
+
+        let assets = self
+            .asset_repo
+            .find_by_book(book_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch digital assets: {}", e))?;
+
+        Ok(assets.into_iter().map(DigitalAssetResponse::from).collect())
+    }
+
+    pub async fn delete_asset(&self, caller: &UserInfo, asset_id: i32) -> Result<()> {
+        ensure_staff(caller)?;
+
+        let asset = self.find_asset(asset_id).await?;
+        self.asset_repo
+            .delete(asset.id)
+            .await
+            .map_err(|e| anyhow!("Failed to delete digital asset: {}", e))?;
+        self.storage
+            .delete(&asset.storage_key)
+            .await
+            .map_err(|e| anyhow!("Failed to delete stored file: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Gives a user a digital book without an order (gifts, support cases)
+    pub async fn grant(&self, caller: &UserInfo, req: GrantEntitlementRequest) -> Result<EntitlementResponse> {
+        ensure_staff(caller)?;
+
+        let book = self
+            .book_repo
+            .find_by_id(req.book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .ok_or_else(|| anyhow!("Book not found"))?;
+        if !book.format.is_digital() {
+            return Err(anyhow!("Only digital editions can be granted"));
+        }
+
+        let existing = self
+            .entitlement_repo
+            .find_active(req.user_id, req.book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching entitlement: {}", e))?;
+        if existing.is_some() {
+            return Err(anyhow!("User already owns this book"));
+        }
+
+        let mut entitlement = EntitlementEntity::new(req.user_id, req.book_id, None, req.download_limit)?;
+        entitlement.id = self
+            .entitlement_repo
+            .save(&entitlement)
+            .await
+            .map_err(|e| anyhow!("Failed to save entitlement: {}", e))?;
+
+        Ok(EntitlementResponse::from(entitlement))
+    }
+
+    pub async fn revoke(
+        &self,
+        caller: &UserInfo,
+        entitlement_id: i32,
+        req: RevokeEntitlementRequest,
+    ) -> Result<EntitlementResponse> {
+        ensure_staff(caller)?;
+
+        let mut entitlement = self.find_entitlement(entitlement_id).await?;
+        entitlement.revoke(req.reason)?;
+        self.save_entitlement(&entitlement).await
+    }
+
+    pub async fn restore(&self, caller: &UserInfo, entitlement_id: i32) -> Result<EntitlementResponse> {
+        ensure_staff(caller)?;
+
+        let mut entitlement = self.find_entitlement(entitlement_id).await?;
+        entitlement.restore()?;
+        self.save_entitlement(&entitlement).await
+    }
+
+    pub async fn change_download_limit(
+        &self,
+        caller: &UserInfo,
+        entitlement_id: i32,
+        req: DownloadLimitRequest,
+    ) -> Result<EntitlementResponse> {
+        ensure_staff(caller)?;
+
+        let mut entitlement = self.find_entitlement(entitlement_id).await?;
+        entitlement.change_download_limit(req.download_limit, req.reset_used)?;
+        self.save_entitlement(&entitlement).await
+    }
+
+    pub async fn get_download_logs(&self, caller: &UserInfo, entitlement_id: i32) -> Result<Vec<DownloadLogResponse>> {
+        ensure_staff(caller)?;
+
+        let logs = self
+            .log_repo
+            .find_by_entitlement(entitlement_id, DOWNLOAD_LOG_LIMIT)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch download logs: {}", e))?;
+
+        Ok(logs.into_iter().map(DownloadLogResponse::from).collect())
+    }
+
+    // ---------- Customer ----------
+
+    /// Digital books the user owns, with their files
+    pub async fn get_library(&self, user_id: i32) -> Result<Vec<LibraryItemResponse>> {
+        let entitlements = self
+            .entitlement_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch entitlements: {}", e))?;
+
+        let book_ids: Vec<i32> = entitlements.iter().map(|e| e.book_id).collect();
+        let books = self
+            .book_repo
+            .find_by_ids(&book_ids)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching books: {}", e))?;
+        let assets = self
+            .asset_repo
+            .find_by_books(&book_ids)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch digital assets: {}", e))?;
+
+        Ok(entitlements
+            .into_iter()
+            .filter_map(|entitlement| {
+                let book = books.iter().find(|b| b.id == entitlement.book_id)?;
+                Some(LibraryItemResponse {
+                    entitlement_id: entitlement.id,
+                    book_id: book.id,
+                    title: book.title.as_str().to_string(),
+                    author: book.author.clone(),
+                    format: book.format.as_str().to_string(),
+                    downloads_remaining: entitlement.downloads_remaining(),
+                    revoked: entitlement.is_revoked(),
+                    files: assets
+                        .iter()
+                        .filter(|a| a.book_id == book.id)
+                        .cloned()
+                        .map(DigitalAssetResponse::from)
+                        .collect(),
+                    granted_at: entitlement.created_at,
+                })
+            })
+            .collect())
+    }
+
+    /// Issues a short-lived signed link to one file of an owned book
+    pub async fn create_download_link(
+        &self,
+        user_id: i32,
+        entitlement_id: i32,
+        req: DownloadLinkRequest,
+    ) -> Result<DownloadLinkResponse> {
+        let entitlement = self.find_entitlement(entitlement_id).await?;
+        if entitlement.user_id != user_id {
+            return Err(anyhow!("Entitlement not found"));
+        }
+        if entitlement.is_revoked() {
+            return Err(anyhow!("Access to this book has been revoked"));
+        }
+        if !entitlement.can_download() {
+            return Err(anyhow!("Download limit reached"));
+        }
+
+        let asset = self.find_asset(req.asset_id).await?;
+        if asset.book_id != entitlement.book_id {
+            return Err(anyhow!("File not found"));
+        }
+
+        let expires_at = Utc::now() + self.link_ttl;
+        let path = download_path(entitlement.id, asset.id);
+        let signature = self.signer.sign(&path, expires_at.timestamp())?;
+
+        Ok(DownloadLinkResponse {
+            url: format!(
+                "{}{}?expires={}&signature={}",
+                self.base_url,
+                path,
+                expires_at.timestamp(),
+                signature
+            ),
+            expires_at,
+            file_name: asset.file_name,
+            downloads_remaining: entitlement.downloads_remaining(),
+        })
+    }
+
+    /// Serves a signed link. The first request on a link uses up a download,
+    /// whatever range it asks for; resuming or seeking on the same link is
+    /// free, and the next download needs a new link.
+    pub async fn download(&self, req: DownloadRequest) -> Result<DownloadResponse> {
+        let path = download_path(req.entitlement_id, req.asset_id);
+        self.signer
+            .verify(&path, req.expires, &req.signature)
+            .map_err(|_| anyhow!("Invalid download link"))?;
+
+        let entitlement = self.find_entitlement(req.entitlement_id).await?;
+        let asset = self.find_asset(req.asset_id).await?;
+        if asset.book_id != entitlement.book_id {
+            return Err(anyhow!("Invalid download link"));
+        }
+
+        let mut log = DownloadLogEntity::new(
+            entitlement.id,
+            asset.id,
+            entitlement.user_id,
+            DownloadOutcome::Served,
+            req.ip_address,
+            req.user_agent,
+        );
+
+        let Some(expires_at) = DateTime::from_timestamp(req.expires, 0).filter(|at| *at >= Utc::now())
+        else {
+            return self.reject(log, DownloadOutcome::Expired, "Download link has expired").await;
+        };
+        if entitlement.is_revoked() {
+            return self
+                .reject(log, DownloadOutcome::Revoked, "Access to this book has been revoked")
+                .await;
+        }
+        // ไม่มี Range = โหลดใหม่ทั้งไฟล์: ลิมิตหมดแล้วไม่ต้องเสียเวลาประทับ watermark
+        if req.range.is_none() && !entitlement.can_download() {
+            return self
+                .reject(log, DownloadOutcome::LimitReached, "Download limit reached")
+                .await;
+        }
+
+        // EPUB ส่งสำเนาที่ประทับชื่อผู้ซื้อแล้ว ไฟล์อื่นส่งต้นฉบับ
+        let (storage_key, total) = match asset.file_format {
+            DigitalFileFormat::Epub => {
+                let watermark = self.watermarked_copy(&entitlement, &asset).await?;
+                (watermark.storage_key, watermark.size_bytes as u64)
+            }
+            DigitalFileFormat::Pdf | DigitalFileFormat::Mp3 => {
+                (asset.storage_key.clone(), asset.size_bytes as u64)
+            }
+        };
+        let content_disposition = format!("attachment; filename=\"{}\"", asset.file_name);
+        let content_type = asset.file_format.content_type().to_string();
+
+        let request = RangeRequest::from_header(req.range.as_deref(), total);
+        let Some(range) = request.resolve(total) else {
+            return Ok(DownloadResponse {
+                status: 416,
+                content_type,
+                content_disposition,
+                content_range: Some(format!("bytes */{}", total)),
+                body: futures::stream::empty().boxed(),
+            });
+        };
+
+        let link_use = self
+            .entitlement_repo
+            .consume_download(entitlement.id, asset.id, expires_at, range.start > 0)
+            .await
+            .map_err(|e| anyhow!("Failed to record download: {}", e))?;
+        match link_use {
+            LinkUse::Counted => log.counted = true,
+            LinkUse::AlreadyCounted => {}
+            LinkUse::LimitReached => {
+                return self
+                    .reject(log, DownloadOutcome::LimitReached, "Download limit reached")
+                    .await;
+            }
+        }
+
+        let partial = matches!(request, RangeRequest::Partial(_));
+        let body = self
+            .storage
+            .open(&storage_key, partial.then_some(range))
+            .await
+            .map_err(|e| anyhow!("Failed to read file: {}", e))?;
+
+        log.range_start = Some(range.start as i64);
+        log.range_end = Some(range.end as i64);
+        log.bytes_served = range.byte_count() as i64;
+        self.log_repo
+            .save(&log)
+            .await
+            .map_err(|e| anyhow!("Failed to log download: {}", e))?;
+
+        Ok(DownloadResponse {
+            status: if partial { 206 } else { 200 },
+            content_type,
+            content_disposition,
+            content_range: partial.then(|| range.content_range(total)),
+            body,
+        })
+    }
+
+    /// Staff: who a watermark code was sold to
+    pub async fn trace_watermark(&self, caller: &UserInfo, code: &str) -> Result<WatermarkTraceResponse> {
+        ensure_staff(caller)?;
+
+        let watermark = self
+            .watermark_repo
+            .find_by_code(code.trim())
+            .await
+            .map_err(|e| anyhow!("Database error while fetching watermark: {}", e))?
+            .ok_or_else(|| anyhow!("Watermark not found"))?;
+
+        Ok(WatermarkTraceResponse::from(watermark))
+    }
+
+    /// Staff: reads the watermark out of a leaked EPUB and traces it
+    pub async fn trace_file(&self, caller: &UserInfo, content: Vec<u8>) -> Result<WatermarkTraceResponse> {
+        ensure_staff(caller)?;
+
+        let code = tokio::task::spawn_blocking(move || epub_watermark::read_code(Cursor::new(content)))
+            .await
+            .map_err(|e| anyhow!("Watermark task failed: {}", e))?
+            .map_err(|e| anyhow!("Failed to read EPUB: {}", e))?
+            .ok_or_else(|| anyhow!("File carries no watermark"))?;
+
+        self.trace_watermark(caller, &code).await
+    }
+
+    /// The buyer's stamped copy of an EPUB, made and stored on first use
+    async fn watermarked_copy(
+        &self,
+        entitlement: &EntitlementEntity,
+        asset: &DigitalAssetEntity,
+    ) -> Result<DownloadWatermarkEntity> {
+        let existing = self
+            .watermark_repo
+            .find_for_download(entitlement.id, asset.id, &asset.sha256)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching watermark: {}", e))?;
+        if let Some(watermark) = existing {
+            return Ok(watermark);
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(entitlement.user_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching user: {}", e))?
+            .ok_or_else(|| anyhow!("User not found"))?;
+
+        let mut watermark = DownloadWatermarkEntity::new(
+            entitlement.id,
+            asset.id,
+            asset.sha256.clone(),
+            user.id,
+            entitlement.order_id,
+            user.full_name(),
+        );
+        let stamp = WatermarkStamp {
+            code: watermark.code.as_str().to_string(),
+            licensee: watermark.licensee.clone(),
+            order_reference: watermark.order_reference(),
+        };
+
+        // ต้นฉบับและสำเนาอยู่ใน temp file ทั้งคู่ EPUB ใหญ่ๆ จะไม่ค้างอยู่ใน memory
+        let source = self
+            .download_to_temp_file(&asset.storage_key)
+            .await
+            .map_err(|e| anyhow!("Failed to read file: {}", e))?;
+        let stamped = tokio::task::spawn_blocking(move || -> Result<std::fs::File> {
+            let output = BufWriter::new(tempfile::tempfile()?);
+            let output = epub_watermark::stamp(BufReader::new(source), output, &stamp)?;
+            output.into_inner().map_err(|e| anyhow!("{}", e.error()))
+        })
+        .await
+        .map_err(|e| anyhow!("Watermark task failed: {}", e))?
+        .map_err(|e| anyhow!("Failed to watermark EPUB: {}", e))?;
+
+        watermark.size_bytes = stamped
+            .metadata()
+            .map_err(|e| anyhow!("Failed to read watermarked file: {}", e))?
+            .len() as i64;
+        self.storage
+            .put_file(
+                &watermark.storage_key,
+                tokio::fs::File::from_std(stamped),
+                asset.file_format.content_type(),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to store watermarked file: {}", e))?;
+
+        let saved = self
+            .watermark_repo
+            .save(&watermark)
+            .await
+            .map_err(|e| anyhow!("Failed to save watermark: {}", e))?;
+
+        // แพ้ race กับ request อื่น: ลบไฟล์ของเราทิ้งแล้วใช้ของเขา
+        if saved.code != watermark.code
+            && let Err(e) = self.storage.delete(&watermark.storage_key).await
+        {
+            tracing::warn!(key = %watermark.storage_key, "Failed to delete duplicate copy: {}", e);
+        }
+
+        Ok(saved)
+    }
+
+    /// Copies a stored file chunk by chunk into an anonymous temp file
+    async fn download_to_temp_file(&self, key: &str) -> Result<std::fs::File> {
+        let mut stream = self.storage.open(key, None).await?;
+        let mut file = tokio::fs::File::from_std(tempfile::tempfile()?);
+        while let Some(chunk) = stream.next().await {
+            file.write_all(&chunk?).await?;
+        }
+        file.flush().await?;
+        Ok(file.into_std().await)
+    }
+
+    async fn reject(
+        &self,
+        mut log: DownloadLogEntity,
+        outcome: DownloadOutcome,
+        message: &str,
+    ) -> Result<DownloadResponse> {
+        log.outcome = outcome;
+        self.log_repo
+            .save(&log)
+            .await
+            .map_err(|e| anyhow!("Failed to log download: {}", e))?;
+        Err(anyhow!("{}", message))
+    }
+
+    async fn find_entitlement(&self, id: i32) -> Result<EntitlementEntity> {
+        self.entitlement_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching entitlement: {}", e))?
+            .ok_or_else(|| anyhow!("Entitlement not found"))
+    }
+
+    async fn find_asset(&self, id: i32) -> Result<DigitalAssetEntity> {
+        self.asset_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching digital asset: {}", e))?
+            .ok_or_else(|| anyhow!("File not found"))
+    }
+
+    async fn save_entitlement(&self, entitlement: &EntitlementEntity) -> Result<EntitlementResponse> {
+        let entitlement = self
+            .entitlement_repo
+            .update(entitlement)
+            .await
+            .map_err(|e| anyhow!("Failed to update entitlement: {}", e))?;
+
+        Ok(EntitlementResponse::from(entitlement))
+    }
+}
+
+/// Path a download link points at (and that its signature covers)
+fn download_path(entitlement_id: i32, asset_id: i32) -> String {
+    format!("/downloads/{}/files/{}", entitlement_id, asset_id)
+}
+
+/// Writes an upload to a temp file, hashing it on the way. Fails as soon as
+/// it grows past `MAX_ASSET_BYTES`. Returns the rewound file, its size and
+/// hex SHA-256.
+async fn spool_upload(mut content: ByteStream) -> Result<(std::fs::File, u64, String)> {
+    let mut file = tokio::fs::File::from_std(tempfile::tempfile()?);
+    let mut hasher = Sha256::new();
+    let mut size = 0u64;
+    while let Some(chunk) = content.next().await {
+        let chunk = chunk.map_err(|e| anyhow!("Failed to read upload: {}", e))?;
+        size += chunk.len() as u64;
+        if size > MAX_ASSET_BYTES {
+            return Err(anyhow!("File is too large"));
+        }
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+
+    let mut file = file.into_std().await;
+    file.rewind()?;
+    Ok((file, size, hex::encode(hasher.finalize())))
+}
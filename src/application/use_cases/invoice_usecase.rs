@@ -0,0 +1,346 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use sha2::{Digest, Sha256};
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        invoice_dto::{
+            InvoiceDocumentResponse, InvoiceResponse, IssueCreditNoteRequest,
+            RequestTaxInvoiceRequest,
+        },
+    },
+};
+use crate::domain::{
+    entities::{
+        invoice::{InvoiceEntity, InvoiceKind, InvoiceLine, InvoiceParty},
+        order::OrderEntity,
+        return_request::ReturnRequestEntity,
+    },
+    repositories::{
+        invoice_repository::InvoiceRepository, order_repository::OrderRepository,
+        return_repository::ReturnRepository,
+    },
+    services::tax_engine,
+    value_objects::{
+        order_status::OrderStatus,
+        return_status::ReturnStatus,
+        tax_id::{BranchCode, TaxId},
+    },
+};
+use crate::infrastructure::invoice_pdf::InvoicePdfRenderer;
+
+/// InvoiceUseCase — full tax invoices (ใบกำกับภาษีเต็มรูป) on request and
+/// credit notes (ใบลดหนี้) for refunds. Documents are rendered once, when
+/// issued, and served from storage afterwards.
+pub struct InvoiceUseCase {
+    invoice_repo: Arc<dyn InvoiceRepository>,
+    order_repo: Arc<dyn OrderRepository>,
+    return_repo: Arc<dyn ReturnRepository>,
+    renderer: Arc<InvoicePdfRenderer>,
+    /// Our details as registered for VAT
+    seller: InvoiceParty,
+}
+
+impl InvoiceUseCase {
+    pub fn new(
+        invoice_repo: Arc<dyn InvoiceRepository>,
+        order_repo: Arc<dyn OrderRepository>,
+        return_repo: Arc<dyn ReturnRepository>,
+        renderer: Arc<InvoicePdfRenderer>,
+        seller: InvoiceParty,
+    ) -> Self {
+        Self {
+            invoice_repo,
+            order_repo,
+            return_repo,
+            renderer,
+            seller,
+        }
+    }
+
+    /// Customer asks for a full tax invoice for their paid order
+    pub async fn request_tax_invoice(
+        &self,
+        caller: &UserInfo,
+        order_id: i32,
+        req: RequestTaxInvoiceRequest,
+    ) -> Result<InvoiceResponse> {
+        let order = self.find_order(order_id).await?;
+        if order.user_id != caller.id {
+            ensure_staff(caller).map_err(|_| anyhow!("Order not found"))?;
+        }
+        if self.tax_invoice_for(order.id).await?.is_some() {
+            return Err(anyhow!("A tax invoice was already issued for this order"));
+        }
+
+        let tax_id = TaxId::new(req.tax_id).map_err(|e| anyhow!("{}", e))?;
+        let branch = match req.branch {
+            Some(branch) => BranchCode::new(branch).map_err(|e| anyhow!("{}", e))?,
+            None => BranchCode::head_office(),
+        };
+        let address = req
+            .address
+            .or_else(|| {
+                order
+                    .billing_address
+                    .as_ref()
+                    .or(order.shipping_address.as_ref())
+                    .map(|a| a.single_line())
+            })
+            .ok_or_else(|| anyhow!("Address is required for a tax invoice"))?;
+        let buyer = InvoiceParty::new(req.name, tax_id, branch, address)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let invoice = InvoiceEntity::tax_invoice(&order, self.seller.clone(), buyer, Some(caller.id))
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.issue(invoice).await
+    }
+
+    /// Staff: credit a refunded return, or what is left of a cancelled or
+    /// refunded order
+    pub async fn issue_credit_note(
+        &self,
+        caller: &UserInfo,
+        invoice_id: i32,
+        req: IssueCreditNoteRequest,
+    ) -> Result<InvoiceResponse> {
+        ensure_staff(caller)?;
+
+        let invoice = self.find_invoice(invoice_id).await?;
+        if invoice.kind != InvoiceKind::TaxInvoice {
+            return Err(anyhow!("Credit notes can only reduce a tax invoice"));
+        }
+        let order = self.find_order(invoice.order_id).await?;
+
+        match req.return_id {
+            Some(return_id) => {
+                let request = self
+                    .return_repo
+                    .find_by_id(return_id)
+                    .await
+                    .map_err(|e| anyhow!("Database error while fetching return: {}", e))?
+                    .ok_or_else(|| anyhow!("Return request not found"))?;
+                if request.order_id != order.id {
+                    return Err(anyhow!("Return request belongs to another order"));
+                }
+                self.credit_for_return(&invoice, &order, &request, req.reason, caller.id)
+                    .await
+            }
+            None => {
+                if !matches!(order.status, OrderStatus::Cancelled | OrderStatus::Refunded) {
+                    return Err(anyhow!("Only cancelled or refunded orders can be credited in full"));
+                }
+                let (credited_total, credited_tax) = credited(&self.credit_notes(&invoice).await?);
+                let line = InvoiceLine {
+                    description: format!("ยกเลิก/คืนเงินคำสั่งซื้อ #{} (Order {})", order.id, order.status),
+                    quantity: 1,
+                    unit_price: invoice.total - credited_total,
+                    amount: invoice.total - credited_total,
+                };
+                let note = InvoiceEntity::credit_note(
+                    &invoice,
+                    &order,
+                    credited_total,
+                    credited_tax,
+                    vec![line],
+                    invoice.tax_amount - credited_tax,
+                    None,
+                    req.reason,
+                    Some(caller.id),
+                )
+                .map_err(|e| anyhow!("{}", e))?;
+                self.issue(note).await
+            }
+        }
+    }
+
+    /// Credits a refunded return when its order has a tax invoice. Called by
+    /// ReturnUseCase after the money went back; `None` without an invoice.
+    pub async fn credit_return(
+        &self,
+        request: &ReturnRequestEntity,
+        actor_id: i32,
+    ) -> Result<Option<InvoiceResponse>> {
+        let Some(invoice) = self.tax_invoice_for(request.order_id).await? else {
+            return Ok(None);
+        };
+        let order = self.find_order(request.order_id).await?;
+
+        let reason = format!("รับคืนสินค้า (Returned goods) — {}", request.reason);
+        self.credit_for_return(&invoice, &order, request, reason, actor_id)
+            .await
+            .map(Some)
+    }
+
+    pub async fn get_user_invoices(&self, caller: &UserInfo) -> Result<Vec<InvoiceResponse>> {
+        let invoices = self
+            .invoice_repo
+            .find_by_user(caller.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch invoices: {}", e))?;
+
+        Ok(invoices.into_iter().map(InvoiceResponse::from).collect())
+    }
+
+    /// The order's tax invoice and the credit notes against it
+    pub async fn get_order_invoices(&self, caller: &UserInfo, order_id: i32) -> Result<Vec<InvoiceResponse>> {
+        let order = self.find_order(order_id).await?;
+        if order.user_id != caller.id {
+            ensure_staff(caller).map_err(|_| anyhow!("Order not found"))?;
+        }
+
+        let mut invoices = Vec::new();
+        for kind in [InvoiceKind::TaxInvoice, InvoiceKind::CreditNote] {
+            invoices.extend(
+                self.invoice_repo
+                    .find_by_order(order.id, kind)
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch invoices: {}", e))?,
+            );
+        }
+
+        Ok(invoices.into_iter().map(InvoiceResponse::from).collect())
+    }
+
+    pub async fn get_invoice(&self, caller: &UserInfo, id: i32) -> Result<InvoiceResponse> {
+        let invoice = self.find_visible_invoice(caller, id).await?;
+        Ok(InvoiceResponse::from(invoice))
+    }
+
+    /// The PDF exactly as issued; refuses to serve a copy that no longer
+    /// matches its checksum
+    pub async fn download_invoice(&self, caller: &UserInfo, id: i32) -> Result<InvoiceDocumentResponse> {
+        let invoice = self.find_visible_invoice(caller, id).await?;
+
+        let body = self
+            .invoice_repo
+            .find_document(invoice.id)
+            .await
+            .map_err(|e| anyhow!("Failed to read invoice: {}", e))?
+            .ok_or_else(|| anyhow!("Invoice not found"))?;
+        if hex::encode(Sha256::digest(&body)) != invoice.sha256 {
+            return Err(anyhow!("Stored invoice {} does not match its checksum", invoice.number));
+        }
+
+        Ok(InvoiceDocumentResponse {
+            content_type: "application/pdf".to_string(),
+            content_disposition: format!("attachment; filename=\"{}\"", invoice.file_name()),
+            body,
+        })
+    }
+
+    async fn credit_for_return(
+        &self,
+        invoice: &InvoiceEntity,
+        order: &OrderEntity,
+        request: &ReturnRequestEntity,
+        reason: String,
+        actor_id: i32,
+    ) -> Result<InvoiceResponse> {
+        if request.status != ReturnStatus::Refunded {
+            return Err(anyhow!("Only refunded returns can be credited"));
+        }
+        let item = order
+            .items
+            .iter()
+            .find(|i| i.id == request.order_item_id)
+            .ok_or_else(|| anyhow!("Order item not found"))?;
+
+        let notes = self.credit_notes(invoice).await?;
+        if notes.iter().any(|n| n.return_id == Some(request.id)) {
+            return Err(anyhow!("Return {} was already credited", request.id));
+        }
+        let (credited_total, credited_tax) = credited(&notes);
+        let quantity = request.quantity.value();
+        let amount = request.refund_amount;
+        // VAT ของส่วนที่คืนคิดจากอัตราของ line เดิม ไม่เกิน VAT ที่เหลือในใบกำกับ
+        let tax = tax_engine::tax_from_gross(amount, item.tax_rate_bps)
+            .min(invoice.tax_amount - credited_tax);
+
+        let line = InvoiceLine {
+            description: format!("รับคืน: {} (ISBN {})", item.title, item.isbn),
+            quantity,
+            unit_price: amount / quantity as i64,
+            amount,
+        };
+        let note = InvoiceEntity::credit_note(
+            invoice,
+            order,
+            credited_total,
+            credited_tax,
+            vec![line],
+            tax,
+            Some(request.id),
+            reason,
+            Some(actor_id),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        self.issue(note).await
+    }
+
+    /// Numbers, renders and stores the document
+    async fn issue(&self, invoice: InvoiceEntity) -> Result<InvoiceResponse> {
+        let renderer = self.renderer.clone();
+        let render = move |invoice: &InvoiceEntity| renderer.render(invoice);
+
+        let issued = self
+            .invoice_repo
+            .issue(&invoice, &render)
+            .await
+            .map_err(|e| anyhow!("Failed to issue {}: {}", invoice.kind.as_str(), e))?;
+
+        Ok(InvoiceResponse::from(issued))
+    }
+
+    async fn credit_notes(&self, invoice: &InvoiceEntity) -> Result<Vec<InvoiceEntity>> {
+        self.invoice_repo
+            .find_credit_notes(invoice.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch credit notes: {}", e))
+    }
+
+    async fn tax_invoice_for(&self, order_id: i32) -> Result<Option<InvoiceEntity>> {
+        let invoices = self
+            .invoice_repo
+            .find_by_order(order_id, InvoiceKind::TaxInvoice)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch invoices: {}", e))?;
+
+        Ok(invoices.into_iter().next())
+    }
+
+    async fn find_visible_invoice(&self, caller: &UserInfo, id: i32) -> Result<InvoiceEntity> {
+        let invoice = self.find_invoice(id).await?;
+        if invoice.user_id != caller.id {
+            ensure_staff(caller).map_err(|_| anyhow!("Invoice not found"))?;
+        }
+        Ok(invoice)
+    }
+
+    async fn find_invoice(&self, id: i32) -> Result<InvoiceEntity> {
+        self.invoice_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching invoice: {}", e))?
+            .ok_or_else(|| anyhow!("Invoice not found"))
+    }
+
+    async fn find_order(&self, id: i32) -> Result<OrderEntity> {
+        self.order_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching order: {}", e))?
+            .ok_or_else(|| anyhow!("Order not found"))
+    }
+}
+
+/// (total, VAT) already taken off a tax invoice by its credit notes
+fn credited(notes: &[InvoiceEntity]) -> (i64, i64) {
+    notes
+        .iter()
+        .fold((0, 0), |(total, tax), n| (total + n.total, tax + n.tax_amount))
+}
@@ -0,0 +1,314 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Months, Utc};
+use tracing::warn;
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        loyalty_dto::{
+            CategoryMultiplierResponse, CreateLoyaltyCampaignRequest, LoyaltyCampaignResponse,
+            LoyaltyStatementResponse, PointsEntryResponse, PointsExpiryResponse,
+            SetCategoryMultiplierRequest, StatementQuery, TierSyncResponse,
+        },
+    },
+};
+use crate::domain::{
+    entities::{
+        loyalty_campaign::{LoyaltyCampaignEntity, LoyaltyCategoryMultiplier},
+        loyalty_entry::{points_value, LoyaltyEntryEntity, PointsReason},
+    },
+    repositories::{
+        loyalty_repository::LoyaltyRepository, role_repository::RoleRepository,
+        user_repository::UserRepository,
+    },
+    value_objects::loyalty_tier::{LoyaltyTier, TIER_WINDOW_MONTHS},
+};
+
+/// Statements warn about points lapsing within this many days
+const EXPIRY_NOTICE_DAYS: i64 = 30;
+
+/// LoyaltyUseCase — earn rules, member statements, and the jobs that expire
+/// points and keep tier roles in line with rolling spend.
+/// Points are earned when an order is paid (see the order repository) and
+/// spent as a payment method (see PaymentUseCase).
+pub struct LoyaltyUseCase {
+    loyalty_repo: Arc<dyn LoyaltyRepository>,
+    role_repo: Arc<dyn RoleRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl LoyaltyUseCase {
+    pub fn new(
+        loyalty_repo: Arc<dyn LoyaltyRepository>,
+        role_repo: Arc<dyn RoleRepository>,
+        user_repo: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            loyalty_repo,
+            role_repo,
+            user_repo,
+        }
+    }
+
+    /// The member's tier, balance and ledger for a period
+    pub async fn get_statement(&self, user_id: i32, query: StatementQuery) -> Result<LoyaltyStatementResponse> {
+        let now = Utc::now();
+        let to = query.to.unwrap_or(now);
+        let from = match query.from {
+            Some(from) => from,
+            None => window_start(to)?,
+        };
+        if from >= to {
+            return Err(anyhow!("Statement period must end after it starts"));
+        }
+
+        let spend = self
+            .loyalty_repo
+            .spend_since(user_id, window_start(now)?)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch spend: {}", e))?;
+        let tier = LoyaltyTier::for_spend(spend);
+        let next = tier.next();
+
+        let balance = self
+            .loyalty_repo
+            .find_balance(user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch points balance: {}", e))?;
+        let expiring_by = now + Duration::days(EXPIRY_NOTICE_DAYS);
+        let expiring_points = self
+            .loyalty_repo
+            .points_due_to_expire(user_id, expiring_by)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch expiring points: {}", e))?;
+
+        let opening_balance = self
+            .loyalty_repo
+            .balance_before(user_id, from)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch opening balance: {}", e))?;
+        let entries = self
+            .loyalty_repo
+            .find_entries(user_id, from, to)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch points ledger: {}", e))?;
+        let closing_balance = opening_balance + entries.iter().map(|e| e.points).sum::<i64>();
+
+        Ok(LoyaltyStatementResponse {
+            user_id,
+            tier: tier.as_str().to_string(),
+            rolling_spend: spend,
+            next_tier: next.map(|(t, _)| t.as_str().to_string()),
+            spend_to_next_tier: next.map(|(_, min)| min - spend),
+            balance,
+            balance_value: points_value(balance),
+            expiring_points,
+            expiring_by,
+            from,
+            to,
+            opening_balance,
+            closing_balance,
+            entries: entries.into_iter().map(PointsEntryResponse::from).collect(),
+        })
+    }
+
+    pub async fn get_category_multipliers(&self, caller: &UserInfo) -> Result<Vec<CategoryMultiplierResponse>> {
+        ensure_staff(caller)?;
+
+        let multipliers = self
+            .loyalty_repo
+            .find_category_multipliers()
+            .await
+            .map_err(|e| anyhow!("Failed to fetch multipliers: {}", e))?;
+
+        Ok(multipliers.into_iter().map(CategoryMultiplierResponse::from).collect())
+    }
+
+    /// Staff: set the standing earn rate of a category (100% removes it)
+    pub async fn set_category_multiplier(
+        &self,
+        caller: &UserInfo,
+        req: SetCategoryMultiplierRequest,
+    ) -> Result<Vec<CategoryMultiplierResponse>> {
+        ensure_staff(caller)?;
+
+        let multiplier = LoyaltyCategoryMultiplier::new(req.category, req.multiplier_pct)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        if multiplier.multiplier_pct == 100 {
+            self.loyalty_repo
+                .delete_category_multiplier(&multiplier.category)
+                .await
+                .map_err(|e| anyhow!("Failed to remove multiplier: {}", e))?;
+        } else {
+            self.loyalty_repo
+                .save_category_multiplier(&multiplier)
+                .await
+                .map_err(|e| anyhow!("Failed to save multiplier: {}", e))?;
+        }
+
+        self.get_category_multipliers(caller).await
+    }
+
+    pub async fn create_campaign(
+        &self,
+        caller: &UserInfo,
+        req: CreateLoyaltyCampaignRequest,
+    ) -> Result<LoyaltyCampaignResponse> {
+        ensure_staff(caller)?;
+
+        let mut campaign = LoyaltyCampaignEntity::new(
+            req.name,
+            req.category,
+            req.multiplier_pct,
+            req.starts_at,
+            req.ends_at,
+            caller.id,
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        campaign.id = self
+            .loyalty_repo
+            .save_campaign(&campaign)
+            .await
+            .map_err(|e| anyhow!("Failed to save campaign: {}", e))?;
+
+        Ok(LoyaltyCampaignResponse::from(campaign))
+    }
+
+    pub async fn get_campaigns(&self, caller: &UserInfo) -> Result<Vec<LoyaltyCampaignResponse>> {
+        ensure_staff(caller)?;
+
+        let campaigns = self
+            .loyalty_repo
+            .find_campaigns()
+            .await
+            .map_err(|e| anyhow!("Failed to fetch campaigns: {}", e))?;
+
+        Ok(campaigns.into_iter().map(LoyaltyCampaignResponse::from).collect())
+    }
+
+    /// Staff: stop a campaign early
+    pub async fn end_campaign(&self, caller: &UserInfo, id: i32) -> Result<LoyaltyCampaignResponse> {
+        ensure_staff(caller)?;
+
+        let mut campaign = self
+            .loyalty_repo
+            .find_campaign(id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching campaign: {}", e))?
+            .ok_or_else(|| anyhow!("Campaign not found"))?;
+
+        campaign.end();
+        let updated = self
+            .loyalty_repo
+            .update_campaign(&campaign)
+            .await
+            .map_err(|e| anyhow!("Failed to update campaign: {}", e))?;
+
+        Ok(LoyaltyCampaignResponse::from(updated))
+    }
+
+    /// Writes off lapsed points. Meant for a background worker.
+    pub async fn expire_points(&self, now: DateTime<Utc>, limit: i64) -> Result<PointsExpiryResponse> {
+        let due = self
+            .loyalty_repo
+            .find_expiring(now, limit)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch expiring points: {}", e))?;
+
+        let mut summary = PointsExpiryResponse {
+            members: 0,
+            points_expired: 0,
+        };
+
+        for (user_id, points) in due {
+            let entry = LoyaltyEntryEntity::new(user_id, -points, PointsReason::Expiry, None, None, None)
+                .map_err(|e| anyhow!("{}", e))?;
+
+            // แต้มอาจถูกใช้ไปพร้อมกันระหว่างรอบ ข้ามไปรอบหน้า
+            if let Err(e) = self.loyalty_repo.record(&entry).await {
+                warn!("Failed to expire points of user {}: {}", user_id, e);
+                continue;
+            }
+            summary.members += 1;
+            summary.points_expired += points;
+        }
+
+        Ok(summary)
+    }
+
+    /// Gives every member the tier role their rolling 12-month spend earns
+    /// and takes away the others. Meant for a background worker.
+    pub async fn sync_tiers(&self, now: DateTime<Utc>) -> Result<TierSyncResponse> {
+        let silver = self.tier_role_id(LoyaltyTier::Silver).await?;
+        let gold = self.tier_role_id(LoyaltyTier::Gold).await?;
+        let role_id = |tier: LoyaltyTier| match tier {
+            LoyaltyTier::Silver => Some(silver),
+            LoyaltyTier::Gold => Some(gold),
+            LoyaltyTier::Member => None,
+        };
+
+        let members = self
+            .loyalty_repo
+            .find_member_spend(window_start(now)?)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch member spend: {}", e))?;
+
+        let mut summary = TierSyncResponse {
+            members_checked: members.len(),
+            members_changed: 0,
+        };
+
+        for member in members {
+            let tier = LoyaltyTier::for_spend(member.spend);
+            let stale: Vec<i32> = member
+                .current_tiers
+                .iter()
+                .filter(|t| **t != tier)
+                .filter_map(|t| role_id(*t))
+                .collect();
+            let missing = role_id(tier).filter(|_| !member.current_tiers.contains(&tier));
+
+            if stale.is_empty() && missing.is_none() {
+                continue;
+            }
+            if !stale.is_empty() {
+                self.user_repo
+                    .remove_roles(member.user_id, &stale)
+                    .await
+                    .map_err(|e| anyhow!("Failed to remove tier role: {}", e))?;
+            }
+            if let Some(role) = missing {
+                self.user_repo
+                    .assign_roles(member.user_id, &[role])
+                    .await
+                    .map_err(|e| anyhow!("Failed to assign tier role: {}", e))?;
+            }
+            summary.members_changed += 1;
+        }
+
+        Ok(summary)
+    }
+
+    async fn tier_role_id(&self, tier: LoyaltyTier) -> Result<i32> {
+        let name = tier
+            .role_name()
+            .ok_or_else(|| anyhow!("Tier {} has no role", tier.as_str()))?;
+
+        self.role_repo
+            .find_by_name(name)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching role: {}", e))?
+            .map(|r| r.id)
+            .ok_or_else(|| anyhow!("Role {} not found", name))
+    }
+}
+
+/// Start of the rolling tier window ending at `at`
+fn window_start(at: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    at.checked_sub_months(Months::new(TIER_WINDOW_MONTHS))
+        .ok_or_else(|| anyhow!("Invalid date"))
+}
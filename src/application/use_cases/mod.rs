@@ -1,3 +1,26 @@
 pub mod auth_usecase;
+pub mod book_cover_usecase;
+pub mod cart_usecase;
+pub mod catalog_import_usecase;
+pub mod catalog_usecase;
+pub mod digital_delivery_usecase;
+pub mod invoice_usecase;
+pub mod notification_usecase;
+pub mod loyalty_usecase;
+pub mod marc_export_usecase;
+pub mod order_usecase;
+pub mod payment_usecase;
+pub mod preorder_usecase;
+pub mod pricing_usecase;
+pub mod promotion_usecase;
+pub mod report_usecase;
+pub mod return_usecase;
+pub mod review_usecase;
 pub mod role_usecase;
+pub mod search_index_usecase;
+pub mod search_usecase;
+pub mod shipping_usecase;
+pub mod spreadsheet_usecase;
+pub mod stored_value_usecase;
 pub mod user_usecase;
+pub mod wishlist_usecase;
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use tracing::warn;
+
+use crate::application::dtos::wishlist_dto::NotificationDispatchResponse;
+use crate::domain::{
+    entities::notification_job::{NotificationJobEntity, NotificationKind},
+    repositories::{
+        notification_job_repository::NotificationJobRepository,
+        user_repository::UserRepository,
+    },
+    value_objects::notification_status::NotificationStatus,
+};
+use crate::infrastructure::notifier::{Notification, Notifier};
+
+/// NotificationUseCase — delivers queued notification jobs through a Notifier.
+/// Meant to be run periodically by a background worker.
+pub struct NotificationUseCase {
+    job_repo: Arc<dyn NotificationJobRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    notifier: Arc<dyn Notifier>,
+}
+
+impl NotificationUseCase {
+    pub fn new(
+        job_repo: Arc<dyn NotificationJobRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        notifier: Arc<dyn Notifier>,
+    ) -> Self {
+        Self {
+            job_repo,
+            user_repo,
+            notifier,
+        }
+    }
+
+    /// Claims up to `limit` due jobs and sends them. Failed jobs are retried
+    /// later with backoff until they run out of attempts.
+    pub async fn dispatch_due(&self, limit: i64) -> Result<NotificationDispatchResponse> {
+        let jobs = self
+            .job_repo
+            .claim_due(limit)
+            .await
+            .map_err(|e| anyhow!("Failed to claim notification jobs: {}", e))?;
+
+        let mut summary = NotificationDispatchResponse { sent: 0, failed: 0 };
+        for mut job in jobs {
+            match self.deliver(&job).await {
+                Ok(()) => {
+                    job.mark_sent();
+                    summary.sent += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        job_id = job.id,
+                        attempts = job.attempts,
+                        channel = self.notifier.channel(),
+                        "Notification failed: {}",
+                        e
+                    );
+                    job.mark_failed(e.to_string());
+                    if job.status == NotificationStatus::Failed {
+                        summary.failed += 1;
+                    }
+                }
+            }
+
+            self.job_repo
+                .update(&job)
+                .await
+                .map_err(|e| anyhow!("Failed to update notification job: {}", e))?;
+        }
+
+        Ok(summary)
+    }
+
+    async fn deliver(&self, job: &NotificationJobEntity) -> Result<()> {
+        let user = self
+            .user_repo
+            .find_by_id(job.user_id)
+            .await?
+            .ok_or_else(|| anyhow!("User {} not found", job.user_id))?;
+
+        if !user.is_active {
+            return Err(anyhow!("User {} is inactive", job.user_id));
+        }
+
+        let (subject, body) = render(job)?;
+        let notification = Notification {
+            to_email: user.email.as_str().to_string(),
+            to_name: user.full_name(),
+            subject,
+            body,
+        };
+
+        self.notifier.send(&notification).await
+    }
+}
+
+fn render(job: &NotificationJobEntity) -> Result<(String, String)> {
+    match job.kind {
+        NotificationKind::BackInStock => {
+            let title = job
+                .payload
+                .get("title")
+                .and_then(|t| t.as_str())
+                .ok_or_else(|| anyhow!("Notification payload is missing the book title"))?;
+
+            Ok((
+                format!("Back in stock: {}", title),
+                format!(
+                    "Good news! \"{}\" is available again. Stock is limited, so order soon.",
+                    title
+                ),
+            ))
+        }
+    }
+}
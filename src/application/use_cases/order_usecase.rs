@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        order_dto::{CancelOrderRequest, CheckoutRequest, OrderResponse, ShipOrderRequest},
+    },
+    use_cases::{
+        payment_usecase::PaymentUseCase,
+        pricing_usecase::PricingUseCase,
+        promotion_usecase::PromotionUseCase,
+        shipping_usecase::ShippingUseCase,
+    },
+};
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        order::{OrderEntity, OrderItemEntity, OrderPromotion, OrderShipping},
+    },
+    repositories::{
+        address_repository::AddressRepository,
+        book_repository::BookRepository,
+        cart_repository::CartRepository,
+        stock_subscription_repository::StockSubscriptionRepository,
+        order_repository::OrderRepository,
+    },
+    services::{
+        shipping_calculator::{Parcel, ShippingDestination},
+        tax_engine::{self, TaxMode},
+    },
+    value_objects::{
+        order_status::OrderStatus,
+        postal_address::PostalAddress,
+    },
+};
+
+/// OrderUseCase — checkout and order lifecycle (state machine อยู่ใน OrderEntity)
+pub struct OrderUseCase {
+    order_repo: Arc<dyn OrderRepository>,
+    cart_repo: Arc<dyn CartRepository>,
+    book_repo: Arc<dyn BookRepository>,
+    subscription_repo: Arc<dyn StockSubscriptionRepository>,
+    address_repo: Arc<dyn AddressRepository>,
+    promotions: Arc<PromotionUseCase>,
+    pricing: Arc<PricingUseCase>,
+    shipping: Arc<ShippingUseCase>,
+    payments: Arc<PaymentUseCase>,
+}
+
+impl OrderUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        order_repo: Arc<dyn OrderRepository>,
+        cart_repo: Arc<dyn CartRepository>,
+        book_repo: Arc<dyn BookRepository>,
+            subscription_repo: Arc<dyn StockSubscriptionRepository>,
+        address_repo: Arc<dyn AddressRepository>,
+        promotions: Arc<PromotionUseCase>,
+        pricing: Arc<PricingUseCase>,
+        shipping: Arc<ShippingUseCase>,
+        payments: Arc<PaymentUseCase>,
+    ) -> Self {
+        Self {
+            order_repo,
+            cart_repo,
+            book_repo,
+            subscription_repo,
+            address_repo,
+            promotions,
+            pricing,
+            shipping,
+            payments,
+        }
+    }
+
+    /// Turn the user's cart into an order awaiting payment
+    pub async fn checkout(&self, user_id: i32, req: CheckoutRequest) -> Result<OrderResponse> {
+        let mut cart = self
+            .cart_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching cart: {}", e))?
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| anyhow!("Cart is empty"))?;
+
+        let book_ids: Vec<i32> = cart.items.iter().map(|i| i.book_id).collect();
+        let books: HashMap<i32, BookEntity> = self
+            .book_repo
+            .find_by_ids(&book_ids)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch books: {}", e))?
+            .into_iter()
+            .map(|b| (b.id, b))
+            .collect();
+
+        // ราคาของร้านที่เปิดตะกร้า
+        let store = self.pricing.store_by_id(cart.store_id).await?;
+        let prices = self
+            .pricing
+            .shelf_prices(&store, &books.values().collect::<Vec<_>>())
+            .await?;
+
+        // 1. ราคาเปลี่ยนตั้งแต่ลูกค้าดูตะกร้าครั้งล่าสุด -> อัปเดต snapshot แล้วให้ลูกค้ายืนยันใหม่
+        let repriced = prices
+            .iter()
+            .filter(|(book_id, price)| cart.reprice_item(**book_id, price.amount()).is_some())
+            .count();
+        if repriced > 0 {
+            self.cart_repo
+                .update(&cart)
+                .await
+                .map_err(|e| anyhow!("Failed to update cart prices: {}", e))?;
+            return Err(anyhow!("Prices in your cart have changed, please review your cart"));
+        }
+
+        // 2. Freeze lines (price + tax) from the catalog
+        let rates = self.pricing.tax_rates(&store).await?;
+        let now = Utc::now();
+        let mode = if store.prices_include_tax {
+            TaxMode::Inclusive
+        } else {
+            TaxMode::Exclusive
+        };
+
+        let today = now.date_naive();
+        let mut items = Vec::with_capacity(cart.items.len());
+        let mut release_dates = Vec::new();
+        for line in &cart.items {
+            let book = books
+                .get(&line.book_id)
+                .ok_or_else(|| anyhow!("Book {} no longer exists", line.book_id))?;
+
+            if book.is_preorder(today) {
+                release_dates.extend(book.release_date);
+            } else if !book.is_released(today) {
+                return Err(anyhow!("'{}' is not released yet", book.title));
+            }
+            if !book.can_fulfil(line.quantity.value()) {
+                return Err(anyhow!("'{}' is out of stock", book.title));
+            }
+            let price = prices
+                .get(&book.id)
+                .ok_or_else(|| anyhow!("'{}' is not sold in this store", book.title))?;
+            let rate_bps = tax_engine::find_rate(&rates, &store.jurisdiction, book.tax_class, now)
+                .map(|r| r.rate_bps)
+                .unwrap_or(0);
+
+            items.push(
+                OrderItemEntity::new(
+                    book.id,
+                    book.isbn.as_str().to_string(),
+                    book.title.as_str().to_string(),
+                    line.quantity.value(),
+                    price.amount(),
+                    rate_bps,
+                    mode,
+                )
+                .map_err(|e| anyhow!("{}", e))?,
+            );
+        }
+
+        let mut order = OrderEntity::place(user_id, store.id, store.currency.clone(), items).map_err(|e| anyhow!("{}", e))?;
+
+        // Pre-order ส่งพร้อมกันทั้ง order เมื่อเล่มสุดท้ายออก จึงไม่ปนกับของที่มีพร้อมส่ง
+        if let Some(release_date) = release_dates.iter().max() {
+            if release_dates.len() != order.items.len() {
+                return Err(anyhow!(
+                    "Pre-order titles must be checked out separately from books available now"
+                ));
+            }
+            order.set_release_date(*release_date).map_err(|e| anyhow!("{}", e))?;
+        }
+
+        // 3. Promotions — คูปองที่ใช้ไม่ได้แล้วต้องให้ลูกค้าเอาออกก่อน ไม่ตัดทิ้งเงียบๆ
+        let pricing = self.promotions.price_cart(&cart, &books).await?;
+        if let Some(reason) = pricing.coupon_error {
+            return Err(anyhow!("{}", reason));
+        }
+        let free_shipping = pricing.pricing.free_shipping;
+
+        order
+            .apply_promotions(
+                pricing
+                    .pricing
+                    .applied
+                    .into_iter()
+                    .map(|a| OrderPromotion {
+                        id: 0,
+                        promotion_id: a.promotion_id,
+                        name: a.name,
+                        coupon_id: a.coupon_id,
+                        coupon_code: a.coupon_code,
+                        discount: a.discount,
+                    })
+                    .collect(),
+            )
+            .map_err(|e| anyhow!("{}", e))?;
+
+        // 4. Shipping — ราคาคิดจากยอดหลังหักส่วนลด
+        let parcel = Parcel::from_lines(
+            cart.items
+                .iter()
+                .filter_map(|i| Some((books.get(&i.book_id)?, i.quantity.value()))),
+            order.total,
+        );
+        let (shipping_address, billing_address) = self
+            .resolve_addresses(user_id, req.shipping_address_id, req.billing_address_id)
+            .await?;
+        let destination = ShippingDestination::from(&shipping_address);
+        let option = self
+            .shipping
+            .quote(req.shipping_method_id, &destination, &parcel, now)
+            .await?;
+
+        order
+            .set_shipping(OrderShipping {
+                method_id: Some(option.method.id),
+                method_name: option.method.name,
+                kind: option.method.kind,
+                amount: if free_shipping { 0 } else { option.rate.amount },
+                estimated_from: option.rate.estimated_from,
+                estimated_to: option.rate.estimated_to,
+            })
+            .map_err(|e| anyhow!("{}", e))?;
+        order
+            .set_addresses(Some(shipping_address), Some(billing_address))
+            .map_err(|e| anyhow!("{}", e))?;
+
+        // 5. Save (ตัด stock และใช้คูปองใน transaction เดียวกัน)
+        order.id = self
+            .order_repo
+            .save(&order)
+            .await
+            .map_err(|e| anyhow!("Failed to place order: {}", e))?;
+
+        // 6. Empty the cart
+        cart.clear();
+        self.cart_repo
+            .update(&cart)
+            .await
+            .map_err(|e| anyhow!("Failed to clear cart: {}", e))?;
+
+        let placed = self.find_order(order.id).await?;
+        Ok(OrderResponse::from(placed))
+    }
+
+    /// The caller's own order; staff see any. Someone else's order is reported
+    /// as missing.
+    pub async fn get_order(&self, caller: &UserInfo, id: i32) -> Result<Option<OrderResponse>> {
+        let order_opt = self.order_repo.find_by_id(id).await.map_err(|e| {
+            anyhow!("Database error while fetching order: {}", e)
+        })?;
+
+        Ok(order_opt
+            .filter(|o| o.user_id == caller.id || ensure_staff(caller).is_ok())
+            .map(OrderResponse::from))
+    }
+
+    pub async fn get_user_orders(&self, user_id: i32) -> Result<Vec<OrderResponse>> {
+        let orders = self.order_repo.find_by_user(user_id).await.map_err(|e| {
+            anyhow!("Failed to fetch orders: {}", e)
+        })?;
+
+        Ok(orders.into_iter().map(OrderResponse::from).collect())
+    }
+
+    /// Cancel an order and release the stock it reserved. Buyers cancel
+    /// their own orders; staff can cancel any.
+    pub async fn cancel_order(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        req: CancelOrderRequest,
+    ) -> Result<OrderResponse> {
+        let order = self.find_order(id).await?;
+        if order.user_id != caller.id {
+            ensure_staff(caller).map_err(|_| anyhow!("Order not found"))?;
+        }
+        if order.status == OrderStatus::Preordered {
+            // ต้องปล่อยวงเงินที่ authorize ไว้ด้วย -> PreorderUseCase::cancel_preorder
+            return Err(anyhow!("Pre-orders must be cancelled through the pre-order service"));
+        }
+
+        self.cancel_and_release(order, caller.id, req.reason).await
+    }
+
+    /// Cancels, gives the card money back and puts the reserved stock back
+    /// on the shelf
+    pub(crate) async fn cancel_and_release(
+        &self,
+        mut order: OrderEntity,
+        actor_id: i32,
+        reason: Option<String>,
+    ) -> Result<OrderResponse> {
+        let held_stock = order.holds_stock();
+
+        order.cancel(Some(actor_id), reason)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        // คืนเงินก่อนบันทึกสถานะ: ถ้าล้มกลางทาง order ยังยกเลิกซ้ำได้
+        self.payments.refund_for_cancellation(order.id, Some(actor_id)).await?;
+
+        // stock คืนใน transaction เดียวกับการเปลี่ยนสถานะ
+        let updated = self
+            .order_repo
+            .update_closed(&order)
+            .await
+            .map_err(|e| anyhow!("Failed to cancel order: {}", e))?;
+
+        if held_stock {
+            for item in &updated.items {
+                self.notify_back_in_stock(item.book_id).await;
+            }
+        }
+
+        Ok(OrderResponse::from(updated))
+    }
+
+    pub async fn start_picking(&self, caller: &UserInfo, id: i32) -> Result<OrderResponse> {
+        ensure_staff(caller)?;
+        let mut order = self.find_order(id).await?;
+
+        order.start_picking(Some(caller.id))
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.save_transition(order).await
+    }
+
+    pub async fn mark_shipped(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        req: ShipOrderRequest,
+    ) -> Result<OrderResponse> {
+        ensure_staff(caller)?;
+        let mut order = self.find_order(id).await?;
+
+        order.mark_shipped(Some(caller.id), req.tracking_number)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.save_transition(order).await
+    }
+
+    pub async fn mark_delivered(&self, caller: &UserInfo, id: i32) -> Result<OrderResponse> {
+        ensure_staff(caller)?;
+        let mut order = self.find_order(id).await?;
+
+        order.mark_delivered(Some(caller.id))
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.save_transition(order).await
+    }
+
+    /// Copies of the chosen (or default) shipping and billing addresses
+    async fn resolve_addresses(
+        &self,
+        user_id: i32,
+        shipping_address_id: Option<i32>,
+        billing_address_id: Option<i32>,
+    ) -> Result<(PostalAddress, PostalAddress)> {
+        let addresses = self.address_repo.find_by_user(user_id).await.map_err(|e| {
+            anyhow!("Failed to fetch addresses: {}", e)
+        })?;
+        let by_id = |id: i32| {
+            addresses
+                .iter()
+                .find(|a| a.id == id)
+                .ok_or_else(|| anyhow!("Address not found"))
+        };
+
+        let shipping = match shipping_address_id {
+            Some(id) => by_id(id)?,
+            None => addresses
+                .iter()
+                .find(|a| a.is_default_shipping)
+                .ok_or_else(|| anyhow!("Add a shipping address before checkout"))?,
+        };
+        let billing = match billing_address_id {
+            Some(id) => by_id(id)?,
+            None => addresses
+                .iter()
+                .find(|a| a.is_default_billing)
+                .unwrap_or(shipping),
+        };
+
+        Ok((shipping.address.clone(), billing.address.clone()))
+    }
+
+    async fn find_order(&self, id: i32) -> Result<OrderEntity> {
+        match self
+            .order_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch order: {}", e))?
+        {
+            Some(o) => Ok(o),
+            None => Err(anyhow!("Order not found")),
+        }
+    }
+
+    /// The order is already cancelled; a failed notification is retried by
+    /// the next stock movement of the book
+    async fn notify_back_in_stock(&self, book_id: i32) {
+        if let Err(e) = self.subscription_repo.notify_back_in_stock(book_id).await {
+            tracing::warn!("Failed to notify subscribers of book {}: {}", book_id, e);
+        }
+    }
+
+    async fn save_transition(&self, order: OrderEntity) -> Result<OrderResponse> {
+        let updated = self
+            .order_repo
+            .update(&order)
+            .await
+            .map_err(|e| anyhow!("Failed to update order: {}", e))?;
+
+        Ok(OrderResponse::from(updated))
+    }
+}
@@ -0,0 +1,1172 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use tracing::warn;
+
+use crate::application::dtos::{
+    loyalty_dto::RedeemPointsRequest,
+    payment_dto::{PayOrderRequest, PaymentResponse},
+    stored_value_dto::{RedeemGiftCardRequest, RedeemStoreCreditRequest},
+};
+use crate::domain::{
+    entities::{
+        loyalty_entry::{
+            points_for_amount, points_value, LoyaltyEntryEntity, PointsReason, POINTS_CURRENCY,
+            LOYALTY_PAYMENT_PROVIDER,
+        },
+        order::OrderEntity,
+        payment::PaymentEntity,
+        stored_value_account::StoredValueAccountEntity,
+        stored_value_entry::{EntryReason, StoredValueEntryEntity},
+    },
+    repositories::{
+        loyalty_repository::LoyaltyRepository, order_repository::OrderRepository,
+        payment_repository::PaymentRepository, stored_value_repository::StoredValueRepository,
+    },
+    value_objects::{
+        gift_card_code::GiftCardCode, order_status::OrderStatus, payment_status::PaymentStatus,
+    },
+};
+use crate::infrastructure::payment_gateway::{
+    CreatePaymentIntent, PaymentGateway, PaymentIntent, PaymentIntentStatus, WebhookEvent,
+    WebhookEventKind,
+};
+
+/// PaymentUseCase — pays orders through a PaymentGateway and reacts to its webhooks.
+/// Gift cards, store credit and loyalty points can cover part of an order
+/// before the card is charged.
+pub struct PaymentUseCase {
+    payment_repo: Arc<dyn PaymentRepository>,
+    order_repo: Arc<dyn OrderRepository>,
+    stored_value_repo: Arc<dyn StoredValueRepository>,
+    loyalty_repo: Arc<dyn LoyaltyRepository>,
+    gateway: Arc<dyn PaymentGateway>,
+}
+
+impl PaymentUseCase {
+    pub fn new(
+        payment_repo: Arc<dyn PaymentRepository>,
+        order_repo: Arc<dyn OrderRepository>,
+        stored_value_repo: Arc<dyn StoredValueRepository>,
+        loyalty_repo: Arc<dyn LoyaltyRepository>,
+        gateway: Arc<dyn PaymentGateway>,
+    ) -> Self {
+        Self {
+            payment_repo,
+            order_repo,
+            stored_value_repo,
+            loyalty_repo,
+            gateway,
+        }
+    }
+
+    /// Start a payment attempt for an order awaiting payment
+    pub async fn pay_order(
+        &self,
+        user_id: i32,
+        order_id: i32,
+        req: PayOrderRequest,
+    ) -> Result<PaymentResponse> {
+        let order = self.find_order(order_id).await?;
+        if order.user_id != user_id {
+            return Err(anyhow!("Order not found"));
+        }
+        if order.status != OrderStatus::PendingPayment {
+            return Err(anyhow!("Order is not awaiting payment"));
+        }
+
+        // 3DS ที่ลูกค้าทิ้งไว้ต้อง void ก่อน ไม่งั้นทำต่อทีหลังแล้วโดนเก็บเงินสองครั้ง
+        self.void_abandoned_attempts(order.id).await?;
+
+        // ส่วนที่จ่ายด้วย gift card / store credit ไปแล้วไม่ต้องเก็บจากบัตรซ้ำ
+        let outstanding = self.outstanding(&order).await?;
+        if outstanding <= 0 {
+            return Err(anyhow!("Nothing left to pay on this order"));
+        }
+
+        // 1. บันทึก attempt ก่อนเรียก PSP เพื่อให้มี record แม้ PSP จะ error
+        let mut payment = PaymentEntity::new(
+            order.id,
+            self.gateway.provider().to_string(),
+            outstanding,
+            order.currency.as_str().to_string(),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        payment.id = self
+            .payment_repo
+            .save(&payment)
+            .await
+            .map_err(|e| anyhow!("Failed to save payment: {}", e))?;
+
+        // 2. Authorize
+        let intent = match self
+            .gateway
+            .create_intent(&CreatePaymentIntent {
+                amount: payment.amount,
+                currency: payment.currency.clone(),
+                payment_method: req.payment_method,
+                reference: format!("order-{}", order.id),
+            })
+            .await
+        {
+            Ok(intent) => intent,
+            Err(e) => {
+                payment.decline(Some(e.to_string())).map_err(|e| anyhow!("{}", e))?;
+                self.payment_repo
+                    .update(&payment)
+                    .await
+                    .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+                return Err(anyhow!("Payment provider error: {}", e));
+            }
+        };
+
+        payment.attach_intent(intent.id.clone());
+        Self::apply_intent(&mut payment, &intent)?;
+
+        let payment = self
+            .payment_repo
+            .update(&payment)
+            .await
+            .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+
+        // 3. Capture ทันทีถ้า authorize ผ่านโดยไม่ต้องทำ 3DS (pre-order รอวันวางขาย)
+        let payment = if payment.status == PaymentStatus::Authorized {
+            self.settle_authorized(payment).await?
+        } else {
+            payment
+        };
+
+        Ok(PaymentResponse::from(payment))
+    }
+
+    /// Spend a gift card on an order, in full or in part
+    pub async fn pay_with_gift_card(
+        &self,
+        user_id: i32,
+        order_id: i32,
+        req: RedeemGiftCardRequest,
+    ) -> Result<PaymentResponse> {
+        let code = GiftCardCode::new(req.code).map_err(|e| anyhow!("{}", e))?;
+        let account = self
+            .stored_value_repo
+            .find_by_code_hash(&code.hash())
+            .await
+            .map_err(|e| anyhow!("Database error while fetching gift card: {}", e))?
+            .ok_or_else(|| anyhow!("Gift card not found"))?;
+
+        self.redeem(user_id, order_id, account, req.amount).await
+    }
+
+    /// Spend the customer's store credit on an order, in full or in part
+    pub async fn pay_with_store_credit(
+        &self,
+        user_id: i32,
+        order_id: i32,
+        req: RedeemStoreCreditRequest,
+    ) -> Result<PaymentResponse> {
+        let order = self.find_order(order_id).await?;
+        let account = self
+            .stored_value_repo
+            .find_store_credit(user_id, order.currency.as_str())
+            .await
+            .map_err(|e| anyhow!("Database error while fetching store credit: {}", e))?
+            .ok_or_else(|| anyhow!("No store credit available"))?;
+
+        self.redeem(user_id, order_id, account, req.amount).await
+    }
+
+    /// Spend loyalty points on an order. The amount is rounded down to
+    /// whole points.
+    pub async fn pay_with_points(
+        &self,
+        user_id: i32,
+        order_id: i32,
+        req: RedeemPointsRequest,
+    ) -> Result<PaymentResponse> {
+        let order = self.find_order(order_id).await?;
+        if order.user_id != user_id {
+            return Err(anyhow!("Order not found"));
+        }
+        if order.status != OrderStatus::PendingPayment {
+            return Err(anyhow!("Order is not awaiting payment"));
+        }
+        // POINT_VALUE เป็นสตางค์ ใช้กับ order สกุลอื่นไม่ได้
+        if order.currency.as_str() != POINTS_CURRENCY {
+            return Err(anyhow!("Points can only be used on orders in {}", POINTS_CURRENCY));
+        }
+
+        let balance = self
+            .loyalty_repo
+            .find_balance(user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch points balance: {}", e))?;
+        let outstanding = self.outstanding(&order).await?;
+        let points = req
+            .points
+            .unwrap_or(balance)
+            .min(balance)
+            .min(points_for_amount(outstanding));
+        if points <= 0 {
+            return Err(anyhow!("Not enough points to pay towards this order"));
+        }
+        let amount = points_value(points);
+
+        let mut payment = PaymentEntity::new(
+            order.id,
+            LOYALTY_PAYMENT_PROVIDER.to_string(),
+            amount,
+            order.currency.as_str().to_string(),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        payment.id = self
+            .payment_repo
+            .save(&payment)
+            .await
+            .map_err(|e| anyhow!("Failed to save payment: {}", e))?;
+
+        let entry = LoyaltyEntryEntity::new(
+            user_id,
+            -points,
+            PointsReason::Redemption,
+            Some(order.id),
+            None,
+            Some(user_id),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        let entry_id = match self.loyalty_repo.record(&entry).await {
+            Ok(id) => id,
+            Err(e) => {
+                payment.decline(Some(e.to_string())).map_err(|e| anyhow!("{}", e))?;
+                self.payment_repo
+                    .update(&payment)
+                    .await
+                    .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+                return Err(anyhow!("Failed to redeem points: {}", e));
+            }
+        };
+
+        payment.attach_intent(entry_id.to_string());
+        payment.authorize().map_err(|e| anyhow!("{}", e))?;
+        payment.capture(amount).map_err(|e| anyhow!("{}", e))?;
+
+        let payment = self
+            .payment_repo
+            .update(&payment)
+            .await
+            .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+
+        if amount == outstanding {
+            self.complete_order(order.id).await?;
+        }
+
+        Ok(PaymentResponse::from(payment))
+    }
+
+    /// Handle an HMAC-signed webhook from the payment provider
+    pub async fn handle_webhook(&self, payload: &[u8], signature: &str) -> Result<()> {
+        let event = self
+            .gateway
+            .verify_webhook(payload, signature)
+            .map_err(|e| anyhow!("Webhook rejected: {}", e))?;
+        let provider = self.gateway.provider();
+
+        // จอง event id ก่อนประมวลผล: ตัวที่ส่งซ้ำพร้อมกันจะหยุดตรงนี้
+        let first_delivery = self
+            .payment_repo
+            .record_webhook_event(provider, &event.id, event.kind.as_str())
+            .await
+            .map_err(|e| anyhow!("Failed to record webhook event: {}", e))?;
+        if !first_delivery {
+            return Ok(());
+        }
+
+        if let Err(e) = self.apply_webhook(&event).await {
+            // ลบทิ้งเพื่อให้ PSP ส่งซ้ำแล้วประมวลผลใหม่ได้
+            if let Err(forget) = self.payment_repo.forget_webhook_event(provider, &event.id).await {
+                warn!("Failed to forget webhook event {}: {}", event.id, forget);
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_order_payments(&self, order_id: i32) -> Result<Vec<PaymentResponse>> {
+        let payments = self.payment_repo.find_by_order(order_id).await.map_err(|e| {
+            anyhow!("Failed to fetch payments: {}", e)
+        })?;
+
+        Ok(payments.into_iter().map(PaymentResponse::from).collect())
+    }
+
+    /// Captures the held authorization of a released pre-order for what is
+    /// left of the order's (possibly price-protected) total once gift cards
+    /// and store credit are counted. Safe to call again.
+    pub async fn capture_for_release(&self, order: &OrderEntity) -> Result<()> {
+        let payments = self.payment_repo.find_by_order(order.id).await.map_err(|e| {
+            anyhow!("Failed to fetch payments: {}", e)
+        })?;
+
+        let paid: i64 = payments
+            .iter()
+            .filter(|p| p.is_settled())
+            .map(|p| p.refundable_amount())
+            .sum();
+        let outstanding = order.total - paid;
+        let authorized = payments
+            .into_iter()
+            .find(|p| p.status == PaymentStatus::Authorized);
+
+        let mut payment = match authorized {
+            // รอบก่อน capture แล้วแต่ยังไม่ได้ปล่อย order หรือจ่ายด้วยยอดคงเหลือครบแล้ว
+            None if outstanding <= 0 => return Ok(()),
+            None => return Err(anyhow!("Order {} has no authorized payment", order.id)),
+            Some(payment) => payment,
+        };
+        let intent_id = payment
+            .provider_intent_id
+            .clone()
+            .ok_or_else(|| anyhow!("Payment has no provider intent"))?;
+
+        // ราคาลดจนยอดคงเหลือจ่ายครบ ไม่ต้องเก็บจากบัตร
+        if outstanding <= 0 {
+            if outstanding < 0 {
+                warn!("Order {} overpaid by {} after price protection", order.id, -outstanding);
+            }
+            self.gateway
+                .void(&intent_id)
+                .await
+                .map_err(|e| anyhow!("Failed to void payment: {}", e))?;
+            payment.void().map_err(|e| anyhow!("{}", e))?;
+        } else {
+            let intent = self
+                .gateway
+                .capture(&intent_id, Some(outstanding))
+                .await
+                .map_err(|e| anyhow!("Failed to capture payment: {}", e))?;
+            payment.capture(intent.captured_amount).map_err(|e| anyhow!("{}", e))?;
+        }
+
+        self.payment_repo
+            .update(&payment)
+            .await
+            .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Gives back the money of an order being cancelled: open card attempts
+    /// are voided, captured card payments refunded in full and gift card,
+    /// store credit and points put back on their accounts. Safe to call again.
+    pub async fn refund_for_cancellation(&self, order_id: i32, changed_by: Option<i32>) -> Result<()> {
+        self.stored_value_repo
+            .refund_redemptions_for_order(order_id, changed_by)
+            .await
+            .map_err(|e| anyhow!("Failed to refund gift card and store credit: {}", e))?;
+        self.loyalty_repo
+            .reverse_redemptions_for_order(order_id, changed_by)
+            .await
+            .map_err(|e| anyhow!("Failed to give back points: {}", e))?;
+
+        let provider = self.gateway.provider();
+        let payments = self.payment_repo.find_by_order(order_id).await.map_err(|e| {
+            anyhow!("Failed to fetch payments: {}", e)
+        })?;
+
+        for mut payment in payments.into_iter().filter(|p| p.provider == provider) {
+            match payment.status {
+                PaymentStatus::RequiresAction | PaymentStatus::Authorized => {
+                    if let Some(intent_id) = &payment.provider_intent_id {
+                        self.gateway
+                            .void(intent_id)
+                            .await
+                            .map_err(|e| anyhow!("Failed to void payment: {}", e))?;
+                    }
+                    payment.void().map_err(|e| anyhow!("{}", e))?;
+                }
+                // ยังไม่ได้ intent จาก PSP ไม่มีอะไรให้ void
+                PaymentStatus::Pending => {
+                    payment
+                        .decline(Some("Order cancelled".to_string()))
+                        .map_err(|e| anyhow!("{}", e))?;
+                }
+                PaymentStatus::Captured | PaymentStatus::PartiallyRefunded => {
+                    let amount = payment.refundable_amount();
+                    let intent_id = payment
+                        .provider_intent_id
+                        .clone()
+                        .ok_or_else(|| anyhow!("Payment has no provider intent"))?;
+                    self.gateway
+                        .refund(&intent_id, amount, &format!("cancel-payment-{}", payment.id))
+                        .await
+                        .map_err(|e| anyhow!("Failed to refund payment: {}", e))?;
+                    payment.record_refund(amount).map_err(|e| anyhow!("{}", e))?;
+                }
+                _ => continue,
+            }
+
+            self.payment_repo
+                .update(&payment)
+                .await
+                .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    async fn apply_webhook(&self, event: &WebhookEvent) -> Result<()> {
+        let mut payment = self
+            .payment_repo
+            .find_by_intent(self.gateway.provider(), &event.intent_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching payment: {}", e))?
+            .ok_or_else(|| anyhow!("Unknown payment intent {}", event.intent_id))?;
+
+        // ทุก branch เช็ค status ก่อน เพื่อให้ event ที่มาช้ากว่าสถานะจริงไม่มีผล
+        // payment ที่บันทึกไปแล้วแต่ order ยังไม่ตามมา (ล้มกลางทางแล้ว PSP ส่งซ้ำ)
+        // ทำขั้นของ order ต่อจากที่ค้างไว้
+        match event.kind {
+            WebhookEventKind::PaymentAuthorized => match payment.status {
+                PaymentStatus::RequiresAction => {
+                    payment.authorize().map_err(|e| anyhow!("{}", e))?;
+                    let payment = self
+                        .payment_repo
+                        .update(&payment)
+                        .await
+                        .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+                    self.settle_authorized(payment).await?;
+                }
+                PaymentStatus::Authorized => {
+                    self.settle_authorized(payment).await?;
+                }
+                PaymentStatus::Captured => self.resume_order_paid(payment.order_id).await?,
+                _ => {}
+            },
+            WebhookEventKind::PaymentSucceeded => {
+                if !payment.is_settled() {
+                    if payment.status != PaymentStatus::Authorized {
+                        payment.authorize().map_err(|e| anyhow!("{}", e))?;
+                    }
+                    payment.capture(event.amount).map_err(|e| anyhow!("{}", e))?;
+                    self.payment_repo
+                        .update(&payment)
+                        .await
+                        .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+                    self.mark_order_paid(payment.order_id).await?;
+                } else if payment.status == PaymentStatus::Captured {
+                    self.resume_order_paid(payment.order_id).await?;
+                }
+            }
+            WebhookEventKind::PaymentFailed => {
+                if payment.is_open() {
+                    payment
+                        .decline(event.failure_reason.clone())
+                        .map_err(|e| anyhow!("{}", e))?;
+                    self.payment_repo
+                        .update(&payment)
+                        .await
+                        .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Voids card attempts left waiting on 3-D Secure before a new one is
+    /// started. Attempts already authorized or still being created block it.
+    async fn void_abandoned_attempts(&self, order_id: i32) -> Result<()> {
+        let payments = self.payment_repo.find_by_order(order_id).await.map_err(|e| {
+            anyhow!("Failed to fetch payments: {}", e)
+        })?;
+
+        for mut payment in payments.into_iter().filter(|p| {
+            p.status == PaymentStatus::RequiresAction && p.provider == self.gateway.provider()
+        }) {
+            if let Some(intent_id) = &payment.provider_intent_id {
+                self.gateway
+                    .void(intent_id)
+                    .await
+                    .map_err(|e| anyhow!("Failed to void payment: {}", e))?;
+            }
+            payment.void().map_err(|e| anyhow!("{}", e))?;
+            self.payment_repo
+                .update(&payment)
+                .await
+                .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Maps the provider's intent status onto our payment record
+    fn apply_intent(payment: &mut PaymentEntity, intent: &PaymentIntent) -> Result<()> {
+        match intent.status {
+            PaymentIntentStatus::RequiresCapture => payment.authorize(),
+            PaymentIntentStatus::RequiresAction => {
+                payment.require_action(intent.next_action_url.clone())
+            }
+            PaymentIntentStatus::Succeeded => {
+                payment.authorize()?;
+                payment.capture(intent.captured_amount)
+            }
+            PaymentIntentStatus::Declined => payment.decline(intent.decline_reason.clone()),
+            PaymentIntentStatus::Voided => payment.void(),
+        }
+        .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Pays (part of) an order from a gift card or store credit. The payment's
+    /// intent id is the ledger entry the money was taken with.
+    async fn redeem(
+        &self,
+        user_id: i32,
+        order_id: i32,
+        account: StoredValueAccountEntity,
+        amount: Option<i64>,
+    ) -> Result<PaymentResponse> {
+        let order = self.find_order(order_id).await?;
+        if order.user_id != user_id {
+            return Err(anyhow!("Order not found"));
+        }
+        if order.status != OrderStatus::PendingPayment {
+            return Err(anyhow!("Order is not awaiting payment"));
+        }
+        if account.currency != order.currency {
+            return Err(anyhow!("Balance is in {}, the order in {}", account.currency, order.currency));
+        }
+
+        let outstanding = self.outstanding(&order).await?;
+        if outstanding <= 0 {
+            return Err(anyhow!("Nothing left to pay on this order"));
+        }
+        let amount = amount
+            .unwrap_or(account.balance)
+            .min(account.balance)
+            .min(outstanding);
+        account
+            .ensure_redeemable(amount, Utc::now())
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let mut payment = PaymentEntity::new(
+            order.id,
+            account.kind.as_str().to_string(),
+            amount,
+            order.currency.as_str().to_string(),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        payment.id = self
+            .payment_repo
+            .save(&payment)
+            .await
+            .map_err(|e| anyhow!("Failed to save payment: {}", e))?;
+
+        let entry = StoredValueEntryEntity::new(
+            account.id,
+            -amount,
+            EntryReason::Redemption,
+            Some(order.id),
+            None,
+            Some(user_id),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        // ยอดถูกใช้ไปพร้อมกันจากที่อื่น ให้ attempt นี้ declined
+        let entry_id = match self.stored_value_repo.record(&entry).await {
+            Ok(id) => id,
+            Err(e) => {
+                payment.decline(Some(e.to_string())).map_err(|e| anyhow!("{}", e))?;
+                self.payment_repo
+                    .update(&payment)
+                    .await
+                    .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+                return Err(anyhow!("Failed to redeem balance: {}", e));
+            }
+        };
+
+        payment.attach_intent(entry_id.to_string());
+        payment.authorize().map_err(|e| anyhow!("{}", e))?;
+        payment.capture(amount).map_err(|e| anyhow!("{}", e))?;
+
+        let payment = self
+            .payment_repo
+            .update(&payment)
+            .await
+            .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+
+        if amount == outstanding {
+            self.complete_order(order.id).await?;
+        }
+
+        Ok(PaymentResponse::from(payment))
+    }
+
+    /// What is left to pay after gift cards, store credit and earlier captures.
+    /// Fails while another attempt may still take money for the order.
+    async fn outstanding(&self, order: &OrderEntity) -> Result<i64> {
+        let payments = self
+            .payment_repo
+            .find_by_order(order.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch payments: {}", e))?;
+        if payments.iter().any(|p| p.is_open()) {
+            return Err(anyhow!("Another payment for this order is still in progress"));
+        }
+
+        let paid: i64 = payments
+            .iter()
+            .filter(|p| p.is_settled())
+            .map(|p| p.refundable_amount())
+            .sum();
+
+        Ok(order.total - paid)
+    }
+
+    /// The order is fully covered without a card: an unreleased pre-order
+    /// waits for its release date, anything else is paid.
+    async fn complete_order(&self, order_id: i32) -> Result<()> {
+        let mut order = self.find_order(order_id).await?;
+        let today = Utc::now().date_naive();
+
+        if order.status == OrderStatus::PendingPayment
+            && order.release_date.is_some_and(|d| d > today)
+        {
+            order.hold_for_release(None).map_err(|e| anyhow!("{}", e))?;
+            self.order_repo
+                .update(&order)
+                .await
+                .map_err(|e| anyhow!("Failed to hold pre-order: {}", e))?;
+            return Ok(());
+        }
+
+        self.mark_order_paid(order_id).await
+    }
+
+    /// Captures an authorized payment, unless the order is a pre-order that
+    /// has not been released yet: then the authorization is held until then.
+    /// Does nothing for an order that is no longer waiting for payment.
+    async fn settle_authorized(&self, payment: PaymentEntity) -> Result<PaymentEntity> {
+        let mut order = self.find_order(payment.order_id).await?;
+        let today = Utc::now().date_naive();
+
+        match order.status {
+            OrderStatus::PendingPayment if order.release_date.is_some_and(|d| d > today) => {
+                order.hold_for_release(None).map_err(|e| anyhow!("{}", e))?;
+                self.order_repo
+                    .update(&order)
+                    .await
+                    .map_err(|e| anyhow!("Failed to hold pre-order: {}", e))?;
+                Ok(payment)
+            }
+            OrderStatus::PendingPayment => self.capture_and_settle(payment).await,
+            // ถือวงเงินไว้แล้ว รอ release job capture
+            OrderStatus::Preordered => Ok(payment),
+            _ => {
+                tracing::warn!(
+                    "Payment {} authorized for order {} in status {}",
+                    payment.id,
+                    order.id,
+                    order.status
+                );
+                Ok(payment)
+            }
+        }
+    }
+
+    /// Marks the order of an already captured payment as paid if that step
+    /// never went through
+    async fn resume_order_paid(&self, order_id: i32) -> Result<()> {
+        let order = self.find_order(order_id).await?;
+        if order.status == OrderStatus::PendingPayment {
+            self.mark_order_paid(order_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn capture_and_settle(&self, mut payment: PaymentEntity) -> Result<PaymentEntity> {
+        let intent_id = payment
+            .provider_intent_id
+            .clone()
+            .ok_or_else(|| anyhow!("Payment has no provider intent"))?;
+
+        let intent = self
+            .gateway
+            .capture(&intent_id, None)
+            .await
+            .map_err(|e| anyhow!("Failed to capture payment: {}", e))?;
+
+        payment.capture(intent.captured_amount).map_err(|e| anyhow!("{}", e))?;
+
+        let payment = self
+            .payment_repo
+            .update(&payment)
+            .await
+            .map_err(|e| anyhow!("Failed to update payment: {}", e))?;
+
+        self.mark_order_paid(payment.order_id).await?;
+
+        Ok(payment)
+    }
+
+    async fn mark_order_paid(&self, order_id: i32) -> Result<()> {
+        let mut order = self.find_order(order_id).await?;
+
+        if order.status != OrderStatus::PendingPayment {
+            // เงินเข้าหลังจาก order ถูกยกเลิก/จ่ายไปแล้ว ต้องให้ staff ตรวจสอบและคืนเงิน
+            tracing::warn!(
+                "Payment captured for order {} in status {}",
+                order.id,
+                order.status
+            );
+            return Ok(());
+        }
+
+        order.mark_paid(None).map_err(|e| anyhow!("{}", e))?;
+        self.order_repo
+            .update_paid(&order)
+            .await
+            .map_err(|e| anyhow!("Failed to mark order as paid: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn find_order(&self, id: i32) -> Result<OrderEntity> {
+        match self
+            .order_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch order: {}", e))?
+        {
+            Some(o) => Ok(o),
+            None => Err(anyhow!("Order not found")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, NaiveDate};
+
+    use super::*;
+    use crate::domain::{
+        entities::{
+            loyalty_campaign::{LoyaltyCampaignEntity, LoyaltyCategoryMultiplier},
+            order::OrderItemEntity,
+        },
+        repositories::loyalty_repository::MemberSpend,
+        services::tax_engine::TaxMode,
+        value_objects::money::Currency,
+    };
+    use crate::infrastructure::mock_payment_gateway::{
+        MockPaymentGateway, CARD_REQUIRES_3DS, CARD_SUCCESS,
+    };
+
+    const ORDER_TOTAL: i64 = 45_000;
+
+    #[derive(Default)]
+    struct InMemoryPayments {
+        payments: Mutex<Vec<PaymentEntity>>,
+        events: Mutex<HashSet<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl PaymentRepository for InMemoryPayments {
+        async fn find_by_id(&self, id: i32) -> Result<Option<PaymentEntity>> {
+            Ok(self.payments.lock().unwrap().iter().find(|p| p.id == id).cloned())
+        }
+
+        async fn find_by_order(&self, order_id: i32) -> Result<Vec<PaymentEntity>> {
+            Ok(self.payments.lock().unwrap().iter().filter(|p| p.order_id == order_id).cloned().collect())
+        }
+
+        async fn find_by_intent(&self, provider: &str, intent_id: &str) -> Result<Option<PaymentEntity>> {
+            Ok(self
+                .payments
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|p| p.provider == provider && p.provider_intent_id.as_deref() == Some(intent_id))
+                .cloned())
+        }
+
+        async fn save(&self, payment: &PaymentEntity) -> Result<i32> {
+            let mut payments = self.payments.lock().unwrap();
+            // เหมือน uq_payments_open_attempt
+            if payments.iter().any(|p| p.order_id == payment.order_id && p.is_open()) {
+                return Err(anyhow!("duplicate key value violates unique constraint"));
+            }
+            let mut payment = payment.clone();
+            payment.id = payments.len() as i32 + 1;
+            payments.push(payment.clone());
+            Ok(payment.id)
+        }
+
+        async fn update(&self, payment: &PaymentEntity) -> Result<PaymentEntity> {
+            let mut payments = self.payments.lock().unwrap();
+            let stored = payments
+                .iter_mut()
+                .find(|p| p.id == payment.id)
+                .ok_or_else(|| anyhow!("Payment not found"))?;
+            *stored = payment.clone();
+            Ok(payment.clone())
+        }
+
+        async fn record_webhook_event(&self, provider: &str, event_id: &str, _kind: &str) -> Result<bool> {
+            Ok(self.events.lock().unwrap().insert((provider.to_string(), event_id.to_string())))
+        }
+
+        async fn forget_webhook_event(&self, provider: &str, event_id: &str) -> Result<()> {
+            self.events.lock().unwrap().remove(&(provider.to_string(), event_id.to_string()));
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryOrders {
+        orders: Mutex<HashMap<i32, OrderEntity>>,
+        /// Fails the next `update_paid`, as a lost database connection would
+        fail_next_paid: Mutex<bool>,
+    }
+
+    #[async_trait]
+    impl OrderRepository for InMemoryOrders {
+        async fn find_by_id(&self, id: i32) -> Result<Option<OrderEntity>> {
+            Ok(self.orders.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_user(&self, user_id: i32) -> Result<Vec<OrderEntity>> {
+            Ok(self.orders.lock().unwrap().values().filter(|o| o.user_id == user_id).cloned().collect())
+        }
+
+        async fn find_preordered_book_ids(&self, _store_id: i32) -> Result<Vec<i32>> {
+            Ok(Vec::new())
+        }
+
+        async fn record_preorder_price(&self, _store_id: i32, _book_id: i32, _unit_price: i64) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn reschedule_preorders(&self, _book_id: i32) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn find_preorders_due(&self, _on: NaiveDate, _limit: i64) -> Result<Vec<OrderEntity>> {
+            Ok(Vec::new())
+        }
+
+        async fn save(&self, order: &OrderEntity) -> Result<i32> {
+            let mut orders = self.orders.lock().unwrap();
+            let id = orders.len() as i32 + 1;
+            orders.insert(id, OrderEntity { id, ..order.clone() });
+            Ok(id)
+        }
+
+        async fn update(&self, order: &OrderEntity) -> Result<OrderEntity> {
+            let mut orders = self.orders.lock().unwrap();
+            // เหมือน WHERE status = <สถานะตอนโหลด> ของ Postgres
+            let stored = orders.get(&order.id).ok_or_else(|| anyhow!("Order not found"))?;
+            if stored.status != order.stored_status() {
+                return Err(anyhow!("Order {} was changed by another request", order.id));
+            }
+            let mut saved = order.clone();
+            for change in saved.history.iter_mut().filter(|h| h.id == 0) {
+                change.id = 1;
+            }
+            orders.insert(order.id, saved.clone());
+            Ok(saved)
+        }
+
+        async fn update_paid(&self, order: &OrderEntity) -> Result<OrderEntity> {
+            if std::mem::take(&mut *self.fail_next_paid.lock().unwrap()) {
+                return Err(anyhow!("connection reset"));
+            }
+            self.update(order).await
+        }
+
+        async fn update_closed(&self, order: &OrderEntity) -> Result<OrderEntity> {
+            self.update(order).await
+        }
+    }
+
+    /// Card payments never touch balances or points: accounts are never found
+    /// and writes are refused
+    struct NoBalances;
+
+    #[async_trait]
+    impl StoredValueRepository for NoBalances {
+        async fn find_by_id(&self, _id: i32) -> Result<Option<StoredValueAccountEntity>> {
+            Ok(None)
+        }
+        async fn find_by_code_hash(&self, _code_hash: &str) -> Result<Option<StoredValueAccountEntity>> {
+            Ok(None)
+        }
+        async fn find_store_credit(&self, _user_id: i32, _currency: &str) -> Result<Option<StoredValueAccountEntity>> {
+            Ok(None)
+        }
+        async fn create(&self, _account: &StoredValueAccountEntity, _opening_amount: i64) -> Result<StoredValueAccountEntity> {
+            Err(anyhow!("No balances in these tests"))
+        }
+        async fn find_or_create_store_credit(&self, _user_id: i32, _currency: &str) -> Result<StoredValueAccountEntity> {
+            Err(anyhow!("No balances in these tests"))
+        }
+        async fn update(&self, _account: &StoredValueAccountEntity) -> Result<StoredValueAccountEntity> {
+            Err(anyhow!("No balances in these tests"))
+        }
+        async fn record(&self, _entry: &StoredValueEntryEntity) -> Result<i32> {
+            Err(anyhow!("No balances in these tests"))
+        }
+        async fn refund_redemptions_for_order(&self, _order_id: i32, _changed_by: Option<i32>) -> Result<u64> {
+            Ok(0)
+        }
+        async fn find_entry(&self, _id: i32) -> Result<Option<StoredValueEntryEntity>> {
+            Ok(None)
+        }
+        async fn find_entries(&self, _account_id: i32) -> Result<Vec<StoredValueEntryEntity>> {
+            Ok(Vec::new())
+        }
+        async fn refund_credit_total(&self, _order_id: i32) -> Result<i64> {
+            Ok(0)
+        }
+        async fn find_expired(&self, _now: DateTime<Utc>, _limit: i64) -> Result<Vec<StoredValueAccountEntity>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[async_trait]
+    impl LoyaltyRepository for NoBalances {
+        async fn find_balance(&self, _user_id: i32) -> Result<i64> {
+            Ok(0)
+        }
+        async fn balance_before(&self, _user_id: i32, _before: DateTime<Utc>) -> Result<i64> {
+            Ok(0)
+        }
+        async fn record(&self, _entry: &LoyaltyEntryEntity) -> Result<i32> {
+            Err(anyhow!("No balances in these tests"))
+        }
+        async fn reverse_redemptions_for_order(&self, _order_id: i32, _changed_by: Option<i32>) -> Result<u64> {
+            Ok(0)
+        }
+        async fn find_entry(&self, _id: i32) -> Result<Option<LoyaltyEntryEntity>> {
+            Ok(None)
+        }
+        async fn find_entries(&self, _user_id: i32, _from: DateTime<Utc>, _to: DateTime<Utc>) -> Result<Vec<LoyaltyEntryEntity>> {
+            Ok(Vec::new())
+        }
+        async fn earned_for_order(&self, _order_id: i32) -> Result<(i64, i64)> {
+            Ok((0, 0))
+        }
+        async fn points_due_to_expire(&self, _user_id: i32, _at: DateTime<Utc>) -> Result<i64> {
+            Ok(0)
+        }
+        async fn find_expiring(&self, _at: DateTime<Utc>, _limit: i64) -> Result<Vec<(i32, i64)>> {
+            Ok(Vec::new())
+        }
+        async fn spend_since(&self, _user_id: i32, _since: DateTime<Utc>) -> Result<i64> {
+            Ok(0)
+        }
+        async fn find_member_spend(&self, _since: DateTime<Utc>) -> Result<Vec<MemberSpend>> {
+            Ok(Vec::new())
+        }
+        async fn find_category_multipliers(&self) -> Result<Vec<LoyaltyCategoryMultiplier>> {
+            Ok(Vec::new())
+        }
+        async fn save_category_multiplier(&self, _multiplier: &LoyaltyCategoryMultiplier) -> Result<()> {
+            Err(anyhow!("No balances in these tests"))
+        }
+        async fn delete_category_multiplier(&self, _category: &str) -> Result<()> {
+            Ok(())
+        }
+        async fn find_campaigns(&self) -> Result<Vec<LoyaltyCampaignEntity>> {
+            Ok(Vec::new())
+        }
+        async fn find_campaign(&self, _id: i32) -> Result<Option<LoyaltyCampaignEntity>> {
+            Ok(None)
+        }
+        async fn save_campaign(&self, _campaign: &LoyaltyCampaignEntity) -> Result<i32> {
+            Err(anyhow!("No balances in these tests"))
+        }
+        async fn update_campaign(&self, _campaign: &LoyaltyCampaignEntity) -> Result<LoyaltyCampaignEntity> {
+            Err(anyhow!("No balances in these tests"))
+        }
+    }
+
+    struct Fixture {
+        usecase: PaymentUseCase,
+        payments: Arc<InMemoryPayments>,
+        orders: Arc<InMemoryOrders>,
+        gateway: Arc<MockPaymentGateway>,
+    }
+
+    /// Order 1 of user 7 awaiting payment of ORDER_TOTAL
+    fn fixture() -> Fixture {
+        let item = OrderItemEntity::new(
+            1,
+            "9780261103573".to_string(),
+            "The Fellowship of the Ring".to_string(),
+            1,
+            ORDER_TOTAL,
+            0,
+            TaxMode::Inclusive,
+        )
+        .unwrap();
+        let mut order = OrderEntity::place(7, 1, Currency::thb(), vec![item]).unwrap();
+        order.id = 1;
+        // อย่างที่โหลดมาจาก database: history บันทึกแล้วทั้งหมด
+        for change in &mut order.history {
+            change.id = 1;
+        }
+
+        let payments = Arc::new(InMemoryPayments::default());
+        let orders = Arc::new(InMemoryOrders::default());
+        orders.orders.lock().unwrap().insert(order.id, order);
+        let gateway = Arc::new(MockPaymentGateway::new("whsec_test"));
+
+        Fixture {
+            usecase: PaymentUseCase::new(
+                payments.clone(),
+                orders.clone(),
+                Arc::new(NoBalances),
+                Arc::new(NoBalances),
+                gateway.clone(),
+            ),
+            payments,
+            orders,
+            gateway,
+        }
+    }
+
+    fn card(number: &str) -> PayOrderRequest {
+        PayOrderRequest { payment_method: number.to_string() }
+    }
+
+    fn captured_total(fixture: &Fixture) -> i64 {
+        fixture
+            .payments
+            .payments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|p| p.is_settled())
+            .map(|p| p.captured_amount)
+            .sum()
+    }
+
+    fn order_status(fixture: &Fixture) -> OrderStatus {
+        fixture.orders.orders.lock().unwrap()[&1].status
+    }
+
+    #[tokio::test]
+    async fn pays_order_with_card() {
+        let f = fixture();
+
+        let payment = f.usecase.pay_order(7, 1, card(CARD_SUCCESS)).await.unwrap();
+
+        assert_eq!(payment.status, PaymentStatus::Captured.to_string());
+        assert_eq!(captured_total(&f), ORDER_TOTAL);
+        assert_eq!(order_status(&f), OrderStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn refuses_points_on_orders_outside_thb() {
+        let f = fixture();
+        f.orders.orders.lock().unwrap().get_mut(&1).unwrap().currency = Currency::new("USD").unwrap();
+
+        let err = f
+            .usecase
+            .pay_with_points(7, 1, RedeemPointsRequest { points: Some(100) })
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("THB"), "{err}");
+        assert!(f.payments.payments.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn refuses_new_attempt_while_one_is_authorized() {
+        let f = fixture();
+        let mut held = PaymentEntity::new(1, "mock".to_string(), ORDER_TOTAL, "THB".to_string()).unwrap();
+        held.attach_intent("pi_elsewhere".to_string());
+        held.authorize().unwrap();
+        f.payments.save(&held).await.unwrap();
+
+        let err = f.usecase.pay_order(7, 1, card(CARD_SUCCESS)).await.unwrap_err();
+
+        assert!(err.to_string().contains("still in progress"), "{}", err);
+        assert_eq!(f.payments.payments.lock().unwrap().len(), 1);
+        assert_eq!(captured_total(&f), 0);
+    }
+
+    #[tokio::test]
+    async fn voids_abandoned_3ds_attempt_before_charging_again() {
+        let f = fixture();
+        let first = f.usecase.pay_order(7, 1, card(CARD_REQUIRES_3DS)).await.unwrap();
+        assert_eq!(first.status, PaymentStatus::RequiresAction.to_string());
+        let first_intent = f.payments.find_by_id(first.id).await.unwrap().unwrap().provider_intent_id.unwrap();
+
+        f.usecase.pay_order(7, 1, card(CARD_SUCCESS)).await.unwrap();
+
+        // ลูกค้ากลับไปทำ 3DS ของ attempt แรกต่อไม่ได้แล้ว
+        assert!(f.gateway.complete_3ds(&first_intent, true).is_err());
+        let first = f.payments.find_by_id(first.id).await.unwrap().unwrap();
+        assert_eq!(first.status, PaymentStatus::Voided);
+        assert_eq!(captured_total(&f), ORDER_TOTAL);
+    }
+
+    #[tokio::test]
+    async fn redelivered_webhook_is_applied_once() {
+        let f = fixture();
+        let payment = f.usecase.pay_order(7, 1, card(CARD_REQUIRES_3DS)).await.unwrap();
+        let intent = f.payments.find_by_id(payment.id).await.unwrap().unwrap().provider_intent_id.unwrap();
+        let (payload, signature) = f.gateway.complete_3ds(&intent, true).unwrap();
+
+        f.usecase.handle_webhook(&payload, &signature).await.unwrap();
+        f.usecase.handle_webhook(&payload, &signature).await.unwrap();
+
+        assert_eq!(captured_total(&f), ORDER_TOTAL);
+        assert_eq!(order_status(&f), OrderStatus::Paid);
+        assert_eq!(f.payments.events.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn redelivered_webhook_finishes_order_after_failed_update() {
+        let f = fixture();
+        let payment = f.usecase.pay_order(7, 1, card(CARD_REQUIRES_3DS)).await.unwrap();
+        let intent = f.payments.find_by_id(payment.id).await.unwrap().unwrap().provider_intent_id.unwrap();
+        let (payload, signature) = f.gateway.complete_3ds(&intent, true).unwrap();
+
+        *f.orders.fail_next_paid.lock().unwrap() = true;
+        assert!(f.usecase.handle_webhook(&payload, &signature).await.is_err());
+        assert_eq!(captured_total(&f), ORDER_TOTAL);
+        assert_eq!(order_status(&f), OrderStatus::PendingPayment);
+
+        f.usecase.handle_webhook(&payload, &signature).await.unwrap();
+
+        assert_eq!(captured_total(&f), ORDER_TOTAL);
+        assert_eq!(order_status(&f), OrderStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn failed_webhook_can_be_redelivered() {
+        let f = fixture();
+        let event = WebhookEvent {
+            id: "evt_unknown".to_string(),
+            kind: WebhookEventKind::PaymentSucceeded,
+            intent_id: "pi_unknown".to_string(),
+            amount: ORDER_TOTAL,
+            failure_reason: None,
+        };
+        let payload = serde_json::to_vec(&event).unwrap();
+        let signature = f.gateway.sign_payload(&payload).unwrap();
+
+        assert!(f.usecase.handle_webhook(&payload, &signature).await.is_err());
+        assert!(f.payments.events.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::NaiveDate;
+use tracing::{info, warn};
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        order_dto::{CancelOrderRequest, OrderResponse},
+        preorder_dto::{
+            BookReleaseResponse, PreorderPriceCheckResponse, PreorderReleaseResponse,
+            SetReleaseRequest,
+        },
+    },
+    use_cases::{
+        order_usecase::OrderUseCase,
+        payment_usecase::PaymentUseCase,
+        pricing_usecase::PricingUseCase,
+    },
+};
+use crate::domain::{
+    entities::order::OrderEntity,
+    repositories::{book_repository::BookRepository, order_repository::OrderRepository},
+    value_objects::order_status::OrderStatus,
+};
+
+/// PreorderUseCase — announced titles, price-drop protection and the
+/// release-day job that captures held payments and hands orders to fulfilment.
+pub struct PreorderUseCase {
+    order_repo: Arc<dyn OrderRepository>,
+    book_repo: Arc<dyn BookRepository>,
+    pricing: Arc<PricingUseCase>,
+    payments: Arc<PaymentUseCase>,
+    orders: Arc<OrderUseCase>,
+}
+
+impl PreorderUseCase {
+    pub fn new(
+        order_repo: Arc<dyn OrderRepository>,
+        book_repo: Arc<dyn BookRepository>,
+        pricing: Arc<PricingUseCase>,
+        payments: Arc<PaymentUseCase>,
+        orders: Arc<OrderUseCase>,
+    ) -> Self {
+        Self {
+            order_repo,
+            book_repo,
+            pricing,
+            payments,
+            orders,
+        }
+    }
+
+    /// Staff: announce a release date and open (or close) pre-orders.
+    /// Waiting pre-orders follow the new date.
+    pub async fn set_release(
+        &self,
+        caller: &UserInfo,
+        book_id: i32,
+        req: SetReleaseRequest,
+    ) -> Result<BookReleaseResponse> {
+        ensure_staff(caller)?;
+
+        let mut book = self
+            .book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .ok_or_else(|| anyhow!("Book not found"))?;
+
+        book.change_release(req.release_date, req.preorder_enabled)
+            .map_err(|e| anyhow!("{}", e))?;
+        let book = self
+            .book_repo
+            .update(&book)
+            .await
+            .map_err(|e| anyhow!("Failed to update book: {}", e))?;
+
+        let moved = self
+            .order_repo
+            .reschedule_preorders(book.id)
+            .await
+            .map_err(|e| anyhow!("Failed to reschedule pre-orders: {}", e))?;
+        if moved > 0 {
+            info!(book_id = book.id, moved, "Pre-orders rescheduled");
+        }
+
+        Ok(BookReleaseResponse::from(book))
+    }
+
+    /// Records today's shelf prices against waiting pre-orders, so customers
+    /// pay the lowest price seen before release. Run at least daily.
+    pub async fn track_prices(&self) -> Result<PreorderPriceCheckResponse> {
+        let mut books_checked = 0;
+        let mut lines_lowered = 0;
+
+        // pre-order ถูกคุ้มครองด้วยราคาของร้านที่สั่ง
+        for store in self.pricing.active_stores().await? {
+            let book_ids = self
+                .order_repo
+                .find_preordered_book_ids(store.id)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch pre-ordered books: {}", e))?;
+            if book_ids.is_empty() {
+                continue;
+            }
+            let books = self
+                .book_repo
+                .find_by_ids(&book_ids)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch books: {}", e))?;
+
+            let prices = self
+                .pricing
+                .shelf_prices(&store, &books.iter().collect::<Vec<_>>())
+                .await?;
+
+            books_checked += prices.len();
+            for (book_id, price) in &prices {
+                lines_lowered += self
+                    .order_repo
+                    .record_preorder_price(store.id, *book_id, price.amount())
+                    .await
+                    .map_err(|e| anyhow!("Failed to record pre-order price: {}", e))?;
+            }
+        }
+
+        Ok(PreorderPriceCheckResponse {
+            books_checked,
+            lines_lowered,
+        })
+    }
+
+    /// Captures and releases up to `limit` pre-orders due on `today`.
+    /// Orders that fail stay pre-ordered and are retried on the next run.
+    pub async fn release_due(&self, today: NaiveDate, limit: i64) -> Result<PreorderReleaseResponse> {
+        // ราคาของวันวางขายนับเป็นราคาก่อนปล่อยด้วย
+        self.track_prices().await?;
+
+        let orders = self
+            .order_repo
+            .find_preorders_due(today, limit)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch due pre-orders: {}", e))?;
+
+        let mut summary = PreorderReleaseResponse {
+            released: 0,
+            failed: 0,
+            price_protection_total: 0,
+        };
+        for order in orders {
+            let order_id = order.id;
+            match self.release(order).await {
+                Ok(saved) => {
+                    summary.released += 1;
+                    summary.price_protection_total += saved;
+                }
+                Err(e) => {
+                    warn!(order_id, "Pre-order release failed: {}", e);
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Cancels a waiting pre-order; its card authorization is voided
+    pub async fn cancel_preorder(
+        &self,
+        caller: &UserInfo,
+        order_id: i32,
+        req: CancelOrderRequest,
+    ) -> Result<OrderResponse> {
+        let order = self
+            .order_repo
+            .find_by_id(order_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch order: {}", e))?
+            .ok_or_else(|| anyhow!("Order not found"))?;
+        if order.user_id != caller.id {
+            ensure_staff(caller).map_err(|_| anyhow!("Order not found"))?;
+        }
+        if order.status != OrderStatus::Preordered {
+            return Err(anyhow!("Order is not a pre-order awaiting release"));
+        }
+
+        self.orders.cancel_and_release(order, caller.id, req.reason).await
+    }
+
+    /// Returns the amount saved through price-drop protection
+    async fn release(&self, mut order: OrderEntity) -> Result<i64> {
+        let saved = order
+            .apply_price_protection()
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.payments.capture_for_release(&order).await?;
+
+        order.mark_paid(None).map_err(|e| anyhow!("{}", e))?;
+        self.order_repo
+            .update_paid(&order)
+            .await
+            .map_err(|e| anyhow!("Failed to release pre-order: {}", e))?;
+
+        Ok(saved)
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        pricing_dto::{BookPriceResponse, PriceQuoteResponse, SetBookPriceRequest, StoreResponse},
+    },
+};
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        book_price::BookPriceEntity,
+        store::StoreEntity,
+        tax_rate::TaxRateEntity,
+    },
+    repositories::{
+        book_price_repository::BookPriceRepository,
+        book_repository::BookRepository,
+        store_repository::StoreRepository,
+        tax_rate_repository::TaxRateRepository,
+    },
+    services::tax_engine::{self, TaxMode},
+    value_objects::money::{Currency, Money},
+};
+
+/// PricingUseCase — store prices per currency/store and tax by jurisdiction
+pub struct PricingUseCase {
+    book_repo: Arc<dyn BookRepository>,
+    store_repo: Arc<dyn StoreRepository>,
+    book_price_repo: Arc<dyn BookPriceRepository>,
+    tax_rate_repo: Arc<dyn TaxRateRepository>,
+}
+
+impl PricingUseCase {
+    pub fn new(
+        book_repo: Arc<dyn BookRepository>,
+        store_repo: Arc<dyn StoreRepository>,
+        book_price_repo: Arc<dyn BookPriceRepository>,
+        tax_rate_repo: Arc<dyn TaxRateRepository>,
+    ) -> Self {
+        Self {
+            book_repo,
+            store_repo,
+            book_price_repo,
+            tax_rate_repo,
+        }
+    }
+
+    pub async fn get_stores(&self) -> Result<Vec<StoreResponse>> {
+        Ok(self
+            .active_stores()
+            .await?
+            .into_iter()
+            .map(StoreResponse::from)
+            .collect())
+    }
+
+    /// The store with `code`, or the default store
+    pub async fn find_store(&self, code: Option<&str>) -> Result<StoreEntity> {
+        let store = match code {
+            Some(code) => self.store_repo.find_by_code(code).await,
+            None => self.store_repo.find_default().await,
+        }
+        .map_err(|e| anyhow!("Database error while fetching store: {}", e))?;
+
+        store
+            .filter(|s| s.is_active)
+            .ok_or_else(|| anyhow!("Store not found"))
+    }
+
+    /// The store a cart or order sells from
+    pub async fn store_by_id(&self, id: i32) -> Result<StoreEntity> {
+        self.store_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching store: {}", e))?
+            .filter(|s| s.is_active)
+            .ok_or_else(|| anyhow!("Store not found"))
+    }
+
+    pub async fn active_stores(&self) -> Result<Vec<StoreEntity>> {
+        let stores = self.store_repo.find_all().await.map_err(|e| {
+            anyhow!("Failed to fetch stores: {}", e)
+        })?;
+
+        Ok(stores.into_iter().filter(|s| s.is_active).collect())
+    }
+
+    /// Shelf price of each book in the store. Books without a price in the
+    /// store's currency are left out (not sold there).
+    pub async fn shelf_prices(
+        &self,
+        store: &StoreEntity,
+        books: &[&BookEntity],
+    ) -> Result<HashMap<i32, Money>> {
+        let book_ids: Vec<i32> = books.iter().map(|b| b.id).collect();
+        let overrides = self
+            .book_price_repo
+            .find_by_books(&book_ids)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch book prices: {}", e))?;
+
+        Ok(books
+            .iter()
+            .filter_map(|book| Some((book.id, Self::resolve_price(book, &overrides, store)?)))
+            .collect())
+    }
+
+    /// Rates that can apply in the store's jurisdiction
+    pub async fn tax_rates(&self, store: &StoreEntity) -> Result<Vec<TaxRateEntity>> {
+        self.tax_rate_repo
+            .find_by_jurisdiction(&store.jurisdiction)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch tax rates: {}", e))
+    }
+
+    /// Price of a book in a store, with tax split out for display
+    pub async fn quote_book(&self, book_id: i32, store_code: Option<&str>) -> Result<PriceQuoteResponse> {
+        let book = self
+            .book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .filter(|b| b.is_active)
+            .ok_or_else(|| anyhow!("Book not found"))?;
+        let store = self.find_store(store_code).await?;
+
+        let price = self
+            .shelf_prices(&store, &[&book])
+            .await?
+            .remove(&book.id)
+            .ok_or_else(|| anyhow!("Book is not sold in store {}", store.code))?;
+
+        let rates = self.tax_rates(&store).await?;
+        let rate_bps = tax_engine::find_rate(&rates, &store.jurisdiction, book.tax_class, Utc::now())
+            .map(|r| r.rate_bps)
+            .unwrap_or(0);
+        let mode = if store.prices_include_tax {
+            TaxMode::Inclusive
+        } else {
+            TaxMode::Exclusive
+        };
+        let breakdown = tax_engine::calculate(&price, rate_bps, mode)?;
+        let display = if store.prices_include_tax {
+            &breakdown.gross
+        } else {
+            &breakdown.net
+        };
+
+        Ok(PriceQuoteResponse {
+            book_id: book.id,
+            store_code: store.code.clone(),
+            currency: store.currency.as_str().to_string(),
+            tax_class: book.tax_class.as_str().to_string(),
+            tax_rate_bps: rate_bps,
+            net: breakdown.net.amount(),
+            tax: breakdown.tax.amount(),
+            gross: breakdown.gross.amount(),
+            display_amount: display.amount(),
+            display_includes_tax: store.prices_include_tax,
+            formatted: display.to_string(),
+        })
+    }
+
+    pub async fn set_book_price(
+        &self,
+        caller: &UserInfo,
+        book_id: i32,
+        req: SetBookPriceRequest,
+    ) -> Result<BookPriceResponse> {
+        ensure_staff(caller)?;
+
+        let currency = Currency::new(&req.currency)?;
+        let store_id = match req.store_code.as_deref() {
+            Some(code) => {
+                let store = self.find_store(Some(code)).await?;
+                if store.currency != currency {
+                    return Err(anyhow!(
+                        "Store {} sells in {}, not {}",
+                        store.code,
+                        store.currency,
+                        currency
+                    ));
+                }
+                Some(store.id)
+            }
+            None => None,
+        };
+
+        if self
+            .book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .is_none()
+        {
+            return Err(anyhow!("Book not found"));
+        }
+
+        let price = BookPriceEntity::new(book_id, store_id, Money::parse(&req.amount, currency)?)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let saved = self
+            .book_price_repo
+            .upsert(&price)
+            .await
+            .map_err(|e| anyhow!("Failed to save book price: {}", e))?;
+
+        Ok(BookPriceResponse::from(saved))
+    }
+
+    /// Store price → currency price → base price (if it is in the store's currency)
+    fn resolve_price(
+        book: &BookEntity,
+        overrides: &[BookPriceEntity],
+        store: &StoreEntity,
+    ) -> Option<Money> {
+        let for_book = || overrides.iter().filter(|p| p.book_id == book.id);
+
+        for_book()
+            .find(|p| p.store_id == Some(store.id))
+            .or_else(|| {
+                for_book().find(|p| p.store_id.is_none() && p.price.currency() == &store.currency)
+            })
+            .map(|p| p.price.clone())
+            .or_else(|| (book.price.currency() == &store.currency).then(|| book.price.clone()))
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        promotion_dto::{CouponResponse, CreateCouponRequest, CreatePromotionRequest, PromotionResponse},
+    },
+};
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        cart::{CartEntity, CartOwner},
+        coupon::CouponEntity,
+        promotion::PromotionEntity,
+    },
+    repositories::{
+        coupon_repository::CouponRepository,
+        promotion_repository::PromotionRepository,
+        user_repository::UserRepository,
+    },
+    services::promotion_evaluator::{self, PricingContext, PricingLine, PricingResult},
+};
+
+/// Outcome of pricing a cart with promotions.
+#[derive(Debug, Clone)]
+pub struct CartPricing {
+    pub pricing: PricingResult,
+    /// Why the cart's coupon was not applied, if it wasn't
+    pub coupon_error: Option<String>,
+}
+
+/// PromotionUseCase — manages promotions/coupons and prices carts with them
+pub struct PromotionUseCase {
+    promotion_repo: Arc<dyn PromotionRepository>,
+    coupon_repo: Arc<dyn CouponRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl PromotionUseCase {
+    pub fn new(
+        promotion_repo: Arc<dyn PromotionRepository>,
+        coupon_repo: Arc<dyn CouponRepository>,
+        user_repo: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            promotion_repo,
+            coupon_repo,
+            user_repo,
+        }
+    }
+
+    pub async fn create_promotion(
+        &self,
+        caller: &UserInfo,
+        req: CreatePromotionRequest,
+    ) -> Result<PromotionResponse> {
+        ensure_staff(caller)?;
+
+        let mut promotion = PromotionEntity::new(
+            req.name,
+            req.conditions,
+            req.action,
+            req.priority.unwrap_or(0),
+            req.stackable.unwrap_or(true),
+            req.requires_coupon.unwrap_or(false),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        promotion.id = self
+            .promotion_repo
+            .save(&promotion)
+            .await
+            .map_err(|e| anyhow!("Failed to save promotion: {}", e))?;
+
+        Ok(PromotionResponse::from(promotion))
+    }
+
+    pub async fn get_all_promotions(&self, caller: &UserInfo) -> Result<Vec<PromotionResponse>> {
+        ensure_staff(caller)?;
+
+        let promotions = self.promotion_repo.find_all().await.map_err(|e| {
+            anyhow!("Failed to fetch promotions: {}", e)
+        })?;
+
+        Ok(promotions.into_iter().map(PromotionResponse::from).collect())
+    }
+
+    pub async fn set_promotion_active(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        active: bool,
+    ) -> Result<PromotionResponse> {
+        ensure_staff(caller)?;
+        let mut promotion = self.find_promotion(id).await?;
+
+        if active {
+            promotion.activate();
+        } else {
+            promotion.deactivate();
+        }
+
+        let updated = self
+            .promotion_repo
+            .update(&promotion)
+            .await
+            .map_err(|e| anyhow!("Failed to update promotion: {}", e))?;
+
+        Ok(PromotionResponse::from(updated))
+    }
+
+    pub async fn create_coupon(
+        &self,
+        caller: &UserInfo,
+        promotion_id: i32,
+        req: CreateCouponRequest,
+    ) -> Result<CouponResponse> {
+        ensure_staff(caller)?;
+
+        let promotion = self.find_promotion(promotion_id).await?;
+        if !promotion.requires_coupon {
+            return Err(anyhow!("Promotion '{}' does not use coupon codes", promotion.name));
+        }
+
+        let mut coupon = CouponEntity::new(
+            req.code,
+            promotion.id,
+            req.usage_limit,
+            req.per_customer_limit,
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        if self
+            .coupon_repo
+            .find_by_code(coupon.code.as_str())
+            .await
+            .map_err(|e| anyhow!("Database error while checking coupon: {}", e))?
+            .is_some()
+        {
+            return Err(anyhow!("Coupon code {} already exists", coupon.code));
+        }
+
+        coupon.id = self
+            .coupon_repo
+            .save(&coupon)
+            .await
+            .map_err(|e| anyhow!("Failed to save coupon: {}", e))?;
+
+        Ok(CouponResponse::from(coupon))
+    }
+
+    pub async fn get_promotion_coupons(
+        &self,
+        caller: &UserInfo,
+        promotion_id: i32,
+    ) -> Result<Vec<CouponResponse>> {
+        ensure_staff(caller)?;
+
+        let coupons = self.coupon_repo.find_by_promotion(promotion_id).await.map_err(|e| {
+            anyhow!("Failed to fetch coupons: {}", e)
+        })?;
+
+        Ok(coupons.into_iter().map(CouponResponse::from).collect())
+    }
+
+    /// Runs the promotion evaluator over a cart. `books` must contain the
+    /// cart's books; lines whose book is missing are left out.
+    pub async fn price_cart(
+        &self,
+        cart: &CartEntity,
+        books: &HashMap<i32, BookEntity>,
+    ) -> Result<CartPricing> {
+        let lines: Vec<PricingLine> = cart
+            .items
+            .iter()
+            .filter_map(|item| {
+                let book = books.get(&item.book_id)?;
+                Some(PricingLine {
+                    book_id: item.book_id,
+                    category: book.category.clone(),
+                    author: book.author.clone(),
+                    quantity: item.quantity.value(),
+                    unit_price: item.unit_price,
+                })
+            })
+            .collect();
+
+        let user_id = match cart.owner {
+            CartOwner::User(id) => Some(id),
+            CartOwner::Guest(_) => None,
+        };
+
+        let customer_roles = match user_id {
+            Some(id) => self
+                .user_repo
+                .find_roles(id)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch user roles: {}", e))?
+                .iter()
+                .map(|r| r.name.as_str().to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        // 1. Validate the coupon (per-customer limit is only known for logged-in users)
+        let (coupon, mut coupon_error) = match &cart.coupon_code {
+            Some(code) => match self.find_redeemable_coupon(code.as_str(), user_id).await? {
+                Ok(coupon) => (Some(coupon), None),
+                Err(reason) => (None, Some(reason)),
+            },
+            None => (None, None),
+        };
+
+        // 2. Evaluate
+        let promotions = self.promotion_repo.find_active().await.map_err(|e| {
+            anyhow!("Failed to fetch promotions: {}", e)
+        })?;
+
+        let ctx = PricingContext {
+            lines,
+            customer_roles,
+            now: Utc::now(),
+        };
+        let pricing = promotion_evaluator::evaluate(&ctx, &promotions, coupon.as_ref());
+
+        if let Some(coupon) = &coupon
+            && !pricing.applied.iter().any(|a| a.coupon_id == Some(coupon.id))
+        {
+            coupon_error = Some(format!("Coupon {} does not apply to this cart", coupon.code));
+        }
+
+        Ok(CartPricing {
+            pricing,
+            coupon_error,
+        })
+    }
+
+    /// Outer error = infrastructure failure, inner error = why the coupon cannot be used.
+    async fn find_redeemable_coupon(
+        &self,
+        code: &str,
+        user_id: Option<i32>,
+    ) -> Result<std::result::Result<CouponEntity, String>> {
+        let coupon = match self
+            .coupon_repo
+            .find_by_code(code)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching coupon: {}", e))?
+        {
+            Some(c) => c,
+            None => return Ok(Err(format!("Coupon {} not found", code))),
+        };
+
+        let used_by_customer = match user_id {
+            Some(id) => self
+                .coupon_repo
+                .count_redemptions(coupon.id, id)
+                .await
+                .map_err(|e| anyhow!("Failed to count coupon redemptions: {}", e))?,
+            None => 0,
+        };
+
+        Ok(coupon
+            .ensure_redeemable(used_by_customer)
+            .map(|_| coupon)
+            .map_err(|e| e.to_string()))
+    }
+
+    async fn find_promotion(&self, id: i32) -> Result<PromotionEntity> {
+        match self
+            .promotion_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch promotion: {}", e))?
+        {
+            Some(p) => Ok(p),
+            None => Err(anyhow!("Promotion not found")),
+        }
+    }
+}
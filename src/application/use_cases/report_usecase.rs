@@ -0,0 +1,398 @@
+use std::{
+    io::{Seek, Write},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Utc};
+
+use crate::application::{
+    authorization::ensure_reports,
+    dtos::{
+        auth_dto::UserInfo,
+        report_dto::{
+            CustomerSalesResponse, ReportExportQuery, ReportRefreshResponse,
+            RevenuePeriodResponse, RevenueReportResponse, SalesReportQuery,
+            SalesSummaryResponse, SalesTotalsResponse, TopCustomersResponse,
+            UnitsSoldReportResponse, UnitsSoldResponse,
+        },
+    },
+};
+use crate::domain::{
+    entities::invoice::DOCUMENT_UTC_OFFSET_SECS,
+    repositories::{
+        report_repository::{
+            CustomerSales, ReportGranularity, ReportRepository, SalesDimension, SalesFilter,
+            SalesTotals, UnitsSold,
+        },
+        store_repository::StoreRepository,
+    },
+    value_objects::money::{Currency, Money, Rounding},
+};
+use crate::infrastructure::spreadsheet::{Cell, SheetFormat, SheetWriter};
+
+/// Days covered when the query gives no `from`
+const DEFAULT_RANGE_DAYS: i64 = 30;
+/// Longest range one report may cover (about three years of daily rows)
+const MAX_RANGE_DAYS: i64 = 1096;
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 500;
+
+const REVENUE_EXPORT_HEADER: &[&str] = &[
+    "period_start", "currency", "orders", "units", "revenue", "discount_total",
+    "shipping_total", "tax_total", "refunded_orders", "refunded_amount", "net_revenue",
+    "average_order_value", "refund_rate",
+];
+const CUSTOMER_EXPORT_HEADER: &[&str] = &[
+    "user_id", "email", "first_name", "last_name", "currency", "orders", "revenue",
+    "average_order_value",
+];
+
+/// ReportUseCase — sales reports for management: revenue over time, units by
+/// title/author/category/store, average order value, refund rate and top
+/// customers. Reads the materialized views kept fresh by the `reports` job,
+/// so figures lag orders by up to one refresh.
+pub struct ReportUseCase {
+    report_repo: Arc<dyn ReportRepository>,
+    store_repo: Arc<dyn StoreRepository>,
+}
+
+impl ReportUseCase {
+    pub fn new(report_repo: Arc<dyn ReportRepository>, store_repo: Arc<dyn StoreRepository>) -> Self {
+        Self {
+            report_repo,
+            store_repo,
+        }
+    }
+
+    /// Totals, average order value and refund rate for the period
+    pub async fn sales_summary(&self, caller: &UserInfo, query: SalesReportQuery) -> Result<SalesSummaryResponse> {
+        ensure_reports(caller)?;
+        let filter = self.filter(&query).await?;
+
+        let totals = self
+            .report_repo
+            .sales_totals(&filter)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch sales totals: {}", e))?;
+
+        Ok(SalesSummaryResponse {
+            from: filter.from,
+            to: filter.to,
+            store: query.store,
+            refreshed_at: self.refreshed_at().await?,
+            totals: totals.into_iter().map(totals_response).collect(),
+        })
+    }
+
+    /// Daily, weekly or monthly revenue
+    pub async fn revenue(&self, caller: &UserInfo, query: SalesReportQuery) -> Result<RevenueReportResponse> {
+        ensure_reports(caller)?;
+        let filter = self.filter(&query).await?;
+        let granularity = granularity(&query)?;
+
+        let periods = self
+            .report_repo
+            .revenue_series(&filter, granularity)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch revenue: {}", e))?;
+
+        Ok(RevenueReportResponse {
+            from: filter.from,
+            to: filter.to,
+            store: query.store,
+            granularity: granularity.as_str().to_string(),
+            refreshed_at: self.refreshed_at().await?,
+            periods: periods
+                .into_iter()
+                .map(|p| RevenuePeriodResponse {
+                    period_start: p.period_start,
+                    totals: totals_response(p.totals),
+                })
+                .collect(),
+        })
+    }
+
+    /// Best sellers by title, author, category or store
+    pub async fn units_sold(
+        &self,
+        caller: &UserInfo,
+        by: &str,
+        query: SalesReportQuery,
+    ) -> Result<UnitsSoldReportResponse> {
+        ensure_reports(caller)?;
+        let filter = self.filter(&query).await?;
+        let dimension = by.parse::<SalesDimension>()?;
+
+        let rows = self
+            .report_repo
+            .units_sold(&filter, dimension, Some(limit(&query)?))
+            .await
+            .map_err(|e| anyhow!("Failed to fetch units sold: {}", e))?;
+
+        Ok(UnitsSoldReportResponse {
+            from: filter.from,
+            to: filter.to,
+            store: query.store,
+            by: dimension.as_str().to_string(),
+            refreshed_at: self.refreshed_at().await?,
+            rows: rows.into_iter().map(units_response).collect(),
+        })
+    }
+
+    /// Customers who spent the most in the period
+    pub async fn top_customers(&self, caller: &UserInfo, query: SalesReportQuery) -> Result<TopCustomersResponse> {
+        ensure_reports(caller)?;
+        let filter = self.filter(&query).await?;
+
+        let customers = self
+            .report_repo
+            .top_customers(&filter, Some(limit(&query)?))
+            .await
+            .map_err(|e| anyhow!("Failed to fetch top customers: {}", e))?;
+
+        Ok(TopCustomersResponse {
+            from: filter.from,
+            to: filter.to,
+            store: query.store,
+            refreshed_at: self.refreshed_at().await?,
+            customers: customers.into_iter().map(customer_response).collect(),
+        })
+    }
+
+    /// Writes one report as CSV (or XLSX). Ranked reports export every row
+    /// unless the query sets a limit.
+    pub async fn export<W: Write + Seek + Send>(
+        &self,
+        caller: &UserInfo,
+        export: ReportExportQuery,
+        query: SalesReportQuery,
+        output: W,
+    ) -> Result<W> {
+        ensure_reports(caller)?;
+        let filter = self.filter(&query).await?;
+        let format = match export.format.as_deref() {
+            Some(format) => format.parse()?,
+            None => SheetFormat::Csv,
+        };
+        let limit = query.limit.map(|_| limit(&query)).transpose()?;
+
+        let mut writer = SheetWriter::new(format, output)?;
+        match export.report.as_str() {
+            "revenue" => {
+                let periods = self
+                    .report_repo
+                    .revenue_series(&filter, granularity(&query)?)
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch revenue: {}", e))?;
+
+                writer.write_row(&header_cells(REVENUE_EXPORT_HEADER))?;
+                for period in periods {
+                    let currency = period.totals.currency.clone();
+                    let mut row = vec![Cell::Text(period.period_start.to_string())];
+                    row.extend(totals_cells(&totals_response(period.totals), &currency));
+                    writer.write_row(&row)?;
+                }
+            }
+            "units" => {
+                let dimension = export.by.as_deref().unwrap_or("title").parse::<SalesDimension>()?;
+                let rows = self
+                    .report_repo
+                    .units_sold(&filter, dimension, limit)
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch units sold: {}", e))?;
+
+                writer.write_row(&header_cells(&[
+                    dimension.as_str(),
+                    "book_id",
+                    "currency",
+                    "units",
+                    "revenue",
+                ]))?;
+                for row in rows {
+                    writer.write_row(&[
+                        row.key.clone().map_or(Cell::Empty, Cell::Text),
+                        row.book_id.map_or(Cell::Empty, |id| Cell::Number(id.to_string())),
+                        Cell::Text(row.currency.as_str().to_string()),
+                        Cell::Number(row.units.to_string()),
+                        Cell::Number(major_units(row.revenue, &row.currency)),
+                    ])?;
+                }
+            }
+            "customers" => {
+                let customers = self
+                    .report_repo
+                    .top_customers(&filter, limit)
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch top customers: {}", e))?;
+
+                writer.write_row(&header_cells(CUSTOMER_EXPORT_HEADER))?;
+                for customer in customers {
+                    let currency = customer.currency.clone();
+                    let customer = customer_response(customer);
+                    writer.write_row(&[
+                        Cell::Number(customer.user_id.to_string()),
+                        Cell::Text(customer.email),
+                        Cell::Text(customer.first_name),
+                        Cell::Text(customer.last_name),
+                        Cell::Text(customer.currency),
+                        Cell::Number(customer.orders.to_string()),
+                        Cell::Number(major_units(customer.revenue, &currency)),
+                        Cell::Number(major_units(customer.average_order_value, &currency)),
+                    ])?;
+                }
+            }
+            other => return Err(anyhow!("Invalid report: {}", other)),
+        }
+
+        writer.finish()
+    }
+
+    /// Rebuilds the report views. Meant for a scheduled job.
+    pub async fn refresh(&self) -> Result<ReportRefreshResponse> {
+        let refreshed_at = self
+            .report_repo
+            .refresh()
+            .await
+            .map_err(|e| anyhow!("Failed to refresh reports: {}", e))?;
+
+        Ok(ReportRefreshResponse { refreshed_at })
+    }
+
+    async fn refreshed_at(&self) -> Result<Option<DateTime<Utc>>> {
+        self.report_repo
+            .refreshed_at()
+            .await
+            .map_err(|e| anyhow!("Failed to fetch report freshness: {}", e))
+    }
+
+    /// Date range and store of the query, checked
+    async fn filter(&self, query: &SalesReportQuery) -> Result<SalesFilter> {
+        let to = query.to.unwrap_or_else(today);
+        let from = query
+            .from
+            .unwrap_or_else(|| to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+        if from > to {
+            return Err(anyhow!("'from' must not be after 'to'"));
+        }
+        if (to - from).num_days() >= MAX_RANGE_DAYS {
+            return Err(anyhow!("Reports cover at most {} days", MAX_RANGE_DAYS));
+        }
+
+        let store_id = match query.store.as_deref() {
+            Some(code) => Some(
+                self.store_repo
+                    .find_by_code(code)
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch store: {}", e))?
+                    .ok_or_else(|| anyhow!("Store not found"))?
+                    .id,
+            ),
+            None => None,
+        };
+
+        Ok(SalesFilter { from, to, store_id })
+    }
+}
+
+/// Today in Thai time; sales days follow the same clock as invoice dates
+fn today() -> NaiveDate {
+    let offset = FixedOffset::east_opt(DOCUMENT_UTC_OFFSET_SECS).expect("Valid UTC offset");
+    Utc::now().with_timezone(&offset).date_naive()
+}
+
+fn granularity(query: &SalesReportQuery) -> Result<ReportGranularity> {
+    query
+        .granularity
+        .as_deref()
+        .map_or(Ok(ReportGranularity::default()), str::parse)
+}
+
+fn limit(query: &SalesReportQuery) -> Result<i64> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(anyhow!("Limit must be between 1 and {}", MAX_LIMIT));
+    }
+    Ok(limit)
+}
+
+fn average(amount: i64, count: i64) -> i64 {
+    if count == 0 {
+        return 0;
+    }
+    Rounding::HalfUp.divide(amount as i128, count as i128) as i64
+}
+
+fn totals_response(totals: SalesTotals) -> SalesTotalsResponse {
+    SalesTotalsResponse {
+        currency: totals.currency.as_str().to_string(),
+        orders: totals.orders,
+        units: totals.units,
+        revenue: totals.revenue,
+        discount_total: totals.discount_total,
+        shipping_total: totals.shipping_total,
+        tax_total: totals.tax_total,
+        refunded_orders: totals.refunded_orders,
+        refunded_amount: totals.refunded_amount,
+        net_revenue: totals.revenue - totals.refunded_amount,
+        average_order_value: average(totals.revenue, totals.orders),
+        refund_rate: if totals.orders == 0 {
+            0.0
+        } else {
+            totals.refunded_orders as f64 / totals.orders as f64
+        },
+    }
+}
+
+fn units_response(row: UnitsSold) -> UnitsSoldResponse {
+    UnitsSoldResponse {
+        key: row.key,
+        book_id: row.book_id,
+        currency: row.currency.as_str().to_string(),
+        units: row.units,
+        revenue: row.revenue,
+    }
+}
+
+fn customer_response(customer: CustomerSales) -> CustomerSalesResponse {
+    CustomerSalesResponse {
+        user_id: customer.user_id,
+        email: customer.email,
+        first_name: customer.first_name,
+        last_name: customer.last_name,
+        currency: customer.currency.as_str().to_string(),
+        orders: customer.orders,
+        revenue: customer.revenue,
+        average_order_value: average(customer.revenue, customer.orders),
+    }
+}
+
+/// Revenue columns of `REVENUE_EXPORT_HEADER`, after `period_start`
+fn totals_cells(totals: &SalesTotalsResponse, currency: &Currency) -> Vec<Cell> {
+    vec![
+        Cell::Text(totals.currency.clone()),
+        Cell::Number(totals.orders.to_string()),
+        Cell::Number(totals.units.to_string()),
+        Cell::Number(major_units(totals.revenue, currency)),
+        Cell::Number(major_units(totals.discount_total, currency)),
+        Cell::Number(major_units(totals.shipping_total, currency)),
+        Cell::Number(major_units(totals.tax_total, currency)),
+        Cell::Number(totals.refunded_orders.to_string()),
+        Cell::Number(major_units(totals.refunded_amount, currency)),
+        Cell::Number(major_units(totals.net_revenue, currency)),
+        Cell::Number(major_units(totals.average_order_value, currency)),
+        Cell::Number(format!("{:.4}", totals.refund_rate)),
+    ]
+}
+
+fn header_cells(header: &[&str]) -> Vec<Cell> {
+    header.iter().map(|h| Cell::Text(h.to_string())).collect()
+}
+
+/// `1290.00` for 1290.00 THB
+fn major_units(amount: i64, currency: &Currency) -> String {
+    let formatted = Money::new(amount, currency.clone()).to_string();
+    formatted
+        .rsplit_once(' ')
+        .map_or(formatted.clone(), |(amount, _)| amount.to_string())
+}
@@ -0,0 +1,567 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use tracing::warn;
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        return_dto::{
+            CreateReturnRequest, ReceiveReturnRequest, RefundReturnRequest, ReturnResponse,
+            ReviewReturnRequest,
+        },
+    },
+};
+use crate::domain::{
+    entities::{
+        inventory_movement::{InventoryMovementEntity, MovementReason},
+        loyalty_entry::{points_to_return, LoyaltyEntryEntity, PointsReason, LOYALTY_PAYMENT_PROVIDER},
+        order::OrderEntity,
+        payment::PaymentEntity,
+        return_request::{ReturnRefundEntity, ReturnRequestEntity},
+        stored_value_account::StoredValueKind,
+        stored_value_entry::{EntryReason, StoredValueEntryEntity},
+    },
+    repositories::{
+        stock_subscription_repository::StockSubscriptionRepository,
+        loyalty_repository::LoyaltyRepository,
+        order_repository::OrderRepository,
+        payment_repository::PaymentRepository,
+        return_repository::{RefundCredit, ReturnRepository},
+        stored_value_repository::StoredValueRepository,
+    },
+    value_objects::{
+        order_status::OrderStatus,
+        return_status::{ReturnCondition, ReturnStatus},
+        stock_bucket::StockBucket,
+    },
+};
+use crate::application::use_cases::invoice_usecase::InvoiceUseCase;
+use crate::infrastructure::payment_gateway::PaymentGateway;
+
+/// ReturnUseCase — RMA: request → approved/rejected → received → refunding → refunded
+pub struct ReturnUseCase {
+    return_repo: Arc<dyn ReturnRepository>,
+    order_repo: Arc<dyn OrderRepository>,
+    subscription_repo: Arc<dyn StockSubscriptionRepository>,
+    payment_repo: Arc<dyn PaymentRepository>,
+    stored_value_repo: Arc<dyn StoredValueRepository>,
+    loyalty_repo: Arc<dyn LoyaltyRepository>,
+    gateway: Arc<dyn PaymentGateway>,
+    invoices: Arc<InvoiceUseCase>,
+}
+
+impl ReturnUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        return_repo: Arc<dyn ReturnRepository>,
+        order_repo: Arc<dyn OrderRepository>,
+        subscription_repo: Arc<dyn StockSubscriptionRepository>,
+        payment_repo: Arc<dyn PaymentRepository>,
+        stored_value_repo: Arc<dyn StoredValueRepository>,
+        loyalty_repo: Arc<dyn LoyaltyRepository>,
+        gateway: Arc<dyn PaymentGateway>,
+        invoices: Arc<InvoiceUseCase>,
+    ) -> Self {
+        Self {
+            return_repo,
+            order_repo,
+            subscription_repo,
+            payment_repo,
+            stored_value_repo,
+            loyalty_repo,
+            gateway,
+            invoices,
+        }
+    }
+
+    /// Customer asks to return copies from a delivered order line
+    pub async fn request_return(
+        &self,
+        caller: &UserInfo,
+        order_id: i32,
+        req: CreateReturnRequest,
+    ) -> Result<ReturnResponse> {
+        let order = self.find_order(order_id).await?;
+        if order.user_id != caller.id {
+            return Err(anyhow!("Order not found"));
+        }
+        if order.status != OrderStatus::Delivered {
+            return Err(anyhow!("Only delivered orders can be returned"));
+        }
+
+        let item = order
+            .items
+            .iter()
+            .find(|i| i.id == req.order_item_id)
+            .ok_or_else(|| anyhow!("Order item not found"))?;
+
+        // จำนวนที่ขอคืนรวมกับคำขอก่อนหน้า (ที่ไม่ถูกปฏิเสธ) ต้องไม่เกินจำนวนที่ซื้อ
+        let already_returned: i32 = self
+            .return_repo
+            .find_by_order(order.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch returns: {}", e))?
+            .iter()
+            .filter(|r| r.order_item_id == item.id && r.counts_against_line())
+            .map(|r| r.quantity.value())
+            .sum();
+
+        if req.quantity + already_returned > item.quantity.value() {
+            return Err(anyhow!(
+                "Only {} item(s) left to return on this line",
+                item.quantity.value() - already_returned
+            ));
+        }
+
+        let mut request = ReturnRequestEntity::new(
+            order.id,
+            item.id,
+            item.book_id,
+            caller.id,
+            req.quantity,
+            req.reason,
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        request.id = self
+            .return_repo
+            .save(&request)
+            .await
+            .map_err(|e| anyhow!("Failed to save return request: {}", e))?;
+
+        Ok(ReturnResponse::from(request))
+    }
+
+    pub async fn get_return(&self, caller: &UserInfo, id: i32) -> Result<ReturnResponse> {
+        let request = self.find_return(id).await?;
+        if request.user_id != caller.id {
+            ensure_staff(caller).map_err(|_| anyhow!("Return request not found"))?;
+        }
+
+        Ok(ReturnResponse::from(request))
+    }
+
+    pub async fn get_user_returns(&self, caller: &UserInfo) -> Result<Vec<ReturnResponse>> {
+        let requests = self.return_repo.find_by_user(caller.id).await.map_err(|e| {
+            anyhow!("Failed to fetch returns: {}", e)
+        })?;
+
+        Ok(requests.into_iter().map(ReturnResponse::from).collect())
+    }
+
+    pub async fn get_order_returns(&self, caller: &UserInfo, order_id: i32) -> Result<Vec<ReturnResponse>> {
+        ensure_staff(caller)?;
+
+        let requests = self.return_repo.find_by_order(order_id).await.map_err(|e| {
+            anyhow!("Failed to fetch returns: {}", e)
+        })?;
+
+        Ok(requests.into_iter().map(ReturnResponse::from).collect())
+    }
+
+    pub async fn approve_return(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        req: ReviewReturnRequest,
+    ) -> Result<ReturnResponse> {
+        ensure_staff(caller)?;
+        let mut request = self.find_return(id).await?;
+
+        request.approve(caller.id, req.note)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.save_return(request).await
+    }
+
+    pub async fn reject_return(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        req: ReviewReturnRequest,
+    ) -> Result<ReturnResponse> {
+        ensure_staff(caller)?;
+        let mut request = self.find_return(id).await?;
+
+        request.reject(caller.id, req.note)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.save_return(request).await
+    }
+
+    /// Parcel arrived: restock resellable copies, write damaged ones off
+    pub async fn receive_return(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        req: ReceiveReturnRequest,
+    ) -> Result<ReturnResponse> {
+        ensure_staff(caller)?;
+        let mut request = self.find_return(id).await?;
+        let condition: ReturnCondition = req.condition.parse()?;
+
+        request.receive(condition)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let (bucket, reason) = match condition {
+            ReturnCondition::Resellable => (StockBucket::Available, MovementReason::ReturnRestock),
+            ReturnCondition::Damaged => (StockBucket::Damaged, MovementReason::ReturnWriteOff),
+        };
+        let movement = InventoryMovementEntity::new(
+            request.book_id,
+            bucket,
+            request.quantity.value(),
+            reason,
+            Some(format!("return:{}", request.id)),
+            Some(caller.id),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        // สถานะกับ stock บันทึกพร้อมกัน ล้มแล้วกดรับซ้ำได้
+        let updated = self
+            .return_repo
+            .receive(&request, &movement)
+            .await
+            .map_err(|e| anyhow!("Failed to receive return: {}", e))?;
+        let updated = ReturnResponse::from(updated);
+
+        if bucket == StockBucket::Available {
+            // ของเข้าแล้ว แจ้งไม่สำเร็จไม่ควรทำให้การรับคืนล้ม
+            if let Err(e) = self.subscription_repo.notify_back_in_stock(updated.book_id).await {
+                tracing::warn!("Failed to notify subscribers of book {}: {}", updated.book_id, e);
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// Refund the returned copies: back to the order's payments (partial
+    /// refund), or as store credit when the customer prefers that. The split
+    /// is saved before any money moves, so a failed refund can be retried.
+    pub async fn refund_return(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        req: RefundReturnRequest,
+    ) -> Result<ReturnResponse> {
+        ensure_staff(caller)?;
+        let mut request = self.find_return(id).await?;
+        let mut order = self.find_order(request.order_id).await?;
+
+        // 1. Plan the refund, unless an earlier attempt already did
+        if request.status != ReturnStatus::Refunding {
+            request = self.plan_refund(&order, request, req.to_store_credit).await?;
+        }
+
+        // 2. Pay out the parts not done yet, each recorded once
+        let refunds = self
+            .return_repo
+            .find_refunds(request.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch refund progress: {}", e))?;
+        for refund in refunds.iter().filter(|r| !r.is_completed()) {
+            self.pay_out(&order, refund, caller.id).await?;
+        }
+
+        // 3. Done, taking back the points earned on what was returned in the
+        // same write; a failure leaves the request refunding to be retried
+        let clawback = self.points_clawback(&order, request.refund_amount, caller.id).await?;
+        request.mark_refunded()
+            .map_err(|e| anyhow!("{}", e))?;
+        let updated = self
+            .return_repo
+            .finish_refund(&request, clawback.as_ref())
+            .await
+            .map_err(|e| anyhow!("Failed to complete refund: {}", e))?;
+        let updated = ReturnResponse::from(updated);
+
+        // 4. Reduce the tax invoice, if the customer asked for one. The money
+        // already went back, so a failure is left for staff to credit by hand.
+        if let Err(e) = self.invoices.credit_return(&request, caller.id).await {
+            warn!("Failed to issue credit note for return {}: {}", updated.id, e);
+        }
+
+        // 5. Whole order paid back -> order is refunded
+        let refunded_total: i64 = self
+            .payment_repo
+            .find_by_order(order.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch payments: {}", e))?
+            .iter()
+            .map(|p| p.refunded_amount)
+            .sum::<i64>()
+            + self.refund_credit_total(order.id).await?;
+
+        if refunded_total >= order.goods_total() && order.status.can_transition_to(OrderStatus::Refunded) {
+            order.refund(Some(caller.id), Some(format!("Refunded via return {}", updated.id)))
+                .map_err(|e| anyhow!("{}", e))?;
+            self.order_repo
+                .update_closed(&order)
+                .await
+                .map_err(|e| anyhow!("Failed to mark order as refunded: {}", e))?;
+        }
+
+        Ok(updated)
+    }
+
+    /// Works out what the returned copies are worth and how it goes back,
+    /// and saves that with the request in its refunding state
+    async fn plan_refund(
+        &self,
+        order: &OrderEntity,
+        mut request: ReturnRequestEntity,
+        to_store_credit: bool,
+    ) -> Result<ReturnRequestEntity> {
+        let item = order
+            .items
+            .iter()
+            .find(|i| i.id == request.order_item_id)
+            .ok_or_else(|| anyhow!("Order item not found"))?;
+        // line_total รวม VAT แล้ว — หักส่วนลดของ order ตามสัดส่วน (ปัดลง) เพื่อคืนเท่าที่ลูกค้าจ่ายจริง
+        // ค่าส่งไม่คืนผ่าน return
+        let gross = item.line_total * request.quantity.value() as i64 / item.quantity.value() as i64;
+        let amount = if order.subtotal == 0 {
+            0
+        } else {
+            gross * order.goods_total() / order.subtotal
+        };
+
+        request.start_refund(amount)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        // Check there is enough captured money before touching the PSP
+        let payments: Vec<_> = self
+            .payment_repo
+            .find_by_order(order.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch payments: {}", e))?
+            .into_iter()
+            .filter(|p| p.is_settled() && p.refundable_amount() > 0)
+            .collect();
+
+        // store credit ที่ออกแทนการคืนเงินครั้งก่อน ๆ ใช้เงินก้อนเดียวกัน
+        let credited = self.refund_credit_total(order.id).await?;
+        let refundable: i64 = payments.iter().map(|p| p.refundable_amount()).sum::<i64>() - credited;
+        if refundable < amount {
+            return Err(anyhow!("Not enough captured funds to refund this return"));
+        }
+
+        // As store credit, or across the settled payments
+        let mut refunds = Vec::new();
+        if to_store_credit {
+            if amount > 0 {
+                refunds.push(ReturnRefundEntity::new(request.id, None, amount)?);
+            }
+        } else {
+            let mut remaining = amount;
+            for payment in &payments {
+                if remaining == 0 {
+                    break;
+                }
+                let part = remaining.min(payment.refundable_amount());
+                refunds.push(ReturnRefundEntity::new(request.id, Some(payment.id), part)?);
+                remaining -= part;
+            }
+        }
+
+        self.return_repo
+            .start_refund(&request, &refunds)
+            .await
+            .map_err(|e| anyhow!("Failed to save refund: {}", e))
+    }
+
+    /// Gives one part back: as store credit, or through the payment it was
+    /// taken with (the PSP for cards, the original account for gift cards,
+    /// store credit and points)
+    async fn pay_out(&self, order: &OrderEntity, refund: &ReturnRefundEntity, actor_id: i32) -> Result<()> {
+        let credit = match refund.payment_id {
+            None => RefundCredit::Balance(self.store_credit_entry(order, refund, actor_id).await?),
+            Some(payment_id) => {
+                let mut payment = self
+                    .payment_repo
+                    .find_by_id(payment_id)
+                    .await
+                    .map_err(|e| anyhow!("Failed to fetch payment: {}", e))?
+                    .ok_or_else(|| anyhow!("Payment {} not found", payment_id))?;
+                // ตรวจสถานะและยอดก่อนเรียก PSP
+                payment.record_refund(refund.amount).map_err(|e| anyhow!("{}", e))?;
+                self.refund_credit(&payment, refund, actor_id).await?
+            }
+        };
+
+        self.return_repo
+            .complete_refund(refund, credit)
+            .await
+            .map_err(|e| anyhow!("Failed to record refund: {}", e))?;
+
+        Ok(())
+    }
+
+    /// The entry that puts the part back where `payment` took it from. Card
+    /// refunds are made at the PSP here, keyed so a retry is not paid twice.
+    async fn refund_credit(&self, payment: &PaymentEntity, refund: &ReturnRefundEntity, actor_id: i32) -> Result<RefundCredit> {
+        let intent_id = payment
+            .provider_intent_id
+            .clone()
+            .ok_or_else(|| anyhow!("Payment has no provider intent"))?;
+
+        if payment.provider == LOYALTY_PAYMENT_PROVIDER {
+            let redemption = intent_id
+                .parse::<i32>()
+                .map_err(|_| anyhow!("Invalid redemption reference {}", intent_id))?;
+            let redemption = self
+                .loyalty_repo
+                .find_entry(redemption)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch points entry: {}", e))?
+                .ok_or_else(|| anyhow!("Redemption {} not found", intent_id))?;
+
+            let entry = LoyaltyEntryEntity::new(
+                redemption.user_id,
+                points_to_return(refund.amount),
+                PointsReason::RedemptionReversal,
+                Some(payment.order_id),
+                Some(format!("Return {}", refund.return_id)),
+                Some(actor_id),
+            )
+            .map_err(|e| anyhow!("{}", e))?;
+            Ok(RefundCredit::Points(entry))
+        } else if payment.provider == StoredValueKind::GiftCard.as_str()
+            || payment.provider == StoredValueKind::StoreCredit.as_str()
+        {
+            let redemption = intent_id
+                .parse::<i32>()
+                .map_err(|_| anyhow!("Invalid redemption reference {}", intent_id))?;
+            let redemption = self
+                .stored_value_repo
+                .find_entry(redemption)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch ledger entry: {}", e))?
+                .ok_or_else(|| anyhow!("Redemption {} not found", intent_id))?;
+
+            let entry = StoredValueEntryEntity::new(
+                redemption.account_id,
+                refund.amount,
+                EntryReason::RedemptionRefund,
+                Some(payment.order_id),
+                Some(format!("Return {}", refund.return_id)),
+                Some(actor_id),
+            )
+            .map_err(|e| anyhow!("{}", e))?;
+            Ok(RefundCredit::Balance(entry))
+        } else {
+            self.gateway
+                .refund(&intent_id, refund.amount, &format!("return-{}-payment-{}", refund.return_id, payment.id))
+                .await
+                .map_err(|e| anyhow!("Failed to refund payment: {}", e))?;
+            Ok(RefundCredit::Provider)
+        }
+    }
+
+    /// Entry taking back the share of the order's earned points that
+    /// `refunded` represents, as far as the member still has them
+    async fn points_clawback(&self, order: &OrderEntity, refunded: i64, actor_id: i32) -> Result<Option<LoyaltyEntryEntity>> {
+        let goods_total = order.goods_total();
+        if refunded <= 0 || goods_total <= 0 {
+            return Ok(None);
+        }
+
+        let (earned, net_earned) = self
+            .loyalty_repo
+            .earned_for_order(order.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch earned points: {}", e))?;
+        let balance = self
+            .loyalty_repo
+            .find_balance(order.user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch points balance: {}", e))?;
+
+        // แต้มที่ได้จาก order คิดตามสัดส่วนยอดที่คืน
+        let points = (earned * refunded / goods_total).min(net_earned).min(balance);
+        if points <= 0 {
+            return Ok(None);
+        }
+
+        LoyaltyEntryEntity::new(
+            order.user_id,
+            -points,
+            PointsReason::Clawback,
+            Some(order.id),
+            None,
+            Some(actor_id),
+        )
+        .map(Some)
+        .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Store credit for the part, on the customer's account (opened on
+    /// first use, its validity extended)
+    async fn store_credit_entry(&self, order: &OrderEntity, refund: &ReturnRefundEntity, actor_id: i32) -> Result<StoredValueEntryEntity> {
+        let mut account = self
+            .stored_value_repo
+            .find_or_create_store_credit(order.user_id, order.currency.as_str())
+            .await
+            .map_err(|e| anyhow!("Failed to open store credit: {}", e))?;
+
+        account.extend_validity(Utc::now());
+        let account = self
+            .stored_value_repo
+            .update(&account)
+            .await
+            .map_err(|e| anyhow!("Failed to update store credit: {}", e))?;
+
+        StoredValueEntryEntity::new(
+            account.id,
+            refund.amount,
+            EntryReason::RefundCredit,
+            Some(order.id),
+            Some(format!("Return {}", refund.return_id)),
+            Some(actor_id),
+        )
+        .map_err(|e| anyhow!("{}", e))
+    }
+
+    async fn refund_credit_total(&self, order_id: i32) -> Result<i64> {
+        self.stored_value_repo
+            .refund_credit_total(order_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch store credit issued: {}", e))
+    }
+
+    async fn find_return(&self, id: i32) -> Result<ReturnRequestEntity> {
+        match self
+            .return_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch return request: {}", e))?
+        {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("Return request not found")),
+        }
+    }
+
+    async fn find_order(&self, id: i32) -> Result<OrderEntity> {
+        match self
+            .order_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch order: {}", e))?
+        {
+            Some(o) => Ok(o),
+            None => Err(anyhow!("Order not found")),
+        }
+    }
+
+    async fn save_return(&self, request: ReturnRequestEntity) -> Result<ReturnResponse> {
+        let updated = self
+            .return_repo
+            .update(&request)
+            .await
+            .map_err(|e| anyhow!("Failed to update return request: {}", e))?;
+
+        Ok(ReturnResponse::from(updated))
+    }
+}
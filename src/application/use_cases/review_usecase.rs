@@ -0,0 +1,231 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        review_dto::{
+            BookRatingResponse, RejectReviewRequest, ReviewRequest, ReviewResponse,
+            VoteReviewRequest,
+        },
+    },
+};
+use crate::domain::{
+    entities::review::ReviewEntity,
+    repositories::{
+        book_repository::BookRepository,
+        order_repository::OrderRepository,
+        review_repository::ReviewRepository,
+    },
+    value_objects::{order_status::OrderStatus, review_status::ReviewStatus},
+};
+
+/// ReviewUseCase — verified-purchase reviews, votes and staff moderation
+pub struct ReviewUseCase {
+    review_repo: Arc<dyn ReviewRepository>,
+    order_repo: Arc<dyn OrderRepository>,
+    book_repo: Arc<dyn BookRepository>,
+}
+
+impl ReviewUseCase {
+    pub fn new(
+        review_repo: Arc<dyn ReviewRepository>,
+        order_repo: Arc<dyn OrderRepository>,
+        book_repo: Arc<dyn BookRepository>,
+    ) -> Self {
+        Self {
+            review_repo,
+            order_repo,
+            book_repo,
+        }
+    }
+
+    /// Post a review; only customers with a delivered order for the book can
+    pub async fn create_review(
+        &self,
+        user_id: i32,
+        book_id: i32,
+        req: ReviewRequest,
+    ) -> Result<ReviewResponse> {
+        self.find_book_rating(book_id).await?;
+
+        if self
+            .review_repo
+            .find_by_user_and_book(user_id, book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching review: {}", e))?
+            .is_some()
+        {
+            return Err(anyhow!("You have already reviewed this book"));
+        }
+
+        let orders = self.order_repo.find_by_user(user_id).await.map_err(|e| {
+            anyhow!("Failed to fetch orders: {}", e)
+        })?;
+        let order = orders
+            .iter()
+            .filter(|o| o.status == OrderStatus::Delivered)
+            .find(|o| o.items.iter().any(|i| i.book_id == book_id))
+            .ok_or_else(|| anyhow!("Only customers who received this book can review it"))?;
+
+        let mut review = ReviewEntity::new(book_id, user_id, order.id, req.rating, req.title, req.body)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        review.id = self
+            .review_repo
+            .save(&review)
+            .await
+            .map_err(|e| anyhow!("Failed to save review: {}", e))?;
+
+        Ok(ReviewResponse::from(review))
+    }
+
+    /// Edit your own review (goes back to moderation)
+    pub async fn update_review(
+        &self,
+        user_id: i32,
+        id: i32,
+        req: ReviewRequest,
+    ) -> Result<ReviewResponse> {
+        let mut review = self.find_review(id).await?;
+        if review.user_id != user_id {
+            return Err(anyhow!("Review not found"));
+        }
+
+        review.edit(req.rating, req.title, req.body)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.save_review(review).await
+    }
+
+    /// Authors can delete their own reviews, staff any review
+    pub async fn delete_review(&self, caller: &UserInfo, id: i32) -> Result<()> {
+        let review = self.find_review(id).await?;
+        if review.user_id != caller.id {
+            ensure_staff(caller)?;
+        }
+
+        self.review_repo
+            .delete(review.id)
+            .await
+            .map_err(|e| anyhow!("Failed to delete review: {}", e))
+    }
+
+    /// Public reviews of a book, most helpful first
+    pub async fn get_book_reviews(&self, book_id: i32) -> Result<Vec<ReviewResponse>> {
+        let reviews = self
+            .review_repo
+            .find_by_book(book_id, ReviewStatus::Approved)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch reviews: {}", e))?;
+
+        Ok(reviews
+            .into_iter()
+            .map(|r| ReviewResponse {
+                rejection_reason: None,
+                ..ReviewResponse::from(r)
+            })
+            .collect())
+    }
+
+    pub async fn get_book_rating(&self, book_id: i32) -> Result<BookRatingResponse> {
+        self.find_book_rating(book_id).await
+    }
+
+    pub async fn vote(
+        &self,
+        user_id: i32,
+        id: i32,
+        req: VoteReviewRequest,
+    ) -> Result<ReviewResponse> {
+        let review = self.find_review(id).await?;
+        if !review.is_public() {
+            return Err(anyhow!("Review not found"));
+        }
+        if review.user_id == user_id {
+            return Err(anyhow!("You cannot vote on your own review"));
+        }
+
+        let updated = self
+            .review_repo
+            .vote(review.id, user_id, req.helpful)
+            .await
+            .map_err(|e| anyhow!("Failed to record vote: {}", e))?;
+
+        Ok(ReviewResponse {
+            rejection_reason: None,
+            ..ReviewResponse::from(updated)
+        })
+    }
+
+    /// Pending reviews, oldest first (staff)
+    pub async fn get_moderation_queue(&self, caller: &UserInfo) -> Result<Vec<ReviewResponse>> {
+        ensure_staff(caller)?;
+
+        let reviews = self
+            .review_repo
+            .find_by_status(ReviewStatus::Pending)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch reviews: {}", e))?;
+
+        Ok(reviews.into_iter().map(ReviewResponse::from).collect())
+    }
+
+    pub async fn approve_review(&self, caller: &UserInfo, id: i32) -> Result<ReviewResponse> {
+        ensure_staff(caller)?;
+        let mut review = self.find_review(id).await?;
+
+        review.approve(caller.id).map_err(|e| anyhow!("{}", e))?;
+
+        self.save_review(review).await
+    }
+
+    pub async fn reject_review(
+        &self,
+        caller: &UserInfo,
+        id: i32,
+        req: RejectReviewRequest,
+    ) -> Result<ReviewResponse> {
+        ensure_staff(caller)?;
+        let mut review = self.find_review(id).await?;
+
+        review.reject(caller.id, req.reason)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        self.save_review(review).await
+    }
+
+    async fn find_book_rating(&self, book_id: i32) -> Result<BookRatingResponse> {
+        let book = self
+            .book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .ok_or_else(|| anyhow!("Book not found"))?;
+
+        Ok(BookRatingResponse::from(&book))
+    }
+
+    async fn find_review(&self, id: i32) -> Result<ReviewEntity> {
+        match self
+            .review_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch review: {}", e))?
+        {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("Review not found")),
+        }
+    }
+
+    async fn save_review(&self, review: ReviewEntity) -> Result<ReviewResponse> {
+        let updated = self
+            .review_repo
+            .update(&review)
+            .await
+            .map_err(|e| anyhow!("Failed to update review: {}", e))?;
+
+        Ok(ReviewResponse::from(updated))
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, sync::Arc};
+use anyhow::{Result, anyhow};
+use tracing::{info, warn};
+
+use crate::application::dtos::search_dto::{
+    IndexSyncResponse, SearchRequest, SearchResponse, SuggestRequest, SuggestionResponse,
+};
+use crate::application::use_cases::search_usecase::to_hit;
+use crate::domain::{
+    repositories::{book_repository::BookRepository, search_feed_repository::SearchFeedRepository},
+    services::search_text,
+};
+use crate::infrastructure::search_index::SearchIndex;
+
+const DEFAULT_PER_PAGE: u32 = 20;
+const MAX_PER_PAGE: u32 = 100;
+const DEFAULT_SUGGESTIONS: u32 = 8;
+const MAX_SUGGESTIONS: u32 = 20;
+
+/// SearchIndexUseCase — search through a dedicated index (typos, synonyms,
+/// autocomplete) and keep that index in sync with the book change feed.
+pub struct SearchIndexUseCase {
+    feed_repo: Arc<dyn SearchFeedRepository>,
+    book_repo: Arc<dyn BookRepository>,
+    index: Arc<dyn SearchIndex>,
+}
+
+impl SearchIndexUseCase {
+    pub fn new(
+        feed_repo: Arc<dyn SearchFeedRepository>,
+        book_repo: Arc<dyn BookRepository>,
+        index: Arc<dyn SearchIndex>,
+    ) -> Self {
+        Self {
+            feed_repo,
+            book_repo,
+            index,
+        }
+    }
+
+    /// Applies feed changes since the index's cursor, committing after each
+    /// batch. A new index is rebuilt instead. Run from a background worker.
+    pub async fn sync_changes(&self, batch_size: i64) -> Result<IndexSyncResponse> {
+        let Some(mut cursor) = self
+            .index
+            .cursor()
+            .await
+            .map_err(|e| anyhow!("Failed to read search index cursor: {}", e))?
+        else {
+            return self.rebuild(batch_size).await;
+        };
+
+        let mut summary = IndexSyncResponse { indexed: 0, removed: 0 };
+        loop {
+            let changes = self
+                .feed_repo
+                .find_changes_after(cursor, batch_size)
+                .await
+                .map_err(|e| anyhow!("Failed to read book changes: {}", e))?;
+            let Some(last) = changes.last() else {
+                break;
+            };
+            cursor = last.cursor;
+
+            let mut book_ids: Vec<i32> = changes.iter().map(|c| c.book_id).collect();
+            book_ids.sort_unstable();
+            book_ids.dedup();
+
+            // หาไม่เจอ = ถูกลบหรือปิดการขาย ให้เอาออกจาก index
+            let documents = self
+                .feed_repo
+                .find_documents(&book_ids)
+                .await
+                .map_err(|e| anyhow!("Failed to load search documents: {}", e))?;
+            let removed: Vec<i32> = book_ids
+                .into_iter()
+                .filter(|id| !documents.iter().any(|d| d.book_id == *id))
+                .collect();
+
+            summary.indexed += documents.len();
+            summary.removed += removed.len();
+            let applied = async {
+                self.index.stage(documents, removed).await?;
+                self.index.commit(cursor).await
+            };
+            if let Err(e) = applied.await {
+                self.discard_staged().await;
+                return Err(e);
+            }
+
+            if (changes.len() as i64) < batch_size {
+                break;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Re-indexes every active book in one commit. The feed cursor is taken
+    /// first, so changes made while rebuilding are replayed by the next sync.
+    pub async fn rebuild(&self, batch_size: i64) -> Result<IndexSyncResponse> {
+        let cursor = self
+            .feed_repo
+            .latest_cursor()
+            .await
+            .map_err(|e| anyhow!("Failed to read book change cursor: {}", e))?;
+
+        self.index.clear().await?;
+        // ล้มกลางทางต้องทิ้งที่ stage ไว้ ไม่งั้น commit ถัดไปจะได้ index ว่างครึ่งๆ
+        let indexed = match self.stage_all(batch_size).await {
+            Ok(indexed) => indexed,
+            Err(e) => {
+                self.discard_staged().await;
+                return Err(e);
+            }
+        };
+        if let Err(e) = self.index.commit(cursor).await {
+            self.discard_staged().await;
+            return Err(e);
+        }
+        info!(backend = self.index.backend(), indexed, "Search index rebuilt");
+
+        Ok(IndexSyncResponse { indexed, removed: 0 })
+    }
+
+    /// Stages every active book, in book id order. Returns how many.
+    async fn stage_all(&self, batch_size: i64) -> Result<usize> {
+        let mut indexed = 0;
+        let mut after_book_id = 0;
+        loop {
+            let documents = self
+                .feed_repo
+                .find_documents_after(after_book_id, batch_size)
+                .await
+                .map_err(|e| anyhow!("Failed to load search documents: {}", e))?;
+            let Some(last) = documents.last() else {
+                break;
+            };
+            after_book_id = last.book_id;
+            let count = documents.len();
+            indexed += count;
+
+            self.index.stage(documents, Vec::new()).await?;
+            if (count as i64) < batch_size {
+                break;
+            }
+        }
+
+        Ok(indexed)
+    }
+
+    async fn discard_staged(&self) {
+        if let Err(e) = self.index.rollback().await {
+            warn!(backend = self.index.backend(), "Failed to roll back search index: {}", e);
+        }
+    }
+
+    pub async fn search(&self, req: SearchRequest) -> Result<SearchResponse> {
+        let query = req.q.trim().to_string();
+        let page = req.page.unwrap_or(1).max(1);
+        let per_page = req.per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE);
+
+        let offset = (page as usize - 1) * per_page as usize;
+        let result = self
+            .index
+            .search(&query, offset, per_page as usize)
+            .await
+            .map_err(|e| anyhow!("Search failed: {}", e))?;
+
+        let ids: Vec<i32> = result.hits.iter().map(|h| h.book_id).collect();
+        let mut books: HashMap<i32, _> = self
+            .book_repo
+            .find_by_ids(&ids)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching books: {}", e))?
+            .into_iter()
+            .filter(|b| b.is_active)
+            .map(|b| (b.id, b))
+            .collect();
+
+        // index อาจตามหลังฐานข้อมูลเล็กน้อย หนังสือที่หายไปแล้วจึงข้ามไป
+        let terms = search_text::tokenize(&query);
+        let results: Vec<_> = result
+            .hits
+            .into_iter()
+            .filter_map(|h| books.remove(&h.book_id).map(|b| to_hit(b, h.score, &terms)))
+            .collect();
+        let total = result.total as i64;
+
+        Ok(SearchResponse {
+            isbn_match: search_text::as_isbn(&query).is_some() && !results.is_empty(),
+            query,
+            page,
+            per_page,
+            total,
+            total_pages: (total as u64).div_ceil(per_page as u64) as u32,
+            results,
+        })
+    }
+
+    pub async fn suggest(&self, req: SuggestRequest) -> Result<Vec<SuggestionResponse>> {
+        let limit = req.limit.unwrap_or(DEFAULT_SUGGESTIONS).clamp(1, MAX_SUGGESTIONS);
+        let suggestions = self
+            .index
+            .suggest(req.q.trim(), limit as usize)
+            .await
+            .map_err(|e| anyhow!("Suggest failed: {}", e))?;
+
+        Ok(suggestions
+            .into_iter()
+            .map(|s| SuggestionResponse {
+                book_id: s.book_id,
+                title: s.title,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+
+use crate::application::dtos::search_dto::{SearchHitResponse, SearchRequest, SearchResponse};
+use crate::domain::{
+    entities::book::BookEntity,
+    repositories::{
+        book_repository::BookRepository,
+        book_search_repository::BookSearchRepository,
+    },
+    services::search_text::{self, SearchTerm},
+};
+
+const DEFAULT_PER_PAGE: u32 = 20;
+const MAX_PER_PAGE: u32 = 100;
+/// Characters of description shown around the first match
+const SNIPPET_CHARS: usize = 160;
+
+/// SearchUseCase — full-text catalog search (title > author > description)
+pub struct SearchUseCase {
+    book_repo: Arc<dyn BookRepository>,
+    search_repo: Arc<dyn BookSearchRepository>,
+}
+
+impl SearchUseCase {
+    pub fn new(
+        book_repo: Arc<dyn BookRepository>,
+        search_repo: Arc<dyn BookSearchRepository>,
+    ) -> Self {
+        Self {
+            book_repo,
+            search_repo,
+        }
+    }
+
+    pub async fn search(&self, req: SearchRequest) -> Result<SearchResponse> {
+        let query = req.q.trim().to_string();
+        let page = req.page.unwrap_or(1).max(1);
+        let per_page = req.per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE);
+
+        let terms = search_text::tokenize(&query);
+
+        // พิมพ์ ISBN มาตรงๆ ไม่ต้องค้น full-text
+        if let Some(isbn) = search_text::as_isbn(&query) {
+            let book = self
+                .book_repo
+                .find_by_isbn(isbn.as_str())
+                .await
+                .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+                .filter(|b| b.is_active);
+
+            if let Some(book) = book {
+                return Ok(SearchResponse {
+                    query,
+                    isbn_match: true,
+                    page: 1,
+                    per_page,
+                    total: 1,
+                    total_pages: 1,
+                    results: vec![to_hit(book, 1.0, &terms)],
+                });
+            }
+        }
+
+        let Some(tsquery) = search_text::to_tsquery(&terms) else {
+            return Ok(SearchResponse {
+                query,
+                isbn_match: false,
+                page,
+                per_page,
+                total: 0,
+                total_pages: 0,
+                results: Vec::new(),
+            });
+        };
+
+        let offset = (page as i64 - 1) * per_page as i64;
+        let result = self
+            .search_repo
+            .search(&tsquery, offset, per_page as i64)
+            .await
+            .map_err(|e| anyhow!("Search failed: {}", e))?;
+
+        Ok(SearchResponse {
+            query,
+            isbn_match: false,
+            page,
+            per_page,
+            total: result.total,
+            total_pages: (result.total as u64).div_ceil(per_page as u64) as u32,
+            results: result
+                .hits
+                .into_iter()
+                .map(|h| to_hit(h.book, h.rank, &terms))
+                .collect(),
+        })
+    }
+}
+
+pub(crate) fn to_hit(book: BookEntity, rank: f32, terms: &[SearchTerm]) -> SearchHitResponse {
+    SearchHitResponse {
+        book_id: book.id,
+        isbn: book.isbn.as_str().to_string(),
+        title_highlight: search_text::highlight(book.title.as_str(), terms),
+        author_highlight: search_text::highlight(&book.author, terms),
+        description_snippet: book
+            .description
+            .as_deref()
+            .map(|d| search_text::snippet(d, terms, SNIPPET_CHARS)),
+        title: book.title.as_str().to_string(),
+        average_rating: book.average_rating(),
+        author: book.author,
+        price: book.price.amount(),
+        currency: book.price.currency().as_str().to_string(),
+        in_stock: book.stock_quantity > 0,
+        rank,
+    }
+}
@@ -0,0 +1,204 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+
+use crate::application::{
+    authorization::ensure_staff,
+    dtos::{
+        auth_dto::UserInfo,
+        shipping_dto::{CreateShippingMethodRequest, CreateShippingZoneRequest, ShippingZoneResponse},
+    },
+};
+use crate::domain::{
+    entities::shipping_zone::{ShippingMethodEntity, ShippingRateEntity, ShippingZoneEntity},
+    repositories::shipping_repository::ShippingRepository,
+    services::shipping_calculator::{self, Parcel, ShippingDestination},
+};
+use crate::infrastructure::shipping_rate_provider::{
+    ShippingRate, ShippingRateProvider, ShippingRateRequest,
+};
+
+/// A method that can deliver the parcel, with its price and delivery window.
+#[derive(Debug, Clone)]
+pub struct ShippingOption {
+    pub method: ShippingMethodEntity,
+    pub rate: ShippingRate,
+}
+
+/// ShippingUseCase — zones/methods/rate tables and delivery quotes
+pub struct ShippingUseCase {
+    shipping_repo: Arc<dyn ShippingRepository>,
+    provider: Arc<dyn ShippingRateProvider>,
+}
+
+impl ShippingUseCase {
+    pub fn new(
+        shipping_repo: Arc<dyn ShippingRepository>,
+        provider: Arc<dyn ShippingRateProvider>,
+    ) -> Self {
+        Self {
+            shipping_repo,
+            provider,
+        }
+    }
+
+    pub async fn get_zones(&self) -> Result<Vec<ShippingZoneResponse>> {
+        let zones = self.shipping_repo.find_all_zones().await.map_err(|e| {
+            anyhow!("Failed to fetch shipping zones: {}", e)
+        })?;
+
+        Ok(zones.into_iter().map(ShippingZoneResponse::from).collect())
+    }
+
+    pub async fn create_zone(
+        &self,
+        caller: &UserInfo,
+        req: CreateShippingZoneRequest,
+    ) -> Result<ShippingZoneResponse> {
+        ensure_staff(caller)?;
+
+        let mut zone = ShippingZoneEntity::new(req.name, req.country, req.postal_prefixes)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        zone.id = self
+            .shipping_repo
+            .save_zone(&zone)
+            .await
+            .map_err(|e| anyhow!("Failed to create shipping zone: {}", e))?;
+
+        Ok(ShippingZoneResponse::from(zone))
+    }
+
+    pub async fn create_method(
+        &self,
+        caller: &UserInfo,
+        zone_id: i32,
+        req: CreateShippingMethodRequest,
+    ) -> Result<ShippingZoneResponse> {
+        ensure_staff(caller)?;
+        let zone = self.find_zone(zone_id).await?;
+
+        if zone.methods.iter().any(|m| m.code == req.code.trim().to_lowercase()) {
+            return Err(anyhow!("Shipping method '{}' already exists in this zone", req.code));
+        }
+
+        let rates = req
+            .rates
+            .into_iter()
+            .map(|r| ShippingRateEntity::new(r.min_value, r.max_value, r.amount))
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let method = ShippingMethodEntity::new(
+            zone.id,
+            req.code,
+            req.name,
+            req.kind.parse()?,
+            req.rate_basis.parse()?,
+            req.min_days,
+            req.max_days,
+            req.max_weight_grams,
+            rates,
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        self.shipping_repo
+            .save_method(&method)
+            .await
+            .map_err(|e| anyhow!("Failed to create shipping method: {}", e))?;
+
+        Ok(ShippingZoneResponse::from(self.find_zone(zone_id).await?))
+    }
+
+    pub async fn deactivate_method(
+        &self,
+        caller: &UserInfo,
+        zone_id: i32,
+        method_id: i32,
+    ) -> Result<ShippingZoneResponse> {
+        ensure_staff(caller)?;
+        let zone = self.find_zone(zone_id).await?;
+
+        let mut method = zone
+            .methods
+            .into_iter()
+            .find(|m| m.id == method_id)
+            .ok_or_else(|| anyhow!("Shipping method not found"))?;
+        method.deactivate();
+
+        self.shipping_repo
+            .update_method(&method)
+            .await
+            .map_err(|e| anyhow!("Failed to update shipping method: {}", e))?;
+
+        Ok(ShippingZoneResponse::from(self.find_zone(zone_id).await?))
+    }
+
+    /// Every method of the destination's zone that can carry the parcel,
+    /// cheapest first.
+    pub async fn options(
+        &self,
+        destination: &ShippingDestination,
+        parcel: &Parcel,
+        placed_at: DateTime<Utc>,
+    ) -> Result<Vec<ShippingOption>> {
+        let zones = self
+            .shipping_repo
+            .find_zones_by_country(&destination.country)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch shipping zones: {}", e))?;
+
+        let Some(zone) = shipping_calculator::find_zone(&zones, destination) else {
+            return Ok(Vec::new());
+        };
+
+        let req = ShippingRateRequest {
+            destination: destination.clone(),
+            parcel: *parcel,
+            placed_at,
+        };
+
+        let mut options = Vec::new();
+        for method in zone.methods.iter().filter(|m| m.is_active) {
+            let rate = self.provider.rate(method, &req).await.map_err(|e| {
+                anyhow!("{} could not quote '{}': {}", self.provider.provider(), method.code, e)
+            })?;
+            if let Some(rate) = rate {
+                options.push(ShippingOption {
+                    method: method.clone(),
+                    rate,
+                });
+            }
+        }
+
+        options.sort_by_key(|o| (o.rate.amount, o.method.max_days, o.method.id));
+        Ok(options)
+    }
+
+    /// Quote for the method the customer picked
+    pub async fn quote(
+        &self,
+        method_id: i32,
+        destination: &ShippingDestination,
+        parcel: &Parcel,
+        placed_at: DateTime<Utc>,
+    ) -> Result<ShippingOption> {
+        self.options(destination, parcel, placed_at)
+            .await?
+            .into_iter()
+            .find(|o| o.method.id == method_id)
+            .ok_or_else(|| anyhow!("Shipping method is not available for this destination"))
+    }
+
+    async fn find_zone(&self, id: i32) -> Result<ShippingZoneEntity> {
+        match self
+            .shipping_repo
+            .find_zone_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch shipping zone: {}", e))?
+        {
+            Some(z) => Ok(z),
+            None => Err(anyhow!("Shipping zone not found")),
+        }
+    }
+}
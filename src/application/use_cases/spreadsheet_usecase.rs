@@ -0,0 +1,743 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Seek, Write},
+    sync::Arc,
+};
+use anyhow::{anyhow, Result};
+use chrono::{Days, NaiveDate, Utc};
+use rand::{distr::Alphanumeric, Rng};
+
+use crate::application::{
+    authorization::{ensure_any_role, ensure_staff, ROLE_ADMIN},
+    dtos::{
+        auth_dto::UserInfo,
+        spreadsheet_dto::{
+            RowErrorResponse, SpreadsheetExportQuery, SpreadsheetImportRequest,
+            SpreadsheetImportResponse,
+        },
+    },
+};
+use crate::domain::{
+    entities::{book::BookEntity, user::UserEntity},
+    repositories::{
+        book_repository::BookRepository, bulk_import_repository::BulkImportRepository,
+        user_repository::UserRepository,
+    },
+    value_objects::{
+        age::Age,
+        book_format::BookFormat,
+        email_address::EmailAddress,
+        isbn::Isbn,
+        money::{Currency, Money},
+        password::Password,
+        person_name::PersonName,
+        phone_number::PhoneNumber,
+        tax_class::TaxClass,
+    },
+};
+use crate::infrastructure::{
+    argon2::PasswordService,
+    spreadsheet::{header_index, read_rows, Cell, SheetFormat, SheetWriter},
+};
+
+/// Columns read on import, and written (in this order) on export. An
+/// exported file can be edited and imported back as it is.
+const BOOK_FIELDS: &[&str] = &[
+    "isbn",
+    "title",
+    "author",
+    "price",
+    "currency",
+    "description",
+    "category",
+    "publisher",
+    "language",
+    "format",
+    "release_date",
+    "preorder_enabled",
+    "weight_grams",
+    "is_active",
+];
+const USER_FIELDS: &[&str] = &["email", "first_name", "last_name", "age", "sex", "phone", "is_active"];
+/// Export-only: stock moves through the inventory ledger, sign-up time is history
+const BOOK_EXPORT_HEADER: &[&str] = &["stock_quantity"];
+const USER_EXPORT_HEADER: &[&str] = &["created_at"];
+
+const MAX_IMPORT_ROWS: usize = 20_000;
+const EXPORT_PAGE: i64 = 500;
+const SEXES: &[&str] = &["MALE", "FEMALE", "OTHER"];
+const IMPORTED_PASSWORD_LENGTH: usize = 32;
+
+/// Problems with one row: (field, message)
+type FieldErrors = Vec<(Option<&'static str>, String)>;
+
+/// SpreadsheetUseCase — bulk edits of books and users from CSV/XLSX files.
+///
+/// An import first checks every row against the same value objects the
+/// rest of the app uses and reports problems by row. Nothing is written
+/// unless the whole file is clean, and then it is written in a single
+/// transaction, so a price list is never half applied.
+pub struct SpreadsheetUseCase {
+    book_repo: Arc<dyn BookRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    bulk_repo: Arc<dyn BulkImportRepository>,
+    password_service: Arc<dyn PasswordService>,
+}
+
+impl SpreadsheetUseCase {
+    pub fn new(
+        book_repo: Arc<dyn BookRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        bulk_repo: Arc<dyn BulkImportRepository>,
+        password_service: Arc<dyn PasswordService>,
+    ) -> Self {
+        Self {
+            book_repo,
+            user_repo,
+            bulk_repo,
+            password_service,
+        }
+    }
+
+    /// Staff: create or update books by ISBN. Empty cells keep the current
+    /// value; stock is never imported.
+    pub async fn import_books(
+        &self,
+        caller: &UserInfo,
+        req: SpreadsheetImportRequest,
+    ) -> Result<SpreadsheetImportResponse> {
+        ensure_staff(caller)?;
+
+        let rows = read_sheet(&req)?;
+        let columns = ColumnMap::resolve(&rows[0], BOOK_FIELDS, "isbn", req.columns.as_ref())?;
+
+        let mut report = ImportReport::new(req.dry_run);
+        let mut books = Vec::new();
+        let mut seen = HashSet::new();
+        for (number, row) in data_rows(&rows) {
+            report.rows += 1;
+            match self.book_from_row(&columns, row, &mut seen).await? {
+                Ok(book) => books.push(book),
+                Err(errors) => report.reject(number, errors),
+            }
+        }
+
+        report.count(books.iter().map(|b| b.id == 0));
+        if report.should_apply() {
+            self.bulk_repo
+                .apply_books(&books)
+                .await
+                .map_err(|e| anyhow!("Failed to apply import: {}", e))?;
+            report.applied = true;
+        }
+
+        Ok(report.into_response())
+    }
+
+    /// Admin: create or update accounts by email. New accounts get a random
+    /// password nobody knows; an admin sets a real one before first sign-in.
+    pub async fn import_users(
+        &self,
+        caller: &UserInfo,
+        req: SpreadsheetImportRequest,
+    ) -> Result<SpreadsheetImportResponse> {
+        ensure_any_role(caller, &[ROLE_ADMIN])?;
+
+        let rows = read_sheet(&req)?;
+        let columns = ColumnMap::resolve(&rows[0], USER_FIELDS, "email", req.columns.as_ref())?;
+
+        let mut report = ImportReport::new(req.dry_run);
+        let mut users = Vec::new();
+        let mut seen = HashSet::new();
+        for (number, row) in data_rows(&rows) {
+            report.rows += 1;
+            match self.user_from_row(&columns, row, &mut seen).await? {
+                Ok(user) => users.push(user),
+                Err(errors) => report.reject(number, errors),
+            }
+        }
+
+        report.count(users.iter().map(|u| u.id == 0));
+        if report.should_apply() {
+            // ตอนตรวจสอบเก็บรหัสสุ่มไว้ในรูป plain text แฮชเฉพาะตอนจะบันทึกจริง
+            for user in users.iter_mut().filter(|u| u.id == 0) {
+                let hashed = self
+                    .password_service
+                    .hash_password(user.password.as_str())
+                    .await
+                    .map_err(|e| anyhow!("Failed to hash password: {}", e))?;
+                user.password = Password::new(hashed)?;
+            }
+            self.bulk_repo
+                .apply_users(&users)
+                .await
+                .map_err(|e| anyhow!("Failed to apply import: {}", e))?;
+            report.applied = true;
+        }
+
+        Ok(report.into_response())
+    }
+
+    /// Staff: every book, written page by page into `output`
+    pub async fn export_books<W: Write + Seek + Send>(
+        &self,
+        caller: &UserInfo,
+        query: SpreadsheetExportQuery,
+        output: W,
+    ) -> Result<W> {
+        ensure_staff(caller)?;
+
+        let mut writer = SheetWriter::new(query.format.parse()?, output)?;
+        writer.write_row(&header_cells(BOOK_FIELDS, BOOK_EXPORT_HEADER))?;
+
+        let mut after_id = 0;
+        loop {
+            let books = self
+                .book_repo
+                .find_after(after_id, EXPORT_PAGE)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch books: {}", e))?;
+            let Some(last) = books.last() else {
+                break;
+            };
+            after_id = last.id;
+
+            for book in &books {
+                writer.write_row(&[
+                    Cell::Text(book.isbn.as_str().to_string()),
+                    Cell::Text(book.title.as_str().to_string()),
+                    Cell::Text(book.author.clone()),
+                    Cell::Number(major_units(&book.price)),
+                    Cell::Text(book.price.currency().as_str().to_string()),
+                    text(book.description.as_deref()),
+                    text(book.category.as_deref()),
+                    text(book.publisher.as_deref()),
+                    text(book.language.as_deref()),
+                    Cell::Text(book.format.as_str().to_string()),
+                    text(book.release_date.map(|d| d.to_string()).as_deref()),
+                    Cell::Text(book.preorder_enabled.to_string()),
+                    book.weight_grams.map_or(Cell::Empty, |w| Cell::Number(w.to_string())),
+                    Cell::Text(book.is_active.to_string()),
+                    Cell::Number(book.stock_quantity.to_string()),
+                ])?;
+            }
+        }
+
+        writer.finish()
+    }
+
+    /// Admin: every account, without password hashes
+    pub async fn export_users<W: Write + Seek + Send>(
+        &self,
+        caller: &UserInfo,
+        query: SpreadsheetExportQuery,
+        output: W,
+    ) -> Result<W> {
+        ensure_any_role(caller, &[ROLE_ADMIN])?;
+
+        let mut writer = SheetWriter::new(query.format.parse()?, output)?;
+        writer.write_row(&header_cells(USER_FIELDS, USER_EXPORT_HEADER))?;
+
+        let mut after_id = 0;
+        loop {
+            let users = self
+                .user_repo
+                .find_after(after_id, EXPORT_PAGE)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch users: {}", e))?;
+            let Some(last) = users.last() else {
+                break;
+            };
+            after_id = last.id;
+
+            for user in &users {
+                writer.write_row(&[
+                    Cell::Text(user.email.as_str().to_string()),
+                    Cell::Text(user.first_name.as_str().to_string()),
+                    Cell::Text(user.last_name.as_str().to_string()),
+                    Cell::Number(user.age.value().to_string()),
+                    Cell::Text(user.sex.clone()),
+                    Cell::Text(user.phone.as_str().to_string()),
+                    Cell::Text(user.is_active.to_string()),
+                    Cell::Text(user.created_at.format("%Y-%m-%d %H:%M:%S").to_string()),
+                ])?;
+            }
+        }
+
+        writer.finish()
+    }
+
+    async fn book_from_row(
+        &self,
+        columns: &ColumnMap,
+        row: &[String],
+        seen: &mut HashSet<Isbn>,
+    ) -> Result<Result<BookEntity, FieldErrors>> {
+        let isbn = match columns.get(row, "isbn").map(Isbn::new) {
+            Some(Ok(isbn)) => isbn,
+            Some(Err(e)) => return Ok(Err(vec![(Some("isbn"), e.to_string())])),
+            None => return Ok(Err(vec![(Some("isbn"), "ISBN is required".to_string())])),
+        };
+        if !seen.insert(isbn.clone()) {
+            return Ok(Err(vec![(Some("isbn"), format!("ISBN {} appears more than once in the file", isbn.as_str()))]));
+        }
+
+        let existing = self
+            .book_repo
+            .find_by_isbn(isbn.as_str())
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?;
+
+        let mut errors: FieldErrors = Vec::new();
+        let current_currency = existing.as_ref().map(|b| b.price.currency().clone());
+        let currency = match columns.get(row, "currency").map(Currency::new) {
+            Some(Ok(currency)) => currency,
+            Some(Err(e)) => {
+                errors.push((Some("currency"), e.to_string()));
+                return Ok(Err(errors));
+            }
+            None => current_currency.clone().unwrap_or_else(Currency::thb),
+        };
+        let price = match columns.get(row, "price").map(|p| Money::parse(p, currency.clone())) {
+            Some(Ok(price)) => Some(price),
+            Some(Err(e)) => {
+                errors.push((Some("price"), e.to_string()));
+                None
+            }
+            None => {
+                if current_currency.is_some_and(|c| c != currency) {
+                    errors.push((Some("currency"), "Changing the currency needs a price in the new currency".to_string()));
+                }
+                None
+            }
+        };
+
+        let title = columns.get(row, "title");
+        let author = columns.get(row, "author");
+        let mut book = match existing {
+            Some(mut book) => {
+                if let Some(title) = title {
+                    push_err(&mut errors, "title", book.change_title(title.to_string()));
+                }
+                if let Some(author) = author {
+                    push_err(&mut errors, "author", book.change_author(author.to_string()));
+                }
+                if let Some(price) = price {
+                    push_err(&mut errors, "price", book.change_price(price));
+                }
+                book
+            }
+            None => {
+                if title.is_none() {
+                    errors.push((Some("title"), "Title is required for a new book".to_string()));
+                }
+                if author.is_none() {
+                    errors.push((Some("author"), "Author is required for a new book".to_string()));
+                }
+                if price.is_none() && !errors.iter().any(|(f, _)| *f == Some("price")) {
+                    errors.push((Some("price"), "Price is required for a new book".to_string()));
+                }
+                let (Some(title), Some(author), Some(price)) = (title, author, price) else {
+                    return Ok(Err(errors));
+                };
+                match BookEntity::new(isbn.as_str().to_string(), title.to_string(), author.to_string(), price, 0) {
+                    Ok(book) => book,
+                    Err(e) => {
+                        errors.push((None, e.to_string()));
+                        return Ok(Err(errors));
+                    }
+                }
+            }
+        };
+        let is_new = book.id == 0;
+
+        if let Some(description) = columns.get(row, "description") {
+            push_err(&mut errors, "description", book.change_description(Some(description.to_string())));
+        }
+        if let Some(category) = columns.get(row, "category") {
+            push_err(&mut errors, "category", book.change_category(Some(category.to_string())));
+        }
+
+        let publisher = columns.get(row, "publisher");
+        let language = columns.get(row, "language");
+        let format = match columns.get(row, "format").map(|f| f.to_lowercase().parse::<BookFormat>()) {
+            Some(Ok(format)) => Some(format),
+            Some(Err(e)) => {
+                errors.push((Some("format"), e.to_string()));
+                None
+            }
+            None => None,
+        };
+        if publisher.is_some() || language.is_some() || format.is_some() {
+            let format = format.unwrap_or(book.format);
+            let field = if language.is_some() { "language" } else { "publisher" };
+            push_err(
+                &mut errors,
+                field,
+                book.change_publication(
+                    publisher.map(str::to_string).or_else(|| book.publisher.clone()),
+                    language.map(str::to_string).or_else(|| book.language.clone()),
+                    format,
+                ),
+            );
+            if is_new && format.is_digital() {
+                book.change_tax_class(TaxClass::Ebook);
+            }
+        }
+
+        if let Some(weight) = columns.get(row, "weight_grams") {
+            match parse_whole(weight) {
+                Some(weight) => push_err(&mut errors, "weight_grams", book.change_shipping_profile(Some(weight), book.dimensions)),
+                None => errors.push((Some("weight_grams"), format!("Invalid weight: {}", weight))),
+            }
+        }
+
+        let release_date = match columns.get(row, "release_date").map(|d| parse_date(d).ok_or(d)) {
+            Some(Ok(date)) => Some(date),
+            Some(Err(raw)) => {
+                errors.push((Some("release_date"), format!("Invalid date (expected YYYY-MM-DD): {}", raw)));
+                None
+            }
+            None => book.release_date,
+        };
+        let preorder = match columns.get(row, "preorder_enabled").map(|v| parse_bool(v).ok_or(v)) {
+            Some(Ok(preorder)) => preorder,
+            Some(Err(raw)) => {
+                errors.push((Some("preorder_enabled"), format!("Expected true or false: {}", raw)));
+                book.preorder_enabled
+            }
+            None => book.preorder_enabled,
+        };
+        push_err(&mut errors, "release_date", book.change_release(release_date, preorder));
+
+        match columns.get(row, "is_active").map(|v| parse_bool(v).ok_or(v)) {
+            Some(Ok(true)) => book.activate(),
+            Some(Ok(false)) => book.deactivate(),
+            Some(Err(raw)) => errors.push((Some("is_active"), format!("Expected true or false: {}", raw))),
+            None => {}
+        }
+
+        Ok(if errors.is_empty() { Ok(book) } else { Err(errors) })
+    }
+
+    async fn user_from_row(
+        &self,
+        columns: &ColumnMap,
+        row: &[String],
+        seen: &mut HashSet<EmailAddress>,
+    ) -> Result<Result<UserEntity, FieldErrors>> {
+        let email = match columns.get(row, "email").map(EmailAddress::new) {
+            Some(Ok(email)) => email,
+            Some(Err(e)) => return Ok(Err(vec![(Some("email"), e.to_string())])),
+            None => return Ok(Err(vec![(Some("email"), "Email is required".to_string())])),
+        };
+        if !seen.insert(email.clone()) {
+            return Ok(Err(vec![(Some("email"), format!("{} appears more than once in the file", email.as_str()))]));
+        }
+
+        let existing = self
+            .user_repo
+            .find_by_email(email.as_str())
+            .await
+            .map_err(|e| anyhow!("Database error while fetching user: {}", e))?;
+
+        let mut errors: FieldErrors = Vec::new();
+        let first_name = parse_field(&mut errors, columns, row, "first_name", |v| PersonName::new(v.to_string()));
+        let last_name = parse_field(&mut errors, columns, row, "last_name", |v| PersonName::new(v.to_string()));
+        let phone = parse_field(&mut errors, columns, row, "phone", |v| PhoneNumber::new(v.to_string()));
+        let age = parse_field(&mut errors, columns, row, "age", |v| {
+            parse_whole(v).ok_or_else(|| anyhow!("Invalid age: {}", v)).and_then(Age::new)
+        });
+        let sex = parse_field(&mut errors, columns, row, "sex", |v| {
+            let sex = v.to_uppercase();
+            if SEXES.contains(&sex.as_str()) {
+                Ok(sex)
+            } else {
+                Err(anyhow!("Sex must be one of {}: {}", SEXES.join(", "), v))
+            }
+        });
+
+        let mut user = match existing {
+            Some(mut user) => {
+                if let Some(first_name) = first_name {
+                    user.first_name = first_name;
+                }
+                if let Some(last_name) = last_name {
+                    user.last_name = last_name;
+                }
+                if let Some(age) = age {
+                    user.age = age;
+                }
+                if let Some(sex) = sex {
+                    user.sex = sex;
+                }
+                if let Some(phone) = phone {
+                    user.phone = phone;
+                }
+                user.updated_at = Utc::now();
+                user
+            }
+            None => {
+                for (field, value) in [
+                    ("first_name", first_name.is_some()),
+                    ("last_name", last_name.is_some()),
+                    ("age", age.is_some()),
+                    ("sex", sex.is_some()),
+                    ("phone", phone.is_some()),
+                ] {
+                    if !value && !errors.iter().any(|(f, _)| *f == Some(field)) {
+                        errors.push((Some(field), format!("{} is required for a new user", field)));
+                    }
+                }
+                let (Some(first_name), Some(last_name), Some(age), Some(sex), Some(phone)) =
+                    (first_name, last_name, age, sex, phone)
+                else {
+                    return Ok(Err(errors));
+                };
+                let secret: String = rand::rng()
+                    .sample_iter(&Alphanumeric)
+                    .take(IMPORTED_PASSWORD_LENGTH)
+                    .map(char::from)
+                    .collect();
+                match UserEntity::new(
+                    first_name.as_str().to_string(),
+                    last_name.as_str().to_string(),
+                    email.as_str().to_string(),
+                    age.value(),
+                    sex,
+                    phone.as_str().to_string(),
+                    secret,
+                ) {
+                    Ok(user) => user,
+                    Err(e) => {
+                        errors.push((None, e.to_string()));
+                        return Ok(Err(errors));
+                    }
+                }
+            }
+        };
+
+        match columns.get(row, "is_active").map(|v| parse_bool(v).ok_or(v)) {
+            Some(Ok(true)) => user.activate(),
+            Some(Ok(false)) => user.deactivate(),
+            Some(Err(raw)) => errors.push((Some("is_active"), format!("Expected true or false: {}", raw))),
+            None => {}
+        }
+
+        Ok(if errors.is_empty() { Ok(user) } else { Err(errors) })
+    }
+}
+
+/// Where each field is in the file
+struct ColumnMap {
+    columns: HashMap<&'static str, usize>,
+}
+
+impl ColumnMap {
+    /// Fields in `mapping` must name a header that exists; every other field
+    /// is looked up by its own name. `key` must end up mapped.
+    fn resolve(
+        header: &[String],
+        fields: &'static [&'static str],
+        key: &str,
+        mapping: Option<&HashMap<String, String>>,
+    ) -> Result<Self> {
+        let index = header_index(header);
+        let mapping = mapping.cloned().unwrap_or_default();
+
+        for field in mapping.keys() {
+            if !fields.contains(&field.as_str()) {
+                return Err(anyhow!("Unknown field in column mapping: {} (expected one of {})", field, fields.join(", ")));
+            }
+        }
+
+        let mut columns = HashMap::new();
+        for field in fields {
+            match mapping.get(*field) {
+                Some(name) => {
+                    let column = index
+                        .get(&name.trim().to_lowercase())
+                        .ok_or_else(|| anyhow!("Column \"{}\" mapped to {} is not in the file", name, field))?;
+                    columns.insert(*field, *column);
+                }
+                None => {
+                    if let Some(column) = index.get(*field) {
+                        columns.insert(*field, *column);
+                    }
+                }
+            }
+        }
+        if !columns.contains_key(key) {
+            return Err(anyhow!("The file needs a \"{}\" column", key));
+        }
+
+        Ok(Self { columns })
+    }
+
+    /// The trimmed cell, `None` when the column is absent or the cell is blank
+    fn get<'a>(&self, row: &'a [String], field: &str) -> Option<&'a str> {
+        self.columns
+            .get(field)
+            .and_then(|i| row.get(*i))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// Tallies one import for the response
+struct ImportReport {
+    dry_run: bool,
+    applied: bool,
+    rows: usize,
+    created: usize,
+    updated: usize,
+    errors: Vec<RowErrorResponse>,
+}
+
+impl ImportReport {
+    fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            applied: false,
+            rows: 0,
+            created: 0,
+            updated: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    fn reject(&mut self, row: usize, errors: FieldErrors) {
+        self.errors.extend(errors.into_iter().map(|(field, message)| RowErrorResponse {
+            row,
+            field: field.map(str::to_string),
+            message,
+        }));
+    }
+
+    /// What the valid rows create and update (or would, on a dry run)
+    fn count(&mut self, is_new: impl Iterator<Item = bool>) {
+        for is_new in is_new {
+            if is_new {
+                self.created += 1;
+            } else {
+                self.updated += 1;
+            }
+        }
+    }
+
+    fn should_apply(&self) -> bool {
+        !self.dry_run && self.errors.is_empty()
+    }
+
+    fn into_response(self) -> SpreadsheetImportResponse {
+        SpreadsheetImportResponse {
+            dry_run: self.dry_run,
+            applied: self.applied,
+            rows: self.rows,
+            created: self.created,
+            updated: self.updated,
+            errors: self.errors,
+        }
+    }
+}
+
+/// Parses the upload; row 0 of the result is the header
+fn read_sheet(req: &SpreadsheetImportRequest) -> Result<Vec<Vec<String>>> {
+    let format: SheetFormat = req.format.parse()?;
+    let rows = read_rows(format, &req.content).map_err(|e| anyhow!("Could not read the file: {:#}", e))?;
+
+    if rows.first().is_none_or(|header| header.is_empty()) {
+        return Err(anyhow!("The file has no header row"));
+    }
+    let data = rows.iter().skip(1).filter(|r| !is_blank(r)).count();
+    if data == 0 {
+        return Err(anyhow!("The file has no data rows"));
+    }
+    if data > MAX_IMPORT_ROWS {
+        return Err(anyhow!("Too many rows: {} (max {} per file)", data, MAX_IMPORT_ROWS));
+    }
+
+    Ok(rows)
+}
+
+/// Non-blank rows after the header, with their spreadsheet row number
+fn data_rows(rows: &[Vec<String>]) -> impl Iterator<Item = (usize, &[String])> {
+    rows.iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, row)| !is_blank(row))
+        .map(|(i, row)| (i + 1, row.as_slice()))
+}
+
+fn is_blank(row: &[String]) -> bool {
+    row.iter().all(|c| c.trim().is_empty())
+}
+
+fn push_err(errors: &mut FieldErrors, field: &'static str, result: Result<()>) {
+    if let Err(e) = result {
+        errors.push((Some(field), e.to_string()));
+    }
+}
+
+/// Parses a cell when there is one, recording why it is invalid
+fn parse_field<T>(
+    errors: &mut FieldErrors,
+    columns: &ColumnMap,
+    row: &[String],
+    field: &'static str,
+    parse: impl FnOnce(&str) -> Result<T>,
+) -> Option<T> {
+    match columns.get(row, field).map(parse) {
+        Some(Ok(value)) => Some(value),
+        Some(Err(e)) => {
+            errors.push((Some(field), e.to_string()));
+            None
+        }
+        None => None,
+    }
+}
+
+fn header_cells(fields: &[&str], extra: &[&str]) -> Vec<Cell> {
+    fields.iter().chain(extra).map(|f| Cell::Text(f.to_string())).collect()
+}
+
+fn text(value: Option<&str>) -> Cell {
+    value.map_or(Cell::Empty, |v| Cell::Text(v.to_string()))
+}
+
+/// `290.00` for 290.00 THB
+fn major_units(money: &Money) -> String {
+    let formatted = money.to_string();
+    formatted
+        .rsplit_once(' ')
+        .map_or(formatted.clone(), |(amount, _)| amount.to_string())
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" => Some(true),
+        "false" | "no" | "n" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whole numbers; spreadsheets sometimes hand back `250.0`
+fn parse_whole(value: &str) -> Option<i32> {
+    value.parse::<i32>().ok().or_else(|| {
+        value
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.fract() == 0.0 && n.abs() <= i32::MAX as f64)
+            .map(|n| n as i32)
+    })
+}
+
+/// `YYYY-MM-DD`, or the day serial an XLSX date cell is stored as
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date);
+    }
+    // Excel นับวันจาก 1899-12-30 (รวมวันที่ 29 ก.พ. 1900 ที่ไม่มีจริง)
+    let serial = value.parse::<f64>().ok().filter(|n| (1.0..=2_958_465.0).contains(n))?;
+    NaiveDate::from_ymd_opt(1899, 12, 30)?.checked_add_days(Days::new(serial.trunc() as u64))
+}
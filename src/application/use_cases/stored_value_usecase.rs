@@ -0,0 +1,204 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use tracing::warn;
+
+use crate::application::{
+    authorization::{ensure_any_role, ensure_staff, ROLE_ADMIN},
+    dtos::{
+        auth_dto::UserInfo,
+        stored_value_dto::{
+            AccountLedgerResponse, AdjustBalanceRequest, ExpiryRunResponse, GiftCardCodeRequest,
+            IssueGiftCardRequest, IssuedGiftCardResponse, StoredValueAccountResponse,
+            StoredValueEntryResponse,
+        },
+    },
+};
+use crate::domain::{
+    entities::{
+        stored_value_account::StoredValueAccountEntity,
+        stored_value_entry::{EntryReason, StoredValueEntryEntity},
+    },
+    repositories::stored_value_repository::StoredValueRepository,
+    value_objects::{gift_card_code::GiftCardCode, money::Currency},
+};
+
+/// StoredValueUseCase — gift cards and store credit: issuance, balance
+/// lookups, admin adjustments and the expiry write-off.
+/// Spending a balance is a payment method, see PaymentUseCase.
+pub struct StoredValueUseCase {
+    stored_value_repo: Arc<dyn StoredValueRepository>,
+}
+
+impl StoredValueUseCase {
+    pub fn new(stored_value_repo: Arc<dyn StoredValueRepository>) -> Self {
+        Self { stored_value_repo }
+    }
+
+    /// Staff: issue a gift card. The code is only ever returned here.
+    pub async fn issue_gift_card(
+        &self,
+        caller: &UserInfo,
+        req: IssueGiftCardRequest,
+    ) -> Result<IssuedGiftCardResponse> {
+        ensure_staff(caller)?;
+
+        if req.amount <= 0 {
+            return Err(anyhow!("Gift card amount must be positive"));
+        }
+        let currency = match req.currency {
+            Some(c) => Currency::new(&c).map_err(|e| anyhow!("{}", e))?,
+            None => Currency::thb(),
+        };
+
+        let code = GiftCardCode::generate();
+        let account = StoredValueAccountEntity::gift_card(&code, currency, req.expires_at, caller.id)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let account = self
+            .stored_value_repo
+            .create(&account, req.amount)
+            .await
+            .map_err(|e| anyhow!("Failed to issue gift card: {}", e))?;
+
+        Ok(IssuedGiftCardResponse {
+            code: code.formatted(),
+            account: StoredValueAccountResponse::from(account),
+        })
+    }
+
+    /// Anyone holding a card can check what is left on it
+    pub async fn check_gift_card(&self, req: GiftCardCodeRequest) -> Result<StoredValueAccountResponse> {
+        let account = self.find_gift_card(&req.code).await?;
+        Ok(StoredValueAccountResponse::from(account))
+    }
+
+    /// The caller's store credit (empty if none was ever issued)
+    pub async fn get_store_credit(&self, user_id: i32) -> Result<StoredValueAccountResponse> {
+        let currency = Currency::thb();
+        let account = self
+            .stored_value_repo
+            .find_store_credit(user_id, currency.as_str())
+            .await
+            .map_err(|e| anyhow!("Database error while fetching store credit: {}", e))?
+            .unwrap_or_else(|| StoredValueAccountEntity::store_credit(user_id, currency));
+
+        Ok(StoredValueAccountResponse::from(account))
+    }
+
+    /// Admin: balance and ledger of a gift card, by its code
+    pub async fn lookup_gift_card(
+        &self,
+        caller: &UserInfo,
+        req: GiftCardCodeRequest,
+    ) -> Result<AccountLedgerResponse> {
+        ensure_any_role(caller, &[ROLE_ADMIN])?;
+
+        let account = self.find_gift_card(&req.code).await?;
+        self.ledger(account).await
+    }
+
+    /// Admin: balance and ledger of any account
+    pub async fn lookup_account(&self, caller: &UserInfo, account_id: i32) -> Result<AccountLedgerResponse> {
+        ensure_any_role(caller, &[ROLE_ADMIN])?;
+
+        let account = self.find_account(account_id).await?;
+        self.ledger(account).await
+    }
+
+    /// Admin: correct a balance. Recorded with the reason and who made it.
+    pub async fn adjust_balance(
+        &self,
+        caller: &UserInfo,
+        account_id: i32,
+        req: AdjustBalanceRequest,
+    ) -> Result<AccountLedgerResponse> {
+        ensure_any_role(caller, &[ROLE_ADMIN])?;
+
+        let account = self.find_account(account_id).await?;
+        let entry = StoredValueEntryEntity::new(
+            account.id,
+            req.amount,
+            EntryReason::Adjustment,
+            None,
+            Some(req.reason),
+            Some(caller.id),
+        )
+        .map_err(|e| anyhow!("{}", e))?;
+
+        self.stored_value_repo
+            .record(&entry)
+            .await
+            .map_err(|e| anyhow!("Failed to adjust balance: {}", e))?;
+
+        let account = self.find_account(account_id).await?;
+        self.ledger(account).await
+    }
+
+    /// Writes off balances past their expiry. Meant for a background worker.
+    pub async fn expire_balances(&self, now: DateTime<Utc>, limit: i64) -> Result<ExpiryRunResponse> {
+        let accounts = self
+            .stored_value_repo
+            .find_expired(now, limit)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch expired balances: {}", e))?;
+
+        let mut summary = ExpiryRunResponse {
+            accounts_expired: 0,
+            amount_expired: 0,
+        };
+
+        for account in accounts {
+            let entry = StoredValueEntryEntity::new(
+                account.id,
+                -account.balance,
+                EntryReason::Expiry,
+                None,
+                None,
+                None,
+            )
+            .map_err(|e| anyhow!("{}", e))?;
+
+            // ยอดอาจถูกใช้ไปพร้อมกันระหว่างรอบ ข้ามไปรอบหน้า
+            if let Err(e) = self.stored_value_repo.record(&entry).await {
+                warn!("Failed to expire account {}: {}", account.id, e);
+                continue;
+            }
+            summary.accounts_expired += 1;
+            summary.amount_expired += account.balance;
+        }
+
+        Ok(summary)
+    }
+
+    async fn ledger(&self, account: StoredValueAccountEntity) -> Result<AccountLedgerResponse> {
+        let entries = self
+            .stored_value_repo
+            .find_entries(account.id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch ledger: {}", e))?;
+
+        Ok(AccountLedgerResponse {
+            account: StoredValueAccountResponse::from(account),
+            entries: entries.into_iter().map(StoredValueEntryResponse::from).collect(),
+        })
+    }
+
+    async fn find_gift_card(&self, code: &str) -> Result<StoredValueAccountEntity> {
+        let code = GiftCardCode::new(code.to_string()).map_err(|e| anyhow!("{}", e))?;
+
+        self.stored_value_repo
+            .find_by_code_hash(&code.hash())
+            .await
+            .map_err(|e| anyhow!("Database error while fetching gift card: {}", e))?
+            .ok_or_else(|| anyhow!("Gift card not found"))
+    }
+
+    async fn find_account(&self, id: i32) -> Result<StoredValueAccountEntity> {
+        self.stored_value_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching account: {}", e))?
+            .ok_or_else(|| anyhow!("Account not found"))
+    }
+}
@@ -1,20 +1,44 @@
 use std::sync::Arc;
 use anyhow::{Result, anyhow};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
 
 use crate::application::dtos::user_dto::{
-    CreateUserRequest, RoleSummary, UpdatePasswordRequest, UpdateUserRequest, UserResponse,
+    AddressRequest, AddressResponse, CreateUserRequest, RoleSummary, UpdatePasswordRequest,
+    UpdateUserRequest, UploadAvatarRequest, UserResponse,
 };
 use crate::domain::{
-    entities::user::UserEntity,
-    repositories::{role_repository::RoleRepository, user_repository::UserRepository},
-    value_objects::{person_name::PersonName, age::Age},
+    entities::{address::AddressEntity, user::UserEntity},
+    repositories::{
+        address_repository::AddressRepository,
+        role_repository::RoleRepository,
+        user_repository::UserRepository,
+    },
+    value_objects::{
+        person_name::PersonName,
+        age::Age,
+        user_avatar::{AvatarVariant, UserAvatar},
+    },
 };
-use crate::infrastructure::argon2::PasswordService;
+use crate::infrastructure::{
+    argon2::PasswordService, avatar_image, file_storage::FileStorage, uploaded_image,
+};
+
+/// Max saved addresses per user
+const MAX_ADDRESSES: usize = 20;
+/// Largest avatar upload accepted
+const MAX_AVATAR_BYTES: usize = 5 * 1024 * 1024;
+/// Larger pictures are refused before decoding
+const MAX_AVATAR_PIXELS: u64 = 25_000_000;
 
 pub struct UserUseCase {
     user_repo: Arc<dyn UserRepository>,
     role_repo: Arc<dyn RoleRepository>,
     password_repo: Arc<dyn PasswordService>,
+    address_repo: Arc<dyn AddressRepository>,
+    storage: Arc<dyn FileStorage>,
+    /// Origin the stored files are served from, without trailing slash
+    public_base_url: String,
 }
 
 impl UserUseCase {
@@ -22,11 +46,17 @@ impl UserUseCase {
         user_repo: Arc<dyn UserRepository>,
         role_repo: Arc<dyn RoleRepository>,
         password_repo: Arc<dyn PasswordService>,
+        address_repo: Arc<dyn AddressRepository>,
+        storage: Arc<dyn FileStorage>,
+        public_base_url: String,
     ) -> Self {
         Self {
             user_repo,
             role_repo,
             password_repo,
+            address_repo,
+            storage,
+            public_base_url,
         }
     }
 
@@ -61,19 +91,19 @@ impl UserUseCase {
             .map_err(|e| anyhow!("Failed to save user: {}", e))?;
         user.id = user_id;
 
-        if let Some(role_ids) = req.role_ids.clone() {
-            if !role_ids.is_empty() {
-                let roles = self.role_repo.find_by_ids(&role_ids).await.map_err(|e| {
-                    anyhow!("Failed to fetch roles: {}", e)
-                })?;
-                if roles.len() != role_ids.len() {
-                    return Err(anyhow!("Some roles not found"));
-                }
-                self.user_repo
-                    .assign_roles(user_id, &role_ids)
-                    .await
-                    .map_err(|e| anyhow!("Failed to assign roles: {}", e))?;
+        if let Some(role_ids) = req.role_ids.clone()
+            && !role_ids.is_empty()
+        {
+            let roles = self.role_repo.find_by_ids(&role_ids).await.map_err(|e| {
+                anyhow!("Failed to fetch roles: {}", e)
+            })?;
+            if roles.len() != role_ids.len() {
+                return Err(anyhow!("Some roles not found"));
             }
+            self.user_repo
+                .assign_roles(user_id, &role_ids)
+                .await
+                .map_err(|e| anyhow!("Failed to assign roles: {}", e))?;
         }
 
         let roles = self
@@ -190,6 +220,9 @@ impl UserUseCase {
             .delete(id)
             .await
             .map_err(|e| anyhow!("Failed to delete user: {}", e))?;
+        if let Some(avatar) = &user.avatar {
+            self.delete_avatar_files(avatar, &[]).await;
+        }
 
         let mut user_response = UserResponse::from(user);
         user_response.roles = roles.into_iter().map(RoleSummary::from).collect();
@@ -335,4 +368,220 @@ impl UserUseCase {
 
         Ok(user_response)
     }
-}
\ No newline at end of file
+
+    // ===================== Avatar =====================
+
+    /// Replaces the user's avatar with a square crop of the uploaded picture
+    pub async fn upload_avatar(
+        &self,
+        user_id: i32,
+        req: UploadAvatarRequest,
+        content: Vec<u8>,
+    ) -> Result<UserResponse> {
+        if self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch user: {}", e))?
+            .is_none()
+        {
+            return Err(anyhow!("User not found"));
+        }
+        if content.len() > MAX_AVATAR_BYTES {
+            return Err(anyhow!("Image is too large (max 5 MB)"));
+        }
+
+        // เชื่อ content type ที่ client ส่งมาไม่ได้ ต้องตรงกับหัวไฟล์จริงด้วย
+        let declared = req.content_type.trim().to_lowercase();
+        if declared != "image/jpeg" && declared != "image/png" {
+            return Err(anyhow!("Unsupported image type {}; upload a JPEG or PNG", declared));
+        }
+        if uploaded_image::sniff_content_type(&content) != Some(declared.as_str()) {
+            return Err(anyhow!("File content is not {}", declared));
+        }
+
+        let digest = hex::encode(Sha256::digest(&content));
+        let rendered = tokio::task::spawn_blocking(move || avatar_image::render(&content, MAX_AVATAR_PIXELS))
+            .await
+            .map_err(|e| anyhow!("Image processing task failed: {}", e))?
+            .map_err(|e| anyhow!("Cannot process image: {}", e))?;
+
+        let mut variants = Vec::with_capacity(rendered.len());
+        for avatar in rendered {
+            let storage_key = format!("avatars/{}/{}-{}.webp", user_id, &digest[..16], avatar.size);
+            self.storage
+                .put(&storage_key, &avatar.content, "image/webp")
+                .await
+                .map_err(|e| anyhow!("Failed to store avatar: {}", e))?;
+            variants.push(AvatarVariant {
+                size: avatar.size,
+                size_bytes: avatar.content.len() as u64,
+                url: format!("{}/{}", self.public_base_url, storage_key),
+                storage_key,
+            });
+        }
+
+        let avatar = UserAvatar {
+            variants,
+            uploaded_at: Utc::now(),
+        };
+        let previous = self
+            .user_repo
+            .update_avatar(user_id, Some(&avatar))
+            .await
+            .map_err(|e| anyhow!("Failed to save avatar: {}", e))?;
+        if let Some(previous) = previous {
+            // อัปโหลดรูปเดิมซ้ำจะได้ key เดิม ห้ามลบ
+            let kept: Vec<&str> = avatar.variants.iter().map(|v| v.storage_key.as_str()).collect();
+            self.delete_avatar_files(&previous, &kept).await;
+        }
+
+        self.get_user_by_id(user_id)
+            .await?
+            .ok_or_else(|| anyhow!("User not found"))
+    }
+
+    pub async fn remove_avatar(&self, user_id: i32) -> Result<UserResponse> {
+        let previous = self
+            .user_repo
+            .update_avatar(user_id, None)
+            .await
+            .map_err(|e| anyhow!("Failed to remove avatar: {}", e))?;
+        if let Some(previous) = previous {
+            self.delete_avatar_files(&previous, &[]).await;
+        }
+
+        self.get_user_by_id(user_id)
+            .await?
+            .ok_or_else(|| anyhow!("User not found"))
+    }
+
+    /// Files of a replaced avatar; failures only leave orphans behind
+    async fn delete_avatar_files(&self, avatar: &UserAvatar, keep: &[&str]) {
+        for variant in &avatar.variants {
+            if keep.contains(&variant.storage_key.as_str()) {
+                continue;
+            }
+            if let Err(e) = self.storage.delete(&variant.storage_key).await {
+                tracing::warn!(key = %variant.storage_key, "Failed to delete replaced avatar: {}", e);
+            }
+        }
+    }
+
+    // ===================== Address book =====================
+
+    pub async fn get_addresses(&self, user_id: i32) -> Result<Vec<AddressResponse>> {
+        let addresses = self.address_repo.find_by_user(user_id).await.map_err(|e| {
+            anyhow!("Failed to fetch addresses: {}", e)
+        })?;
+
+        Ok(addresses.into_iter().map(AddressResponse::from).collect())
+    }
+
+    pub async fn add_address(&self, user_id: i32, req: AddressRequest) -> Result<AddressResponse> {
+        if self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch user: {}", e))?
+            .is_none()
+        {
+            return Err(anyhow!("User not found"));
+        }
+
+        let existing = self.address_repo.find_by_user(user_id).await.map_err(|e| {
+            anyhow!("Failed to fetch addresses: {}", e)
+        })?;
+        if existing.len() >= MAX_ADDRESSES {
+            return Err(anyhow!("Address book is full (max {} addresses)", MAX_ADDRESSES));
+        }
+
+        let postal = req.to_postal_address().map_err(|e| anyhow!("{}", e))?;
+        let mut address = AddressEntity::new(user_id, req.label, postal)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        // ที่อยู่แรกเป็น default ทั้งสองแบบเสมอ
+        address.set_default_shipping(
+            existing.iter().all(|a| !a.is_default_shipping) || req.is_default_shipping == Some(true),
+        );
+        address.set_default_billing(
+            existing.iter().all(|a| !a.is_default_billing) || req.is_default_billing == Some(true),
+        );
+
+        address.id = self
+            .address_repo
+            .save(&address)
+            .await
+            .map_err(|e| anyhow!("Failed to save address: {}", e))?;
+
+        Ok(AddressResponse::from(address))
+    }
+
+    pub async fn update_address(
+        &self,
+        user_id: i32,
+        address_id: i32,
+        req: AddressRequest,
+    ) -> Result<AddressResponse> {
+        let mut address = self.find_address(user_id, address_id).await?;
+
+        let postal = req.to_postal_address().map_err(|e| anyhow!("{}", e))?;
+        address.update(req.label, postal).map_err(|e| anyhow!("{}", e))?;
+
+        // default ถูกย้ายได้ด้วยการตั้งที่อยู่อื่นเป็น default เท่านั้น จึงไม่ปล่อยให้ user ไม่มี default
+        if req.is_default_shipping == Some(true) {
+            address.set_default_shipping(true);
+        }
+        if req.is_default_billing == Some(true) {
+            address.set_default_billing(true);
+        }
+
+        let updated = self
+            .address_repo
+            .update(&address)
+            .await
+            .map_err(|e| anyhow!("Failed to update address: {}", e))?;
+
+        Ok(AddressResponse::from(updated))
+    }
+
+    pub async fn delete_address(&self, user_id: i32, address_id: i32) -> Result<()> {
+        let address = self.find_address(user_id, address_id).await?;
+
+        self.address_repo
+            .delete(address.id)
+            .await
+            .map_err(|e| anyhow!("Failed to delete address: {}", e))?;
+
+        // Hand the default flags over to the oldest remaining address
+        if address.is_default_shipping || address.is_default_billing {
+            let remaining = self.address_repo.find_by_user(user_id).await.map_err(|e| {
+                anyhow!("Failed to fetch addresses: {}", e)
+            })?;
+            if let Some(mut next) = remaining.into_iter().next() {
+                if address.is_default_shipping {
+                    next.set_default_shipping(true);
+                }
+                if address.is_default_billing {
+                    next.set_default_billing(true);
+                }
+                self.address_repo
+                    .update(&next)
+                    .await
+                    .map_err(|e| anyhow!("Failed to update address: {}", e))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The user's address, for checkout and the address book
+    pub async fn find_address(&self, user_id: i32, address_id: i32) -> Result<AddressEntity> {
+        self.address_repo
+            .find_by_id(address_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch address: {}", e))?
+            .filter(|a| a.user_id == user_id)
+            .ok_or_else(|| anyhow!("Address not found"))
+    }
+}
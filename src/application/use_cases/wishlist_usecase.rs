@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+
+use crate::application::dtos::wishlist_dto::{
+    SharedWishlistResponse, StockWatchResponse, WishlistItemRequest, WishlistItemResponse,
+    WishlistRequest, WishlistResponse,
+};
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        stock_subscription::StockSubscriptionEntity,
+        wishlist::WishlistEntity,
+    },
+    repositories::{
+        book_repository::BookRepository,
+        stock_subscription_repository::StockSubscriptionRepository,
+        user_repository::UserRepository,
+        wishlist_repository::WishlistRepository,
+    },
+};
+
+/// Max wishlists per user
+const MAX_WISHLISTS: usize = 20;
+
+/// WishlistUseCase — saved-for-later lists and back-in-stock watches
+pub struct WishlistUseCase {
+    wishlist_repo: Arc<dyn WishlistRepository>,
+    subscription_repo: Arc<dyn StockSubscriptionRepository>,
+    book_repo: Arc<dyn BookRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl WishlistUseCase {
+    pub fn new(
+        wishlist_repo: Arc<dyn WishlistRepository>,
+        subscription_repo: Arc<dyn StockSubscriptionRepository>,
+        book_repo: Arc<dyn BookRepository>,
+        user_repo: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            wishlist_repo,
+            subscription_repo,
+            book_repo,
+            user_repo,
+        }
+    }
+
+    // =========================
+    // Wishlists
+    // =========================
+
+    pub async fn get_wishlists(&self, user_id: i32) -> Result<Vec<WishlistResponse>> {
+        let wishlists = self
+            .wishlist_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch wishlists: {}", e))?;
+
+        let mut responses = Vec::with_capacity(wishlists.len());
+        for wishlist in wishlists {
+            responses.push(self.to_response(wishlist).await?);
+        }
+        Ok(responses)
+    }
+
+    pub async fn get_wishlist(&self, user_id: i32, id: i32) -> Result<WishlistResponse> {
+        let wishlist = self.find_own_wishlist(user_id, id).await?;
+        self.to_response(wishlist).await
+    }
+
+    pub async fn create_wishlist(&self, user_id: i32, req: WishlistRequest) -> Result<WishlistResponse> {
+        let existing = self
+            .wishlist_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch wishlists: {}", e))?;
+
+        if existing.len() >= MAX_WISHLISTS {
+            return Err(anyhow!("Too many wishlists (max {})", MAX_WISHLISTS));
+        }
+
+        let mut wishlist = WishlistEntity::new(user_id, req.name).map_err(|e| anyhow!("{}", e))?;
+        if existing.iter().any(|w| w.name.eq_ignore_ascii_case(&wishlist.name)) {
+            return Err(anyhow!("A wishlist with this name already exists"));
+        }
+
+        wishlist.id = self
+            .wishlist_repo
+            .save(&wishlist)
+            .await
+            .map_err(|e| anyhow!("Failed to save wishlist: {}", e))?;
+
+        self.to_response(wishlist).await
+    }
+
+    pub async fn rename_wishlist(
+        &self,
+        user_id: i32,
+        id: i32,
+        req: WishlistRequest,
+    ) -> Result<WishlistResponse> {
+        let mut wishlist = self.find_own_wishlist(user_id, id).await?;
+        wishlist.rename(req.name).map_err(|e| anyhow!("{}", e))?;
+        self.save_wishlist(wishlist).await
+    }
+
+    pub async fn delete_wishlist(&self, user_id: i32, id: i32) -> Result<()> {
+        let wishlist = self.find_own_wishlist(user_id, id).await?;
+
+        self.wishlist_repo
+            .delete(wishlist.id)
+            .await
+            .map_err(|e| anyhow!("Failed to delete wishlist: {}", e))
+    }
+
+    pub async fn add_item(
+        &self,
+        user_id: i32,
+        id: i32,
+        req: WishlistItemRequest,
+    ) -> Result<WishlistResponse> {
+        let mut wishlist = self.find_own_wishlist(user_id, id).await?;
+
+        let book = self.find_book(req.book_id).await?;
+        if !book.is_active {
+            return Err(anyhow!("Book is not available"));
+        }
+
+        wishlist.add_item(book.id, req.note).map_err(|e| anyhow!("{}", e))?;
+        self.save_wishlist(wishlist).await
+    }
+
+    pub async fn remove_item(&self, user_id: i32, id: i32, book_id: i32) -> Result<WishlistResponse> {
+        let mut wishlist = self.find_own_wishlist(user_id, id).await?;
+        wishlist.remove_item(book_id).map_err(|e| anyhow!("{}", e))?;
+        self.save_wishlist(wishlist).await
+    }
+
+    /// Turns on the public link; calling it again returns the same token.
+    pub async fn share_wishlist(&self, user_id: i32, id: i32) -> Result<WishlistResponse> {
+        let mut wishlist = self.find_own_wishlist(user_id, id).await?;
+        wishlist.share();
+        self.save_wishlist(wishlist).await
+    }
+
+    pub async fn unshare_wishlist(&self, user_id: i32, id: i32) -> Result<WishlistResponse> {
+        let mut wishlist = self.find_own_wishlist(user_id, id).await?;
+        wishlist.unshare();
+        self.save_wishlist(wishlist).await
+    }
+
+    /// Public, read-only view through a share link (no login needed).
+    pub async fn get_shared_wishlist(&self, token: &str) -> Result<SharedWishlistResponse> {
+        let wishlist = self
+            .wishlist_repo
+            .find_by_share_token(token.trim())
+            .await
+            .map_err(|e| anyhow!("Failed to fetch wishlist: {}", e))?
+            .ok_or_else(|| anyhow!("Wishlist not found"))?;
+
+        // แสดงแค่ชื่อจริงของเจ้าของ ไม่เปิดเผยข้อมูลอื่น
+        let owner_name = self
+            .user_repo
+            .find_by_id(wishlist.user_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching user: {}", e))?
+            .map(|u| u.first_name.as_str().to_string())
+            .unwrap_or_default();
+
+        let items = self.item_responses(&wishlist).await?;
+        Ok(SharedWishlistResponse {
+            name: wishlist.name,
+            owner_name,
+            items,
+        })
+    }
+
+    // =========================
+    // Back-in-stock watches
+    // =========================
+
+    pub async fn get_stock_watches(&self, user_id: i32) -> Result<Vec<StockWatchResponse>> {
+        let subscriptions = self
+            .subscription_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch stock watches: {}", e))?;
+
+        let ids: Vec<i32> = subscriptions.iter().map(|s| s.book_id).collect();
+        let books = self.books_by_id(&ids).await?;
+
+        Ok(subscriptions
+            .into_iter()
+            .filter_map(|s| {
+                let book = books.get(&s.book_id)?;
+                Some(StockWatchResponse {
+                    id: s.id,
+                    book_id: s.book_id,
+                    title: book.title.as_str().to_string(),
+                    waiting: s.is_waiting(),
+                    notified_at: s.notified_at,
+                    created_at: s.created_at,
+                })
+            })
+            .collect())
+    }
+
+    /// Asks to be told when an out-of-stock book can be bought again.
+    pub async fn watch_book(&self, user_id: i32, book_id: i32) -> Result<StockWatchResponse> {
+        let book = self.find_book(book_id).await?;
+        if !book.is_active {
+            return Err(anyhow!("Book is not available"));
+        }
+        if book.stock_quantity > 0 {
+            return Err(anyhow!("Book is in stock"));
+        }
+
+        let mut subscription = StockSubscriptionEntity::new(user_id, book.id);
+        subscription.id = self
+            .subscription_repo
+            .save(&subscription)
+            .await
+            .map_err(|e| anyhow!("Failed to save stock watch: {}", e))?;
+
+        Ok(StockWatchResponse {
+            id: subscription.id,
+            book_id: book.id,
+            title: book.title.as_str().to_string(),
+            waiting: true,
+            notified_at: None,
+            created_at: subscription.created_at,
+        })
+    }
+
+    pub async fn unwatch_book(&self, user_id: i32, book_id: i32) -> Result<()> {
+        let subscription = self
+            .subscription_repo
+            .find_by_user_and_book(user_id, book_id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch stock watch: {}", e))?
+            .ok_or_else(|| anyhow!("Stock watch not found"))?;
+
+        self.subscription_repo
+            .delete(subscription.id)
+            .await
+            .map_err(|e| anyhow!("Failed to delete stock watch: {}", e))
+    }
+
+    // =========================
+    // Helpers
+    // =========================
+
+    async fn find_own_wishlist(&self, user_id: i32, id: i32) -> Result<WishlistEntity> {
+        match self
+            .wishlist_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch wishlist: {}", e))?
+        {
+            Some(w) if w.user_id == user_id => Ok(w),
+            _ => Err(anyhow!("Wishlist not found")),
+        }
+    }
+
+    async fn find_book(&self, book_id: i32) -> Result<BookEntity> {
+        self.book_repo
+            .find_by_id(book_id)
+            .await
+            .map_err(|e| anyhow!("Database error while fetching book: {}", e))?
+            .ok_or_else(|| anyhow!("Book not found"))
+    }
+
+    async fn books_by_id(&self, ids: &[i32]) -> Result<HashMap<i32, BookEntity>> {
+        let books = self
+            .book_repo
+            .find_by_ids(ids)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch books: {}", e))?;
+
+        Ok(books.into_iter().map(|b| (b.id, b)).collect())
+    }
+
+    async fn save_wishlist(&self, wishlist: WishlistEntity) -> Result<WishlistResponse> {
+        let updated = self
+            .wishlist_repo
+            .update(&wishlist)
+            .await
+            .map_err(|e| anyhow!("Failed to update wishlist: {}", e))?;
+
+        self.to_response(updated).await
+    }
+
+    async fn item_responses(&self, wishlist: &WishlistEntity) -> Result<Vec<WishlistItemResponse>> {
+        let ids: Vec<i32> = wishlist.items.iter().map(|i| i.book_id).collect();
+        let books = self.books_by_id(&ids).await?;
+
+        Ok(wishlist
+            .items
+            .iter()
+            .filter_map(|item| {
+                let book = books.get(&item.book_id).filter(|b| b.is_active)?;
+                Some(WishlistItemResponse {
+                    book_id: book.id,
+                    isbn: book.isbn.as_str().to_string(),
+                    title: book.title.as_str().to_string(),
+                    author: book.author.clone(),
+                    price: book.price.amount(),
+                    currency: book.price.currency().as_str().to_string(),
+                    in_stock: book.stock_quantity > 0,
+                    note: item.note.clone(),
+                    added_at: item.added_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn to_response(&self, wishlist: WishlistEntity) -> Result<WishlistResponse> {
+        let items = self.item_responses(&wishlist).await?;
+
+        Ok(WishlistResponse {
+            id: wishlist.id,
+            name: wishlist.name,
+            share_token: wishlist.share_token.map(|t| t.as_str().to_string()),
+            items,
+            created_at: wishlist.created_at,
+            updated_at: wishlist.updated_at,
+        })
+    }
+}
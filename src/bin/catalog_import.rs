@@ -0,0 +1,68 @@
+// =============================================================================
+// Catalog import worker (schedule with cron, every few minutes)
+// =============================================================================
+//   cargo run --bin catalog_import           work every due import, then exit
+//   cargo run --bin catalog_import -- --once only the next due import
+// =============================================================================
+// Imports cut off halfway (crash, deploy) are picked up again from their last
+// checkpoint once the claim lease runs out.
+
+use std::sync::Arc;
+
+use clean_architecture_template::{
+    adapters::postgres::{
+        postgres_connector,
+        repositories::{
+            book_repository::PostgresBookRepository,
+            catalog_import_repository::PostgresCatalogImportRepository,
+        },
+    },
+    application::use_cases::catalog_import_usecase::CatalogImportUseCase,
+    infrastructure::config,
+};
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = dotenvy::dotenv() {
+        eprintln!("Warning: couldn't load .env file: {}", e);
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_target(false)
+        .compact()
+        .init();
+
+    if let Err(e) = run().await {
+        error!("Catalog import failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let once = std::env::args().any(|a| a == "--once");
+
+    let app_config = config::load()?;
+    let pool = postgres_connector::establish_connection(&app_config.database.url).await?;
+
+    let usecase = CatalogImportUseCase::new(
+        Arc::new(PostgresCatalogImportRepository::new(pool.clone())),
+        Arc::new(PostgresBookRepository::new(pool)),
+        app_config.catalog_import.inbox_dir,
+    );
+
+    let mut jobs = 0;
+    while usecase.run_next().await?.is_some() {
+        jobs += 1;
+        if once {
+            break;
+        }
+    }
+    info!(jobs, "Catalog imports done");
+    Ok(())
+}
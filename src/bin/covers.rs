@@ -0,0 +1,130 @@
+// =============================================================================
+// Book cover upload
+// =============================================================================
+//   cargo run --bin covers -- upload 12 cover.jpg --as staff@shop.test
+//   cargo run --bin covers -- remove 12 --as staff@shop.test
+// =============================================================================
+// Covers go to the storage backend from STORAGE_BACKEND (local directory or
+// an S3 bucket) and are linked under STORAGE_PUBLIC_BASE_URL. --as is the
+// account the work is done for, and its roles decide what is allowed.
+
+use std::{path::Path, sync::Arc};
+
+use anyhow::{anyhow, Context};
+use clean_architecture_template::{
+    adapters::postgres::{
+        postgres_connector,
+        repositories::{book_repository::PostgresBookRepository, user_repository::PostgresUserRepository},
+    },
+    application::{
+        dtos::{auth_dto::UserInfo, cover_dto::UploadCoverRequest},
+        use_cases::book_cover_usecase::BookCoverUseCase,
+    },
+    domain::repositories::user_repository::UserRepository,
+    infrastructure::{config, file_storage},
+};
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+
+const USAGE: &str = "usage: covers <upload <book_id> <file.jpg|file.png> | remove <book_id>> --as <email>";
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = dotenvy::dotenv() {
+        eprintln!("Warning: couldn't load .env file: {}", e);
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_target(false)
+        .compact()
+        .init();
+
+    if let Err(e) = run().await {
+        error!("Cover job failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let mut positional = Vec::new();
+    let mut operator = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--as" => operator = Some(args.next().ok_or_else(|| anyhow!(USAGE))?),
+            _ => positional.push(arg),
+        }
+    }
+    let operator = operator.ok_or_else(|| anyhow!(USAGE))?;
+    let book_id: i32 = positional
+        .get(1)
+        .ok_or_else(|| anyhow!(USAGE))?
+        .parse()
+        .context("book_id must be a number")?;
+
+    let app_config = config::load()?;
+    let pool = postgres_connector::establish_connection(&app_config.database.url).await?;
+
+    let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
+    let caller = load_caller(user_repo.as_ref(), &operator).await?;
+    let storage = file_storage::from_config(&app_config.storage)?;
+    let usecase = BookCoverUseCase::new(
+        Arc::new(PostgresBookRepository::new(pool)),
+        storage.clone(),
+        app_config.storage.public_base_url.clone(),
+    );
+
+    match (positional[0].as_str(), positional.get(2)) {
+        ("upload", Some(file)) if positional.len() == 3 => {
+            let content_type = match Path::new(file)
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.to_lowercase())
+                .as_deref()
+            {
+                Some("jpg" | "jpeg") => "image/jpeg",
+                Some("png") => "image/png",
+                _ => return Err(anyhow!("Cannot tell the type of {}; use .jpg or .png", file)),
+            };
+            let content = std::fs::read(file).with_context(|| format!("Cannot read {}", file))?;
+            let req = UploadCoverRequest {
+                content_type: content_type.to_string(),
+            };
+            let cover = usecase.upload_cover(&caller, book_id, req, content).await?;
+
+            for v in &cover.variants {
+                info!(size = %v.size, format = %v.format, width = v.width, height = v.height, bytes = v.size_bytes, "{}", v.url);
+            }
+            info!(book_id, backend = storage.backend(), blurhash = %cover.blurhash, "Cover uploaded");
+        }
+        ("remove", None) => {
+            usecase.remove_cover(&caller, book_id).await?;
+            info!(book_id, "Cover removed");
+        }
+        _ => return Err(anyhow!(USAGE)),
+    }
+
+    Ok(())
+}
+
+/// The operator's account and roles, as a signed-in caller would have them
+async fn load_caller(user_repo: &dyn UserRepository, email: &str) -> anyhow::Result<UserInfo> {
+    let user = user_repo
+        .find_by_email(&email.trim().to_lowercase())
+        .await?
+        .filter(|u| u.is_active)
+        .ok_or_else(|| anyhow!("No active account for {}", email))?;
+    let roles = user_repo.find_roles(user.id).await?;
+
+    Ok(UserInfo {
+        id: user.id,
+        email: user.email.as_str().to_string(),
+        fname: user.first_name.as_str().to_string(),
+        lname: user.last_name.as_str().to_string(),
+        roles: roles.into_iter().map(|r| r.name.as_str().to_string()).collect(),
+    })
+}
@@ -0,0 +1,84 @@
+// =============================================================================
+// Loyalty job (schedule with cron, daily)
+// =============================================================================
+//   cargo run --bin loyalty                  expire points, then sync tiers
+//   cargo run --bin loyalty -- --tiers       only sync tier roles
+// =============================================================================
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use clean_architecture_template::{
+    adapters::postgres::{
+        postgres_connector,
+        repositories::{
+            loyalty_repository::PostgresLoyaltyRepository,
+            role_repository::PostgresRoleRepository,
+            user_repository::PostgresUserRepository,
+        },
+    },
+    application::use_cases::loyalty_usecase::LoyaltyUseCase,
+    infrastructure::config,
+};
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+
+const BATCH_SIZE: i64 = 500;
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = dotenvy::dotenv() {
+        eprintln!("Warning: couldn't load .env file: {}", e);
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_target(false)
+        .compact()
+        .init();
+
+    if let Err(e) = run().await {
+        error!("Loyalty job failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let tiers_only = std::env::args().any(|a| a == "--tiers");
+
+    let app_config = config::load()?;
+    let pool = postgres_connector::establish_connection(&app_config.database.url).await?;
+
+    let usecase = LoyaltyUseCase::new(
+        Arc::new(PostgresLoyaltyRepository::new(pool.clone())),
+        Arc::new(PostgresRoleRepository::new(pool.clone())),
+        Arc::new(PostgresUserRepository::new(pool)),
+    );
+    let now = Utc::now();
+
+    if !tiers_only {
+        // วนจนหมด batch เพราะ member ที่ตัดแต้มแล้วจะไม่ถูกดึงซ้ำ
+        loop {
+            let summary = usecase.expire_points(now, BATCH_SIZE).await?;
+            info!(
+                members = summary.members,
+                points = summary.points_expired,
+                "Points expired"
+            );
+            if summary.members < BATCH_SIZE as usize {
+                break;
+            }
+        }
+    }
+
+    let summary = usecase.sync_tiers(now).await?;
+    info!(
+        checked = summary.members_checked,
+        changed = summary.members_changed,
+        "Tier roles synced"
+    );
+    Ok(())
+}
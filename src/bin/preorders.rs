@@ -0,0 +1,133 @@
+// =============================================================================
+// Pre-order job (schedule with cron, at least daily)
+// =============================================================================
+//   cargo run --bin preorders                record prices, release due orders
+//   cargo run --bin preorders -- --prices    only record today's prices
+// =============================================================================
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use clean_architecture_template::{
+    adapters::postgres::{
+        postgres_connector,
+        repositories::{
+            address_repository::PostgresAddressRepository,
+            book_price_repository::PostgresBookPriceRepository,
+            book_repository::PostgresBookRepository,
+            cart_repository::PostgresCartRepository,
+            coupon_repository::PostgresCouponRepository,
+            stock_subscription_repository::PostgresStockSubscriptionRepository,
+            loyalty_repository::PostgresLoyaltyRepository,
+            order_repository::PostgresOrderRepository,
+            payment_repository::PostgresPaymentRepository,
+            promotion_repository::PostgresPromotionRepository,
+            shipping_repository::PostgresShippingRepository,
+            stored_value_repository::PostgresStoredValueRepository,
+            store_repository::PostgresStoreRepository,
+            tax_rate_repository::PostgresTaxRateRepository,
+            user_repository::PostgresUserRepository,
+        },
+    },
+    application::use_cases::{
+        order_usecase::OrderUseCase,
+        payment_usecase::PaymentUseCase,
+        preorder_usecase::PreorderUseCase,
+        pricing_usecase::PricingUseCase,
+        promotion_usecase::PromotionUseCase,
+        shipping_usecase::ShippingUseCase,
+    },
+    infrastructure::{
+        config,
+        mock_payment_gateway::MockPaymentGateway,
+        table_shipping_rate_provider::TableShippingRateProvider,
+    },
+};
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+
+const BATCH_SIZE: i64 = 200;
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = dotenvy::dotenv() {
+        eprintln!("Warning: couldn't load .env file: {}", e);
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_target(false)
+        .compact()
+        .init();
+
+    if let Err(e) = run().await {
+        error!("Pre-order job failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let prices_only = std::env::args().any(|a| a == "--prices");
+
+    let app_config = config::load()?;
+    let pool = postgres_connector::establish_connection(&app_config.database.url).await?;
+
+    let order_repo = Arc::new(PostgresOrderRepository::new(pool.clone()));
+    let book_repo = Arc::new(PostgresBookRepository::new(pool.clone()));
+    let pricing = Arc::new(PricingUseCase::new(
+        book_repo.clone(),
+        Arc::new(PostgresStoreRepository::new(pool.clone())),
+        Arc::new(PostgresBookPriceRepository::new(pool.clone())),
+        Arc::new(PostgresTaxRateRepository::new(pool.clone())),
+    ));
+    // TODO: swap in the production PSP adapter; the mock only knows intents
+    // created in the same process
+    let payments = Arc::new(PaymentUseCase::new(
+        Arc::new(PostgresPaymentRepository::new(pool.clone())),
+        order_repo.clone(),
+        Arc::new(PostgresStoredValueRepository::new(pool.clone())),
+        Arc::new(PostgresLoyaltyRepository::new(pool.clone())),
+        Arc::new(MockPaymentGateway::new(&app_config.payment.webhook_secret)),
+    ));
+    let orders = Arc::new(OrderUseCase::new(
+        order_repo.clone(),
+        Arc::new(PostgresCartRepository::new(pool.clone())),
+        book_repo.clone(),
+        Arc::new(PostgresStockSubscriptionRepository::new(pool.clone())),
+        Arc::new(PostgresAddressRepository::new(pool.clone())),
+        Arc::new(PromotionUseCase::new(
+            Arc::new(PostgresPromotionRepository::new(pool.clone())),
+            Arc::new(PostgresCouponRepository::new(pool.clone())),
+            Arc::new(PostgresUserRepository::new(pool.clone())),
+        )),
+        pricing.clone(),
+        Arc::new(ShippingUseCase::new(
+            Arc::new(PostgresShippingRepository::new(pool)),
+            Arc::new(TableShippingRateProvider::new()),
+        )),
+        payments.clone(),
+    ));
+    let usecase = PreorderUseCase::new(order_repo, book_repo, pricing, payments, orders);
+
+    if prices_only {
+        let summary = usecase.track_prices().await?;
+        info!(
+            books = summary.books_checked,
+            lines_lowered = summary.lines_lowered,
+            "Pre-order prices recorded"
+        );
+        return Ok(());
+    }
+
+    let summary = usecase.release_due(Utc::now().date_naive(), BATCH_SIZE).await?;
+    info!(
+        released = summary.released,
+        failed = summary.failed,
+        price_protection = summary.price_protection_total,
+        "Pre-orders released"
+    );
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// =============================================================================
+// Search index maintenance
+// =============================================================================
+//   cargo run --bin reindex           rebuild the index from the database
+//   cargo run --bin reindex -- --sync apply book changes since the last run
+// =============================================================================
+
+use std::sync::Arc;
+
+use clean_architecture_template::{
+    adapters::postgres::{
+        postgres_connector,
+        repositories::{
+            book_repository::PostgresBookRepository,
+            search_feed_repository::PostgresSearchFeedRepository,
+        },
+    },
+    application::use_cases::search_index_usecase::SearchIndexUseCase,
+    infrastructure::{config, tantivy_search_index::TantivySearchIndex},
+};
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+
+const BATCH_SIZE: i64 = 500;
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = dotenvy::dotenv() {
+        eprintln!("Warning: couldn't load .env file: {}", e);
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_target(false)
+        .compact()
+        .init();
+
+    if let Err(e) = run().await {
+        error!("Reindex failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let sync_only = std::env::args().any(|a| a == "--sync");
+
+    let app_config = config::load()?;
+    let pool = postgres_connector::establish_connection(&app_config.database.url).await?;
+
+    let synonyms = match &app_config.search.synonyms_file {
+        Some(path) => TantivySearchIndex::load_synonyms(path)?,
+        None => Vec::new(),
+    };
+    let index = TantivySearchIndex::open(&app_config.search.index_dir, synonyms)?;
+
+    let usecase = SearchIndexUseCase::new(
+        Arc::new(PostgresSearchFeedRepository::new(pool.clone())),
+        Arc::new(PostgresBookRepository::new(pool)),
+        Arc::new(index),
+    );
+
+    let summary = if sync_only {
+        usecase.sync_changes(BATCH_SIZE).await?
+    } else {
+        usecase.rebuild(BATCH_SIZE).await?
+    };
+    info!(
+        indexed = summary.indexed,
+        removed = summary.removed,
+        "Search index up to date"
+    );
+    Ok(())
+}
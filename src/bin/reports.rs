@@ -0,0 +1,148 @@
+// =============================================================================
+// Sales reports (schedule with cron, hourly, to refresh the report views)
+// =============================================================================
+//   cargo run --bin reports                  refresh the report views
+//   cargo run --bin reports -- export revenue sales.csv --as boss@shop.test \
+//       --from 2026-01-01 --to 2026-03-31 --granularity month
+//   cargo run --bin reports -- export units authors.csv --as boss@shop.test --by author
+//   cargo run --bin reports -- export customers top.xlsx --as boss@shop.test \
+//       --store TH-ONLINE --limit 100
+// =============================================================================
+// Reports read the views as of the last refresh. The export format comes from
+// the file extension (.csv or .xlsx); --as needs the REPORTS or ADMIN role.
+
+use std::{fs::File, io::BufWriter, path::Path, sync::Arc};
+
+use anyhow::{anyhow, Context};
+use chrono::NaiveDate;
+use clean_architecture_template::{
+    adapters::postgres::{
+        postgres_connector,
+        repositories::{
+            report_repository::PostgresReportRepository,
+            store_repository::PostgresStoreRepository,
+            user_repository::PostgresUserRepository,
+        },
+    },
+    application::{
+        dtos::{
+            auth_dto::UserInfo,
+            report_dto::{ReportExportQuery, SalesReportQuery},
+        },
+        use_cases::report_usecase::ReportUseCase,
+    },
+    domain::repositories::user_repository::UserRepository,
+    infrastructure::config,
+};
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+
+const USAGE: &str = "usage: reports [export <revenue|units|customers> <file.csv|file.xlsx> --as <email> [--from YYYY-MM-DD] [--to YYYY-MM-DD] [--store CODE] [--granularity day|week|month] [--by title|author|category|store] [--limit N]]";
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = dotenvy::dotenv() {
+        eprintln!("Warning: couldn't load .env file: {}", e);
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_target(false)
+        .compact()
+        .init();
+
+    if let Err(e) = run().await {
+        error!("Reports job failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let mut positional = Vec::new();
+    let mut operator = None;
+    let mut by = None;
+    let mut query = SalesReportQuery::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| anyhow!(USAGE));
+        match arg.as_str() {
+            "--as" => operator = Some(value()?),
+            "--from" => query.from = Some(parse_date(&value()?)?),
+            "--to" => query.to = Some(parse_date(&value()?)?),
+            "--store" => query.store = Some(value()?),
+            "--granularity" => query.granularity = Some(value()?),
+            "--by" => by = Some(value()?),
+            "--limit" => {
+                let limit = value()?;
+                query.limit = Some(limit.parse().map_err(|_| anyhow!("Invalid limit: {}", limit))?);
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let app_config = config::load()?;
+    let pool = postgres_connector::establish_connection(&app_config.database.url).await?;
+
+    let usecase = ReportUseCase::new(
+        Arc::new(PostgresReportRepository::new(pool.clone())),
+        Arc::new(PostgresStoreRepository::new(pool.clone())),
+    );
+
+    match positional.as_slice() {
+        [] => {
+            let refresh = usecase.refresh().await?;
+            info!(refreshed_at = %refresh.refreshed_at, "Report views refreshed");
+        }
+        [action, report, file] if action == "export" => {
+            let operator = operator.ok_or_else(|| anyhow!(USAGE))?;
+            let user_repo = PostgresUserRepository::new(pool);
+            let caller = load_caller(&user_repo, &operator).await?;
+
+            let path = Path::new(file);
+            let format = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .ok_or_else(|| anyhow!("Cannot tell the format of {}; use .csv or .xlsx", file))?
+                .to_string();
+            let export = ReportExportQuery {
+                report: report.clone(),
+                by,
+                format: Some(format),
+            };
+
+            let output = BufWriter::new(File::create(path).with_context(|| format!("Cannot create {}", file))?);
+            usecase.export(&caller, export, query, output).await?;
+            info!(file = %file, "Report written");
+        }
+        _ => return Err(anyhow!(USAGE)),
+    }
+
+    Ok(())
+}
+
+fn parse_date(value: &str) -> anyhow::Result<NaiveDate> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("Invalid date {}; use YYYY-MM-DD", value))
+}
+
+/// The operator's account and roles, as a signed-in caller would have them
+async fn load_caller(user_repo: &dyn UserRepository, email: &str) -> anyhow::Result<UserInfo> {
+    let user = user_repo
+        .find_by_email(&email.trim().to_lowercase())
+        .await?
+        .filter(|u| u.is_active)
+        .ok_or_else(|| anyhow!("No active account for {}", email))?;
+    let roles = user_repo.find_roles(user.id).await?;
+
+    Ok(UserInfo {
+        id: user.id,
+        email: user.email.as_str().to_string(),
+        fname: user.first_name.as_str().to_string(),
+        lname: user.last_name.as_str().to_string(),
+        roles: roles.into_iter().map(|r| r.name.as_str().to_string()).collect(),
+    })
+}
@@ -0,0 +1,173 @@
+// =============================================================================
+// Spreadsheet import / export for books and users
+// =============================================================================
+//   cargo run --bin spreadsheet -- import books prices.xlsx --as staff@shop.test
+//   cargo run --bin spreadsheet -- import books prices.csv --as staff@shop.test --apply
+//   cargo run --bin spreadsheet -- import users people.csv --as admin@shop.test \
+//       --map email="E-mail" --map phone="Mobile" --apply
+//   cargo run --bin spreadsheet -- export books books.xlsx --as staff@shop.test
+// =============================================================================
+// The format comes from the file extension (.csv or .xlsx). Imports are a dry
+// run unless --apply is given; either way every row error is printed. --as is
+// the account the work is done for, and its roles decide what is allowed.
+
+use std::{collections::HashMap, fs::File, io::BufWriter, path::Path, sync::Arc};
+
+use anyhow::{anyhow, Context};
+use clean_architecture_template::{
+    adapters::postgres::{
+        postgres_connector,
+        repositories::{
+            book_repository::PostgresBookRepository,
+            bulk_import_repository::PostgresBulkImportRepository,
+            user_repository::PostgresUserRepository,
+        },
+    },
+    application::{
+        dtos::{
+            auth_dto::UserInfo,
+            spreadsheet_dto::{SpreadsheetExportQuery, SpreadsheetImportRequest},
+        },
+        use_cases::spreadsheet_usecase::SpreadsheetUseCase,
+    },
+    domain::repositories::user_repository::UserRepository,
+    infrastructure::{argon2::Argon2PasswordHasher, config},
+};
+use tracing::{error, info, warn};
+use tracing_subscriber::EnvFilter;
+
+const USAGE: &str = "usage: spreadsheet <import|export> <books|users> <file.csv|file.xlsx> --as <email> [--apply] [--map field=Header ...]";
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = dotenvy::dotenv() {
+        eprintln!("Warning: couldn't load .env file: {}", e);
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_target(false)
+        .compact()
+        .init();
+
+    if let Err(e) = run().await {
+        error!("Spreadsheet job failed: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let mut positional = Vec::new();
+    let mut operator = None;
+    let mut apply = false;
+    let mut columns = HashMap::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--apply" => apply = true,
+            "--as" => operator = Some(args.next().ok_or_else(|| anyhow!(USAGE))?),
+            "--map" => {
+                let mapping = args.next().ok_or_else(|| anyhow!(USAGE))?;
+                let (field, header) = mapping
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("--map expects field=Header, got {}", mapping))?;
+                columns.insert(field.trim().to_string(), header.trim().to_string());
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [action, entity, file] = positional.as_slice() else {
+        return Err(anyhow!(USAGE));
+    };
+    let operator = operator.ok_or_else(|| anyhow!(USAGE))?;
+    let path = Path::new(file);
+    let format = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .ok_or_else(|| anyhow!("Cannot tell the format of {}; use .csv or .xlsx", file))?
+        .to_string();
+
+    let app_config = config::load()?;
+    let pool = postgres_connector::establish_connection(&app_config.database.url).await?;
+
+    let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
+    let caller = load_caller(user_repo.as_ref(), &operator).await?;
+    let usecase = SpreadsheetUseCase::new(
+        Arc::new(PostgresBookRepository::new(pool.clone())),
+        user_repo,
+        Arc::new(PostgresBulkImportRepository::new(pool)),
+        Arc::new(Argon2PasswordHasher::new()),
+    );
+
+    match action.as_str() {
+        "import" => {
+            let req = SpreadsheetImportRequest {
+                format,
+                content: std::fs::read(path).with_context(|| format!("Cannot read {}", file))?,
+                columns: Some(columns).filter(|c| !c.is_empty()),
+                dry_run: !apply,
+            };
+            let report = match entity.as_str() {
+                "books" => usecase.import_books(&caller, req).await?,
+                "users" => usecase.import_users(&caller, req).await?,
+                _ => return Err(anyhow!(USAGE)),
+            };
+
+            for e in &report.errors {
+                warn!(row = e.row, field = e.field.as_deref().unwrap_or("-"), "{}", e.message);
+            }
+            info!(
+                rows = report.rows,
+                created = report.created,
+                updated = report.updated,
+                errors = report.errors.len(),
+                applied = report.applied,
+                "{}",
+                if report.applied {
+                    "Import applied"
+                } else if report.errors.is_empty() {
+                    "Dry run passed; run again with --apply to write it"
+                } else {
+                    "Nothing written; fix the rows above and try again"
+                }
+            );
+            if !report.errors.is_empty() {
+                std::process::exit(2);
+            }
+        }
+        "export" => {
+            let output = BufWriter::new(File::create(path).with_context(|| format!("Cannot create {}", file))?);
+            let query = SpreadsheetExportQuery { format };
+            match entity.as_str() {
+                "books" => usecase.export_books(&caller, query, output).await?,
+                "users" => usecase.export_users(&caller, query, output).await?,
+                _ => return Err(anyhow!(USAGE)),
+            };
+            info!(file = %file, "Export written");
+        }
+        _ => return Err(anyhow!(USAGE)),
+    }
+
+    Ok(())
+}
+
+/// The operator's account and roles, as a signed-in caller would have them
+async fn load_caller(user_repo: &dyn UserRepository, email: &str) -> anyhow::Result<UserInfo> {
+    let user = user_repo
+        .find_by_email(&email.trim().to_lowercase())
+        .await?
+        .filter(|u| u.is_active)
+        .ok_or_else(|| anyhow!("No active account for {}", email))?;
+    let roles = user_repo.find_roles(user.id).await?;
+
+    Ok(UserInfo {
+        id: user.id,
+        email: user.email.as_str().to_string(),
+        fname: user.first_name.as_str().to_string(),
+        lname: user.last_name.as_str().to_string(),
+        roles: roles.into_iter().map(|r| r.name.as_str().to_string()).collect(),
+    })
+}
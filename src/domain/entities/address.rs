@@ -0,0 +1,59 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::postal_address::PostalAddress;
+
+/// A saved address in a user's address book.
+#[derive(Debug, Clone)]
+pub struct AddressEntity {
+    pub id: i32,
+    pub user_id: i32,
+    /// e.g. "Home", "Office"
+    pub label: Option<String>,
+    pub address: PostalAddress,
+    pub is_default_shipping: bool,
+    pub is_default_billing: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AddressEntity {
+    pub fn new(user_id: i32, label: Option<String>, address: PostalAddress) -> Result<Self> {
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            user_id,
+            label: Self::clean_label(label)?,
+            address,
+            is_default_shipping: false,
+            is_default_billing: false,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn update(&mut self, label: Option<String>, address: PostalAddress) -> Result<()> {
+        self.label = Self::clean_label(label)?;
+        self.address = address;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn set_default_shipping(&mut self, is_default: bool) {
+        self.is_default_shipping = is_default;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_default_billing(&mut self, is_default: bool) {
+        self.is_default_billing = is_default;
+        self.updated_at = Utc::now();
+    }
+
+    fn clean_label(label: Option<String>) -> Result<Option<String>> {
+        let label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+        if label.as_ref().is_some_and(|l| l.chars().count() > 50) {
+            return Err(anyhow!("Label too long (max 50 chars)"));
+        }
+        Ok(label)
+    }
+}
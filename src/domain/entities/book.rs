@@ -0,0 +1,243 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::domain::value_objects::{
+    book_cover::BookCover,
+    book_format::BookFormat,
+    book_title::BookTitle,
+    dimensions::Dimensions,
+    isbn::Isbn,
+    money::Money,
+    tax_class::TaxClass,
+};
+
+#[derive(Debug, Clone)]
+pub struct BookEntity {
+    pub id: i32,
+    pub isbn: Isbn,
+    pub title: BookTitle,
+    pub author: String,
+    /// Blurb shown on the product page and searched with lower weight
+    pub description: Option<String>,
+    /// Catalog category used for browsing and promotions (e.g. "Fantasy")
+    pub category: Option<String>,
+    pub publisher: Option<String>,
+    /// ISO 639-1 code, e.g. "th", "en"
+    pub language: Option<String>,
+    pub format: BookFormat,
+    /// Base price, used when no store or currency price is set
+    pub price: Money,
+    pub tax_class: TaxClass,
+    /// Shipping weight; unknown weights fall back to a default when quoting
+    pub weight_grams: Option<i32>,
+    pub dimensions: Option<Dimensions>,
+    /// For unreleased titles this is the pre-order allocation
+    pub stock_quantity: i32,
+    /// Publication day announced by the publisher
+    pub release_date: Option<NaiveDate>,
+    /// Whether the book can be ordered before `release_date`
+    pub preorder_enabled: bool,
+    /// Approved reviews, kept in sync by the review repository
+    pub rating_count: i32,
+    /// Sum of the approved reviews' stars
+    pub rating_total: i32,
+    /// Uploaded cover art; changed only through the cover upload
+    pub cover: Option<BookCover>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BookEntity {
+    pub fn new(
+        isbn: String,
+        title: String,
+        author: String,
+        price: Money,
+        stock_quantity: i32,
+    ) -> Result<Self> {
+        let author = author.trim().to_string();
+        if author.is_empty() {
+            return Err(anyhow!("Author cannot be empty"));
+        }
+        if price.is_negative() {
+            return Err(anyhow!("Price cannot be negative"));
+        }
+        if stock_quantity < 0 {
+            return Err(anyhow!("Stock quantity cannot be negative"));
+        }
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            isbn: Isbn::new(&isbn)?,
+            title: BookTitle::new(title)?,
+            author,
+            description: None,
+            category: None,
+            publisher: None,
+            language: None,
+            format: BookFormat::Paperback,
+            price,
+            tax_class: TaxClass::PrintedBook,
+            weight_grams: None,
+            dimensions: None,
+            stock_quantity,
+            release_date: None,
+            preorder_enabled: false,
+            rating_count: 0,
+            rating_total: 0,
+            cover: None,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn change_title(&mut self, title: String) -> Result<()> {
+        self.title = BookTitle::new(title)?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn change_author(&mut self, author: String) -> Result<()> {
+        let author = author.trim().to_string();
+        if author.is_empty() {
+            return Err(anyhow!("Author cannot be empty"));
+        }
+        if author.chars().count() > 255 {
+            return Err(anyhow!("Author too long (max 255 chars)"));
+        }
+        self.author = author;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn change_price(&mut self, new_price: Money) -> Result<()> {
+        if new_price.is_negative() {
+            return Err(anyhow!("Price cannot be negative"));
+        }
+        self.price = new_price;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn change_tax_class(&mut self, tax_class: TaxClass) {
+        self.tax_class = tax_class;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn change_shipping_profile(
+        &mut self,
+        weight_grams: Option<i32>,
+        dimensions: Option<Dimensions>,
+    ) -> Result<()> {
+        if weight_grams.is_some_and(|w| w <= 0) {
+            return Err(anyhow!("Weight must be positive"));
+        }
+        self.weight_grams = weight_grams;
+        self.dimensions = dimensions;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn change_description(&mut self, description: Option<String>) -> Result<()> {
+        let description = description
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty());
+        if description.as_ref().is_some_and(|d| d.chars().count() > 10_000) {
+            return Err(anyhow!("Description too long (max 10000 chars)"));
+        }
+        self.description = description;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn change_category(&mut self, category: Option<String>) -> Result<()> {
+        let category = category
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty());
+        if category.as_ref().is_some_and(|c| c.len() > 100) {
+            return Err(anyhow!("Category too long (max 100 chars)"));
+        }
+        self.category = category;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn change_publication(
+        &mut self,
+        publisher: Option<String>,
+        language: Option<String>,
+        format: BookFormat,
+    ) -> Result<()> {
+        let publisher = publisher
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty());
+        if publisher.as_ref().is_some_and(|p| p.chars().count() > 255) {
+            return Err(anyhow!("Publisher too long (max 255 chars)"));
+        }
+        let language = language
+            .map(|l| l.trim().to_lowercase())
+            .filter(|l| !l.is_empty());
+        if language
+            .as_ref()
+            .is_some_and(|l| l.len() != 2 || !l.chars().all(|c| c.is_ascii_lowercase()))
+        {
+            return Err(anyhow!("Language must be an ISO 639-1 code"));
+        }
+        self.publisher = publisher;
+        self.language = language;
+        self.format = format;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Announces (or clears) the release date. Pre-orders need a date.
+    pub fn change_release(
+        &mut self,
+        release_date: Option<NaiveDate>,
+        preorder_enabled: bool,
+    ) -> Result<()> {
+        if preorder_enabled && release_date.is_none() {
+            return Err(anyhow!("Pre-orders need a release date"));
+        }
+        self.release_date = release_date;
+        self.preorder_enabled = preorder_enabled;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn is_released(&self, today: NaiveDate) -> bool {
+        self.release_date.is_none_or(|d| d <= today)
+    }
+
+    /// Not out yet, but open for pre-orders.
+    pub fn is_preorder(&self, today: NaiveDate) -> bool {
+        self.preorder_enabled && !self.is_released(today)
+    }
+
+    /// Average stars rounded to one decimal, e.g. 4.3
+    pub fn average_rating(&self) -> Option<f64> {
+        if self.rating_count == 0 {
+            return None;
+        }
+        let tenths = (self.rating_total * 20 + self.rating_count) / (self.rating_count * 2);
+        Some(tenths as f64 / 10.0)
+    }
+
+    /// Whether `quantity` copies can currently be sold.
+    pub fn can_fulfil(&self, quantity: i32) -> bool {
+        self.is_active && self.stock_quantity >= quantity
+    }
+
+    pub fn deactivate(&mut self) {
+        self.is_active = false;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn activate(&mut self) {
+        self.is_active = true;
+        self.updated_at = Utc::now();
+    }
+}
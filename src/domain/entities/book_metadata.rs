@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// ONIX contributor role for "By (author)"
+pub const ROLE_AUTHOR: &str = "A01";
+
+/// A credited person or organisation. `BookEntity::author` is the display
+/// string built from these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookContributor {
+    /// 1-based order of the credits
+    pub sequence: i32,
+    /// ONIX contributor role code (list 17), e.g. A01 author, B06 translator
+    pub role: String,
+    pub name: String,
+}
+
+impl BookContributor {
+    pub fn new(sequence: i32, role: String, name: String) -> Result<Self> {
+        let role = role.trim().to_uppercase();
+        if role.len() != 3 || !role.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(anyhow!("Invalid contributor role: {}", role));
+        }
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow!("Contributor name cannot be empty"));
+        }
+        if name.chars().count() > 255 {
+            return Err(anyhow!("Contributor name too long (max 255 chars)"));
+        }
+        if sequence <= 0 {
+            return Err(anyhow!("Contributor sequence must be positive"));
+        }
+        Ok(Self { sequence, role, name })
+    }
+
+    pub fn is_author(&self) -> bool {
+        self.role == ROLE_AUTHOR
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubjectScheme {
+    /// BISAC Subject Headings, e.g. FIC009000
+    Bisac,
+    /// Thema subject categories and qualifiers, e.g. FM, 1FPT
+    Thema,
+}
+
+impl SubjectScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bisac => "bisac",
+            Self::Thema => "thema",
+        }
+    }
+}
+
+impl FromStr for SubjectScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "bisac" => Ok(Self::Bisac),
+            "thema" => Ok(Self::Thema),
+            _ => Err(anyhow!("Invalid subject scheme: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookSubject {
+    pub scheme: SubjectScheme,
+    pub code: String,
+    pub heading: Option<String>,
+    /// The publisher's main subject for the scheme
+    pub is_main: bool,
+}
+
+impl BookSubject {
+    pub fn new(scheme: SubjectScheme, code: String, heading: Option<String>, is_main: bool) -> Result<Self> {
+        let code = code.trim().to_uppercase();
+        if code.is_empty() || code.len() > 20 {
+            return Err(anyhow!("Invalid {} subject code: {}", scheme.as_str(), code));
+        }
+        let heading = heading
+            .map(|h| h.trim().chars().take(255).collect::<String>())
+            .filter(|h| !h.is_empty());
+        Ok(Self {
+            scheme,
+            code,
+            heading,
+            is_main,
+        })
+    }
+}
@@ -0,0 +1,32 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::money::Money;
+
+/// Price override for a book, either for a whole currency or for one store.
+///
+/// Resolution order: store price → currency price → the book's base price.
+#[derive(Debug, Clone)]
+pub struct BookPriceEntity {
+    pub id: i32,
+    pub book_id: i32,
+    /// `None` = applies to every store selling in this currency
+    pub store_id: Option<i32>,
+    pub price: Money,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BookPriceEntity {
+    pub fn new(book_id: i32, store_id: Option<i32>, price: Money) -> Result<Self> {
+        if price.is_negative() {
+            return Err(anyhow!("Price cannot be negative"));
+        }
+
+        Ok(Self {
+            id: 0,
+            book_id,
+            store_id,
+            price,
+            updated_at: Utc::now(),
+        })
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::{
+    cart_token::CartToken,
+    coupon_code::CouponCode,
+    quantity::Quantity,
+};
+
+/// Who a cart belongs to: a registered user or a guest holding a cart token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CartOwner {
+    User(i32),
+    Guest(CartToken),
+}
+
+#[derive(Debug, Clone)]
+pub struct CartItemEntity {
+    pub id: i32,
+    pub book_id: i32,
+    pub quantity: Quantity,
+    /// Price snapshot in minor units, taken when the line was added or last repriced
+    pub unit_price: i64,
+    pub added_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CartItemEntity {
+    pub fn line_total(&self) -> i64 {
+        self.unit_price * self.quantity.value() as i64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CartEntity {
+    pub id: i32,
+    pub owner: CartOwner,
+    /// Store the cart was opened in; its lines are priced in that store's currency
+    pub store_id: i32,
+    pub items: Vec<CartItemEntity>,
+    /// Coupon entered by the customer; validated whenever the cart is priced
+    pub coupon_code: Option<CouponCode>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CartEntity {
+    pub fn new(owner: CartOwner, store_id: i32) -> Self {
+        let now = Utc::now();
+
+        Self {
+            id: 0,
+            owner,
+            store_id,
+            items: Vec::new(),
+            coupon_code: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Creates an empty cart for a guest with a freshly generated token.
+    pub fn new_guest(store_id: i32) -> Self {
+        Self::new(CartOwner::Guest(CartToken::generate()), store_id)
+    }
+
+    pub fn add_item(&mut self, book_id: i32, quantity: i32, unit_price: i64) -> Result<()> {
+        let quantity = Quantity::new(quantity)?;
+        let now = Utc::now();
+
+        match self.items.iter_mut().find(|i| i.book_id == book_id) {
+            Some(item) => {
+                item.quantity = Quantity::new(item.quantity.value() + quantity.value())?;
+                item.unit_price = unit_price;
+                item.updated_at = now;
+            }
+            None => self.items.push(CartItemEntity {
+                id: 0,
+                book_id,
+                quantity,
+                unit_price,
+                added_at: now,
+                updated_at: now,
+            }),
+        }
+
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn set_quantity(&mut self, book_id: i32, quantity: i32) -> Result<()> {
+        let quantity = Quantity::new(quantity)?;
+        let item = self
+            .items
+            .iter_mut()
+            .find(|i| i.book_id == book_id)
+            .ok_or_else(|| anyhow!("Item not in cart"))?;
+
+        let now = Utc::now();
+        item.quantity = quantity;
+        item.updated_at = now;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn remove_item(&mut self, book_id: i32) -> Result<()> {
+        let before = self.items.len();
+        self.items.retain(|i| i.book_id != book_id);
+        if self.items.len() == before {
+            return Err(anyhow!("Item not in cart"));
+        }
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.coupon_code = None;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn apply_coupon(&mut self, code: String) -> Result<()> {
+        self.coupon_code = Some(CouponCode::new(code)?);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn remove_coupon(&mut self) {
+        self.coupon_code = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Updates a line's price snapshot. Returns the previous price if it changed.
+    pub fn reprice_item(&mut self, book_id: i32, current_price: i64) -> Option<i64> {
+        let item = self.items.iter_mut().find(|i| i.book_id == book_id)?;
+        if item.unit_price == current_price {
+            return None;
+        }
+
+        let previous = item.unit_price;
+        let now = Utc::now();
+        item.unit_price = current_price;
+        item.updated_at = now;
+        self.updated_at = now;
+        Some(previous)
+    }
+
+    /// Moves every line of `other` into this cart. Lines for the same book are
+    /// combined (capped at the maximum quantity) and keep this cart's price snapshot.
+    /// This cart's coupon wins over the other's.
+    pub fn merge(&mut self, other: CartEntity) {
+        let now = Utc::now();
+
+        for incoming in other.items {
+            match self.items.iter_mut().find(|i| i.book_id == incoming.book_id) {
+                Some(item) => {
+                    item.quantity = item.quantity.saturating_add(incoming.quantity);
+                    item.updated_at = now;
+                }
+                None => self.items.push(CartItemEntity { id: 0, ..incoming }),
+            }
+        }
+
+        if self.coupon_code.is_none() {
+            self.coupon_code = other.coupon_code;
+        }
+
+        self.updated_at = now;
+    }
+
+    /// Re-assigns a guest cart to a registered user.
+    pub fn assign_to_user(&mut self, user_id: i32) {
+        self.owner = CartOwner::User(user_id);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn subtotal(&self) -> i64 {
+        self.items.iter().map(|i| i.line_total()).sum()
+    }
+
+    pub fn item_count(&self) -> i32 {
+        self.items.iter().map(|i| i.quantity.value()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
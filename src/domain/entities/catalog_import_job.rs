@@ -0,0 +1,108 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+use crate::domain::value_objects::import_job_status::ImportJobStatus;
+
+/// Claims before a job that keeps dying is given up on
+pub const MAX_IMPORT_ATTEMPTS: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportFormat {
+    /// ONIX for Books 3.0, reference or short tags
+    Onix,
+}
+
+impl ImportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Onix => "onix",
+        }
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "onix" => Ok(Self::Onix),
+            _ => Err(anyhow!("Invalid import format: {}", s)),
+        }
+    }
+}
+
+/// A feed file being worked into the catalog by a background worker.
+#[derive(Debug, Clone)]
+pub struct CatalogImportJobEntity {
+    pub id: i32,
+    pub format: ImportFormat,
+    /// Relative to the import inbox
+    pub file_name: String,
+    /// Who sent the feed, from its header
+    pub sender: Option<String>,
+    pub status: ImportJobStatus,
+    /// Byte offset the next unprocessed record starts at (0 = from the top)
+    pub checkpoint_offset: i64,
+    pub records_processed: i32,
+    pub created_count: i32,
+    pub updated_count: i32,
+    pub rejected_count: i32,
+    /// Incremented every time a worker claims the job
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// Claim lease while running
+    pub run_after: DateTime<Utc>,
+    pub requested_by: Option<i32>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CatalogImportJobEntity {
+    pub fn new(format: ImportFormat, file_name: String, requested_by: Option<i32>) -> Result<Self> {
+        let file_name = file_name.trim().to_string();
+        if file_name.is_empty() || file_name.chars().count() > 255 {
+            return Err(anyhow!("File name must be 1-255 characters"));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: 0,
+            format,
+            file_name,
+            sender: None,
+            status: ImportJobStatus::Queued,
+            checkpoint_offset: 0,
+            records_processed: 0,
+            created_count: 0,
+            updated_count: 0,
+            rejected_count: 0,
+            attempts: 0,
+            last_error: None,
+            run_after: now,
+            requested_by,
+            started_at: None,
+            finished_at: None,
+            created_at: now,
+        })
+    }
+
+    pub fn complete(&mut self) {
+        self.status = ImportJobStatus::Completed;
+        self.last_error = None;
+        self.finished_at = Some(Utc::now());
+    }
+
+    /// Gives up on a file that cannot be read; records already imported stay.
+    pub fn fail(&mut self, error: String) {
+        self.status = ImportJobStatus::Failed;
+        self.last_error = Some(error);
+        self.finished_at = Some(Utc::now());
+    }
+
+    /// Whether a worker already died on this job too often to try again
+    pub fn is_exhausted(&self) -> bool {
+        self.attempts > MAX_IMPORT_ATTEMPTS
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportOutcome {
+    Created,
+    Updated,
+    Rejected,
+}
+
+impl ImportOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+impl FromStr for ImportOutcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "created" => Ok(Self::Created),
+            "updated" => Ok(Self::Updated),
+            "rejected" => Ok(Self::Rejected),
+            _ => Err(anyhow!("Invalid import outcome: {}", s)),
+        }
+    }
+}
+
+/// One line of an import job's report.
+#[derive(Debug, Clone)]
+pub struct CatalogImportRecordEntity {
+    pub job_id: i32,
+    /// 0-based position of the record in the file
+    pub record_index: i32,
+    pub reference: Option<String>,
+    pub isbn: Option<String>,
+    pub book_id: Option<i32>,
+    pub outcome: ImportOutcome,
+    /// Reasons for a rejection, or what was skipped on an accepted record
+    pub messages: Vec<String>,
+}
@@ -0,0 +1,241 @@
+use chrono::NaiveDate;
+
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        book_metadata::{BookContributor, BookSubject},
+    },
+    value_objects::{
+        book_format::BookFormat,
+        dimensions::Dimensions,
+        money::{Currency, Money},
+        tax_class::TaxClass,
+    },
+};
+
+/// Longest `author` display string; longer credit lists are shortened
+const MAX_AUTHOR_CHARS: usize = 255;
+
+/// What the sender says about getting hold of the product
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordAvailability {
+    /// Not published yet, orders accepted
+    Preorder,
+    Available,
+    /// Withdrawn, out of print or otherwise not sold any more
+    Unavailable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordPrice {
+    /// Major units as sent, e.g. "350.00"
+    pub amount: String,
+    pub currency: String,
+}
+
+/// One product from a supplier feed, already mapped from the feed's code
+/// lists but not yet validated against the catalog. Fields left `None` (or
+/// empty) were not in the record and keep their current value.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogRecord {
+    /// The sender's own id for the record
+    pub reference: Option<String>,
+    pub isbn: Option<String>,
+    /// Delete notice: the book is taken off sale, never removed
+    pub delete: bool,
+    pub title: Option<String>,
+    pub contributors: Vec<BookContributor>,
+    pub description: Option<String>,
+    pub publisher: Option<String>,
+    /// ISO 639-1
+    pub language: Option<String>,
+    pub format: Option<BookFormat>,
+    pub release_date: Option<NaiveDate>,
+    pub availability: Option<RecordAvailability>,
+    pub prices: Vec<RecordPrice>,
+    pub subjects: Vec<BookSubject>,
+    pub weight_grams: Option<i32>,
+    pub dimensions: Option<Dimensions>,
+    /// Problems that reject the record
+    pub errors: Vec<String>,
+    /// Data that was skipped; the record is still imported
+    pub warnings: Vec<String>,
+}
+
+/// A record applied to a new or existing book, ready to be stored
+#[derive(Debug, Clone)]
+pub struct ImportedBook {
+    pub book: BookEntity,
+    /// `None` keeps the stored credits
+    pub contributors: Option<Vec<BookContributor>>,
+    /// `None` keeps the stored subjects
+    pub subjects: Option<Vec<BookSubject>>,
+    /// Prices in currencies other than the book's base currency
+    pub currency_prices: Vec<Money>,
+    pub warnings: Vec<String>,
+}
+
+impl CatalogRecord {
+    /// Applies the record to `existing` (or a new book), collecting every
+    /// reason to reject it rather than stopping at the first.
+    pub fn into_book(self, existing: Option<BookEntity>, today: NaiveDate) -> Result<ImportedBook, Vec<String>> {
+        let mut errors = self.errors;
+        let mut warnings = self.warnings;
+        let Some(isbn) = self.isbn else {
+            errors.push("Record has no ISBN-13 or ISBN-10".to_string());
+            return Err(errors);
+        };
+
+        if self.delete {
+            let Some(mut book) = existing else {
+                errors.push(format!("Delete notice for unknown ISBN {}", isbn));
+                return Err(errors);
+            };
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+            book.deactivate();
+            return Ok(ImportedBook {
+                book,
+                contributors: None,
+                subjects: None,
+                currency_prices: Vec::new(),
+                warnings,
+            });
+        }
+
+        let base_currency = existing
+            .as_ref()
+            .map(|b| b.price.currency().clone())
+            .unwrap_or_else(Currency::thb);
+        let mut base_price = None;
+        let mut currency_prices: Vec<Money> = Vec::new();
+        for price in &self.prices {
+            let money = match Currency::new(&price.currency).and_then(|c| Money::parse(&price.amount, c)) {
+                Ok(money) => money,
+                Err(e) => {
+                    warnings.push(format!("Price skipped: {}", e));
+                    continue;
+                }
+            };
+            if *money.currency() == base_currency {
+                base_price.get_or_insert(money);
+            } else if !currency_prices.iter().any(|p| p.currency() == money.currency()) {
+                currency_prices.push(money);
+            }
+        }
+
+        let author = author_line(&self.contributors);
+        let is_new = existing.is_none();
+        let mut book = match existing {
+            Some(book) => book,
+            None => {
+                if self.title.is_none() {
+                    errors.push("Title is required for a new book".to_string());
+                }
+                if author.is_none() {
+                    errors.push("At least one contributor is required for a new book".to_string());
+                }
+                if base_price.is_none() {
+                    errors.push(format!("A {} price is required for a new book", base_currency));
+                }
+                let (Some(title), Some(author), Some(price)) = (self.title.clone(), author.clone(), base_price.clone())
+                else {
+                    return Err(errors);
+                };
+                match BookEntity::new(isbn, title, author, price, 0) {
+                    Ok(book) => book,
+                    Err(e) => {
+                        errors.push(e.to_string());
+                        return Err(errors);
+                    }
+                }
+            }
+        };
+
+        let mut check = |result: anyhow::Result<()>| {
+            if let Err(e) = result {
+                errors.push(e.to_string());
+            }
+        };
+        if !is_new {
+            if let Some(title) = self.title {
+                check(book.change_title(title));
+            }
+            if let Some(author) = author {
+                check(book.change_author(author));
+            }
+            if let Some(price) = base_price {
+                check(book.change_price(price));
+            }
+        }
+        if self.description.is_some() {
+            check(book.change_description(self.description));
+        }
+        if self.publisher.is_some() || self.language.is_some() || self.format.is_some() {
+            let format = self.format.unwrap_or(book.format);
+            check(book.change_publication(
+                self.publisher.or_else(|| book.publisher.clone()),
+                self.language.or_else(|| book.language.clone()),
+                format,
+            ));
+            if is_new && format.is_digital() {
+                book.change_tax_class(TaxClass::Ebook);
+            }
+        }
+        if !book.format.is_digital() && (self.weight_grams.is_some() || self.dimensions.is_some()) {
+            check(book.change_shipping_profile(
+                self.weight_grams.or(book.weight_grams),
+                self.dimensions.or(book.dimensions),
+            ));
+        }
+
+        let release_date = self.release_date.or(book.release_date);
+        let preorder = match self.availability {
+            Some(RecordAvailability::Preorder) => {
+                let upcoming = release_date.is_some_and(|d| d > today);
+                if !upcoming {
+                    warnings.push("Pre-order availability without a future publication date; not opened for pre-order".to_string());
+                }
+                upcoming
+            }
+            Some(_) => false,
+            None => book.preorder_enabled,
+        };
+        check(book.change_release(release_date, preorder));
+        match self.availability {
+            Some(RecordAvailability::Preorder | RecordAvailability::Available) => book.activate(),
+            Some(RecordAvailability::Unavailable) => book.deactivate(),
+            None => {}
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(ImportedBook {
+            book,
+            contributors: Some(self.contributors).filter(|c| !c.is_empty()),
+            subjects: Some(self.subjects).filter(|s| !s.is_empty()),
+            currency_prices,
+            warnings,
+        })
+    }
+}
+
+/// "A, B" for the authors (every contributor when none is marked as author),
+/// cut to the first name plus "et al." when it would not fit
+fn author_line(contributors: &[BookContributor]) -> Option<String> {
+    let mut credited: Vec<&BookContributor> = contributors.iter().filter(|c| c.is_author()).collect();
+    if credited.is_empty() {
+        credited = contributors.iter().collect();
+    }
+    let first = credited.first()?;
+
+    let line = credited.iter().map(|c| c.name.as_str()).collect::<Vec<_>>().join(", ");
+    if line.chars().count() <= MAX_AUTHOR_CHARS {
+        Some(line)
+    } else {
+        Some(format!("{} et al.", first.name))
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::coupon_code::CouponCode;
+
+/// A code that unlocks a coupon-only promotion.
+#[derive(Debug, Clone)]
+pub struct CouponEntity {
+    pub id: i32,
+    pub code: CouponCode,
+    pub promotion_id: i32,
+    /// Total redemptions allowed across all customers (`None` = unlimited)
+    pub usage_limit: Option<i32>,
+    /// Redemptions allowed per customer (`None` = unlimited)
+    pub per_customer_limit: Option<i32>,
+    pub times_used: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CouponEntity {
+    pub fn new(
+        code: String,
+        promotion_id: i32,
+        usage_limit: Option<i32>,
+        per_customer_limit: Option<i32>,
+    ) -> Result<Self> {
+        if usage_limit.is_some_and(|l| l < 1) || per_customer_limit.is_some_and(|l| l < 1) {
+            return Err(anyhow!("Usage limits must be at least 1"));
+        }
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            code: CouponCode::new(code)?,
+            promotion_id,
+            usage_limit,
+            per_customer_limit,
+            times_used: 0,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Whether the global usage limit has been reached.
+    pub fn is_exhausted(&self) -> bool {
+        self.usage_limit.is_some_and(|l| self.times_used >= l)
+    }
+
+    /// Checks the coupon can still be redeemed by a customer who already used it `used_by_customer` times.
+    pub fn ensure_redeemable(&self, used_by_customer: i64) -> Result<()> {
+        if !self.is_active {
+            return Err(anyhow!("Coupon {} is no longer active", self.code));
+        }
+        if self.is_exhausted() {
+            return Err(anyhow!("Coupon {} has been fully redeemed", self.code));
+        }
+        if self
+            .per_customer_limit
+            .is_some_and(|l| used_by_customer >= l as i64)
+        {
+            return Err(anyhow!("You have already used coupon {}", self.code));
+        }
+        Ok(())
+    }
+
+    pub fn deactivate(&mut self) {
+        self.is_active = false;
+        self.updated_at = Utc::now();
+    }
+}
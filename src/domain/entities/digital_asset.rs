@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+
+use crate::domain::value_objects::digital_file_format::DigitalFileFormat;
+
+/// A downloadable file of a digital edition, at most one per file format.
+/// The bytes live in file storage under `storage_key`.
+#[derive(Debug, Clone)]
+pub struct DigitalAssetEntity {
+    pub id: i32,
+    pub book_id: i32,
+    pub file_format: DigitalFileFormat,
+    pub storage_key: String,
+    /// Offered to the browser in `Content-Disposition`
+    pub file_name: String,
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the content
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DigitalAssetEntity {
+    pub fn new(
+        book_id: i32,
+        file_format: DigitalFileFormat,
+        file_name: String,
+        size_bytes: i64,
+        sha256: String,
+    ) -> Result<Self> {
+        let file_name = file_name.trim().to_string();
+        if file_name.is_empty() || file_name.chars().count() > 255 {
+            return Err(anyhow!("File name must be 1-255 characters"));
+        }
+        if file_name
+            .chars()
+            .any(|c| c.is_control() || matches!(c, '/' | '\\' | '"'))
+        {
+            return Err(anyhow!("File name contains invalid characters"));
+        }
+        if size_bytes <= 0 {
+            return Err(anyhow!("File is empty"));
+        }
+        if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("Invalid SHA-256 digest"));
+        }
+
+        // key มี hash ของไฟล์ อัปโหลดไฟล์ใหม่จึงไม่ทับไฟล์เก่าที่กำลังถูกดาวน์โหลด
+        let storage_key = format!(
+            "books/{}/{}-{}.{}",
+            book_id,
+            file_format.as_str(),
+            &sha256[..16],
+            file_format.as_str()
+        );
+
+        let now = Utc::now();
+        Ok(Self {
+            id: 0,
+            book_id,
+            file_format,
+            storage_key,
+            file_name,
+            size_bytes,
+            sha256: sha256.to_lowercase(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::value_objects::download_outcome::DownloadOutcome;
+
+/// One download request made with a validly signed link.
+#[derive(Debug, Clone)]
+pub struct DownloadLogEntity {
+    pub id: i64,
+    pub entitlement_id: i32,
+    pub asset_id: i32,
+    pub user_id: i32,
+    pub outcome: DownloadOutcome,
+    /// Inclusive byte range served; `None` when nothing was sent
+    pub range_start: Option<i64>,
+    pub range_end: Option<i64>,
+    pub bytes_served: i64,
+    /// Whether it used up one of the entitlement's downloads
+    pub counted: bool,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DownloadLogEntity {
+    pub fn new(
+        entitlement_id: i32,
+        asset_id: i32,
+        user_id: i32,
+        outcome: DownloadOutcome,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> Self {
+        Self {
+            id: 0,
+            entitlement_id,
+            asset_id,
+            user_id,
+            outcome,
+            range_start: None,
+            range_end: None,
+            bytes_served: 0,
+            counted: false,
+            ip_address,
+            // user agent ยาวได้ไม่จำกัด ตัดไว้ไม่ให้ log บวม
+            user_agent: user_agent.map(|ua| ua.chars().take(500).collect()),
+            created_at: Utc::now(),
+        }
+    }
+}
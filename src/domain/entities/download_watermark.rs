@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::value_objects::watermark_code::WatermarkCode;
+
+/// A customer's stamped copy of a digital asset. Made on first download and
+/// reused afterwards so range requests see the same bytes. The row is the
+/// lookup from a watermark code to the buyer, so it keeps its own copy of
+/// user, order and name in case the entitlement goes away.
+#[derive(Debug, Clone)]
+pub struct DownloadWatermarkEntity {
+    pub id: i32,
+    pub code: WatermarkCode,
+    pub entitlement_id: Option<i32>,
+    pub asset_id: i32,
+    /// Source file the copy was made from; a new upload gets a new copy
+    pub asset_sha256: String,
+    pub user_id: i32,
+    pub order_id: Option<i32>,
+    /// Name stamped into the file
+    pub licensee: String,
+    pub storage_key: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DownloadWatermarkEntity {
+    pub fn new(
+        entitlement_id: i32,
+        asset_id: i32,
+        asset_sha256: String,
+        user_id: i32,
+        order_id: Option<i32>,
+        licensee: String,
+    ) -> Self {
+        let code = WatermarkCode::generate();
+        let storage_key = format!("watermarked/{}/{}.epub", user_id, code.as_str());
+        Self {
+            id: 0,
+            code,
+            entitlement_id: Some(entitlement_id),
+            asset_id,
+            asset_sha256,
+            user_id,
+            order_id,
+            licensee,
+            storage_key,
+            size_bytes: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Order number as printed in the file
+    pub fn order_reference(&self) -> String {
+        match self.order_id {
+            Some(id) => format!("order #{}", id),
+            None => "complimentary copy".to_string(),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+
+/// Downloads allowed per purchase unless staff grant a different limit
+pub const DEFAULT_DOWNLOAD_LIMIT: i32 = 5;
+
+/// A user's right to download the files of a digital book. Granted when an
+/// order containing the book is paid (or manually by staff) and revoked when
+/// that order is cancelled or refunded.
+#[derive(Debug, Clone)]
+pub struct EntitlementEntity {
+    pub id: i32,
+    pub user_id: i32,
+    pub book_id: i32,
+    /// `None` for grants made by staff
+    pub order_id: Option<i32>,
+    /// `None` means unlimited
+    pub download_limit: Option<i32>,
+    pub downloads_used: i32,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoke_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EntitlementEntity {
+    pub fn new(
+        user_id: i32,
+        book_id: i32,
+        order_id: Option<i32>,
+        download_limit: Option<i32>,
+    ) -> Result<Self> {
+        if download_limit.is_some_and(|l| l < 0) {
+            return Err(anyhow!("Download limit cannot be negative"));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: 0,
+            user_id,
+            book_id,
+            order_id,
+            download_limit,
+            downloads_used: 0,
+            revoked_at: None,
+            revoke_reason: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn downloads_remaining(&self) -> Option<i32> {
+        self.download_limit
+            .map(|limit| (limit - self.downloads_used).max(0))
+    }
+
+    pub fn can_download(&self) -> bool {
+        !self.is_revoked() && self.downloads_remaining().is_none_or(|r| r > 0)
+    }
+
+    pub fn revoke(&mut self, reason: Option<String>) -> Result<()> {
+        if self.is_revoked() {
+            return Err(anyhow!("Entitlement is already revoked"));
+        }
+        self.revoked_at = Some(Utc::now());
+        self.revoke_reason = reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn restore(&mut self) -> Result<()> {
+        if !self.is_revoked() {
+            return Err(anyhow!("Entitlement is not revoked"));
+        }
+        self.revoked_at = None;
+        self.revoke_reason = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Staff override, e.g. after a customer lost their device
+    pub fn change_download_limit(&mut self, download_limit: Option<i32>, reset_used: bool) -> Result<()> {
+        if download_limit.is_some_and(|l| l < 0) {
+            return Err(anyhow!("Download limit cannot be negative"));
+        }
+        self.download_limit = download_limit;
+        if reset_used {
+            self.downloads_used = 0;
+        }
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
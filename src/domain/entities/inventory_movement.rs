@@ -0,0 +1,84 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+use crate::domain::value_objects::stock_bucket::StockBucket;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementReason {
+    OpeningBalance,
+    Sale,
+    OrderCancelled,
+    ReturnRestock,
+    ReturnWriteOff,
+    Adjustment,
+}
+
+impl MovementReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OpeningBalance => "opening_balance",
+            Self::Sale => "sale",
+            Self::OrderCancelled => "order_cancelled",
+            Self::ReturnRestock => "return_restock",
+            Self::ReturnWriteOff => "return_write_off",
+            Self::Adjustment => "adjustment",
+        }
+    }
+}
+
+impl FromStr for MovementReason {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "opening_balance" => Ok(Self::OpeningBalance),
+            "sale" => Ok(Self::Sale),
+            "order_cancelled" => Ok(Self::OrderCancelled),
+            "return_restock" => Ok(Self::ReturnRestock),
+            "return_write_off" => Ok(Self::ReturnWriteOff),
+            "adjustment" => Ok(Self::Adjustment),
+            _ => Err(anyhow!("Invalid movement reason: {}", s)),
+        }
+    }
+}
+
+/// Append-only entry of the inventory ledger.
+#[derive(Debug, Clone)]
+pub struct InventoryMovementEntity {
+    pub id: i32,
+    pub book_id: i32,
+    pub bucket: StockBucket,
+    /// Signed change to the bucket
+    pub quantity: i32,
+    pub reason: MovementReason,
+    /// Free-form pointer to the cause, e.g. `order:12` or `return:3`
+    pub reference: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InventoryMovementEntity {
+    pub fn new(
+        book_id: i32,
+        bucket: StockBucket,
+        quantity: i32,
+        reason: MovementReason,
+        reference: Option<String>,
+        created_by: Option<i32>,
+    ) -> Result<Self> {
+        if quantity == 0 {
+            return Err(anyhow!("Inventory movement quantity cannot be zero"));
+        }
+
+        Ok(Self {
+            id: 0,
+            book_id,
+            bucket,
+            quantity,
+            reason,
+            reference,
+            created_by,
+            created_at: Utc::now(),
+        })
+    }
+}
@@ -0,0 +1,320 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::domain::{
+    entities::order::OrderEntity,
+    value_objects::{
+        money::Currency,
+        order_status::OrderStatus,
+        tax_id::{BranchCode, TaxId},
+    },
+};
+
+/// Documents are dated in Thai time (UTC+7, no daylight saving)
+pub const DOCUMENT_UTC_OFFSET_SECS: i32 = 7 * 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvoiceKind {
+    /// ใบกำกับภาษีเต็มรูป
+    TaxInvoice,
+    /// ใบลดหนี้, reduces a tax invoice after a refund
+    CreditNote,
+}
+
+impl InvoiceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TaxInvoice => "tax_invoice",
+            Self::CreditNote => "credit_note",
+        }
+    }
+
+    /// Each kind is numbered on its own, e.g. `INV2026-000042`, `CN2026-000003`
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Self::TaxInvoice => "INV",
+            Self::CreditNote => "CN",
+        }
+    }
+}
+
+impl FromStr for InvoiceKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "tax_invoice" => Ok(Self::TaxInvoice),
+            "credit_note" => Ok(Self::CreditNote),
+            _ => Err(anyhow!("Invalid invoice kind: {}", s)),
+        }
+    }
+}
+
+/// Seller or buyer as printed on the document
+#[derive(Debug, Clone)]
+pub struct InvoiceParty {
+    pub name: String,
+    pub tax_id: TaxId,
+    pub branch: BranchCode,
+    pub address: String,
+}
+
+impl InvoiceParty {
+    pub fn new(name: String, tax_id: TaxId, branch: BranchCode, address: String) -> Result<Self> {
+        let name = name.trim().to_string();
+        if name.is_empty() || name.chars().count() > 255 {
+            return Err(anyhow!("Name must be between 1 and 255 characters"));
+        }
+        let address = address.trim().to_string();
+        if address.is_empty() || address.chars().count() > 500 {
+            return Err(anyhow!("Address must be between 1 and 500 characters"));
+        }
+        Ok(Self {
+            name,
+            tax_id,
+            branch,
+            address,
+        })
+    }
+}
+
+/// A printed line. Amounts include VAT, like the order they come from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvoiceLine {
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: i64,
+    pub amount: i64,
+}
+
+/// The tax invoice a credit note reduces, frozen when the note is issued
+#[derive(Debug, Clone)]
+pub struct CreditNoteReference {
+    pub invoice_id: i32,
+    pub number: String,
+    pub issued_at: DateTime<Utc>,
+    /// What the invoice was worth before this note (after earlier notes)
+    pub value_before: i64,
+}
+
+impl CreditNoteReference {
+    /// Date of the referenced invoice as printed, in Thai time
+    pub fn issued_on(&self) -> DateTime<FixedOffset> {
+        local_time(self.issued_at)
+    }
+}
+
+/// A tax invoice or credit note. Never changed once issued: the number is
+/// taken from a gap-free sequence in the same transaction that stores the
+/// rendered PDF, and corrections are made with credit notes.
+#[derive(Debug, Clone)]
+pub struct InvoiceEntity {
+    pub id: i32,
+    pub kind: InvoiceKind,
+    /// Empty until issued
+    pub number: String,
+    pub fiscal_year: i32,
+    pub sequence: i32,
+    pub order_id: i32,
+    pub user_id: i32,
+    /// Credit notes only
+    pub reference: Option<CreditNoteReference>,
+    /// Credit notes for a returned line
+    pub return_id: Option<i32>,
+    pub seller: InvoiceParty,
+    pub buyer: InvoiceParty,
+    pub currency: Currency,
+    pub lines: Vec<InvoiceLine>,
+    /// total - tax_amount
+    pub net_amount: i64,
+    pub tax_amount: i64,
+    pub total: i64,
+    /// Credit notes only: why the sale was reduced
+    pub reason: Option<String>,
+    /// SHA-256 of the stored PDF, hex encoded
+    pub sha256: String,
+    pub size_bytes: i64,
+    pub issued_by: Option<i32>,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl InvoiceEntity {
+    /// Full tax invoice for a paid order. Discounts and shipping are printed
+    /// as their own lines so the lines add up to the order total.
+    pub fn tax_invoice(
+        order: &OrderEntity,
+        seller: InvoiceParty,
+        buyer: InvoiceParty,
+        issued_by: Option<i32>,
+    ) -> Result<Self> {
+        let paid = matches!(
+            order.status,
+            OrderStatus::Paid | OrderStatus::Picking | OrderStatus::Shipped | OrderStatus::Delivered
+        );
+        if !paid {
+            return Err(anyhow!("Tax invoices are only issued for paid orders"));
+        }
+
+        let mut lines: Vec<InvoiceLine> = order
+            .items
+            .iter()
+            .map(|item| InvoiceLine {
+                description: format!("{} (ISBN {})", item.title, item.isbn),
+                quantity: item.quantity.value(),
+                unit_price: if item.price_includes_tax {
+                    item.unit_price
+                } else {
+                    item.line_total / item.quantity.value() as i64
+                },
+                amount: item.line_total,
+            })
+            .collect();
+        if order.discount_total > 0 {
+            lines.push(InvoiceLine {
+                description: "ส่วนลด (Discount)".to_string(),
+                quantity: 1,
+                unit_price: -order.discount_total,
+                amount: -order.discount_total,
+            });
+        }
+        if order.shipping_total > 0 {
+            let method = order.shipping.as_ref().map(|s| s.method_name.as_str()).unwrap_or("Shipping");
+            lines.push(InvoiceLine {
+                description: format!("ค่าจัดส่ง ({})", method),
+                quantity: 1,
+                unit_price: order.shipping_total,
+                amount: order.shipping_total,
+            });
+        }
+
+        Ok(Self::draft(
+            InvoiceKind::TaxInvoice,
+            order,
+            None,
+            None,
+            seller,
+            buyer,
+            lines,
+            order.tax_total,
+            None,
+            issued_by,
+        ))
+    }
+
+    /// Credit note reducing `original` by the sum of `lines`.
+    /// `credited_total` / `credited_tax` are what earlier notes already took off.
+    #[allow(clippy::too_many_arguments)]
+    pub fn credit_note(
+        original: &InvoiceEntity,
+        order: &OrderEntity,
+        credited_total: i64,
+        credited_tax: i64,
+        lines: Vec<InvoiceLine>,
+        tax_amount: i64,
+        return_id: Option<i32>,
+        reason: String,
+        issued_by: Option<i32>,
+    ) -> Result<Self> {
+        if original.kind != InvoiceKind::TaxInvoice {
+            return Err(anyhow!("Credit notes can only reduce a tax invoice"));
+        }
+        let reason = reason.trim().to_string();
+        if reason.is_empty() || reason.chars().count() > 500 {
+            return Err(anyhow!("Credit note reason must be between 1 and 500 characters"));
+        }
+
+        let total: i64 = lines.iter().map(|l| l.amount).sum();
+        let value_before = original.total - credited_total;
+        if value_before <= 0 {
+            return Err(anyhow!("Invoice {} has been credited in full", original.number));
+        }
+        if total <= 0 || total > value_before {
+            return Err(anyhow!("Credit note amount must be between 0 and {}", value_before));
+        }
+        if tax_amount < 0 || tax_amount > total || tax_amount > original.tax_amount - credited_tax {
+            return Err(anyhow!("Credit note VAT exceeds the VAT left on the invoice"));
+        }
+
+        let reference = CreditNoteReference {
+            invoice_id: original.id,
+            number: original.number.clone(),
+            issued_at: original.issued_at,
+            value_before,
+        };
+
+        Ok(Self::draft(
+            InvoiceKind::CreditNote,
+            order,
+            Some(reference),
+            return_id,
+            original.seller.clone(),
+            original.buyer.clone(),
+            lines,
+            tax_amount,
+            Some(reason),
+            issued_by,
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draft(
+        kind: InvoiceKind,
+        order: &OrderEntity,
+        reference: Option<CreditNoteReference>,
+        return_id: Option<i32>,
+        seller: InvoiceParty,
+        buyer: InvoiceParty,
+        lines: Vec<InvoiceLine>,
+        tax_amount: i64,
+        reason: Option<String>,
+        issued_by: Option<i32>,
+    ) -> Self {
+        let total: i64 = lines.iter().map(|l| l.amount).sum();
+        let issued_at = Utc::now();
+        Self {
+            id: 0,
+            kind,
+            number: String::new(),
+            fiscal_year: local_time(issued_at).year(),
+            sequence: 0,
+            order_id: order.id,
+            user_id: order.user_id,
+            reference,
+            return_id,
+            seller,
+            buyer,
+            currency: order.currency.clone(),
+            lines,
+            net_amount: total - tax_amount,
+            tax_amount,
+            total,
+            reason,
+            sha256: String::new(),
+            size_bytes: 0,
+            issued_by,
+            issued_at,
+        }
+    }
+
+    /// Gives the document its place in the sequence of its kind and year
+    pub fn assign_number(&mut self, sequence: i32) {
+        self.sequence = sequence;
+        self.number = format!("{}{}-{:06}", self.kind.prefix(), self.fiscal_year, sequence);
+    }
+
+    /// Date as printed, in Thai time
+    pub fn issued_on(&self) -> DateTime<FixedOffset> {
+        local_time(self.issued_at)
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("{}.pdf", self.number)
+    }
+}
+
+fn local_time(at: DateTime<Utc>) -> DateTime<FixedOffset> {
+    let offset = FixedOffset::east_opt(DOCUMENT_UTC_OFFSET_SECS).expect("Valid UTC offset");
+    at.with_timezone(&offset)
+}
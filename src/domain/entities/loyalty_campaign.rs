@@ -0,0 +1,100 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+
+/// Earn multipliers are percentages of the base rate: 100 = 1x, 200 = 2x
+pub const MAX_MULTIPLIER_PCT: i32 = 1000;
+
+/// Standing earn rate of a category (e.g. 50% on textbooks, 150% on comics)
+#[derive(Debug, Clone)]
+pub struct LoyaltyCategoryMultiplier {
+    pub category: String,
+    pub multiplier_pct: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LoyaltyCategoryMultiplier {
+    pub fn new(category: String, multiplier_pct: i32) -> Result<Self> {
+        let category = category.trim().to_string();
+        if category.is_empty() {
+            return Err(anyhow!("Category cannot be empty"));
+        }
+        if !(0..=MAX_MULTIPLIER_PCT).contains(&multiplier_pct) {
+            return Err(anyhow!("Multiplier must be between 0% and {}%", MAX_MULTIPLIER_PCT));
+        }
+
+        Ok(Self {
+            category,
+            multiplier_pct,
+            updated_at: Utc::now(),
+        })
+    }
+}
+
+/// Time-boxed bonus on top of the category rate, e.g. "double points weekend"
+#[derive(Debug, Clone)]
+pub struct LoyaltyCampaignEntity {
+    pub id: i32,
+    pub name: String,
+    /// `None` = every category
+    pub category: Option<String>,
+    pub multiplier_pct: i32,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub is_active: bool,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LoyaltyCampaignEntity {
+    pub fn new(
+        name: String,
+        category: Option<String>,
+        multiplier_pct: i32,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        created_by: i32,
+    ) -> Result<Self> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow!("Campaign name cannot be empty"));
+        }
+        if !(100..=MAX_MULTIPLIER_PCT).contains(&multiplier_pct) {
+            return Err(anyhow!("Campaign multiplier must be between 100% and {}%", MAX_MULTIPLIER_PCT));
+        }
+        if ends_at <= starts_at {
+            return Err(anyhow!("Campaign must end after it starts"));
+        }
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            name,
+            category: category.map(|c| c.trim().to_string()).filter(|c| !c.is_empty()),
+            multiplier_pct,
+            starts_at,
+            ends_at,
+            is_active: true,
+            created_by: Some(created_by),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn is_running(&self, now: DateTime<Utc>) -> bool {
+        self.is_active && self.starts_at <= now && now < self.ends_at
+    }
+
+    pub fn applies_to(&self, category: Option<&str>) -> bool {
+        match &self.category {
+            None => true,
+            Some(c) => category.is_some_and(|cat| cat.eq_ignore_ascii_case(c)),
+        }
+    }
+
+    pub fn end(&mut self) {
+        self.is_active = false;
+        self.updated_at = Utc::now();
+    }
+}
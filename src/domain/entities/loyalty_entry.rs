@@ -0,0 +1,132 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Months, Utc};
+use std::str::FromStr;
+
+/// The programme runs in baht: only orders in this currency earn or take points
+pub const POINTS_CURRENCY: &str = "THB";
+/// Points earned per baht spent, before multipliers
+pub const POINTS_PER_MAJOR_UNIT: i64 = 1;
+/// What one point is worth at checkout, in satang
+pub const POINT_VALUE: i64 = 10;
+/// Points lapse this many months after they were earned
+pub const POINTS_EXPIRY_MONTHS: u32 = 12;
+/// `provider` of payments made with points
+pub const LOYALTY_PAYMENT_PROVIDER: &str = "loyalty_points";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointsReason {
+    /// Earned on a paid order
+    Earn,
+    /// Earned points taken back after a return, cancellation or refund
+    Clawback,
+    /// Spent at checkout
+    Redemption,
+    /// Spent points given back, e.g. the order was cancelled
+    RedemptionReversal,
+    /// Lapsed points written off
+    Expiry,
+}
+
+impl PointsReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Earn => "earn",
+            Self::Clawback => "clawback",
+            Self::Redemption => "redemption",
+            Self::RedemptionReversal => "redemption_reversal",
+            Self::Expiry => "expiry",
+        }
+    }
+
+    fn is_credit(&self) -> bool {
+        matches!(self, Self::Earn | Self::RedemptionReversal)
+    }
+}
+
+impl FromStr for PointsReason {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "earn" => Ok(Self::Earn),
+            "clawback" => Ok(Self::Clawback),
+            "redemption" => Ok(Self::Redemption),
+            "redemption_reversal" => Ok(Self::RedemptionReversal),
+            "expiry" => Ok(Self::Expiry),
+            _ => Err(anyhow!("Invalid points reason: {}", s)),
+        }
+    }
+}
+
+/// Append-only entry of a member's points ledger.
+#[derive(Debug, Clone)]
+pub struct LoyaltyEntryEntity {
+    pub id: i32,
+    pub user_id: i32,
+    /// Signed change to the balance
+    pub points: i64,
+    pub reason: PointsReason,
+    pub order_id: Option<i32>,
+    /// Credits only
+    pub expires_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LoyaltyEntryEntity {
+    /// Credits get their expiry date here; debits must be negative.
+    pub fn new(
+        user_id: i32,
+        points: i64,
+        reason: PointsReason,
+        order_id: Option<i32>,
+        note: Option<String>,
+        created_by: Option<i32>,
+    ) -> Result<Self> {
+        if points == 0 {
+            return Err(anyhow!("Points entry cannot be zero"));
+        }
+        if reason.is_credit() != (points > 0) {
+            return Err(anyhow!("Wrong sign for a {} entry", reason.as_str()));
+        }
+
+        let now = Utc::now();
+        let expires_at = if points > 0 {
+            Some(
+                now.checked_add_months(Months::new(POINTS_EXPIRY_MONTHS))
+                    .ok_or_else(|| anyhow!("Invalid expiry date"))?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Self {
+            id: 0,
+            user_id,
+            points,
+            reason,
+            order_id,
+            expires_at,
+            note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+            created_by,
+            created_at: now,
+        })
+    }
+}
+
+/// Checkout value of `points`
+pub fn points_value(points: i64) -> i64 {
+    points * POINT_VALUE
+}
+
+/// Points worth at most `amount`
+pub fn points_for_amount(amount: i64) -> i64 {
+    amount / POINT_VALUE
+}
+
+/// Points to give back for `amount` refunded from a points payment,
+/// rounded in the member's favour
+pub fn points_to_return(amount: i64) -> i64 {
+    (amount + POINT_VALUE - 1) / POINT_VALUE
+}
@@ -1,2 +1,32 @@
+pub mod address;
+pub mod book;
+pub mod book_metadata;
+pub mod book_price;
+pub mod cart;
+pub mod catalog_import_job;
+pub mod catalog_import_record;
+pub mod catalog_record;
+pub mod coupon;
+pub mod digital_asset;
+pub mod download_log;
+pub mod download_watermark;
+pub mod entitlement;
+pub mod inventory_movement;
+pub mod invoice;
+pub mod loyalty_campaign;
+pub mod loyalty_entry;
+pub mod notification_job;
+pub mod order;
+pub mod payment;
+pub mod promotion;
+pub mod return_request;
+pub mod review;
 pub mod role;
+pub mod shipping_zone;
+pub mod stock_subscription;
+pub mod store;
+pub mod stored_value_account;
+pub mod stored_value_entry;
+pub mod tax_rate;
 pub mod user;
+pub mod wishlist;
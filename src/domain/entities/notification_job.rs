@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::str::FromStr;
+
+use crate::domain::value_objects::notification_status::NotificationStatus;
+
+/// Attempts before a job is given up on
+pub const MAX_NOTIFICATION_ATTEMPTS: i32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// payload: `{ "book_id", "isbn", "title" }`
+    BackInStock,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BackInStock => "back_in_stock",
+        }
+    }
+}
+
+impl FromStr for NotificationKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "back_in_stock" => Ok(Self::BackInStock),
+            _ => Err(anyhow!("Invalid notification kind: {}", s)),
+        }
+    }
+}
+
+/// A queued message to a user, delivered by a background worker.
+#[derive(Debug, Clone)]
+pub struct NotificationJobEntity {
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: NotificationKind,
+    pub payload: serde_json::Value,
+    pub status: NotificationStatus,
+    /// Incremented every time a worker claims the job
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// Not picked up before this time (retry backoff / claim lease)
+    pub run_after: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NotificationJobEntity {
+    pub fn mark_sent(&mut self) {
+        self.status = NotificationStatus::Sent;
+        self.last_error = None;
+        self.sent_at = Some(Utc::now());
+    }
+
+    /// Schedules a retry with exponential backoff, or fails the job for good.
+    pub fn mark_failed(&mut self, error: String) {
+        self.last_error = Some(error);
+        if self.attempts >= MAX_NOTIFICATION_ATTEMPTS {
+            self.status = NotificationStatus::Failed;
+        } else {
+            self.status = NotificationStatus::Pending;
+            // 1, 2, 4, 8 นาที
+            self.run_after = Utc::now() + Duration::minutes(1 << (self.attempts - 1).clamp(0, 10));
+        }
+    }
+}
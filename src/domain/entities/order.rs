@@ -0,0 +1,396 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::domain::{
+    services::tax_engine::{self, TaxMode},
+    value_objects::{
+        money::Currency,
+        order_status::OrderStatus,
+        postal_address::PostalAddress,
+        quantity::Quantity,
+        shipping_method_kind::ShippingMethodKind,
+    },
+};
+
+/// A line of a placed order. Price and tax are frozen at checkout.
+#[derive(Debug, Clone)]
+pub struct OrderItemEntity {
+    pub id: i32,
+    pub book_id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub quantity: Quantity,
+    /// Unit price as shown in the store
+    pub unit_price: i64,
+    pub price_includes_tax: bool,
+    pub tax_rate_bps: i32,
+    pub tax_amount: i64,
+    /// Always tax-inclusive
+    pub line_total: i64,
+    /// Pre-order lines: lowest shelf price seen before release
+    pub lowest_unit_price: Option<i64>,
+}
+
+impl OrderItemEntity {
+    /// Freezes a line. `mode` tells whether `unit_price` already includes tax.
+    pub fn new(
+        book_id: i32,
+        isbn: String,
+        title: String,
+        quantity: i32,
+        unit_price: i64,
+        tax_rate_bps: i32,
+        mode: TaxMode,
+    ) -> Result<Self> {
+        if unit_price < 0 {
+            return Err(anyhow!("Unit price cannot be negative"));
+        }
+        if tax_rate_bps < 0 {
+            return Err(anyhow!("Tax rate cannot be negative"));
+        }
+
+        let quantity = Quantity::new(quantity)?;
+        let (tax_amount, line_total) = line_amounts(unit_price, quantity, tax_rate_bps, mode);
+
+        Ok(Self {
+            id: 0,
+            book_id,
+            isbn,
+            title,
+            quantity,
+            unit_price,
+            price_includes_tax: mode == TaxMode::Inclusive,
+            tax_rate_bps,
+            tax_amount,
+            line_total,
+            lowest_unit_price: None,
+        })
+    }
+
+    /// Lowers the unit price, recomputing tax and total at the frozen rate.
+    pub fn reprice(&mut self, unit_price: i64) -> Result<()> {
+        if unit_price < 0 || unit_price > self.unit_price {
+            return Err(anyhow!("A line can only be repriced down"));
+        }
+        let mode = if self.price_includes_tax {
+            TaxMode::Inclusive
+        } else {
+            TaxMode::Exclusive
+        };
+        (self.tax_amount, self.line_total) =
+            line_amounts(unit_price, self.quantity, self.tax_rate_bps, mode);
+        self.unit_price = unit_price;
+        Ok(())
+    }
+}
+
+/// (tax, tax-inclusive total) of a line
+fn line_amounts(unit_price: i64, quantity: Quantity, tax_rate_bps: i32, mode: TaxMode) -> (i64, i64) {
+    let amount = unit_price * quantity.value() as i64;
+    // ภาษีคิดต่อ line (ไม่ใช่ต่อชิ้น) เพื่อลดความคลาดเคลื่อนจากการปัดเศษ
+    match mode {
+        TaxMode::Inclusive => (tax_engine::tax_from_gross(amount, tax_rate_bps), amount),
+        TaxMode::Exclusive => {
+            let tax = tax_engine::tax_from_net(amount, tax_rate_bps);
+            (tax, amount + tax)
+        }
+    }
+}
+
+/// A promotion applied at checkout, frozen with its discount.
+#[derive(Debug, Clone)]
+pub struct OrderPromotion {
+    pub id: i32,
+    pub promotion_id: i32,
+    pub name: String,
+    pub coupon_id: Option<i32>,
+    pub coupon_code: Option<String>,
+    pub discount: i64,
+}
+
+/// Delivery option chosen at checkout, frozen with its price.
+#[derive(Debug, Clone)]
+pub struct OrderShipping {
+    /// `None` once the method has been deleted
+    pub method_id: Option<i32>,
+    pub method_name: String,
+    pub kind: ShippingMethodKind,
+    pub amount: i64,
+    pub estimated_from: NaiveDate,
+    pub estimated_to: NaiveDate,
+}
+
+/// One entry of the order's status history.
+#[derive(Debug, Clone)]
+pub struct OrderStatusChange {
+    pub id: i32,
+    pub from_status: Option<OrderStatus>,
+    pub to_status: OrderStatus,
+    pub changed_by: Option<i32>,
+    pub note: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderEntity {
+    pub id: i32,
+    pub user_id: i32,
+    /// Store the order was placed in; its currency and tax rules apply
+    pub store_id: i32,
+    pub status: OrderStatus,
+    pub currency: Currency,
+    pub items: Vec<OrderItemEntity>,
+    pub subtotal: i64,
+    pub discount_total: i64,
+    pub shipping_total: i64,
+    pub tax_total: i64,
+    /// subtotal - discount_total + shipping_total
+    pub total: i64,
+    pub promotions: Vec<OrderPromotion>,
+    pub shipping: Option<OrderShipping>,
+    /// Copies of the addresses used at checkout; later address book edits do not touch them
+    pub shipping_address: Option<PostalAddress>,
+    pub billing_address: Option<PostalAddress>,
+    pub tracking_number: Option<String>,
+    /// Pre-orders only: the day the payment is captured and the order released
+    pub release_date: Option<NaiveDate>,
+    /// Full history; entries with `id == 0` have not been persisted yet
+    pub history: Vec<OrderStatusChange>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OrderEntity {
+    /// Places a new order awaiting payment.
+    pub fn place(
+        user_id: i32,
+        store_id: i32,
+        currency: Currency,
+        items: Vec<OrderItemEntity>,
+    ) -> Result<Self> {
+        if items.is_empty() {
+            return Err(anyhow!("Order must contain at least one item"));
+        }
+
+        let now = Utc::now();
+        let subtotal = items.iter().map(|i| i.line_total).sum();
+        let tax_total = items.iter().map(|i| i.tax_amount).sum();
+
+        Ok(Self {
+            id: 0,
+            user_id,
+            store_id,
+            status: OrderStatus::PendingPayment,
+            currency,
+            items,
+            subtotal,
+            discount_total: 0,
+            shipping_total: 0,
+            tax_total,
+            total: subtotal,
+            promotions: Vec::new(),
+            shipping: None,
+            shipping_address: None,
+            billing_address: None,
+            tracking_number: None,
+            release_date: None,
+            history: vec![OrderStatusChange {
+                id: 0,
+                from_status: None,
+                to_status: OrderStatus::PendingPayment,
+                changed_by: Some(user_id),
+                note: None,
+                changed_at: now,
+            }],
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Applies checkout discounts.
+    pub fn apply_promotions(&mut self, promotions: Vec<OrderPromotion>) -> Result<()> {
+        if self.id != 0 {
+            return Err(anyhow!("Promotions can only be applied before the order is placed"));
+        }
+
+        let discount_total: i64 = promotions.iter().map(|p| p.discount).sum();
+        if discount_total < 0 || discount_total > self.subtotal {
+            return Err(anyhow!("Discount must be between 0 and the order subtotal"));
+        }
+
+        self.discount_total = discount_total;
+        self.promotions = promotions;
+        self.recalculate_totals();
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Makes this a pre-order released on `release_date`. Every line starts
+    /// its price-drop protection at the price paid.
+    pub fn set_release_date(&mut self, release_date: NaiveDate) -> Result<()> {
+        if self.id != 0 {
+            return Err(anyhow!("Release date can only be set before the order is placed"));
+        }
+        for item in &mut self.items {
+            item.lowest_unit_price = Some(item.unit_price);
+        }
+        self.release_date = Some(release_date);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn is_preorder(&self) -> bool {
+        self.release_date.is_some()
+    }
+
+    /// Applies the lowest price seen before release to every pre-order line.
+    /// Returns how much the total went down.
+    pub fn apply_price_protection(&mut self) -> Result<i64> {
+        if self.status != OrderStatus::Preordered {
+            return Err(anyhow!("Only pre-orders awaiting release are price protected"));
+        }
+
+        let before = self.total;
+        for item in &mut self.items {
+            if let Some(lowest) = item.lowest_unit_price.filter(|p| *p < item.unit_price) {
+                item.reprice(lowest)?;
+            }
+        }
+        self.recalculate_totals();
+        self.updated_at = Utc::now();
+        Ok(before - self.total)
+    }
+
+    /// Sets the delivery option chosen at checkout and adds its price to the total.
+    pub fn set_shipping(&mut self, shipping: OrderShipping) -> Result<()> {
+        if self.id != 0 {
+            return Err(anyhow!("Shipping can only be set before the order is placed"));
+        }
+        if shipping.amount < 0 {
+            return Err(anyhow!("Shipping amount cannot be negative"));
+        }
+
+        self.shipping_total = shipping.amount;
+        self.total = self.subtotal - self.discount_total + self.shipping_total;
+        self.shipping = Some(shipping);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Freezes the addresses used for this order.
+    pub fn set_addresses(
+        &mut self,
+        shipping_address: Option<PostalAddress>,
+        billing_address: Option<PostalAddress>,
+    ) -> Result<()> {
+        if self.id != 0 {
+            return Err(anyhow!("Addresses cannot be changed after the order is placed"));
+        }
+        self.shipping_address = shipping_address;
+        self.billing_address = billing_address;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// What was paid for the goods, i.e. the total without shipping.
+    pub fn goods_total(&self) -> i64 {
+        self.total - self.shipping_total
+    }
+
+    /// Payment authorized; the order waits for the release date.
+    pub fn hold_for_release(&mut self, changed_by: Option<i32>) -> Result<()> {
+        let release_date = self
+            .release_date
+            .ok_or_else(|| anyhow!("Order is not a pre-order"))?;
+        self.transition(
+            OrderStatus::Preordered,
+            changed_by,
+            Some(format!("Releases on {}", release_date)),
+        )
+    }
+
+    pub fn mark_paid(&mut self, changed_by: Option<i32>) -> Result<()> {
+        self.transition(OrderStatus::Paid, changed_by, None)
+    }
+
+    pub fn start_picking(&mut self, changed_by: Option<i32>) -> Result<()> {
+        self.transition(OrderStatus::Picking, changed_by, None)
+    }
+
+    pub fn mark_shipped(&mut self, changed_by: Option<i32>, tracking_number: Option<String>) -> Result<()> {
+        self.transition(OrderStatus::Shipped, changed_by, None)?;
+        self.tracking_number = tracking_number
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+        Ok(())
+    }
+
+    pub fn mark_delivered(&mut self, changed_by: Option<i32>) -> Result<()> {
+        self.transition(OrderStatus::Delivered, changed_by, None)
+    }
+
+    pub fn cancel(&mut self, changed_by: Option<i32>, reason: Option<String>) -> Result<()> {
+        self.transition(OrderStatus::Cancelled, changed_by, reason)
+    }
+
+    pub fn refund(&mut self, changed_by: Option<i32>, note: Option<String>) -> Result<()> {
+        self.transition(OrderStatus::Refunded, changed_by, note)
+    }
+
+    /// Whether stock reserved at checkout is still held by this order.
+    pub fn holds_stock(&self) -> bool {
+        self.status.holds_stock()
+    }
+
+    /// Status as last loaded or saved, before the transitions made since
+    pub fn stored_status(&self) -> OrderStatus {
+        self.history
+            .iter()
+            .find(|h| h.id == 0)
+            .and_then(|h| h.from_status)
+            .unwrap_or(self.status)
+    }
+
+    /// Subtotal, tax and total from the lines. VAT is included in prices, so
+    /// the tax shrinks in proportion to the discounted total.
+    fn recalculate_totals(&mut self) {
+        self.subtotal = self.items.iter().map(|i| i.line_total).sum();
+        self.discount_total = self.discount_total.min(self.subtotal);
+
+        let goods_total = self.subtotal - self.discount_total;
+        let items_tax: i64 = self.items.iter().map(|i| i.tax_amount).sum();
+        self.tax_total = if self.subtotal == 0 {
+            0
+        } else {
+            (items_tax * goods_total * 2 + self.subtotal) / (self.subtotal * 2)
+        };
+        self.total = goods_total + self.shipping_total;
+    }
+
+    fn transition(
+        &mut self,
+        to: OrderStatus,
+        changed_by: Option<i32>,
+        note: Option<String>,
+    ) -> Result<()> {
+        if !self.status.can_transition_to(to) {
+            return Err(anyhow!(
+                "Cannot change order status from {} to {}",
+                self.status,
+                to
+            ));
+        }
+
+        let now = Utc::now();
+        self.history.push(OrderStatusChange {
+            id: 0,
+            from_status: Some(self.status),
+            to_status: to,
+            changed_by,
+            note,
+            changed_at: now,
+        });
+        self.status = to;
+        self.updated_at = now;
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::payment_status::PaymentStatus;
+
+/// One payment attempt against an order. Every attempt is kept, including declines.
+#[derive(Debug, Clone)]
+pub struct PaymentEntity {
+    pub id: i32,
+    pub order_id: i32,
+    pub provider: String,
+    pub provider_intent_id: Option<String>,
+    pub amount: i64,
+    pub currency: String,
+    pub status: PaymentStatus,
+    pub captured_amount: i64,
+    pub refunded_amount: i64,
+    pub next_action_url: Option<String>,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PaymentEntity {
+    pub fn new(order_id: i32, provider: String, amount: i64, currency: String) -> Result<Self> {
+        if amount <= 0 {
+            return Err(anyhow!("Payment amount must be positive"));
+        }
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            order_id,
+            provider,
+            provider_intent_id: None,
+            amount,
+            currency: currency.trim().to_uppercase(),
+            status: PaymentStatus::Pending,
+            captured_amount: 0,
+            refunded_amount: 0,
+            next_action_url: None,
+            failure_reason: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn attach_intent(&mut self, intent_id: String) {
+        self.provider_intent_id = Some(intent_id);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn require_action(&mut self, next_action_url: Option<String>) -> Result<()> {
+        self.ensure_status(&[PaymentStatus::Pending], "require action")?;
+        self.status = PaymentStatus::RequiresAction;
+        self.next_action_url = next_action_url;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn authorize(&mut self) -> Result<()> {
+        self.ensure_status(
+            &[PaymentStatus::Pending, PaymentStatus::RequiresAction],
+            "authorize",
+        )?;
+        self.status = PaymentStatus::Authorized;
+        self.next_action_url = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn capture(&mut self, amount: i64) -> Result<()> {
+        self.ensure_status(&[PaymentStatus::Authorized], "capture")?;
+        if amount <= 0 || amount > self.amount {
+            return Err(anyhow!("Capture amount must be between 1 and the authorized amount"));
+        }
+        self.status = PaymentStatus::Captured;
+        self.captured_amount = amount;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn decline(&mut self, reason: Option<String>) -> Result<()> {
+        self.ensure_status(
+            &[PaymentStatus::Pending, PaymentStatus::RequiresAction, PaymentStatus::Authorized],
+            "decline",
+        )?;
+        self.status = PaymentStatus::Declined;
+        self.failure_reason = reason;
+        self.next_action_url = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn void(&mut self) -> Result<()> {
+        self.ensure_status(
+            &[PaymentStatus::RequiresAction, PaymentStatus::Authorized],
+            "void",
+        )?;
+        self.status = PaymentStatus::Voided;
+        self.next_action_url = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn record_refund(&mut self, amount: i64) -> Result<()> {
+        self.ensure_status(
+            &[PaymentStatus::Captured, PaymentStatus::PartiallyRefunded],
+            "refund",
+        )?;
+        if amount <= 0 || amount > self.refundable_amount() {
+            return Err(anyhow!("Refund amount exceeds the refundable amount"));
+        }
+        self.refunded_amount += amount;
+        self.status = if self.refunded_amount == self.captured_amount {
+            PaymentStatus::Refunded
+        } else {
+            PaymentStatus::PartiallyRefunded
+        };
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn refundable_amount(&self) -> i64 {
+        self.captured_amount - self.refunded_amount
+    }
+
+    /// Still waiting on the customer or the capture; may yet take money
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            PaymentStatus::Pending | PaymentStatus::RequiresAction | PaymentStatus::Authorized
+        )
+    }
+
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self.status,
+            PaymentStatus::Captured | PaymentStatus::PartiallyRefunded | PaymentStatus::Refunded
+        )
+    }
+
+    fn ensure_status(&self, allowed: &[PaymentStatus], action: &str) -> Result<()> {
+        if !allowed.contains(&self.status) {
+            return Err(anyhow!("Cannot {} a payment that is {}", action, self.status));
+        }
+        Ok(())
+    }
+}
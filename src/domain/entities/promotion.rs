@@ -0,0 +1,113 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// When a promotion applies. All conditions of a promotion must hold.
+///
+/// `Category` and `Author` select the cart lines the action works on; the
+/// others are checked against the whole cart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromotionCondition {
+    Category { category: String },
+    Author { author: String },
+    /// Cart subtotal (before discounts) of at least `amount` minor units
+    MinCartTotal { amount: i64 },
+    CustomerRole { role: String },
+    DateWindow {
+        starts_at: Option<DateTime<Utc>>,
+        ends_at: Option<DateTime<Utc>>,
+    },
+}
+
+/// What a promotion gives.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromotionAction {
+    PercentOff { percent: i32 },
+    /// Fixed amount off the matching lines, in minor units
+    FixedOff { amount: i64 },
+    /// "Buy 3 pay 2": every `buy` matching copies, the cheapest `buy - pay` are free.
+    /// BOGO is `buy: 2, pay: 1`.
+    BuyXPayY { buy: i32, pay: i32 },
+    FreeShipping,
+}
+
+impl PromotionAction {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::PercentOff { percent } if !(1..=100).contains(percent) => {
+                Err(anyhow!("Percent off must be between 1 and 100"))
+            }
+            Self::FixedOff { amount } if *amount <= 0 => {
+                Err(anyhow!("Fixed discount must be positive"))
+            }
+            Self::BuyXPayY { buy, pay } if *pay < 1 || buy <= pay => {
+                Err(anyhow!("Buy quantity must be greater than pay quantity"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PromotionEntity {
+    pub id: i32,
+    pub name: String,
+    pub conditions: Vec<PromotionCondition>,
+    pub action: PromotionAction,
+    /// Higher priority is evaluated first
+    pub priority: i32,
+    /// Non-stackable promotions are only applied alone
+    pub stackable: bool,
+    /// Only applies when the cart carries one of its coupon codes
+    pub requires_coupon: bool,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PromotionEntity {
+    pub fn new(
+        name: String,
+        conditions: Vec<PromotionCondition>,
+        action: PromotionAction,
+        priority: i32,
+        stackable: bool,
+        requires_coupon: bool,
+    ) -> Result<Self> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow!("Promotion name cannot be empty"));
+        }
+        if name.len() > 255 {
+            return Err(anyhow!("Promotion name too long (max 255 chars)"));
+        }
+        action.validate()?;
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            name,
+            conditions,
+            action,
+            priority,
+            stackable,
+            requires_coupon,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn deactivate(&mut self) {
+        self.is_active = false;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn activate(&mut self) {
+        self.is_active = true;
+        self.updated_at = Utc::now();
+    }
+}
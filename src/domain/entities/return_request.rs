@@ -0,0 +1,150 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::{
+    quantity::Quantity,
+    return_status::{ReturnCondition, ReturnStatus},
+};
+
+/// A customer's request to send back copies from one order line (RMA).
+#[derive(Debug, Clone)]
+pub struct ReturnRequestEntity {
+    pub id: i32,
+    pub order_id: i32,
+    pub order_item_id: i32,
+    pub book_id: i32,
+    pub user_id: i32,
+    pub quantity: Quantity,
+    pub reason: String,
+    pub status: ReturnStatus,
+    pub reviewed_by: Option<i32>,
+    pub review_note: Option<String>,
+    /// Set when the parcel is received
+    pub condition: Option<ReturnCondition>,
+    /// Amount refunded to the customer, in minor units
+    pub refund_amount: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReturnRequestEntity {
+    pub fn new(
+        order_id: i32,
+        order_item_id: i32,
+        book_id: i32,
+        user_id: i32,
+        quantity: i32,
+        reason: String,
+    ) -> Result<Self> {
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(anyhow!("Return reason cannot be empty"));
+        }
+        if reason.len() > 1000 {
+            return Err(anyhow!("Return reason too long (max 1000 chars)"));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: 0,
+            order_id,
+            order_item_id,
+            book_id,
+            user_id,
+            quantity: Quantity::new(quantity)?,
+            reason,
+            status: ReturnStatus::Requested,
+            reviewed_by: None,
+            review_note: None,
+            condition: None,
+            refund_amount: 0,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn approve(&mut self, reviewer_id: i32, note: Option<String>) -> Result<()> {
+        self.transition(ReturnStatus::Approved)?;
+        self.reviewed_by = Some(reviewer_id);
+        self.review_note = note;
+        Ok(())
+    }
+
+    pub fn reject(&mut self, reviewer_id: i32, note: Option<String>) -> Result<()> {
+        self.transition(ReturnStatus::Rejected)?;
+        self.reviewed_by = Some(reviewer_id);
+        self.review_note = note;
+        Ok(())
+    }
+
+    pub fn receive(&mut self, condition: ReturnCondition) -> Result<()> {
+        self.transition(ReturnStatus::Received)?;
+        self.condition = Some(condition);
+        Ok(())
+    }
+
+    /// Fixes the amount before any money moves; the refund's parts are
+    /// saved together with this state.
+    pub fn start_refund(&mut self, amount: i64) -> Result<()> {
+        if amount < 0 {
+            return Err(anyhow!("Refund amount cannot be negative"));
+        }
+        self.transition(ReturnStatus::Refunding)?;
+        self.refund_amount = amount;
+        Ok(())
+    }
+
+    pub fn mark_refunded(&mut self) -> Result<()> {
+        self.transition(ReturnStatus::Refunded)
+    }
+
+    /// Whether the request still counts against the line's returnable quantity.
+    pub fn counts_against_line(&self) -> bool {
+        self.status != ReturnStatus::Rejected
+    }
+
+    fn transition(&mut self, to: ReturnStatus) -> Result<()> {
+        if !self.status.can_transition_to(to) {
+            return Err(anyhow!(
+                "Cannot change return status from {} to {}",
+                self.status,
+                to
+            ));
+        }
+        self.status = to;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+/// One part of a return's refund: money given back through one of the
+/// order's payments, or as store credit when `payment_id` is None.
+#[derive(Debug, Clone)]
+pub struct ReturnRefundEntity {
+    pub id: i32,
+    pub return_id: i32,
+    pub payment_id: Option<i32>,
+    /// Minor units
+    pub amount: i64,
+    /// Set in the transaction that records the money as given back
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ReturnRefundEntity {
+    pub fn new(return_id: i32, payment_id: Option<i32>, amount: i64) -> Result<Self> {
+        if amount <= 0 {
+            return Err(anyhow!("Refund amount must be positive"));
+        }
+
+        Ok(Self {
+            id: 0,
+            return_id,
+            payment_id,
+            amount,
+            completed_at: None,
+        })
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completed_at.is_some()
+    }
+}
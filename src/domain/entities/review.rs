@@ -0,0 +1,121 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::{rating::Rating, review_status::ReviewStatus};
+
+/// A customer's review of a book. New and edited reviews wait for moderation.
+#[derive(Debug, Clone)]
+pub struct ReviewEntity {
+    pub id: i32,
+    pub book_id: i32,
+    pub user_id: i32,
+    /// Delivered order the reviewer bought the book in
+    pub order_id: Option<i32>,
+    pub rating: Rating,
+    pub title: String,
+    pub body: String,
+    pub verified_purchase: bool,
+    pub status: ReviewStatus,
+    pub rejection_reason: Option<String>,
+    pub moderated_by: Option<i32>,
+    pub moderated_at: Option<DateTime<Utc>>,
+    pub helpful_count: i32,
+    pub unhelpful_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReviewEntity {
+    /// A review backed by a delivered order (verified purchase).
+    pub fn new(
+        book_id: i32,
+        user_id: i32,
+        order_id: i32,
+        rating: i32,
+        title: String,
+        body: String,
+    ) -> Result<Self> {
+        let (title, body) = Self::validate_text(title, body)?;
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            book_id,
+            user_id,
+            order_id: Some(order_id),
+            rating: Rating::new(rating)?,
+            title,
+            body,
+            verified_purchase: true,
+            status: ReviewStatus::Pending,
+            rejection_reason: None,
+            moderated_by: None,
+            moderated_at: None,
+            helpful_count: 0,
+            unhelpful_count: 0,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Edits go back to the moderation queue.
+    pub fn edit(&mut self, rating: i32, title: String, body: String) -> Result<()> {
+        let (title, body) = Self::validate_text(title, body)?;
+        self.rating = Rating::new(rating)?;
+        self.title = title;
+        self.body = body;
+        self.status = ReviewStatus::Pending;
+        self.rejection_reason = None;
+        self.moderated_by = None;
+        self.moderated_at = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn approve(&mut self, moderator_id: i32) -> Result<()> {
+        if self.status == ReviewStatus::Approved {
+            return Err(anyhow!("Review is already approved"));
+        }
+        self.moderate(moderator_id, ReviewStatus::Approved, None);
+        Ok(())
+    }
+
+    pub fn reject(&mut self, moderator_id: i32, reason: String) -> Result<()> {
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(anyhow!("Rejection reason cannot be empty"));
+        }
+        if self.status == ReviewStatus::Rejected {
+            return Err(anyhow!("Review is already rejected"));
+        }
+        self.moderate(moderator_id, ReviewStatus::Rejected, Some(reason));
+        Ok(())
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.status == ReviewStatus::Approved
+    }
+
+    fn moderate(&mut self, moderator_id: i32, status: ReviewStatus, reason: Option<String>) {
+        let now = Utc::now();
+        self.status = status;
+        self.rejection_reason = reason;
+        self.moderated_by = Some(moderator_id);
+        self.moderated_at = Some(now);
+        self.updated_at = now;
+    }
+
+    fn validate_text(title: String, body: String) -> Result<(String, String)> {
+        let title = title.trim().to_string();
+        if title.is_empty() {
+            return Err(anyhow!("Review title cannot be empty"));
+        }
+        if title.chars().count() > 150 {
+            return Err(anyhow!("Review title too long (max 150 chars)"));
+        }
+        let body = body.trim().to_string();
+        if body.chars().count() > 5000 {
+            return Err(anyhow!("Review too long (max 5000 chars)"));
+        }
+        Ok((title, body))
+    }
+}
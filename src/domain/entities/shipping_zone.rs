@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+use crate::domain::value_objects::shipping_method_kind::ShippingMethodKind;
+
+/// What a rate table is keyed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateBasis {
+    /// Chargeable parcel weight in grams
+    Weight,
+    ItemCount,
+    /// Cart total after discounts, in minor units
+    Subtotal,
+}
+
+impl RateBasis {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Weight => "weight",
+            Self::ItemCount => "item_count",
+            Self::Subtotal => "subtotal",
+        }
+    }
+}
+
+impl FromStr for RateBasis {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "weight" => Ok(Self::Weight),
+            "item_count" => Ok(Self::ItemCount),
+            "subtotal" => Ok(Self::Subtotal),
+            _ => Err(anyhow!("Invalid rate basis: {}", s)),
+        }
+    }
+}
+
+/// One bracket of a rate table: `min_value <= value < max_value` costs `amount`.
+#[derive(Debug, Clone)]
+pub struct ShippingRateEntity {
+    pub id: i32,
+    pub min_value: i64,
+    /// `None` means no upper bound
+    pub max_value: Option<i64>,
+    pub amount: i64,
+}
+
+impl ShippingRateEntity {
+    pub fn new(min_value: i64, max_value: Option<i64>, amount: i64) -> Result<Self> {
+        if min_value < 0 {
+            return Err(anyhow!("Rate bracket cannot start below 0"));
+        }
+        if max_value.is_some_and(|max| max <= min_value) {
+            return Err(anyhow!("Rate bracket upper bound must be above its lower bound"));
+        }
+        if amount < 0 {
+            return Err(anyhow!("Shipping amount cannot be negative"));
+        }
+        Ok(Self {
+            id: 0,
+            min_value,
+            max_value,
+            amount,
+        })
+    }
+
+    pub fn covers(&self, value: i64) -> bool {
+        value >= self.min_value && self.max_value.is_none_or(|max| value < max)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ShippingMethodEntity {
+    pub id: i32,
+    pub zone_id: i32,
+    pub code: String,
+    pub name: String,
+    pub kind: ShippingMethodKind,
+    pub rate_basis: RateBasis,
+    /// Delivery estimate in business days after the order is placed
+    pub min_days: i32,
+    pub max_days: i32,
+    pub max_weight_grams: Option<i32>,
+    pub is_active: bool,
+    /// Sorted by `min_value`
+    pub rates: Vec<ShippingRateEntity>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ShippingMethodEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        zone_id: i32,
+        code: String,
+        name: String,
+        kind: ShippingMethodKind,
+        rate_basis: RateBasis,
+        min_days: i32,
+        max_days: i32,
+        max_weight_grams: Option<i32>,
+        mut rates: Vec<ShippingRateEntity>,
+    ) -> Result<Self> {
+        let code = code.trim().to_lowercase();
+        if code.is_empty() || code.len() > 50 {
+            return Err(anyhow!("Method code must be 1-50 characters"));
+        }
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow!("Method name cannot be empty"));
+        }
+        if min_days < 0 || max_days < min_days {
+            return Err(anyhow!("Delivery days must satisfy 0 <= min_days <= max_days"));
+        }
+        if max_weight_grams.is_some_and(|w| w <= 0) {
+            return Err(anyhow!("Max weight must be positive"));
+        }
+
+        // Pickup ฟรีได้โดยไม่ต้องมีตาราง ส่วนวิธีอื่นต้องมีราคาอย่างน้อยหนึ่งช่วง
+        if rates.is_empty() && kind != ShippingMethodKind::StorePickup {
+            return Err(anyhow!("Shipping method needs at least one rate"));
+        }
+        rates.sort_by_key(|r| r.min_value);
+        for pair in rates.windows(2) {
+            if pair[0].max_value.is_none_or(|max| max > pair[1].min_value) {
+                return Err(anyhow!("Rate brackets must not overlap"));
+            }
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: 0,
+            zone_id,
+            code,
+            name,
+            kind,
+            rate_basis,
+            min_days,
+            max_days,
+            max_weight_grams,
+            is_active: true,
+            rates,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Price from the rate table, or `None` when no bracket covers `value`.
+    pub fn rate_for(&self, value: i64) -> Option<i64> {
+        if self.rates.is_empty() {
+            return (self.kind == ShippingMethodKind::StorePickup).then_some(0);
+        }
+        self.rates.iter().find(|r| r.covers(value)).map(|r| r.amount)
+    }
+
+    pub fn deactivate(&mut self) {
+        self.is_active = false;
+        self.updated_at = Utc::now();
+    }
+}
+
+/// A delivery area: a whole country, or part of it selected by postal code prefix.
+#[derive(Debug, Clone)]
+pub struct ShippingZoneEntity {
+    pub id: i32,
+    pub name: String,
+    /// ISO 3166-1 alpha-2
+    pub country: String,
+    /// Empty means the whole country
+    pub postal_prefixes: Vec<String>,
+    pub is_active: bool,
+    pub methods: Vec<ShippingMethodEntity>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ShippingZoneEntity {
+    pub fn new(name: String, country: String, postal_prefixes: Vec<String>) -> Result<Self> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow!("Zone name cannot be empty"));
+        }
+        let country = country.trim().to_uppercase();
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(anyhow!("Country must be an ISO 3166 alpha-2 code"));
+        }
+        let postal_prefixes: Vec<String> = postal_prefixes
+            .into_iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if postal_prefixes.iter().any(|p| !p.chars().all(|c| c.is_ascii_alphanumeric())) {
+            return Err(anyhow!("Postal prefixes must be alphanumeric"));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: 0,
+            name,
+            country,
+            postal_prefixes,
+            is_active: true,
+            methods: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// How specifically this zone covers the destination: `None` if it does
+    /// not, otherwise the length of the matching postal prefix (0 = whole country).
+    pub fn specificity(&self, country: &str, postal_code: Option<&str>) -> Option<usize> {
+        if !self.is_active || !self.country.eq_ignore_ascii_case(country) {
+            return None;
+        }
+        if self.postal_prefixes.is_empty() {
+            return Some(0);
+        }
+        let postal_code = postal_code?.trim();
+        self.postal_prefixes
+            .iter()
+            .filter(|p| postal_code.starts_with(p.as_str()))
+            .map(|p| p.len())
+            .max()
+    }
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+
+/// A customer waiting for an out-of-stock book. It fires once: when stock
+/// comes back a notification is queued and `notified_at` is set.
+#[derive(Debug, Clone)]
+pub struct StockSubscriptionEntity {
+    pub id: i32,
+    pub user_id: i32,
+    pub book_id: i32,
+    pub notified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StockSubscriptionEntity {
+    pub fn new(user_id: i32, book_id: i32) -> Self {
+        Self {
+            id: 0,
+            user_id,
+            book_id,
+            notified_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        self.notified_at.is_none()
+    }
+}
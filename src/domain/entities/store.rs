@@ -0,0 +1,53 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::money::Currency;
+
+/// A storefront: sells in one currency and charges the tax of one jurisdiction.
+#[derive(Debug, Clone)]
+pub struct StoreEntity {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    pub currency: Currency,
+    pub jurisdiction: String,
+    /// Whether shelf prices are shown with tax included (Thai retail norm)
+    pub prices_include_tax: bool,
+    pub is_default: bool,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoreEntity {
+    pub fn new(
+        code: String,
+        name: String,
+        currency: Currency,
+        jurisdiction: String,
+        prices_include_tax: bool,
+    ) -> Result<Self> {
+        let code = code.trim().to_uppercase();
+        if code.is_empty() || code.len() > 30 {
+            return Err(anyhow!("Store code must be 1-30 characters"));
+        }
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow!("Store name cannot be empty"));
+        }
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            code,
+            name,
+            currency,
+            jurisdiction: jurisdiction.trim().to_uppercase(),
+            prices_include_tax,
+            is_default: false,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::str::FromStr;
+
+use crate::domain::value_objects::{gift_card_code::GiftCardCode, money::Currency};
+
+/// Gift cards are valid for 3 years unless issued with a later date...
+pub const DEFAULT_GIFT_CARD_VALIDITY_DAYS: i64 = 3 * 365;
+/// ...and never for less than a year
+pub const MIN_GIFT_CARD_VALIDITY_DAYS: i64 = 365;
+/// Store credit lapses 2 years after the last time it was topped up
+pub const STORE_CREDIT_VALIDITY_DAYS: i64 = 2 * 365;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoredValueKind {
+    /// Bearer card redeemed with its code
+    GiftCard,
+    /// Per-customer balance, e.g. issued from a refund
+    StoreCredit,
+}
+
+impl StoredValueKind {
+    /// Also the `provider` of payments made from this kind of balance
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GiftCard => "gift_card",
+            Self::StoreCredit => "store_credit",
+        }
+    }
+}
+
+impl FromStr for StoredValueKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "gift_card" => Ok(Self::GiftCard),
+            "store_credit" => Ok(Self::StoreCredit),
+            _ => Err(anyhow!("Invalid stored value kind: {}", s)),
+        }
+    }
+}
+
+/// A gift card or a customer's store credit. `balance` mirrors the sum of
+/// its ledger entries and only changes together with a new entry.
+#[derive(Debug, Clone)]
+pub struct StoredValueAccountEntity {
+    pub id: i32,
+    pub kind: StoredValueKind,
+    /// Gift cards only: SHA-256 of the code
+    pub code_hash: Option<String>,
+    pub code_last4: Option<String>,
+    /// Store credit only: the customer it belongs to
+    pub user_id: Option<i32>,
+    pub currency: Currency,
+    pub balance: i64,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoredValueAccountEntity {
+    /// A new, still empty gift card; the amount is loaded by its first entry.
+    pub fn gift_card(
+        code: &GiftCardCode,
+        currency: Currency,
+        expires_at: Option<DateTime<Utc>>,
+        issued_by: i32,
+    ) -> Result<Self> {
+        let now = Utc::now();
+        let expires_at =
+            expires_at.unwrap_or(now + Duration::days(DEFAULT_GIFT_CARD_VALIDITY_DAYS));
+        if expires_at < now + Duration::days(MIN_GIFT_CARD_VALIDITY_DAYS) {
+            return Err(anyhow!(
+                "Gift cards must be valid for at least {} days",
+                MIN_GIFT_CARD_VALIDITY_DAYS
+            ));
+        }
+
+        Ok(Self {
+            id: 0,
+            kind: StoredValueKind::GiftCard,
+            code_hash: Some(code.hash()),
+            code_last4: Some(code.last4().to_string()),
+            user_id: None,
+            currency,
+            balance: 0,
+            expires_at: Some(expires_at),
+            created_by: Some(issued_by),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn store_credit(user_id: i32, currency: Currency) -> Self {
+        let now = Utc::now();
+
+        Self {
+            id: 0,
+            kind: StoredValueKind::StoreCredit,
+            code_hash: None,
+            code_last4: None,
+            user_id: Some(user_id),
+            currency,
+            balance: 0,
+            expires_at: None,
+            created_by: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|e| e <= now)
+    }
+
+    /// Fails unless `amount` can be spent from this balance right now.
+    pub fn ensure_redeemable(&self, amount: i64, now: DateTime<Utc>) -> Result<()> {
+        if self.is_expired(now) {
+            return Err(anyhow!("This {} has expired", self.label()));
+        }
+        if amount <= 0 || amount > self.balance {
+            return Err(anyhow!("Insufficient {} balance", self.label()));
+        }
+        Ok(())
+    }
+
+    /// Topping up store credit restarts its validity period.
+    pub fn extend_validity(&mut self, now: DateTime<Utc>) {
+        if self.kind == StoredValueKind::StoreCredit {
+            let renewed = now + Duration::days(STORE_CREDIT_VALIDITY_DAYS);
+            self.expires_at = Some(self.expires_at.map_or(renewed, |e| e.max(renewed)));
+            self.updated_at = now;
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self.kind {
+            StoredValueKind::GiftCard => "gift card",
+            StoredValueKind::StoreCredit => "store credit",
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryReason {
+    /// Initial value of a gift card
+    Issue,
+    /// Spent on an order
+    Redemption,
+    /// Money from a redemption going back, on cancellation or refund
+    RedemptionRefund,
+    /// Store credit given instead of a refund to the original payment
+    RefundCredit,
+    /// Manual correction by an admin
+    Adjustment,
+    /// Remaining balance written off at expiry
+    Expiry,
+}
+
+impl EntryReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Issue => "issue",
+            Self::Redemption => "redemption",
+            Self::RedemptionRefund => "redemption_refund",
+            Self::RefundCredit => "refund_credit",
+            Self::Adjustment => "adjustment",
+            Self::Expiry => "expiry",
+        }
+    }
+}
+
+impl FromStr for EntryReason {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "issue" => Ok(Self::Issue),
+            "redemption" => Ok(Self::Redemption),
+            "redemption_refund" => Ok(Self::RedemptionRefund),
+            "refund_credit" => Ok(Self::RefundCredit),
+            "adjustment" => Ok(Self::Adjustment),
+            "expiry" => Ok(Self::Expiry),
+            _ => Err(anyhow!("Invalid stored value entry reason: {}", s)),
+        }
+    }
+}
+
+/// Append-only entry of the gift card / store credit ledger.
+#[derive(Debug, Clone)]
+pub struct StoredValueEntryEntity {
+    pub id: i32,
+    pub account_id: i32,
+    /// Signed change to the balance
+    pub amount: i64,
+    pub reason: EntryReason,
+    pub order_id: Option<i32>,
+    /// Required for adjustments
+    pub note: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredValueEntryEntity {
+    pub fn new(
+        account_id: i32,
+        amount: i64,
+        reason: EntryReason,
+        order_id: Option<i32>,
+        note: Option<String>,
+        created_by: Option<i32>,
+    ) -> Result<Self> {
+        if amount == 0 {
+            return Err(anyhow!("Ledger entry amount cannot be zero"));
+        }
+        let debit = matches!(reason, EntryReason::Redemption | EntryReason::Expiry);
+        let signed_ok = match reason {
+            EntryReason::Adjustment => true,
+            _ if debit => amount < 0,
+            _ => amount > 0,
+        };
+        if !signed_ok {
+            return Err(anyhow!("Wrong sign for a {} entry", reason.as_str()));
+        }
+
+        let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        if reason == EntryReason::Adjustment && note.is_none() {
+            return Err(anyhow!("Adjustments need a reason"));
+        }
+        if note.as_ref().is_some_and(|n| n.chars().count() > 500) {
+            return Err(anyhow!("Note too long (max 500 chars)"));
+        }
+
+        Ok(Self {
+            id: 0,
+            account_id,
+            amount,
+            reason,
+            order_id,
+            note,
+            created_by,
+            created_at: Utc::now(),
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::tax_class::TaxClass;
+
+/// Tax rate for one product class in one jurisdiction, valid for a period.
+#[derive(Debug, Clone)]
+pub struct TaxRateEntity {
+    pub id: i32,
+    /// ISO 3166 country code, optionally with a region (`TH`, `US-CA`)
+    pub jurisdiction: String,
+    pub tax_class: TaxClass,
+    /// Display name, e.g. "VAT"
+    pub name: String,
+    /// Rate in basis points (700 = 7%)
+    pub rate_bps: i32,
+    pub valid_from: DateTime<Utc>,
+    pub valid_to: Option<DateTime<Utc>>,
+}
+
+impl TaxRateEntity {
+    pub fn new(
+        jurisdiction: String,
+        tax_class: TaxClass,
+        name: String,
+        rate_bps: i32,
+        valid_from: DateTime<Utc>,
+    ) -> Result<Self> {
+        let jurisdiction = jurisdiction.trim().to_uppercase();
+        if jurisdiction.len() < 2 || jurisdiction.len() > 10 {
+            return Err(anyhow!("Invalid jurisdiction: {}", jurisdiction));
+        }
+        if !(0..=10_000).contains(&rate_bps) {
+            return Err(anyhow!("Tax rate must be between 0 and 10000 bps"));
+        }
+
+        Ok(Self {
+            id: 0,
+            jurisdiction,
+            tax_class,
+            name: name.trim().to_string(),
+            rate_bps,
+            valid_from,
+            valid_to: None,
+        })
+    }
+
+    pub fn is_valid_at(&self, at: DateTime<Utc>) -> bool {
+        self.valid_from <= at && self.valid_to.is_none_or(|to| at < to)
+    }
+}
@@ -6,6 +6,7 @@ use crate::domain::value_objects::{
     password::Password,
     person_name::PersonName,
     phone_number::PhoneNumber,
+    user_avatar::UserAvatar,
 };
 
 #[derive(Debug, Clone)]
@@ -18,6 +19,8 @@ pub struct UserEntity {
     pub sex: String,
     pub phone: PhoneNumber,
     pub password: Password,
+    /// Uploaded profile picture; changed only through the avatar upload
+    pub avatar: Option<UserAvatar>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -44,6 +47,7 @@ impl UserEntity {
             sex: sex.trim().to_uppercase(),
             phone: PhoneNumber::new(phone)?,
             password: Password::new(password)?,
+            avatar: None,
             is_active: true,
             created_at: now,
             updated_at: now,
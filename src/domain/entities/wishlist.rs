@@ -0,0 +1,109 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::share_token::ShareToken;
+
+pub const MAX_WISHLIST_ITEMS: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct WishlistItemEntity {
+    pub book_id: i32,
+    pub note: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+/// A named list of books a customer saved for later.
+#[derive(Debug, Clone)]
+pub struct WishlistEntity {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    /// Set while the list is shared; anyone with the token can read it
+    pub share_token: Option<ShareToken>,
+    pub items: Vec<WishlistItemEntity>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WishlistEntity {
+    pub fn new(user_id: i32, name: String) -> Result<Self> {
+        let now = Utc::now();
+
+        Ok(Self {
+            id: 0,
+            user_id,
+            name: Self::clean_name(name)?,
+            share_token: None,
+            items: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn rename(&mut self, name: String) -> Result<()> {
+        self.name = Self::clean_name(name)?;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Starts sharing the list; an already shared list keeps its token.
+    pub fn share(&mut self) -> &ShareToken {
+        if self.share_token.is_none() {
+            self.updated_at = Utc::now();
+        }
+        self.share_token.get_or_insert_with(ShareToken::generate)
+    }
+
+    /// Stops sharing; old links stop working.
+    pub fn unshare(&mut self) {
+        self.share_token = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Adds a book, or updates its note when it is already on the list.
+    pub fn add_item(&mut self, book_id: i32, note: Option<String>) -> Result<()> {
+        let note = Self::clean_note(note)?;
+        let now = Utc::now();
+
+        match self.items.iter_mut().find(|i| i.book_id == book_id) {
+            Some(item) => item.note = note,
+            None => {
+                if self.items.len() >= MAX_WISHLIST_ITEMS {
+                    return Err(anyhow!("Wishlist is full (max {} books)", MAX_WISHLIST_ITEMS));
+                }
+                self.items.push(WishlistItemEntity {
+                    book_id,
+                    note,
+                    added_at: now,
+                });
+            }
+        }
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn remove_item(&mut self, book_id: i32) -> Result<()> {
+        let before = self.items.len();
+        self.items.retain(|i| i.book_id != book_id);
+        if self.items.len() == before {
+            return Err(anyhow!("Book is not on this wishlist"));
+        }
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    fn clean_name(name: String) -> Result<String> {
+        let name = name.trim().to_string();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(anyhow!("Wishlist name must be 1-100 characters"));
+        }
+        Ok(name)
+    }
+
+    fn clean_note(note: Option<String>) -> Result<Option<String>> {
+        let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        if note.as_ref().is_some_and(|n| n.chars().count() > 500) {
+            return Err(anyhow!("Note too long (max 500 chars)"));
+        }
+        Ok(note)
+    }
+}
@@ -1,4 +1,4 @@
 pub mod entities;
 pub mod repositories;
+pub mod services;
 pub mod value_objects;
-
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::domain::entities::address::AddressEntity;
+
+#[async_trait]
+pub trait AddressRepository: Send + Sync {
+    async fn find_by_user(&self, user_id: i32) -> anyhow::Result<Vec<AddressEntity>>;
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<AddressEntity>>;
+    /// Setting a default flag clears it on the user's other addresses
+    async fn save(&self, address: &AddressEntity) -> anyhow::Result<i32>;
+    async fn update(&self, address: &AddressEntity) -> anyhow::Result<AddressEntity>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use crate::domain::entities::book_metadata::{BookContributor, BookSubject};
+
+/// Credits and subject codes kept alongside a book (filled by catalog imports)
+#[async_trait]
+pub trait BookMetadataRepository: Send + Sync {
+    /// In credit order
+    async fn find_contributors(&self, book_id: i32) -> anyhow::Result<Vec<BookContributor>>;
+    /// Main subjects first
+    async fn find_subjects(&self, book_id: i32) -> anyhow::Result<Vec<BookSubject>>;
+}
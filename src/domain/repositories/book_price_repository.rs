@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::entities::book_price::BookPriceEntity;
+
+#[async_trait]
+pub trait BookPriceRepository: Send + Sync {
+    async fn find_by_books(&self, book_ids: &[i32]) -> anyhow::Result<Vec<BookPriceEntity>>;
+    /// Inserts or replaces the price for (book, store, currency)
+    async fn upsert(&self, price: &BookPriceEntity) -> anyhow::Result<BookPriceEntity>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+}
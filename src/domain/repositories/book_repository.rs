@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use crate::domain::{entities::book::BookEntity, value_objects::book_cover::BookCover};
+
+#[async_trait]
+pub trait BookRepository: Send + Sync {
+    async fn find_all(&self) -> anyhow::Result<Vec<BookEntity>>;
+    /// Up to `limit` books with an id above `after_id`, by id
+    async fn find_after(&self, after_id: i32, limit: i64) -> anyhow::Result<Vec<BookEntity>>;
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<BookEntity>>;
+    async fn find_by_ids(&self, ids: &[i32]) -> anyhow::Result<Vec<BookEntity>>;
+    async fn find_by_isbn(&self, isbn: &str) -> anyhow::Result<Option<BookEntity>>;
+    async fn save(&self, book: &BookEntity) -> anyhow::Result<i32>;
+    /// Stock is not written here; it only moves through the inventory ledger
+    async fn update(&self, book: &BookEntity) -> anyhow::Result<BookEntity>;
+    /// Sets or clears the cover; returns the one it replaced
+    async fn update_cover(&self, book_id: i32, cover: Option<&BookCover>) -> anyhow::Result<Option<BookCover>>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+}
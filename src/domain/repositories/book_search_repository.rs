@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use crate::domain::entities::book::BookEntity;
+
+#[derive(Debug, Clone)]
+pub struct BookSearchHit {
+    pub book: BookEntity,
+    pub rank: f32,
+}
+
+/// One page of ranked results plus the total number of matches.
+#[derive(Debug, Clone)]
+pub struct BookSearchPage {
+    pub hits: Vec<BookSearchHit>,
+    pub total: i64,
+}
+
+#[async_trait]
+pub trait BookSearchRepository: Send + Sync {
+    /// Active books matching `tsquery`, best match first
+    async fn search(&self, tsquery: &str, offset: i64, limit: i64) -> anyhow::Result<BookSearchPage>;
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::entities::{book::BookEntity, user::UserEntity};
+
+/// Writes a whole spreadsheet import at once: every row or none.
+/// Entities with id 0 are inserted, the rest updated.
+#[async_trait]
+pub trait BulkImportRepository: Send + Sync {
+    async fn apply_books(&self, books: &[BookEntity]) -> anyhow::Result<()>;
+    async fn apply_users(&self, users: &[UserEntity]) -> anyhow::Result<()>;
+}
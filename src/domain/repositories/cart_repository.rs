@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use crate::domain::{entities::cart::CartEntity, value_objects::cart_token::CartToken};
+
+#[async_trait]
+pub trait CartRepository: Send + Sync {
+    async fn find_by_user(&self, user_id: i32) -> anyhow::Result<Option<CartEntity>>;
+    async fn find_by_token(&self, token: &CartToken) -> anyhow::Result<Option<CartEntity>>;
+    /// Inserts the cart together with its lines
+    async fn save(&self, cart: &CartEntity) -> anyhow::Result<i32>;
+    /// Persists owner and lines (lines are replaced as a whole)
+    async fn update(&self, cart: &CartEntity) -> anyhow::Result<CartEntity>;
+    /// `update` of a user's cart that has absorbed the guest cart
+    /// `guest_cart_id`, deleting the guest cart in the same transaction. Fails
+    /// and writes nothing when the guest cart is already gone.
+    async fn update_merged(&self, cart: &CartEntity, guest_cart_id: i32) -> anyhow::Result<CartEntity>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use crate::domain::entities::{
+    catalog_import_job::CatalogImportJobEntity,
+    catalog_import_record::{CatalogImportRecordEntity, ImportOutcome},
+    catalog_record::ImportedBook,
+};
+
+#[async_trait]
+pub trait CatalogImportRepository: Send + Sync {
+    async fn create_job(&self, job: &CatalogImportJobEntity) -> anyhow::Result<CatalogImportJobEntity>;
+    async fn find_job(&self, id: i32) -> anyhow::Result<Option<CatalogImportJobEntity>>;
+    /// Newest first
+    async fn find_jobs(&self, limit: i64) -> anyhow::Result<Vec<CatalogImportJobEntity>>;
+    /// Claims the oldest queued job, or a running one whose worker's lease
+    /// ran out: marks it running, bumps `attempts` and takes a fresh lease.
+    async fn claim_next(&self) -> anyhow::Result<Option<CatalogImportJobEntity>>;
+    /// Stores a batch of report lines together with the job's new checkpoint,
+    /// recounts the outcomes and renews the lease. Returns the updated job.
+    async fn checkpoint(
+        &self,
+        job: &CatalogImportJobEntity,
+        records: &[CatalogImportRecordEntity],
+    ) -> anyhow::Result<CatalogImportJobEntity>;
+    /// Saves status, error and finish time
+    async fn finish(&self, job: &CatalogImportJobEntity) -> anyhow::Result<()>;
+    /// Report lines after `after_index`, optionally of one outcome only
+    async fn find_records(
+        &self,
+        job_id: i32,
+        outcome: Option<ImportOutcome>,
+        after_index: i32,
+        limit: i64,
+    ) -> anyhow::Result<Vec<CatalogImportRecordEntity>>;
+    /// Inserts or updates the book (by `book.id`) with its credits, subjects
+    /// and currency prices in one transaction. Returns the book id.
+    async fn save_book(&self, imported: &ImportedBook) -> anyhow::Result<i32>;
+}
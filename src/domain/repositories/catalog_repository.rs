@@ -0,0 +1,133 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::str::FromStr;
+
+use crate::domain::{entities::book::BookEntity, value_objects::book_format::BookFormat};
+
+/// Upper bounds (minor units) of the price facet buckets; the last bucket is open-ended.
+pub const PRICE_FACET_BOUNDS: &[i64] = &[20_000, 50_000, 100_000, 200_000];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Facet {
+    Category,
+    Author,
+    Publisher,
+    Language,
+    Format,
+    /// Index into `PRICE_FACET_BOUNDS` buckets
+    Price,
+    /// Whole stars of the average rating (1-5)
+    Rating,
+    /// "in_stock" / "out_of_stock"
+    Availability,
+}
+
+impl Facet {
+    pub const ALL: [Facet; 8] = [
+        Self::Category,
+        Self::Author,
+        Self::Publisher,
+        Self::Language,
+        Self::Format,
+        Self::Price,
+        Self::Rating,
+        Self::Availability,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Category => "category",
+            Self::Author => "author",
+            Self::Publisher => "publisher",
+            Self::Language => "language",
+            Self::Format => "format",
+            Self::Price => "price",
+            Self::Rating => "rating",
+            Self::Availability => "availability",
+        }
+    }
+}
+
+impl FromStr for Facet {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.as_str() == s)
+            .ok_or_else(|| anyhow!("Invalid facet: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CatalogSort {
+    /// Search rank when there is a query, newest otherwise
+    #[default]
+    Relevance,
+    Newest,
+    PriceAsc,
+    PriceDesc,
+    Rating,
+    Title,
+}
+
+impl FromStr for CatalogSort {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "relevance" => Ok(Self::Relevance),
+            "newest" => Ok(Self::Newest),
+            "price_asc" => Ok(Self::PriceAsc),
+            "price_desc" => Ok(Self::PriceDesc),
+            "rating" => Ok(Self::Rating),
+            "title" => Ok(Self::Title),
+            _ => Err(anyhow!("Invalid sort: {}", s)),
+        }
+    }
+}
+
+/// Storefront filters. Values within one facet are OR-ed, facets are AND-ed.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogFilter {
+    /// From `search_text::to_tsquery`
+    pub tsquery: Option<String>,
+    pub categories: Vec<String>,
+    pub authors: Vec<String>,
+    pub publishers: Vec<String>,
+    pub languages: Vec<String>,
+    pub formats: Vec<BookFormat>,
+    pub price_min: Option<i64>,
+    pub price_max: Option<i64>,
+    pub min_rating: Option<i32>,
+    pub in_stock_only: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FacetCount {
+    pub facet: Facet,
+    pub value: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CatalogPage {
+    pub books: Vec<BookEntity>,
+    pub total: i64,
+    /// Each facet is counted with every filter applied except its own, so
+    /// the sidebar shows what selecting another value would return.
+    pub facets: Vec<FacetCount>,
+}
+
+#[async_trait]
+pub trait CatalogRepository: Send + Sync {
+    /// `facet_limit` caps the values returned per facet (most common first)
+    async fn browse(
+        &self,
+        filter: &CatalogFilter,
+        sort: CatalogSort,
+        offset: i64,
+        limit: i64,
+        facet_limit: i64,
+    ) -> anyhow::Result<CatalogPage>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::domain::entities::coupon::CouponEntity;
+
+#[async_trait]
+pub trait CouponRepository: Send + Sync {
+    async fn find_by_code(&self, code: &str) -> anyhow::Result<Option<CouponEntity>>;
+    async fn find_by_promotion(&self, promotion_id: i32) -> anyhow::Result<Vec<CouponEntity>>;
+    async fn save(&self, coupon: &CouponEntity) -> anyhow::Result<i32>;
+    async fn update(&self, coupon: &CouponEntity) -> anyhow::Result<CouponEntity>;
+    /// How many times `user_id` has redeemed the coupon
+    async fn count_redemptions(&self, coupon_id: i32, user_id: i32) -> anyhow::Result<i64>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use crate::domain::entities::digital_asset::DigitalAssetEntity;
+
+#[async_trait]
+pub trait DigitalAssetRepository: Send + Sync {
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<DigitalAssetEntity>>;
+    async fn find_by_book(&self, book_id: i32) -> anyhow::Result<Vec<DigitalAssetEntity>>;
+    async fn find_by_books(&self, book_ids: &[i32]) -> anyhow::Result<Vec<DigitalAssetEntity>>;
+    /// Replaces the book's asset of the same file format, returning the
+    /// storage key of the file it replaced (if any)
+    async fn save(&self, asset: &DigitalAssetEntity) -> anyhow::Result<(i32, Option<String>)>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+}
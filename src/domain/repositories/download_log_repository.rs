@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use crate::domain::entities::download_log::DownloadLogEntity;
+
+#[async_trait]
+pub trait DownloadLogRepository: Send + Sync {
+    async fn save(&self, log: &DownloadLogEntity) -> anyhow::Result<i64>;
+    /// Newest first
+    async fn find_by_entitlement(&self, entitlement_id: i32, limit: i64) -> anyhow::Result<Vec<DownloadLogEntity>>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use crate::domain::entities::download_watermark::DownloadWatermarkEntity;
+
+#[async_trait]
+pub trait DownloadWatermarkRepository: Send + Sync {
+    async fn find_by_code(&self, code: &str) -> anyhow::Result<Option<DownloadWatermarkEntity>>;
+    /// The stamped copy of this exact source file for the entitlement
+    async fn find_for_download(
+        &self,
+        entitlement_id: i32,
+        asset_id: i32,
+        asset_sha256: &str,
+    ) -> anyhow::Result<Option<DownloadWatermarkEntity>>;
+    /// Saves a new copy; if another request stored one first, returns that
+    /// one instead (compare codes to tell)
+    async fn save(&self, watermark: &DownloadWatermarkEntity) -> anyhow::Result<DownloadWatermarkEntity>;
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::entitlement::EntitlementEntity;
+
+/// What a request on a signed link did to the download count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkUse {
+    /// One download used up
+    Counted,
+    /// Resume of a link that already used up its download
+    AlreadyCounted,
+    /// Revoked or at the limit; nothing used up
+    LimitReached,
+}
+
+#[async_trait]
+pub trait EntitlementRepository: Send + Sync {
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<EntitlementEntity>>;
+    async fn find_by_user(&self, user_id: i32) -> anyhow::Result<Vec<EntitlementEntity>>;
+    /// Active (not revoked) entitlement of the user to the book, if any
+    async fn find_active(&self, user_id: i32, book_id: i32) -> anyhow::Result<Option<EntitlementEntity>>;
+    async fn save(&self, entitlement: &EntitlementEntity) -> anyhow::Result<i32>;
+    async fn update(&self, entitlement: &EntitlementEntity) -> anyhow::Result<EntitlementEntity>;
+    /// Atomically uses up one download for the signed link to `asset_id`
+    /// expiring at `expires_at`. Only a `resume` (a range not starting at the
+    /// first byte) of a link that already used up a download is free.
+    async fn consume_download(
+        &self,
+        id: i32,
+        asset_id: i32,
+        expires_at: DateTime<Utc>,
+        resume: bool,
+    ) -> anyhow::Result<LinkUse>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use crate::domain::entities::inventory_movement::InventoryMovementEntity;
+
+/// Error of a movement that would take available stock below zero
+#[derive(Debug, thiserror::Error)]
+#[error("Insufficient stock for book {book_id}")]
+pub struct InsufficientStock {
+    pub book_id: i32,
+}
+
+#[async_trait]
+pub trait InventoryRepository: Send + Sync {
+    /// Appends a movement and applies it to the book's available stock when it
+    /// touches the available bucket. Fails if available stock would go negative.
+    async fn record(&self, movement: &InventoryMovementEntity) -> anyhow::Result<i32>;
+    async fn find_by_book(&self, book_id: i32) -> anyhow::Result<Vec<InventoryMovementEntity>>;
+}
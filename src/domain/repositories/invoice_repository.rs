@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use crate::domain::entities::invoice::{InvoiceEntity, InvoiceKind};
+
+/// Turns an invoice, once numbered, into its PDF
+pub type RenderInvoice<'a> = &'a (dyn Fn(&InvoiceEntity) -> anyhow::Result<Vec<u8>> + Send + Sync);
+
+#[async_trait]
+pub trait InvoiceRepository: Send + Sync {
+    /// Takes the next number of the invoice's kind and year, renders the
+    /// document and stores both in one transaction, so a failed render or
+    /// insert leaves no gap in the sequence. Returns the issued invoice.
+    async fn issue(&self, invoice: &InvoiceEntity, render: RenderInvoice<'_>) -> anyhow::Result<InvoiceEntity>;
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<InvoiceEntity>>;
+    async fn find_by_order(&self, order_id: i32, kind: InvoiceKind) -> anyhow::Result<Vec<InvoiceEntity>>;
+    /// Credit notes issued against a tax invoice, oldest first
+    async fn find_credit_notes(&self, invoice_id: i32) -> anyhow::Result<Vec<InvoiceEntity>>;
+    async fn find_by_user(&self, user_id: i32) -> anyhow::Result<Vec<InvoiceEntity>>;
+    /// The stored PDF
+    async fn find_document(&self, id: i32) -> anyhow::Result<Option<Vec<u8>>>;
+}
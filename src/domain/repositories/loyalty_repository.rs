@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    entities::{
+        loyalty_campaign::{LoyaltyCampaignEntity, LoyaltyCategoryMultiplier},
+        loyalty_entry::LoyaltyEntryEntity,
+    },
+    value_objects::loyalty_tier::LoyaltyTier,
+};
+
+/// A member's spend over the tier window and the tier roles they hold now
+#[derive(Debug, Clone)]
+pub struct MemberSpend {
+    pub user_id: i32,
+    pub spend: i64,
+    pub current_tiers: Vec<LoyaltyTier>,
+}
+
+#[async_trait]
+pub trait LoyaltyRepository: Send + Sync {
+    async fn find_balance(&self, user_id: i32) -> anyhow::Result<i64>;
+    /// Balance from entries created before `before`
+    async fn balance_before(&self, user_id: i32, before: DateTime<Utc>) -> anyhow::Result<i64>;
+    /// Appends an entry and applies it to the balance. Returns the entry id.
+    /// Fails without side effects if the balance would go negative.
+    async fn record(&self, entry: &LoyaltyEntryEntity) -> anyhow::Result<i32>;
+    /// Gives back points spent on a cancelled order. Returns how many
+    /// payments were reversed; safe to repeat.
+    async fn reverse_redemptions_for_order(&self, order_id: i32, changed_by: Option<i32>) -> anyhow::Result<u64>;
+    async fn find_entry(&self, id: i32) -> anyhow::Result<Option<LoyaltyEntryEntity>>;
+    /// Entries created in `[from, to)`, oldest first
+    async fn find_entries(&self, user_id: i32, from: DateTime<Utc>, to: DateTime<Utc>) -> anyhow::Result<Vec<LoyaltyEntryEntity>>;
+    /// Points earned on an order, and what is left of them after clawbacks
+    async fn earned_for_order(&self, order_id: i32) -> anyhow::Result<(i64, i64)>;
+    /// Points of the member that will have lapsed by `at`
+    async fn points_due_to_expire(&self, user_id: i32, at: DateTime<Utc>) -> anyhow::Result<i64>;
+    /// Members with lapsed points at `at` and how many
+    async fn find_expiring(&self, at: DateTime<Utc>, limit: i64) -> anyhow::Result<Vec<(i32, i64)>>;
+
+    /// Spend of one member on orders placed since `since`, net of refunds
+    async fn spend_since(&self, user_id: i32, since: DateTime<Utc>) -> anyhow::Result<i64>;
+    /// Every member who spent since `since` or holds a tier role
+    async fn find_member_spend(&self, since: DateTime<Utc>) -> anyhow::Result<Vec<MemberSpend>>;
+
+    async fn find_category_multipliers(&self) -> anyhow::Result<Vec<LoyaltyCategoryMultiplier>>;
+    async fn save_category_multiplier(&self, multiplier: &LoyaltyCategoryMultiplier) -> anyhow::Result<()>;
+    async fn delete_category_multiplier(&self, category: &str) -> anyhow::Result<()>;
+
+    async fn find_campaigns(&self) -> anyhow::Result<Vec<LoyaltyCampaignEntity>>;
+    async fn find_campaign(&self, id: i32) -> anyhow::Result<Option<LoyaltyCampaignEntity>>;
+    async fn save_campaign(&self, campaign: &LoyaltyCampaignEntity) -> anyhow::Result<i32>;
+    async fn update_campaign(&self, campaign: &LoyaltyCampaignEntity) -> anyhow::Result<LoyaltyCampaignEntity>;
+}
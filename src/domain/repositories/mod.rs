@@ -1,2 +1,33 @@
+pub mod address_repository;
+pub mod book_metadata_repository;
+pub mod book_price_repository;
+pub mod book_repository;
+pub mod book_search_repository;
+pub mod bulk_import_repository;
+pub mod cart_repository;
+pub mod catalog_import_repository;
+pub mod catalog_repository;
+pub mod coupon_repository;
+pub mod digital_asset_repository;
+pub mod download_log_repository;
+pub mod download_watermark_repository;
+pub mod entitlement_repository;
+pub mod inventory_repository;
+pub mod invoice_repository;
+pub mod loyalty_repository;
+pub mod notification_job_repository;
+pub mod order_repository;
+pub mod payment_repository;
+pub mod promotion_repository;
+pub mod report_repository;
+pub mod return_repository;
+pub mod review_repository;
 pub mod role_repository;
+pub mod search_feed_repository;
+pub mod shipping_repository;
+pub mod stock_subscription_repository;
+pub mod store_repository;
+pub mod stored_value_repository;
+pub mod tax_rate_repository;
 pub mod user_repository;
+pub mod wishlist_repository;
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::entities::notification_job::NotificationJobEntity;
+
+#[async_trait]
+pub trait NotificationJobRepository: Send + Sync {
+    /// Claims up to `limit` due pending jobs for this worker: bumps `attempts`
+    /// and pushes `run_after` out so other workers skip them meanwhile.
+    async fn claim_due(&self, limit: i64) -> anyhow::Result<Vec<NotificationJobEntity>>;
+    async fn update(&self, job: &NotificationJobEntity) -> anyhow::Result<()>;
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use crate::domain::entities::order::OrderEntity;
+
+#[async_trait]
+pub trait OrderRepository: Send + Sync {
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<OrderEntity>>;
+    async fn find_by_user(&self, user_id: i32) -> anyhow::Result<Vec<OrderEntity>>;
+    /// Books appearing in the store's orders that wait for their release date
+    async fn find_preordered_book_ids(&self, store_id: i32) -> anyhow::Result<Vec<i32>>;
+    /// Lowers the protected price of the store's waiting pre-order lines of a
+    /// book. Returns the number of lines that got cheaper.
+    async fn record_preorder_price(&self, store_id: i32, book_id: i32, unit_price: i64) -> anyhow::Result<u64>;
+    /// Moves waiting pre-orders containing the book to the (changed) release
+    /// date of their latest title. Returns the number of orders moved.
+    async fn reschedule_preorders(&self, book_id: i32) -> anyhow::Result<u64>;
+    /// Pre-orders whose release date is `on` or earlier, oldest first
+    async fn find_preorders_due(&self, on: NaiveDate, limit: i64) -> anyhow::Result<Vec<OrderEntity>>;
+    /// Inserts the order, its lines and history, and books a sale in the
+    /// inventory ledger for every line.
+    /// Fails without side effects if any book no longer has enough stock.
+    async fn save(&self, order: &OrderEntity) -> anyhow::Result<i32>;
+    /// Persists status, tracking number, amounts and line prices and appends
+    /// unsaved history entries. Fails if another request changed the status
+    /// since the order was loaded.
+    async fn update(&self, order: &OrderEntity) -> anyhow::Result<OrderEntity>;
+    /// `update` for an order that has just been paid. In the same transaction
+    /// grants the digital books in it and credits the points earned on it.
+    async fn update_paid(&self, order: &OrderEntity) -> anyhow::Result<OrderEntity>;
+    /// `update` for an order that has just been cancelled or refunded. In the
+    /// same transaction revokes what `update_paid` granted and, on cancel,
+    /// puts the stock the order still held back on the shelf.
+    async fn update_closed(&self, order: &OrderEntity) -> anyhow::Result<OrderEntity>;
+}
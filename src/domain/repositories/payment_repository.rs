@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use crate::domain::entities::payment::PaymentEntity;
+
+#[async_trait]
+pub trait PaymentRepository: Send + Sync {
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<PaymentEntity>>;
+    async fn find_by_order(&self, order_id: i32) -> anyhow::Result<Vec<PaymentEntity>>;
+    async fn find_by_intent(&self, provider: &str, intent_id: &str) -> anyhow::Result<Option<PaymentEntity>>;
+    async fn save(&self, payment: &PaymentEntity) -> anyhow::Result<i32>;
+    async fn update(&self, payment: &PaymentEntity) -> anyhow::Result<PaymentEntity>;
+    /// Records a webhook event id. Returns false if it was already processed.
+    async fn record_webhook_event(&self, provider: &str, event_id: &str, kind: &str) -> anyhow::Result<bool>;
+    /// Drops a recorded event whose processing failed, so a redelivery is handled
+    async fn forget_webhook_event(&self, provider: &str, event_id: &str) -> anyhow::Result<()>;
+}
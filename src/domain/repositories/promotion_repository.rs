@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use crate::domain::entities::promotion::PromotionEntity;
+
+#[async_trait]
+pub trait PromotionRepository: Send + Sync {
+    async fn find_all(&self) -> anyhow::Result<Vec<PromotionEntity>>;
+    async fn find_active(&self) -> anyhow::Result<Vec<PromotionEntity>>;
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<PromotionEntity>>;
+    async fn save(&self, promotion: &PromotionEntity) -> anyhow::Result<i32>;
+    async fn update(&self, promotion: &PromotionEntity) -> anyhow::Result<PromotionEntity>;
+}
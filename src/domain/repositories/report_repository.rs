@@ -0,0 +1,154 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::str::FromStr;
+
+use crate::domain::value_objects::money::Currency;
+
+/// Bucket size of the revenue series. Weeks start on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportGranularity {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl ReportGranularity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+}
+
+impl FromStr for ReportGranularity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            "month" => Ok(Self::Month),
+            _ => Err(anyhow!("Invalid granularity: {}", s)),
+        }
+    }
+}
+
+/// What units sold are broken down by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SalesDimension {
+    Title,
+    Author,
+    Category,
+    Store,
+}
+
+impl SalesDimension {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Author => "author",
+            Self::Category => "category",
+            Self::Store => "store",
+        }
+    }
+}
+
+impl FromStr for SalesDimension {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "title" => Ok(Self::Title),
+            "author" => Ok(Self::Author),
+            "category" => Ok(Self::Category),
+            "store" => Ok(Self::Store),
+            _ => Err(anyhow!("Invalid report dimension: {}", s)),
+        }
+    }
+}
+
+/// Sales days (Thai time) from `from` to `to`, both included
+#[derive(Debug, Clone)]
+pub struct SalesFilter {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// `None` for every store
+    pub store_id: Option<i32>,
+}
+
+/// Order totals in one currency. Amounts are minor units.
+#[derive(Debug, Clone)]
+pub struct SalesTotals {
+    pub currency: Currency,
+    pub orders: i64,
+    pub units: i64,
+    /// Order totals, after discounts and including shipping and tax
+    pub revenue: i64,
+    pub discount_total: i64,
+    pub shipping_total: i64,
+    pub tax_total: i64,
+    /// Orders with any money given back
+    pub refunded_orders: i64,
+    pub refunded_amount: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RevenuePeriod {
+    /// First day of the bucket
+    pub period_start: NaiveDate,
+    pub totals: SalesTotals,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnitsSold {
+    /// Title, author, category or store code; `None` for books without a category
+    pub key: Option<String>,
+    /// Set when broken down by title
+    pub book_id: Option<i32>,
+    pub currency: Currency,
+    pub units: i64,
+    /// Line totals before order-level discounts
+    pub revenue: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomerSales {
+    pub user_id: i32,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub currency: Currency,
+    pub orders: i64,
+    pub revenue: i64,
+}
+
+/// Read side of the sales reports. Everything is read from materialized
+/// views, so figures are as of the last `refresh`.
+#[async_trait]
+pub trait ReportRepository: Send + Sync {
+    /// One row per currency
+    async fn sales_totals(&self, filter: &SalesFilter) -> Result<Vec<SalesTotals>>;
+    /// Oldest period first; periods without sales are left out
+    async fn revenue_series(
+        &self,
+        filter: &SalesFilter,
+        granularity: ReportGranularity,
+    ) -> Result<Vec<RevenuePeriod>>;
+    /// Most units first; `limit: None` returns every row
+    async fn units_sold(
+        &self,
+        filter: &SalesFilter,
+        dimension: SalesDimension,
+        limit: Option<i64>,
+    ) -> Result<Vec<UnitsSold>>;
+    /// Highest spend first
+    async fn top_customers(&self, filter: &SalesFilter, limit: Option<i64>) -> Result<Vec<CustomerSales>>;
+    /// When the least recently refreshed view was refreshed
+    async fn refreshed_at(&self) -> Result<Option<DateTime<Utc>>>;
+    /// Rebuilds every view without blocking readers
+    async fn refresh(&self) -> Result<DateTime<Utc>>;
+}
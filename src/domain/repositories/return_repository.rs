@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use crate::domain::entities::{
+    inventory_movement::InventoryMovementEntity,
+    loyalty_entry::LoyaltyEntryEntity,
+    return_request::{ReturnRefundEntity, ReturnRequestEntity},
+    stored_value_entry::StoredValueEntryEntity,
+};
+
+/// How a refund part gives the money back
+#[derive(Debug, Clone)]
+pub enum RefundCredit {
+    /// Refunded at the payment provider before the part is completed
+    Provider,
+    /// Credited to a gift card or store credit account
+    Balance(StoredValueEntryEntity),
+    /// Points given back
+    Points(LoyaltyEntryEntity),
+}
+
+#[async_trait]
+pub trait ReturnRepository: Send + Sync {
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<ReturnRequestEntity>>;
+    async fn find_by_order(&self, order_id: i32) -> anyhow::Result<Vec<ReturnRequestEntity>>;
+    async fn find_by_user(&self, user_id: i32) -> anyhow::Result<Vec<ReturnRequestEntity>>;
+    async fn save(&self, request: &ReturnRequestEntity) -> anyhow::Result<i32>;
+    async fn update(&self, request: &ReturnRequestEntity) -> anyhow::Result<ReturnRequestEntity>;
+    /// Persists the request in its received state together with the
+    /// `movement` of the returned copies, in one transaction. Fails without
+    /// changes if the parcel was already received.
+    async fn receive(&self, request: &ReturnRequestEntity, movement: &InventoryMovementEntity) -> anyhow::Result<ReturnRequestEntity>;
+    /// Persists the request in its refunding state together with the parts
+    /// the refund is split into, in one transaction
+    async fn start_refund(&self, request: &ReturnRequestEntity, refunds: &[ReturnRefundEntity]) -> anyhow::Result<ReturnRequestEntity>;
+    /// Parts of the return's refund, in the order they are paid out
+    async fn find_refunds(&self, return_id: i32) -> anyhow::Result<Vec<ReturnRefundEntity>>;
+    /// Marks the part done, adds it to its payment's refunded amount and
+    /// writes the `credit` entry, in one transaction. Returns false without
+    /// changes if the part was already done.
+    async fn complete_refund(&self, refund: &ReturnRefundEntity, credit: RefundCredit) -> anyhow::Result<bool>;
+    /// Persists the request in its refunded state together with the
+    /// `clawback` of points earned on the returned copies, in one
+    /// transaction. Fails without changes if it is no longer refunding.
+    async fn finish_refund(
+        &self,
+        request: &ReturnRequestEntity,
+        clawback: Option<&LoyaltyEntryEntity>,
+    ) -> anyhow::Result<ReturnRequestEntity>;
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use crate::domain::{
+    entities::review::ReviewEntity,
+    value_objects::review_status::ReviewStatus,
+};
+
+/// Writes keep `books.rating_count` / `books.rating_total` in sync with the
+/// book's approved reviews.
+#[async_trait]
+pub trait ReviewRepository: Send + Sync {
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<ReviewEntity>>;
+    /// Most helpful first, then newest
+    async fn find_by_book(&self, book_id: i32, status: ReviewStatus) -> anyhow::Result<Vec<ReviewEntity>>;
+    /// Oldest first (moderation queue order)
+    async fn find_by_status(&self, status: ReviewStatus) -> anyhow::Result<Vec<ReviewEntity>>;
+    async fn find_by_user_and_book(&self, user_id: i32, book_id: i32) -> anyhow::Result<Option<ReviewEntity>>;
+    async fn save(&self, review: &ReviewEntity) -> anyhow::Result<i32>;
+    async fn update(&self, review: &ReviewEntity) -> anyhow::Result<ReviewEntity>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+    /// Records (or changes) the user's vote and returns the recounted review
+    async fn vote(&self, review_id: i32, user_id: i32, helpful: bool) -> anyhow::Result<ReviewEntity>;
+}
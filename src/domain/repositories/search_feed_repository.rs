@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+
+/// Denormalized book data a search index is built from.
+#[derive(Debug, Clone)]
+pub struct SearchDocument {
+    pub book_id: i32,
+    pub isbn: String,
+    pub title: String,
+    pub author: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub publisher: Option<String>,
+    pub in_stock: bool,
+    /// Copies sold recently (paid and later orders), used to boost relevance
+    pub sales_count: i64,
+}
+
+/// Position in the book change feed. Events are ordered by the id of the
+/// transaction that wrote them, so a slow transaction cannot be skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct ChangeCursor {
+    pub txid: i64,
+    pub id: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BookChange {
+    pub cursor: ChangeCursor,
+    pub book_id: i32,
+}
+
+#[async_trait]
+pub trait SearchFeedRepository: Send + Sync {
+    /// Position after every finished transaction. Changes still in flight
+    /// lie beyond it, so reading from here may replay a few, never miss one.
+    async fn latest_cursor(&self) -> anyhow::Result<ChangeCursor>;
+    /// Committed changes after `cursor`, oldest first
+    async fn find_changes_after(&self, cursor: ChangeCursor, limit: i64) -> anyhow::Result<Vec<BookChange>>;
+    /// Documents of active books; inactive or deleted ids are left out
+    async fn find_documents(&self, book_ids: &[i32]) -> anyhow::Result<Vec<SearchDocument>>;
+    /// Active books with id above `after_book_id`, by id (for rebuilds)
+    async fn find_documents_after(&self, after_book_id: i32, limit: i64) -> anyhow::Result<Vec<SearchDocument>>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use crate::domain::entities::shipping_zone::{ShippingMethodEntity, ShippingZoneEntity};
+
+/// Zones are loaded together with their methods and rate tables.
+#[async_trait]
+pub trait ShippingRepository: Send + Sync {
+    async fn find_all_zones(&self) -> anyhow::Result<Vec<ShippingZoneEntity>>;
+    async fn find_zones_by_country(&self, country: &str) -> anyhow::Result<Vec<ShippingZoneEntity>>;
+    async fn find_zone_by_id(&self, id: i32) -> anyhow::Result<Option<ShippingZoneEntity>>;
+    async fn save_zone(&self, zone: &ShippingZoneEntity) -> anyhow::Result<i32>;
+    /// Inserts the method and its rate table
+    async fn save_method(&self, method: &ShippingMethodEntity) -> anyhow::Result<i32>;
+    async fn update_method(&self, method: &ShippingMethodEntity) -> anyhow::Result<()>;
+}
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use crate::domain::entities::stock_subscription::StockSubscriptionEntity;
+
+#[async_trait]
+pub trait StockSubscriptionRepository: Send + Sync {
+    async fn find_by_user(&self, user_id: i32) -> anyhow::Result<Vec<StockSubscriptionEntity>>;
+    async fn find_by_user_and_book(
+        &self,
+        user_id: i32,
+        book_id: i32,
+    ) -> anyhow::Result<Option<StockSubscriptionEntity>>;
+    /// Subscribing again after a notification re-arms the existing subscription
+    async fn save(&self, subscription: &StockSubscriptionEntity) -> anyhow::Result<i32>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+    /// Queues one back-in-stock job per waiting subscriber of the book and
+    /// marks them notified, if the book has stock now. Returns how many were
+    /// queued; safe to repeat.
+    async fn notify_back_in_stock(&self, book_id: i32) -> anyhow::Result<u64>;
+}
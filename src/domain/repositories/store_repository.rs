@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use crate::domain::entities::store::StoreEntity;
+
+#[async_trait]
+pub trait StoreRepository: Send + Sync {
+    async fn find_all(&self) -> anyhow::Result<Vec<StoreEntity>>;
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<StoreEntity>>;
+    async fn find_by_code(&self, code: &str) -> anyhow::Result<Option<StoreEntity>>;
+    /// The store used when the client does not pick one
+    async fn find_default(&self) -> anyhow::Result<Option<StoreEntity>>;
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::{
+    stored_value_account::StoredValueAccountEntity,
+    stored_value_entry::StoredValueEntryEntity,
+};
+
+#[async_trait]
+pub trait StoredValueRepository: Send + Sync {
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<StoredValueAccountEntity>>;
+    async fn find_by_code_hash(&self, code_hash: &str) -> anyhow::Result<Option<StoredValueAccountEntity>>;
+    async fn find_store_credit(&self, user_id: i32, currency: &str) -> anyhow::Result<Option<StoredValueAccountEntity>>;
+    /// Inserts the account and loads `opening_amount` onto it with an
+    /// `issue` entry, in one transaction.
+    async fn create(&self, account: &StoredValueAccountEntity, opening_amount: i64) -> anyhow::Result<StoredValueAccountEntity>;
+    /// The customer's store credit account, created empty on first use
+    async fn find_or_create_store_credit(&self, user_id: i32, currency: &str) -> anyhow::Result<StoredValueAccountEntity>;
+    /// Persists the expiry date
+    async fn update(&self, account: &StoredValueAccountEntity) -> anyhow::Result<StoredValueAccountEntity>;
+    /// Appends an entry and applies it to the balance. Returns the entry id.
+    /// Fails without side effects if the balance would go negative.
+    async fn record(&self, entry: &StoredValueEntryEntity) -> anyhow::Result<i32>;
+    /// Puts gift card and store credit money spent on a cancelled order back
+    /// on the accounts it came from. Returns how many payments were refunded;
+    /// safe to repeat.
+    async fn refund_redemptions_for_order(&self, order_id: i32, changed_by: Option<i32>) -> anyhow::Result<u64>;
+    async fn find_entry(&self, id: i32) -> anyhow::Result<Option<StoredValueEntryEntity>>;
+    async fn find_entries(&self, account_id: i32) -> anyhow::Result<Vec<StoredValueEntryEntity>>;
+    /// Store credit issued instead of refunds for an order
+    async fn refund_credit_total(&self, order_id: i32) -> anyhow::Result<i64>;
+    /// Accounts past their expiry that still hold a balance
+    async fn find_expired(&self, now: DateTime<Utc>, limit: i64) -> anyhow::Result<Vec<StoredValueAccountEntity>>;
+}
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use crate::domain::entities::tax_rate::TaxRateEntity;
+
+#[async_trait]
+pub trait TaxRateRepository: Send + Sync {
+    /// Rates of a jurisdiction and of its country (`US-CA` also returns `US`)
+    async fn find_by_jurisdiction(&self, jurisdiction: &str) -> anyhow::Result<Vec<TaxRateEntity>>;
+    async fn save(&self, rate: &TaxRateEntity) -> anyhow::Result<i32>;
+}
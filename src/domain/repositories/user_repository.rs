@@ -1,14 +1,21 @@
 use async_trait::async_trait;
-use crate::domain::entities::{user::UserEntity, role::RoleEntity};
+use crate::domain::{
+    entities::{user::UserEntity, role::RoleEntity},
+    value_objects::user_avatar::UserAvatar,
+};
 
 #[async_trait]
 pub trait UserRepository: Send + Sync {
     async fn find_all(&self) -> anyhow::Result<Vec<UserEntity>>;
+    /// Up to `limit` users with an id above `after_id`, by id
+    async fn find_after(&self, after_id: i32, limit: i64) -> anyhow::Result<Vec<UserEntity>>;
     async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<UserEntity>>;
     async fn find_by_email(&self, email: &str) -> anyhow::Result<Option<UserEntity>>;
     async fn save(&self, user: &UserEntity) -> anyhow::Result<i32>;
     async fn update(&self, user: &UserEntity) -> anyhow::Result<UserEntity>;
     async fn update_password(&self, id: i32, new_password_hash: &str) -> anyhow::Result<()>;
+    /// Sets or clears the avatar; returns the one it replaced
+    async fn update_avatar(&self, user_id: i32, avatar: Option<&UserAvatar>) -> anyhow::Result<Option<UserAvatar>>;
     async fn delete(&self, id: i32) -> anyhow::Result<()>;
     
     // RBAC
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::domain::entities::wishlist::WishlistEntity;
+
+#[async_trait]
+pub trait WishlistRepository: Send + Sync {
+    async fn find_by_user(&self, user_id: i32) -> anyhow::Result<Vec<WishlistEntity>>;
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<WishlistEntity>>;
+    async fn find_by_share_token(&self, token: &str) -> anyhow::Result<Option<WishlistEntity>>;
+    async fn save(&self, wishlist: &WishlistEntity) -> anyhow::Result<i32>;
+    async fn update(&self, wishlist: &WishlistEntity) -> anyhow::Result<WishlistEntity>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+}
@@ -0,0 +1,172 @@
+use anyhow::{anyhow, Result};
+
+/// Thai provinces: (Thai name, English name, first two digits of their postal codes).
+/// Samut Prakan shares the "10" block with Bangkok.
+pub const THAI_PROVINCES: &[(&str, &str, &str)] = &[
+    ("กรุงเทพมหานคร", "Bangkok", "10"),
+    ("สมุทรปราการ", "Samut Prakan", "10"),
+    ("นนทบุรี", "Nonthaburi", "11"),
+    ("ปทุมธานี", "Pathum Thani", "12"),
+    ("พระนครศรีอยุธยา", "Phra Nakhon Si Ayutthaya", "13"),
+    ("อ่างทอง", "Ang Thong", "14"),
+    ("ลพบุรี", "Lopburi", "15"),
+    ("สิงห์บุรี", "Sing Buri", "16"),
+    ("ชัยนาท", "Chai Nat", "17"),
+    ("สระบุรี", "Saraburi", "18"),
+    ("ชลบุรี", "Chonburi", "20"),
+    ("ระยอง", "Rayong", "21"),
+    ("จันทบุรี", "Chanthaburi", "22"),
+    ("ตราด", "Trat", "23"),
+    ("ฉะเชิงเทรา", "Chachoengsao", "24"),
+    ("ปราจีนบุรี", "Prachinburi", "25"),
+    ("นครนายก", "Nakhon Nayok", "26"),
+    ("สระแก้ว", "Sa Kaeo", "27"),
+    ("นครราชสีมา", "Nakhon Ratchasima", "30"),
+    ("บุรีรัมย์", "Buriram", "31"),
+    ("สุรินทร์", "Surin", "32"),
+    ("ศรีสะเกษ", "Sisaket", "33"),
+    ("อุบลราชธานี", "Ubon Ratchathani", "34"),
+    ("ยโสธร", "Yasothon", "35"),
+    ("ชัยภูมิ", "Chaiyaphum", "36"),
+    ("อำนาจเจริญ", "Amnat Charoen", "37"),
+    ("บึงกาฬ", "Bueng Kan", "38"),
+    ("หนองบัวลำภู", "Nong Bua Lamphu", "39"),
+    ("ขอนแก่น", "Khon Kaen", "40"),
+    ("อุดรธานี", "Udon Thani", "41"),
+    ("เลย", "Loei", "42"),
+    ("หนองคาย", "Nong Khai", "43"),
+    ("มหาสารคาม", "Maha Sarakham", "44"),
+    ("ร้อยเอ็ด", "Roi Et", "45"),
+    ("กาฬสินธุ์", "Kalasin", "46"),
+    ("สกลนคร", "Sakon Nakhon", "47"),
+    ("นครพนม", "Nakhon Phanom", "48"),
+    ("มุกดาหาร", "Mukdahan", "49"),
+    ("เชียงใหม่", "Chiang Mai", "50"),
+    ("ลำพูน", "Lamphun", "51"),
+    ("ลำปาง", "Lampang", "52"),
+    ("อุตรดิตถ์", "Uttaradit", "53"),
+    ("แพร่", "Phrae", "54"),
+    ("น่าน", "Nan", "55"),
+    ("พะเยา", "Phayao", "56"),
+    ("เชียงราย", "Chiang Rai", "57"),
+    ("แม่ฮ่องสอน", "Mae Hong Son", "58"),
+    ("นครสวรรค์", "Nakhon Sawan", "60"),
+    ("อุทัยธานี", "Uthai Thani", "61"),
+    ("กำแพงเพชร", "Kamphaeng Phet", "62"),
+    ("ตาก", "Tak", "63"),
+    ("สุโขทัย", "Sukhothai", "64"),
+    ("พิษณุโลก", "Phitsanulok", "65"),
+    ("พิจิตร", "Phichit", "66"),
+    ("เพชรบูรณ์", "Phetchabun", "67"),
+    ("ราชบุรี", "Ratchaburi", "70"),
+    ("กาญจนบุรี", "Kanchanaburi", "71"),
+    ("สุพรรณบุรี", "Suphan Buri", "72"),
+    ("นครปฐม", "Nakhon Pathom", "73"),
+    ("สมุทรสาคร", "Samut Sakhon", "74"),
+    ("สมุทรสงคราม", "Samut Songkhram", "75"),
+    ("เพชรบุรี", "Phetchaburi", "76"),
+    ("ประจวบคีรีขันธ์", "Prachuap Khiri Khan", "77"),
+    ("นครศรีธรรมราช", "Nakhon Si Thammarat", "80"),
+    ("กระบี่", "Krabi", "81"),
+    ("พังงา", "Phang Nga", "82"),
+    ("ภูเก็ต", "Phuket", "83"),
+    ("สุราษฎร์ธานี", "Surat Thani", "84"),
+    ("ระนอง", "Ranong", "85"),
+    ("ชุมพร", "Chumphon", "86"),
+    ("สงขลา", "Songkhla", "90"),
+    ("สตูล", "Satun", "91"),
+    ("ตรัง", "Trang", "92"),
+    ("พัทลุง", "Phatthalung", "93"),
+    ("ปัตตานี", "Pattani", "94"),
+    ("ยะลา", "Yala", "95"),
+    ("นราธิวาส", "Narathiwat", "96"),
+];
+
+/// Province and postal code after the country's rules have been applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub province: Option<String>,
+    pub postal_code: Option<String>,
+}
+
+/// Validates and normalizes the region part of an address for `country`
+/// (ISO 3166-1 alpha-2, already upper-cased).
+pub fn normalize_region(
+    country: &str,
+    province: Option<&str>,
+    postal_code: Option<&str>,
+    district: Option<&str>,
+    subdistrict: Option<&str>,
+) -> Result<Region> {
+    let province = province.map(str::trim).filter(|p| !p.is_empty());
+    let postal_code = postal_code.map(str::trim).filter(|p| !p.is_empty());
+
+    match country {
+        "TH" => {
+            // ที่อยู่ไทยต้องมี ตำบล/แขวง และ อำเภอ/เขต ครบ
+            if district.is_none_or(|d| d.trim().is_empty())
+                || subdistrict.is_none_or(|s| s.trim().is_empty())
+            {
+                return Err(anyhow!("Thai addresses need a subdistrict and a district"));
+            }
+
+            let (thai_name, _, prefix) = province
+                .and_then(find_thai_province)
+                .ok_or_else(|| anyhow!("Unknown Thai province: {}", province.unwrap_or("")))?;
+
+            let postal_code = postal_code.ok_or_else(|| anyhow!("Postal code is required"))?;
+            if postal_code.len() != 5 || !postal_code.chars().all(|c| c.is_ascii_digit()) {
+                return Err(anyhow!("Thai postal codes have 5 digits"));
+            }
+            if !postal_code.starts_with(prefix) {
+                return Err(anyhow!(
+                    "Postal code {} is not in {}",
+                    postal_code,
+                    thai_name
+                ));
+            }
+
+            Ok(Region {
+                province: Some(thai_name.to_string()),
+                postal_code: Some(postal_code.to_string()),
+            })
+        }
+        _ => {
+            if let Some(code) = postal_code
+                && (code.len() > 10
+                    || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-'))
+            {
+                return Err(anyhow!("Invalid postal code: {}", code));
+            }
+            if province.is_some_and(|p| p.chars().count() > 100) {
+                return Err(anyhow!("Province too long (max 100 chars)"));
+            }
+
+            Ok(Region {
+                province: province.map(str::to_string),
+                postal_code: postal_code.map(|c| c.to_uppercase()),
+            })
+        }
+    }
+}
+
+/// Looks a province up by its Thai or English name (case and spacing insensitive).
+pub fn find_thai_province(name: &str) -> Option<(&'static str, &'static str, &'static str)> {
+    let key = normalize_name(name.trim_start_matches("จังหวัด"));
+    let key = match key.as_str() {
+        "กรุงเทพฯ" | "กรุงเทพ" | "bangkokmetropolis" | "krungthep" => normalize_name("Bangkok"),
+        _ => key,
+    };
+
+    THAI_PROVINCES
+        .iter()
+        .find(|(th, en, _)| normalize_name(th) == key || normalize_name(en) == key)
+        .copied()
+}
+
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
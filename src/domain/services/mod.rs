@@ -0,0 +1,6 @@
+pub mod promotion_evaluator;
+pub mod tax_engine;
+pub mod shipping_calculator;
+pub mod address_rules;
+pub mod search_text;
+pub mod points_calculator;
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::{
+    entities::{
+        loyalty_campaign::{LoyaltyCampaignEntity, LoyaltyCategoryMultiplier},
+        loyalty_entry::{POINTS_CURRENCY, POINTS_PER_MAJOR_UNIT},
+    },
+    value_objects::money::Currency,
+};
+
+/// What a paid order line contributes to earning.
+#[derive(Debug, Clone)]
+pub struct EarnLine {
+    pub category: Option<String>,
+    /// Paid for the line after discounts, in minor units
+    pub amount: i64,
+}
+
+/// Points earned on `lines` of an order in `currency`. Orders outside
+/// `POINTS_CURRENCY` earn nothing.
+///
+/// Each line earns the base rate times its category multiplier (100% when
+/// the category has none) times the best campaign running at `now` for it.
+/// Fractions are summed over the order and rounded down once.
+pub fn points_for(
+    lines: &[EarnLine],
+    categories: &[LoyaltyCategoryMultiplier],
+    campaigns: &[LoyaltyCampaignEntity],
+    currency: &Currency,
+    now: DateTime<Utc>,
+) -> i64 {
+    if currency.as_str() != POINTS_CURRENCY {
+        return 0;
+    }
+
+    let numerator: i128 = lines
+        .iter()
+        .filter(|l| l.amount > 0)
+        .map(|line| {
+            let category = line.category.as_deref();
+            let category_pct = category
+                .and_then(|c| {
+                    categories
+                        .iter()
+                        .find(|m| m.category.eq_ignore_ascii_case(c))
+                })
+                .map_or(100, |m| m.multiplier_pct);
+            let campaign_pct = campaigns
+                .iter()
+                .filter(|c| c.is_running(now) && c.applies_to(category))
+                .map(|c| c.multiplier_pct)
+                .max()
+                .unwrap_or(100);
+
+            line.amount as i128 * category_pct as i128 * campaign_pct as i128
+        })
+        .sum();
+
+    let denominator = 10_i128.pow(currency.minor_units()) * 100 * 100;
+    (numerator * POINTS_PER_MAJOR_UNIT as i128 / denominator) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn line(category: Option<&str>, amount: i64) -> EarnLine {
+        EarnLine { category: category.map(str::to_string), amount }
+    }
+
+    fn campaign(category: Option<&str>, multiplier_pct: i32, now: DateTime<Utc>) -> LoyaltyCampaignEntity {
+        LoyaltyCampaignEntity::new(
+            "Campaign".to_string(),
+            category.map(str::to_string),
+            multiplier_pct,
+            now - Duration::days(1),
+            now + Duration::days(1),
+            1,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn earns_per_baht_rounding_the_order_down_once() {
+        let lines = [line(None, 15_050), line(None, 4_950), line(None, -1_000)];
+        assert_eq!(points_for(&lines, &[], &[], &Currency::thb(), Utc::now()), 200);
+    }
+
+    #[test]
+    fn applies_category_and_best_campaign_multipliers() {
+        let now = Utc::now();
+        let categories = [LoyaltyCategoryMultiplier::new("Manga".to_string(), 200).unwrap()];
+        let campaigns = [campaign(None, 150, now), campaign(Some("manga"), 300, now)];
+        let lines = [line(Some("manga"), 10_000), line(Some("novel"), 10_000)];
+
+        // manga: 100 x 200% x 300%, novel: 100 x 100% x 150%
+        assert_eq!(points_for(&lines, &categories, &campaigns, &Currency::thb(), now), 750);
+    }
+
+    #[test]
+    fn orders_in_other_currencies_earn_nothing() {
+        let lines = [line(None, 10_000)];
+        for code in ["USD", "JPY"] {
+            let currency = Currency::new(code).unwrap();
+            assert_eq!(points_for(&lines, &[], &[], &currency, Utc::now()), 0, "{code}");
+        }
+    }
+}
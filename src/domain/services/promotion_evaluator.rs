@@ -0,0 +1,341 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::{
+    coupon::CouponEntity,
+    promotion::{PromotionAction, PromotionCondition, PromotionEntity},
+};
+
+/// A cart line as seen by the evaluator.
+#[derive(Debug, Clone)]
+pub struct PricingLine {
+    pub book_id: i32,
+    pub category: Option<String>,
+    pub author: String,
+    pub quantity: i32,
+    pub unit_price: i64,
+}
+
+impl PricingLine {
+    pub fn line_total(&self) -> i64 {
+        self.unit_price * self.quantity as i64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PricingContext {
+    pub lines: Vec<PricingLine>,
+    /// Role names of the customer; empty for guests
+    pub customer_roles: Vec<String>,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedPromotion {
+    pub promotion_id: i32,
+    pub name: String,
+    pub coupon_id: Option<i32>,
+    pub coupon_code: Option<String>,
+    pub discount: i64,
+    pub free_shipping: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PricingResult {
+    pub subtotal: i64,
+    pub discount_total: i64,
+    pub total: i64,
+    pub free_shipping: bool,
+    /// In the order they were applied
+    pub applied: Vec<AppliedPromotion>,
+}
+
+/// Applies promotions to a cart.
+///
+/// Deterministic: promotions are tried by priority (highest first), then by
+/// id. Each promotion discounts what is left of its matching lines, so the
+/// total never goes below zero. A non-stackable promotion is skipped once
+/// anything was applied, and stops evaluation when it applies itself.
+/// Coupon-only promotions are considered only for the given `coupon`.
+pub fn evaluate(
+    ctx: &PricingContext,
+    promotions: &[PromotionEntity],
+    coupon: Option<&CouponEntity>,
+) -> PricingResult {
+    let subtotal: i64 = ctx.lines.iter().map(|l| l.line_total()).sum();
+    let mut remaining: Vec<i64> = ctx.lines.iter().map(|l| l.line_total()).collect();
+
+    let mut candidates: Vec<&PromotionEntity> = promotions
+        .iter()
+        .filter(|p| p.is_active)
+        .filter(|p| !p.requires_coupon || coupon.is_some_and(|c| c.promotion_id == p.id))
+        .collect();
+    candidates.sort_by_key(|p| (std::cmp::Reverse(p.priority), p.id));
+
+    let mut applied: Vec<AppliedPromotion> = Vec::new();
+    for promotion in candidates {
+        if !promotion.stackable && !applied.is_empty() {
+            continue;
+        }
+        if !cart_conditions_hold(promotion, ctx, subtotal) {
+            continue;
+        }
+
+        let eligible: Vec<usize> = (0..ctx.lines.len())
+            .filter(|&i| line_matches(promotion, &ctx.lines[i]))
+            .collect();
+        if eligible.is_empty() {
+            continue;
+        }
+
+        let discount = apply_action(&promotion.action, ctx, &eligible, &mut remaining);
+        let free_shipping = promotion.action == PromotionAction::FreeShipping;
+        if discount == 0 && !free_shipping {
+            continue;
+        }
+
+        let coupon = coupon.filter(|c| promotion.requires_coupon && c.promotion_id == promotion.id);
+        applied.push(AppliedPromotion {
+            promotion_id: promotion.id,
+            name: promotion.name.clone(),
+            coupon_id: coupon.map(|c| c.id),
+            coupon_code: coupon.map(|c| c.code.as_str().to_string()),
+            discount,
+            free_shipping,
+        });
+
+        if !promotion.stackable {
+            break;
+        }
+    }
+
+    let discount_total = applied.iter().map(|a| a.discount).sum();
+    PricingResult {
+        subtotal,
+        discount_total,
+        total: subtotal - discount_total,
+        free_shipping: applied.iter().any(|a| a.free_shipping),
+        applied,
+    }
+}
+
+fn cart_conditions_hold(promotion: &PromotionEntity, ctx: &PricingContext, subtotal: i64) -> bool {
+    promotion.conditions.iter().all(|condition| match condition {
+        PromotionCondition::MinCartTotal { amount } => subtotal >= *amount,
+        PromotionCondition::CustomerRole { role } => {
+            ctx.customer_roles.iter().any(|r| r.eq_ignore_ascii_case(role))
+        }
+        PromotionCondition::DateWindow { starts_at, ends_at } => {
+            starts_at.is_none_or(|s| ctx.now >= s) && ends_at.is_none_or(|e| ctx.now < e)
+        }
+        PromotionCondition::Category { .. } | PromotionCondition::Author { .. } => true,
+    })
+}
+
+fn line_matches(promotion: &PromotionEntity, line: &PricingLine) -> bool {
+    promotion.conditions.iter().all(|condition| match condition {
+        PromotionCondition::Category { category } => line
+            .category
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case(category)),
+        PromotionCondition::Author { author } => line.author.eq_ignore_ascii_case(author),
+        _ => true,
+    })
+}
+
+/// Takes the discount out of `remaining` and returns how much was taken.
+fn apply_action(
+    action: &PromotionAction,
+    ctx: &PricingContext,
+    eligible: &[usize],
+    remaining: &mut [i64],
+) -> i64 {
+    let mut taken = 0;
+    match action {
+        PromotionAction::PercentOff { percent } => {
+            for &i in eligible {
+                // ปัดเศษครึ่งขึ้นทีละ line
+                let off = (remaining[i] * *percent as i64 + 50) / 100;
+                remaining[i] -= off;
+                taken += off;
+            }
+        }
+        PromotionAction::FixedOff { amount } => {
+            let mut left = *amount;
+            for &i in eligible {
+                let off = left.min(remaining[i]);
+                remaining[i] -= off;
+                taken += off;
+                left -= off;
+            }
+        }
+        PromotionAction::BuyXPayY { buy, pay } => {
+            // กระจายเป็นราย copy แล้วให้ copy ที่ถูกที่สุดฟรี
+            let mut units: Vec<(i64, i32, usize)> = eligible
+                .iter()
+                .flat_map(|&i| {
+                    let line = &ctx.lines[i];
+                    (0..line.quantity).map(move |_| (line.unit_price, line.book_id, i))
+                })
+                .collect();
+            units.sort();
+
+            let free_units = (units.len() as i32 / buy) * (buy - pay);
+            for &(unit_price, _, i) in units.iter().take(free_units as usize) {
+                let off = unit_price.min(remaining[i]);
+                remaining[i] -= off;
+                taken += off;
+            }
+        }
+        PromotionAction::FreeShipping => {}
+    }
+    taken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(book_id: i32, quantity: i32, unit_price: i64) -> PricingLine {
+        PricingLine {
+            book_id,
+            category: Some("Fantasy".to_string()),
+            author: "J.R.R. Tolkien".to_string(),
+            quantity,
+            unit_price,
+        }
+    }
+
+    fn cart(lines: Vec<PricingLine>) -> PricingContext {
+        PricingContext { lines, customer_roles: Vec::new(), now: Utc::now() }
+    }
+
+    fn promotion(id: i32, action: PromotionAction, priority: i32, stackable: bool) -> PromotionEntity {
+        let mut promotion =
+            PromotionEntity::new(format!("Promotion {}", id), Vec::new(), action, priority, stackable, false)
+                .unwrap();
+        promotion.id = id;
+        promotion
+    }
+
+    fn applied_ids(result: &PricingResult) -> Vec<i32> {
+        result.applied.iter().map(|a| a.promotion_id).collect()
+    }
+
+    #[test]
+    fn applies_by_priority_then_id_each_on_what_is_left() {
+        let ctx = cart(vec![line(1, 1, 1_000)]);
+        let promotions = vec![
+            promotion(3, PromotionAction::PercentOff { percent: 10 }, 5, true),
+            promotion(2, PromotionAction::FixedOff { amount: 50 }, 10, true),
+            promotion(1, PromotionAction::FixedOff { amount: 50 }, 10, true),
+        ];
+
+        let result = evaluate(&ctx, &promotions, None);
+
+        assert_eq!(applied_ids(&result), vec![1, 2, 3]);
+        // 1000 - 50 - 50 = 900, แล้ว 10% ของที่เหลือ
+        assert_eq!(result.applied.iter().map(|a| a.discount).collect::<Vec<_>>(), vec![50, 50, 90]);
+        assert_eq!(result.discount_total, 190);
+        assert_eq!(result.total, 810);
+        // ลำดับใน slice ไม่มีผลกับผลลัพธ์
+        let mut reversed = promotions.clone();
+        reversed.reverse();
+        assert_eq!(evaluate(&ctx, &reversed, None), result);
+    }
+
+    #[test]
+    fn non_stackable_promotion_only_applies_alone() {
+        let ctx = cart(vec![line(1, 1, 1_000)]);
+
+        let exclusive_first = vec![
+            promotion(1, PromotionAction::PercentOff { percent: 20 }, 10, false),
+            promotion(2, PromotionAction::FixedOff { amount: 100 }, 5, true),
+        ];
+        let result = evaluate(&ctx, &exclusive_first, None);
+        assert_eq!(applied_ids(&result), vec![1]);
+        assert_eq!(result.total, 800);
+
+        let exclusive_later = vec![
+            promotion(1, PromotionAction::FixedOff { amount: 100 }, 10, true),
+            promotion(2, PromotionAction::PercentOff { percent: 20 }, 5, false),
+        ];
+        let result = evaluate(&ctx, &exclusive_later, None);
+        assert_eq!(applied_ids(&result), vec![1]);
+        assert_eq!(result.total, 900);
+    }
+
+    #[test]
+    fn buy_x_pay_y_gives_the_cheapest_copies_free() {
+        let buy_3_pay_2 = vec![promotion(1, PromotionAction::BuyXPayY { buy: 3, pay: 2 }, 0, true)];
+
+        let four_copies = cart(vec![line(1, 1, 300), line(2, 2, 200), line(3, 1, 100)]);
+        assert_eq!(evaluate(&four_copies, &buy_3_pay_2, None).discount_total, 100);
+
+        let six_copies = cart(vec![line(1, 3, 300), line(2, 2, 200), line(3, 1, 100)]);
+        assert_eq!(evaluate(&six_copies, &buy_3_pay_2, None).discount_total, 300);
+
+        let two_copies = cart(vec![line(1, 2, 300)]);
+        assert!(evaluate(&two_copies, &buy_3_pay_2, None).applied.is_empty());
+    }
+
+    #[test]
+    fn coupon_only_promotion_needs_its_own_coupon() {
+        let ctx = cart(vec![line(1, 1, 1_000)]);
+        let mut with_coupon = promotion(1, PromotionAction::PercentOff { percent: 10 }, 0, true);
+        with_coupon.requires_coupon = true;
+        let promotions = vec![with_coupon];
+
+        assert!(evaluate(&ctx, &promotions, None).applied.is_empty());
+
+        let mut other = CouponEntity::new("OTHER-10".to_string(), 2, None, None).unwrap();
+        other.id = 8;
+        assert!(evaluate(&ctx, &promotions, Some(&other)).applied.is_empty());
+
+        let mut coupon = CouponEntity::new("welcome10".to_string(), 1, None, None).unwrap();
+        coupon.id = 9;
+        let result = evaluate(&ctx, &promotions, Some(&coupon));
+        assert_eq!(result.applied.len(), 1);
+        assert_eq!(result.applied[0].coupon_id, Some(9));
+        assert_eq!(result.applied[0].coupon_code.as_deref(), Some("WELCOME10"));
+        assert_eq!(result.total, 900);
+    }
+
+    #[test]
+    fn reports_each_applied_promotion() {
+        let mut ctx = cart(vec![line(1, 2, 500), line(2, 1, 400)]);
+        ctx.lines[1].category = Some("Cooking".to_string());
+        let mut fantasy = promotion(1, PromotionAction::FixedOff { amount: 150 }, 10, true);
+        fantasy.conditions = vec![PromotionCondition::Category { category: "fantasy".to_string() }];
+        let mut big_cart = promotion(2, PromotionAction::FreeShipping, 5, true);
+        big_cart.conditions = vec![PromotionCondition::MinCartTotal { amount: 1_400 }];
+        let mut members = promotion(3, PromotionAction::PercentOff { percent: 50 }, 1, true);
+        members.conditions = vec![PromotionCondition::CustomerRole { role: "MEMBER".to_string() }];
+
+        let result = evaluate(&ctx, &[members, big_cart, fantasy], None);
+
+        assert_eq!(
+            result.applied,
+            vec![
+                AppliedPromotion {
+                    promotion_id: 1,
+                    name: "Promotion 1".to_string(),
+                    coupon_id: None,
+                    coupon_code: None,
+                    discount: 150,
+                    free_shipping: false,
+                },
+                AppliedPromotion {
+                    promotion_id: 2,
+                    name: "Promotion 2".to_string(),
+                    coupon_id: None,
+                    coupon_code: None,
+                    discount: 0,
+                    free_shipping: true,
+                },
+            ]
+        );
+        assert_eq!(result.subtotal, 1_400);
+        assert_eq!(result.total, 1_250);
+        assert!(result.free_shipping);
+    }
+}
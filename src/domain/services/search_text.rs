@@ -0,0 +1,369 @@
+use crate::domain::value_objects::isbn::Isbn;
+
+/// Longest query we bother tokenizing
+const MAX_QUERY_CHARS: usize = 200;
+
+/// A query term. Postgres has no Thai dictionary and Thai has no spaces, so
+/// Thai runs are matched through character bigrams (the `thai_bigrams` SQL
+/// function indexes them the same way); everything else uses `simple`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchTerm {
+    /// Lowercased word, matched as a prefix
+    Word(String),
+    /// A run of Thai characters
+    Thai(String),
+}
+
+impl SearchTerm {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Word(w) | Self::Thai(w) => w,
+        }
+    }
+}
+
+pub fn is_thai(c: char) -> bool {
+    ('\u{0E01}'..='\u{0E5B}').contains(&c)
+}
+
+/// Returns the ISBN when the whole query is one (hyphens and spaces allowed).
+pub fn as_isbn(query: &str) -> Option<Isbn> {
+    let trimmed = query.trim();
+    let looks_like_isbn = !trimmed.is_empty()
+        && trimmed
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | ' ' | 'x' | 'X'));
+    if !looks_like_isbn {
+        return None;
+    }
+    Isbn::new(trimmed).ok()
+}
+
+/// Splits a query into words and Thai runs, dropping punctuation and duplicates.
+pub fn tokenize(query: &str) -> Vec<SearchTerm> {
+    let mut terms: Vec<SearchTerm> = Vec::new();
+    let mut current = String::new();
+    let mut current_thai = false;
+
+    let mut flush = |current: &mut String, thai: bool| {
+        if current.is_empty() {
+            return;
+        }
+        let term = if thai {
+            SearchTerm::Thai(std::mem::take(current))
+        } else {
+            SearchTerm::Word(std::mem::take(current).to_lowercase())
+        };
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    };
+
+    for c in query.chars().take(MAX_QUERY_CHARS) {
+        let thai = is_thai(c);
+        if !thai && !c.is_alphanumeric() {
+            flush(&mut current, current_thai);
+            continue;
+        }
+        if !current.is_empty() && thai != current_thai {
+            flush(&mut current, current_thai);
+        }
+        current_thai = thai;
+        current.push(c);
+    }
+    flush(&mut current, current_thai);
+
+    terms
+}
+
+/// Builds `tsquery` text that every term must match, or `None` for an empty query.
+///
+/// Lexemes are quoted and fed to a `::tsquery` cast so they are used verbatim;
+/// terms only contain alphanumerics, so no escaping is needed.
+pub fn to_tsquery(terms: &[SearchTerm]) -> Option<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for term in terms {
+        match term {
+            SearchTerm::Word(w) => parts.push(format!("'{}':*", w)),
+            SearchTerm::Thai(run) if run.chars().count() == 1 => {
+                parts.push(format!("'{}':*", run));
+            }
+            SearchTerm::Thai(run) => {
+                parts.extend(thai_bigrams(run).into_iter().map(|b| format!("'{}'", b)));
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        parts.dedup();
+        Some(parts.join(" & "))
+    }
+}
+
+/// Overlapping character pairs of a Thai run; a single character is kept as is.
+pub fn thai_bigrams(run: &str) -> Vec<String> {
+    let chars: Vec<char> = run.chars().collect();
+    if chars.len() < 2 {
+        return vec![run.to_string()];
+    }
+    chars.windows(2).map(|pair| pair.iter().collect()).collect()
+}
+
+/// Tokens for indexing a document as (byte start, byte end, text): lowercased
+/// words, and bigrams for Thai runs. Queries built from `tokenize` match them.
+pub fn index_tokens(text: &str) -> Vec<(usize, usize, String)> {
+    let mut tokens = Vec::new();
+    let mut run_start: Option<(usize, bool)> = None;
+
+    let flush = |start: usize, end: usize, thai: bool, tokens: &mut Vec<(usize, usize, String)>| {
+        let run = &text[start..end];
+        if !thai {
+            tokens.push((start, end, run.to_lowercase()));
+            return;
+        }
+        let offsets: Vec<usize> = run.char_indices().map(|(i, _)| start + i).chain([end]).collect();
+        if offsets.len() <= 2 {
+            tokens.push((start, end, run.to_string()));
+            return;
+        }
+        for w in offsets.windows(3) {
+            tokens.push((w[0], w[2], text[w[0]..w[2]].to_string()));
+        }
+    };
+
+    for (i, c) in text.char_indices() {
+        let thai = is_thai(c);
+        let is_token_char = thai || c.is_alphanumeric();
+        match run_start {
+            Some((start, run_thai)) if !is_token_char || run_thai != thai => {
+                flush(start, i, run_thai, &mut tokens);
+                run_start = is_token_char.then_some((i, thai));
+            }
+            None if is_token_char => run_start = Some((i, thai)),
+            _ => {}
+        }
+    }
+    if let Some((start, thai)) = run_start {
+        flush(start, text.len(), thai, &mut tokens);
+    }
+
+    tokens
+}
+
+/// HTML-escapes `text` and wraps every match of the terms in `<mark>`.
+pub fn highlight(text: &str, terms: &[SearchTerm]) -> String {
+    let ranges = match_ranges(text, terms);
+    let mut out = String::with_capacity(text.len() + ranges.len() * 13);
+    let mut pos = 0;
+    for (start, end) in ranges {
+        out.push_str(&escape_html(&text[pos..start]));
+        out.push_str("<mark>");
+        out.push_str(&escape_html(&text[start..end]));
+        out.push_str("</mark>");
+        pos = end;
+    }
+    out.push_str(&escape_html(&text[pos..]));
+    out
+}
+
+/// About `max_chars` characters of `text` around the first match, highlighted.
+pub fn snippet(text: &str, terms: &[SearchTerm], max_chars: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return highlight(text, terms);
+    }
+
+    // เริ่มก่อนคำแรกที่เจอเล็กน้อย เพื่อให้เห็นบริบท
+    let first_match = match_ranges(text, terms).first().map(|(s, _)| *s).unwrap_or(0);
+    let match_char = text[..first_match].chars().count();
+    let start_char = match_char.saturating_sub(max_chars / 4).min(total - max_chars);
+
+    let start = char_to_byte(text, start_char);
+    let end = char_to_byte(text, start_char + max_chars);
+
+    let mut out = String::new();
+    if start_char > 0 {
+        out.push('…');
+    }
+    out.push_str(&highlight(&text[start..end], terms));
+    if end < text.len() {
+        out.push('…');
+    }
+    out
+}
+
+/// Byte ranges of term matches in `text`, sorted and merged.
+fn match_ranges(text: &str, terms: &[SearchTerm]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+
+    for term in terms {
+        match term {
+            SearchTerm::Thai(run) => {
+                ranges.extend(text.match_indices(run.as_str()).map(|(i, m)| (i, i + m.len())));
+            }
+            SearchTerm::Word(word) => {
+                // prefix ของคำ: ต้องเริ่มที่ต้นคำเท่านั้น
+                let mut prev: Option<char> = None;
+                for (i, c) in text.char_indices() {
+                    let at_word_start = prev.is_none_or(|p| !p.is_alphanumeric());
+                    prev = Some(c);
+                    if !at_word_start {
+                        continue;
+                    }
+                    if let Some(end) = prefix_match_end(&text[i..], word) {
+                        ranges.push((i, i + end));
+                    }
+                }
+            }
+        }
+    }
+
+    ranges.sort();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Byte length of the prefix of `text` that case-insensitively equals `word`.
+fn prefix_match_end(text: &str, word: &str) -> Option<usize> {
+    let mut expected = word.chars();
+    let mut end = 0;
+    for (i, c) in text.char_indices() {
+        let mut lower = c.to_lowercase();
+        let matches = lower.all(|l| expected.next() == Some(l));
+        if !matches {
+            return None;
+        }
+        end = i + c.len_utf8();
+        if expected.as_str().is_empty() {
+            return Some(end);
+        }
+    }
+    expected.as_str().is_empty().then_some(end)
+}
+
+fn char_to_byte(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len())
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(w: &str) -> SearchTerm {
+        SearchTerm::Word(w.to_string())
+    }
+
+    fn thai(run: &str) -> SearchTerm {
+        SearchTerm::Thai(run.to_string())
+    }
+
+    #[test]
+    fn splits_mixed_thai_and_latin_queries() {
+        assert_eq!(
+            tokenize("Harry Potter กับศิลาอาถรรพ์ 2nd-Edition"),
+            vec![word("harry"), word("potter"), thai("กับศิลาอาถรรพ์"), word("2nd"), word("edition")]
+        );
+        // ไม่มีช่องว่างคั่นระหว่างภาษา
+        assert_eq!(tokenize("Pythonภาษาไทย"), vec![word("python"), thai("ภาษาไทย")]);
+        assert_eq!(tokenize("Dune DUNE, dune!"), vec![word("dune")]);
+        assert!(tokenize(" !?- ").is_empty());
+    }
+
+    #[test]
+    fn bigrams_match_sql_function() {
+        // SELECT thai_bigrams('แฮร์รี่ กับ ก')
+        let sql = ["แฮ", "ฮร", "ร์", "์ร", "รี", "ี่", "กั", "ับ", "ก"];
+        let rust: Vec<String> = ["แฮร์รี่", "กับ", "ก"].into_iter().flat_map(thai_bigrams).collect();
+        assert_eq!(rust, sql);
+    }
+
+    #[test]
+    fn builds_tsquery_from_words_and_bigrams() {
+        assert_eq!(
+            to_tsquery(&tokenize("แฮร์รี่ Potter")).as_deref(),
+            Some("'แฮ' & 'ฮร' & 'ร์' & '์ร' & 'รี' & 'ี่' & 'potter':*")
+        );
+        // อักษรไทยตัวเดียวค้นแบบ prefix
+        assert_eq!(to_tsquery(&tokenize("ก")).as_deref(), Some("'ก':*"));
+        assert_eq!(to_tsquery(&tokenize("...")), None);
+    }
+
+    #[test]
+    fn index_tokens_use_byte_offsets() {
+        assert_eq!(
+            index_tokens("Dune ดูน"),
+            vec![
+                (0, 4, "dune".to_string()),
+                (5, 11, "ดู".to_string()),
+                (8, 14, "ูน".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn highlight_escapes_html() {
+        assert_eq!(
+            highlight("<b>Tom & Jerry</b>", &tokenize("tom")),
+            "&lt;b&gt;<mark>Tom</mark> &amp; Jerry&lt;/b&gt;"
+        );
+        assert_eq!(highlight("\"It's\"", &[]), "&quot;It&#39;s&quot;");
+    }
+
+    #[test]
+    fn highlight_marks_word_prefixes_and_thai_runs() {
+        assert_eq!(
+            highlight("Potterhead Teapot Potter", &tokenize("pot")),
+            "<mark>Pot</mark>terhead Teapot <mark>Pot</mark>ter"
+        );
+        assert_eq!(
+            highlight("แฮร์รี่ พอตเตอร์", &tokenize("พอต")),
+            "แฮร์รี่ <mark>พอต</mark>เตอร์"
+        );
+    }
+
+    #[test]
+    fn snippet_cuts_multibyte_text_on_char_boundaries() {
+        let text = format!("{} Dune {}", "ก".repeat(50), "ข".repeat(50));
+        assert_eq!(
+            snippet(&text, &tokenize("dune"), 20),
+            format!("…กกกก <mark>Dune</mark> {}…", "ข".repeat(10))
+        );
+
+        // ไม่เจอคำค้น: เริ่มตั้งแต่ต้นข้อความ
+        assert_eq!(snippet(&"ก".repeat(30), &[], 10), format!("{}…", "ก".repeat(10)));
+        assert_eq!(snippet("สั้น <3", &[], 10), "สั้น &lt;3");
+    }
+
+    #[test]
+    fn recognises_isbn_queries() {
+        assert_eq!(as_isbn(" 978-0-261-10357-3 ").unwrap().as_str(), "9780261103573");
+        assert!(as_isbn("Dune").is_none());
+        assert!(as_isbn("12345").is_none());
+    }
+}
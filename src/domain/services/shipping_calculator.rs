@@ -0,0 +1,121 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        shipping_zone::{RateBasis, ShippingMethodEntity, ShippingZoneEntity},
+    },
+    value_objects::postal_address::PostalAddress,
+};
+
+/// Used for books whose weight has not been entered yet (a typical paperback).
+pub const DEFAULT_BOOK_WEIGHT_GRAMS: i64 = 400;
+
+/// Courier convention: volumetric kg = L x W x H (cm) / 5000, i.e. grams = mm³ / 5000.
+pub const VOLUMETRIC_DIVISOR: i64 = 5000;
+
+/// Where the parcel goes.
+#[derive(Debug, Clone)]
+pub struct ShippingDestination {
+    /// ISO 3166-1 alpha-2
+    pub country: String,
+    pub postal_code: Option<String>,
+}
+
+impl From<&PostalAddress> for ShippingDestination {
+    fn from(address: &PostalAddress) -> Self {
+        Self {
+            country: address.country.clone(),
+            postal_code: address.postal_code.clone(),
+        }
+    }
+}
+
+/// What is being shipped, reduced to the values rate tables are keyed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parcel {
+    /// max(actual, volumetric) summed over all copies
+    pub chargeable_weight_grams: i64,
+    pub item_count: i32,
+    /// Goods total after discounts, in minor units
+    pub subtotal: i64,
+}
+
+impl Parcel {
+    pub fn from_lines<'a>(
+        lines: impl IntoIterator<Item = (&'a BookEntity, i32)>,
+        subtotal: i64,
+    ) -> Self {
+        let mut chargeable_weight_grams = 0;
+        let mut item_count = 0;
+        for (book, quantity) in lines {
+            let actual = book
+                .weight_grams
+                .map(|w| w as i64)
+                .unwrap_or(DEFAULT_BOOK_WEIGHT_GRAMS);
+            let volumetric = book
+                .dimensions
+                .map(|d| d.volume_mm3() / VOLUMETRIC_DIVISOR)
+                .unwrap_or(0);
+            chargeable_weight_grams += actual.max(volumetric) * quantity as i64;
+            item_count += quantity;
+        }
+
+        Self {
+            chargeable_weight_grams,
+            item_count,
+            subtotal,
+        }
+    }
+
+    pub fn value_for(&self, basis: RateBasis) -> i64 {
+        match basis {
+            RateBasis::Weight => self.chargeable_weight_grams,
+            RateBasis::ItemCount => self.item_count as i64,
+            RateBasis::Subtotal => self.subtotal,
+        }
+    }
+}
+
+/// The most specific active zone covering the destination.
+pub fn find_zone<'a>(
+    zones: &'a [ShippingZoneEntity],
+    destination: &ShippingDestination,
+) -> Option<&'a ShippingZoneEntity> {
+    zones
+        .iter()
+        .filter_map(|z| {
+            z.specificity(&destination.country, destination.postal_code.as_deref())
+                .map(|s| (s, z))
+        })
+        .max_by_key(|(s, z)| (*s, std::cmp::Reverse(z.id)))
+        .map(|(_, z)| z)
+}
+
+/// Whether the method can take the parcel at all (active, under its weight limit).
+pub fn accepts(method: &ShippingMethodEntity, parcel: &Parcel) -> bool {
+    method.is_active
+        && method
+            .max_weight_grams
+            .is_none_or(|max| parcel.chargeable_weight_grams <= max as i64)
+}
+
+/// Delivery window counting business days (Mon-Fri) from the day after `from`.
+pub fn estimate_delivery(from: DateTime<Utc>, min_days: i32, max_days: i32) -> (NaiveDate, NaiveDate) {
+    let start = from.date_naive();
+    (
+        add_business_days(start, min_days),
+        add_business_days(start, max_days),
+    )
+}
+
+fn add_business_days(mut date: NaiveDate, days: i32) -> NaiveDate {
+    let mut remaining = days;
+    while remaining > 0 {
+        date += Duration::days(1);
+        if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            remaining -= 1;
+        }
+    }
+    date
+}
@@ -0,0 +1,174 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+
+use crate::domain::{
+    entities::tax_rate::TaxRateEntity,
+    value_objects::{
+        money::{Money, Rounding},
+        tax_class::TaxClass,
+    },
+};
+
+const BPS: i64 = 10_000;
+
+/// Whether an amount already contains tax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxMode {
+    Inclusive,
+    Exclusive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxBreakdown {
+    pub net: Money,
+    pub tax: Money,
+    pub gross: Money,
+    pub rate_bps: i32,
+}
+
+/// Tax contained in a tax-inclusive amount: `gross * rate / (1 + rate)`, half-up.
+pub fn tax_from_gross(gross: i64, rate_bps: i32) -> i64 {
+    let rate = rate_bps as i128;
+    Rounding::HalfUp.divide(gross as i128 * rate, BPS as i128 + rate) as i64
+}
+
+/// Tax on top of a tax-exclusive amount: `net * rate`, half-up.
+pub fn tax_from_net(net: i64, rate_bps: i32) -> i64 {
+    Rounding::HalfUp.divide(net as i128 * rate_bps as i128, BPS as i128) as i64
+}
+
+/// Splits `amount` into net, tax and gross.
+pub fn calculate(amount: &Money, rate_bps: i32, mode: TaxMode) -> Result<TaxBreakdown> {
+    if rate_bps < 0 {
+        return Err(anyhow!("Tax rate cannot be negative"));
+    }
+
+    let currency = amount.currency().clone();
+    let (net, tax, gross) = match mode {
+        TaxMode::Inclusive => {
+            let tax = tax_from_gross(amount.amount(), rate_bps);
+            (amount.amount() - tax, tax, amount.amount())
+        }
+        TaxMode::Exclusive => {
+            let tax = tax_from_net(amount.amount(), rate_bps);
+            (amount.amount(), tax, amount.amount() + tax)
+        }
+    };
+
+    Ok(TaxBreakdown {
+        net: Money::new(net, currency.clone()),
+        tax: Money::new(tax, currency.clone()),
+        gross: Money::new(gross, currency),
+        rate_bps,
+    })
+}
+
+/// Picks the rate for a product class in a jurisdiction at a point in time.
+/// A region (`US-CA`) falls back to its country (`US`). No rate means untaxed.
+pub fn find_rate<'a>(
+    rates: &'a [TaxRateEntity],
+    jurisdiction: &str,
+    tax_class: TaxClass,
+    at: DateTime<Utc>,
+) -> Option<&'a TaxRateEntity> {
+    if tax_class == TaxClass::Exempt {
+        return None;
+    }
+
+    let country = jurisdiction.split('-').next().unwrap_or(jurisdiction);
+    [jurisdiction, country].into_iter().find_map(|j| {
+        rates
+            .iter()
+            .filter(|r| r.jurisdiction == j && r.tax_class == tax_class && r.is_valid_at(at))
+            .max_by_key(|r| r.valid_from)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::domain::value_objects::money::Currency;
+
+    fn at(year: i32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn rate(jurisdiction: &str, tax_class: TaxClass, rate_bps: i32, from: i32) -> TaxRateEntity {
+        TaxRateEntity::new(jurisdiction.to_string(), tax_class, "VAT".to_string(), rate_bps, at(from)).unwrap()
+    }
+
+    #[test]
+    fn extracts_tax_from_gross() {
+        assert_eq!(tax_from_gross(10_700, 700), 700);
+        // 1.00 * 7/107 = 0.0654
+        assert_eq!(tax_from_gross(100, 700), 7);
+        assert_eq!(tax_from_gross(10_700, 0), 0);
+    }
+
+    #[test]
+    fn adds_tax_to_net_half_up() {
+        assert_eq!(tax_from_net(10_000, 700), 700);
+        assert_eq!(tax_from_net(50, 700), 4);
+        assert_eq!(tax_from_net(5, 700), 0);
+    }
+
+    #[test]
+    fn breakdown_adds_up_in_both_modes() {
+        let inclusive = calculate(&Money::new(10_700, Currency::thb()), 700, TaxMode::Inclusive).unwrap();
+        assert_eq!(
+            (inclusive.net.amount(), inclusive.tax.amount(), inclusive.gross.amount()),
+            (10_000, 700, 10_700)
+        );
+
+        let exclusive = calculate(&Money::new(10_000, Currency::thb()), 700, TaxMode::Exclusive).unwrap();
+        assert_eq!(
+            (exclusive.net.amount(), exclusive.tax.amount(), exclusive.gross.amount()),
+            (10_000, 700, 10_700)
+        );
+
+        assert!(calculate(&Money::new(100, Currency::thb()), -1, TaxMode::Inclusive).is_err());
+    }
+
+    #[test]
+    fn zero_rate_leaves_price_untaxed() {
+        let exempt = calculate(&Money::new(35_000, Currency::thb()), 0, TaxMode::Inclusive).unwrap();
+        assert_eq!(exempt.tax.amount(), 0);
+        assert_eq!(exempt.net, exempt.gross);
+    }
+
+    #[test]
+    fn finds_rate_for_class() {
+        let rates = vec![
+            rate("TH", TaxClass::PrintedBook, 0, 2000),
+            rate("TH", TaxClass::Ebook, 700, 2000),
+        ];
+
+        assert_eq!(find_rate(&rates, "TH", TaxClass::PrintedBook, at(2026)).unwrap().rate_bps, 0);
+        assert_eq!(find_rate(&rates, "TH", TaxClass::Ebook, at(2026)).unwrap().rate_bps, 700);
+        assert!(find_rate(&rates, "TH", TaxClass::Standard, at(2026)).is_none());
+        assert!(find_rate(&rates, "TH", TaxClass::Exempt, at(2026)).is_none());
+    }
+
+    #[test]
+    fn region_falls_back_to_country() {
+        let rates = vec![
+            rate("US", TaxClass::Standard, 500, 2000),
+            rate("US-CA", TaxClass::Standard, 725, 2000),
+        ];
+
+        assert_eq!(find_rate(&rates, "US-CA", TaxClass::Standard, at(2026)).unwrap().rate_bps, 725);
+        assert_eq!(find_rate(&rates, "US-NY", TaxClass::Standard, at(2026)).unwrap().rate_bps, 500);
+    }
+
+    #[test]
+    fn picks_rate_valid_at_the_time() {
+        let mut old = rate("TH", TaxClass::Standard, 700, 2000);
+        old.valid_to = Some(at(2030));
+        let rates = vec![old, rate("TH", TaxClass::Standard, 1_000, 2030)];
+
+        assert_eq!(find_rate(&rates, "TH", TaxClass::Standard, at(2026)).unwrap().rate_bps, 700);
+        assert_eq!(find_rate(&rates, "TH", TaxClass::Standard, at(2031)).unwrap().rate_bps, 1_000);
+        assert!(find_rate(&rates, "TH", TaxClass::Standard, at(1999)).is_none());
+    }
+}
@@ -5,7 +5,7 @@ pub struct Age(i32);
 
 impl Age {
     pub fn new(age: i32) -> Result<Self> {
-        if age < 1 || age > 120 {
+        if !(1..=120).contains(&age) {
             return Err(anyhow!("Age must be between 1 and 120"));
         }
         Ok(Self(age))
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Rendition sizes of a cover, by longest edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverSize {
+    Thumbnail,
+    Medium,
+    Large,
+}
+
+impl CoverSize {
+    pub const ALL: [CoverSize; 3] = [Self::Thumbnail, Self::Medium, Self::Large];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Thumbnail => "thumbnail",
+            Self::Medium => "medium",
+            Self::Large => "large",
+        }
+    }
+
+    /// Longest edge in pixels; smaller uploads are not enlarged
+    pub fn max_edge(&self) -> u32 {
+        match self {
+            Self::Thumbnail => 200,
+            Self::Medium => 600,
+            Self::Large => 1200,
+        }
+    }
+}
+
+/// Encodings every size is rendered in: WebP for browsers that take it,
+/// JPEG for everything else
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverFormat {
+    Webp,
+    Jpeg,
+}
+
+impl CoverFormat {
+    pub const ALL: [CoverFormat; 2] = [Self::Webp, Self::Jpeg];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Webp => "webp",
+            Self::Jpeg => "jpeg",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Webp => "image/webp",
+            Self::Jpeg => "image/jpeg",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Webp => "webp",
+            Self::Jpeg => "jpg",
+        }
+    }
+}
+
+/// One stored rendition of a cover
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverVariant {
+    pub size: CoverSize,
+    pub format: CoverFormat,
+    pub width: u32,
+    pub height: u32,
+    pub size_bytes: u64,
+    pub storage_key: String,
+    /// Public address the storefront links to
+    pub url: String,
+}
+
+/// Cover art of a book. Kept on the book as JSON, so the field names are
+/// part of the stored format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookCover {
+    /// Upright size of the uploaded picture
+    pub width: u32,
+    pub height: u32,
+    /// Placeholder painted while the picture loads
+    pub blurhash: String,
+    pub variants: Vec<CoverVariant>,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl BookCover {
+    pub fn variant(&self, size: CoverSize, format: CoverFormat) -> Option<&CoverVariant> {
+        self.variants
+            .iter()
+            .find(|v| v.size == size && v.format == format)
+    }
+}
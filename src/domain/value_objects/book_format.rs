@@ -0,0 +1,40 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// Physical or digital edition of a title.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BookFormat {
+    Paperback,
+    Hardcover,
+    Ebook,
+    Audiobook,
+}
+
+impl BookFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Paperback => "paperback",
+            Self::Hardcover => "hardcover",
+            Self::Ebook => "ebook",
+            Self::Audiobook => "audiobook",
+        }
+    }
+
+    pub fn is_digital(&self) -> bool {
+        matches!(self, Self::Ebook | Self::Audiobook)
+    }
+}
+
+impl FromStr for BookFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "paperback" => Ok(Self::Paperback),
+            "hardcover" => Ok(Self::Hardcover),
+            "ebook" => Ok(Self::Ebook),
+            "audiobook" => Ok(Self::Audiobook),
+            _ => Err(anyhow!("Invalid book format: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::{anyhow, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookTitle(String);
+
+impl BookTitle {
+    pub fn new(title: String) -> Result<Self> {
+        let trimmed = title.trim();
+        if trimmed.is_empty() {
+            return Err(anyhow!("Book title cannot be empty"));
+        }
+        if trimmed.chars().count() > 255 {
+            return Err(anyhow!("Book title too long (max 255 chars)"));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for BookTitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
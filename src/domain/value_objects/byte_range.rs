@@ -0,0 +1,89 @@
+/// Inclusive byte range of a file, as in `Content-Range: bytes start-end/size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    pub fn byte_count(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    pub fn content_range(&self, total: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, total)
+    }
+}
+
+/// What a `Range` request header asks for, resolved against the file size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeRequest {
+    /// No header, or one we ignore (malformed, multiple ranges): send it all
+    Full,
+    Partial(ByteRange),
+    /// Starts past the end of the file (answer 416)
+    Unsatisfiable,
+}
+
+impl RangeRequest {
+    /// Parses a single `bytes=` range (`a-b`, `a-` or `-suffix`). Per RFC 9110
+    /// anything we do not understand is ignored rather than rejected.
+    pub fn from_header(header: Option<&str>, total: u64) -> Self {
+        let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+            return Self::Full;
+        };
+        if spec.contains(',') {
+            return Self::Full;
+        }
+        let Some((first, last)) = spec.trim().split_once('-') else {
+            return Self::Full;
+        };
+
+        let range = match (first.trim(), last.trim()) {
+            ("", "") => return Self::Full,
+            // ขอ n ไบต์สุดท้าย
+            ("", suffix) => match suffix.parse::<u64>() {
+                Ok(0) => return Self::Unsatisfiable,
+                Ok(n) if total > 0 => ByteRange {
+                    start: total.saturating_sub(n),
+                    end: total - 1,
+                },
+                Ok(_) => return Self::Unsatisfiable,
+                Err(_) => return Self::Full,
+            },
+            (start, end) => {
+                let Ok(start) = start.parse::<u64>() else {
+                    return Self::Full;
+                };
+                let end = match end {
+                    "" => u64::MAX,
+                    e => match e.parse::<u64>() {
+                        Ok(e) if e >= start => e,
+                        _ => return Self::Full,
+                    },
+                };
+                if start >= total {
+                    return Self::Unsatisfiable;
+                }
+                ByteRange {
+                    start,
+                    end: end.min(total - 1),
+                }
+            }
+        };
+
+        Self::Partial(range)
+    }
+
+    /// Range actually served; `Full` becomes the whole file
+    pub fn resolve(&self, total: u64) -> Option<ByteRange> {
+        match self {
+            Self::Partial(range) => Some(*range),
+            Self::Full if total > 0 => Some(ByteRange {
+                start: 0,
+                end: total - 1,
+            }),
+            Self::Full | Self::Unsatisfiable => None,
+        }
+    }
+}
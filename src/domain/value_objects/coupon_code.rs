@@ -0,0 +1,28 @@
+use anyhow::{anyhow, Result};
+
+/// Coupon code typed by customers. Stored upper-case so lookups are case-insensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CouponCode(String);
+
+impl CouponCode {
+    pub fn new(code: String) -> Result<Self> {
+        let normalized = code.trim().to_uppercase();
+        if normalized.len() < 4 || normalized.len() > 32 {
+            return Err(anyhow!("Coupon code must be 4-32 characters"));
+        }
+        if !normalized.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(anyhow!("Coupon code may only contain letters, digits and '-'"));
+        }
+        Ok(Self(normalized))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for CouponCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
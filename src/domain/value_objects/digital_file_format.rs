@@ -0,0 +1,51 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+use crate::domain::value_objects::book_format::BookFormat;
+
+/// File type of a downloadable edition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigitalFileFormat {
+    Epub,
+    Pdf,
+    Mp3,
+}
+
+impl DigitalFileFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Epub => "epub",
+            Self::Pdf => "pdf",
+            Self::Mp3 => "mp3",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Epub => "application/epub+zip",
+            Self::Pdf => "application/pdf",
+            Self::Mp3 => "audio/mpeg",
+        }
+    }
+
+    /// The book format this file is an edition of
+    pub fn book_format(&self) -> BookFormat {
+        match self {
+            Self::Epub | Self::Pdf => BookFormat::Ebook,
+            Self::Mp3 => BookFormat::Audiobook,
+        }
+    }
+}
+
+impl FromStr for DigitalFileFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "epub" => Ok(Self::Epub),
+            "pdf" => Ok(Self::Pdf),
+            "mp3" => Ok(Self::Mp3),
+            _ => Err(anyhow!("Invalid digital file format: {}", s)),
+        }
+    }
+}
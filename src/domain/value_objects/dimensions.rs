@@ -0,0 +1,41 @@
+use anyhow::{anyhow, Result};
+
+/// Physical size of an item in millimetres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dimensions {
+    length_mm: i32,
+    width_mm: i32,
+    height_mm: i32,
+}
+
+impl Dimensions {
+    pub fn new(length_mm: i32, width_mm: i32, height_mm: i32) -> Result<Self> {
+        if length_mm <= 0 || width_mm <= 0 || height_mm <= 0 {
+            return Err(anyhow!("Dimensions must be positive"));
+        }
+        if length_mm > 2000 || width_mm > 2000 || height_mm > 2000 {
+            return Err(anyhow!("Dimensions too large (max 2000 mm per side)"));
+        }
+        Ok(Self {
+            length_mm,
+            width_mm,
+            height_mm,
+        })
+    }
+
+    pub fn length_mm(&self) -> i32 {
+        self.length_mm
+    }
+
+    pub fn width_mm(&self) -> i32 {
+        self.width_mm
+    }
+
+    pub fn height_mm(&self) -> i32 {
+        self.height_mm
+    }
+
+    pub fn volume_mm3(&self) -> i64 {
+        self.length_mm as i64 * self.width_mm as i64 * self.height_mm as i64
+    }
+}
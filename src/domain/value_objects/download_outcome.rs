@@ -0,0 +1,36 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// Result of a download attempt with a validly signed link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DownloadOutcome {
+    Served,
+    Expired,
+    Revoked,
+    LimitReached,
+}
+
+impl DownloadOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Served => "served",
+            Self::Expired => "expired",
+            Self::Revoked => "revoked",
+            Self::LimitReached => "limit_reached",
+        }
+    }
+}
+
+impl FromStr for DownloadOutcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "served" => Ok(Self::Served),
+            "expired" => Ok(Self::Expired),
+            "revoked" => Ok(Self::Revoked),
+            "limit_reached" => Ok(Self::LimitReached),
+            _ => Err(anyhow!("Invalid download outcome: {}", s)),
+        }
+    }
+}
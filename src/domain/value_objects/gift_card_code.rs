@@ -0,0 +1,56 @@
+use anyhow::{anyhow, Result};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// No 0/O, 1/I/L: codes are read off cards and typed in by hand
+const ALPHABET: &[u8] = b"23456789ABCDEFGHJKMNPQRSTUVWXYZ";
+const CODE_LENGTH: usize = 16;
+const GROUP: usize = 4;
+
+/// Redeemable gift card code. Only its hash is stored; the code itself is
+/// shown once, when the card is issued.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GiftCardCode(String);
+
+impl GiftCardCode {
+    /// Accepts any case, with or without the dashes and spaces of the printed form
+    pub fn new(code: String) -> Result<Self> {
+        let normalized: String = code
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if normalized.len() != CODE_LENGTH || !normalized.bytes().all(|b| ALPHABET.contains(&b)) {
+            return Err(anyhow!("Invalid gift card code"));
+        }
+        Ok(Self(normalized))
+    }
+
+    pub fn generate() -> Self {
+        let mut rng = rand::rng();
+        let code: String = (0..CODE_LENGTH)
+            .map(|_| char::from(ALPHABET[rng.random_range(0..ALPHABET.len())]))
+            .collect();
+        Self(code)
+    }
+
+    /// Grouped for printing, e.g. `7KQM-3XRT-...`
+    pub fn formatted(&self) -> String {
+        self.0
+            .as_bytes()
+            .chunks(GROUP)
+            .map(|c| String::from_utf8_lossy(c).into_owned())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// SHA-256 of the normalized code, hex encoded; what the database keeps
+    pub fn hash(&self) -> String {
+        hex::encode(Sha256::digest(self.0.as_bytes()))
+    }
+
+    /// Shown to staff and customers to tell cards apart
+    pub fn last4(&self) -> &str {
+        &self.0[CODE_LENGTH - GROUP..]
+    }
+}
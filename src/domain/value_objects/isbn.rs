@@ -0,0 +1,132 @@
+use anyhow::{anyhow, Result};
+
+/// ISBN normalized to its 13-digit form (ISBN-10 input is converted).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Isbn(String);
+
+impl Isbn {
+    pub fn new(value: &str) -> Result<Self> {
+        let cleaned: String = value
+            .trim()
+            .chars()
+            .filter(|c| *c != '-' && *c != ' ')
+            .collect::<String>()
+            .to_uppercase();
+        // ตรวจก่อนนับความยาว: len() นับ byte และ split_at ตัดที่ byte
+        if !cleaned.is_ascii() {
+            return Err(anyhow!("ISBN must contain only digits (and X as ISBN-10 check digit)"));
+        }
+
+        match cleaned.len() {
+            13 => {
+                if !cleaned.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(anyhow!("ISBN-13 must contain only digits"));
+                }
+                if !cleaned.starts_with("978") && !cleaned.starts_with("979") {
+                    return Err(anyhow!("ISBN-13 must start with 978 or 979"));
+                }
+                if Self::isbn13_check_digit(&cleaned[..12]) != cleaned.as_bytes()[12] - b'0' {
+                    return Err(anyhow!("Invalid ISBN-13 check digit"));
+                }
+                Ok(Self(cleaned))
+            }
+            10 => {
+                let (body, check) = cleaned.split_at(9);
+                if !body.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(anyhow!("ISBN-10 must contain only digits (and X as check digit)"));
+                }
+                let expected = Self::isbn10_check_digit(body);
+                let actual = match check {
+                    "X" => 10,
+                    d if d.as_bytes()[0].is_ascii_digit() => d.as_bytes()[0] - b'0',
+                    _ => return Err(anyhow!("Invalid ISBN-10 check digit")),
+                };
+                if expected != actual {
+                    return Err(anyhow!("Invalid ISBN-10 check digit"));
+                }
+
+                let prefixed = format!("978{}", body);
+                let check13 = Self::isbn13_check_digit(&prefixed);
+                Ok(Self(format!("{}{}", prefixed, check13)))
+            }
+            _ => Err(anyhow!("ISBN must have 10 or 13 digits")),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn isbn13_check_digit(first12: &str) -> u8 {
+        let sum: u32 = first12
+            .bytes()
+            .enumerate()
+            .map(|(i, b)| {
+                let digit = (b - b'0') as u32;
+                if i % 2 == 0 { digit } else { digit * 3 }
+            })
+            .sum();
+        ((10 - (sum % 10)) % 10) as u8
+    }
+
+    fn isbn10_check_digit(first9: &str) -> u8 {
+        let sum: u32 = first9
+            .bytes()
+            .enumerate()
+            .map(|(i, b)| (b - b'0') as u32 * (10 - i as u32))
+            .sum();
+        ((11 - (sum % 11)) % 11) as u8
+    }
+}
+
+impl std::fmt::Display for Isbn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_isbn13_with_separators() {
+        let isbn = Isbn::new(" 978-0-261-10357-3 ").unwrap();
+        assert_eq!(isbn.as_str(), "9780261103573");
+    }
+
+    #[test]
+    fn rejects_bad_isbn13() {
+        assert!(Isbn::new("9780261103574").is_err(), "check digit");
+        assert!(Isbn::new("9770261103573").is_err(), "prefix");
+        assert!(Isbn::new("978026110357A").is_err(), "letter");
+    }
+
+    #[test]
+    fn converts_isbn10_to_isbn13() {
+        assert_eq!(Isbn::new("0-261-10357-1").unwrap().as_str(), "9780261103573");
+        // X as check digit, lower case accepted
+        assert_eq!(Isbn::new("080442957x").unwrap().as_str(), "9780804429573");
+    }
+
+    #[test]
+    fn rejects_bad_isbn10() {
+        assert!(Isbn::new("0261103576").is_err(), "check digit");
+        assert!(Isbn::new("02611X3571").is_err(), "X in body");
+        assert!(Isbn::new("026110357Y").is_err(), "check character");
+    }
+
+    #[test]
+    fn rejects_non_ascii_without_panicking() {
+        // 10 and 13 bytes long, but not 10 or 13 characters
+        assert!(Isbn::new("1234567ก").is_err());
+        assert!(Isbn::new("ก1234567890").is_err());
+        assert!(Isbn::new("123456789０").is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        assert!(Isbn::new("").is_err());
+        assert!(Isbn::new("97802611035").is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// Rolling 12-month spend (minor units) needed for Silver...
+pub const SILVER_MIN_SPEND: i64 = 1_000_000;
+/// ...and for Gold
+pub const GOLD_MIN_SPEND: i64 = 3_000_000;
+/// Window the spend is measured over
+pub const TIER_WINDOW_MONTHS: u32 = 12;
+
+/// Loyalty tier. Silver and Gold are held as RBAC roles of the same name;
+/// every customer is at least a member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoyaltyTier {
+    Member,
+    Silver,
+    Gold,
+}
+
+impl LoyaltyTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Member => "member",
+            Self::Silver => "silver",
+            Self::Gold => "gold",
+        }
+    }
+
+    /// The role that marks the tier; members have none
+    pub fn role_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Member => None,
+            Self::Silver => Some("SILVER"),
+            Self::Gold => Some("GOLD"),
+        }
+    }
+
+    pub fn from_role_name(role: &str) -> Option<Self> {
+        match role {
+            "SILVER" => Some(Self::Silver),
+            "GOLD" => Some(Self::Gold),
+            _ => None,
+        }
+    }
+
+    pub fn for_spend(spend: i64) -> Self {
+        if spend >= GOLD_MIN_SPEND {
+            Self::Gold
+        } else if spend >= SILVER_MIN_SPEND {
+            Self::Silver
+        } else {
+            Self::Member
+        }
+    }
+
+    /// The next tier up and the spend it needs
+    pub fn next(&self) -> Option<(Self, i64)> {
+        match self {
+            Self::Member => Some((Self::Silver, SILVER_MIN_SPEND)),
+            Self::Silver => Some((Self::Gold, GOLD_MIN_SPEND)),
+            Self::Gold => None,
+        }
+    }
+}
+
+impl FromStr for LoyaltyTier {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "member" => Ok(Self::Member),
+            "silver" => Ok(Self::Silver),
+            "gold" => Ok(Self::Gold),
+            _ => Err(anyhow!("Invalid loyalty tier: {}", s)),
+        }
+    }
+}
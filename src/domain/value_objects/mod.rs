@@ -4,4 +4,33 @@ pub mod age;
 pub mod phone_number;
 pub mod password;
 pub mod role_name;
-pub mod role_description;
\ No newline at end of file
+pub mod role_description;
+pub mod isbn;
+pub mod book_title;
+pub mod quantity;
+pub mod cart_token;
+pub mod order_status;
+pub mod payment_status;
+pub mod stock_bucket;
+pub mod return_status;
+pub mod coupon_code;
+pub mod money;
+pub mod tax_class;
+pub mod shipping_method_kind;
+pub mod dimensions;
+pub mod postal_address;
+pub mod review_status;
+pub mod rating;
+pub mod share_token;
+pub mod notification_status;
+pub mod book_format;
+pub mod digital_file_format;
+pub mod byte_range;
+pub mod download_outcome;
+pub mod watermark_code;
+pub mod gift_card_code;
+pub mod loyalty_tier;
+pub mod tax_id;
+pub mod import_job_status;
+pub mod book_cover;
+pub mod user_avatar;
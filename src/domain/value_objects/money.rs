@@ -0,0 +1,345 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// ISO 4217 currencies we can price in, with their number of minor units.
+const CURRENCIES: &[(&str, u32)] = &[
+    ("THB", 2),
+    ("USD", 2),
+    ("EUR", 2),
+    ("GBP", 2),
+    ("SGD", 2),
+    ("MYR", 2),
+    ("CNY", 2),
+    ("JPY", 0),
+    ("KRW", 0),
+    ("VND", 0),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Currency(String);
+
+impl Currency {
+    pub fn new(code: &str) -> Result<Self> {
+        let code = code.trim().to_uppercase();
+        if !CURRENCIES.iter().any(|(c, _)| *c == code) {
+            return Err(anyhow!("Unsupported currency: {}", code));
+        }
+        Ok(Self(code))
+    }
+
+    pub fn thb() -> Self {
+        Self("THB".to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Number of digits after the decimal point (2 for THB, 0 for JPY).
+    pub fn minor_units(&self) -> u32 {
+        CURRENCIES
+            .iter()
+            .find(|(c, _)| *c == self.0)
+            .map(|(_, units)| *units)
+            .unwrap_or(2)
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Currency {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+
+/// How to round when an amount does not divide evenly into minor units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// 0.5 goes away from zero (Thai Revenue Department convention)
+    HalfUp,
+    /// Banker's rounding: 0.5 goes to the even neighbour
+    HalfEven,
+    /// Towards zero
+    Down,
+}
+
+impl Rounding {
+    /// `numerator / denominator` rounded to an integer. `denominator` must be positive.
+    pub fn divide(&self, numerator: i128, denominator: i128) -> i128 {
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+        if remainder == 0 {
+            return quotient;
+        }
+
+        let away = if numerator < 0 { -1 } else { 1 };
+        let twice = remainder.abs() * 2;
+        match self {
+            Self::Down => quotient,
+            Self::HalfUp if twice >= denominator => quotient + away,
+            Self::HalfEven if twice > denominator || (twice == denominator && quotient % 2 != 0) => {
+                quotient + away
+            }
+            _ => quotient,
+        }
+    }
+}
+
+/// An amount of money in integer minor units (satang for THB). Never a float.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    amount: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: i64, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Parses a major-unit decimal string such as `"199.50"` without going through floats.
+    pub fn parse(value: &str, currency: Currency) -> Result<Self> {
+        let value = value.trim();
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        let scale = currency.minor_units() as usize;
+        if whole.is_empty()
+            || fraction.len() > scale
+            || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(anyhow!("Invalid {} amount: {}", currency, value));
+        }
+
+        let padded = format!("{}{:0<scale$}", whole, fraction, scale = scale);
+        let amount: i64 = padded
+            .parse()
+            .map_err(|_| anyhow!("Amount out of range: {}", value))?;
+
+        Ok(Self::new(if negative { -amount } else { amount }, currency))
+    }
+
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount < 0
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money> {
+        self.ensure_same_currency(other)?;
+        let amount = self
+            .amount
+            .checked_add(other.amount)
+            .ok_or_else(|| anyhow!("Money overflow"))?;
+        Ok(Self::new(amount, self.currency.clone()))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money> {
+        self.ensure_same_currency(other)?;
+        let amount = self
+            .amount
+            .checked_sub(other.amount)
+            .ok_or_else(|| anyhow!("Money overflow"))?;
+        Ok(Self::new(amount, self.currency.clone()))
+    }
+
+    pub fn checked_mul(&self, factor: i64) -> Result<Money> {
+        let amount = self
+            .amount
+            .checked_mul(factor)
+            .ok_or_else(|| anyhow!("Money overflow"))?;
+        Ok(Self::new(amount, self.currency.clone()))
+    }
+
+    /// Multiplies by `numerator / denominator` with the given rounding,
+    /// e.g. `ratio(700, 10_000, HalfUp)` for 7%.
+    pub fn ratio(&self, numerator: i64, denominator: i64, rounding: Rounding) -> Result<Money> {
+        if denominator <= 0 {
+            return Err(anyhow!("Denominator must be positive"));
+        }
+        let value = rounding.divide(self.amount as i128 * numerator as i128, denominator as i128);
+        let amount = i64::try_from(value).map_err(|_| anyhow!("Money overflow"))?;
+        Ok(Self::new(amount, self.currency.clone()))
+    }
+
+    /// Splits the amount into parts proportional to `weights`. Parts always
+    /// add up to the original amount; leftover minor units go to the first
+    /// parts with a non-zero weight.
+    pub fn allocate(&self, weights: &[i64]) -> Result<Vec<Money>> {
+        let total: i128 = weights.iter().map(|w| *w as i128).sum();
+        if weights.iter().any(|w| *w < 0) || total <= 0 {
+            return Err(anyhow!("Allocation weights must be non-negative and not all zero"));
+        }
+
+        let mut parts: Vec<i64> = weights
+            .iter()
+            .map(|w| (self.amount as i128 * *w as i128 / total) as i64)
+            .collect();
+        let mut leftover = self.amount - parts.iter().sum::<i64>();
+        let step = leftover.signum();
+        for (part, _) in parts.iter_mut().zip(weights).filter(|(_, w)| **w > 0) {
+            if leftover == 0 {
+                break;
+            }
+            *part += step;
+            leftover -= step;
+        }
+
+        Ok(parts
+            .into_iter()
+            .map(|amount| Self::new(amount, self.currency.clone()))
+            .collect())
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> Result<()> {
+        if self.currency != other.currency {
+            return Err(anyhow!(
+                "Currency mismatch: {} vs {}",
+                self.currency,
+                other.currency
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Money {
+    /// `1234.50 THB`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scale = self.currency.minor_units();
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let abs = self.amount.unsigned_abs();
+        if scale == 0 {
+            return write!(f, "{}{} {}", sign, abs, self.currency);
+        }
+
+        let divisor = 10u64.pow(scale);
+        write!(
+            f,
+            "{}{}.{:0width$} {}",
+            sign,
+            abs / divisor,
+            abs % divisor,
+            self.currency,
+            width = scale as usize
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thb(amount: i64) -> Money {
+        Money::new(amount, Currency::thb())
+    }
+
+    fn amounts(parts: &[Money]) -> Vec<i64> {
+        parts.iter().map(Money::amount).collect()
+    }
+
+    #[test]
+    fn divide_rounds_halves_per_mode() {
+        assert_eq!(Rounding::HalfUp.divide(5, 2), 3);
+        assert_eq!(Rounding::HalfUp.divide(-5, 2), -3);
+        assert_eq!(Rounding::HalfEven.divide(5, 2), 2);
+        assert_eq!(Rounding::HalfEven.divide(7, 2), 4);
+        assert_eq!(Rounding::HalfEven.divide(-5, 2), -2);
+        assert_eq!(Rounding::HalfEven.divide(-7, 2), -4);
+        assert_eq!(Rounding::Down.divide(8, 3), 2);
+        assert_eq!(Rounding::Down.divide(-8, 3), -2);
+    }
+
+    #[test]
+    fn divide_rounds_non_halves_to_nearest() {
+        assert_eq!(Rounding::HalfUp.divide(7, 3), 2);
+        assert_eq!(Rounding::HalfUp.divide(8, 3), 3);
+        assert_eq!(Rounding::HalfEven.divide(11, 4), 3);
+        assert_eq!(Rounding::HalfEven.divide(9, 4), 2);
+        assert_eq!(Rounding::HalfUp.divide(12, 4), 3);
+    }
+
+    #[test]
+    fn ratio_applies_rounding() {
+        // 7% of 13.50 = 0.945
+        assert_eq!(thb(1_350).ratio(700, 10_000, Rounding::HalfUp).unwrap(), thb(95));
+        assert_eq!(thb(1_350).ratio(700, 10_000, Rounding::HalfEven).unwrap(), thb(94));
+        assert_eq!(thb(1_350).ratio(700, 10_000, Rounding::Down).unwrap(), thb(94));
+        assert!(thb(1_350).ratio(1, 0, Rounding::HalfUp).is_err());
+    }
+
+    #[test]
+    fn allocate_gives_leftover_to_first_parts() {
+        assert_eq!(amounts(&thb(100).allocate(&[1, 1, 1]).unwrap()), vec![34, 33, 33]);
+        assert_eq!(amounts(&thb(1_001).allocate(&[1, 2]).unwrap()), vec![334, 667]);
+        assert_eq!(amounts(&thb(-100).allocate(&[1, 1, 1]).unwrap()), vec![-34, -33, -33]);
+        assert_eq!(amounts(&thb(1_000).allocate(&[3, 3, 3, 1]).unwrap()), vec![300, 300, 300, 100]);
+    }
+
+    #[test]
+    fn allocate_skips_zero_weights() {
+        assert_eq!(amounts(&thb(5).allocate(&[0, 1, 1]).unwrap()), vec![0, 3, 2]);
+    }
+
+    #[test]
+    fn allocate_rejects_bad_weights() {
+        assert!(thb(100).allocate(&[]).is_err());
+        assert!(thb(100).allocate(&[0, 0]).is_err());
+        assert!(thb(100).allocate(&[2, -1]).is_err());
+    }
+
+    #[test]
+    fn parses_decimal_strings() {
+        assert_eq!(Money::parse("199.50", Currency::thb()).unwrap(), thb(19_950));
+        assert_eq!(Money::parse("199.5", Currency::thb()).unwrap(), thb(19_950));
+        assert_eq!(Money::parse(" 199 ", Currency::thb()).unwrap(), thb(19_900));
+        assert_eq!(Money::parse("-12.05", Currency::thb()).unwrap(), thb(-1_205));
+
+        let jpy = Currency::new("jpy").unwrap();
+        assert_eq!(Money::parse("1000", jpy.clone()).unwrap(), Money::new(1_000, jpy));
+    }
+
+    #[test]
+    fn rejects_malformed_amounts() {
+        for value in ["", ".50", "1.234", "1,000", "1e3", "+1", "12.-5", "99999999999999999999"] {
+            assert!(Money::parse(value, Currency::thb()).is_err(), "{value}");
+        }
+        assert!(Money::parse("1.5", Currency::new("JPY").unwrap()).is_err());
+    }
+
+    #[test]
+    fn displays_in_major_units() {
+        assert_eq!(thb(123_450).to_string(), "1234.50 THB");
+        assert_eq!(thb(-5).to_string(), "-0.05 THB");
+        assert_eq!(Money::new(1_000, Currency::new("JPY").unwrap()).to_string(), "1000 JPY");
+    }
+
+    #[test]
+    fn refuses_mixed_currencies() {
+        let usd = Money::new(100, Currency::new("usd").unwrap());
+        assert!(thb(100).checked_add(&usd).is_err());
+        assert!(thb(100).checked_sub(&usd).is_err());
+        assert_eq!(thb(100).checked_add(&thb(50)).unwrap(), thb(150));
+        assert!(Currency::new("XXX").is_err());
+    }
+}
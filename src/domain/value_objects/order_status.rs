@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderStatus {
+    PendingPayment,
+    /// Pre-order with the payment authorized; captured on the release date
+    Preordered,
+    Paid,
+    Picking,
+    Shipped,
+    Delivered,
+    Cancelled,
+    Refunded,
+}
+
+impl OrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PendingPayment => "pending_payment",
+            Self::Preordered => "preordered",
+            Self::Paid => "paid",
+            Self::Picking => "picking",
+            Self::Shipped => "shipped",
+            Self::Delivered => "delivered",
+            Self::Cancelled => "cancelled",
+            Self::Refunded => "refunded",
+        }
+    }
+
+    /// Legal transitions of the order state machine.
+    pub fn can_transition_to(&self, next: OrderStatus) -> bool {
+        use OrderStatus::*;
+
+        matches!(
+            (self, next),
+            (PendingPayment, Paid)
+                | (PendingPayment, Cancelled)
+                | (PendingPayment, Preordered)
+                | (Preordered, Paid)
+                | (Preordered, Cancelled)
+                | (Paid, Picking)
+                | (Paid, Cancelled)
+                | (Paid, Refunded)
+                | (Picking, Shipped)
+                | (Picking, Cancelled)
+                | (Shipped, Delivered)
+                | (Shipped, Refunded)
+                | (Delivered, Refunded)
+        )
+    }
+
+    /// Whether an order in this status still holds the stock reserved at
+    /// checkout.
+    pub fn holds_stock(&self) -> bool {
+        matches!(
+            self,
+            Self::PendingPayment | Self::Preordered | Self::Paid | Self::Picking
+        )
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Cancelled | Self::Refunded)
+    }
+}
+
+impl FromStr for OrderStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending_payment" => Ok(Self::PendingPayment),
+            "preordered" => Ok(Self::Preordered),
+            "paid" => Ok(Self::Paid),
+            "picking" => Ok(Self::Picking),
+            "shipped" => Ok(Self::Shipped),
+            "delivered" => Ok(Self::Delivered),
+            "cancelled" => Ok(Self::Cancelled),
+            "refunded" => Ok(Self::Refunded),
+            _ => Err(anyhow!("Invalid order status: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
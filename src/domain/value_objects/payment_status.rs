@@ -0,0 +1,53 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaymentStatus {
+    Pending,
+    RequiresAction,
+    Authorized,
+    Captured,
+    Declined,
+    Voided,
+    PartiallyRefunded,
+    Refunded,
+}
+
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::RequiresAction => "requires_action",
+            Self::Authorized => "authorized",
+            Self::Captured => "captured",
+            Self::Declined => "declined",
+            Self::Voided => "voided",
+            Self::PartiallyRefunded => "partially_refunded",
+            Self::Refunded => "refunded",
+        }
+    }
+}
+
+impl FromStr for PaymentStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "requires_action" => Ok(Self::RequiresAction),
+            "authorized" => Ok(Self::Authorized),
+            "captured" => Ok(Self::Captured),
+            "declined" => Ok(Self::Declined),
+            "voided" => Ok(Self::Voided),
+            "partially_refunded" => Ok(Self::PartiallyRefunded),
+            "refunded" => Ok(Self::Refunded),
+            _ => Err(anyhow!("Invalid payment status: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for PaymentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
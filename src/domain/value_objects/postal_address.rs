@@ -0,0 +1,105 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    services::address_rules,
+    value_objects::{person_name::PersonName, phone_number::PhoneNumber},
+};
+
+/// A validated postal address. Orders keep a copy of it as JSON, so the
+/// field names are part of the stored format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostalAddress {
+    pub recipient_name: String,
+    pub phone: String,
+    pub line1: String,
+    pub line2: Option<String>,
+    /// ตำบล / แขวง
+    pub subdistrict: Option<String>,
+    /// อำเภอ / เขต
+    pub district: Option<String>,
+    pub province: Option<String>,
+    pub postal_code: Option<String>,
+    /// ISO 3166-1 alpha-2
+    pub country: String,
+}
+
+impl PostalAddress {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        recipient_name: String,
+        phone: String,
+        line1: String,
+        line2: Option<String>,
+        subdistrict: Option<String>,
+        district: Option<String>,
+        province: Option<String>,
+        postal_code: Option<String>,
+        country: String,
+    ) -> Result<Self> {
+        let recipient_name = PersonName::new(recipient_name)?;
+        let phone = PhoneNumber::new(phone)?;
+
+        let line1 = line1.trim().to_string();
+        if line1.is_empty() {
+            return Err(anyhow!("Address line 1 cannot be empty"));
+        }
+        let line2 = clean(line2);
+        let subdistrict = clean(subdistrict);
+        let district = clean(district);
+        if [Some(&line1), line2.as_ref(), subdistrict.as_ref(), district.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|part| part.chars().count() > 200)
+        {
+            return Err(anyhow!("Address lines are limited to 200 characters"));
+        }
+
+        let country = country.trim().to_uppercase();
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(anyhow!("Country must be an ISO 3166 alpha-2 code"));
+        }
+
+        let region = address_rules::normalize_region(
+            &country,
+            province.as_deref(),
+            postal_code.as_deref(),
+            district.as_deref(),
+            subdistrict.as_deref(),
+        )?;
+
+        Ok(Self {
+            recipient_name: recipient_name.to_string(),
+            phone: phone.as_str().to_string(),
+            line1,
+            line2,
+            subdistrict,
+            district,
+            province: region.province,
+            postal_code: region.postal_code,
+            country,
+        })
+    }
+
+    /// The address without recipient and phone, on one line, as printed on
+    /// tax invoices
+    pub fn single_line(&self) -> String {
+        [
+            Some(&self.line1),
+            self.line2.as_ref(),
+            self.subdistrict.as_ref(),
+            self.district.as_ref(),
+            self.province.as_ref(),
+            self.postal_code.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+fn clean(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
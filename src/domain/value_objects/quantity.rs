@@ -0,0 +1,24 @@
+use anyhow::{anyhow, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Quantity(i32);
+
+impl Quantity {
+    pub const MAX: i32 = 99;
+
+    pub fn new(quantity: i32) -> Result<Self> {
+        if !(1..=Self::MAX).contains(&quantity) {
+            return Err(anyhow!("Quantity must be between 1 and {}", Self::MAX));
+        }
+        Ok(Self(quantity))
+    }
+
+    /// Adds two quantities, capping the result at `Quantity::MAX`.
+    pub fn saturating_add(self, other: Quantity) -> Self {
+        Self((self.0 + other.0).min(Self::MAX))
+    }
+
+    pub fn value(&self) -> i32 {
+        self.0
+    }
+}
@@ -0,0 +1,18 @@
+use anyhow::{anyhow, Result};
+
+/// A star rating from 1 to 5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Rating(i32);
+
+impl Rating {
+    pub fn new(value: i32) -> Result<Self> {
+        if !(1..=5).contains(&value) {
+            return Err(anyhow!("Rating must be between 1 and 5"));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> i32 {
+        self.0
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReturnStatus {
+    Requested,
+    Approved,
+    Rejected,
+    Received,
+    /// Refund planned and under way; retrying resumes the unfinished parts
+    Refunding,
+    Refunded,
+}
+
+impl ReturnStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Requested => "requested",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Received => "received",
+            Self::Refunding => "refunding",
+            Self::Refunded => "refunded",
+        }
+    }
+
+    /// Legal moves: requested → approved/rejected → received → refunding → refunded
+    pub fn can_transition_to(&self, next: ReturnStatus) -> bool {
+        matches!(
+            (self, next),
+            (Self::Requested, Self::Approved)
+                | (Self::Requested, Self::Rejected)
+                | (Self::Approved, Self::Received)
+                | (Self::Received, Self::Refunding)
+                | (Self::Refunding, Self::Refunded)
+        )
+    }
+}
+
+impl FromStr for ReturnStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "requested" => Ok(Self::Requested),
+            "approved" => Ok(Self::Approved),
+            "rejected" => Ok(Self::Rejected),
+            "received" => Ok(Self::Received),
+            "refunding" => Ok(Self::Refunding),
+            "refunded" => Ok(Self::Refunded),
+            _ => Err(anyhow!("Invalid return status: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ReturnStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// State of a returned copy when it arrives at the warehouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReturnCondition {
+    /// Goes back on the shelf
+    Resellable,
+    /// Written off into the damaged bucket
+    Damaged,
+}
+
+impl ReturnCondition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Resellable => "resellable",
+            Self::Damaged => "damaged",
+        }
+    }
+}
+
+impl FromStr for ReturnCondition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "resellable" => Ok(Self::Resellable),
+            "damaged" => Ok(Self::Damaged),
+            _ => Err(anyhow!("Invalid return condition: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use anyhow::{anyhow, Result};
+use rand::{distr::Alphanumeric, Rng};
+
+const TOKEN_LENGTH: usize = 24;
+
+/// Unguessable token for reading a shared resource (e.g. a wishlist) without logging in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShareToken(String);
+
+impl ShareToken {
+    pub fn new(token: String) -> Result<Self> {
+        let trimmed = token.trim();
+        if trimmed.len() != TOKEN_LENGTH || !trimmed.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(anyhow!("Invalid share token"));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+
+    pub fn generate() -> Self {
+        let token: String = rand::rng()
+            .sample_iter(&Alphanumeric)
+            .take(TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+        Self(token)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
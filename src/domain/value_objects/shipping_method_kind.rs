@@ -0,0 +1,34 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// How an order gets to the customer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShippingMethodKind {
+    Standard,
+    Express,
+    /// Customer collects the parcel at a store; no address needed
+    StorePickup,
+}
+
+impl ShippingMethodKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Express => "express",
+            Self::StorePickup => "store_pickup",
+        }
+    }
+}
+
+impl FromStr for ShippingMethodKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "standard" => Ok(Self::Standard),
+            "express" => Ok(Self::Express),
+            "store_pickup" => Ok(Self::StorePickup),
+            _ => Err(anyhow!("Invalid shipping method kind: {}", s)),
+        }
+    }
+}
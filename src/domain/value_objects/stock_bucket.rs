@@ -0,0 +1,32 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// Which pile of stock an inventory movement affects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StockBucket {
+    /// Sellable stock (mirrored in `books.stock_quantity`)
+    Available,
+    /// Written-off copies that cannot be sold
+    Damaged,
+}
+
+impl StockBucket {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Available => "available",
+            Self::Damaged => "damaged",
+        }
+    }
+}
+
+impl FromStr for StockBucket {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "available" => Ok(Self::Available),
+            "damaged" => Ok(Self::Damaged),
+            _ => Err(anyhow!("Invalid stock bucket: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+/// Product class used to pick a tax rate within a jurisdiction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaxClass {
+    Standard,
+    PrintedBook,
+    Ebook,
+    Exempt,
+}
+
+impl TaxClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::PrintedBook => "printed_book",
+            Self::Ebook => "ebook",
+            Self::Exempt => "exempt",
+        }
+    }
+}
+
+impl FromStr for TaxClass {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "standard" => Ok(Self::Standard),
+            "printed_book" => Ok(Self::PrintedBook),
+            "ebook" => Ok(Self::Ebook),
+            "exempt" => Ok(Self::Exempt),
+            _ => Err(anyhow!("Invalid tax class: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Result};
+
+const TAX_ID_LENGTH: usize = 13;
+const BRANCH_LENGTH: usize = 5;
+const HEAD_OFFICE: &str = "00000";
+
+/// Thai taxpayer identification number (เลขประจำตัวผู้เสียภาษีอากร):
+/// 13 digits, the last one a mod-11 check digit. Individuals use their
+/// national ID number, which follows the same rule.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaxId(String);
+
+impl TaxId {
+    /// Accepts the printed form with dashes or spaces, e.g. `0-1055-12345-67-8`
+    pub fn new(tax_id: String) -> Result<Self> {
+        let digits: String = tax_id
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .collect();
+        if digits.len() != TAX_ID_LENGTH || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(anyhow!("Tax ID must have 13 digits"));
+        }
+
+        let values: Vec<u32> = digits.bytes().map(|b| (b - b'0') as u32).collect();
+        let sum: u32 = values[..12]
+            .iter()
+            .enumerate()
+            .map(|(i, d)| d * (13 - i as u32))
+            .sum();
+        if (11 - sum % 11) % 10 != values[12] {
+            return Err(anyhow!("Invalid tax ID"));
+        }
+
+        Ok(Self(digits))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Branch of a VAT-registered business as printed on tax invoices:
+/// `00000` is the head office (สำนักงานใหญ่), anything else a numbered branch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BranchCode(String);
+
+impl BranchCode {
+    /// Shorter numbers are zero-padded, so `1` is branch `00001`
+    pub fn new(code: String) -> Result<Self> {
+        let code = code.trim();
+        if code.is_empty() || code.len() > BRANCH_LENGTH || !code.chars().all(|c| c.is_ascii_digit()) {
+            return Err(anyhow!("Branch must be a number of up to 5 digits"));
+        }
+        Ok(Self(format!("{:0>width$}", code, width = BRANCH_LENGTH)))
+    }
+
+    pub fn head_office() -> Self {
+        Self(HEAD_OFFICE.to_string())
+    }
+
+    pub fn is_head_office(&self) -> bool {
+        self.0 == HEAD_OFFICE
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// How the Revenue Department expects it on the document
+    pub fn label(&self) -> String {
+        if self.is_head_office() {
+            "สำนักงานใหญ่ (Head office)".to_string()
+        } else {
+            format!("สาขาที่ {} (Branch {})", self.0, self.0)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Edge lengths, in pixels, every avatar is rendered at
+pub const AVATAR_SIZES: [u32; 3] = [64, 128, 256];
+
+/// One stored rendition of an avatar (square WebP)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AvatarVariant {
+    pub size: u32,
+    pub size_bytes: u64,
+    pub storage_key: String,
+    pub url: String,
+}
+
+/// Profile picture of a user. Kept on the user as JSON, so the field names
+/// are part of the stored format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserAvatar {
+    pub variants: Vec<AvatarVariant>,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl UserAvatar {
+    /// The largest rendition; clients scale it down for small frames
+    pub fn url(&self) -> Option<&str> {
+        self.variants
+            .iter()
+            .max_by_key(|v| v.size)
+            .map(|v| v.url.as_str())
+    }
+}
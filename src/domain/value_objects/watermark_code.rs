@@ -0,0 +1,32 @@
+use anyhow::{anyhow, Result};
+use rand::{distr::Alphanumeric, Rng};
+
+const CODE_LENGTH: usize = 20;
+
+/// Random identifier stamped into a customer's copy of a file, used to trace
+/// a leaked copy back to the order it was sold with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WatermarkCode(String);
+
+impl WatermarkCode {
+    pub fn new(code: String) -> Result<Self> {
+        let trimmed = code.trim();
+        if trimmed.len() != CODE_LENGTH || !trimmed.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(anyhow!("Invalid watermark code"));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+
+    pub fn generate() -> Self {
+        let code: String = rand::rng()
+            .sample_iter(&Alphanumeric)
+            .take(CODE_LENGTH)
+            .map(char::from)
+            .collect();
+        Self(code)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
@@ -23,6 +23,12 @@ impl Argon2PasswordHasher {
     }
 }
 
+impl Default for Argon2PasswordHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl PasswordService for Argon2PasswordHasher {
     async fn hash_password(&self, password: &str) -> anyhow::Result<String> {
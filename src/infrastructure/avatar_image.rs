@@ -0,0 +1,34 @@
+use anyhow::Result;
+
+use crate::domain::value_objects::user_avatar::AVATAR_SIZES;
+use crate::infrastructure::{raster, uploaded_image};
+
+const WEBP_QUALITY: u8 = 80;
+
+pub struct RenderedAvatar {
+    /// Edge length in pixels
+    pub size: u32,
+    pub content: Vec<u8>,
+}
+
+/// Decodes the upload, crops the centre square and renders it at every
+/// avatar size. Pictures smaller than a size are enlarged to it, so every
+/// URL has the size its name promises. CPU heavy; run it off the async
+/// workers.
+pub fn render(data: &[u8], max_pixels: u64) -> Result<Vec<RenderedAvatar>> {
+    let square = raster::center_square(&uploaded_image::decode_upright(data, max_pixels)?);
+
+    // ย่อจากขนาดใหญ่ลงมาทีละขั้นเหมือนปกหนังสือ
+    let mut source = square;
+    let mut avatars = Vec::with_capacity(AVATAR_SIZES.len());
+    for &size in AVATAR_SIZES.iter().rev() {
+        let resized = raster::resized(&source, size, size);
+        avatars.push(RenderedAvatar {
+            size,
+            content: raster::encode_webp(&resized, WEBP_QUALITY)?,
+        });
+        source = resized;
+    }
+    avatars.reverse();
+    Ok(avatars)
+}
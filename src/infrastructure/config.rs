@@ -1,5 +1,11 @@
 use anyhow::{bail, Context, Result};
-use std::{env, str::FromStr};
+use std::{env, path::PathBuf, str::FromStr};
+
+use crate::domain::{
+    entities::invoice::InvoiceParty,
+    value_objects::tax_id::{BranchCode, TaxId},
+};
+use crate::infrastructure::s3_file_storage::S3Settings;
 
 // Configuration Models
 #[derive(Debug, Clone)]
@@ -7,6 +13,13 @@ pub struct AppConfig {
     pub server: Server,
     pub database: Database,
     pub jwt: JwtConfig,
+    pub payment: PaymentConfig,
+    pub search: SearchConfig,
+    pub storage: StorageConfig,
+    pub download: DownloadConfig,
+    pub invoice: InvoiceConfig,
+    pub catalog_import: CatalogImportConfig,
+    pub marc: MarcConfig,
     pub environment: Environment,
 }
 
@@ -15,6 +28,13 @@ impl AppConfig {
         self.server.validate()?;
         self.database.validate()?;
         self.jwt.validate()?;
+        self.payment.validate()?;
+        self.search.validate()?;
+        self.storage.validate()?;
+        self.download.validate()?;
+        self.invoice.validate()?;
+        self.catalog_import.validate()?;
+        self.marc.validate()?;
 
         // cross-field validation
         if self.jwt.access_token_expiry_minutes > 60 * 24 {
@@ -79,6 +99,194 @@ impl JwtConfig {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct PaymentConfig {
+    pub webhook_secret: String,
+}
+
+impl PaymentConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.webhook_secret.len() < 32 {
+            bail!("PAYMENT_WEBHOOK_SECRET must be at least 32 characters");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    /// Directory of the local search index
+    pub index_dir: PathBuf,
+    /// Optional synonym groups, one comma-separated group per line
+    pub synonyms_file: Option<PathBuf>,
+}
+
+impl SearchConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.index_dir.as_os_str().is_empty() {
+            bail!("SEARCH_INDEX_DIR cannot be empty");
+        }
+        if let Some(file) = self.synonyms_file.as_ref().filter(|f| !f.is_file()) {
+            bail!("SEARCH_SYNONYMS_FILE not found: {}", file.display());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    /// Root directory of the local file storage
+    pub local_root: PathBuf,
+    /// Origin public files (covers, avatars) are served from, e.g. a CDN in
+    /// front of the bucket; object keys are appended to it
+    pub public_base_url: String,
+    /// Required when `backend` is S3
+    pub s3: Option<S3Settings>,
+}
+
+impl StorageConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.local_root.as_os_str().is_empty() {
+            bail!("STORAGE_LOCAL_ROOT cannot be empty");
+        }
+        if !self.public_base_url.starts_with("http://") && !self.public_base_url.starts_with("https://") {
+            bail!("STORAGE_PUBLIC_BASE_URL must start with http:// or https://");
+        }
+        if self.backend == StorageBackend::S3 {
+            let Some(s3) = &self.s3 else {
+                bail!("S3_ENDPOINT, S3_BUCKET, S3_ACCESS_KEY_ID and S3_SECRET_ACCESS_KEY are required for STORAGE_BACKEND=s3");
+            };
+            if !s3.endpoint.starts_with("http://") && !s3.endpoint.starts_with("https://") {
+                bail!("S3_ENDPOINT must start with http:// or https://");
+            }
+            if s3.bucket.is_empty() || s3.access_key_id.is_empty() || s3.secret_access_key.is_empty() {
+                bail!("S3_BUCKET, S3_ACCESS_KEY_ID and S3_SECRET_ACCESS_KEY cannot be empty");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StorageBackend {
+    Local,
+    S3,
+}
+
+impl FromStr for StorageBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "s3" => Ok(Self::S3),
+            _ => bail!("Invalid STORAGE_BACKEND value: {}", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadConfig {
+    /// Public origin download links are built on, e.g. https://shop.example.com
+    pub base_url: String,
+    pub signing_secret: String,
+    pub link_ttl_minutes: i64,
+}
+
+impl DownloadConfig {
+    pub fn validate(&self) -> Result<()> {
+        if !self.base_url.starts_with("http://") && !self.base_url.starts_with("https://") {
+            bail!("DOWNLOAD_BASE_URL must start with http:// or https://");
+        }
+        if self.signing_secret.len() < 32 {
+            bail!("DOWNLOAD_SIGNING_SECRET must be at least 32 characters");
+        }
+        if !(1..=60 * 24).contains(&self.link_ttl_minutes) {
+            bail!("DOWNLOAD_LINK_TTL_MINUTES must be between 1 and 1440");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvoiceConfig {
+    /// TrueType font with Thai glyphs embedded in every document, e.g. Sarabun
+    pub font_path: PathBuf,
+    /// Bold face for headings; the regular face is used when unset
+    pub bold_font_path: Option<PathBuf>,
+    /// Our registered name, tax ID, branch and address as printed on invoices
+    pub seller_name: String,
+    pub seller_tax_id: String,
+    pub seller_branch: String,
+    pub seller_address: String,
+}
+
+impl InvoiceConfig {
+    pub fn validate(&self) -> Result<()> {
+        if !self.font_path.is_file() {
+            bail!("INVOICE_FONT_PATH not found: {}", self.font_path.display());
+        }
+        if let Some(file) = self.bold_font_path.as_ref().filter(|f| !f.is_file()) {
+            bail!("INVOICE_BOLD_FONT_PATH not found: {}", file.display());
+        }
+        if self.seller_name.trim().is_empty() {
+            bail!("INVOICE_SELLER_NAME cannot be empty");
+        }
+        if TaxId::new(self.seller_tax_id.clone()).is_err() {
+            bail!("INVOICE_SELLER_TAX_ID must be a valid 13-digit tax ID");
+        }
+        if BranchCode::new(self.seller_branch.clone()).is_err() {
+            bail!("INVOICE_SELLER_BRANCH must be a number of up to 5 digits");
+        }
+        if self.seller_address.trim().is_empty() {
+            bail!("INVOICE_SELLER_ADDRESS cannot be empty");
+        }
+        Ok(())
+    }
+
+    /// The seller block printed on every invoice
+    pub fn seller(&self) -> Result<InvoiceParty> {
+        InvoiceParty::new(
+            self.seller_name.clone(),
+            TaxId::new(self.seller_tax_id.clone())?,
+            BranchCode::new(self.seller_branch.clone())?,
+            self.seller_address.clone(),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CatalogImportConfig {
+    /// Where supplier feeds are dropped (e.g. by SFTP) before an import is started
+    pub inbox_dir: PathBuf,
+}
+
+impl CatalogImportConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.inbox_dir.as_os_str().is_empty() {
+            bail!("CATALOG_IMPORT_DIR cannot be empty");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MarcConfig {
+    /// MARC organization code written to 003 and 040 of exported records
+    pub organization_code: String,
+}
+
+impl MarcConfig {
+    pub fn validate(&self) -> Result<()> {
+        let code = &self.organization_code;
+        if code.is_empty() || code.len() > 16 || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            bail!("MARC_ORGANIZATION_CODE must be 1-16 letters, digits or '-'");
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Environment {
     Development,
@@ -120,6 +328,95 @@ pub fn load() -> Result<AppConfig> {
         refresh_secret: env::var("JWT_REFRESH_SECRET").context("JWT_REFRESH_SECRET is required")?,
     };
 
+    let payment = PaymentConfig {
+        webhook_secret: env::var("PAYMENT_WEBHOOK_SECRET")
+            .context("PAYMENT_WEBHOOK_SECRET is required")?,
+    };
+
+    let search = SearchConfig {
+        index_dir: env::var("SEARCH_INDEX_DIR")
+            .unwrap_or_else(|_| "./data/search-index".to_string())
+            .into(),
+        synonyms_file: env::var("SEARCH_SYNONYMS_FILE")
+            .ok()
+            .filter(|f| !f.trim().is_empty())
+            .map(PathBuf::from),
+    };
+
+    let s3 = match (
+        env::var("S3_ENDPOINT"),
+        env::var("S3_BUCKET"),
+        env::var("S3_ACCESS_KEY_ID"),
+        env::var("S3_SECRET_ACCESS_KEY"),
+    ) {
+        (Ok(endpoint), Ok(bucket), Ok(access_key_id), Ok(secret_access_key)) => Some(S3Settings {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            bucket,
+            access_key_id,
+            secret_access_key,
+            path_style: env::var("S3_PATH_STYLE")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .context("S3_PATH_STYLE must be true or false")?,
+        }),
+        _ => None,
+    };
+    let storage = StorageConfig {
+        backend: env::var("STORAGE_BACKEND")
+            .unwrap_or_else(|_| "local".to_string())
+            .parse()?,
+        local_root: env::var("STORAGE_LOCAL_ROOT")
+            .unwrap_or_else(|_| "./data/storage".to_string())
+            .into(),
+        public_base_url: env::var("STORAGE_PUBLIC_BASE_URL")
+            .unwrap_or_else(|_| "http://localhost:8080/files".to_string())
+            .trim_end_matches('/')
+            .to_string(),
+        s3,
+    };
+
+    let download = DownloadConfig {
+        base_url: env::var("DOWNLOAD_BASE_URL")
+            .unwrap_or_else(|_| "http://localhost:8080".to_string())
+            .trim_end_matches('/')
+            .to_string(),
+        signing_secret: env::var("DOWNLOAD_SIGNING_SECRET")
+            .context("DOWNLOAD_SIGNING_SECRET is required")?,
+        link_ttl_minutes: env::var("DOWNLOAD_LINK_TTL_MINUTES")
+            .unwrap_or_else(|_| "15".to_string())
+            .parse()
+            .context("DOWNLOAD_LINK_TTL_MINUTES must be a number")?,
+    };
+
+    let invoice = InvoiceConfig {
+        font_path: env::var("INVOICE_FONT_PATH")
+            .unwrap_or_else(|_| "./assets/fonts/Sarabun-Regular.ttf".to_string())
+            .into(),
+        bold_font_path: env::var("INVOICE_BOLD_FONT_PATH")
+            .ok()
+            .filter(|f| !f.trim().is_empty())
+            .map(PathBuf::from),
+        seller_name: env::var("INVOICE_SELLER_NAME").context("INVOICE_SELLER_NAME is required")?,
+        seller_tax_id: env::var("INVOICE_SELLER_TAX_ID").context("INVOICE_SELLER_TAX_ID is required")?,
+        seller_branch: env::var("INVOICE_SELLER_BRANCH").unwrap_or_else(|_| "00000".to_string()),
+        seller_address: env::var("INVOICE_SELLER_ADDRESS")
+            .context("INVOICE_SELLER_ADDRESS is required")?,
+    };
+
+    let catalog_import = CatalogImportConfig {
+        inbox_dir: env::var("CATALOG_IMPORT_DIR")
+            .unwrap_or_else(|_| "./data/imports".to_string())
+            .into(),
+    };
+
+    let marc = MarcConfig {
+        organization_code: env::var("MARC_ORGANIZATION_CODE")
+            .unwrap_or_else(|_| "BOOKSTORE".to_string())
+            .trim()
+            .to_string(),
+    };
+
     let environment = env::var("ENVIRONMENT")?
         .parse::<Environment>()?;
 
@@ -127,6 +424,13 @@ pub fn load() -> Result<AppConfig> {
         server,
         database,
         jwt,
+        payment,
+        search,
+        storage,
+        download,
+        invoice,
+        catalog_import,
+        marc,
         environment,
     };
 
@@ -0,0 +1,123 @@
+use anyhow::Result;
+
+use crate::domain::value_objects::book_cover::{CoverFormat, CoverSize};
+use crate::infrastructure::{raster, uploaded_image};
+
+const JPEG_QUALITY: u8 = 85;
+const WEBP_QUALITY: u8 = 80;
+/// BlurHash is worked out on a tiny copy; more detail would be blurred away
+const BLURHASH_EDGE: u32 = 32;
+
+/// An uploaded cover decoded and rendered in every size and format
+pub struct RenderedCover {
+    pub width: u32,
+    pub height: u32,
+    pub blurhash: String,
+    pub variants: Vec<RenderedVariant>,
+}
+
+pub struct RenderedVariant {
+    pub size: CoverSize,
+    pub format: CoverFormat,
+    pub width: u32,
+    pub height: u32,
+    pub content: Vec<u8>,
+}
+
+/// Decodes the upload, turns it upright and renders every variant. The
+/// variants are encoded from pixels only, so EXIF and any other metadata
+/// never reach storage. CPU heavy; run it off the async workers.
+pub fn render(data: &[u8], max_pixels: u64) -> Result<RenderedCover> {
+    let image = uploaded_image::decode_upright(data, max_pixels)?;
+
+    // ย่อจากขนาดใหญ่ลงมาทีละขั้น ไม่ต้องกรองภาพต้นฉบับเต็มๆ ทุกขนาด
+    let mut variants = Vec::with_capacity(CoverSize::ALL.len() * CoverFormat::ALL.len());
+    let mut source = image.clone();
+    for size in CoverSize::ALL.iter().rev() {
+        let (width, height) = raster::fit_within(&source, size.max_edge());
+        let resized = raster::resized(&source, width, height);
+        for format in CoverFormat::ALL {
+            let content = match format {
+                CoverFormat::Webp => raster::encode_webp(&resized, WEBP_QUALITY)?,
+                CoverFormat::Jpeg => raster::encode_jpeg(&resized, JPEG_QUALITY)?,
+            };
+            variants.push(RenderedVariant {
+                size: *size,
+                format,
+                width,
+                height,
+                content,
+            });
+        }
+        source = resized;
+    }
+    variants.sort_by_key(|v| v.size);
+
+    // source ตอนนี้คือ thumbnail
+    let (width, height) = raster::fit_within(&source, BLURHASH_EDGE);
+    let (x_components, y_components) = if image.width() >= image.height() { (4, 3) } else { (3, 4) };
+    let blurhash = raster::blurhash(&raster::resized(&source, width, height), x_components, y_components)?;
+
+    Ok(RenderedCover {
+        width: image.width(),
+        height: image.height(),
+        blurhash,
+        variants,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_fn(width, height, |x, y| Rgba([(x % 256) as u8, (y % 256) as u8, 90, 255]));
+        let mut out = Vec::new();
+        image.write_to(&mut Cursor::new(&mut out), ImageFormat::Png).unwrap();
+        out
+    }
+
+    #[test]
+    fn renders_every_variant_that_decodes_back_at_its_size() {
+        let cover = render(&png(800, 1200), 10_000_000).unwrap();
+        assert_eq!((cover.width, cover.height), (800, 1200));
+        assert_eq!(cover.blurhash.len(), 6 + 2 * (3 * 4 - 1));
+        assert_eq!(cover.variants.len(), CoverSize::ALL.len() * CoverFormat::ALL.len());
+
+        for variant in &cover.variants {
+            let expected = match variant.size {
+                CoverSize::Thumbnail => (133, 200),
+                CoverSize::Medium => (400, 600),
+                CoverSize::Large => (800, 1200),
+            };
+            assert_eq!((variant.width, variant.height), expected);
+
+            let format = match variant.format {
+                CoverFormat::Webp => ImageFormat::WebP,
+                CoverFormat::Jpeg => ImageFormat::Jpeg,
+            };
+            let decoded = image::load_from_memory_with_format(&variant.content, format).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), expected);
+        }
+    }
+
+    #[test]
+    fn variants_carry_no_exif() {
+        let mut jpeg = Vec::new();
+        image::RgbImage::from_pixel(300, 300, image::Rgb([10, 20, 30]))
+            .write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg)
+            .unwrap();
+        // APP1 ที่มี TIFF block ว่างๆ พอให้ต้นฉบับมี EXIF
+        let app1 = b"\xFF\xE1\x00\x16Exif\0\0II*\0\x08\0\0\0\0\0\0\0\0\0";
+        let upload = [&jpeg[..2], &app1[..], &jpeg[2..]].concat();
+        assert!(upload.windows(4).any(|w| w == b"Exif"));
+
+        let cover = render(&upload, 10_000_000).unwrap();
+        for variant in cover.variants {
+            let has_exif = variant.content.windows(4).any(|w| w == b"Exif" || w == b"EXIF");
+            assert!(!has_exif, "{:?} {:?} has EXIF", variant.size, variant.format);
+        }
+    }
+}
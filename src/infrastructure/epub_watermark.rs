@@ -0,0 +1,368 @@
+use std::io::{Read, Seek, Write};
+
+use anyhow::{anyhow, Context, Result};
+use quick_xml::{escape::escape, events::Event, Reader};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+/// `<meta name=...>` entries written into the OPF
+pub const META_WATERMARK: &str = "shop:watermark";
+pub const META_LICENSEE: &str = "shop:licensee";
+
+const CONTAINER_PATH: &str = "META-INF/container.xml";
+
+/// What gets stamped into a copy
+#[derive(Debug, Clone)]
+pub struct WatermarkStamp {
+    /// Identifier looked up when a leaked file is found
+    pub code: String,
+    pub licensee: String,
+    /// Shown to the reader, e.g. `order #1024`
+    pub order_reference: String,
+}
+
+/// Copies an EPUB entry by entry, stamping the OPF metadata (identifier,
+/// licensee, order) and adding a visible line to the colophon page.
+///
+/// Untouched entries are copied raw, without recompressing, so `mimetype`
+/// stays first and stored as the EPUB spec requires.
+pub fn stamp<R, W>(source: R, output: W, stamp: &WatermarkStamp) -> Result<W>
+where
+    R: Read + Seek,
+    W: Write + Seek,
+{
+    let mut archive = ZipArchive::new(source).context("Not a valid EPUB (zip) file")?;
+
+    let opf_path = find_opf_path(&mut archive)?;
+    let opf = read_text(&mut archive, &opf_path)?;
+    let colophon_path = find_colophon_path(&opf, &opf_path)?;
+
+    let stamped_opf = stamp_opf(&opf, stamp)?;
+    let stamped_colophon = stamp_colophon(&read_text(&mut archive, &colophon_path)?, stamp)?;
+
+    let mut writer = ZipWriter::new(output);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        let replacement = if entry.name() == opf_path {
+            Some(&stamped_opf)
+        } else if entry.name() == colophon_path {
+            Some(&stamped_colophon)
+        } else {
+            None
+        };
+
+        match replacement {
+            Some(content) => {
+                let name = entry.name().to_string();
+                drop(entry);
+                writer.start_file(name, options)?;
+                writer.write_all(content.as_bytes())?;
+            }
+            None => writer.raw_copy_file(entry)?,
+        }
+    }
+
+    Ok(writer.finish()?)
+}
+
+/// Reads the watermark identifier back out of a (possibly leaked) EPUB.
+pub fn read_code<R: Read + Seek>(source: R) -> Result<Option<String>> {
+    let mut archive = ZipArchive::new(source).context("Not a valid EPUB (zip) file")?;
+    let opf_path = find_opf_path(&mut archive)?;
+    let opf = read_text(&mut archive, &opf_path)?;
+
+    let mut reader = Reader::from_str(&opf);
+    loop {
+        match reader.read_event()? {
+            Event::Empty(e) | Event::Start(e) if e.local_name().as_ref() == b"meta" => {
+                let name = e.try_get_attribute("name")?;
+                if name.is_some_and(|n| n.value.as_ref() == META_WATERMARK.as_bytes()) {
+                    return Ok(e
+                        .try_get_attribute("content")?
+                        .map(|c| c.unescape_value().map(|v| v.into_owned()))
+                        .transpose()?);
+                }
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+fn read_text<R: Read + Seek>(archive: &mut ZipArchive<R>, path: &str) -> Result<String> {
+    let mut content = String::new();
+    archive
+        .by_name(path)
+        .with_context(|| format!("EPUB entry not found: {}", path))?
+        .read_to_string(&mut content)
+        .with_context(|| format!("EPUB entry is not UTF-8: {}", path))?;
+    Ok(content)
+}
+
+/// Package document path, from `META-INF/container.xml`
+fn find_opf_path<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<String> {
+    let container = read_text(archive, CONTAINER_PATH)?;
+    let mut reader = Reader::from_str(&container);
+    loop {
+        match reader.read_event()? {
+            Event::Empty(e) | Event::Start(e) if e.local_name().as_ref() == b"rootfile" => {
+                if let Some(path) = e.try_get_attribute("full-path")? {
+                    return Ok(path.unescape_value()?.into_owned());
+                }
+            }
+            Event::Eof => return Err(anyhow!("EPUB has no package document")),
+            _ => {}
+        }
+    }
+}
+
+/// Picks the page to stamp: the manifest item or guide reference marked as
+/// colophon/copyright page, else the last document in the spine.
+fn find_colophon_path(opf: &str, opf_path: &str) -> Result<String> {
+    let mut documents: Vec<(String, String)> = Vec::new();
+    let mut spine: Vec<String> = Vec::new();
+    let mut guide: Option<String> = None;
+
+    let mut reader = Reader::from_str(opf);
+    loop {
+        match reader.read_event()? {
+            Event::Empty(e) | Event::Start(e) => {
+                let attr = |name: &str| -> Result<Option<String>> {
+                    Ok(e.try_get_attribute(name)?
+                        .map(|a| a.unescape_value().map(|v| v.into_owned()))
+                        .transpose()?)
+                };
+                match e.local_name().as_ref() {
+                    b"item" => {
+                        let is_xhtml = attr("media-type")?.as_deref() == Some("application/xhtml+xml");
+                        if let (true, Some(id), Some(href)) = (is_xhtml, attr("id")?, attr("href")?) {
+                            documents.push((id, href));
+                        }
+                    }
+                    b"itemref" => spine.extend(attr("idref")?),
+                    b"reference" => {
+                        let kind = attr("type")?.unwrap_or_default().to_lowercase();
+                        if matches!(kind.as_str(), "colophon" | "copyright-page") {
+                            guide = attr("href")?;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let named = |needle: &str| {
+        documents
+            .iter()
+            .find(|(id, href)| {
+                id.to_lowercase().contains(needle) || href.to_lowercase().contains(needle)
+            })
+            .map(|(_, href)| href.clone())
+    };
+    let href = named("colophon")
+        .or_else(|| named("copyright"))
+        .or(guide)
+        .or_else(|| {
+            spine
+                .iter()
+                .rev()
+                .find_map(|idref| documents.iter().find(|(id, _)| id == idref))
+                .map(|(_, href)| href.clone())
+        })
+        .ok_or_else(|| anyhow!("EPUB has no content document to stamp"))?;
+
+    resolve_href(opf_path, &href)
+}
+
+/// Zip entry path of a manifest href: drops the `#fragment`, percent-decodes
+/// and resolves `.`/`..` against the OPF's folder.
+fn resolve_href(opf_path: &str, href: &str) -> Result<String> {
+    let href = percent_decode(href.split('#').next().unwrap_or_default())?;
+    let mut segments: Vec<&str> = match opf_path.rsplit_once('/') {
+        Some((dir, _)) => dir.split('/').collect(),
+        None => Vec::new(),
+    };
+    for segment in href.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments
+                    .pop()
+                    .ok_or_else(|| anyhow!("EPUB href points outside the archive: {}", href))?;
+            }
+            name => segments.push(name),
+        }
+    }
+    Ok(segments.join("/"))
+}
+
+/// `%XX` escapes to bytes; a `%` not followed by two hex digits is kept as is
+fn percent_decode(text: &str) -> Result<String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).with_context(|| format!("EPUB href is not valid UTF-8: {}", text))
+}
+
+/// Byte offset of the closing tag `</local_name>`
+fn find_end_tag(xml: &str, local_name: &[u8]) -> Result<usize> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        let position = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::End(e) if e.local_name().as_ref() == local_name => return Ok(position),
+            Event::Eof => {
+                return Err(anyhow!(
+                    "Closing </{}> not found",
+                    String::from_utf8_lossy(local_name)
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Inserts before the closing tag; the rest of the document is kept byte for byte
+fn insert_before_end_tag(xml: &str, local_name: &[u8], snippet: &str) -> Result<String> {
+    let at = find_end_tag(xml, local_name)?;
+    Ok(format!("{}{}{}", &xml[..at], snippet, &xml[at..]))
+}
+
+fn stamp_opf(opf: &str, stamp: &WatermarkStamp) -> Result<String> {
+    let snippet = format!(
+        "  <meta name=\"{}\" content=\"{}\"/>\n    <meta name=\"{}\" content=\"{} ({})\"/>\n  ",
+        META_WATERMARK,
+        escape(stamp.code.as_str()),
+        META_LICENSEE,
+        escape(stamp.licensee.as_str()),
+        escape(stamp.order_reference.as_str()),
+    );
+    insert_before_end_tag(opf, b"metadata", &snippet)
+}
+
+fn stamp_colophon(page: &str, stamp: &WatermarkStamp) -> Result<String> {
+    let snippet = format!(
+        "<p class=\"watermark\" id=\"wm-{}\">This e-book is licensed to {} ({}) for personal use.</p>\n",
+        escape(stamp.code.as_str()),
+        escape(stamp.licensee.as_str()),
+        escape(stamp.order_reference.as_str()),
+    );
+    insert_before_end_tag(page, b"body", &snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const OPF_PATH: &str = "OEBPS/content/package.opf";
+    const COLOPHON_PATH: &str = "OEBPS/text/colophon page.xhtml";
+
+    fn page(body: &str) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+             <html xmlns=\"http://www.w3.org/1999/xhtml\"><body>{}</body></html>",
+            body
+        )
+    }
+
+    /// Minimal EPUB whose OPF sits in a subfolder and points at the colophon
+    /// with `../` and a percent-escaped space
+    fn sample_epub() -> Vec<u8> {
+        let container = format!(
+            "<?xml version=\"1.0\"?>\n\
+             <container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">\
+             <rootfiles><rootfile full-path=\"{}\" media-type=\"application/oebps-package+xml\"/></rootfiles>\
+             </container>",
+            OPF_PATH
+        );
+        let opf = "<?xml version=\"1.0\"?>\n\
+            <package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\">\n\
+            <metadata><dc:title xmlns:dc=\"http://purl.org/dc/elements/1.1/\">Test</dc:title></metadata>\n\
+            <manifest>\
+            <item id=\"ch1\" href=\"../text/ch1.xhtml\" media-type=\"application/xhtml+xml\"/>\
+            <item id=\"colophon\" href=\"../text/colophon%20page.xhtml#end\" media-type=\"application/xhtml+xml\"/>\
+            </manifest>\n\
+            <spine><itemref idref=\"ch1\"/><itemref idref=\"colophon\"/></spine>\n\
+            </package>";
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        writer.start_file("mimetype", stored).unwrap();
+        writer.write_all(b"application/epub+zip").unwrap();
+        for (name, content) in [
+            (CONTAINER_PATH, container),
+            (OPF_PATH, opf.to_string()),
+            ("OEBPS/text/ch1.xhtml", page("<p>Chapter one</p>")),
+            (COLOPHON_PATH, page("<p>Printed in Bangkok</p>")),
+        ] {
+            writer.start_file(name, deflated).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn stamped_copy_round_trips() {
+        let watermark = WatermarkStamp {
+            code: "WM-7F3A".to_string(),
+            licensee: "สมชาย & Co".to_string(),
+            order_reference: "order #1024".to_string(),
+        };
+        let stamped = stamp(Cursor::new(sample_epub()), Cursor::new(Vec::new()), &watermark)
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(read_code(Cursor::new(&stamped)).unwrap().as_deref(), Some("WM-7F3A"));
+
+        let mut archive = ZipArchive::new(Cursor::new(&stamped)).unwrap();
+        let opf = read_text(&mut archive, OPF_PATH).unwrap();
+        assert!(opf.contains(r#"<meta name="shop:licensee" content="สมชาย &amp; Co (order #1024)"/>"#));
+
+        let colophon = read_text(&mut archive, COLOPHON_PATH).unwrap();
+        assert!(colophon.contains(
+            "This e-book is licensed to สมชาย &amp; Co (order #1024) for personal use.</p>\n</body>"
+        ));
+        assert!(!read_text(&mut archive, "OEBPS/text/ch1.xhtml").unwrap().contains("licensed"));
+
+        // mimetype ต้องเป็นไฟล์แรกและไม่บีบอัด
+        let first = archive.by_index(0).unwrap();
+        assert_eq!(first.name(), "mimetype");
+        assert_eq!(first.compression(), CompressionMethod::Stored);
+    }
+
+    #[test]
+    fn unstamped_epub_has_no_code() {
+        assert_eq!(read_code(Cursor::new(sample_epub())).unwrap(), None);
+    }
+
+    #[test]
+    fn resolves_hrefs_against_opf_folder() {
+        assert_eq!(resolve_href(OPF_PATH, "../text/a%20b.xhtml#p1").unwrap(), "OEBPS/text/a b.xhtml");
+        assert_eq!(resolve_href(OPF_PATH, "./c.xhtml").unwrap(), "OEBPS/content/c.xhtml");
+        assert_eq!(resolve_href("package.opf", "text/%E0%B8%81.xhtml").unwrap(), "text/ก.xhtml");
+        assert_eq!(resolve_href("package.opf", "100%.xhtml").unwrap(), "100%.xhtml");
+        assert!(resolve_href(OPF_PATH, "../../../x.xhtml").is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+
+use crate::domain::value_objects::byte_range::ByteRange;
+use crate::infrastructure::{
+    config::{StorageBackend, StorageConfig},
+    local_file_storage::LocalFileStorage,
+    s3_file_storage::S3FileStorage,
+};
+
+/// Object content as it is read, chunk by chunk
+pub type ByteStream = BoxStream<'static, std::io::Result<Bytes>>;
+
+/// Port to blob storage for uploaded files (digital editions, ...).
+///
+/// Keys are relative, `/`-separated paths such as `books/12/epub-ab12.epub`.
+#[async_trait]
+pub trait FileStorage: Send + Sync {
+    /// Backend name, for logs
+    fn backend(&self) -> &'static str;
+    /// Stores `content` under `key`, replacing any existing object
+    async fn put(&self, key: &str, content: &[u8], content_type: &str) -> Result<()>;
+    /// Like `put`, streaming the whole of `file` instead of holding it in memory
+    async fn put_file(&self, key: &str, file: tokio::fs::File, content_type: &str) -> Result<()>;
+    /// Streams the object, or only `range` of it. A missing object or a
+    /// range past its end fails here, before any chunk is read.
+    async fn open(&self, key: &str, range: Option<ByteRange>) -> Result<ByteStream>;
+    /// Size in bytes; `None` when the object does not exist
+    async fn size(&self, key: &str) -> Result<Option<u64>>;
+    /// Deleting a missing object is not an error
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// The backend selected by STORAGE_BACKEND
+pub fn from_config(config: &StorageConfig) -> Result<Arc<dyn FileStorage>> {
+    match config.backend {
+        StorageBackend::Local => Ok(Arc::new(LocalFileStorage::new(config.local_root.clone()))),
+        StorageBackend::S3 => {
+            let settings = config
+                .s3
+                .clone()
+                .ok_or_else(|| anyhow!("S3 storage is not configured"))?;
+            Ok(Arc::new(S3FileStorage::new(settings)?))
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+use printpdf::{
+    IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point,
+};
+use ttf_parser::Face;
+
+use crate::domain::entities::invoice::{InvoiceEntity, InvoiceKind, InvoiceParty};
+
+// A4 portrait, in millimetres from the bottom left corner
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const LEFT: f32 = 15.0;
+const RIGHT: f32 = 195.0;
+const TOP: f32 = 282.0;
+/// Rows stop here on every page but the last, which also needs the totals
+const ROWS_BOTTOM: f32 = 30.0;
+const TOTALS_HEIGHT: f32 = 50.0;
+
+const COL_DESCRIPTION: f32 = 24.0;
+const DESCRIPTION_WIDTH: f32 = 96.0;
+const COL_QUANTITY_RIGHT: f32 = 136.0;
+const COL_UNIT_PRICE_RIGHT: f32 = 165.0;
+
+const TEXT_SIZE: f32 = 9.0;
+const LINE_HEIGHT: f32 = 4.6;
+const PT_TO_MM: f32 = 25.4 / 72.0;
+
+/// Renders tax invoices and credit notes as PDF, embedding a TrueType font
+/// that covers Thai (e.g. Sarabun).
+///
+/// printpdf places glyphs without shaping, which is enough for Thai fonts
+/// whose vowel and tone marks carry their own offsets, as most do.
+pub struct InvoicePdfRenderer {
+    regular: Vec<u8>,
+    bold: Option<Vec<u8>>,
+}
+
+impl InvoicePdfRenderer {
+    pub fn from_files(regular: &Path, bold: Option<&Path>) -> Result<Self> {
+        let regular = load_font(regular)?;
+        let bold = bold.map(load_font).transpose()?;
+        Ok(Self { regular, bold })
+    }
+
+    pub fn render(&self, invoice: &InvoiceEntity) -> Result<Vec<u8>> {
+        let (doc, page, layer) =
+            PdfDocument::new(invoice.number.as_str(), Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let regular = doc
+            .add_external_font(self.regular.as_slice())
+            .map_err(|e| anyhow!("Failed to embed font: {:?}", e))?;
+        let bold = match &self.bold {
+            Some(font) => doc
+                .add_external_font(font.as_slice())
+                .map_err(|e| anyhow!("Failed to embed bold font: {:?}", e))?,
+            None => regular.clone(),
+        };
+
+        let mut canvas = Canvas {
+            doc: &doc,
+            pages: vec![doc.get_page(page).get_layer(layer)],
+            regular: Typeface::new(regular, &self.regular)?,
+            bold: Typeface::new(bold, self.bold.as_deref().unwrap_or(&self.regular))?,
+            y: TOP,
+        };
+
+        draw_heading(&mut canvas, invoice);
+        draw_rows(&mut canvas, invoice);
+        draw_totals(&mut canvas, invoice);
+        draw_page_numbers(&canvas, invoice);
+
+        doc.save_to_bytes()
+            .map_err(|e| anyhow!("Failed to write PDF: {:?}", e))
+    }
+}
+
+fn load_font(path: &Path) -> Result<Vec<u8>> {
+    let data = fs::read(path).with_context(|| format!("Failed to read font {}", path.display()))?;
+    let face = Face::parse(&data, 0).map_err(|e| anyhow!("Invalid font {}: {}", path.display(), e))?;
+    if face.glyph_index('ก').is_none() {
+        return Err(anyhow!("Font {} has no Thai glyphs", path.display()));
+    }
+    Ok(data)
+}
+
+/// An embedded font and the metrics to measure text set in it
+struct Typeface<'a> {
+    font: IndirectFontRef,
+    face: Face<'a>,
+}
+
+impl<'a> Typeface<'a> {
+    fn new(font: IndirectFontRef, data: &'a [u8]) -> Result<Self> {
+        let face = Face::parse(data, 0).map_err(|e| anyhow!("Invalid font: {}", e))?;
+        Ok(Self { font, face })
+    }
+
+    /// Width of `text` at `size` points, in millimetres
+    fn width(&self, text: &str, size: f32) -> f32 {
+        let units: u32 = text
+            .chars()
+            .filter_map(|c| self.face.glyph_index(c))
+            .filter_map(|g| self.face.glyph_hor_advance(g))
+            .map(u32::from)
+            .sum();
+        units as f32 / self.face.units_per_em() as f32 * size * PT_TO_MM
+    }
+
+    /// Splits `text` into lines no wider than `width`. Breaks at spaces when
+    /// it can; Thai has none between words, so long runs break between
+    /// characters, never before a vowel or tone mark.
+    fn wrap(&self, text: &str, size: f32, width: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut line = String::new();
+
+        for c in text.chars() {
+            line.push(c);
+            if self.width(&line, size) <= width || is_thai_mark(c) {
+                continue;
+            }
+
+            // c ไม่ใช่สระบน/ล่างหรือวรรณยุกต์ ตัดก่อน c ได้โดยไม่แยกออกจากพยัญชนะ
+            let break_at = match line.trim_end().rfind(' ') {
+                Some(space) if space > 0 => space + 1,
+                _ => line.len() - c.len_utf8(),
+            };
+            if break_at == 0 {
+                continue;
+            }
+            let rest = line.split_off(break_at);
+            lines.push(line.trim_end().to_string());
+            line = rest.trim_start().to_string();
+        }
+        if !line.trim().is_empty() || lines.is_empty() {
+            lines.push(line.trim_end().to_string());
+        }
+        lines
+    }
+}
+
+/// Thai vowels and tone marks that sit above or below the previous consonant
+fn is_thai_mark(c: char) -> bool {
+    matches!(c, '\u{0E31}' | '\u{0E34}'..='\u{0E3A}' | '\u{0E47}'..='\u{0E4E}')
+}
+
+/// Pages written so far and where the next line goes on the last one
+struct Canvas<'a> {
+    doc: &'a PdfDocumentReference,
+    pages: Vec<PdfLayerReference>,
+    regular: Typeface<'a>,
+    bold: Typeface<'a>,
+    y: f32,
+}
+
+impl Canvas<'_> {
+    fn layer(&self) -> &PdfLayerReference {
+        self.pages.last().expect("Document has a page")
+    }
+
+    fn face(&self, bold: bool) -> &Typeface<'_> {
+        if bold { &self.bold } else { &self.regular }
+    }
+
+    fn text(&self, text: &str, x: f32, y: f32, size: f32, bold: bool) {
+        let face = self.face(bold);
+        self.layer().use_text(text, size, Mm(x), Mm(y), &face.font);
+    }
+
+    fn text_right(&self, text: &str, right: f32, y: f32, size: f32, bold: bool) {
+        let x = right - self.face(bold).width(text, size);
+        self.text(text, x, y, size, bold);
+    }
+
+    fn text_center(&self, text: &str, y: f32, size: f32, bold: bool) {
+        let x = (PAGE_WIDTH - self.face(bold).width(text, size)) / 2.0;
+        self.text(text, x, y, size, bold);
+    }
+
+    /// Writes wrapped lines from the current position and moves below them
+    fn paragraph(&mut self, text: &str, x: f32, width: f32, size: f32, bold: bool) {
+        for line in self.face(bold).wrap(text, size, width) {
+            self.text(&line, x, self.y, size, bold);
+            self.y -= LINE_HEIGHT;
+        }
+    }
+
+    fn rule(&self, y: f32) {
+        let line = Line {
+            points: vec![(Point::new(Mm(LEFT), Mm(y)), false), (Point::new(Mm(RIGHT), Mm(y)), false)],
+            is_closed: false,
+        };
+        self.layer().set_outline_thickness(0.5);
+        self.layer().add_line(line);
+    }
+
+    fn new_page(&mut self) {
+        let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        self.pages.push(self.doc.get_page(page).get_layer(layer));
+        self.y = TOP;
+    }
+}
+
+fn draw_heading(canvas: &mut Canvas, invoice: &InvoiceEntity) {
+    let title = match invoice.kind {
+        InvoiceKind::TaxInvoice => "ใบกำกับภาษี / ใบเสร็จรับเงิน (TAX INVOICE / RECEIPT)",
+        InvoiceKind::CreditNote => "ใบลดหนี้ (CREDIT NOTE)",
+    };
+    canvas.text_right("ต้นฉบับ (Original)", RIGHT, canvas.y + 6.0, TEXT_SIZE, false);
+    canvas.text_center(title, canvas.y, 15.0, true);
+    canvas.y -= 12.0;
+
+    // ผู้ขายซ้าย / เลขที่เอกสารขวา
+    let top = canvas.y;
+    party_block(canvas, "ผู้ขาย (Seller)", &invoice.seller);
+    let seller_bottom = canvas.y;
+
+    let date = invoice.issued_on().format("%d/%m/%Y").to_string();
+    let order = format!("#{}", invoice.order_id);
+    let details = [
+        ("เลขที่ (No.)", invoice.number.as_str()),
+        ("วันที่ (Date)", date.as_str()),
+        ("คำสั่งซื้อ (Order)", order.as_str()),
+    ];
+    for (i, (label, value)) in details.iter().enumerate() {
+        let y = top - i as f32 * 5.5;
+        canvas.text(label, 138.0, y, TEXT_SIZE, true);
+        canvas.text_right(value, RIGHT, y, TEXT_SIZE, false);
+    }
+
+    canvas.y = seller_bottom - 3.0;
+    party_block(canvas, "ลูกค้า (Customer)", &invoice.buyer);
+
+    if let Some(reference) = &invoice.reference {
+        canvas.y -= 2.0;
+        let original = format!(
+            "อ้างถึงใบกำกับภาษีเลขที่ {} ลงวันที่ {} (Reference tax invoice)",
+            reference.number,
+            reference.issued_on().format("%d/%m/%Y"),
+        );
+        canvas.paragraph(&original, LEFT, RIGHT - LEFT, TEXT_SIZE, false);
+        if let Some(reason) = &invoice.reason {
+            canvas.paragraph(&format!("เหตุผลในการลดหนี้ (Reason): {}", reason), LEFT, RIGHT - LEFT, TEXT_SIZE, false);
+        }
+    }
+    canvas.y -= 3.0;
+}
+
+fn party_block(canvas: &mut Canvas, label: &str, party: &InvoiceParty) {
+    canvas.text(label, LEFT, canvas.y, TEXT_SIZE, true);
+    canvas.y -= LINE_HEIGHT + 0.6;
+    canvas.paragraph(&party.name, LEFT, 115.0, 11.0, true);
+    canvas.paragraph(&party.address, LEFT, 115.0, TEXT_SIZE, false);
+    let tax_id = format!(
+        "เลขประจำตัวผู้เสียภาษี (Tax ID) {}  {}",
+        party.tax_id.as_str(),
+        party.branch.label()
+    );
+    canvas.paragraph(&tax_id, LEFT, 115.0, TEXT_SIZE, false);
+}
+
+fn table_header(canvas: &mut Canvas) {
+    canvas.rule(canvas.y + LINE_HEIGHT);
+    canvas.text("#", LEFT + 1.0, canvas.y, TEXT_SIZE, true);
+    canvas.text("รายการ (Description)", COL_DESCRIPTION, canvas.y, TEXT_SIZE, true);
+    canvas.text_right("จำนวน (Qty)", COL_QUANTITY_RIGHT, canvas.y, TEXT_SIZE, true);
+    canvas.text_right("ราคาต่อหน่วย", COL_UNIT_PRICE_RIGHT, canvas.y, TEXT_SIZE, true);
+    canvas.text_right("จำนวนเงิน (Amount)", RIGHT, canvas.y, TEXT_SIZE, true);
+    canvas.rule(canvas.y - 2.0);
+    canvas.y -= LINE_HEIGHT + 2.0;
+}
+
+fn draw_rows(canvas: &mut Canvas, invoice: &InvoiceEntity) {
+    let minor_units = invoice.currency.minor_units();
+    table_header(canvas);
+
+    for (i, line) in invoice.lines.iter().enumerate() {
+        let description = canvas.regular.wrap(&line.description, TEXT_SIZE, DESCRIPTION_WIDTH);
+        let height = description.len() as f32 * LINE_HEIGHT;
+        if canvas.y - height < ROWS_BOTTOM {
+            canvas.new_page();
+            table_header(canvas);
+        }
+
+        canvas.text(&(i + 1).to_string(), LEFT + 1.0, canvas.y, TEXT_SIZE, false);
+        canvas.text_right(&line.quantity.to_string(), COL_QUANTITY_RIGHT, canvas.y, TEXT_SIZE, false);
+        canvas.text_right(&format_amount(line.unit_price, minor_units), COL_UNIT_PRICE_RIGHT, canvas.y, TEXT_SIZE, false);
+        canvas.text_right(&format_amount(line.amount, minor_units), RIGHT, canvas.y, TEXT_SIZE, false);
+        for text in description {
+            canvas.text(&text, COL_DESCRIPTION, canvas.y, TEXT_SIZE, false);
+            canvas.y -= LINE_HEIGHT;
+        }
+        canvas.y -= 1.0;
+    }
+    canvas.rule(canvas.y + LINE_HEIGHT - 1.0);
+}
+
+fn draw_totals(canvas: &mut Canvas, invoice: &InvoiceEntity) {
+    if canvas.y - TOTALS_HEIGHT < ROWS_BOTTOM {
+        canvas.new_page();
+    }
+    let minor_units = invoice.currency.minor_units();
+    let currency = invoice.currency.as_str();
+
+    let totals: Vec<(&str, i64, bool)> = match &invoice.reference {
+        None => vec![
+            ("มูลค่าสินค้า/บริการ (Value before VAT)", invoice.net_amount, false),
+            ("ภาษีมูลค่าเพิ่ม (VAT)", invoice.tax_amount, false),
+            ("รวมทั้งสิ้น (Total)", invoice.total, true),
+        ],
+        Some(reference) => vec![
+            ("มูลค่าตามใบกำกับภาษีเดิม (Original value)", reference.value_before, false),
+            ("มูลค่าที่ถูกต้อง (Correct value)", reference.value_before - invoice.total, false),
+            ("ผลต่าง ไม่รวมภาษี (Difference before VAT)", invoice.net_amount, false),
+            ("ภาษีมูลค่าเพิ่ม (VAT)", invoice.tax_amount, false),
+            ("รวมลดหนี้ทั้งสิ้น (Total credited)", invoice.total, true),
+        ],
+    };
+
+    canvas.y -= 2.0;
+    let words_y = canvas.y;
+    for (label, amount, bold) in totals {
+        canvas.text_right(label, 160.0, canvas.y, TEXT_SIZE, bold);
+        canvas.text_right(&format!("{} {}", format_amount(amount, minor_units), currency), RIGHT, canvas.y, TEXT_SIZE, bold);
+        canvas.y -= LINE_HEIGHT + 0.8;
+    }
+
+    if currency == "THB" && minor_units == 2 {
+        let saved = canvas.y;
+        canvas.y = words_y;
+        canvas.paragraph(&format!("({})", baht_text(invoice.total)), LEFT, 75.0, TEXT_SIZE, false);
+        canvas.y = saved.min(canvas.y);
+    }
+
+    canvas.y -= 14.0;
+    canvas.rule(canvas.y + 5.0);
+    canvas.text("ผู้รับเงิน / ผู้มีอำนาจลงนาม (Authorized signature)", LEFT, canvas.y, TEXT_SIZE, false);
+    canvas.text_right("เอกสารนี้จัดทำด้วยระบบคอมพิวเตอร์ (Computer-generated document)", RIGHT, canvas.y, TEXT_SIZE, false);
+}
+
+fn draw_page_numbers(canvas: &Canvas, invoice: &InvoiceEntity) {
+    let count = canvas.pages.len();
+    for (i, layer) in canvas.pages.iter().enumerate() {
+        let text = format!("{}  หน้า {}/{}", invoice.number, i + 1, count);
+        let x = RIGHT - canvas.regular.width(&text, 8.0);
+        layer.use_text(text, 8.0, Mm(x), Mm(12.0), &canvas.regular.font);
+    }
+}
+
+/// `123456` with 2 minor units -> `1,234.56`
+fn format_amount(amount: i64, minor_units: u32) -> String {
+    let scale = 10_i64.pow(minor_units);
+    let whole = (amount.abs() / scale).to_string();
+    let mut grouped = String::new();
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let sign = if amount < 0 { "-" } else { "" };
+    if minor_units == 0 {
+        format!("{}{}", sign, grouped)
+    } else {
+        format!("{}{}.{:0width$}", sign, grouped, amount.abs() % scale, width = minor_units as usize)
+    }
+}
+
+/// Amount in satang spelled out in Thai, e.g. `150025` -> หนึ่งพันห้าร้อยบาทยี่สิบห้าสตางค์
+fn baht_text(satang: i64) -> String {
+    let satang = satang.unsigned_abs();
+    let (baht, rest) = (satang / 100, satang % 100);
+    match (baht, rest) {
+        (0, 0) => "ศูนย์บาทถ้วน".to_string(),
+        (_, 0) => format!("{}บาทถ้วน", thai_number(baht)),
+        (0, _) => format!("{}สตางค์", thai_number(rest)),
+        _ => format!("{}บาท{}สตางค์", thai_number(baht), thai_number(rest)),
+    }
+}
+
+fn thai_number(n: u64) -> String {
+    if n >= 1_000_000 {
+        let rest = n % 1_000_000;
+        let rest = if rest == 0 { String::new() } else { thai_group(rest, true) };
+        return format!("{}ล้าน{}", thai_number(n / 1_000_000), rest);
+    }
+    thai_group(n, false)
+}
+
+/// Below a million. `after_million`: a lone trailing 1 still reads เอ็ด
+fn thai_group(n: u64, after_million: bool) -> String {
+    const DIGITS: [&str; 10] = ["", "หนึ่ง", "สอง", "สาม", "สี่", "ห้า", "หก", "เจ็ด", "แปด", "เก้า"];
+    const PLACES: [&str; 6] = ["", "สิบ", "ร้อย", "พัน", "หมื่น", "แสน"];
+
+    let digits: Vec<usize> = n.to_string().bytes().map(|b| (b - b'0') as usize).collect();
+    let mut text = String::new();
+    for (i, digit) in digits.iter().enumerate() {
+        let place = digits.len() - 1 - i;
+        match (place, *digit) {
+            (_, 0) => {}
+            (0, 1) if n > 1 || after_million => text.push_str("เอ็ด"),
+            (1, 1) => text.push_str("สิบ"),
+            (1, 2) => text.push_str("ยี่สิบ"),
+            _ => {
+                text.push_str(DIGITS[*digit]);
+                text.push_str(PLACES[place]);
+            }
+        }
+    }
+    text
+}
@@ -0,0 +1,184 @@
+use std::{
+    io::{ErrorKind, SeekFrom},
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio::{
+    fs,
+    io::{AsyncRead, AsyncReadExt, AsyncSeekExt},
+};
+use tokio_util::io::ReaderStream;
+
+use crate::domain::value_objects::byte_range::ByteRange;
+use crate::infrastructure::file_storage::{ByteStream, FileStorage};
+
+/// Stores objects as files below a root directory. Suited to a single
+/// server; the content type is not kept, callers know it from their records.
+pub struct LocalFileStorage {
+    root: PathBuf,
+}
+
+impl LocalFileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Maps a key to a path, refusing anything that could escape the root.
+    fn path_for(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && !key.contains('\\')
+            && relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+        if !is_safe {
+            return Err(anyhow!("Invalid storage key: {}", key));
+        }
+        Ok(self.root.join(relative))
+    }
+
+    async fn write(&self, key: &str, content: &mut (impl AsyncRead + Unpin)) -> Result<()> {
+        let path = self.path_for(key)?;
+        let parent = path
+            .parent()
+            .ok_or_else(|| anyhow!("Invalid storage key: {}", key))?;
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+
+        // เขียนไฟล์ชั่วคราวแล้ว rename คนที่กำลังอ่านจะไม่เห็นไฟล์ครึ่งๆ กลางๆ
+        // ชื่อไม่ซ้ำกัน: key ที่ต่างกันแค่นามสกุล หรือเขียน key เดียวกันพร้อมกัน ไม่ทับไฟล์กัน
+        let mut builder = tempfile::Builder::new();
+        builder.suffix(".partial");
+        // temp file เป็น 0600 โดย default; ให้สิทธิ์เหมือนไฟล์ที่สร้างตามปกติ
+        #[cfg(unix)]
+        builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o644));
+        let temp = builder
+            .tempfile_in(parent)
+            .with_context(|| format!("Failed to create a temp file in {}", parent.display()))?;
+        let (file, temp_path) = temp.into_parts();
+        let mut file = fs::File::from_std(file);
+        tokio::io::copy(content, &mut file).await?;
+        file.sync_all().await?;
+        drop(file);
+        temp_path
+            .persist(&path)
+            .with_context(|| format!("Failed to move file into {}", path.display()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FileStorage for LocalFileStorage {
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put(&self, key: &str, mut content: &[u8], _content_type: &str) -> Result<()> {
+        self.write(key, &mut content).await
+    }
+
+    async fn put_file(&self, key: &str, mut file: fs::File, _content_type: &str) -> Result<()> {
+        file.seek(SeekFrom::Start(0)).await?;
+        self.write(key, &mut file).await
+    }
+
+    async fn open(&self, key: &str, range: Option<ByteRange>) -> Result<ByteStream> {
+        let path = self.path_for(key)?;
+        let mut file = fs::File::open(&path)
+            .await
+            .with_context(|| format!("Stored file not found: {}", key))?;
+
+        let Some(range) = range else {
+            return Ok(ReaderStream::new(file).boxed());
+        };
+
+        let size = file.metadata().await?.len();
+        if range.end >= size {
+            return Err(anyhow!("Range {}-{} is beyond {}", range.start, range.end, key));
+        }
+        file.seek(SeekFrom::Start(range.start)).await?;
+        Ok(ReaderStream::new(file.take(range.byte_count())).boxed())
+    }
+
+    async fn size(&self, key: &str) -> Result<Option<u64>> {
+        match fs::metadata(self.path_for(key)?).await {
+            Ok(meta) => Ok(Some(meta.len())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match fs::remove_file(self.path_for(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    use std::io::Write;
+
+    async fn read_all(stream: ByteStream) -> Vec<u8> {
+        let chunks: Vec<_> = stream.try_collect().await.unwrap();
+        chunks.concat()
+    }
+
+    #[tokio::test]
+    async fn streams_files_in_and_ranges_out() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = LocalFileStorage::new(root.path());
+        let content: Vec<u8> = (0..=255u8).cycle().take(100_000).collect();
+
+        let mut source = tempfile::tempfile().unwrap();
+        source.write_all(&content).unwrap();
+        storage
+            .put_file("epub/1/copy.epub", fs::File::from_std(source), "application/epub+zip")
+            .await
+            .unwrap();
+        assert_eq!(storage.size("epub/1/copy.epub").await.unwrap(), Some(100_000));
+        assert_eq!(read_all(storage.open("epub/1/copy.epub", None).await.unwrap()).await, content);
+
+        let range = ByteRange { start: 70_000, end: 99_999 };
+        let part = read_all(storage.open("epub/1/copy.epub", Some(range)).await.unwrap()).await;
+        assert_eq!(part, content[70_000..]);
+
+        let beyond = ByteRange { start: 90_000, end: 100_000 };
+        assert!(storage.open("epub/1/copy.epub", Some(beyond)).await.is_err());
+        assert!(storage.open("epub/1/missing.epub", None).await.is_err());
+        assert!(storage.open("../outside", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn concurrent_writes_do_not_share_temp_files() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = LocalFileStorage::new(root.path());
+        let webp = vec![1u8; 200_000];
+        let jpg = vec![2u8; 300_000];
+
+        // key ที่ต่างกันแค่นามสกุล เขียนพร้อมกัน
+        let (a, b) = tokio::join!(
+            storage.put("covers/1/9-large.webp", &webp, "image/webp"),
+            storage.put("covers/1/9-large.jpg", &jpg, "image/jpeg"),
+        );
+        a.unwrap();
+        b.unwrap();
+        assert_eq!(read_all(storage.open("covers/1/9-large.webp", None).await.unwrap()).await, webp);
+        assert_eq!(read_all(storage.open("covers/1/9-large.jpg", None).await.unwrap()).await, jpg);
+
+        let mut names: Vec<String> = std::fs::read_dir(root.path().join("covers/1"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["9-large.jpg", "9-large.webp"]);
+    }
+}
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::infrastructure::notifier::{Notification, Notifier};
+
+/// Development notifier: writes messages to the log instead of sending them.
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    fn channel(&self) -> &'static str {
+        "log"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        info!(
+            to = %notification.to_email,
+            subject = %notification.subject,
+            "notification: {}",
+            notification.body
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,925 @@
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate};
+use anyhow::{anyhow, Context, Result};
+use quick_xml::{escape::escape, events::Event, Reader};
+
+use crate::domain::{
+    entities::{
+        book::BookEntity,
+        book_metadata::{BookContributor, BookSubject, SubjectScheme},
+    },
+    services::search_text::is_thai,
+    value_objects::book_format::BookFormat,
+};
+use crate::infrastructure::onix_reader::LANGUAGES;
+
+// ISO 2709 structure characters
+const SUBFIELD_DELIMITER: u8 = 0x1F;
+const FIELD_TERMINATOR: u8 = 0x1E;
+const RECORD_TERMINATOR: u8 = 0x1D;
+
+const LEADER_LENGTH: usize = 24;
+const DIRECTORY_ENTRY_LENGTH: usize = 12;
+/// Four length digits per directory entry, five for the whole record
+const MAX_FIELD_BYTES: usize = 9_999;
+const MAX_RECORD_BYTES: usize = 99_999;
+/// Room left for the summary in 520 once indicators and delimiters are in
+const MAX_SUMMARY_BYTES: usize = 9_000;
+
+const MARCXML_NAMESPACE: &str = "http://www.loc.gov/MARC21/slim";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarcFormat {
+    /// MARC 21 exchange format, `.mrc`
+    Iso2709,
+    MarcXml,
+}
+
+impl MarcFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Iso2709 => "application/marc",
+            Self::MarcXml => "application/marcxml+xml",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Iso2709 => "mrc",
+            Self::MarcXml => "xml",
+        }
+    }
+
+    pub fn encode(&self, records: &[MarcRecord]) -> Result<Vec<u8>> {
+        match self {
+            Self::Iso2709 => to_iso2709(records),
+            Self::MarcXml => to_marcxml(records).map(String::into_bytes),
+        }
+    }
+}
+
+impl FromStr for MarcFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "marc" | "marc21" | "mrc" | "iso2709" => Ok(Self::Iso2709),
+            "marcxml" | "xml" => Ok(Self::MarcXml),
+            _ => Err(anyhow!("Invalid MARC format: {} (expected marc21 or marcxml)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subfield {
+    pub code: char,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarcField {
+    /// 001–009: a single value, no indicators or subfields
+    Control { tag: String, value: String },
+    Data {
+        tag: String,
+        indicators: [char; 2],
+        subfields: Vec<Subfield>,
+    },
+}
+
+impl MarcField {
+    pub fn tag(&self) -> &str {
+        match self {
+            Self::Control { tag, .. } | Self::Data { tag, .. } => tag,
+        }
+    }
+
+    fn control(tag: &str, value: String) -> Self {
+        Self::Control {
+            tag: tag.to_string(),
+            value,
+        }
+    }
+
+    fn data(tag: &str, indicators: [char; 2], subfields: &[(char, String)]) -> Self {
+        Self::Data {
+            tag: tag.to_string(),
+            indicators,
+            subfields: subfields
+                .iter()
+                .map(|(code, value)| Subfield {
+                    code: *code,
+                    value: value.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// One MARC 21 bibliographic record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarcRecord {
+    /// 24 characters; record length and base address (0–4, 12–16) are
+    /// filled in when the record is encoded
+    pub leader: String,
+    pub fields: Vec<MarcField>,
+}
+
+impl MarcRecord {
+    pub fn control_value(&self, tag: &str) -> Option<&str> {
+        self.fields.iter().find_map(|f| match f {
+            MarcField::Control { tag: t, value } if t == tag => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    /// First `$code` of the first `tag` field that has one
+    pub fn subfield(&self, tag: &str, code: char) -> Option<&str> {
+        self.fields.iter().find_map(|f| match f {
+            MarcField::Data { tag: t, subfields, .. } if t == tag => {
+                subfields.iter().find(|s| s.code == code).map(|s| s.value.as_str())
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Leader, 001/003/005 and the fixed-length fields a library system
+/// expects, then the descriptive fields for what we know about the book:
+/// ISBN and price (020), subjects (072/650/653), credits (100/700), title
+/// (245), publication (264), physical description and RDA types
+/// (300/336–338) and the blurb (520).
+///
+/// Books that are not out yet are coded as prepublication (leader/17 = 8).
+pub fn book_record(
+    book: &BookEntity,
+    contributors: &[BookContributor],
+    subjects: &[BookSubject],
+    organization_code: &str,
+    today: NaiveDate,
+) -> MarcRecord {
+    let audio = book.format == BookFormat::Audiobook;
+    let prepublication = !book.is_released(today);
+    let leader = format!(
+        "00000n{}m a2200000{}i 4500",
+        if audio { 'i' } else { 'a' },
+        if prepublication { '8' } else { '7' }
+    );
+
+    let mut fields = vec![
+        MarcField::control("001", book.isbn.as_str().to_string()),
+        MarcField::control("003", organization_code.to_string()),
+        MarcField::control("005", book.updated_at.format("%Y%m%d%H%M%S.0").to_string()),
+    ];
+    if book.format.is_digital() {
+        if audio {
+            fields.push(MarcField::control("007", "sz zunnnnnzned".to_string()));
+        }
+        fields.push(MarcField::control("007", "cr |n|||||||||".to_string()));
+    }
+    fields.push(MarcField::control("008", fixed_length_data(book, audio)));
+
+    fields.push(MarcField::data(
+        "020",
+        [' ', ' '],
+        &[
+            ('a', book.isbn.as_str().to_string()),
+            ('q', book.format.as_str().to_string()),
+            ('c', book.price.to_string()),
+        ],
+    ));
+    fields.push(MarcField::data(
+        "040",
+        [' ', ' '],
+        &[
+            ('a', organization_code.to_string()),
+            ('b', "eng".to_string()),
+            ('e', "rda".to_string()),
+            ('c', organization_code.to_string()),
+        ],
+    ));
+    for subject in subjects {
+        let source = match subject.scheme {
+            SubjectScheme::Bisac => "bisacsh",
+            SubjectScheme::Thema => "thema",
+        };
+        fields.push(MarcField::data("072", [' ', '7'], &[('a', subject.code.clone()), ('2', source.to_string())]));
+    }
+
+    let credits = credits(book, contributors);
+    let mut credits = credits.iter();
+    let main_entry = credits.next();
+    if let Some((name, role)) = main_entry {
+        fields.push(name_field("100", name, *role));
+    }
+
+    fields.push(title_field(book, main_entry.is_some()));
+    fields.push(publication_field(book));
+    fields.extend(physical_description(book, audio));
+
+    if let Some(description) = &book.description {
+        fields.push(MarcField::data("520", [' ', ' '], &[('a', summary(description))]));
+    }
+    for subject in subjects.iter().filter(|s| s.scheme == SubjectScheme::Bisac) {
+        if let Some(heading) = &subject.heading {
+            fields.push(MarcField::data(
+                "650",
+                [' ', '7'],
+                &[('a', ensure_period(&clean(heading))), ('2', "bisacsh".to_string())],
+            ));
+        }
+    }
+    if let Some(category) = &book.category {
+        fields.push(MarcField::data("653", [' ', ' '], &[('a', clean(category))]));
+    }
+    for (name, role) in credits {
+        fields.push(name_field("700", name, *role));
+    }
+
+    MarcRecord { leader, fields }
+}
+
+/// 008 for books (or, for audiobooks, sound recordings)
+fn fixed_length_data(book: &BookEntity, audio: bool) -> String {
+    let (date_type, year) = match book.release_date {
+        Some(date) => ('s', format!("{:04}", date.year())),
+        None => ('n', "uuuu".to_string()),
+    };
+    let language = book
+        .language
+        .as_deref()
+        .and_then(|code| LANGUAGES.iter().find(|(_, iso1)| *iso1 == code))
+        .map_or("und", |(iso2, _)| iso2);
+    let form = if book.format.is_digital() { 'o' } else { ' ' };
+    // 18–34 ต่างกันตามชนิดวัสดุ: หนังสือ กับ สื่อเสียง
+    let material = if audio {
+        format!("nnnn {}      || n ", form)
+    } else {
+        format!("     {}     000 u ", form)
+    };
+
+    format!(
+        "{}{}{}    xx {}{} d",
+        book.created_at.format("%y%m%d"),
+        date_type,
+        year,
+        material,
+        language
+    )
+}
+
+/// Credits in order, the first author first. Without stored credits the
+/// display author string is split on commas.
+fn credits(book: &BookEntity, contributors: &[BookContributor]) -> Vec<(String, Option<&'static str>)> {
+    if contributors.is_empty() {
+        return book
+            .author
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty() && *n != "et al.")
+            .map(|n| (n.to_string(), Some("author")))
+            .collect();
+    }
+
+    let mut ordered: Vec<&BookContributor> = contributors.iter().collect();
+    ordered.sort_by_key(|c| (!c.is_author(), c.sequence));
+    ordered
+        .into_iter()
+        .map(|c| (c.name.clone(), relator_term(&c.role)))
+        .collect()
+}
+
+/// ONIX contributor role (list 17) → MARC relator term
+fn relator_term(role: &str) -> Option<&'static str> {
+    match role {
+        "A01" => Some("author"),
+        "A12" => Some("illustrator"),
+        "A13" => Some("photographer"),
+        "A15" | "A16" => Some("author of introduction, etc."),
+        "B01" => Some("editor"),
+        "B06" => Some("translator"),
+        "E07" => Some("narrator"),
+        _ => None,
+    }
+}
+
+/// 100/700. Western names are inverted ("Tolkien, J. R. R."); Thai names and
+/// single names stay in direct order, as Thai libraries file them.
+fn name_field(tag: &str, name: &str, relator: Option<&str>) -> MarcField {
+    let name = clean(name);
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let (indicator, heading) = if name.contains(',') {
+        ('1', name.clone())
+    } else if words.len() < 2 || name.chars().any(is_thai) {
+        ('0', name.clone())
+    } else {
+        let (surname, forenames) = words.split_last().unwrap_or((&"", &[]));
+        ('1', format!("{}, {}", surname, forenames.join(" ")))
+    };
+
+    match relator {
+        Some(term) => MarcField::data(
+            tag,
+            [indicator, ' '],
+            &[('a', format!("{},", without_period(&heading))), ('e', format!("{}.", term))],
+        ),
+        None => MarcField::data(tag, [indicator, ' '], &[('a', ensure_period(&heading))]),
+    }
+}
+
+/// Drops a closing full stop, but not the one after an initial ("J.R.R.")
+fn without_period(heading: &str) -> &str {
+    let Some(rest) = heading.strip_suffix('.') else {
+        return heading;
+    };
+    let mut tail = rest.chars().rev();
+    match (tail.next(), tail.next()) {
+        (Some(c), None | Some(' ' | '.' | ',')) if c.is_alphabetic() => heading,
+        _ => rest,
+    }
+}
+
+/// 245 with ISBD punctuation; "Title: subtitle" is split into $a and $b
+fn title_field(book: &BookEntity, has_main_entry: bool) -> MarcField {
+    let title = clean(book.title.as_str());
+    let (main, remainder) = match title.split_once(": ") {
+        Some((main, rest)) => (main.trim().to_string(), Some(rest.trim().to_string())),
+        None => (title.clone(), None),
+    };
+    let nonfiling = nonfiling_characters(&main, book.language.as_deref());
+
+    let mut subfields = Vec::new();
+    match remainder {
+        Some(rest) => {
+            subfields.push(('a', format!("{} :", main)));
+            subfields.push(('b', format!("{} /", rest)));
+        }
+        None => subfields.push(('a', format!("{} /", main))),
+    }
+    subfields.push(('c', ensure_period(&clean(&book.author))));
+
+    MarcField::data(
+        "245",
+        [if has_main_entry { '1' } else { '0' }, nonfiling],
+        &subfields,
+    )
+}
+
+/// Leading article skipped when filing, English titles only
+fn nonfiling_characters(title: &str, language: Option<&str>) -> char {
+    if language.is_some_and(|l| l != "en") {
+        return '0';
+    }
+    ["The ", "An ", "A "]
+        .iter()
+        .find(|article| title.starts_with(*article))
+        .map_or('0', |article| char::from(b'0' + article.len() as u8))
+}
+
+/// 264 _1: we do not keep the place of publication
+fn publication_field(book: &BookEntity) -> MarcField {
+    let publisher = book
+        .publisher
+        .as_deref()
+        .map(clean)
+        .unwrap_or_else(|| "[publisher not identified]".to_string());
+    let date = book
+        .release_date
+        .map(|d| format!("{}.", d.year()))
+        .unwrap_or_else(|| "[date of publication not identified]".to_string());
+
+    MarcField::data(
+        "264",
+        [' ', '1'],
+        &[
+            ('a', "[Place of publication not identified] :".to_string()),
+            ('b', format!("{},", publisher)),
+            ('c', date),
+        ],
+    )
+}
+
+/// 300 and the RDA content, media and carrier types
+fn physical_description(book: &BookEntity, audio: bool) -> Vec<MarcField> {
+    let extent = match book.format {
+        BookFormat::Paperback | BookFormat::Hardcover => match book.dimensions {
+            // ความสูงของเล่มเป็นเซนติเมตร ปัดขึ้นตามหลักลงรายการ
+            Some(d) => vec![
+                ('a', "1 volume ;".to_string()),
+                ('c', format!("{} cm", (d.length_mm() + 9) / 10)),
+            ],
+            None => vec![('a', "1 volume".to_string())],
+        },
+        BookFormat::Ebook => vec![('a', "1 online resource".to_string())],
+        BookFormat::Audiobook => vec![('a', "1 online resource (1 audio file)".to_string())],
+    };
+
+    let (content, media, carrier) = match book.format {
+        _ if audio => (("spoken word", "spw"), ("computer", "c"), ("online resource", "cr")),
+        BookFormat::Ebook => (("text", "txt"), ("computer", "c"), ("online resource", "cr")),
+        _ => (("text", "txt"), ("unmediated", "n"), ("volume", "nc")),
+    };
+    let rda_type = |tag: &str, (term, code): (&str, &str), source: &str| {
+        MarcField::data(
+            tag,
+            [' ', ' '],
+            &[('a', term.to_string()), ('b', code.to_string()), ('2', source.to_string())],
+        )
+    };
+
+    vec![
+        MarcField::data("300", [' ', ' '], &extent),
+        rda_type("336", content, "rdacontent"),
+        rda_type("337", media, "rdamedia"),
+        rda_type("338", carrier, "rdacarrier"),
+    ]
+}
+
+/// The blurb on one line, cut at a word so the field fits ISO 2709
+fn summary(description: &str) -> String {
+    let text = clean(description);
+    if text.len() <= MAX_SUMMARY_BYTES {
+        return text;
+    }
+    let mut end = MAX_SUMMARY_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let cut = text[..end].rfind(' ').unwrap_or(end);
+    format!("{}...", text[..cut].trim_end())
+}
+
+/// Whitespace collapsed; control characters would break the record structure
+fn clean(value: &str) -> String {
+    value
+        .split(|c: char| c.is_whitespace() || c.is_control())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn ensure_period(value: &str) -> String {
+    if value.ends_with(['.', '?', '!', ')', ']']) {
+        value.to_string()
+    } else {
+        format!("{}.", value)
+    }
+}
+
+/// MARC 21 exchange format (ISO 2709), UTF-8
+pub fn to_iso2709(records: &[MarcRecord]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for record in records {
+        out.extend(encode(record)?);
+    }
+    Ok(out)
+}
+
+fn encode(record: &MarcRecord) -> Result<Vec<u8>> {
+    if record.leader.len() != LEADER_LENGTH || !record.leader.is_ascii() {
+        return Err(anyhow!("Leader must be {} ASCII characters", LEADER_LENGTH));
+    }
+
+    let mut directory = Vec::new();
+    let mut data = Vec::new();
+    for field in &record.fields {
+        let tag = field.tag();
+        if tag.len() != 3 || !tag.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(anyhow!("Invalid tag: {}", tag));
+        }
+        let start = data.len();
+        match field {
+            MarcField::Control { value, .. } => {
+                check_value(tag, value)?;
+                data.extend_from_slice(value.as_bytes());
+            }
+            MarcField::Data {
+                indicators,
+                subfields,
+                ..
+            } => {
+                for indicator in indicators {
+                    if !indicator.is_ascii() || indicator.is_ascii_control() {
+                        return Err(anyhow!("Invalid indicator in {}", tag));
+                    }
+                    data.push(*indicator as u8);
+                }
+                for subfield in subfields {
+                    if !subfield.code.is_ascii_graphic() {
+                        return Err(anyhow!("Invalid subfield code in {}", tag));
+                    }
+                    check_value(tag, &subfield.value)?;
+                    data.push(SUBFIELD_DELIMITER);
+                    data.push(subfield.code as u8);
+                    data.extend_from_slice(subfield.value.as_bytes());
+                }
+            }
+        }
+        data.push(FIELD_TERMINATOR);
+
+        let length = data.len() - start;
+        if length > MAX_FIELD_BYTES {
+            return Err(anyhow!("Field {} is {} bytes (max {})", tag, length, MAX_FIELD_BYTES));
+        }
+        directory.extend_from_slice(format!("{}{:04}{:05}", tag, length, start).as_bytes());
+    }
+    directory.push(FIELD_TERMINATOR);
+
+    let base_address = LEADER_LENGTH + directory.len();
+    let record_length = base_address + data.len() + 1;
+    if record_length > MAX_RECORD_BYTES {
+        return Err(anyhow!("Record is {} bytes (max {})", record_length, MAX_RECORD_BYTES));
+    }
+
+    let leader = &record.leader;
+    let mut out = Vec::with_capacity(record_length);
+    out.extend_from_slice(format!("{:05}", record_length).as_bytes());
+    out.extend_from_slice(&leader.as_bytes()[5..12]);
+    out.extend_from_slice(format!("{:05}", base_address).as_bytes());
+    out.extend_from_slice(&leader.as_bytes()[17..]);
+    out.extend(directory);
+    out.extend(data);
+    out.push(RECORD_TERMINATOR);
+    Ok(out)
+}
+
+fn check_value(tag: &str, value: &str) -> Result<()> {
+    if value
+        .bytes()
+        .any(|b| matches!(b, SUBFIELD_DELIMITER | FIELD_TERMINATOR | RECORD_TERMINATOR))
+    {
+        return Err(anyhow!("Field {} contains a MARC structure character", tag));
+    }
+    Ok(())
+}
+
+/// Reads records written by `to_iso2709` or by a library system, as long as
+/// they are UTF-8 (leader/09 = a)
+pub fn from_iso2709(bytes: &[u8]) -> Result<Vec<MarcRecord>> {
+    let mut records = Vec::new();
+    let mut rest = bytes;
+    while !rest.iter().all(|b| b.is_ascii_whitespace()) {
+        let length: usize = ascii_number(rest.get(..5).ok_or_else(|| anyhow!("Truncated leader"))?)?;
+        let raw = rest
+            .get(..length)
+            .ok_or_else(|| anyhow!("Record {} is cut short", records.len() + 1))?;
+        records.push(decode(raw).with_context(|| format!("Record {}", records.len() + 1))?);
+        rest = &rest[length..];
+    }
+    Ok(records)
+}
+
+fn decode(raw: &[u8]) -> Result<MarcRecord> {
+    if raw.len() < LEADER_LENGTH + 2 || raw[raw.len() - 1] != RECORD_TERMINATOR {
+        return Err(anyhow!("Missing record terminator"));
+    }
+    let leader = std::str::from_utf8(&raw[..LEADER_LENGTH])
+        .ok()
+        .filter(|l| l.is_ascii())
+        .ok_or_else(|| anyhow!("Leader is not ASCII"))?
+        .to_string();
+    if leader.as_bytes()[9] != b'a' {
+        return Err(anyhow!("Only UTF-8 records (leader/09 = a) are supported"));
+    }
+    let base_address: usize = ascii_number(&raw[12..17])?;
+    let directory = raw
+        .get(LEADER_LENGTH..base_address.saturating_sub(1))
+        .filter(|d| d.len() % DIRECTORY_ENTRY_LENGTH == 0)
+        .ok_or_else(|| anyhow!("Malformed directory"))?;
+    let data = &raw[base_address..raw.len() - 1];
+
+    let mut fields = Vec::new();
+    for entry in directory.chunks(DIRECTORY_ENTRY_LENGTH) {
+        let tag = std::str::from_utf8(&entry[..3])?.to_string();
+        let length: usize = ascii_number(&entry[3..7])?;
+        let start: usize = ascii_number(&entry[7..12])?;
+        let bytes = data
+            .get(start..start + length)
+            .filter(|b| b.last() == Some(&FIELD_TERMINATOR))
+            .ok_or_else(|| anyhow!("Field {} points outside the record", tag))?;
+        let body = &bytes[..bytes.len() - 1];
+
+        if is_control_tag(&tag) {
+            let value = String::from_utf8(body.to_vec()).with_context(|| format!("Field {} is not UTF-8", tag))?;
+            fields.push(MarcField::Control { tag, value });
+            continue;
+        }
+
+        let mut parts = body.split(|b| *b == SUBFIELD_DELIMITER);
+        let indicators = parts.next().filter(|i| i.len() == 2 && i.is_ascii()).ok_or_else(|| anyhow!("Field {} has bad indicators", tag))?;
+        let mut subfields = Vec::new();
+        for part in parts {
+            let (code, value) = part.split_first().ok_or_else(|| anyhow!("Empty subfield in {}", tag))?;
+            subfields.push(Subfield {
+                code: char::from(*code),
+                value: String::from_utf8(value.to_vec()).with_context(|| format!("Field {} is not UTF-8", tag))?,
+            });
+        }
+        fields.push(MarcField::Data {
+            tag,
+            indicators: [char::from(indicators[0]), char::from(indicators[1])],
+            subfields,
+        });
+    }
+
+    Ok(MarcRecord { leader, fields })
+}
+
+fn ascii_number(bytes: &[u8]) -> Result<usize> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| anyhow!("Expected digits, found {:?}", String::from_utf8_lossy(bytes)))
+}
+
+fn is_control_tag(tag: &str) -> bool {
+    tag.starts_with("00")
+}
+
+/// MARCXML `<collection>`; leaders carry the same lengths as the ISO 2709
+/// encoding
+pub fn to_marcxml(records: &[MarcRecord]) -> Result<String> {
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<collection xmlns=\"{}\">\n",
+        MARCXML_NAMESPACE
+    );
+    for record in records {
+        let encoded = encode(record)?;
+        xml.push_str("  <record>\n");
+        xml.push_str(&format!("    <leader>{}</leader>\n", String::from_utf8_lossy(&encoded[..LEADER_LENGTH])));
+        for field in &record.fields {
+            match field {
+                MarcField::Control { tag, value } => xml.push_str(&format!(
+                    "    <controlfield tag=\"{}\">{}</controlfield>\n",
+                    escape(tag.as_str()),
+                    escape(value.as_str())
+                )),
+                MarcField::Data {
+                    tag,
+                    indicators,
+                    subfields,
+                } => {
+                    xml.push_str(&format!(
+                        "    <datafield tag=\"{}\" ind1=\"{}\" ind2=\"{}\">\n",
+                        escape(tag.as_str()),
+                        escape(indicators[0].to_string().as_str()),
+                        escape(indicators[1].to_string().as_str())
+                    ));
+                    for subfield in subfields {
+                        xml.push_str(&format!(
+                            "      <subfield code=\"{}\">{}</subfield>\n",
+                            escape(subfield.code.to_string().as_str()),
+                            escape(subfield.value.as_str())
+                        ));
+                    }
+                    xml.push_str("    </datafield>\n");
+                }
+            }
+        }
+        xml.push_str("  </record>\n");
+    }
+    xml.push_str("</collection>\n");
+    Ok(xml)
+}
+
+/// Reads a MARCXML `<collection>` or a single `<record>`, with or without a
+/// namespace prefix
+pub fn from_marcxml(xml: &str) -> Result<Vec<MarcRecord>> {
+    let mut reader = Reader::from_str(xml);
+    let mut records = Vec::new();
+    let mut record: Option<MarcRecord> = None;
+    // ข้อความที่กำลังสะสมของ element ปัจจุบัน (leader / controlfield / subfield)
+    let mut text: Option<String> = None;
+    let mut control_tag = None;
+    loop {
+        let event = reader.read_event()?;
+        match &event {
+            Event::Start(e) | Event::Empty(e) => {
+                let attr = |name: &[u8]| -> Option<String> {
+                    e.attributes()
+                        .flatten()
+                        .find(|a| a.key.local_name().as_ref() == name)
+                        .map(|a| a.unescape_value().map(|v| v.into_owned()).unwrap_or_default())
+                };
+                match e.local_name().as_ref() {
+                    b"record" => {
+                        record = Some(MarcRecord {
+                            leader: String::new(),
+                            fields: Vec::new(),
+                        })
+                    }
+                    b"leader" => text = Some(String::new()),
+                    b"controlfield" => {
+                        control_tag = attr(b"tag");
+                        text = Some(String::new());
+                    }
+                    b"datafield" => {
+                        let record = record.as_mut().ok_or_else(|| anyhow!("datafield outside a record"))?;
+                        let indicator = |name: &[u8]| attr(name).and_then(|v| v.chars().next()).unwrap_or(' ');
+                        record.fields.push(MarcField::Data {
+                            tag: attr(b"tag").ok_or_else(|| anyhow!("datafield without a tag"))?,
+                            indicators: [indicator(b"ind1"), indicator(b"ind2")],
+                            subfields: Vec::new(),
+                        });
+                    }
+                    b"subfield" => {
+                        let code = attr(b"code")
+                            .and_then(|c| c.chars().next())
+                            .ok_or_else(|| anyhow!("subfield without a code"))?;
+                        let Some(MarcField::Data { subfields, .. }) =
+                            record.as_mut().and_then(|r| r.fields.last_mut())
+                        else {
+                            return Err(anyhow!("subfield outside a datafield"));
+                        };
+                        subfields.push(Subfield {
+                            code,
+                            value: String::new(),
+                        });
+                        text = Some(String::new());
+                    }
+                    _ => {}
+                }
+                if matches!(event, Event::Empty(_)) {
+                    finish_element(&mut record, &mut text, &mut control_tag, e.local_name().as_ref(), &mut records)?;
+                }
+            }
+            Event::Text(e) => {
+                if let Some(text) = text.as_mut() {
+                    text.push_str(&e.unescape()?);
+                }
+            }
+            Event::CData(e) => {
+                if let Some(text) = text.as_mut() {
+                    text.push_str(&String::from_utf8_lossy(e));
+                }
+            }
+            Event::End(e) => {
+                finish_element(&mut record, &mut text, &mut control_tag, e.local_name().as_ref(), &mut records)?;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(records)
+}
+
+fn finish_element(
+    record: &mut Option<MarcRecord>,
+    text: &mut Option<String>,
+    control_tag: &mut Option<String>,
+    name: &[u8],
+    records: &mut Vec<MarcRecord>,
+) -> Result<()> {
+    match name {
+        b"record" => {
+            let record = record.take().ok_or_else(|| anyhow!("Unbalanced record element"))?;
+            if record.leader.len() != LEADER_LENGTH {
+                return Err(anyhow!("Record without a {}-character leader", LEADER_LENGTH));
+            }
+            records.push(record);
+        }
+        b"leader" => {
+            let leader = text.take().unwrap_or_default();
+            record
+                .as_mut()
+                .ok_or_else(|| anyhow!("leader outside a record"))?
+                .leader = leader;
+        }
+        b"controlfield" => {
+            let tag = control_tag.take().ok_or_else(|| anyhow!("controlfield without a tag"))?;
+            let value = text.take().unwrap_or_default();
+            record
+                .as_mut()
+                .ok_or_else(|| anyhow!("controlfield outside a record"))?
+                .fields
+                .push(MarcField::Control { tag, value });
+        }
+        b"subfield" => {
+            let value = text.take().unwrap_or_default();
+            if let Some(MarcField::Data { subfields, .. }) = record.as_mut().and_then(|r| r.fields.last_mut())
+                && let Some(subfield) = subfields.last_mut()
+            {
+                subfield.value = value;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{
+        dimensions::Dimensions,
+        money::{Currency, Money},
+    };
+
+    // ตัวอย่างที่เขียนด้วย encoder อีกตัว: Tolkien ภาษาอังกฤษ และ ebook ภาษาไทย
+    const SAMPLE_MRC: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/marc21/sample.mrc"));
+    const SAMPLE_XML: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/marc21/sample.xml"));
+
+    fn fellowship() -> BookEntity {
+        let mut book = BookEntity::new(
+            "9780261103573".to_string(),
+            "The Fellowship of the Ring: Being the First Part".to_string(),
+            "J.R.R. Tolkien".to_string(),
+            Money::parse("450.00", Currency::thb()).unwrap(),
+            3,
+        )
+        .unwrap();
+        book.change_publication(Some("HarperCollins".to_string()), Some("en".to_string()), BookFormat::Paperback)
+            .unwrap();
+        book.change_shipping_profile(Some(350), Some(Dimensions::new(178, 111, 30).unwrap()))
+            .unwrap();
+        book.change_description(Some("Frodo inherits\nthe One Ring.".to_string())).unwrap();
+        book.change_category(Some("Fantasy".to_string())).unwrap();
+        book.change_release(NaiveDate::from_ymd_opt(1991, 7, 4), false).unwrap();
+        book
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn sample_records_round_trip_byte_for_byte() {
+        let records = from_iso2709(SAMPLE_MRC).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].subfield("245", 'a'), Some("The fellowship of the ring /"));
+        assert_eq!(records[1].subfield("245", 'b'), Some("เรื่องสั้นคัดสรร /"));
+        assert_eq!(records[1].control_value("007"), Some("cr |n|||||||||"));
+        assert_eq!(to_iso2709(&records).unwrap(), SAMPLE_MRC);
+    }
+
+    #[test]
+    fn sample_marcxml_matches_iso2709() {
+        let from_xml = from_marcxml(SAMPLE_XML).unwrap();
+
+        assert_eq!(from_xml, from_iso2709(SAMPLE_MRC).unwrap());
+        assert_eq!(from_marcxml(&to_marcxml(&from_xml).unwrap()).unwrap(), from_xml);
+    }
+
+    #[test]
+    fn book_record_survives_both_formats() {
+        let contributors = vec![
+            BookContributor::new(1, "A01".to_string(), "J.R.R. Tolkien".to_string()).unwrap(),
+            BookContributor::new(2, "A12".to_string(), "Alan Lee".to_string()).unwrap(),
+        ];
+        let subjects = vec![
+            BookSubject::new(SubjectScheme::Bisac, "FIC009020".to_string(), Some("FICTION / Fantasy / Epic".to_string()), true)
+                .unwrap(),
+        ];
+        let record = book_record(&fellowship(), &contributors, &subjects, "BOOKSTORE", date(2026, 10, 19));
+
+        assert_eq!(record.control_value("008").map(|v| v.chars().count()), Some(40));
+        assert_eq!(record.subfield("100", 'a'), Some("Tolkien, J.R.R.,"));
+        assert_eq!(record.subfield("245", 'a'), Some("The Fellowship of the Ring :"));
+        assert_eq!(record.subfield("650", 'a'), Some("FICTION / Fantasy / Epic."));
+
+        let decoded = from_iso2709(&to_iso2709(std::slice::from_ref(&record)).unwrap()).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].fields, record.fields);
+        assert_eq!(&decoded[0].leader[5..12], &record.leader[5..12]);
+        assert_eq!(&decoded[0].leader[17..], &record.leader[17..]);
+
+        let from_xml = from_marcxml(&to_marcxml(&decoded).unwrap()).unwrap();
+        assert_eq!(from_xml, decoded);
+    }
+
+    #[test]
+    fn long_thai_blurb_is_cut_to_fit() {
+        let mut book = fellowship();
+        book.change_description(Some("หนังสือดีมาก ".repeat(700))).unwrap();
+        let record = book_record(&book, &[], &[], "BOOKSTORE", date(2026, 10, 19));
+
+        let summary = record.subfield("520", 'a').unwrap();
+        assert!(summary.len() <= MAX_SUMMARY_BYTES + 3);
+        assert!(summary.ends_with("..."));
+        let decoded = from_iso2709(&to_iso2709(std::slice::from_ref(&record)).unwrap()).unwrap();
+        assert_eq!(decoded[0].subfield("520", 'a').unwrap().len(), summary.len());
+    }
+
+    #[test]
+    fn unreleased_ebook_is_prepublication() {
+        let mut book = fellowship();
+        book.change_publication(None, Some("th".to_string()), BookFormat::Ebook).unwrap();
+        book.change_release(Some(date(2027, 1, 1)), true).unwrap();
+        let record = book_record(&book, &[], &[], "BOOKSTORE", date(2026, 10, 19));
+
+        assert_eq!(record.leader.as_bytes()[17], b'8');
+        assert_eq!(record.control_value("007"), Some("cr |n|||||||||"));
+        assert!(to_iso2709(&[record]).is_ok());
+    }
+
+    #[test]
+    fn rejects_broken_input() {
+        let mut record = from_iso2709(SAMPLE_MRC).unwrap().remove(0);
+        record.fields.push(MarcField::data("500", [' ', ' '], &[('a', "bad\u{1e}note".to_string())]));
+        assert!(to_iso2709(&[record]).is_err());
+
+        assert!(from_iso2709(&SAMPLE_MRC[..100]).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::infrastructure::payment_gateway::{
+    CreatePaymentIntent, PaymentGateway, PaymentIntent, PaymentIntentStatus, PaymentRefund,
+    WebhookEvent, WebhookEventKind,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Magic card numbers understood by the mock (same spirit as PSP test cards)
+pub const CARD_SUCCESS: &str = "4242424242424242";
+pub const CARD_DECLINED: &str = "4000000000000002";
+pub const CARD_INSUFFICIENT_FUNDS: &str = "4000000000009995";
+pub const CARD_REQUIRES_3DS: &str = "4000000000003220";
+
+/// Deterministic in-process PSP for development and CI.
+///
+/// Intent, refund and event ids come from counters, so the same sequence of
+/// calls always yields the same results. Webhook bodies are signed with
+/// HMAC-SHA256 over the raw payload, hex encoded.
+pub struct MockPaymentGateway {
+    webhook_secret: String,
+    state: Mutex<MockState>,
+}
+
+#[derive(Default)]
+struct MockState {
+    next_id: u64,
+    intents: HashMap<String, PaymentIntent>,
+    /// Refunds by idempotency key
+    refunds: HashMap<String, PaymentRefund>,
+}
+
+impl MockState {
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}_mock_{:06}", prefix, self.next_id)
+    }
+}
+
+impl MockPaymentGateway {
+    pub fn new(webhook_secret: &str) -> Self {
+        Self {
+            webhook_secret: webhook_secret.to_string(),
+            state: Mutex::new(MockState::default()),
+        }
+    }
+
+    /// Signs a webhook body the same way the provider would.
+    pub fn sign_payload(&self, payload: &[u8]) -> Result<String> {
+        let mut mac = HmacSha256::new_from_slice(self.webhook_secret.as_bytes())
+            .context("Invalid webhook secret")?;
+        mac.update(payload);
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Simulates the customer finishing 3-D Secure. Returns the signed webhook
+    /// (payload, signature) that the provider would deliver afterwards.
+    pub fn complete_3ds(&self, intent_id: &str, approved: bool) -> Result<(Vec<u8>, String)> {
+        let event = {
+            let mut state = self.state.lock().map_err(|_| anyhow!("Mock gateway state poisoned"))?;
+            let event_id = state.next_id("evt");
+            let intent = state
+                .intents
+                .get_mut(intent_id)
+                .ok_or_else(|| anyhow!("Unknown payment intent {}", intent_id))?;
+
+            if intent.status != PaymentIntentStatus::RequiresAction {
+                bail!("Payment intent {} does not require action", intent_id);
+            }
+
+            intent.next_action_url = None;
+            if approved {
+                intent.status = PaymentIntentStatus::RequiresCapture;
+            } else {
+                intent.status = PaymentIntentStatus::Declined;
+                intent.decline_reason = Some("authentication_failed".to_string());
+            }
+
+            WebhookEvent {
+                id: event_id,
+                kind: if approved {
+                    WebhookEventKind::PaymentAuthorized
+                } else {
+                    WebhookEventKind::PaymentFailed
+                },
+                intent_id: intent.id.clone(),
+                amount: intent.amount,
+                failure_reason: intent.decline_reason.clone(),
+            }
+        };
+
+        let payload = serde_json::to_vec(&event)?;
+        let signature = self.sign_payload(&payload)?;
+        Ok((payload, signature))
+    }
+
+    fn with_intent<T>(
+        &self,
+        intent_id: &str,
+        f: impl FnOnce(&mut MockState, &mut PaymentIntent) -> Result<T>,
+    ) -> Result<T> {
+        let mut state = self.state.lock().map_err(|_| anyhow!("Mock gateway state poisoned"))?;
+        let mut intent = state
+            .intents
+            .remove(intent_id)
+            .ok_or_else(|| anyhow!("Unknown payment intent {}", intent_id))?;
+
+        let result = f(&mut state, &mut intent);
+        state.intents.insert(intent.id.clone(), intent);
+        result
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for MockPaymentGateway {
+    fn provider(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn create_intent(&self, req: &CreatePaymentIntent) -> Result<PaymentIntent> {
+        if req.amount <= 0 {
+            bail!("Amount must be positive");
+        }
+
+        let mut state = self.state.lock().map_err(|_| anyhow!("Mock gateway state poisoned"))?;
+        let id = state.next_id("pi");
+
+        let (status, decline_reason, next_action_url) = match req.payment_method.as_str() {
+            CARD_DECLINED => (PaymentIntentStatus::Declined, Some("card_declined"), None),
+            CARD_INSUFFICIENT_FUNDS => {
+                (PaymentIntentStatus::Declined, Some("insufficient_funds"), None)
+            }
+            CARD_REQUIRES_3DS => (
+                PaymentIntentStatus::RequiresAction,
+                None,
+                Some(format!("https://mock-psp.local/3ds/{}", id)),
+            ),
+            _ => (PaymentIntentStatus::RequiresCapture, None, None),
+        };
+
+        let intent = PaymentIntent {
+            id: id.clone(),
+            status,
+            amount: req.amount,
+            captured_amount: 0,
+            refunded_amount: 0,
+            next_action_url,
+            decline_reason: decline_reason.map(str::to_string),
+        };
+        state.intents.insert(id, intent.clone());
+
+        Ok(intent)
+    }
+
+    async fn capture(&self, intent_id: &str, amount: Option<i64>) -> Result<PaymentIntent> {
+        self.with_intent(intent_id, |_, intent| {
+            if intent.status != PaymentIntentStatus::RequiresCapture {
+                bail!("Payment intent {} cannot be captured", intent.id);
+            }
+
+            let amount = amount.unwrap_or(intent.amount);
+            if amount <= 0 || amount > intent.amount {
+                bail!("Capture amount must be between 1 and the authorized amount");
+            }
+
+            intent.captured_amount = amount;
+            intent.status = PaymentIntentStatus::Succeeded;
+            Ok(intent.clone())
+        })
+    }
+
+    async fn void(&self, intent_id: &str) -> Result<PaymentIntent> {
+        self.with_intent(intent_id, |_, intent| {
+            if !matches!(
+                intent.status,
+                PaymentIntentStatus::RequiresCapture | PaymentIntentStatus::RequiresAction
+            ) {
+                bail!("Payment intent {} cannot be voided", intent.id);
+            }
+
+            intent.status = PaymentIntentStatus::Voided;
+            intent.next_action_url = None;
+            Ok(intent.clone())
+        })
+    }
+
+    async fn refund(&self, intent_id: &str, amount: i64, idempotency_key: &str) -> Result<PaymentRefund> {
+        self.with_intent(intent_id, |state, intent| {
+            if let Some(refund) = state.refunds.get(idempotency_key) {
+                if refund.intent_id != intent.id || refund.amount != amount {
+                    bail!("Idempotency key {} was used for a different refund", idempotency_key);
+                }
+                return Ok(refund.clone());
+            }
+            if intent.status != PaymentIntentStatus::Succeeded {
+                bail!("Payment intent {} has not been captured", intent.id);
+            }
+            if amount <= 0 || intent.refunded_amount + amount > intent.captured_amount {
+                bail!("Refund amount exceeds the captured amount");
+            }
+
+            intent.refunded_amount += amount;
+            let refund = PaymentRefund {
+                id: state.next_id("re"),
+                intent_id: intent.id.clone(),
+                amount,
+            };
+            state.refunds.insert(idempotency_key.to_string(), refund.clone());
+            Ok(refund)
+        })
+    }
+
+    fn verify_webhook(&self, payload: &[u8], signature: &str) -> Result<WebhookEvent> {
+        let signature = hex::decode(signature.trim()).context("Malformed webhook signature")?;
+
+        let mut mac = HmacSha256::new_from_slice(self.webhook_secret.as_bytes())
+            .context("Invalid webhook secret")?;
+        mac.update(payload);
+        // verify_slice เปรียบเทียบแบบ constant-time
+        mac.verify_slice(&signature)
+            .map_err(|_| anyhow!("Invalid webhook signature"))?;
+
+        serde_json::from_slice(payload).context("Malformed webhook payload")
+    }
+}
@@ -1,3 +1,24 @@
 pub mod argon2;
 pub mod config;
 pub mod jwt;
+pub mod mock_payment_gateway;
+pub mod payment_gateway;
+pub mod shipping_rate_provider;
+pub mod table_shipping_rate_provider;
+pub mod notifier;
+pub mod log_notifier;
+pub mod search_index;
+pub mod tantivy_search_index;
+pub mod file_storage;
+pub mod local_file_storage;
+pub mod url_signer;
+pub mod epub_watermark;
+pub mod invoice_pdf;
+pub mod onix_reader;
+pub mod spreadsheet;
+pub mod marc21;
+pub mod raster;
+pub mod cover_image;
+pub mod s3_file_storage;
+pub mod uploaded_image;
+pub mod avatar_image;
@@ -0,0 +1,19 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// A rendered message ready to be delivered to one user.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub to_email: String,
+    pub to_name: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Port to whatever delivers messages to customers (email, push, ...).
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Channel name, for logs
+    fn channel(&self) -> &'static str;
+    async fn send(&self, notification: &Notification) -> Result<()>;
+}
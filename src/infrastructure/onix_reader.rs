@@ -0,0 +1,728 @@
+use std::{
+    fs::File,
+    io::{BufReader, Seek, SeekFrom},
+    path::Path,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::NaiveDate;
+use quick_xml::{events::Event, Reader};
+
+use crate::domain::{
+    entities::{
+        book_metadata::{BookContributor, BookSubject, SubjectScheme},
+        catalog_record::{CatalogRecord, RecordAvailability, RecordPrice},
+    },
+    value_objects::{book_format::BookFormat, dimensions::Dimensions, isbn::Isbn},
+};
+
+/// Short tag → reference name for the composites and elements we read
+const SHORT_TAGS: &[(&str, &str)] = &[
+    ("ONIXmessage", "ONIXMessage"),
+    ("header", "Header"),
+    ("sender", "Sender"),
+    ("x298", "SenderName"),
+    ("m186", "DefaultCurrencyCode"),
+    ("x310", "DefaultPriceType"),
+    ("product", "Product"),
+    ("a001", "RecordReference"),
+    ("a002", "NotificationType"),
+    ("productidentifier", "ProductIdentifier"),
+    ("b221", "ProductIDType"),
+    ("b244", "IDValue"),
+    ("descriptivedetail", "DescriptiveDetail"),
+    ("b012", "ProductForm"),
+    ("titledetail", "TitleDetail"),
+    ("b202", "TitleType"),
+    ("titleelement", "TitleElement"),
+    ("x409", "TitleElementLevel"),
+    ("b030", "TitlePrefix"),
+    ("b031", "TitleWithoutPrefix"),
+    ("b203", "TitleText"),
+    ("b029", "Subtitle"),
+    ("contributor", "Contributor"),
+    ("b034", "SequenceNumber"),
+    ("b035", "ContributorRole"),
+    ("b036", "PersonName"),
+    ("b037", "PersonNameInverted"),
+    ("b039", "NamesBeforeKey"),
+    ("b040", "KeyNames"),
+    ("b047", "CorporateName"),
+    ("language", "Language"),
+    ("b253", "LanguageRole"),
+    ("b252", "LanguageCode"),
+    ("subject", "Subject"),
+    ("x425", "MainSubject"),
+    ("b067", "SubjectSchemeIdentifier"),
+    ("b069", "SubjectCode"),
+    ("b070", "SubjectHeadingText"),
+    ("measure", "Measure"),
+    ("x315", "MeasureType"),
+    ("c094", "Measurement"),
+    ("c095", "MeasureUnitCode"),
+    ("collateraldetail", "CollateralDetail"),
+    ("textcontent", "TextContent"),
+    ("x426", "TextType"),
+    ("d104", "Text"),
+    ("publishingdetail", "PublishingDetail"),
+    ("publisher", "Publisher"),
+    ("b291", "PublishingRole"),
+    ("b081", "PublisherName"),
+    ("b394", "PublishingStatus"),
+    ("publishingdate", "PublishingDate"),
+    ("x448", "PublishingDateRole"),
+    ("b306", "Date"),
+    ("productsupply", "ProductSupply"),
+    ("supplydetail", "SupplyDetail"),
+    ("j396", "ProductAvailability"),
+    ("price", "Price"),
+    ("x462", "PriceType"),
+    ("j151", "PriceAmount"),
+    ("j152", "CurrencyCode"),
+];
+
+/// ISO 639-2 (B and T) → ISO 639-1 for the languages we sell in. Where the
+/// two differ the bibliographic (B) code comes first, as MARC uses it.
+pub(crate) const LANGUAGES: &[(&str, &str)] = &[
+    ("tha", "th"), ("eng", "en"), ("jpn", "ja"), ("chi", "zh"), ("zho", "zh"),
+    ("kor", "ko"), ("fre", "fr"), ("fra", "fr"), ("ger", "de"), ("deu", "de"),
+    ("spa", "es"), ("ita", "it"), ("por", "pt"), ("rus", "ru"), ("vie", "vi"),
+    ("ind", "id"), ("may", "ms"), ("msa", "ms"), ("lao", "lo"), ("bur", "my"),
+    ("mya", "my"), ("khm", "km"), ("ara", "ar"), ("hin", "hi"), ("dut", "nl"),
+    ("nld", "nl"), ("swe", "sv"), ("dan", "da"), ("nor", "no"), ("fin", "fi"),
+    ("pol", "pl"), ("tur", "tr"), ("gre", "el"), ("ell", "el"), ("heb", "he"),
+    ("tgl", "tl"), ("lat", "la"),
+];
+
+/// Price types (list 58) we take: RRP / fixed retail, with tax first
+const TAX_INCLUSIVE_PRICE_TYPES: &[&str] = &["02", "04"];
+const TAX_EXCLUSIVE_PRICE_TYPES: &[&str] = &["01", "03"];
+
+/// One `<Product>` and where it sits in the file
+#[derive(Debug)]
+pub struct OnixProduct {
+    /// Byte offset of the product's start tag
+    pub offset: u64,
+    /// Byte offset just past its end tag; resuming here skips it
+    pub end_offset: u64,
+    pub record: CatalogRecord,
+}
+
+/// Header values that apply to every product
+#[derive(Debug, Clone, Default)]
+struct OnixHeader {
+    sender: Option<String>,
+    default_currency: Option<String>,
+    default_price_type: Option<String>,
+}
+
+/// Streams products out of an ONIX 3.0 message one at a time, so memory
+/// stays flat however large the file is. Reference and short tags are both
+/// understood; namespaces are ignored.
+///
+/// A reader opened at a product's offset carries on from there, which is
+/// how an interrupted import resumes.
+pub struct OnixReader {
+    reader: Reader<BufReader<File>>,
+    /// File offset the current `reader` started at
+    base_offset: u64,
+    header: OnixHeader,
+    buf: Vec<u8>,
+    /// Start tag of the first product, read while looking for the header
+    first_product: Option<u64>,
+    /// `release` attribute of the root element
+    root_release: Option<String>,
+}
+
+impl OnixReader {
+    /// Reads the header, then positions at `resume_at` (0 = first product)
+    pub fn open(path: &Path, resume_at: u64) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut this = Self {
+            reader: xml_reader(file),
+            base_offset: 0,
+            header: OnixHeader::default(),
+            buf: Vec::new(),
+            first_product: None,
+            root_release: None,
+        };
+        this.read_header()?;
+
+        if resume_at > 0 {
+            let mut file = File::open(path)?;
+            file.seek(SeekFrom::Start(resume_at))?;
+            this.reader = xml_reader(file);
+            // ต่อกลางไฟล์ ไม่เห็น start tag ของ ONIXMessage จึงต้องยอมให้ end tag ของมันไม่มีคู่
+            let config = this.reader.config_mut();
+            config.check_end_names = false;
+            config.allow_unmatched_ends = true;
+            this.base_offset = resume_at;
+            this.first_product = None;
+        }
+        Ok(this)
+    }
+
+    /// `SenderName` from the header
+    pub fn sender(&self) -> Option<&str> {
+        self.header.sender.as_deref()
+    }
+
+    /// The next product, or `None` at the end of the message. Errors only
+    /// for XML that cannot be parsed; bad data ends up in the record.
+    pub fn next_product(&mut self) -> Result<Option<OnixProduct>> {
+        loop {
+            let offset = match self.first_product.take() {
+                Some(offset) => offset,
+                None => {
+                    let before = self.position();
+                    match self.next_event()? {
+                        Some(Tag::Start(name)) if name == "Product" => before,
+                        Some(Tag::Start(_)) => {
+                            // องค์ประกอบอื่นระดับบนสุดไม่ใช่สิ่งที่เราอ่าน ข้ามไปทั้งก้อน
+                            self.read_node(String::new())?;
+                            continue;
+                        }
+                        Some(_) => continue,
+                        None => return Ok(None),
+                    }
+                }
+            };
+
+            let node = self.read_node("Product".to_string())?;
+            let record = map_product(&node, &self.header);
+            return Ok(Some(OnixProduct {
+                offset,
+                end_offset: self.position(),
+                record,
+            }));
+        }
+    }
+
+    fn read_header(&mut self) -> Result<()> {
+        // ข้าม XML declaration, DOCTYPE, comment จนถึง root element
+        loop {
+            match self.next_event()? {
+                Some(Tag::Start(name)) if name == "ONIXMessage" => break,
+                Some(Tag::Text(_)) => {}
+                _ => bail!("Not an ONIX message: the root element must be ONIXMessage"),
+            }
+        }
+        let release = self.root_release.take();
+        if !release.as_deref().is_some_and(|r| r.starts_with("3.")) {
+            bail!(
+                "Only ONIX 3.0 is supported (release {})",
+                release.as_deref().unwrap_or("not given")
+            );
+        }
+
+        loop {
+            let before = self.position();
+            match self.next_event()? {
+                Some(Tag::Start(name)) if name == "Header" => {
+                    let node = self.read_node(name)?;
+                    self.header = OnixHeader {
+                        sender: node.child("Sender").and_then(|s| s.text_of("SenderName")),
+                        default_currency: node.text_of("DefaultCurrencyCode"),
+                        default_price_type: node.text_of("DefaultPriceType"),
+                    };
+                }
+                Some(Tag::Start(name)) if name == "Product" => {
+                    self.first_product = Some(before);
+                    return Ok(());
+                }
+                Some(Tag::Start(name)) => {
+                    self.read_node(name)?;
+                }
+                Some(_) => {}
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Reads the children of the element whose start tag was just read
+    fn read_node(&mut self, name: String) -> Result<Node> {
+        let mut stack = vec![Node::new(name)];
+        loop {
+            match self.next_event()? {
+                Some(Tag::Start(name)) => stack.push(Node::new(name)),
+                Some(Tag::Empty(name)) => {
+                    let parent = stack.last_mut().expect("stack never empty");
+                    if is_block(&name) {
+                        parent.content.push('\n');
+                    }
+                    parent.children.push(Node::new(name));
+                }
+                Some(Tag::Text(text)) => {
+                    let node = stack.last_mut().expect("stack never empty");
+                    node.text.push_str(&text);
+                    node.content.push_str(&text);
+                }
+                Some(Tag::End) => {
+                    let node = stack.pop().expect("stack never empty");
+                    let Some(parent) = stack.last_mut() else {
+                        return Ok(node);
+                    };
+                    if is_block(&node.name) {
+                        parent.content.push('\n');
+                        parent.content.push_str(&node.content);
+                        parent.content.push('\n');
+                    } else {
+                        parent.content.push_str(&node.content);
+                    }
+                    parent.children.push(node);
+                }
+                None => bail!("Unexpected end of file inside <{}>", stack[0].name),
+            }
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<Tag>> {
+        self.buf.clear();
+        let event = self.reader.read_event_into(&mut self.buf).map_err(|e| {
+            anyhow!(
+                "Malformed XML near byte {}: {}",
+                self.base_offset + self.reader.error_position(),
+                e
+            )
+        })?;
+
+        let tag = match event {
+            Event::Start(e) => {
+                let name = reference_name(&String::from_utf8_lossy(e.local_name().as_ref()));
+                if name == "ONIXMessage" {
+                    self.root_release = e
+                        .try_get_attribute("release")
+                        .ok()
+                        .flatten()
+                        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()));
+                }
+                Tag::Start(name)
+            }
+            Event::Empty(e) => Tag::Empty(reference_name(&String::from_utf8_lossy(e.local_name().as_ref()))),
+            Event::End(_) => Tag::End,
+            // entity ที่ XML ไม่รู้จัก (เช่น &nbsp; จาก HTML) เก็บแบบดิบไว้ ไม่ทำให้ทั้งไฟล์ล้ม
+            Event::Text(t) => Tag::Text(
+                t.unescape()
+                    .map(|c| c.into_owned())
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&t).into_owned()),
+            ),
+            Event::CData(c) => Tag::Text(String::from_utf8_lossy(&c).into_owned()),
+            Event::Eof => return Ok(None),
+            _ => Tag::Text(String::new()),
+        };
+        Ok(Some(tag))
+    }
+
+    fn position(&self) -> u64 {
+        self.base_offset + self.reader.buffer_position()
+    }
+}
+
+fn xml_reader(file: File) -> Reader<BufReader<File>> {
+    Reader::from_reader(BufReader::with_capacity(64 * 1024, file))
+}
+
+enum Tag {
+    Start(String),
+    Empty(String),
+    End,
+    Text(String),
+}
+
+/// XHTML elements that start a new line in a blurb
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "div" | "br" | "li" | "ul" | "ol" | "dl" | "dt" | "dd" | "blockquote"
+            | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "tr"
+    )
+}
+
+fn reference_name(tag: &str) -> String {
+    SHORT_TAGS
+        .iter()
+        .find(|(short, _)| *short == tag)
+        .map(|(_, reference)| reference.to_string())
+        .unwrap_or_else(|| tag.to_string())
+}
+
+/// A product (or header) held in memory while it is mapped
+#[derive(Debug)]
+struct Node {
+    name: String,
+    /// The element's own text
+    text: String,
+    /// Its text and its descendants', in document order (XHTML blurbs)
+    content: String,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            text: String::new(),
+            content: String::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn child(&self, name: &str) -> Option<&Node> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Node> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.child(name).is_some()
+    }
+
+    /// Trimmed text of a child element, `None` when missing or blank
+    fn text_of(&self, name: &str) -> Option<String> {
+        self.child(name)
+            .map(|c| c.text.trim().to_string())
+            .filter(|t| !t.is_empty())
+    }
+}
+
+fn map_product(product: &Node, header: &OnixHeader) -> CatalogRecord {
+    let mut record = CatalogRecord {
+        reference: product.text_of("RecordReference"),
+        delete: product.text_of("NotificationType").as_deref() == Some("05"),
+        ..Default::default()
+    };
+
+    map_identifier(product, &mut record);
+    if let Some(detail) = product.child("DescriptiveDetail") {
+        map_descriptive_detail(detail, &mut record);
+    }
+    if let Some(collateral) = product.child("CollateralDetail") {
+        record.description = description(collateral);
+    }
+    if let Some(publishing) = product.child("PublishingDetail") {
+        map_publishing_detail(publishing, &mut record);
+    }
+    let mut prices = Vec::new();
+    for supply in product.children("ProductSupply") {
+        map_supply(supply, header, &mut record, &mut prices);
+    }
+    // ราคาที่รวมภาษีมาก่อน ราคาแรกของแต่ละสกุลเงินคือราคาที่ใช้
+    prices.sort_by_key(|(_, inclusive)| !*inclusive);
+    record.prices = prices.into_iter().map(|(price, _)| price).collect();
+    record
+}
+
+fn map_identifier(product: &Node, record: &mut CatalogRecord) {
+    let identifiers: Vec<(String, String)> = product
+        .children("ProductIdentifier")
+        .filter_map(|id| Some((id.text_of("ProductIDType")?, id.text_of("IDValue")?)))
+        .collect();
+    // 15 = ISBN-13, 03 = GTIN-13 (ISBN ขึ้นต้น 978/979), 02 = ISBN-10
+    let value = ["15", "03", "02"].iter().find_map(|kind| {
+        identifiers
+            .iter()
+            .find(|(t, v)| t == kind && (*kind != "03" || v.starts_with("978") || v.starts_with("979")))
+            .map(|(_, v)| v.clone())
+    });
+    let Some(value) = value else {
+        return;
+    };
+
+    match Isbn::new(&value) {
+        Ok(isbn) => record.isbn = Some(isbn.as_str().to_string()),
+        Err(e) => {
+            record.isbn = Some(value.clone());
+            record.errors.push(format!("Invalid ISBN {}: {}", value, e));
+        }
+    }
+}
+
+fn map_descriptive_detail(detail: &Node, record: &mut CatalogRecord) {
+    if let Some(form) = detail.text_of("ProductForm") {
+        match product_form(&form) {
+            Some(format) => record.format = Some(format),
+            None => record.errors.push(format!("Unsupported product form {}", form)),
+        }
+    }
+
+    record.title = detail
+        .children("TitleDetail")
+        .find(|t| t.text_of("TitleType").as_deref() == Some("01"))
+        .and_then(|t| {
+            t.children("TitleElement")
+                .find(|e| e.text_of("TitleElementLevel").as_deref() == Some("01"))
+                .or_else(|| t.child("TitleElement"))
+        })
+        .and_then(title);
+
+    let mut contributors: Vec<(i32, &Node)> = detail
+        .children("Contributor")
+        .enumerate()
+        .map(|(i, c)| {
+            let sequence = c.text_of("SequenceNumber").and_then(|s| s.parse().ok());
+            (sequence.unwrap_or(i as i32 + 1), c)
+        })
+        .collect();
+    contributors.sort_by_key(|(sequence, _)| *sequence);
+    for (contributor, sequence) in contributors.into_iter().map(|(_, c)| c).zip(1..) {
+        let role = contributor.text_of("ContributorRole").unwrap_or_default();
+        let Some(name) = contributor_name(contributor) else {
+            record.warnings.push(format!("Contributor {} has no name; skipped", sequence));
+            continue;
+        };
+        match BookContributor::new(sequence, role, name) {
+            Ok(c) => record.contributors.push(c),
+            Err(e) => record.warnings.push(format!("Contributor skipped: {}", e)),
+        }
+    }
+
+    // 01 = ภาษาของเนื้อหา
+    if let Some(code) = detail
+        .children("Language")
+        .find(|l| l.text_of("LanguageRole").as_deref() == Some("01"))
+        .and_then(|l| l.text_of("LanguageCode"))
+    {
+        let code = code.to_lowercase();
+        match LANGUAGES.iter().find(|(iso3, _)| *iso3 == code) {
+            Some((_, iso1)) => record.language = Some(iso1.to_string()),
+            None => record
+                .warnings
+                .push(format!("Language {} has no ISO 639-1 code we use; skipped", code)),
+        }
+    }
+
+    for subject in detail.children("Subject") {
+        let scheme = match subject.text_of("SubjectSchemeIdentifier").as_deref() {
+            Some("10") => SubjectScheme::Bisac,
+            Some("93" | "94" | "95" | "96" | "97" | "98" | "99") => SubjectScheme::Thema,
+            _ => continue,
+        };
+        let Some(code) = subject.text_of("SubjectCode") else {
+            continue;
+        };
+        match BookSubject::new(scheme, code, subject.text_of("SubjectHeadingText"), subject.has("MainSubject")) {
+            Ok(s) => record.subjects.push(s),
+            Err(e) => record.warnings.push(format!("Subject skipped: {}", e)),
+        }
+    }
+
+    map_measures(detail, record);
+}
+
+fn title(element: &Node) -> Option<String> {
+    let main = match (element.text_of("TitlePrefix"), element.text_of("TitleWithoutPrefix")) {
+        (Some(prefix), Some(rest)) => format!("{} {}", prefix, rest),
+        (None, Some(rest)) => rest,
+        _ => element.text_of("TitleText")?,
+    };
+    Some(match element.text_of("Subtitle") {
+        Some(subtitle) => format!("{}: {}", main, subtitle),
+        None => main,
+    })
+}
+
+fn contributor_name(contributor: &Node) -> Option<String> {
+    contributor
+        .text_of("PersonName")
+        .or_else(|| match (contributor.text_of("NamesBeforeKey"), contributor.text_of("KeyNames")) {
+            (Some(before), Some(key)) => Some(format!("{} {}", before, key)),
+            (None, Some(key)) => Some(key),
+            _ => None,
+        })
+        .or_else(|| contributor.text_of("PersonNameInverted").map(|n| uninvert(&n)))
+        .or_else(|| contributor.text_of("CorporateName"))
+}
+
+/// "Orwell, George" → "George Orwell"; anything else is kept as sent
+fn uninvert(name: &str) -> String {
+    match name.split_once(',') {
+        Some((key, before)) if !before.contains(',') && !before.trim().is_empty() => {
+            format!("{} {}", before.trim(), key.trim())
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// ONIX list 150
+fn product_form(code: &str) -> Option<BookFormat> {
+    match code {
+        "BB" => Some(BookFormat::Hardcover),
+        "BA" | "BC" | "BE" | "BF" | "BH" | "BZ" => Some(BookFormat::Paperback),
+        "AJ" | "AN" | "AC" | "AE" => Some(BookFormat::Audiobook),
+        c if c.starts_with('E') => Some(BookFormat::Ebook),
+        _ => None,
+    }
+}
+
+/// Height, width and thickness as dimensions; weight in grams
+fn map_measures(detail: &Node, record: &mut CatalogRecord) {
+    let mut height = None;
+    let mut width = None;
+    let mut thickness = None;
+    for measure in detail.children("Measure") {
+        let (Some(kind), Some(value), Some(unit)) = (
+            measure.text_of("MeasureType"),
+            measure.text_of("Measurement").and_then(|v| v.parse::<f64>().ok()),
+            measure.text_of("MeasureUnitCode"),
+        ) else {
+            continue;
+        };
+        let factor = match unit.as_str() {
+            "mm" => 1.0,
+            "cm" => 10.0,
+            "in" => 25.4,
+            "gr" => 1.0,
+            "kg" => 1000.0,
+            "oz" => 28.3495,
+            "lb" => 453.592,
+            _ => {
+                record.warnings.push(format!("Unknown measure unit {}; skipped", unit));
+                continue;
+            }
+        };
+        let converted = Some((value * factor).round() as i32);
+        match kind.as_str() {
+            "01" => height = converted,
+            "02" => width = converted,
+            "03" => thickness = converted,
+            "08" => record.weight_grams = converted.filter(|w| *w > 0),
+            _ => {}
+        }
+    }
+
+    if let (Some(height), Some(width), Some(thickness)) = (height, width, thickness) {
+        match Dimensions::new(height, width, thickness) {
+            Ok(d) => record.dimensions = Some(d),
+            Err(e) => record.warnings.push(format!("Dimensions skipped: {}", e)),
+        }
+    }
+}
+
+/// Description (03), or the short description (02) when that is all there is
+fn description(collateral: &Node) -> Option<String> {
+    let text_of_type = |kind: &str| {
+        collateral
+            .children("TextContent")
+            .find(|t| t.text_of("TextType").as_deref() == Some(kind))
+            .and_then(|t| t.child("Text"))
+            .map(|t| strip_markup(&t.content))
+            .filter(|t| !t.is_empty())
+    };
+    text_of_type("03").or_else(|| text_of_type("02"))
+}
+
+/// Drops (escaped) HTML tags and squeezes blank runs; paragraphs stay apart
+fn strip_markup(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut tag: Option<String> = None;
+    for c in text.chars() {
+        match (&mut tag, c) {
+            (None, '<') => tag = Some(String::new()),
+            (Some(name), '>') => {
+                let name = name.trim_start_matches('/').split([' ', '/']).next().unwrap_or_default();
+                if is_block(&name.to_lowercase()) {
+                    plain.push('\n');
+                }
+                tag = None;
+            }
+            (Some(name), c) => name.push(c),
+            (None, c) => plain.push(c),
+        }
+    }
+
+    plain
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn map_publishing_detail(publishing: &Node, record: &mut CatalogRecord) {
+    record.publisher = publishing
+        .children("Publisher")
+        .find(|p| p.text_of("PublishingRole").as_deref() == Some("01"))
+        .or_else(|| publishing.child("Publisher"))
+        .and_then(|p| p.text_of("PublisherName"));
+
+    // 01 = วันวางจำหน่าย
+    if let Some(date) = publishing
+        .children("PublishingDate")
+        .find(|d| d.text_of("PublishingDateRole").as_deref() == Some("01"))
+        .and_then(|d| d.text_of("Date"))
+    {
+        match parse_date(&date) {
+            Some(date) => record.release_date = Some(date),
+            None => record.warnings.push(format!("Publication date {} not understood; skipped", date)),
+        }
+    }
+
+    // list 64; ProductSupply มีผลเหนือกว่าถ้าระบุ availability ไว้
+    record.availability = match publishing.text_of("PublishingStatus").as_deref() {
+        Some("02") => Some(RecordAvailability::Preorder),
+        Some("04") => Some(RecordAvailability::Available),
+        Some("06" | "07" | "08" | "11") => Some(RecordAvailability::Unavailable),
+        _ => None,
+    };
+}
+
+fn map_supply(
+    supply: &Node,
+    header: &OnixHeader,
+    record: &mut CatalogRecord,
+    prices: &mut Vec<(RecordPrice, bool)>,
+) {
+    for detail in supply.children("SupplyDetail") {
+        // list 65
+        if let Some(code) = detail.text_of("ProductAvailability") {
+            let availability = match code.as_str() {
+                "10" | "11" | "12" => Some(RecordAvailability::Preorder),
+                c if c.starts_with('2') || c.starts_with('3') => Some(RecordAvailability::Available),
+                c if c.starts_with('4') || c.starts_with('5') => Some(RecordAvailability::Unavailable),
+                _ => None,
+            };
+            if availability.is_some() {
+                record.availability = availability;
+            }
+        }
+
+        for price in detail.children("Price") {
+            let Some(kind) = price
+                .text_of("PriceType")
+                .or_else(|| header.default_price_type.clone())
+            else {
+                continue;
+            };
+            let inclusive = TAX_INCLUSIVE_PRICE_TYPES.contains(&kind.as_str());
+            if !inclusive && !TAX_EXCLUSIVE_PRICE_TYPES.contains(&kind.as_str()) {
+                continue;
+            }
+            let Some(amount) = price.text_of("PriceAmount") else {
+                continue;
+            };
+            let Some(currency) = price
+                .text_of("CurrencyCode")
+                .or_else(|| header.default_currency.clone())
+            else {
+                record.warnings.push(format!("Price {} has no currency; skipped", amount));
+                continue;
+            };
+            prices.push((RecordPrice { amount, currency }, inclusive));
+        }
+    }
+}
+
+/// YYYYMMDD, YYYY-MM-DD, or YYYYMM / YYYY (first day of the period)
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+    let part = |range: std::ops::Range<usize>| digits.get(range)?.parse::<u32>().ok();
+    let year = part(0..4)? as i32;
+    match digits.len() {
+        8 => NaiveDate::from_ymd_opt(year, part(4..6)?, part(6..8)?),
+        6 => NaiveDate::from_ymd_opt(year, part(4..6)?, 1),
+        4 => NaiveDate::from_ymd_opt(year, 1, 1),
+        _ => None,
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Status of a payment intent as reported by the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentIntentStatus {
+    /// Authorized; funds are held until captured or voided
+    RequiresCapture,
+    /// Customer must complete an extra step (e.g. 3-D Secure)
+    RequiresAction,
+    Succeeded,
+    Declined,
+    Voided,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePaymentIntent {
+    /// Amount in minor units
+    pub amount: i64,
+    pub currency: String,
+    /// Tokenized payment method from the client-side SDK
+    pub payment_method: String,
+    /// Our reference for the payment (e.g. the order id)
+    pub reference: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaymentIntent {
+    pub id: String,
+    pub status: PaymentIntentStatus,
+    pub amount: i64,
+    pub captured_amount: i64,
+    pub refunded_amount: i64,
+    /// Where to redirect the customer when `status` is `RequiresAction`
+    pub next_action_url: Option<String>,
+    pub decline_reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaymentRefund {
+    pub id: String,
+    pub intent_id: String,
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEventKind {
+    /// Authorization completed asynchronously (e.g. after 3-D Secure)
+    #[serde(rename = "payment_intent.authorized")]
+    PaymentAuthorized,
+    #[serde(rename = "payment_intent.succeeded")]
+    PaymentSucceeded,
+    #[serde(rename = "payment_intent.payment_failed")]
+    PaymentFailed,
+}
+
+impl WebhookEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PaymentAuthorized => "payment_intent.authorized",
+            Self::PaymentSucceeded => "payment_intent.succeeded",
+            Self::PaymentFailed => "payment_intent.payment_failed",
+        }
+    }
+}
+
+/// A verified webhook notification from the provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: WebhookEventKind,
+    pub intent_id: String,
+    pub amount: i64,
+    pub failure_reason: Option<String>,
+}
+
+/// Port to a payment service provider (PSP).
+#[async_trait]
+pub trait PaymentGateway: Send + Sync {
+    /// Provider name stored with every payment attempt
+    fn provider(&self) -> &'static str;
+    /// Authorizes the payment without capturing it
+    async fn create_intent(&self, req: &CreatePaymentIntent) -> Result<PaymentIntent>;
+    /// Captures an authorized intent; `amount` may be lower than authorized
+    async fn capture(&self, intent_id: &str, amount: Option<i64>) -> Result<PaymentIntent>;
+    /// Releases an authorization that has not been captured
+    async fn void(&self, intent_id: &str) -> Result<PaymentIntent>;
+    /// Refunds (part of) a captured intent. A repeated call with the same
+    /// `idempotency_key` returns the first refund instead of paying again.
+    async fn refund(&self, intent_id: &str, amount: i64, idempotency_key: &str) -> Result<PaymentRefund>;
+    /// Checks the signature and parses the webhook body
+    fn verify_webhook(&self, payload: &[u8], signature: &str) -> Result<WebhookEvent>;
+}
@@ -0,0 +1,78 @@
+use anyhow::{anyhow, Result};
+use image::{
+    codecs::jpeg::JpegEncoder,
+    imageops::{self, FilterType},
+    RgbImage, RgbaImage,
+};
+
+// Resizing and encoding of decoded pictures. The codecs are the `image`,
+// `webp` (libwebp) and `blurhash` crates; this module only fixes how they
+// are called for covers and avatars.
+
+/// Size that fits within `max_edge` on both sides, never larger than now
+pub fn fit_within(image: &RgbaImage, max_edge: u32) -> (u32, u32) {
+    let (width, height) = image.dimensions();
+    let longest = width.max(height);
+    if longest <= max_edge {
+        return (width, height);
+    }
+    let scale = max_edge as f64 / longest as f64;
+    (
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    )
+}
+
+/// Resampled with a triangle filter widened to the scale factor, so
+/// shrinking averages every source pixel instead of skipping some
+pub fn resized(image: &RgbaImage, width: u32, height: u32) -> RgbaImage {
+    if image.dimensions() == (width, height) {
+        return image.clone();
+    }
+    imageops::resize(image, width, height, FilterType::Triangle)
+}
+
+/// The largest centred square, for pictures shown in a square frame
+pub fn center_square(image: &RgbaImage) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let edge = width.min(height);
+    imageops::crop_imm(image, (width - edge) / 2, (height - edge) / 2, edge, edge).to_image()
+}
+
+/// Transparent areas painted white, for output without alpha
+fn flattened(image: &RgbaImage) -> RgbImage {
+    RgbImage::from_fn(image.width(), image.height(), |x, y| {
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        let alpha = a as u32;
+        let over_white = |c: u8| ((c as u32 * alpha + 255 * (255 - alpha) + 127) / 255) as u8;
+        image::Rgb([over_white(r), over_white(g), over_white(b)])
+    })
+}
+
+/// Baseline JPEG at `quality` 1–100. Transparent areas come out white;
+/// nothing but the pixels is written (no EXIF, no ICC).
+pub fn encode_jpeg(image: &RgbaImage, quality: u8) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    JpegEncoder::new_with_quality(&mut out, quality.clamp(1, 100))
+        .encode_image(&flattened(image))
+        .map_err(|e| anyhow!("JPEG encoding failed: {}", e))?;
+    Ok(out)
+}
+
+/// Lossy WebP at `quality` 0–100. Transparent areas come out white and no
+/// metadata is written.
+pub fn encode_webp(image: &RgbaImage, quality: u8) -> Result<Vec<u8>> {
+    let rgb = flattened(image);
+    let encoded = webp::Encoder::from_rgb(rgb.as_raw(), rgb.width(), rgb.height())
+        .encode_simple(false, quality.min(100) as f32)
+        .map_err(|e| anyhow!("WebP encoding failed: {:?}", e))?;
+    Ok(encoded.to_vec())
+}
+
+/// BlurHash (https://blurha.sh) with `x_components` × `y_components` (each
+/// 1–9). The picture should already be small; every pixel is visited per
+/// component.
+pub fn blurhash(image: &RgbaImage, x_components: u32, y_components: u32) -> Result<String> {
+    blurhash::encode(x_components, y_components, image.width(), image.height(), image.as_raw())
+        .map_err(|e| anyhow!("BlurHash encoding failed: {}", e))
+}
@@ -0,0 +1,248 @@
+use std::{io::SeekFrom, sync::Arc};
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+use object_store::{
+    aws::AmazonS3Builder, buffered::BufWriter, path::Path, Attribute, Attributes, GetOptions,
+    GetRange, ObjectStore, PutOptions, PutPayload,
+};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+use crate::domain::value_objects::byte_range::ByteRange;
+use crate::infrastructure::file_storage::{ByteStream, FileStorage};
+
+/// Connection settings of an S3-compatible service (AWS S3, MinIO, R2, ...)
+#[derive(Debug, Clone)]
+pub struct S3Settings {
+    /// e.g. https://s3.ap-southeast-1.amazonaws.com or http://localhost:9000
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// `endpoint/bucket/key` instead of `bucket.endpoint/key`; MinIO needs this
+    pub path_style: bool,
+}
+
+/// Stores objects in an S3 bucket through the `object_store` crate, which
+/// signs (SigV4), retries and pools connections.
+pub struct S3FileStorage {
+    store: Arc<dyn ObjectStore>,
+}
+
+impl S3FileStorage {
+    pub fn new(settings: S3Settings) -> Result<Self> {
+        let (scheme, authority) = settings
+            .endpoint
+            .split_once("://")
+            .filter(|(scheme, _)| *scheme == "http" || *scheme == "https")
+            .ok_or_else(|| anyhow!("S3 endpoint must start with http:// or https://"))?;
+        let authority = authority.trim_end_matches('/');
+        if authority.is_empty() || authority.contains('/') {
+            bail!("S3 endpoint must not have a path: {}", settings.endpoint);
+        }
+
+        // virtual-hosted style: object_store อยากได้ endpoint ที่มีชื่อ bucket อยู่แล้ว
+        let endpoint = if settings.path_style {
+            format!("{}://{}", scheme, authority)
+        } else {
+            format!("{}://{}.{}", scheme, settings.bucket, authority)
+        };
+
+        let store = AmazonS3Builder::new()
+            .with_endpoint(endpoint)
+            .with_region(&settings.region)
+            .with_bucket_name(&settings.bucket)
+            .with_access_key_id(&settings.access_key_id)
+            .with_secret_access_key(&settings.secret_access_key)
+            .with_virtual_hosted_style_request(!settings.path_style)
+            .with_allow_http(scheme == "http")
+            .build()
+            .context("Invalid S3 settings")?;
+
+        Ok(Self { store: Arc::new(store) })
+    }
+
+    fn content_type(content_type: &str) -> Attributes {
+        let mut attributes = Attributes::new();
+        attributes.insert(Attribute::ContentType, content_type.to_string().into());
+        attributes
+    }
+
+    fn path_for(key: &str) -> Result<Path> {
+        if key.is_empty() || key.starts_with('/') {
+            return Err(anyhow!("Invalid storage key: {}", key));
+        }
+        Path::parse(key).map_err(|_| anyhow!("Invalid storage key: {}", key))
+    }
+}
+
+#[async_trait]
+impl FileStorage for S3FileStorage {
+    fn backend(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn put(&self, key: &str, content: &[u8], content_type: &str) -> Result<()> {
+        let options = PutOptions {
+            attributes: Self::content_type(content_type),
+            ..PutOptions::default()
+        };
+
+        self.store
+            .put_opts(&Self::path_for(key)?, PutPayload::from(content.to_vec()), options)
+            .await
+            .map_err(|e| anyhow!("S3 upload of {} failed: {}", key, e))?;
+        Ok(())
+    }
+
+    async fn put_file(&self, key: &str, mut file: tokio::fs::File, content_type: &str) -> Result<()> {
+        file.seek(SeekFrom::Start(0)).await?;
+        // ไฟล์เล็กส่งทีเดียว ไฟล์ใหญ่ BufWriter เปลี่ยนเป็น multipart upload เอง
+        let mut writer = BufWriter::new(self.store.clone(), Self::path_for(key)?)
+            .with_attributes(Self::content_type(content_type));
+        let copied = tokio::io::copy(&mut file, &mut writer).await;
+        let result = match copied {
+            Ok(_) => writer.shutdown().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            if let Err(abort) = writer.abort().await {
+                tracing::warn!(key = %key, "Failed to abort S3 upload: {}", abort);
+            }
+            return Err(anyhow!("S3 upload of {} failed: {}", key, e));
+        }
+        Ok(())
+    }
+
+    async fn open(&self, key: &str, range: Option<ByteRange>) -> Result<ByteStream> {
+        let options = GetOptions {
+            range: range.map(|r| GetRange::Bounded(r.start..r.end + 1)),
+            ..GetOptions::default()
+        };
+        let result = match self.store.get_opts(&Self::path_for(key)?, options).await {
+            Ok(result) => result,
+            Err(object_store::Error::NotFound { .. }) => return Err(anyhow!("Stored file not found: {}", key)),
+            Err(e) => return Err(anyhow!("S3 download of {} failed: {}", key, e)),
+        };
+
+        // GetRange::Bounded ยอมคืนสั้นกว่าที่ขอถ้าเกินท้ายไฟล์
+        if let Some(range) = range
+            && result.range != (range.start..range.end + 1)
+        {
+            return Err(anyhow!("Range {}-{} is beyond {}", range.start, range.end, key));
+        }
+        Ok(result.into_stream().map_err(std::io::Error::other).boxed())
+    }
+
+    async fn size(&self, key: &str) -> Result<Option<u64>> {
+        match self.store.head(&Self::path_for(key)?).await {
+            Ok(meta) => Ok(Some(meta.size)),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(anyhow!("S3 lookup of {} failed: {}", key, e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self.store.delete(&Self::path_for(key)?).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(anyhow!("S3 delete of {} failed: {}", key, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    async fn read_all(stream: ByteStream) -> Vec<u8> {
+        let chunks: Vec<_> = stream.try_collect().await.unwrap();
+        chunks.concat()
+    }
+
+    fn settings(endpoint: &str) -> S3Settings {
+        S3Settings {
+            endpoint: endpoint.to_string(),
+            region: "us-east-1".to_string(),
+            bucket: "bookstore".to_string(),
+            access_key_id: "minioadmin".to_string(),
+            secret_access_key: "minioadmin".to_string(),
+            path_style: true,
+        }
+    }
+
+    #[test]
+    fn refuses_endpoint_without_scheme_or_with_path() {
+        assert!(S3FileStorage::new(settings("localhost:9000")).is_err());
+        assert!(S3FileStorage::new(settings("ftp://localhost:9000")).is_err());
+        assert!(S3FileStorage::new(settings("http://localhost:9000/bookstore")).is_err());
+        assert!(S3FileStorage::new(settings("http://localhost:9000/")).is_ok());
+    }
+
+    #[test]
+    fn refuses_keys_that_are_not_plain_paths() {
+        assert!(S3FileStorage::path_for("").is_err());
+        assert!(S3FileStorage::path_for("/covers/1.webp").is_err());
+        assert!(S3FileStorage::path_for("covers/../1.webp").is_err());
+        assert!(S3FileStorage::path_for("covers//1.webp").is_err());
+        assert!(S3FileStorage::path_for("covers/12/large-ab12.webp").is_ok());
+    }
+
+    /// Runs against a real S3-compatible server; MinIO in a container will do:
+    ///
+    /// ```text
+    /// docker run -d -p 9000:9000 -e MINIO_ROOT_USER=minioadmin -e MINIO_ROOT_PASSWORD=minioadmin \
+    ///   minio/minio server /data
+    /// docker run --rm --network host --entrypoint sh minio/mc -c \
+    ///   "mc alias set local http://localhost:9000 minioadmin minioadmin && mc mb -p local/bookstore-test"
+    /// S3_TEST_ENDPOINT=http://localhost:9000 cargo test s3_file_storage -- --ignored
+    /// ```
+    ///
+    /// S3_TEST_BUCKET, S3_TEST_ACCESS_KEY_ID and S3_TEST_SECRET_ACCESS_KEY
+    /// override the defaults above.
+    #[tokio::test]
+    #[ignore = "needs an S3-compatible server, see the doc comment"]
+    async fn round_trip_against_s3_compatible_server() {
+        let env = |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_string());
+        let storage = S3FileStorage::new(S3Settings {
+            endpoint: env("S3_TEST_ENDPOINT", "http://localhost:9000"),
+            region: env("S3_TEST_REGION", "us-east-1"),
+            bucket: env("S3_TEST_BUCKET", "bookstore-test"),
+            access_key_id: env("S3_TEST_ACCESS_KEY_ID", "minioadmin"),
+            secret_access_key: env("S3_TEST_SECRET_ACCESS_KEY", "minioadmin"),
+            path_style: env("S3_TEST_PATH_STYLE", "true") == "true",
+        })
+        .unwrap();
+
+        let key = format!("test/{}/หนังสือ ปก.bin", chrono::Utc::now().timestamp_nanos_opt().unwrap());
+        let content: Vec<u8> = (0..=255u8).cycle().take(10_000).collect();
+
+        assert_eq!(storage.size(&key).await.unwrap(), None);
+        storage.put(&key, &content, "application/octet-stream").await.unwrap();
+        assert_eq!(storage.size(&key).await.unwrap(), Some(10_000));
+        assert_eq!(read_all(storage.open(&key, None).await.unwrap()).await, content);
+
+        let range = ByteRange { start: 100, end: 1099 };
+        assert_eq!(read_all(storage.open(&key, Some(range)).await.unwrap()).await, content[100..1100]);
+        let beyond = ByteRange { start: 9_000, end: 10_500 };
+        assert!(storage.open(&key, Some(beyond)).await.is_err());
+
+        // ใหญ่กว่า part ของ BufWriter (10 MiB) จึงต้องไปทาง multipart upload
+        let large: Vec<u8> = (0..=250u8).cycle().take(12 * 1024 * 1024).collect();
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&large).unwrap();
+        storage
+            .put_file(&key, tokio::fs::File::from_std(file), "application/epub+zip")
+            .await
+            .unwrap();
+        assert_eq!(storage.size(&key).await.unwrap(), Some(large.len() as u64));
+        assert!(read_all(storage.open(&key, None).await.unwrap()).await == large);
+
+        storage.delete(&key).await.unwrap();
+        storage.delete(&key).await.unwrap();
+        assert_eq!(storage.size(&key).await.unwrap(), None);
+        assert!(storage.open(&key, None).await.is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::repositories::search_feed_repository::{ChangeCursor, SearchDocument};
+
+#[derive(Debug, Clone)]
+pub struct IndexHit {
+    pub book_id: i32,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexSearchResult {
+    pub hits: Vec<IndexHit>,
+    /// Matches across all pages
+    pub total: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexSuggestion {
+    pub book_id: i32,
+    pub title: String,
+}
+
+/// Port to a dedicated search engine kept in sync from the book change feed.
+///
+/// Writes are staged and only become visible (together with the new feed
+/// cursor) on `commit`, so a crash replays changes instead of losing them.
+/// Drive writes from a single worker.
+#[async_trait]
+pub trait SearchIndex: Send + Sync {
+    /// Backend name, for logs
+    fn backend(&self) -> &'static str;
+    /// Feed position of the last commit; `None` for a new index
+    async fn cursor(&self) -> Result<Option<ChangeCursor>>;
+    /// Stages removal of every document (start of a rebuild)
+    async fn clear(&self) -> Result<()>;
+    /// Stages upserts and deletions by book id
+    async fn stage(&self, upserts: Vec<SearchDocument>, deletes: Vec<i32>) -> Result<()>;
+    async fn commit(&self, cursor: ChangeCursor) -> Result<()>;
+    /// Drops everything staged since the last commit
+    async fn rollback(&self) -> Result<()>;
+    /// Typo tolerant search, boosted by recent sales
+    async fn search(&self, query: &str, offset: usize, limit: usize) -> Result<IndexSearchResult>;
+    /// Title autocomplete; the last word is treated as a prefix
+    async fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<IndexSuggestion>>;
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::domain::{
+    entities::shipping_zone::ShippingMethodEntity,
+    services::shipping_calculator::{Parcel, ShippingDestination},
+};
+
+#[derive(Debug, Clone)]
+pub struct ShippingRateRequest {
+    pub destination: ShippingDestination,
+    pub parcel: Parcel,
+    /// When the order would be placed (start of the delivery estimate)
+    pub placed_at: DateTime<Utc>,
+}
+
+/// A price and delivery window for one shipping method.
+#[derive(Debug, Clone)]
+pub struct ShippingRate {
+    /// Amount in minor units of the store currency
+    pub amount: i64,
+    pub estimated_from: NaiveDate,
+    pub estimated_to: NaiveDate,
+}
+
+/// Port to whatever prices a shipment: our own rate tables or a carrier API.
+#[async_trait]
+pub trait ShippingRateProvider: Send + Sync {
+    /// Provider name, for logs
+    fn provider(&self) -> &'static str;
+    /// Rate for the parcel with this method; `None` when the method cannot carry it
+    async fn rate(
+        &self,
+        method: &ShippingMethodEntity,
+        req: &ShippingRateRequest,
+    ) -> Result<Option<ShippingRate>>;
+}
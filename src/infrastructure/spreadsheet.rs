@@ -0,0 +1,509 @@
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Seek, Write},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Result};
+use quick_xml::{escape::escape, events::Event, Reader};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const XLSX_SHEET_PATH: &str = "xl/worksheets/sheet1.xml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetFormat {
+    Csv,
+    Xlsx,
+}
+
+impl SheetFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+}
+
+impl FromStr for SheetFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "xlsx" => Ok(Self::Xlsx),
+            _ => Err(anyhow!("Unsupported spreadsheet format: {}", s)),
+        }
+    }
+}
+
+/// One cell of an exported row
+#[derive(Debug, Clone)]
+pub enum Cell {
+    Text(String),
+    /// Written as a number in XLSX so it can be summed; must be a plain
+    /// decimal such as `290.00`
+    Number(String),
+    Empty,
+}
+
+impl Cell {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Text(s) | Self::Number(s) => s,
+            Self::Empty => "",
+        }
+    }
+}
+
+/// Reads every row of a CSV file or of the first worksheet of an XLSX file.
+///
+/// Row `i` of the result is spreadsheet row `i + 1`, so error reports can
+/// point at the line staff see in their editor. Blank rows are kept (as
+/// empty vectors) for the same reason.
+pub fn read_rows(format: SheetFormat, content: &[u8]) -> Result<Vec<Vec<String>>> {
+    match format {
+        SheetFormat::Csv => read_csv(content),
+        SheetFormat::Xlsx => read_xlsx(content),
+    }
+}
+
+/// RFC 4180 with the usual spreadsheet leniencies: optional UTF-8 BOM, CRLF
+/// or LF line ends, and `;` or tab as the separator when the header uses it.
+fn read_csv(content: &[u8]) -> Result<Vec<Vec<String>>> {
+    let content = content.strip_prefix(UTF8_BOM).unwrap_or(content);
+    let text = std::str::from_utf8(content).context("CSV file is not UTF-8")?;
+    let delimiter = detect_delimiter(text);
+
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(unguard(std::mem::take(&mut field)));
+                rows.push(finish_row(std::mem::take(&mut row)));
+            }
+            c if c == delimiter => row.push(unguard(std::mem::take(&mut field))),
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(anyhow!("CSV file ends inside a quoted field"));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(unguard(field));
+        rows.push(finish_row(row));
+    }
+
+    Ok(rows)
+}
+
+/// Drops the `'` that `csv_field` puts in front of formula-like text
+fn unguard(field: String) -> String {
+    match field.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(['=', '+', '-', '@']) => rest.to_string(),
+        _ => field,
+    }
+}
+
+/// A line with nothing on it reads as no cells rather than one empty cell
+fn finish_row(row: Vec<String>) -> Vec<String> {
+    if row.len() == 1 && row[0].is_empty() {
+        Vec::new()
+    } else {
+        row
+    }
+}
+
+fn detect_delimiter(text: &str) -> char {
+    let header = text.lines().next().unwrap_or("");
+    [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|d| (header.matches(*d).count(), *d == ','))
+        .unwrap_or(',')
+}
+
+fn read_xlsx(content: &[u8]) -> Result<Vec<Vec<String>>> {
+    let mut archive = ZipArchive::new(Cursor::new(content)).context("Not a valid XLSX (zip) file")?;
+
+    let sheet_path = first_sheet_path(&mut archive)?;
+    let shared = if archive.file_names().any(|n| n == "xl/sharedStrings.xml") {
+        read_shared_strings(&read_entry(&mut archive, "xl/sharedStrings.xml")?)?
+    } else {
+        Vec::new()
+    };
+    let sheet = read_entry(&mut archive, &sheet_path)?;
+
+    let mut reader = Reader::from_str(&sheet);
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    // สถานะของ cell ที่กำลังอ่าน: ตำแหน่ง, ชนิด, และข้อความที่สะสมไว้
+    let mut cell: Option<(usize, Vec<u8>, String)> = None;
+    let mut next_column = 0;
+    let mut in_value = false;
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if e.local_name().as_ref() == b"row" => {
+                let number = match attribute(&e, b"r") {
+                    Some(r) => r.parse::<usize>().map_err(|_| anyhow!("Invalid row number: {}", r))?,
+                    None => rows.len() + 1,
+                };
+                if number < rows.len() + 1 {
+                    return Err(anyhow!("Worksheet rows are out of order at row {}", number));
+                }
+                rows.resize(number, Vec::new());
+                next_column = 0;
+            }
+            Event::Start(e) if e.local_name().as_ref() == b"c" => {
+                let column = match attribute(&e, b"r") {
+                    Some(r) => column_index(&r)?,
+                    None => next_column,
+                };
+                let kind = attribute(&e, b"t").unwrap_or_default().into_bytes();
+                cell = Some((column, kind, String::new()));
+            }
+            Event::Start(e) if matches!(e.local_name().as_ref(), b"v" | b"t") => in_value = true,
+            Event::End(e) if matches!(e.local_name().as_ref(), b"v" | b"t") => in_value = false,
+            Event::Text(e) if in_value => {
+                if let Some((_, _, text)) = cell.as_mut() {
+                    text.push_str(&e.unescape()?);
+                }
+            }
+            Event::End(e) if e.local_name().as_ref() == b"c" => {
+                let Some((column, kind, raw)) = cell.take() else {
+                    continue;
+                };
+                let value = match kind.as_slice() {
+                    b"s" => {
+                        let index: usize = raw.trim().parse().map_err(|_| anyhow!("Invalid shared string index: {}", raw))?;
+                        shared
+                            .get(index)
+                            .cloned()
+                            .ok_or_else(|| anyhow!("Shared string {} does not exist", index))?
+                    }
+                    b"b" => if raw.trim() == "1" { "true" } else { "false" }.to_string(),
+                    b"e" => String::new(),
+                    b"inlineStr" | b"str" => raw,
+                    _ => normalize_number(&raw),
+                };
+                let row = rows.last_mut().ok_or_else(|| anyhow!("Cell outside of a row"))?;
+                if row.len() <= column {
+                    row.resize(column + 1, String::new());
+                }
+                row[column] = value;
+                next_column = column + 1;
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"c" => {
+                next_column = match attribute(&e, b"r") {
+                    Some(r) => column_index(&r)? + 1,
+                    None => next_column + 1,
+                };
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    // ช่องว่างท้ายแถวที่ Excel ชอบเก็บไว้ ตัดทิ้งเพื่อให้แถวว่างเป็นแถวว่างจริง
+    for row in rows.iter_mut() {
+        while row.last().is_some_and(|c| c.is_empty()) {
+            row.pop();
+        }
+    }
+    Ok(rows)
+}
+
+/// The workbook's first `<sheet>`, resolved through the workbook relationships
+fn first_sheet_path<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<String> {
+    let workbook = read_entry(archive, "xl/workbook.xml")?;
+    let mut reader = Reader::from_str(&workbook);
+    let rel_id = loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"sheet" => {
+                break e
+                    .attributes()
+                    .flatten()
+                    .find(|a| a.key.local_name().as_ref() == b"id")
+                    .map(|a| String::from_utf8_lossy(&a.value).into_owned())
+                    .ok_or_else(|| anyhow!("Workbook sheet has no relationship id"))?;
+            }
+            Event::Eof => return Err(anyhow!("Workbook has no sheets")),
+            _ => {}
+        }
+    };
+
+    let rels = read_entry(archive, "xl/_rels/workbook.xml.rels")?;
+    let mut reader = Reader::from_str(&rels);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e)
+                if e.local_name().as_ref() == b"Relationship"
+                    && attribute(&e, b"Id").as_deref() == Some(rel_id.as_str()) =>
+            {
+                let target = attribute(&e, b"Target").ok_or_else(|| anyhow!("Sheet relationship has no target"))?;
+                return Ok(match target.strip_prefix('/') {
+                    Some(absolute) => absolute.to_string(),
+                    None => format!("xl/{}", target),
+                });
+            }
+            Event::Eof => return Err(anyhow!("Sheet {} not found in workbook relationships", rel_id)),
+            _ => {}
+        }
+    }
+}
+
+/// Each `<si>` as plain text; rich-text runs are joined
+fn read_shared_strings(xml: &str) -> Result<Vec<String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    // เสียงอ่าน (phonetic) ใน <rPh> ไม่ใช่ข้อความของ cell
+    let mut in_phonetic = false;
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"si" => current.clear(),
+                b"t" => in_text = !in_phonetic,
+                b"rPh" => in_phonetic = true,
+                _ => {}
+            },
+            Event::End(e) => match e.local_name().as_ref() {
+                b"si" => strings.push(std::mem::take(&mut current)),
+                b"t" => in_text = false,
+                b"rPh" => in_phonetic = false,
+                _ => {}
+            },
+            Event::Empty(e) if e.local_name().as_ref() == b"si" => strings.push(String::new()),
+            Event::Text(e) if in_text => current.push_str(&e.unescape()?),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(strings)
+}
+
+/// Excel stores numbers as doubles; `289.99000000000001` reads back as `289.99`
+fn normalize_number(raw: &str) -> String {
+    let raw = raw.trim();
+    match raw.parse::<f64>() {
+        Ok(n) if n.is_finite() && (raw.contains(['e', 'E']) || raw.len() > 15) => {
+            let fixed = format!("{:.9}", n);
+            fixed.trim_end_matches('0').trim_end_matches('.').to_string()
+        }
+        _ => raw.to_string(),
+    }
+}
+
+/// `"C12"` -> 2
+fn column_index(reference: &str) -> Result<usize> {
+    let letters: String = reference.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if letters.is_empty() || letters.len() > 3 {
+        return Err(anyhow!("Invalid cell reference: {}", reference));
+    }
+    let index = letters
+        .to_ascii_uppercase()
+        .bytes()
+        .fold(0usize, |acc, b| acc * 26 + (b - b'A') as usize + 1);
+    Ok(index - 1)
+}
+
+/// 2 -> `"C"`
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+fn attribute(e: &quick_xml::events::BytesStart<'_>, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.as_ref() == name)
+        .map(|a| String::from_utf8_lossy(&a.value).into_owned())
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<String> {
+    let mut entry = archive
+        .by_name(name)
+        .with_context(|| format!("XLSX file is missing {}", name))?;
+    let mut text = String::new();
+    entry
+        .read_to_string(&mut text)
+        .with_context(|| format!("{} is not UTF-8", name))?;
+    Ok(text)
+}
+
+/// Writes rows as they come, so exports never hold the whole table in
+/// memory. XLSX output is a single sheet with inline strings.
+pub struct SheetWriter<W: Write + Seek> {
+    out: SheetOutput<W>,
+    rows_written: usize,
+}
+
+enum SheetOutput<W: Write + Seek> {
+    Csv(W),
+    Xlsx(Box<ZipWriter<W>>),
+}
+
+impl<W: Write + Seek> SheetWriter<W> {
+    pub fn new(format: SheetFormat, mut output: W) -> Result<Self> {
+        let out = match format {
+            SheetFormat::Csv => {
+                // BOM ให้ Excel เปิดภาษาไทยได้ถูกต้อง
+                output.write_all(UTF8_BOM)?;
+                SheetOutput::Csv(output)
+            }
+            SheetFormat::Xlsx => {
+                let mut zip = ZipWriter::new(output);
+                let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+                for (name, content) in XLSX_PARTS {
+                    zip.start_file(*name, options)?;
+                    zip.write_all(content.as_bytes())?;
+                }
+                zip.start_file(XLSX_SHEET_PATH, options)?;
+                zip.write_all(
+                    br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+                )?;
+                SheetOutput::Xlsx(Box::new(zip))
+            }
+        };
+        Ok(Self { out, rows_written: 0 })
+    }
+
+    pub fn write_row(&mut self, cells: &[Cell]) -> Result<()> {
+        self.rows_written += 1;
+        match &mut self.out {
+            SheetOutput::Csv(out) => {
+                let line = cells.iter().map(|c| csv_field(c.as_str())).collect::<Vec<_>>().join(",");
+                out.write_all(line.as_bytes())?;
+                out.write_all(b"\r\n")?;
+            }
+            SheetOutput::Xlsx(zip) => {
+                let mut xml = format!(r#"<row r="{}">"#, self.rows_written);
+                for (i, cell) in cells.iter().enumerate() {
+                    let reference = format!("{}{}", column_name(i), self.rows_written);
+                    match cell {
+                        Cell::Text(s) if !s.is_empty() => xml.push_str(&format!(
+                            r#"<c r="{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#,
+                            reference,
+                            escape(strip_control_chars(s).as_str())
+                        )),
+                        Cell::Number(s) if !s.is_empty() => {
+                            xml.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, escape(s.as_str())))
+                        }
+                        _ => {}
+                    }
+                }
+                xml.push_str("</row>");
+                zip.write_all(xml.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<W> {
+        match self.out {
+            SheetOutput::Csv(mut out) => {
+                out.flush()?;
+                Ok(out)
+            }
+            SheetOutput::Xlsx(mut zip) => {
+                zip.write_all(b"</sheetData></worksheet>")?;
+                Ok(zip.finish()?)
+            }
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    // ขึ้นต้นด้วย = + - @ Excel จะตีความเป็นสูตร จึงใส่ ' นำหน้ากัน formula injection
+    // ยกเว้นตัวเลขและเบอร์โทร (+66-81...) ที่ปล่อยไว้ตามเดิม
+    let numeric = value.chars().all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | ' '));
+    let value = if value.starts_with(['=', '@']) || (value.starts_with(['+', '-']) && !numeric) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// XML 1.0 has no way to write most control characters
+fn strip_control_chars(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect()
+}
+
+/// Fixed parts of a one-sheet workbook
+const XLSX_PARTS: &[(&str, &str)] = &[
+    (
+        "[Content_Types].xml",
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#,
+    ),
+    (
+        "_rels/.rels",
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#,
+    ),
+    (
+        "xl/workbook.xml",
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+    ),
+    (
+        "xl/_rels/workbook.xml.rels",
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#,
+    ),
+    (
+        "xl/styles.xml",
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="1"><font><sz val="11"/><name val="Calibri"/></font></fonts><fills count="1"><fill><patternFill patternType="none"/></fill></fills><borders count="1"><border/></borders><cellStyleXfs count="1"><xf/></cellStyleXfs><cellXfs count="1"><xf/></cellXfs></styleSheet>"#,
+    ),
+];
+
+/// Header name -> column index, matched case-insensitively
+pub fn header_index(header: &[String]) -> HashMap<String, usize> {
+    let mut index = HashMap::new();
+    for (i, name) in header.iter().enumerate() {
+        index.entry(name.trim().to_lowercase()).or_insert(i);
+    }
+    index
+}
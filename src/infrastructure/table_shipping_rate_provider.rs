@@ -0,0 +1,49 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::shipping_zone::ShippingMethodEntity,
+    services::shipping_calculator,
+};
+use crate::infrastructure::shipping_rate_provider::{
+    ShippingRate, ShippingRateProvider, ShippingRateRequest,
+};
+
+/// Prices shipments from the rate tables configured on each method.
+#[derive(Default)]
+pub struct TableShippingRateProvider;
+
+impl TableShippingRateProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl ShippingRateProvider for TableShippingRateProvider {
+    fn provider(&self) -> &'static str {
+        "rate_table"
+    }
+
+    async fn rate(
+        &self,
+        method: &ShippingMethodEntity,
+        req: &ShippingRateRequest,
+    ) -> Result<Option<ShippingRate>> {
+        if !shipping_calculator::accepts(method, &req.parcel) {
+            return Ok(None);
+        }
+
+        let Some(amount) = method.rate_for(req.parcel.value_for(method.rate_basis)) else {
+            return Ok(None);
+        };
+        let (estimated_from, estimated_to) =
+            shipping_calculator::estimate_delivery(req.placed_at, method.min_days, method.max_days);
+
+        Ok(Some(ShippingRate {
+            amount,
+            estimated_from,
+            estimated_to,
+        }))
+    }
+}
@@ -0,0 +1,566 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use tantivy::{
+    collector::{Count, TopDocs},
+    directory::MmapDirectory,
+    query::{BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, Query, TermQuery},
+    schema::{
+        Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, FAST, INDEXED,
+        STORED, STRING,
+    },
+    tokenizer::{Token, TokenStream, Tokenizer},
+    DocAddress, DocId, Index, IndexReader, IndexWriter, ReloadPolicy, Score, Searcher,
+    SegmentReader, TantivyDocument, Term,
+};
+
+use crate::domain::{
+    repositories::search_feed_repository::{ChangeCursor, SearchDocument},
+    services::search_text::{self, SearchTerm},
+};
+use crate::infrastructure::search_index::{
+    IndexHit, IndexSearchResult, IndexSuggestion, SearchIndex,
+};
+
+const TOKENIZER: &str = "catalog";
+const WRITER_MEMORY_BYTES: usize = 50_000_000;
+
+/// score × (1 + SALES_BOOST × ln(1 + sales)): a best seller ranks above an
+/// equally relevant slow mover, but cannot outrank a clearly better match.
+const SALES_BOOST: f32 = 0.15;
+/// Out of stock books still show, just lower
+const OUT_OF_STOCK_FACTOR: f32 = 0.8;
+
+/// Shorter words are matched exactly; typos in them change the word too much
+const MIN_FUZZY_CHARS: usize = 4;
+const TWO_TYPOS_CHARS: usize = 8;
+
+#[derive(Clone, Copy)]
+struct Fields {
+    book_id: Field,
+    isbn: Field,
+    title: Field,
+    author: Field,
+    description: Field,
+    category: Field,
+    publisher: Field,
+    in_stock: Field,
+    sales: Field,
+}
+
+impl Fields {
+    /// Text fields a query is matched against, with their boosts
+    fn weighted(&self) -> [(Field, f32); 5] {
+        [
+            (self.title, 3.0),
+            (self.author, 2.0),
+            (self.category, 1.0),
+            (self.publisher, 1.0),
+            (self.description, 0.5),
+        ]
+    }
+}
+
+fn build_schema() -> (Schema, Fields) {
+    let indexing = TextFieldIndexing::default()
+        .set_tokenizer(TOKENIZER)
+        .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+    let text = TextOptions::default().set_indexing_options(indexing);
+    let stored_text = text.clone().set_stored();
+
+    let mut builder = Schema::builder();
+    let fields = Fields {
+        book_id: builder.add_u64_field("book_id", INDEXED | STORED | FAST),
+        isbn: builder.add_text_field("isbn", STRING),
+        title: builder.add_text_field("title", stored_text),
+        author: builder.add_text_field("author", text.clone()),
+        description: builder.add_text_field("description", text.clone()),
+        category: builder.add_text_field("category", text.clone()),
+        publisher: builder.add_text_field("publisher", text),
+        in_stock: builder.add_u64_field("in_stock", FAST),
+        sales: builder.add_u64_field("sales", FAST),
+    };
+    (builder.build(), fields)
+}
+
+/// Words lowercased, Thai runs as bigrams (see `search_text::index_tokens`),
+/// so documents are split exactly the way queries are.
+#[derive(Clone)]
+struct CatalogTokenizer;
+
+struct CatalogTokenStream {
+    tokens: Vec<Token>,
+    /// One past the current token; 0 before the first `advance`
+    next: usize,
+}
+
+impl Tokenizer for CatalogTokenizer {
+    type TokenStream<'a> = CatalogTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> CatalogTokenStream {
+        let tokens = search_text::index_tokens(text)
+            .into_iter()
+            .enumerate()
+            .map(|(position, (offset_from, offset_to, text))| Token {
+                offset_from,
+                offset_to,
+                position,
+                text,
+                position_length: 1,
+            })
+            .collect();
+        CatalogTokenStream { tokens, next: 0 }
+    }
+}
+
+impl TokenStream for CatalogTokenStream {
+    fn advance(&mut self) -> bool {
+        if self.next < self.tokens.len() {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.next - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.next - 1]
+    }
+}
+
+struct Inner {
+    index: Index,
+    reader: IndexReader,
+    writer: Mutex<IndexWriter>,
+    fields: Fields,
+    /// word → the other words of its synonym groups
+    synonyms: HashMap<String, Vec<String>>,
+}
+
+/// `SearchIndex` on an embedded Tantivy index in a local directory.
+///
+/// Tantivy is synchronous, so every call runs on the blocking thread pool.
+/// The feed cursor is stored as the commit payload, making documents and
+/// cursor move together atomically.
+#[derive(Clone)]
+pub struct TantivySearchIndex {
+    inner: Arc<Inner>,
+}
+
+impl TantivySearchIndex {
+    /// Opens the index in `dir`, creating it (and the directory) when missing.
+    pub fn open(dir: &Path, synonyms: Vec<Vec<String>>) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create search index directory {}", dir.display()))?;
+        let (schema, fields) = build_schema();
+        let directory = MmapDirectory::open(dir)
+            .map_err(|e| anyhow!("Failed to open search index directory: {}", e))?;
+        let index = Index::open_or_create(directory, schema)
+            .map_err(|e| anyhow!("Failed to open search index: {}", e))?;
+        Self::from_index(index, fields, synonyms)
+    }
+
+    /// Index held in memory only; for development and tests.
+    pub fn in_memory(synonyms: Vec<Vec<String>>) -> Result<Self> {
+        let (schema, fields) = build_schema();
+        Self::from_index(Index::create_in_ram(schema), fields, synonyms)
+    }
+
+    fn from_index(index: Index, fields: Fields, synonyms: Vec<Vec<String>>) -> Result<Self> {
+        index.tokenizers().register(TOKENIZER, CatalogTokenizer);
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let writer = index.writer(WRITER_MEMORY_BYTES)?;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                index,
+                reader,
+                writer: Mutex::new(writer),
+                fields,
+                synonyms: synonym_map(synonyms),
+            }),
+        })
+    }
+
+    /// Reads synonym groups: one group per line, words separated by commas,
+    /// `#` starts a comment. Example: `novel, fiction, นิยาย`
+    pub fn load_synonyms(path: &Path) -> Result<Vec<Vec<String>>> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read synonyms file {}", path.display()))?;
+
+        let mut groups = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let words: Vec<String> = line
+                .split(',')
+                .map(|w| w.trim().to_lowercase())
+                .filter(|w| !w.is_empty())
+                .collect();
+            if words.iter().any(|w| search_text::tokenize(w).len() != 1) {
+                return Err(anyhow!(
+                    "Synonyms line {}: entries must be single words",
+                    number + 1
+                ));
+            }
+            if words.len() > 1 {
+                groups.push(words);
+            }
+        }
+        Ok(groups)
+    }
+
+    async fn blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Inner) -> Result<T> + Send + 'static,
+    {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || f(&inner))
+            .await
+            .map_err(|e| anyhow!("Search index task failed: {}", e))?
+    }
+}
+
+fn synonym_map(groups: Vec<Vec<String>>) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for group in groups {
+        for word in &group {
+            let entry = map.entry(word.clone()).or_default();
+            for other in group.iter().filter(|o| *o != word) {
+                if !entry.contains(other) {
+                    entry.push(other.clone());
+                }
+            }
+        }
+    }
+    map
+}
+
+fn encode_cursor(cursor: ChangeCursor) -> String {
+    format!("{}:{}", cursor.txid, cursor.id)
+}
+
+fn decode_cursor(payload: &str) -> Result<ChangeCursor> {
+    let (txid, id) = payload
+        .split_once(':')
+        .ok_or_else(|| anyhow!("Invalid search index cursor: {}", payload))?;
+    Ok(ChangeCursor {
+        txid: txid.parse().context("Invalid search index cursor")?,
+        id: id.parse().context("Invalid search index cursor")?,
+    })
+}
+
+fn boosted(query: Box<dyn Query>, boost: f32) -> Box<dyn Query> {
+    Box::new(BoostQuery::new(query, boost))
+}
+
+fn term_query(field: Field, text: &str) -> Box<dyn Query> {
+    Box::new(TermQuery::new(
+        Term::from_field_text(field, text),
+        IndexRecordOption::WithFreqs,
+    ))
+}
+
+fn typo_distance(word: &str) -> u8 {
+    match word.chars().count() {
+        n if n >= TWO_TYPOS_CHARS => 2,
+        n if n >= MIN_FUZZY_CHARS => 1,
+        _ => 0,
+    }
+}
+
+/// All bigrams of a Thai run, in one field
+fn thai_query(field: Field, run: &str) -> Box<dyn Query> {
+    Box::new(BooleanQuery::new(
+        search_text::thai_bigrams(run)
+            .into_iter()
+            .map(|bigram| (Occur::Must, term_query(field, &bigram)))
+            .collect(),
+    ))
+}
+
+impl Inner {
+    /// One clause per query term, all required. A word matches any field
+    /// exactly, with typos, or through a synonym; exact hits score highest.
+    fn text_query(&self, terms: &[SearchTerm]) -> Box<dyn Query> {
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for term in terms {
+            let mut alternatives: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+            for (field, boost) in self.fields.weighted() {
+                match term {
+                    SearchTerm::Word(word) => {
+                        alternatives.push((Occur::Should, boosted(term_query(field, word), boost * 2.0)));
+                        let distance = typo_distance(word);
+                        if distance > 0 {
+                            let fuzzy = FuzzyTermQuery::new(Term::from_field_text(field, word), distance, true);
+                            alternatives.push((Occur::Should, boosted(Box::new(fuzzy), boost)));
+                        }
+                    }
+                    SearchTerm::Thai(run) => {
+                        alternatives.push((Occur::Should, boosted(thai_query(field, run), boost)));
+                    }
+                }
+                for synonym in self.synonyms.get(term.as_str()).into_iter().flatten() {
+                    let query = match search_text::tokenize(synonym).first() {
+                        Some(SearchTerm::Thai(run)) => thai_query(field, run),
+                        _ => term_query(field, synonym),
+                    };
+                    alternatives.push((Occur::Should, boosted(query, boost * 1.5)));
+                }
+            }
+            clauses.push((Occur::Must, Box::new(BooleanQuery::new(alternatives))));
+        }
+        Box::new(BooleanQuery::new(clauses))
+    }
+
+    /// Title query for autocomplete: earlier words exactly, the last as a prefix
+    fn prefix_query(&self, terms: &[SearchTerm]) -> Box<dyn Query> {
+        let title = self.fields.title;
+        let last = terms.len() - 1;
+        let clauses = terms
+            .iter()
+            .enumerate()
+            .map(|(i, term)| {
+                let query: Box<dyn Query> = match term {
+                    SearchTerm::Word(word) if i == last => {
+                        let distance = typo_distance(word).min(1);
+                        Box::new(FuzzyTermQuery::new_prefix(Term::from_field_text(title, word), distance, true))
+                    }
+                    SearchTerm::Word(word) => term_query(title, word),
+                    SearchTerm::Thai(run) => thai_query(title, run),
+                };
+                (Occur::Must, query)
+            })
+            .collect();
+        Box::new(BooleanQuery::new(clauses))
+    }
+
+    /// Top matches re-scored by sales and availability
+    fn ranked(
+        &self,
+        searcher: &Searcher,
+        query: &dyn Query,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<(Score, DocAddress)>, usize)> {
+        let collector = TopDocs::with_limit(limit.max(1))
+            .and_offset(offset)
+            .tweak_score(move |segment: &SegmentReader| {
+                let fast_fields = segment.fast_fields();
+                let sales = fast_fields
+                    .u64("sales")
+                    .expect("sales is a fast field")
+                    .first_or_default_col(0);
+                let in_stock = fast_fields
+                    .u64("in_stock")
+                    .expect("in_stock is a fast field")
+                    .first_or_default_col(0);
+                move |doc: DocId, score: Score| {
+                    let popularity = 1.0 + SALES_BOOST * (sales.get_val(doc) as f32).ln_1p();
+                    let availability = if in_stock.get_val(doc) > 0 { 1.0 } else { OUT_OF_STOCK_FACTOR };
+                    score * popularity * availability
+                }
+            });
+        let (top, total) = searcher.search(query, &(collector, Count))?;
+        Ok((top, total))
+    }
+
+    fn book_id(&self, searcher: &Searcher, address: DocAddress) -> Result<(i32, TantivyDocument)> {
+        let doc: TantivyDocument = searcher.doc(address)?;
+        let book_id = doc
+            .get_first(self.fields.book_id)
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow!("Search document without book_id"))?;
+        Ok((book_id as i32, doc))
+    }
+}
+
+#[async_trait]
+impl SearchIndex for TantivySearchIndex {
+    fn backend(&self) -> &'static str {
+        "tantivy"
+    }
+
+    async fn cursor(&self) -> Result<Option<ChangeCursor>> {
+        self.blocking(|inner| {
+            let metas = inner.index.load_metas()?;
+            metas.payload.as_deref().map(decode_cursor).transpose()
+        })
+        .await
+    }
+
+    async fn clear(&self) -> Result<()> {
+        self.blocking(|inner| {
+            let writer = inner.writer.lock().map_err(|_| anyhow!("Search index writer poisoned"))?;
+            writer.delete_all_documents()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn stage(&self, upserts: Vec<SearchDocument>, deletes: Vec<i32>) -> Result<()> {
+        self.blocking(move |inner| {
+            let f = inner.fields;
+            let writer = inner.writer.lock().map_err(|_| anyhow!("Search index writer poisoned"))?;
+
+            for book_id in deletes {
+                writer.delete_term(Term::from_field_u64(f.book_id, book_id as u64));
+            }
+            for document in upserts {
+                writer.delete_term(Term::from_field_u64(f.book_id, document.book_id as u64));
+
+                let mut doc = TantivyDocument::default();
+                doc.add_u64(f.book_id, document.book_id as u64);
+                doc.add_text(f.isbn, &document.isbn);
+                doc.add_text(f.title, &document.title);
+                doc.add_text(f.author, &document.author);
+                if let Some(description) = &document.description {
+                    doc.add_text(f.description, description);
+                }
+                if let Some(category) = &document.category {
+                    doc.add_text(f.category, category);
+                }
+                if let Some(publisher) = &document.publisher {
+                    doc.add_text(f.publisher, publisher);
+                }
+                doc.add_u64(f.in_stock, document.in_stock as u64);
+                doc.add_u64(f.sales, document.sales_count.max(0) as u64);
+                writer.add_document(doc)?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn commit(&self, cursor: ChangeCursor) -> Result<()> {
+        self.blocking(move |inner| {
+            {
+                let mut writer = inner.writer.lock().map_err(|_| anyhow!("Search index writer poisoned"))?;
+                let mut prepared = writer.prepare_commit()?;
+                prepared.set_payload(&encode_cursor(cursor));
+                prepared.commit()?;
+            }
+            inner.reader.reload()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn rollback(&self) -> Result<()> {
+        self.blocking(|inner| {
+            let mut writer = inner.writer.lock().map_err(|_| anyhow!("Search index writer poisoned"))?;
+            writer.rollback()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn search(&self, query: &str, offset: usize, limit: usize) -> Result<IndexSearchResult> {
+        let query = query.to_string();
+        self.blocking(move |inner| {
+            let searcher = inner.reader.searcher();
+
+            let parsed: Box<dyn Query> = match search_text::as_isbn(&query) {
+                Some(isbn) => term_query(inner.fields.isbn, isbn.as_str()),
+                None => {
+                    let terms = search_text::tokenize(&query);
+                    if terms.is_empty() {
+                        return Ok(IndexSearchResult { hits: Vec::new(), total: 0 });
+                    }
+                    inner.text_query(&terms)
+                }
+            };
+
+            let (top, total) = inner.ranked(&searcher, parsed.as_ref(), offset, limit)?;
+            let hits = top
+                .into_iter()
+                .map(|(score, address)| {
+                    let (book_id, _) = inner.book_id(&searcher, address)?;
+                    Ok(IndexHit { book_id, score })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(IndexSearchResult { hits, total })
+        })
+        .await
+    }
+
+    async fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<IndexSuggestion>> {
+        let prefix = prefix.to_string();
+        self.blocking(move |inner| {
+            let terms = search_text::tokenize(&prefix);
+            if terms.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let searcher = inner.reader.searcher();
+            let (top, _) = inner.ranked(&searcher, inner.prefix_query(&terms).as_ref(), 0, limit)?;
+            top.into_iter()
+                .map(|(_, address)| {
+                    let (book_id, doc) = inner.book_id(&searcher, address)?;
+                    let title = doc
+                        .get_first(inner.fields.title)
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    Ok(IndexSuggestion { book_id, title })
+                })
+                .collect()
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(book_id: i32, title: &str) -> SearchDocument {
+        SearchDocument {
+            book_id,
+            isbn: format!("978000000000{}", book_id),
+            title: title.to_string(),
+            author: "Author".to_string(),
+            description: None,
+            category: None,
+            publisher: None,
+            in_stock: true,
+            sales_count: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn rollback_keeps_last_commit_after_failed_rebuild() {
+        let index = TantivySearchIndex::in_memory(Vec::new()).unwrap();
+        index.stage(vec![document(1, "Rust in Action")], Vec::new()).await.unwrap();
+        index.commit(ChangeCursor { txid: 1, id: 1 }).await.unwrap();
+
+        // rebuild ล้มหลัง clear: ต้องไม่เหลือ delete-all ค้างให้ commit ถัดไป
+        index.clear().await.unwrap();
+        index.stage(vec![document(2, "Half staged")], Vec::new()).await.unwrap();
+        index.rollback().await.unwrap();
+        index.stage(vec![document(3, "Rust Atomics")], Vec::new()).await.unwrap();
+        index.commit(ChangeCursor { txid: 2, id: 2 }).await.unwrap();
+
+        let mut found: Vec<i32> = index.search("rust", 0, 10).await.unwrap()
+            .hits.into_iter().map(|h| h.book_id).collect();
+        found.sort_unstable();
+        assert_eq!(found, vec![1, 3]);
+        assert!(index.search("staged", 0, 10).await.unwrap().hits.is_empty());
+        assert_eq!(index.cursor().await.unwrap(), Some(ChangeCursor { txid: 2, id: 2 }));
+    }
+}
@@ -0,0 +1,115 @@
+use std::io::Cursor;
+
+use anyhow::{anyhow, Result};
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, RgbaImage};
+
+/// Image type read from the file's signature (JPEG and PNG only)
+pub fn sniff_content_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if data.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some("image/png")
+    } else {
+        None
+    }
+}
+
+/// Decodes an uploaded JPEG or PNG and turns it the way the camera's EXIF
+/// orientation says it is meant to be seen. Only pixels come out; whatever
+/// metadata the file carried (GPS position, camera serial, ...) is dropped.
+/// Refuses pictures with more than `max_pixels` pixels before decoding them.
+pub fn decode_upright(data: &[u8], max_pixels: u64) -> Result<RgbaImage> {
+    let format = match sniff_content_type(data) {
+        Some("image/jpeg") => ImageFormat::Jpeg,
+        Some(_) => ImageFormat::Png,
+        None => return Err(anyhow!("Unsupported image type; upload a JPEG or PNG")),
+    };
+
+    let mut decoder = ImageReader::with_format(Cursor::new(data), format)
+        .into_decoder()
+        .map_err(|e| anyhow!("Image cannot be read: {}", e))?;
+    let (width, height) = decoder.dimensions();
+    if width == 0 || height == 0 {
+        return Err(anyhow!("Image has no pixels"));
+    }
+    if width as u64 * height as u64 > max_pixels {
+        return Err(anyhow!("Image is too large ({}x{})", width, height));
+    }
+    // อ่าน orientation ก่อน decode เพราะ decoder ถูกใช้หมดไปใน from_decoder
+    let orientation = decoder
+        .orientation()
+        .map_err(|e| anyhow!("Image cannot be read: {}", e))?;
+
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|e| anyhow!("Image cannot be read: {}", e))?;
+    image.apply_orientation(orientation);
+    Ok(image.into_rgba8())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{codecs::jpeg::JpegEncoder, ImageEncoder, Rgba};
+
+    fn jpeg(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_pixel(width, height, Rgba([200, 40, 40, 255]));
+        let mut out = Vec::new();
+        JpegEncoder::new_with_quality(&mut out, 90)
+            .write_image(
+                &DynamicImage::ImageRgba8(image).into_rgb8(),
+                width,
+                height,
+                image::ExtendedColorType::Rgb8,
+            )
+            .unwrap();
+        out
+    }
+
+    /// APP1 segment with a little-endian TIFF block holding only the orientation
+    fn with_orientation(jpeg: &[u8], orientation: u16) -> Vec<u8> {
+        let mut tiff = b"II*\0\x08\0\0\0\x01\0".to_vec();
+        tiff.extend_from_slice(&0x0112u16.to_le_bytes());
+        tiff.extend_from_slice(&3u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&orientation.to_le_bytes());
+        tiff.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+        let mut payload = b"Exif\0\0".to_vec();
+        payload.extend_from_slice(&tiff);
+        let mut out = jpeg[..2].to_vec();
+        out.extend_from_slice(&[0xFF, 0xE1]);
+        out.extend_from_slice(&(payload.len() as u16 + 2).to_be_bytes());
+        out.extend_from_slice(&payload);
+        out.extend_from_slice(&jpeg[2..]);
+        out
+    }
+
+    #[test]
+    fn decodes_jpeg_and_png() {
+        let from_jpeg = decode_upright(&jpeg(40, 30), 10_000).unwrap();
+        assert_eq!(from_jpeg.dimensions(), (40, 30));
+
+        let mut png = Vec::new();
+        RgbaImage::from_pixel(7, 5, Rgba([0, 0, 0, 0]))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        assert_eq!(sniff_content_type(&png), Some("image/png"));
+        let from_png = decode_upright(&png, 10_000).unwrap();
+        assert_eq!(from_png.dimensions(), (7, 5));
+        assert_eq!(from_png.get_pixel(0, 0).0[3], 0);
+    }
+
+    #[test]
+    fn turns_picture_upright_from_exif() {
+        let rotated = with_orientation(&jpeg(40, 30), 6);
+        assert_eq!(decode_upright(&rotated, 10_000).unwrap().dimensions(), (30, 40));
+    }
+
+    #[test]
+    fn refuses_malformed_and_oversized_input() {
+        assert!(decode_upright(b"GIF89a", 10_000).is_err());
+        let data = jpeg(40, 30);
+        assert!(decode_upright(&data[..20], 10_000).is_err());
+        assert!(decode_upright(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0], 10_000).is_err());
+        assert!(decode_upright(&data, 40 * 30 - 1).is_err());
+    }
+}